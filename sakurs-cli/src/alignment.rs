@@ -0,0 +1,105 @@
+//! Boundary offsets from sentence lists, and boundary context for reports
+//!
+//! Shared by `sakurs diff` and `sakurs evaluate`, which compare boundaries
+//! from different sources (a live processor, a one-sentence-per-line file, a
+//! gold annotation) over the same text.
+
+use anyhow::{bail, Result};
+
+/// Byte offsets in `text` where each sentence ends
+///
+/// Sentences are matched against the text one non-whitespace character at a
+/// time, so files whose sentences were trimmed, or whose line breaks were
+/// turned into sentence separators, still align. Empty sentences are
+/// skipped. Fails if a sentence does not occur at the expected position.
+pub fn boundaries_from_sentences<'a>(
+    text: &str,
+    sentences: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<usize>> {
+    let mut chars = text.char_indices().filter(|(_, c)| !c.is_whitespace());
+    let mut boundaries = Vec::new();
+    for (index, sentence) in sentences.into_iter().enumerate() {
+        let mut end = None;
+        for expected in sentence.chars().filter(|c| !c.is_whitespace()) {
+            match chars.next() {
+                Some((offset, c)) if c == expected => end = Some(offset + c.len_utf8()),
+                Some((offset, c)) => bail!(
+                    "sentence {} does not match the text at byte {offset}: expected {expected:?}, found {c:?}",
+                    index + 1
+                ),
+                None => bail!("sentence {} runs past the end of the text", index + 1),
+            }
+        }
+        boundaries.extend(end);
+    }
+    if let Some((offset, _)) = chars.next() {
+        bail!("the sentences end before the text does (at byte {offset})");
+    }
+    Ok(boundaries)
+}
+
+/// Put boundaries from any source into one canonical form: moved back over
+/// trailing whitespace, sorted, deduplicated, and without the boundary
+/// implied at the end of the text
+pub fn normalize_boundaries(text: &str, boundaries: impl IntoIterator<Item = usize>) -> Vec<usize> {
+    let end = text.trim_end().len();
+    let mut normalized: Vec<usize> = boundaries
+        .into_iter()
+        .map(|offset| text[..offset.min(text.len())].trim_end().len())
+        .filter(|&offset| offset > 0 && offset < end)
+        .collect();
+    normalized.sort_unstable();
+    normalized.dedup();
+    normalized
+}
+
+/// Up to `width` characters on each side of `offset`, joined by `|`, with
+/// line breaks and tabs escaped so the context stays on one line
+pub fn context(text: &str, offset: usize, width: usize) -> String {
+    let before: String = {
+        let mut chars: Vec<char> = text[..offset].chars().rev().take(width).collect();
+        chars.reverse();
+        chars.into_iter().collect()
+    };
+    let after: String = text[offset..].chars().take(width).collect();
+    let escape = |s: &str| {
+        s.replace('\n', "\\n")
+            .replace('\r', "\\r")
+            .replace('\t', "\\t")
+    };
+    format!("{}|{}", escape(&before), escape(&after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundaries_from_trimmed_sentences() {
+        let text = "Hello world.  How are\nyou?\n\nFine.";
+        let sentences = ["Hello world.", "How are you?", "", "Fine."];
+        let boundaries = boundaries_from_sentences(text, sentences).unwrap();
+        assert_eq!(boundaries, [12, 26, 33]);
+    }
+
+    #[test]
+    fn test_mismatched_sentences_are_rejected() {
+        let text = "Hello world. Bye.";
+        assert!(boundaries_from_sentences(text, ["Hello world.", "Hi."]).is_err());
+        assert!(boundaries_from_sentences(text, ["Hello world."]).is_err());
+        assert!(boundaries_from_sentences(text, ["Hello world. Bye. More"]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_boundaries() {
+        let text = "One. Two.  Three. ";
+        assert_eq!(normalize_boundaries(text, [5, 4, 11, 17, 18]), [4, 9]);
+    }
+
+    #[test]
+    fn test_context_escapes_line_breaks() {
+        let text = "First line.\nSecond line.";
+        assert_eq!(context(text, 11, 5), "line.|\\nSeco");
+        assert_eq!(context(text, 11, 100), "First line.|\\nSecond line.");
+    }
+}
@@ -0,0 +1,341 @@
+//! Resumable streaming for `sakurs process --stream --checkpoint-file`
+//!
+//! The input is read and split in pieces of `--stream-chunk-mb`. After each
+//! piece, the sentences completed so far are flushed to the output and a
+//! checkpoint is saved: the input offset reached, the length of the output,
+//! and the stream's carried-over state. A run that finds a checkpoint
+//! truncates the output to the recorded length, seeks the input to the
+//! recorded offset and continues, writing exactly what the interrupted run
+//! would have; a finished run removes the checkpoint.
+
+use anyhow::{Context, Result};
+use sakurs_core::{SentenceProcessor, SentenceSpan, SentenceStream, StreamCheckpoint};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::output::{Document, OutputFormatter, Sentence};
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Contents of the checkpoint file
+#[derive(Debug, Serialize, Deserialize)]
+struct Checkpoint {
+    /// The input file the checkpoint belongs to
+    input: PathBuf,
+    /// Input bytes consumed: a byte order mark, then the text fed to the
+    /// stream
+    input_offset: u64,
+    /// Output bytes written; the output ends after a whole sentence there
+    output_len: u64,
+    /// Sentences written, so that JSON Lines numbering continues
+    sentences: usize,
+    stream: StreamCheckpoint,
+}
+
+/// One checkpointed run over a single input file
+pub(super) struct CheckpointedRun<'a> {
+    pub input: &'a Path,
+    pub output: &'a Path,
+    pub checkpoint: &'a Path,
+    /// Bytes read and fed per piece
+    pub piece_size: usize,
+    /// JSON Lines output rather than plain text
+    pub jsonl: bool,
+}
+
+impl CheckpointedRun<'_> {
+    /// Split the input into the output, resuming from the checkpoint file
+    /// if there is one
+    pub(super) fn run(&self, processor: &SentenceProcessor) -> Result<()> {
+        let mut input = File::open(self.input)
+            .with_context(|| format!("Failed to open input file: {}", self.input.display()))?;
+        let (mut stream, bom, mut sentences, output) = match self.load()? {
+            Some(saved) => {
+                log::info!(
+                    "Resuming {} at byte {} from {}",
+                    self.input.display(),
+                    saved.input_offset,
+                    self.checkpoint.display()
+                );
+                let bom = saved.input_offset - saved.stream.bytes_processed() as u64;
+                input.seek(SeekFrom::Start(saved.input_offset))?;
+                let output = OpenOptions::new()
+                    .write(true)
+                    .open(self.output)
+                    .with_context(|| {
+                        format!("Failed to open output file: {}", self.output.display())
+                    })?;
+                if output.metadata()?.len() < saved.output_len {
+                    anyhow::bail!(
+                        "{} is shorter than {} records; remove the checkpoint to start over",
+                        self.output.display(),
+                        self.checkpoint.display()
+                    );
+                }
+                output.set_len(saved.output_len)?;
+                let stream = processor.resume_stream(saved.stream)?;
+                (stream, bom as usize, saved.sentences, output)
+            }
+            None => {
+                let mut head = [0u8; 3];
+                let read = read_full(&mut input, &mut head)?;
+                let bom = if head[..read] == *BOM { BOM.len() } else { 0 };
+                input.seek(SeekFrom::Start(bom as u64))?;
+                let output = File::create(self.output).with_context(|| {
+                    format!("Failed to create output file: {}", self.output.display())
+                })?;
+                (processor.stream(), bom, 0, output)
+            }
+        };
+
+        let mut output = BufWriter::new(output);
+        output.seek(SeekFrom::End(0))?;
+        let path = self.input.display().to_string();
+        let document = Document {
+            id: &path,
+            path: &path,
+        };
+        let mut formatter: Box<dyn OutputFormatter> = if self.jsonl {
+            let mut formatter = crate::output::JsonlFormatter::new(output);
+            formatter.start_document(&document)?;
+            Box::new(formatter.starting_at(sentences))
+        } else {
+            let mut formatter = crate::output::TextFormatter::new(output);
+            formatter.start_document(&document)?;
+            Box::new(formatter)
+        };
+
+        let mut buf = vec![0u8; self.piece_size.max(4)];
+        let mut carry = Vec::new();
+        loop {
+            let read = read_full(&mut input, &mut buf)?;
+            if read == 0 {
+                break;
+            }
+            carry.extend_from_slice(&buf[..read]);
+            let valid = match std::str::from_utf8(&carry) {
+                Ok(text) => text.len(),
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                Err(e) => anyhow::bail!(
+                    "{} is not valid UTF-8 at byte {}",
+                    self.input.display(),
+                    bom + stream.bytes_processed() + e.valid_up_to()
+                ),
+            };
+            let rest = carry.split_off(valid);
+            let text =
+                String::from_utf8(std::mem::replace(&mut carry, rest)).expect("validated above");
+            let spans = stream.feed_spans(&text);
+            sentences += write(formatter.as_mut(), &spans, bom)?;
+            formatter.flush()?;
+            self.save(&stream, bom, sentences)?;
+        }
+        if !carry.is_empty() {
+            anyhow::bail!("{} ends inside a UTF-8 sequence", self.input.display());
+        }
+
+        write(formatter.as_mut(), &stream.finish_spans(), bom)?;
+        formatter.end_document()?;
+        formatter.finish()?;
+        drop(formatter);
+        std::fs::remove_file(self.checkpoint)
+            .with_context(|| format!("Failed to remove checkpoint: {}", self.checkpoint.display()))
+    }
+
+    /// The saved checkpoint, if any
+    fn load(&self) -> Result<Option<Checkpoint>> {
+        let json = match std::fs::read(self.checkpoint) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to read checkpoint: {}", self.checkpoint.display())
+                })
+            }
+        };
+        let saved: Checkpoint = serde_json::from_slice(&json)
+            .with_context(|| format!("Invalid checkpoint: {}", self.checkpoint.display()))?;
+        if saved.input != self.input {
+            anyhow::bail!(
+                "{} is a checkpoint for {}, not {}",
+                self.checkpoint.display(),
+                saved.input.display(),
+                self.input.display()
+            );
+        }
+        Ok(Some(saved))
+    }
+
+    /// Save the run's progress, replacing the checkpoint file atomically
+    ///
+    /// The output written so far, which the caller has flushed, and the new
+    /// checkpoint are synced to disk first, so that after a crash the
+    /// checkpoint never records output that was lost.
+    fn save(&self, stream: &SentenceStream, bom: usize, sentences: usize) -> Result<()> {
+        let output = File::open(self.output)
+            .and_then(|output| output.sync_data().map(|()| output))
+            .with_context(|| format!("Failed to sync output file: {}", self.output.display()))?;
+        let checkpoint = stream.checkpoint();
+        let saved = Checkpoint {
+            input: self.input.to_path_buf(),
+            input_offset: (bom + checkpoint.bytes_processed()) as u64,
+            output_len: output.metadata()?.len(),
+            sentences,
+            stream: checkpoint,
+        };
+        let mut temp = self.checkpoint.as_os_str().to_owned();
+        temp.push(".tmp");
+        File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(&serde_json::to_vec(&saved)?)?;
+                file.sync_all()
+            })
+            .with_context(|| {
+                format!("Failed to write checkpoint: {}", Path::new(&temp).display())
+            })?;
+        std::fs::rename(&temp, self.checkpoint).with_context(|| {
+            format!("Failed to write checkpoint: {}", self.checkpoint.display())
+        })?;
+        log::debug!("Checkpoint at input byte {}", saved.input_offset);
+        Ok(())
+    }
+}
+
+/// Write the trimmed, non-empty sentences of `spans`, whose offsets are
+/// `bom` bytes short of input offsets; returns how many were written
+fn write(formatter: &mut dyn OutputFormatter, spans: &[SentenceSpan], bom: usize) -> Result<usize> {
+    let mut written = 0;
+    for span in spans {
+        let text = span.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let start = bom + span.start + (span.text.len() - span.text.trim_start().len());
+        formatter.format_sentence(&Sentence {
+            text,
+            start,
+            end: start + text.len(),
+            // Streams do not classify boundaries; neither line format
+            // shows the kind
+            kind: sakurs_core::BoundaryKind::Weak,
+            warnings: &[],
+        })?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Read until `buf` is full or the reader is exhausted
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str =
+        "\u{FEFF}First one. (Second one. Still second.) Third one! 「四つ目。」 Fifth? ";
+
+    /// Input, output and checkpoint paths in a temporary directory
+    struct Files {
+        _dir: tempfile::TempDir,
+        input: PathBuf,
+        output: PathBuf,
+        checkpoint: PathBuf,
+    }
+
+    impl Files {
+        fn new(input: &[u8]) -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            let files = Self {
+                input: dir.path().join("input.txt"),
+                output: dir.path().join("out"),
+                checkpoint: dir.path().join("checkpoint.json"),
+                _dir: dir,
+            };
+            std::fs::write(&files.input, input).unwrap();
+            files
+        }
+
+        fn run(&self, jsonl: bool) -> CheckpointedRun<'_> {
+            CheckpointedRun {
+                input: &self.input,
+                output: &self.output,
+                checkpoint: &self.checkpoint,
+                piece_size: 16,
+                jsonl,
+            }
+        }
+    }
+
+    #[test]
+    fn test_resumed_run_matches_uninterrupted_run() {
+        let files = Files::new(TEXT.repeat(5).as_bytes());
+        let processor = SentenceProcessor::with_language("ja").unwrap();
+
+        for jsonl in [false, true] {
+            let run = files.run(jsonl);
+            run.run(&processor).unwrap();
+            assert!(!files.checkpoint.exists());
+            let expected = std::fs::read_to_string(&files.output).unwrap();
+
+            // An interrupted run: a checkpoint partway through, then output
+            // written after it
+            let output = File::create(&files.output).unwrap();
+            let mut formatter: Box<dyn OutputFormatter> = if jsonl {
+                Box::new(crate::output::JsonlFormatter::new(output))
+            } else {
+                Box::new(crate::output::TextFormatter::new(output))
+            };
+            let mut stream = processor.stream();
+            let spans = stream.feed_spans(&TEXT[BOM.len()..]);
+            let sentences = write(formatter.as_mut(), &spans, BOM.len()).unwrap();
+            formatter.flush().unwrap();
+            run.save(&stream, BOM.len(), sentences).unwrap();
+            drop(formatter);
+            let mut output = OpenOptions::new().append(true).open(&files.output).unwrap();
+            output.write_all(b"half a sent").unwrap();
+
+            run.run(&processor).unwrap();
+            assert_eq!(std::fs::read_to_string(&files.output).unwrap(), expected);
+            assert!(!files.checkpoint.exists());
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_for_another_input_is_refused() {
+        let files = Files::new(TEXT.as_bytes());
+        let processor = SentenceProcessor::with_language("ja").unwrap();
+        std::fs::write(&files.output, "").unwrap();
+        files.run(false).save(&processor.stream(), 0, 0).unwrap();
+
+        let other = files.input.with_file_name("other.txt");
+        std::fs::write(&other, TEXT).unwrap();
+        let error = CheckpointedRun {
+            input: &other,
+            ..files.run(false)
+        }
+        .run(&processor)
+        .unwrap_err();
+        assert!(error.to_string().contains("is a checkpoint for"), "{error}");
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_an_error() {
+        let files = Files::new(b"Fine so far. \xFF Not fine.");
+        let error = files.run(false).run(&SentenceProcessor::new()).unwrap_err();
+        assert!(error.to_string().contains("at byte 13"), "{error}");
+    }
+}
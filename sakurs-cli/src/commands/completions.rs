@@ -0,0 +1,96 @@
+//! Completions command implementation
+
+use anyhow::{Context, Result};
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Args, Command, ValueEnum};
+use clap_complete::Shell;
+use std::io::Write;
+
+use crate::commands::process::Language;
+
+/// Arguments for the completions command
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    /// Shell to generate the completion script for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+impl CompletionsArgs {
+    /// Execute the completions command, writing the script for `command`
+    /// (the whole CLI) to stdout
+    pub fn execute(&self, command: Command) -> Result<()> {
+        let mut command = with_language_values(command);
+        let name = command.get_name().to_string();
+        // Generate into a buffer: the generator panics on write errors such
+        // as a closed pipe
+        let mut script = Vec::new();
+        clap_complete::generate(self.shell, &mut command, name, &mut script);
+        std::io::stdout()
+            .write_all(&script)
+            .context("Failed to write the completion script")
+    }
+}
+
+/// Every name a language can be given by (code, ISO 639-2 code and full
+/// name), each described by the language's name
+pub fn language_values() -> Vec<PossibleValue> {
+    Language::value_variants()
+        .iter()
+        .filter_map(ValueEnum::to_possible_value)
+        .flat_map(|value| {
+            let help = value
+                .get_help()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            value
+                .get_name_and_aliases()
+                .map(|name| PossibleValue::new(name.to_string()).help(help.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// `command` with the language options (`--language`, `--left-language`,
+/// ...) of every subcommand completing each language code and alias rather
+/// than only the full names
+fn with_language_values(command: Command) -> Command {
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    let mut command = command;
+    for name in subcommands {
+        command = command.mut_subcommand(name, |sub| {
+            let ids: Vec<String> = sub
+                .get_arguments()
+                .map(|arg| arg.get_id().to_string())
+                .filter(|id| id == "language" || id.ends_with("_language"))
+                .collect();
+            let mut sub = with_language_values(sub);
+            for id in ids {
+                sub = sub.mut_arg(id, |arg| {
+                    arg.value_parser(PossibleValuesParser::new(language_values()))
+                });
+            }
+            sub
+        });
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_values_include_codes() {
+        let names: Vec<String> = language_values()
+            .iter()
+            .map(|value| value.get_name().to_string())
+            .collect();
+        for name in ["en", "eng", "english", "ja", "hindi"] {
+            assert!(names.iter().any(|n| n == name), "{name}");
+        }
+    }
+}
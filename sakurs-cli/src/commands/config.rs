@@ -0,0 +1,69 @@
+//! Config command implementation
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use std::path::PathBuf;
+
+use crate::config::{default_path, CliConfig, TEMPLATE};
+
+/// Config subcommands
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommands {
+    /// Show the configuration file in effect and the values it sets
+    Show {
+        /// Configuration file to show (default: ~/.config/sakurs/config.toml)
+        #[arg(long, value_name = "FILE", env = "SAKURS_CONFIG")]
+        config: Option<PathBuf>,
+    },
+
+    /// Write a commented configuration template
+    Init {
+        /// Where to write it (default: ~/.config/sakurs/config.toml)
+        #[arg(value_name = "FILE")]
+        path: Option<PathBuf>,
+
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+impl ConfigCommands {
+    /// Execute the config subcommand
+    pub fn execute(&self) -> Result<()> {
+        match self {
+            ConfigCommands::Show { config } => {
+                let (settings, path) = CliConfig::discover(config.as_deref())?;
+                match (path, default_path()) {
+                    (Some(path), _) => println!("# {}", path.display()),
+                    (None, Some(default)) => {
+                        println!("# No config file (looked for {})", default.display())
+                    }
+                    (None, None) => println!("# No config file"),
+                }
+                print!("{}", settings.to_toml()?);
+                Ok(())
+            }
+            ConfigCommands::Init { path, force } => {
+                let path = path
+                    .clone()
+                    .or_else(default_path)
+                    .context("Cannot locate the config directory; pass a path")?;
+                if path.exists() && !force {
+                    anyhow::bail!(
+                        "{} already exists; use --force to overwrite it",
+                        path.display()
+                    );
+                }
+                if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(dir)
+                        .with_context(|| format!("Failed to create {}", dir.display()))?;
+                }
+                std::fs::write(&path, TEMPLATE)
+                    .with_context(|| format!("Failed to write to {}", path.display()))?;
+                println!("✓ Wrote {}", path.display());
+                Ok(())
+            }
+        }
+    }
+}
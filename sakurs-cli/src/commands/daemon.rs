@@ -0,0 +1,413 @@
+//! Daemon command implementation: sentence splitting over a Unix domain
+//! socket
+//!
+//! Editors and shell scripts keep the daemon running and reuse its compiled
+//! rules instead of paying the CLI's startup cost on every call. Every
+//! message, in both directions, is a 4-byte big-endian length followed by
+//! that many bytes of UTF-8 JSON:
+//!
+//! - request: `{"text": "...", "language": "ja", "threads": 2, "chunk_kb": 64}`,
+//!   all fields but `text` optional
+//! - reply: the body `serve` returns for `POST /split`, or `{"error": "..."}`
+//!
+//! A connection may carry any number of requests, each answered in turn.
+//! The daemon is Unix-only; Windows named pipes are not supported, and
+//! `serve` covers that platform.
+
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Deserialize;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+use super::process::Language;
+use super::serve::{error, Reply, SplitOptions, SplitService};
+
+/// Arguments for the daemon command
+#[derive(Debug, Args)]
+pub struct DaemonArgs {
+    /// Path of the Unix domain socket to listen on, or to connect to with
+    /// --send
+    #[arg(short, long, value_name = "PATH")]
+    pub socket: PathBuf,
+
+    /// Send standard input to the daemon listening on --socket and print
+    /// its sentences, one per line
+    #[arg(long, conflicts_with = "language_config")]
+    pub send: bool,
+
+    /// Default language when a request has none; with --send, the language
+    /// to request
+    #[arg(short, long, value_enum, conflicts_with = "language_config")]
+    pub language: Option<Language>,
+
+    /// Path to external language configuration file (TOML format); it
+    /// becomes the default language and is also selectable by its code.
+    /// Edits to the file are picked up without a restart
+    #[arg(short = 'c', long, value_name = "FILE", conflicts_with = "language")]
+    pub language_config: Option<PathBuf>,
+
+    /// Language code for external configuration (optional)
+    /// NOTE: Only used with --language-config
+    #[arg(long, requires = "language_config")]
+    pub language_code: Option<String>,
+
+    /// Maximum request size in MB
+    #[arg(long, default_value = "10", value_name = "SIZE_MB")]
+    pub max_body_mb: usize,
+
+    /// Increase verbosity
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+impl DaemonArgs {
+    /// Execute the daemon command
+    pub fn execute(&self) -> Result<()> {
+        if self.send {
+            return self.send();
+        }
+        crate::logging::init(self.verbose, crate::logging::LogFormat::Text);
+        let service = SplitService::from_options(
+            self.language,
+            self.language_config.as_deref(),
+            self.language_code.as_deref(),
+            self.max_body_mb * 1024 * 1024,
+        )?;
+        self.listen(service)
+    }
+
+    /// Accept connections until the process is stopped, serving each on
+    /// its own thread
+    #[cfg(unix)]
+    fn listen(&self, service: SplitService) -> Result<()> {
+        use std::sync::Arc;
+
+        let service = Arc::new(service);
+        if let Some(path) = &self.language_config {
+            service.watch(path)?;
+        }
+        let listener = unix::bind(&self.socket)?;
+        println!("Listening on {}", self.socket.display());
+
+        let max_body = self.max_body_mb * 1024 * 1024;
+        for connection in listener.incoming() {
+            let connection = match connection {
+                Ok(connection) => connection,
+                Err(e) => {
+                    log::warn!("Failed to accept a connection: {e}");
+                    continue;
+                }
+            };
+            let service = Arc::clone(&service);
+            std::thread::spawn(move || {
+                let reader = match connection.try_clone() {
+                    Ok(reader) => reader,
+                    Err(e) => return log::warn!("Failed to set up a connection: {e}"),
+                };
+                if let Err(e) = serve_connection(&service, reader, connection, max_body) {
+                    log::warn!("Connection closed: {e}");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn listen(&self, _service: SplitService) -> Result<()> {
+        anyhow::bail!("The daemon needs Unix domain sockets; use `sakurs serve` on this platform")
+    }
+
+    /// Send standard input to a running daemon and print the sentences
+    #[cfg(unix)]
+    fn send(&self) -> Result<()> {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .context("Failed to read standard input")?;
+        let mut request = serde_json::json!({ "text": text });
+        if let Some(language) = self.language {
+            request["language"] = language.code().into();
+        }
+
+        let mut connection = std::os::unix::net::UnixStream::connect(&self.socket)
+            .with_context(|| format!("Failed to connect to {}", self.socket.display()))?;
+        write_frame(&mut connection, &request.to_string())?;
+        let reply = read_frame(&mut connection, usize::MAX)?
+            .context("The daemon closed the connection without replying")?;
+        let reply: serde_json::Value =
+            serde_json::from_slice(&reply).context("The daemon sent invalid JSON")?;
+        if let Some(message) = reply["error"].as_str() {
+            anyhow::bail!("{message}");
+        }
+
+        let stdout = io::stdout();
+        let mut out = io::BufWriter::new(stdout.lock());
+        for sentence in reply["sentences"].as_array().into_iter().flatten() {
+            writeln!(out, "{}", sentence["text"].as_str().unwrap_or_default())?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn send(&self) -> Result<()> {
+        anyhow::bail!("The daemon needs Unix domain sockets; use `sakurs serve` on this platform")
+    }
+}
+
+/// Request message
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DaemonRequest {
+    text: String,
+    language: Option<String>,
+    threads: Option<usize>,
+    chunk_kb: Option<usize>,
+}
+
+/// Answer requests on one connection until the client closes it
+fn serve_connection(
+    service: &SplitService,
+    mut reader: impl Read,
+    mut writer: impl Write,
+    max_body: usize,
+) -> io::Result<()> {
+    loop {
+        let (reply, keep_open) = match read_frame(&mut reader, max_body) {
+            Ok(None) => return Ok(()),
+            Ok(Some(body)) => (handle(service, &body), true),
+            // The rest of an oversized request cannot be skipped reliably
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => (error(413, e.to_string()), false),
+            Err(e) => return Err(e),
+        };
+        log::info!("request -> {}", reply.0);
+        write_frame(&mut writer, &reply.1.to_string())?;
+        if !keep_open {
+            return Ok(());
+        }
+    }
+}
+
+/// Reply to one request message
+fn handle(service: &SplitService, body: &[u8]) -> Reply {
+    let request: DaemonRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return error(400, format!("Invalid request: {e}")),
+    };
+    for (name, value) in [("threads", request.threads), ("chunk_kb", request.chunk_kb)] {
+        if value == Some(0) {
+            return error(400, format!("{name} must be a positive integer, got 0"));
+        }
+    }
+    let options = SplitOptions {
+        threads: request.threads,
+        chunk_kb: request.chunk_kb,
+    };
+    let language = request
+        .language
+        .unwrap_or_else(|| service.default_language().to_string());
+    service.split_text(language, &options, &request.text)
+}
+
+/// Read one length-prefixed message; `None` when the peer closed the
+/// connection between messages
+fn read_frame(reader: &mut impl Read, max_len: usize) -> io::Result<Option<Vec<u8>>> {
+    let mut prefix = [0u8; 4];
+    let mut filled = 0;
+    while filled < prefix.len() {
+        match reader.read(&mut prefix[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    let len = u32::from_be_bytes(prefix) as usize;
+    if len > max_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Request exceeds {max_len} bytes"),
+        ));
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+/// Write one length-prefixed message
+fn write_frame(writer: &mut impl Write, body: &str) -> io::Result<()> {
+    let len = u32::try_from(body.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Message exceeds 4 GiB"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(body.as_bytes())?;
+    writer.flush()
+}
+
+#[cfg(unix)]
+mod unix {
+    use anyhow::{Context, Result};
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+
+    /// Listen on `path`, replacing the socket file a stopped daemon left
+    /// behind; any other file there is left alone. Only the current user
+    /// may connect: the socket is bound inside a directory only they can
+    /// enter, restricted, and only then linked at `path`
+    pub(super) fn bind(path: &Path) -> Result<UnixListener> {
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                anyhow::bail!("{} exists and is not a socket", path.display());
+            }
+            if UnixStream::connect(path).is_ok() {
+                anyhow::bail!("Another daemon is listening on {}", path.display());
+            }
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+        }
+
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let private = parent.join(format!(".sakurs-{}", std::process::id()));
+        std::fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&private)
+            .with_context(|| format!("Failed to create {}", private.display()))?;
+        let bound = private.join("s");
+        let listener = UnixListener::bind(&bound)
+            .with_context(|| format!("Failed to bind {}", path.display()))
+            .and_then(|listener| {
+                std::fs::set_permissions(&bound, std::fs::Permissions::from_mode(0o600))
+                    .with_context(|| format!("Failed to restrict access to {}", path.display()))?;
+                // Unlike a rename, a link never replaces a file created at
+                // `path` since the check above
+                std::fs::hard_link(&bound, path)
+                    .with_context(|| format!("Failed to bind {}", path.display()))?;
+                Ok(listener)
+            });
+        let _ = std::fs::remove_file(&bound);
+        let _ = std::fs::remove_dir(&private);
+        listener
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(bodies: &[&str]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for body in bodies {
+            write_frame(&mut bytes, body).unwrap();
+        }
+        bytes
+    }
+
+    fn replies(mut bytes: &[u8]) -> Vec<serde_json::Value> {
+        let mut replies = Vec::new();
+        while let Some(body) = read_frame(&mut bytes, usize::MAX).unwrap() {
+            replies.push(serde_json::from_slice(&body).unwrap());
+        }
+        replies
+    }
+
+    #[test]
+    fn test_requests_on_one_connection() {
+        let service = SplitService::new(1024);
+        let input = frames(&[
+            r#"{"text": "Hello world. This is a test."}"#,
+            r#"{"text": "こんにちは。元気ですか？", "language": "ja", "threads": 2}"#,
+        ]);
+        let mut output = Vec::new();
+        serve_connection(&service, input.as_slice(), &mut output, 1024).unwrap();
+
+        let replies = replies(&output);
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0]["language"], "en");
+        assert_eq!(replies[0]["sentences"][1]["text"], "This is a test.");
+        assert_eq!(replies[1]["sentences"][1]["char_start"], 6);
+    }
+
+    #[test]
+    fn test_bad_requests_get_errors() {
+        let service = SplitService::new(1024);
+        for body in [
+            "{",
+            r#"{"text": "Hi.", "bogus": 1}"#,
+            r#"{"text": "Hi.", "language": "xx"}"#,
+            r#"{"text": "Hi.", "chunk_kb": 0}"#,
+        ] {
+            let (status, reply) = handle(&service, body.as_bytes());
+            assert_eq!(status, 400, "{body}");
+            assert!(reply["error"].is_string(), "{body}");
+        }
+    }
+
+    #[test]
+    fn test_huge_options_are_clamped() {
+        let service = SplitService::new(1024);
+        let body = format!(
+            r#"{{"text": "Hi. Bye.", "threads": {0}, "chunk_kb": {0}}}"#,
+            usize::MAX
+        );
+        let (status, reply) = handle(&service, body.as_bytes());
+        assert_eq!(status, 200, "{reply}");
+        assert_eq!(reply["sentences"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_oversized_request_closes_connection() {
+        let service = SplitService::new(16);
+        let input = frames(&[r#"{"text": "A long sentence here."}"#, r#"{"text": "Hi."}"#]);
+        let mut output = Vec::new();
+        serve_connection(&service, input.as_slice(), &mut output, 16).unwrap();
+
+        let replies = replies(&output);
+        assert_eq!(replies.len(), 1);
+        assert!(replies[0]["error"].as_str().unwrap().contains("16 bytes"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_bind_leaves_other_files_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "keep me").unwrap();
+        let err = unix::bind(&path).unwrap_err();
+        assert!(err.to_string().contains("is not a socket"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+
+        // A socket left by a stopped daemon is replaced
+        let socket = dir.path().join("daemon.sock");
+        drop(unix::bind(&socket).unwrap());
+        let _listener = unix::bind(&socket).unwrap();
+        assert!(std::os::unix::net::UnixStream::connect(&socket).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_socket_is_private_from_the_start() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("daemon.sock");
+        let _listener = unix::bind(&socket).unwrap();
+        let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        // Nothing but the socket is left next to it
+        let entries: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_truncated_frame_is_an_error() {
+        let input = [0u8, 0];
+        assert!(read_frame(&mut input.as_slice(), 1024).is_err());
+        let input = [0u8, 0, 0, 5, b'a'];
+        assert!(read_frame(&mut input.as_slice(), 1024).is_err());
+    }
+}
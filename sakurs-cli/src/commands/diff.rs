@@ -0,0 +1,320 @@
+//! Diff command implementation: compare the boundaries of two segmentations
+
+use anyhow::{Context, Result};
+use clap::Args;
+use sakurs_core::{Config, Input, SentenceProcessor};
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+
+use super::process::Language;
+use crate::alignment;
+use crate::language_source::LanguageSource;
+
+/// Arguments for the diff command
+///
+/// Each side is either segmented live (with a built-in language or an
+/// external language configuration) or read from a file with one sentence
+/// per line, as written by `sakurs process` or any other segmenter.
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// Input text files or patterns (supports glob)
+    #[arg(short, long, value_name = "FILE/PATTERN", required = true)]
+    pub input: Vec<String>,
+
+    /// Language for both sides, unless a side sets its own (default: english)
+    #[arg(short, long, value_enum)]
+    pub language: Option<Language>,
+
+    /// Language for the left side
+    #[arg(long, value_enum, conflicts_with_all = ["left_config", "left_sentences"])]
+    pub left_language: Option<Language>,
+
+    /// External language configuration (TOML) for the left side
+    #[arg(long, value_name = "FILE", conflicts_with = "left_sentences")]
+    pub left_config: Option<PathBuf>,
+
+    /// Precomputed left segmentation, one sentence per line (single input only)
+    #[arg(long, value_name = "FILE")]
+    pub left_sentences: Option<PathBuf>,
+
+    /// Language for the right side
+    #[arg(long, value_enum, conflicts_with_all = ["right_config", "right_sentences"])]
+    pub right_language: Option<Language>,
+
+    /// External language configuration (TOML) for the right side
+    #[arg(long, value_name = "FILE", conflicts_with = "right_sentences")]
+    pub right_config: Option<PathBuf>,
+
+    /// Precomputed right segmentation, one sentence per line (single input only)
+    #[arg(long, value_name = "FILE")]
+    pub right_sentences: Option<PathBuf>,
+
+    /// Characters of context shown on each side of a boundary
+    #[arg(long, default_value = "40", value_name = "CHARS")]
+    pub context: usize,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "text")]
+    pub format: DiffFormat,
+
+    /// Exit with status 1 when the sides differ
+    #[arg(long)]
+    pub exit_code: bool,
+}
+
+/// Output formats of the diff command
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum DiffFormat {
+    /// Aligned differences with context, one per line
+    Text,
+    /// JSON report
+    Json,
+}
+
+/// Where one side's boundaries come from
+enum Side {
+    Live(Box<SentenceProcessor>),
+    Sentences(PathBuf),
+}
+
+impl Side {
+    fn boundaries(&self, text: &str) -> Result<Vec<usize>> {
+        let boundaries = match self {
+            Side::Live(processor) => processor
+                .process(Input::from_text(text))
+                .map_err(|e| anyhow::anyhow!("Processing failed: {e}"))?
+                .boundaries
+                .iter()
+                .map(|b| b.offset)
+                .collect(),
+            Side::Sentences(path) => {
+                let sentences = crate::input::FileReader::read_text(path)?;
+                alignment::boundaries_from_sentences(text, sentences.lines())
+                    .with_context(|| format!("{} does not match the input", path.display()))?
+            }
+        };
+        Ok(alignment::normalize_boundaries(text, boundaries))
+    }
+}
+
+/// A boundary found by only one side
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BoundaryDiff {
+    /// Byte offset of the boundary
+    pub offset: usize,
+    /// Text around the boundary, which is marked with `|`
+    pub context: String,
+}
+
+impl BoundaryDiff {
+    /// The boundaries of `ours` that `theirs` lacks (both sorted), each with
+    /// `width` characters of context
+    pub fn missing(text: &str, ours: &[usize], theirs: &[usize], width: usize) -> Vec<Self> {
+        ours.iter()
+            .filter(|offset| theirs.binary_search(offset).is_err())
+            .map(|&offset| Self {
+                offset,
+                context: alignment::context(text, offset, width),
+            })
+            .collect()
+    }
+}
+
+/// Differences within one input file
+#[derive(Debug, Serialize)]
+pub struct FileDiff {
+    /// Input file
+    pub path: String,
+    /// Boundaries found by both sides
+    pub shared: usize,
+    /// Boundaries found only by the left side
+    pub left_only: Vec<BoundaryDiff>,
+    /// Boundaries found only by the right side
+    pub right_only: Vec<BoundaryDiff>,
+}
+
+impl FileDiff {
+    /// Compare two sorted, normalized boundary lists over `text`
+    pub fn new(path: String, text: &str, left: &[usize], right: &[usize], width: usize) -> Self {
+        let left_only = BoundaryDiff::missing(text, left, right, width);
+        let right_only = BoundaryDiff::missing(text, right, left, width);
+        Self {
+            path,
+            shared: left.len() - left_only.len(),
+            left_only,
+            right_only,
+        }
+    }
+
+    fn has_differences(&self) -> bool {
+        !self.left_only.is_empty() || !self.right_only.is_empty()
+    }
+}
+
+/// Full report, as written by `--format json`
+#[derive(Debug, Serialize)]
+struct DiffReport<'a> {
+    left: &'a str,
+    right: &'a str,
+    files: &'a [FileDiff],
+}
+
+impl DiffArgs {
+    /// Execute the diff command
+    pub fn execute(&self) -> Result<()> {
+        let files = crate::input::resolve_patterns(&self.input)?;
+        if files.len() > 1 && (self.left_sentences.is_some() || self.right_sentences.is_some()) {
+            anyhow::bail!(
+                "--left-sentences/--right-sentences describe a single input, but {} files matched",
+                files.len()
+            );
+        }
+
+        let (left, left_name) =
+            self.side(self.left_language, &self.left_config, &self.left_sentences)?;
+        let (right, right_name) = self.side(
+            self.right_language,
+            &self.right_config,
+            &self.right_sentences,
+        )?;
+
+        let mut diffs = Vec::with_capacity(files.len());
+        for file in &files {
+            let text = crate::input::FileReader::read_text(file)?;
+            diffs.push(FileDiff::new(
+                file.display().to_string(),
+                &text,
+                &left.boundaries(&text)?,
+                &right.boundaries(&text)?,
+                self.context,
+            ));
+        }
+
+        let stdout = std::io::stdout();
+        let mut out = stdout.lock();
+        match self.format {
+            DiffFormat::Text => write_text(&mut out, &left_name, &right_name, &diffs)?,
+            DiffFormat::Json => {
+                let report = DiffReport {
+                    left: &left_name,
+                    right: &right_name,
+                    files: &diffs,
+                };
+                serde_json::to_writer_pretty(&mut out, &report)?;
+                writeln!(out)?;
+            }
+        }
+        out.flush()?;
+
+        if self.exit_code && diffs.iter().any(FileDiff::has_differences) {
+            std::process::exit(1);
+        }
+        Ok(())
+    }
+
+    /// Resolve one side's options into a boundary source and its display name
+    fn side(
+        &self,
+        language: Option<Language>,
+        config: &Option<PathBuf>,
+        sentences: &Option<PathBuf>,
+    ) -> Result<(Side, String)> {
+        if let Some(path) = sentences {
+            let name = format!("Sentences: {}", path.display());
+            return Ok((Side::Sentences(path.clone()), name));
+        }
+        let source = match config {
+            Some(path) => LanguageSource::External {
+                path: path.clone(),
+                language_code: None,
+            },
+            None => {
+                LanguageSource::BuiltIn(language.or(self.language).unwrap_or(Language::English))
+            }
+        };
+        let processor = source.processor(Config::builder())?;
+        Ok((Side::Live(Box::new(processor)), source.display_name()))
+    }
+}
+
+/// Write a unified-diff-like report: `-` lines are boundaries only the left
+/// side found, `+` lines those only the right side found
+fn write_text(out: &mut impl Write, left: &str, right: &str, diffs: &[FileDiff]) -> Result<()> {
+    writeln!(out, "--- {left}")?;
+    writeln!(out, "+++ {right}")?;
+    let (mut shared, mut left_only, mut right_only) = (0, 0, 0);
+    for diff in diffs {
+        shared += diff.shared;
+        left_only += diff.left_only.len();
+        right_only += diff.right_only.len();
+        if !diff.has_differences() {
+            continue;
+        }
+        writeln!(
+            out,
+            "@@ {}: {} only in left, {} only in right, {} shared",
+            diff.path,
+            diff.left_only.len(),
+            diff.right_only.len(),
+            diff.shared
+        )?;
+        let mut lines: Vec<(usize, char, &str)> = diff
+            .left_only
+            .iter()
+            .map(|b| (b.offset, '-', b.context.as_str()))
+            .chain(
+                diff.right_only
+                    .iter()
+                    .map(|b| (b.offset, '+', b.context.as_str())),
+            )
+            .collect();
+        lines.sort_by_key(|&(offset, sign, _)| (offset, sign));
+        for (offset, sign, context) in lines {
+            writeln!(out, "{sign} {offset:>8}  {context}")?;
+        }
+    }
+    writeln!(
+        out,
+        "{} files: {left_only} only in left, {right_only} only in right, {shared} shared",
+        diffs.len()
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_diff_splits_boundaries() {
+        let text = "Dr. Smith left. He came back.";
+        let diff = FileDiff::new("t.txt".into(), text, &[3, 15], &[15], 5);
+        assert_eq!(diff.shared, 1);
+        assert_eq!(
+            diff.left_only,
+            [BoundaryDiff {
+                offset: 3,
+                context: "Dr.| Smit".into()
+            }]
+        );
+        assert!(diff.right_only.is_empty());
+    }
+
+    #[test]
+    fn test_text_report() {
+        let text = "A. B. C.";
+        let diffs = [FileDiff::new("t.txt".into(), text, &[2], &[5], 2)];
+        let mut out = Vec::new();
+        write_text(&mut out, "left", "right", &diffs).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "--- left\n+++ right\n\
+             @@ t.txt: 1 only in left, 1 only in right, 0 shared\n\
+             -        2  A.| B\n\
+             +        5  B.| C\n\
+             1 files: 1 only in left, 1 only in right, 0 shared\n"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Evaluate command implementation: score boundaries against a gold annotation
+
+use anyhow::{Context, Result};
+use clap::Args;
+use sakurs_core::{Config, Input};
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+
+use super::diff::BoundaryDiff;
+use super::process::Language;
+use crate::alignment;
+use crate::language_source::LanguageSource;
+
+/// Arguments for the evaluate command
+#[derive(Debug, Args)]
+pub struct EvaluateArgs {
+    /// Raw text file to segment
+    #[arg(short, long, value_name = "FILE")]
+    pub input: PathBuf,
+
+    /// Gold annotation of the same text
+    #[arg(short, long, value_name = "FILE")]
+    pub gold: PathBuf,
+
+    /// How the gold file is written
+    #[arg(long, value_enum, default_value = "sentences")]
+    pub gold_format: GoldFormat,
+
+    /// Language for sentence detection rules (default: english)
+    /// NOTE: Mutually exclusive with --language-config
+    #[arg(short, long, value_enum, conflicts_with = "language_config")]
+    pub language: Option<Language>,
+
+    /// Path to external language configuration file (TOML format)
+    /// NOTE: Mutually exclusive with --language
+    #[arg(short = 'c', long, value_name = "FILE", conflicts_with = "language")]
+    pub language_config: Option<PathBuf>,
+
+    /// Language code for external configuration (optional)
+    /// NOTE: Only used with --language-config
+    #[arg(long, requires = "language_config")]
+    pub language_code: Option<String>,
+
+    /// Characters of context shown on each side of an error
+    #[arg(long, default_value = "40", value_name = "CHARS")]
+    pub context: usize,
+
+    /// Maximum number of false positives and of false negatives listed in
+    /// text output (0 lists all)
+    #[arg(long, default_value = "20", value_name = "COUNT")]
+    pub max_errors: usize,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "text")]
+    pub format: EvaluateFormat,
+}
+
+/// Formats of the gold annotation file
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum GoldFormat {
+    /// One sentence per line, in order, covering the whole text
+    Sentences,
+    /// One byte offset per line where a sentence ends
+    Offsets,
+    /// One character offset per line where a sentence ends
+    CharOffsets,
+}
+
+/// Output formats of the evaluate command
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum EvaluateFormat {
+    /// Scores followed by the errors with context
+    Text,
+    /// JSON report with every error
+    Json,
+}
+
+/// Boundary precision, recall and F1 with the errors behind them
+#[derive(Debug, Serialize)]
+pub struct Evaluation {
+    /// Fraction of predicted boundaries that are gold boundaries
+    pub precision: f64,
+    /// Fraction of gold boundaries that were predicted
+    pub recall: f64,
+    /// Harmonic mean of precision and recall
+    pub f1: f64,
+    /// Number of predicted boundaries
+    pub predicted: usize,
+    /// Number of gold boundaries
+    pub gold: usize,
+    /// Predicted boundaries that are not gold boundaries
+    pub false_positives: Vec<BoundaryDiff>,
+    /// Gold boundaries that were not predicted
+    pub false_negatives: Vec<BoundaryDiff>,
+}
+
+impl Evaluation {
+    /// Score sorted, normalized predicted boundaries against gold ones
+    pub fn new(text: &str, predicted: &[usize], gold: &[usize], width: usize) -> Self {
+        let false_positives = BoundaryDiff::missing(text, predicted, gold, width);
+        let false_negatives = BoundaryDiff::missing(text, gold, predicted, width);
+        let correct = (predicted.len() - false_positives.len()) as f64;
+        // With nothing to find (or nothing predicted) there is nothing wrong
+        let ratio = |total: usize| {
+            if total == 0 {
+                1.0
+            } else {
+                correct / total as f64
+            }
+        };
+        let precision = ratio(predicted.len());
+        let recall = ratio(gold.len());
+        let f1 = if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        };
+        Self {
+            precision,
+            recall,
+            f1,
+            predicted: predicted.len(),
+            gold: gold.len(),
+            false_positives,
+            false_negatives,
+        }
+    }
+}
+
+impl EvaluateArgs {
+    /// Execute the evaluate command
+    pub fn execute(&self) -> Result<()> {
+        let text = crate::input::FileReader::read_text(&self.input)?;
+        let gold = self.gold_boundaries(&text)?;
+
+        let source = match &self.language_config {
+            Some(path) => LanguageSource::External {
+                path: path.clone(),
+                language_code: self.language_code.clone(),
+            },
+            None => LanguageSource::BuiltIn(self.language.unwrap_or(Language::English)),
+        };
+        let output = source
+            .processor(Config::builder())?
+            .process(Input::from_text(text.as_str()))
+            .map_err(|e| anyhow::anyhow!("Processing failed: {e}"))?;
+        let predicted =
+            alignment::normalize_boundaries(&text, output.boundaries.iter().map(|b| b.offset));
+
+        let evaluation = Evaluation::new(&text, &predicted, &gold, self.context);
+        let stdout = std::io::stdout();
+        let mut out = stdout.lock();
+        match self.format {
+            EvaluateFormat::Text => write_text(&mut out, &evaluation, self.max_errors)?,
+            EvaluateFormat::Json => {
+                serde_json::to_writer_pretty(&mut out, &evaluation)?;
+                writeln!(out)?;
+            }
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Read the gold file as normalized boundary offsets into `text`
+    fn gold_boundaries(&self, text: &str) -> Result<Vec<usize>> {
+        let gold = crate::input::FileReader::read_text(&self.gold)?;
+        let boundaries = match self.gold_format {
+            GoldFormat::Sentences => alignment::boundaries_from_sentences(text, gold.lines())
+                .with_context(|| format!("{} does not match the input", self.gold.display()))?,
+            GoldFormat::Offsets | GoldFormat::CharOffsets => {
+                let mut offsets = Vec::new();
+                for (number, line) in gold.lines().enumerate() {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    let offset: usize = line.parse().with_context(|| {
+                        format!("{}:{}: not an offset", self.gold.display(), number + 1)
+                    })?;
+                    offsets.push(self.byte_offset(text, offset).with_context(|| {
+                        format!(
+                            "{}:{}: offset out of range",
+                            self.gold.display(),
+                            number + 1
+                        )
+                    })?);
+                }
+                offsets
+            }
+        };
+        Ok(alignment::normalize_boundaries(text, boundaries))
+    }
+
+    /// Convert a gold offset to a byte offset, checking that it is valid
+    fn byte_offset(&self, text: &str, offset: usize) -> Option<usize> {
+        match self.gold_format {
+            GoldFormat::CharOffsets => text
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain(std::iter::once(text.len()))
+                .nth(offset),
+            _ => text.is_char_boundary(offset).then_some(offset),
+        }
+    }
+}
+
+fn write_text(out: &mut impl Write, evaluation: &Evaluation, max_errors: usize) -> Result<()> {
+    let correct = evaluation.predicted - evaluation.false_positives.len();
+    writeln!(
+        out,
+        "Precision: {:.4} ({correct}/{})",
+        evaluation.precision, evaluation.predicted
+    )?;
+    writeln!(
+        out,
+        "Recall:    {:.4} ({correct}/{})",
+        evaluation.recall, evaluation.gold
+    )?;
+    writeln!(out, "F1:        {:.4}", evaluation.f1)?;
+    for (title, errors) in [
+        ("False positives", &evaluation.false_positives),
+        ("False negatives", &evaluation.false_negatives),
+    ] {
+        if errors.is_empty() {
+            continue;
+        }
+        writeln!(out, "\n{title} ({}):", errors.len())?;
+        let shown = if max_errors == 0 {
+            errors.len()
+        } else {
+            max_errors
+        };
+        for error in errors.iter().take(shown) {
+            writeln!(out, "  {:>8}  {}", error.offset, error.context)?;
+        }
+        if errors.len() > shown {
+            writeln!(out, "  ... {} more", errors.len() - shown)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluation_scores() {
+        let text = "Dr. Smith left. He came back. It rained.";
+        let evaluation = Evaluation::new(text, &[3, 15], &[15, 29], 4);
+        assert_eq!(evaluation.precision, 0.5);
+        assert_eq!(evaluation.recall, 0.5);
+        assert_eq!(evaluation.f1, 0.5);
+        assert_eq!(evaluation.false_positives[0].context, "Dr.| Smi");
+        assert_eq!(evaluation.false_negatives[0].offset, 29);
+    }
+
+    #[test]
+    fn test_empty_sides_score_perfectly() {
+        let evaluation = Evaluation::new("One sentence", &[], &[], 4);
+        assert_eq!(
+            (evaluation.precision, evaluation.recall, evaluation.f1),
+            (1.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_text_report_truncates_errors() {
+        let text = "A. B. C. D.";
+        let evaluation = Evaluation::new(text, &[2, 5, 8], &[], 2);
+        let mut out = Vec::new();
+        write_text(&mut out, &evaluation, 2).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("Precision: 0.0000 (0/3)\nRecall:    1.0000 (0/0)\n"));
+        assert!(out.contains(
+            "False positives (3):\n         2  A.| B\n         5  B.| C\n  ... 1 more\n"
+        ));
+    }
+}
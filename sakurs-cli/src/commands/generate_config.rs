@@ -52,9 +52,15 @@ impl GenerateConfigArgs {
 
     /// Generate template configuration content
     fn generate_template(&self) -> String {
+        use sakurs_core::api::language_config::SCHEMA_VERSION;
+
         format!(
             r#"# Language configuration for {}
 
+# Version of the configuration format; files written for an older version
+# are migrated when loaded
+schema_version = {SCHEMA_VERSION}
+
 [metadata]
 code = "{}"
 name = "Custom Language"
@@ -102,8 +108,6 @@ pairs = [
 [suppression]
 # Fast pattern matching for enclosure suppression
 fast_patterns = [
-    # Apostrophes in contractions
-    {{ char = "'", before = "alpha", after = "alpha" }},
     # List items at line start: 1) item
     {{ char = ")", line_start = true, before = "alnum" }}
 ]
@@ -113,6 +117,12 @@ regex_patterns = [
     # Example: {{ pattern = "\\d+'\\d+\"", description = "Feet and inches like 5'9\"" }}
 ]
 
+# Apostrophes that are not quotes: contractions ("don't"), possessives
+# ("students'") and years ("'90s"), plus elided words listed here
+[suppression.apostrophes]
+elided_starts = []  # e.g. "em" for "'em"
+elided_ends = []    # e.g. "goin" for "goin'"
+
 # Abbreviations organized by category
 [abbreviations]
 # Add your abbreviations here, organized by category
@@ -137,12 +147,20 @@ require_following_space = true
 # Minimum word length to consider (default: 1)
 min_word_length = 1
 
+# Lowest weight that ends a sentence after an abbreviation (default: 0.5)
+threshold = 0.5
+
+# Weight of a capitalized word that is not listed below (default: unset,
+# such words never end a sentence after an abbreviation)
+# uppercase_fallback = 0.3
+
 # Categories of sentence starter words
-# Words should be written exactly as they should be matched
+# Words should be written exactly as they should be matched; a plain word has
+# weight 1, {{ word = "...", weight = 0.4 }} sets how reliably it starts a sentence
 pronouns = ["I", "You", "He", "She", "It", "We", "They"]
 articles = ["The", "A", "An"]
 demonstratives = ["This", "That", "These", "Those"]
-conjunctions = ["However", "But", "And", "So", "Therefore"]
+conjunctions = ["However", "But", "And", {{ word = "So", weight = 0.4 }}, "Therefore"]
 interrogatives = ["What", "When", "Where", "Why", "How", "Who"]
 # Add more categories as needed
 "#,
@@ -182,6 +200,9 @@ mod tests {
         assert!(template.contains("[terminators]"));
         assert!(template.contains("[abbreviations]"));
         assert!(template.contains("[sentence_starters]"));
+
+        let (_, report) = sakurs_core::LanguageConfig::from_toml(&template).unwrap();
+        assert!(report.is_clean(), "{report:?}");
     }
 
     #[test]
@@ -0,0 +1,360 @@
+//! Learn-abbrev command implementation: propose abbreviations from a corpus
+//!
+//! Candidates are scored with the type-based collocation statistics of Punkt
+//! (Kiss & Strunk, 2006): a word type that almost always ends with a period,
+//! is short, and contains internal periods is likely an abbreviation.
+
+use anyhow::{Context, Result};
+use clap::Args;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::PathBuf;
+
+use super::process::Language;
+use crate::language_source::LanguageSource;
+
+/// Arguments for the learn-abbrev command
+#[derive(Debug, Args)]
+pub struct LearnAbbrevArgs {
+    /// Corpus files or patterns (supports glob)
+    #[arg(short, long, value_name = "FILE/PATTERN", required = true)]
+    pub input: Vec<String>,
+
+    /// Leave out the abbreviations this built-in language already knows
+    /// NOTE: Mutually exclusive with --language-config
+    #[arg(short, long, value_enum, conflicts_with = "language_config")]
+    pub language: Option<Language>,
+
+    /// Leave out the abbreviations this language configuration already knows
+    /// NOTE: Mutually exclusive with --language
+    #[arg(short = 'c', long, value_name = "FILE", conflicts_with = "language")]
+    pub language_config: Option<PathBuf>,
+
+    /// Lowest score of a proposed abbreviation
+    #[arg(long, default_value = "0.3", value_name = "SCORE")]
+    pub threshold: f64,
+
+    /// Fewest occurrences with a final period of a proposed abbreviation
+    #[arg(long, default_value = "2", value_name = "COUNT")]
+    pub min_count: usize,
+
+    /// Name of the `[abbreviations]` category to write
+    #[arg(long, default_value = "learned", value_name = "NAME")]
+    pub category: String,
+
+    /// Output file (default: stdout)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// A proposed abbreviation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    /// The abbreviation without its final period, in its most frequent form
+    pub abbr: String,
+    /// Punkt score; higher is more likely an abbreviation
+    pub score: f64,
+    /// Occurrences followed by a period
+    pub with_period: usize,
+    /// All occurrences
+    pub total: usize,
+}
+
+/// Word type counts of a corpus, keyed by lowercase type without its final
+/// period
+#[derive(Debug, Default)]
+pub struct AbbreviationLearner {
+    with_period: HashMap<String, usize>,
+    without_period: HashMap<String, usize>,
+    /// Spellings seen before a period, per type
+    forms: HashMap<String, HashMap<String, usize>>,
+    tokens: usize,
+    period_tokens: usize,
+}
+
+impl AbbreviationLearner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of tokens counted so far
+    pub fn tokens(&self) -> usize {
+        self.tokens
+    }
+
+    /// Count the word types of `text`
+    pub fn add_text(&mut self, text: &str) {
+        for raw in text.split_whitespace() {
+            self.tokens += 1;
+            let token = raw
+                .trim_start_matches(|c: char| !c.is_alphanumeric())
+                .trim_end_matches(|c: char| !c.is_alphanumeric() && c != '.');
+            // Ellipses and other period runs are not abbreviation periods
+            if token.ends_with("..") {
+                continue;
+            }
+            let (word, period) = match token.strip_suffix('.') {
+                Some(word) => (word, true),
+                None => (token, false),
+            };
+            if period {
+                self.period_tokens += 1;
+            }
+            if !word.chars().any(char::is_alphabetic) {
+                continue;
+            }
+            let key = word.to_lowercase();
+            if period {
+                *self
+                    .forms
+                    .entry(key.clone())
+                    .or_default()
+                    .entry(word.to_string())
+                    .or_insert(0) += 1;
+                *self.with_period.entry(key).or_insert(0) += 1;
+            } else {
+                *self.without_period.entry(key).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// The types scoring at least `threshold` that occur at least
+    /// `min_count` times with a period, best first
+    pub fn candidates(&self, threshold: f64, min_count: usize) -> Vec<Candidate> {
+        let mut candidates: Vec<Candidate> = self
+            .with_period
+            .iter()
+            .filter(|&(_, &count)| count >= min_count.max(1))
+            .map(|(key, &with_period)| {
+                let without = self.without_period.get(key).copied().unwrap_or(0);
+                let abbr = self.forms[key]
+                    .iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                    .map(|(form, _)| form.clone())
+                    .unwrap_or_else(|| key.clone());
+                Candidate {
+                    abbr,
+                    score: self.score(key, with_period, without),
+                    with_period,
+                    total: with_period + without,
+                }
+            })
+            .filter(|c| c.score >= threshold)
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.abbr.cmp(&b.abbr))
+        });
+        candidates
+    }
+
+    /// Punkt's abbreviation score: the log-likelihood ratio of the type and
+    /// the period occurring together, scaled down for long types and types
+    /// also seen without a period, and up for internal periods
+    fn score(&self, key: &str, with_period: usize, without: usize) -> f64 {
+        let likelihood = dunning_log_likelihood(
+            with_period + without,
+            self.period_tokens,
+            with_period,
+            self.tokens,
+        );
+        let periods = key.matches('.').count();
+        let non_periods = key.chars().count() - periods + 1;
+        let length = (-(non_periods as f64)).exp();
+        let penalty = (non_periods as f64).powi(-(without as i32));
+        likelihood * length * (periods + 1) as f64 * penalty
+    }
+}
+
+/// Dunning's log-likelihood ratio that `count_ab` of the `count_a`
+/// occurrences of a type end with a period, against the period's overall
+/// rate `count_b / n`, with Punkt's fixed 0.99 alternative
+fn dunning_log_likelihood(count_a: usize, count_b: usize, count_ab: usize, n: usize) -> f64 {
+    let p1 = count_b as f64 / n as f64;
+    let p2: f64 = 0.99;
+    let (ab, a_not_b) = (count_ab as f64, (count_a - count_ab) as f64);
+    let null = ab * p1.ln() + a_not_b * (1.0 - p1).ln();
+    let alt = ab * p2.ln() + a_not_b * (1.0 - p2).ln();
+    -2.0 * (null - alt)
+}
+
+impl LearnAbbrevArgs {
+    /// Execute the learn-abbrev command
+    pub fn execute(&self) -> Result<()> {
+        let files = crate::input::resolve_patterns(&self.input)?;
+        let mut learner = AbbreviationLearner::new();
+        for file in &files {
+            learner.add_text(&crate::input::FileReader::read_text(file)?);
+        }
+
+        let known = self.known_abbreviations()?;
+        let candidates: Vec<Candidate> = learner
+            .candidates(self.threshold, self.min_count)
+            .into_iter()
+            .filter(|c| !known.contains(&c.abbr.to_lowercase()))
+            .collect();
+        if candidates.is_empty() {
+            eprintln!("No abbreviation candidates found");
+        }
+
+        let mut out: Box<dyn Write> = match &self.output {
+            Some(path) => Box::new(std::io::BufWriter::new(
+                std::fs::File::create(path)
+                    .with_context(|| format!("Failed to create {}", path.display()))?,
+            )),
+            None => Box::new(std::io::stdout().lock()),
+        };
+        write_toml(
+            &mut out,
+            &self.category,
+            &candidates,
+            files.len(),
+            learner.tokens(),
+        )?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Lowercase abbreviations of the language given, if any
+    fn known_abbreviations(&self) -> Result<HashSet<String>> {
+        let source = match (&self.language_config, self.language) {
+            (Some(path), _) => LanguageSource::External {
+                path: path.clone(),
+                language_code: None,
+            },
+            (None, Some(language)) => LanguageSource::BuiltIn(language),
+            (None, None) => return Ok(HashSet::new()),
+        };
+        let config = source.language_config()?;
+        Ok(config
+            .abbreviations
+            .categories
+            .values()
+            .flatten()
+            .map(|entry| entry.abbr().to_lowercase())
+            .collect())
+    }
+}
+
+/// Write the candidates as an `[abbreviations]` fragment, each with its
+/// score and period counts in a comment
+fn write_toml(
+    out: &mut impl Write,
+    category: &str,
+    candidates: &[Candidate],
+    files: usize,
+    tokens: usize,
+) -> Result<()> {
+    writeln!(
+        out,
+        "# Learned by `sakurs learn-abbrev` from {files} files ({tokens} tokens)"
+    )?;
+    writeln!(
+        out,
+        "# Review the list before merging it into a language configuration"
+    )?;
+    writeln!(out, "[abbreviations]")?;
+    // An empty category does not validate, so write none
+    if candidates.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "{} = [", toml_key(category))?;
+    for c in candidates {
+        writeln!(
+            out,
+            "    {}, # score {:.2}, {}/{} with period",
+            toml::Value::from(c.abbr.as_str()),
+            c.score,
+            c.with_period,
+            c.total
+        )?;
+    }
+    writeln!(out, "]")?;
+    Ok(())
+}
+
+/// `key` as written in TOML: bare when possible, quoted otherwise
+fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.to_string()
+    } else {
+        toml::Value::from(key).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn learner(text: &str) -> AbbreviationLearner {
+        let mut learner = AbbreviationLearner::new();
+        learner.add_text(text);
+        learner
+    }
+
+    #[test]
+    fn test_candidates_prefer_short_period_final_types() {
+        // Sentence-final words also occur inside sentences, abbreviations
+        // only before a period
+        let sentence = "We walked home along the river and talked about the weather. \
+                        The weather was fine and the river was calm when we got home. ";
+        let mut text = sentence.repeat(20);
+        text.push_str("See Fig. 2 and fig. 3 for the river vs. the lake. ");
+        text.push_str("Fig. 4 shows the weather vs. the season, e.g. in spring, as Fig. 5 does. ");
+        let candidates = learner(&text).candidates(0.3, 2);
+        let abbrs: Vec<&str> = candidates.iter().map(|c| c.abbr.as_str()).collect();
+        assert_eq!(abbrs, ["vs", "Fig"]);
+        assert_eq!((candidates[1].with_period, candidates[1].total), (4, 4));
+        // Seen once, "e.g" stays below the default minimum count
+        let all = learner(&text).candidates(0.3, 1);
+        assert_eq!(all[0].abbr, "e.g");
+    }
+
+    #[test]
+    fn test_types_seen_without_period_are_penalized() {
+        let with = learner("etc. etc. more text here.").candidates(f64::NEG_INFINITY, 1);
+        let without = learner("etc. etc. etc more text here.").candidates(f64::NEG_INFINITY, 1);
+        let score = |c: &[Candidate]| c.iter().find(|c| c.abbr == "etc").unwrap().score;
+        assert!(score(&without) < score(&with));
+    }
+
+    #[test]
+    fn test_tokens_ignore_ellipses_numbers_and_punctuation() {
+        let learner = learner("(e.g. this) wait... 3.14 and 12. \"Dr.\"");
+        let candidates = learner.candidates(0.0, 1);
+        let abbrs: Vec<&str> = candidates.iter().map(|c| c.abbr.as_str()).collect();
+        assert!(abbrs.contains(&"e.g"));
+        assert!(abbrs.contains(&"Dr"));
+        assert!(!abbrs.iter().any(|a| a.contains("wait") || a.contains('1')));
+        assert_eq!(learner.tokens(), 7);
+    }
+
+    #[test]
+    fn test_write_toml_fragment_parses() {
+        let candidates = [Candidate {
+            abbr: "approx".into(),
+            score: 1.234,
+            with_period: 4,
+            total: 5,
+        }];
+        let mut out = Vec::new();
+        write_toml(&mut out, "my domain", &candidates, 2, 100).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("    \"approx\", # score 1.23, 4/5 with period\n"));
+        let parsed: toml::Table = toml::from_str(&out).unwrap();
+        assert_eq!(
+            parsed["abbreviations"]["my domain"][0].as_str(),
+            Some("approx")
+        );
+
+        let mut out = Vec::new();
+        write_toml(&mut out, "learned", &[], 0, 0).unwrap();
+        let parsed: toml::Table = toml::from_str(&String::from_utf8(out).unwrap()).unwrap();
+        assert!(parsed["abbreviations"].as_table().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+//! List command implementation
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use sakurs_core::LanguageConfig;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// List subcommands
+#[derive(Debug, Subcommand)]
+pub enum ListCommands {
+    /// List available language rules
+    Languages {
+        /// Directory of external language configurations (*.toml) to list
+        /// along with the built-in languages
+        #[arg(long, value_name = "DIR", env = "SAKURS_LANGUAGE_DIR")]
+        language_dir: Option<PathBuf>,
+    },
+
+    /// List available output formats
+    Formats,
+}
+
+/// A language configuration and where it was found
+#[derive(Debug)]
+pub struct LanguageEntry {
+    pub config: Cow<'static, LanguageConfig>,
+    /// The file it was read from; None for a built-in language
+    pub path: Option<PathBuf>,
+}
+
+impl LanguageEntry {
+    /// Where the language comes from, for display
+    pub fn source(&self) -> Cow<'_, str> {
+        match &self.path {
+            Some(path) => path.display().to_string().into(),
+            None => "built-in".into(),
+        }
+    }
+}
+
+/// The built-in languages followed by the valid configurations in
+/// `language_dir`, sorted by file name; invalid files are reported with a
+/// warning and skipped
+pub fn languages(language_dir: Option<&Path>) -> Result<Vec<LanguageEntry>> {
+    let mut entries: Vec<LanguageEntry> = LanguageConfig::embedded()
+        .into_iter()
+        .map(|config| LanguageEntry {
+            config: Cow::Borrowed(config),
+            path: None,
+        })
+        .collect();
+
+    let Some(dir) = language_dir else {
+        return Ok(entries);
+    };
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read language directory: {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        match LanguageConfig::from_file(&path, None) {
+            Ok(config) => entries.push(LanguageEntry {
+                config: Cow::Owned(config),
+                path: Some(path),
+            }),
+            Err(e) => eprintln!("Warning: skipping {}: {e}", path.display()),
+        }
+    }
+    Ok(entries)
+}
+
+impl ListCommands {
+    /// Execute the list subcommand
+    pub fn execute(&self) -> Result<()> {
+        match self {
+            ListCommands::Languages { language_dir } => {
+                let entries = languages(language_dir.as_deref())?;
+                println!(
+                    "{:<6} {:<16} {:>11} {:>13}  SOURCE",
+                    "CODE", "NAME", "TERMINATORS", "ABBREVIATIONS"
+                );
+                for entry in &entries {
+                    let config = &entry.config;
+                    println!(
+                        "{:<6} {:<16} {:>11} {:>13}  {}",
+                        config.metadata.code,
+                        config.metadata.name,
+                        config.terminators.chars.len() + config.terminators.patterns.len(),
+                        config.abbreviation_count(),
+                        entry.source()
+                    );
+                }
+                Ok(())
+            }
+            ListCommands::Formats => {
+                println!("Available output formats:");
+                println!("  - text (Plain text, one sentence per line)");
+                println!("  - json (JSON array with sentence metadata)");
+                println!("  - jsonl (JSON Lines, one record per sentence with its document)");
+                println!("  - markdown (Markdown formatted output)");
+                Ok(())
+            }
+        }
+    }
+}
@@ -2,15 +2,28 @@
 
 use clap::Subcommand;
 
+pub use config::ConfigCommands;
+pub use list::ListCommands;
+
+mod checkpoint;
+pub mod completions;
+pub mod config;
+pub mod daemon;
+pub mod diff;
+pub mod evaluate;
 pub mod generate_config;
+pub mod learn_abbrev;
+pub mod list;
 pub mod process;
+pub mod serve;
+pub mod tune;
 pub mod validate;
 
 /// Available CLI commands
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// Process text files for sentence boundary detection
-    Process(process::ProcessArgs),
+    Process(Box<process::ProcessArgs>),
 
     /// Validate a language configuration file
     Validate(validate::ValidateArgs),
@@ -19,21 +32,40 @@ pub enum Commands {
     #[command(name = "generate-config")]
     GenerateConfig(generate_config::GenerateConfigArgs),
 
+    /// Serve sentence splitting over HTTP (POST /split)
+    Serve(serve::ServeArgs),
+
+    /// Serve sentence splitting to local tools over a Unix domain socket
+    /// (Unix only; use `serve` on Windows)
+    Daemon(daemon::DaemonArgs),
+
+    /// Compare the sentence boundaries of two segmentations
+    Diff(diff::DiffArgs),
+
+    /// Score sentence boundaries against a gold annotation
+    Evaluate(evaluate::EvaluateArgs),
+
+    /// Propose abbreviations learned from a corpus as a configuration fragment
+    #[command(name = "learn-abbrev")]
+    LearnAbbrev(learn_abbrev::LearnAbbrevArgs),
+
+    /// Benchmark this machine and write parallelism settings to the
+    /// configuration file
+    Tune(tune::TuneArgs),
+
     /// List available components
     List {
         #[command(subcommand)]
         subcommand: ListCommands,
     },
-}
-
-/// List subcommands
-#[derive(Debug, Subcommand)]
-pub enum ListCommands {
-    /// List available language rules
-    Languages,
 
-    /// List available output formats
-    Formats,
+    /// Show or create the configuration file
+    Config {
+        #[command(subcommand)]
+        subcommand: ConfigCommands,
+    },
+    /// Generate a shell completion script
+    Completions(completions::CompletionsArgs),
 }
 
 #[cfg(test)]
@@ -43,22 +75,64 @@ mod tests {
     #[test]
     fn test_commands_debug_format() {
         // Test Process command with minimal args
-        let process_cmd = Commands::Process(process::ProcessArgs {
+        let process_cmd = Commands::Process(Box::new(process::ProcessArgs {
             input: vec!["test.txt".to_string()],
+            docs: process::DocsMode::Single,
             output: None,
-            format: process::OutputFormat::Text,
+            output_compression: None,
+            output_dir: None,
+            watch: false,
+            fail_fast: false,
+            error_report: None,
+            format: Some(process::OutputFormat::Text),
             language: Some(process::Language::English),
             language_config: None,
             language_code: None,
             parallel: false,
             adaptive: false,
             threads: None,
+            adaptive_threshold_kb: None,
+            max_threads: None,
+            perf_profile: None,
+            jobs: None,
             chunk_kb: None,
+            chunk_cache: None,
+            encoding: None,
+            invalid_utf8: None,
+            strip_ruby: false,
+            no_strip_ruby: false,
+            ellipsis: None,
+            ellipsis_exception: vec![],
+            ellipsis_boundary: vec![],
+            emoji_terminators: false,
+            no_emoji_terminators: false,
+            profile: None,
+            min_sentence_chars: None,
+            max_sentence_chars: None,
+            force_split_bytes: None,
+            long_sentence_chars: None,
+            enclosure_warnings: false,
+            no_enclosure_warnings: false,
+            close_enclosures_at_paragraphs: false,
+            no_close_enclosures_at_paragraphs: false,
+            max_enclosure_depth: None,
+            depth_overflow: None,
+            infer_quote_direction: false,
+            no_infer_quote_direction: false,
+            dedupe: None,
+            enclosure_boundaries: vec![],
+            corrections: None,
+            fingerprint: None,
+            dump_config: false,
+            config: None,
             quiet: false,
+            no_quiet: false,
             verbose: 0,
+            log_format: None,
             stream: false,
             stream_chunk_mb: 10,
-        });
+            checkpoint_file: None,
+        }));
 
         let debug_str = format!("{:?}", process_cmd);
         assert!(debug_str.contains("Process"));
@@ -66,7 +140,7 @@ mod tests {
 
         // Test List command
         let list_cmd = Commands::List {
-            subcommand: ListCommands::Languages,
+            subcommand: ListCommands::Languages { language_dir: None },
         };
 
         let debug_str = format!("{:?}", list_cmd);
@@ -77,7 +151,7 @@ mod tests {
     #[test]
     fn test_list_commands_variants() {
         // Test Languages variant
-        let languages = ListCommands::Languages;
+        let languages = ListCommands::Languages { language_dir: None };
         let debug_str = format!("{:?}", languages);
         assert!(debug_str.contains("Languages"));
 
@@ -90,25 +164,67 @@ mod tests {
     #[test]
     fn test_enum_variants_completeness() {
         // Ensure all Commands variants are covered
-        let process_cmd = Commands::Process(process::ProcessArgs {
+        let process_cmd = Commands::Process(Box::new(process::ProcessArgs {
             input: vec!["test.txt".to_string()],
+            docs: process::DocsMode::Single,
             output: None,
-            format: process::OutputFormat::Text,
+            output_compression: None,
+            output_dir: None,
+            watch: false,
+            fail_fast: false,
+            error_report: None,
+            format: Some(process::OutputFormat::Text),
             language: Some(process::Language::English),
             language_config: None,
             language_code: None,
             parallel: false,
             adaptive: false,
             threads: None,
+            adaptive_threshold_kb: None,
+            max_threads: None,
+            perf_profile: None,
+            jobs: None,
             chunk_kb: None,
+            chunk_cache: None,
+            encoding: None,
+            invalid_utf8: None,
+            strip_ruby: false,
+            no_strip_ruby: false,
+            ellipsis: None,
+            ellipsis_exception: vec![],
+            ellipsis_boundary: vec![],
+            emoji_terminators: false,
+            no_emoji_terminators: false,
+            profile: None,
+            min_sentence_chars: None,
+            max_sentence_chars: None,
+            force_split_bytes: None,
+            long_sentence_chars: None,
+            enclosure_warnings: false,
+            no_enclosure_warnings: false,
+            close_enclosures_at_paragraphs: false,
+            no_close_enclosures_at_paragraphs: false,
+            max_enclosure_depth: None,
+            depth_overflow: None,
+            infer_quote_direction: false,
+            no_infer_quote_direction: false,
+            dedupe: None,
+            enclosure_boundaries: vec![],
+            corrections: None,
+            fingerprint: None,
+            dump_config: false,
+            config: None,
             quiet: false,
+            no_quiet: false,
             verbose: 0,
+            log_format: None,
             stream: false,
             stream_chunk_mb: 10,
-        });
+            checkpoint_file: None,
+        }));
 
         let list_cmd = Commands::List {
-            subcommand: ListCommands::Languages,
+            subcommand: ListCommands::Languages { language_dir: None },
         };
 
         // Verify all variants can be matched
@@ -116,27 +232,43 @@ mod tests {
             Commands::Process(_) => (),
             Commands::Validate(_) => panic!("Should be Process"),
             Commands::GenerateConfig(_) => panic!("Should be Process"),
+            Commands::Serve(_) => panic!("Should be Process"),
+            Commands::Daemon(_) => panic!("Should be Process"),
+            Commands::Diff(_) => panic!("Should be Process"),
+            Commands::Evaluate(_) => panic!("Should be Process"),
+            Commands::LearnAbbrev(_) => panic!("Should be Process"),
+            Commands::Tune(_) => panic!("Should be Process"),
             Commands::List { .. } => panic!("Should be Process"),
+            Commands::Config { .. } => panic!("Should be Process"),
+            Commands::Completions(_) => panic!("Should be Process"),
         }
 
         match list_cmd {
             Commands::Process(_) => panic!("Should be List"),
             Commands::Validate(_) => panic!("Should be List"),
             Commands::GenerateConfig(_) => panic!("Should be List"),
+            Commands::Serve(_) => panic!("Should be List"),
+            Commands::Daemon(_) => panic!("Should be List"),
+            Commands::Diff(_) => panic!("Should be List"),
+            Commands::Evaluate(_) => panic!("Should be List"),
+            Commands::LearnAbbrev(_) => panic!("Should be List"),
+            Commands::Tune(_) => panic!("Should be List"),
             Commands::List { .. } => (),
+            Commands::Config { .. } => panic!("Should be List"),
+            Commands::Completions(_) => panic!("Should be List"),
         }
     }
 
     #[test]
     fn test_list_commands_completeness() {
         // Test all ListCommands variants
-        match ListCommands::Languages {
-            ListCommands::Languages => (),
+        match (ListCommands::Languages { language_dir: None }) {
+            ListCommands::Languages { .. } => (),
             ListCommands::Formats => panic!("Should be Languages"),
         }
 
         match ListCommands::Formats {
-            ListCommands::Languages => panic!("Should be Formats"),
+            ListCommands::Languages { .. } => panic!("Should be Formats"),
             ListCommands::Formats => (),
         }
     }
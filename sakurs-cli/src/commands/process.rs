@@ -2,22 +2,67 @@
 
 use anyhow::{Context, Result};
 use clap::Args;
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::compression::Compression;
+use crate::input::encoding::{Decoded, OffsetMap};
+use crate::input::ArchiveKind;
+use crate::output::{Document, DocumentWarning, Sentence};
 
 /// Arguments for the process command
-#[derive(Debug, Args)]
+#[derive(Debug, Clone, Args)]
 pub struct ProcessArgs {
     /// Input files or patterns (supports glob, use '-' for stdin)
-    #[arg(short, long, value_name = "FILE/PATTERN", required = true)]
+    #[arg(
+        short,
+        long,
+        value_name = "FILE/PATTERN",
+        required_unless_present = "dump_config"
+    )]
     pub input: Vec<String>,
 
+    /// How stdin is split into documents: single (all of it is one
+    /// document) or nul (documents separated by NUL bytes, as from
+    /// `find -print0`); with nul, text output ends each document with NUL
+    /// and jsonl, arrow and parquet output use its index as doc_id
+    #[arg(long, value_name = "MODE", default_value = "single")]
+    pub docs: DocsMode,
+
     /// Output file (default: stdout)
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
-    /// Output format
-    #[arg(short, long, value_enum, default_value = "text")]
-    pub format: OutputFormat,
+    /// Compress the output: none, gzip or zstd (default: from the output
+    /// file's extension, .gz or .zst)
+    #[arg(long, value_name = "COMPRESSION")]
+    pub output_compression: Option<crate::compression::Compression>,
+
+    /// Write each input file's sentences to its own file in this directory,
+    /// named after the input with the format's extension (doc.txt ->
+    /// DIR/doc.json); inputs whose outputs would overwrite an input or each
+    /// other are refused
+    #[arg(long, value_name = "DIR", conflicts_with = "output")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Keep running and reprocess input files when they change, rewriting
+    /// their outputs in --output-dir
+    #[arg(long, requires = "output_dir")]
+    pub watch: bool,
+
+    /// Stop at the first file that fails instead of skipping it; by default
+    /// failures are summarized at the end and the exit status is 3
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Write a JSON report of the run (files processed and each failure
+    /// with its error) to this file
+    #[arg(long, value_name = "FILE")]
+    pub error_report: Option<PathBuf>,
+
+    /// Output format (default: text)
+    #[arg(short, long, value_enum)]
+    pub format: Option<OutputFormat>,
 
     /// Language for sentence detection rules
     /// NOTE: Mutually exclusive with --language-config
@@ -47,18 +92,212 @@ pub struct ProcessArgs {
     #[arg(short = 't', long, value_name = "COUNT")]
     pub threads: Option<usize>,
 
-    /// Chunk size in KB for parallel processing (default: 256)
+    /// Text size in KB per thread when the thread count is chosen per text;
+    /// smaller texts run on one thread (default: 256; see `sakurs tune`)
+    #[arg(long, value_name = "SIZE_KB")]
+    pub adaptive_threshold_kb: Option<usize>,
+
+    /// Most threads used when the thread count is chosen per text (default:
+    /// all available)
+    #[arg(long, value_name = "COUNT", conflicts_with = "threads")]
+    pub max_threads: Option<usize>,
+
+    /// Chunk size, thread limit and adaptive threshold for a class of
+    /// machine: laptop, server-32c or container-2c (a container limited to
+    /// 2 CPUs). --chunk-kb, --max-threads and --adaptive-threshold-kb
+    /// override the preset's values
+    #[arg(long, value_name = "PROFILE")]
+    pub perf_profile: Option<sakurs_core::PerfProfile>,
+
+    /// Process up to this many input files at once (default: 1); files
+    /// large enough to keep every job busy are still processed one at a
+    /// time with threads inside them, and output stays in input order
+    #[arg(short = 'j', long, value_name = "COUNT")]
+    pub jobs: Option<usize>,
+
+    /// Chunk size in KB for parallel processing (default: adaptive)
     #[arg(long, value_name = "SIZE_KB")]
     pub chunk_kb: Option<usize>,
 
+    /// Keep the scanned state of every chunk in DIR and reuse it when a
+    /// later run meets the same chunk under the same rules, so reprocessing
+    /// a mostly unchanged corpus skips the unchanged parts. Output is the
+    /// same as without it; hits need the same chunk size, so fix it with
+    /// --chunk-kb
+    #[arg(long, value_name = "DIR")]
+    pub chunk_cache: Option<PathBuf>,
+
+    /// Input text encoding; input is transcoded to UTF-8 and offsets refer
+    /// to the input bytes (default: utf-8)
+    #[arg(short = 'e', long, value_enum)]
+    pub encoding: Option<crate::input::InputEncoding>,
+
+    /// How invalid UTF-8 is handled: error, replace (with U+FFFD) or skip.
+    /// Applies to UTF-8 input; other encodings are always strict (default:
+    /// error)
+    #[arg(long, value_name = "MODE")]
+    pub invalid_utf8: Option<sakurs_core::InvalidUtf8>,
+
+    /// Remove ruby (furigana) annotations before segmentation: Aozora Bunko
+    /// readings (漢字《かんじ》, ｜) and HTML <rt>/<rp> elements. Sentences
+    /// keep their annotations in the output
+    #[arg(long, overrides_with = "no_strip_ruby")]
+    pub strip_ruby: bool,
+
+    /// Turn off --strip-ruby, overriding the configuration file
+    #[arg(long, overrides_with = "strip_ruby")]
+    pub no_strip_ruby: bool,
+
+    /// How ellipses ("..." and "…") are judged: language (the language
+    /// configuration's rules), always, never or before-capital (default:
+    /// language)
+    #[arg(long, value_name = "POLICY")]
+    pub ellipsis: Option<sakurs_core::EllipsisPolicy>,
+
+    /// Regex that keeps an ellipsis within 20 bytes of a match from ending
+    /// a sentence, whatever the policy (repeatable)
+    #[arg(long, value_name = "REGEX")]
+    pub ellipsis_exception: Vec<String>,
+
+    /// Regex that makes an ellipsis within 20 bytes of a match end a
+    /// sentence, whatever the policy (repeatable; --ellipsis-exception
+    /// patterns are tried first)
+    #[arg(long, value_name = "REGEX")]
+    pub ellipsis_boundary: Vec<String>,
+
+    /// Let emoji end sentences ("That was wild 😂😂 Next one.")
+    #[arg(long, overrides_with = "no_emoji_terminators")]
+    pub emoji_terminators: bool,
+
+    /// Turn off --emoji-terminators, overriding the configuration file
+    #[arg(long, overrides_with = "emoji_terminators")]
+    pub no_emoji_terminators: bool,
+
+    /// Preset for a kind of text: social (emoji terminators, no splits
+    /// inside URLs, @mentions and #hashtags, a boundary at every line break),
+    /// legal (citations like "Fed. R. Civ. P. 12(b)(6)" and numbered
+    /// subsections stay whole) or scientific ("et al.", "Fig. 3", "e.g.,",
+    /// DOIs and arXiv identifiers stay whole)
+    #[arg(long, value_name = "PROFILE")]
+    pub profile: Option<sakurs_core::Profile>,
+
+    /// Merge sentences shorter than this many characters into the previous
+    /// sentence
+    #[arg(long, value_name = "CHARS")]
+    pub min_sentence_chars: Option<usize>,
+
+    /// Force-split sentences longer than this many characters, preferring
+    /// rejected terminators, then clause punctuation, then spaces
+    #[arg(long, value_name = "CHARS")]
+    pub max_sentence_chars: Option<usize>,
+
+    /// Force-split spans longer than this many bytes that have no boundary
+    /// (machine-generated text without punctuation), at the clause
+    /// punctuation or space nearest the limit
+    #[arg(long, value_name = "BYTES")]
+    pub force_split_bytes: Option<usize>,
+
+    /// Warn of sentences longer than this many characters, which usually
+    /// means a missed boundary [default: 10000]
+    #[arg(long, value_name = "CHARS")]
+    pub long_sentence_chars: Option<usize>,
+
+    /// Warn of quotes and brackets left unclosed and of closers without an
+    /// opener
+    #[arg(long, overrides_with = "no_enclosure_warnings")]
+    pub enclosure_warnings: bool,
+
+    /// Turn off --enclosure-warnings, overriding the configuration file
+    #[arg(long, overrides_with = "enclosure_warnings")]
+    pub no_enclosure_warnings: bool,
+
+    /// Close quotes and brackets still open at a blank line, so that one
+    /// unclosed quote does not swallow the boundaries of the paragraphs
+    /// after it
+    #[arg(long, overrides_with = "no_close_enclosures_at_paragraphs")]
+    pub close_enclosures_at_paragraphs: bool,
+
+    /// Turn off --close-enclosures-at-paragraphs, overriding the configuration file
+    #[arg(long, overrides_with = "close_enclosures_at_paragraphs")]
+    pub no_close_enclosures_at_paragraphs: bool,
+
+    /// Track at most this many nested brackets of each type, so that
+    /// thousands of unmatched "(" cannot swallow the rest of the document
+    #[arg(long, value_name = "DEPTH")]
+    pub max_enclosure_depth: Option<usize>,
+
+    /// What happens to brackets opened past --max-enclosure-depth: ignore
+    /// them, reset the bracket type at the next blank line, or only warn
+    /// (default: ignore)
+    #[arg(long, value_name = "POLICY")]
+    pub depth_overflow: Option<sakurs_core::DepthOverflow>,
+
+    /// Judge straight quotes as opening or closing by the characters around
+    /// them, so that one stray quote does not invert every later quotation
+    #[arg(long, overrides_with = "no_infer_quote_direction")]
+    pub infer_quote_direction: bool,
+
+    /// Turn off --infer-quote-direction, overriding the configuration file
+    #[arg(long, overrides_with = "infer_quote_direction")]
+    pub no_infer_quote_direction: bool,
+
+    /// Drop sentences that repeat an earlier sentence, ignoring case and
+    /// whitespace, within each document or across the whole run (default:
+    /// document); the count dropped is logged per document
+    #[arg(
+        long,
+        value_name = "SCOPE",
+        num_args = 0..=1,
+        default_missing_value = "document"
+    )]
+    pub dedupe: Option<DedupeScope>,
+
+    /// Which sentences end inside the enclosure type of a bracket, as
+    /// CHAR=POLICY with suppress, allow, strong_only or dialogue (repeatable;
+    /// "「=dialogue" splits the lines of dialogue in Japanese fiction)
+    #[arg(long, value_name = "CHAR=POLICY")]
+    pub enclosure_boundaries: Vec<String>,
+
+    /// Boundary corrections (TOML): offsets of each document, by the
+    /// doc_id and byte offsets of jsonl output, that are or are not
+    /// boundaries, overriding the rules
+    #[arg(long, value_name = "FILE")]
+    pub corrections: Option<PathBuf>,
+
+    /// Write a fingerprint of each document's segmentation to FILE, one
+    /// `<fingerprint>  <doc_id>` line per document: a stable hash of its
+    /// boundaries, the sakurs version and the rules, for caching results
+    /// and verifying that a later run segments identically
+    #[arg(long, value_name = "FILE")]
+    pub fingerprint: Option<PathBuf>,
+
+    /// Configuration file with defaults for these options (default:
+    /// ~/.config/sakurs/config.toml if it exists); flags override its values
+    #[arg(long, value_name = "FILE", env = "SAKURS_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Print the effective segmentation configuration (defaults,
+    /// configuration file and flags resolved) as TOML and exit
+    #[arg(long)]
+    pub dump_config: bool,
+
     /// Suppress progress output
-    #[arg(short, long)]
+    #[arg(short, long, overrides_with = "no_quiet")]
     pub quiet: bool,
 
+    /// Show progress output, overriding the configuration file
+    #[arg(long, overrides_with = "quiet")]
+    pub no_quiet: bool,
+
     /// Increase verbosity
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
 
+    /// Log format on stderr: text, or json for one object per record with
+    /// fields such as per-file timing and boundary counts (default: text)
+    #[arg(long, value_name = "FORMAT")]
+    pub log_format: Option<crate::logging::LogFormat>,
+
     /// Enable streaming mode for large files (process in chunks)
     #[arg(long)]
     pub stream: bool,
@@ -66,19 +305,62 @@ pub struct ProcessArgs {
     /// Streaming chunk size in MB (default: 10MB)
     #[arg(long, default_value = "10", requires = "stream")]
     pub stream_chunk_mb: u64,
+
+    /// Save progress to FILE after every streamed chunk and, if FILE
+    /// exists, resume the interrupted run it records instead of starting
+    /// over. Needs one uncompressed UTF-8 input file and --output in text
+    /// or jsonl format; options that only apply to whole-text processing
+    /// (sentence length limits, enclosure depth caps, ruby stripping) are
+    /// refused, as is --invalid-utf8 replace or skip
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires_all = ["stream", "output"],
+        conflicts_with_all = ["output_dir", "dedupe", "corrections", "fingerprint"]
+    )]
+    pub checkpoint_file: Option<PathBuf>,
 }
 
 /// Supported output formats
-#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
 pub enum OutputFormat {
     /// Plain text with one sentence per line
+    #[default]
     #[value(alias = "txt")]
     Text,
     /// JSON array of sentences with metadata
     Json,
+    /// JSON Lines with one record per sentence: doc_id, sentence_index,
+    /// text, start, end
+    #[value(alias = "ndjson")]
+    Jsonl,
     /// Markdown formatted output
     #[value(alias = "md")]
     Markdown,
+    /// Arrow IPC file with columns doc_id, sentence_index, text, start, end
+    #[value(alias = "feather")]
+    Arrow,
+    /// Parquet file with columns doc_id, sentence_index, text, start, end
+    Parquet,
+}
+
+/// How stdin is split into documents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DocsMode {
+    /// All of stdin is one document
+    #[default]
+    Single,
+    /// Documents are separated by NUL bytes
+    Nul,
+}
+
+/// Which earlier sentences `--dedupe` compares a sentence with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DedupeScope {
+    /// Those of the same document
+    Document,
+    /// Those of every document written before it
+    Run,
 }
 
 /// Supported languages
@@ -90,76 +372,238 @@ pub enum Language {
     /// Japanese language rules
     #[value(alias = "ja", alias = "jpn")]
     Japanese,
+    /// Portuguese language rules
+    #[value(alias = "pt", alias = "por")]
+    Portuguese,
+    /// Italian language rules
+    #[value(alias = "it", alias = "ita")]
+    Italian,
+    /// Arabic language rules
+    #[value(alias = "ar", alias = "ara")]
+    Arabic,
+    /// Thai language rules
+    #[value(alias = "th", alias = "tha")]
+    Thai,
+    /// Hindi language rules
+    #[value(alias = "hi", alias = "hin")]
+    Hindi,
 }
 
 impl ProcessArgs {
     /// Execute the process command
     pub fn execute(&self) -> Result<()> {
+        let (config, _) = crate::config::CliConfig::discover(self.config.as_deref())?;
+        self.with_defaults(&config.process)?.run()
+    }
+
+    /// These arguments with the options they leave unset taken from a
+    /// configuration file
+    pub fn with_defaults(&self, defaults: &crate::config::ProcessDefaults) -> Result<Self> {
+        use clap::ValueEnum;
+
+        fn parse<T: std::str::FromStr>(key: &str, value: &Option<String>) -> Result<Option<T>>
+        where
+            T::Err: std::fmt::Display,
+        {
+            value
+                .as_deref()
+                .map(|v| {
+                    v.parse()
+                        .map_err(|e| anyhow::anyhow!("config `{key}`: {e}"))
+                })
+                .transpose()
+        }
+        fn parse_enum<T: ValueEnum>(key: &str, value: &Option<String>) -> Result<Option<T>> {
+            value
+                .as_deref()
+                .map(|v| T::from_str(v, true).map_err(|e| anyhow::anyhow!("config `{key}`: {e}")))
+                .transpose()
+        }
+        /// A switch and its --no- form: on, off, or unset on the command line
+        fn switch(on: bool, off: bool) -> Option<bool> {
+            match (on, off) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            }
+        }
+
+        let mut args = self.clone();
+        args.format = args.format.or(parse_enum("format", &defaults.format)?);
+        // A language given on the command line, built-in or external,
+        // replaces the file's
+        if args.language.is_none() && args.language_config.is_none() {
+            args.language = parse_enum("language", &defaults.language)?;
+            args.language_config = defaults.language_config.clone();
+            if args.language_config.is_some() {
+                args.language = None;
+                args.language_code = args.language_code.or(defaults.language_code.clone());
+            }
+        }
+        args.threads = args.threads.or(defaults.threads);
+        args.adaptive_threshold_kb = args
+            .adaptive_threshold_kb
+            .or(defaults.adaptive_threshold_kb);
+        args.max_threads = args.max_threads.or(defaults.max_threads);
+        args.perf_profile = args
+            .perf_profile
+            .or(parse("perf_profile", &defaults.perf_profile)?);
+        args.jobs = args.jobs.or(defaults.jobs);
+        args.chunk_kb = args.chunk_kb.or(defaults.chunk_kb);
+        args.encoding = args
+            .encoding
+            .or(parse_enum("encoding", &defaults.encoding)?);
+        args.invalid_utf8 = args
+            .invalid_utf8
+            .or(parse("invalid_utf8", &defaults.invalid_utf8)?);
+        args.strip_ruby = switch(args.strip_ruby, args.no_strip_ruby)
+            .or(defaults.strip_ruby)
+            .unwrap_or(false);
+        args.ellipsis = args.ellipsis.or(parse("ellipsis", &defaults.ellipsis)?);
+        args.emoji_terminators = switch(args.emoji_terminators, args.no_emoji_terminators)
+            .or(defaults.emoji_terminators)
+            .unwrap_or(false);
+        args.profile = args.profile.or(parse("profile", &defaults.profile)?);
+        args.min_sentence_chars = args.min_sentence_chars.or(defaults.min_sentence_chars);
+        args.max_sentence_chars = args.max_sentence_chars.or(defaults.max_sentence_chars);
+        args.force_split_bytes = args.force_split_bytes.or(defaults.force_split_bytes);
+        args.long_sentence_chars = args.long_sentence_chars.or(defaults.long_sentence_chars);
+        args.enclosure_warnings = switch(args.enclosure_warnings, args.no_enclosure_warnings)
+            .or(defaults.enclosure_warnings)
+            .unwrap_or(false);
+        args.close_enclosures_at_paragraphs = switch(
+            args.close_enclosures_at_paragraphs,
+            args.no_close_enclosures_at_paragraphs,
+        )
+        .or(defaults.close_enclosures_at_paragraphs)
+        .unwrap_or(false);
+        args.max_enclosure_depth = args.max_enclosure_depth.or(defaults.max_enclosure_depth);
+        args.depth_overflow = args
+            .depth_overflow
+            .or(parse("depth_overflow", &defaults.depth_overflow)?);
+        args.infer_quote_direction =
+            switch(args.infer_quote_direction, args.no_infer_quote_direction)
+                .or(defaults.infer_quote_direction)
+                .unwrap_or(false);
+        args.dedupe = args.dedupe.or(parse_enum("dedupe", &defaults.dedupe)?);
+        args.quiet = switch(args.quiet, args.no_quiet)
+            .or(defaults.quiet)
+            .unwrap_or(false);
+        args.log_format = args
+            .log_format
+            .or(parse_enum("log_format", &defaults.log_format)?);
+        Ok(args)
+    }
+
+    /// Run the command with its final arguments
+    fn run(&self) -> Result<()> {
         // Initialize logging based on verbosity
         self.init_logging()?;
 
+        if self.dump_config {
+            return self.dump_config();
+        }
+
         log::info!("Starting text processing");
         log::debug!("Arguments: {self:?}");
 
-        // Create output formatter
-        let mut formatter: Box<dyn crate::output::OutputFormatter> = self.create_formatter()?;
+        if let Some(checkpoint) = &self.checkpoint_file {
+            return self.run_checkpointed(checkpoint);
+        }
 
         // Create processor
-        let processor = self.create_processor()?;
+        let processor = self.create_segmenter()?;
+
+        if let Some(dir) = &self.output_dir {
+            return self.run_per_file(&processor, dir);
+        }
+
+        // Create output formatter
+        let mut formatter: Box<dyn crate::output::OutputFormatter> =
+            self.create_formatter(self.output.as_deref())?;
 
         // Check if input is stdin
+        let mut report = BatchReport::default();
         if self.input.len() == 1 && self.input[0] == "-" {
             log::info!("Reading from stdin");
             self.process_stdin(&processor, &mut formatter)?;
+        } else if self.docs == DocsMode::Nul {
+            anyhow::bail!("--docs nul reads its documents from stdin; use --input -");
         } else {
             // Resolve file patterns
             let files = crate::input::resolve_patterns(&self.input)?;
             log::info!("Found {} files to process", files.len());
 
-            // Initialize progress reporter
+            // Initialize progress reporter: a single input shows its bytes
             let mut progress = crate::progress::ProgressReporter::new(self.quiet);
-            progress.init_files(files.len() as u64);
-
-            for file in &files {
-                log::info!("Processing file: {}", file.display());
-
-                // Check if we should use streaming mode
-                let file_size_mb = crate::input::FileReader::file_size(file)? / (1024 * 1024);
-                let should_stream = self.stream || file_size_mb > 100; // Auto-stream for files > 100MB
-
-                if should_stream {
-                    log::info!(
-                        "Using streaming mode for {} ({}MB)",
-                        file.display(),
-                        file_size_mb
-                    );
-                    self.process_file_streaming(file, &processor, &mut formatter)?;
-                } else {
-                    // Read entire file content
-                    let content = crate::input::FileReader::read_text(file)?;
-
-                    // Process text
-                    let result = processor
-                        .process(sakurs_core::Input::from_text(content.clone()))
-                        .map_err(|e| anyhow::anyhow!("Processing failed: {e}"))?;
-
-                    // Extract and output sentences
-                    let mut last_offset = 0;
-                    for boundary in &result.boundaries {
-                        let sentence = &content[last_offset..boundary.offset];
-                        formatter.format_sentence(sentence.trim(), last_offset)?;
-                        last_offset = boundary.offset;
-                    }
+            match files.as_slice() {
+                [file] if ArchiveKind::from_path(file).is_none() => {
+                    progress.init_bytes(&file.display().to_string())
+                }
+                _ => progress.init_files(files.len() as u64),
+            }
 
-                    // Don't forget the last sentence after the final boundary
-                    if last_offset < content.len() {
-                        let sentence = &content[last_offset..];
-                        if !sentence.trim().is_empty() {
-                            formatter.format_sentence(sentence.trim(), last_offset)?;
+            let jobs = self.jobs.unwrap_or(1);
+            if jobs == 0 {
+                anyhow::bail!("Job count must be greater than 0");
+            }
+            let steps = plan_jobs(&files, jobs, |file| {
+                crate::input::FileReader::file_size(file).ok()
+            });
+            // Files processed concurrently each get one thread
+            let mut concurrent = None;
+            if steps
+                .iter()
+                .any(|step| matches!(step, JobStep::Concurrent(_)))
+            {
+                let sequential = Segmenter {
+                    processor: sakurs_core::ProcessorHandle::new(
+                        Self {
+                            threads: Some(1),
+                            ..self.clone()
                         }
+                        .create_processor()?,
+                    ),
+                    corrections: processor.corrections.clone(),
+                    fingerprints: processor.fingerprints.clone(),
+                    dedupe: processor.dedupe,
+                    seen: processor.seen.clone(),
+                };
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(jobs)
+                    .build()
+                    .context("Failed to create the job thread pool")?;
+                concurrent = Some((sequential, pool));
+            }
+
+            for step in steps {
+                let file = match (step, &concurrent) {
+                    (JobStep::Concurrent(batch), Some((sequential, pool))) => {
+                        self.process_concurrently(
+                            batch,
+                            sequential,
+                            pool,
+                            &mut formatter,
+                            &progress,
+                            &mut report,
+                        )?;
+                        continue;
+                    }
+                    (JobStep::Concurrent(_), None) => unreachable!("pool is built for batches"),
+                    (JobStep::Alone(file), _) => file,
+                };
+                if let Some(kind) = ArchiveKind::from_path(file) {
+                    // Members are recorded one by one; only a failure to
+                    // read the archive itself is recorded for the file
+                    let result =
+                        self.process_archive(file, kind, &processor, &mut formatter, &mut report);
+                    if result.is_err() {
+                        report.record(file, result, self.fail_fast)?;
                     }
+                } else {
+                    let result = self.process_file(file, &processor, &mut formatter, &progress);
+                    report.record(file, result, self.fail_fast)?;
                 }
-
                 progress.file_completed(&file.file_name().unwrap_or_default().to_string_lossy());
             }
 
@@ -169,125 +613,544 @@ impl ProcessArgs {
 
         // Finalize output
         formatter.finish()?;
-        Ok(())
+        drop(formatter);
+        report.finish(self.error_report.as_deref())
     }
 
-    /// Initialize logging based on verbosity level
-    fn init_logging(&self) -> Result<()> {
-        let log_level = match self.verbose {
-            0 => "warn",
-            1 => "info",
-            2 => "debug",
-            _ => "trace",
+    /// Stream the single input file to --output, saving progress to
+    /// `checkpoint` and resuming from it if it exists
+    fn run_checkpointed(&self, checkpoint: &Path) -> Result<()> {
+        let unsupported = [
+            ("--min-sentence-chars", self.min_sentence_chars.is_some()),
+            ("--max-sentence-chars", self.max_sentence_chars.is_some()),
+            ("--force-split-bytes", self.force_split_bytes.is_some()),
+            ("--long-sentence-chars", self.long_sentence_chars.is_some()),
+            ("--enclosure-warnings", self.enclosure_warnings),
+            ("--max-enclosure-depth", self.max_enclosure_depth.is_some()),
+            (
+                "--close-enclosures-at-paragraphs",
+                self.close_enclosures_at_paragraphs,
+            ),
+            ("--strip-ruby", self.strip_ruby),
+            ("--output-compression", self.output_compression.is_some()),
+        ];
+        if let Some((option, _)) = unsupported.iter().find(|(_, set)| *set) {
+            anyhow::bail!("--checkpoint-file does not support {option}");
+        }
+        let format = self.format.unwrap_or_default();
+        if !matches!(format, OutputFormat::Text | OutputFormat::Jsonl) {
+            anyhow::bail!("--checkpoint-file needs text or jsonl output, got {format:?}");
+        }
+        if self.encoding.unwrap_or_default() != crate::input::InputEncoding::Utf8 {
+            anyhow::bail!("--checkpoint-file needs UTF-8 input");
+        }
+        // Checkpoints record input offsets as the text fed so far, which
+        // replaced or skipped bytes would throw off
+        if self.invalid_utf8.unwrap_or_default() != sakurs_core::InvalidUtf8::Error {
+            anyhow::bail!("--checkpoint-file does not support --invalid-utf8 replace or skip");
+        }
+        let files = crate::input::resolve_patterns(&self.input)?;
+        let [input] = files.as_slice() else {
+            anyhow::bail!("--checkpoint-file needs exactly one input file");
         };
+        if ArchiveKind::from_path(input).is_some()
+            || Compression::from_extension(input) != Compression::None
+        {
+            anyhow::bail!("--checkpoint-file needs an uncompressed input file");
+        }
+        let output = self.output.as_deref().expect("clap requires --output");
+
+        super::checkpoint::CheckpointedRun {
+            input,
+            output,
+            checkpoint,
+            piece_size: (self.stream_chunk_mb.max(1) as usize) << 20,
+            jsonl: matches!(format, OutputFormat::Jsonl),
+        }
+        .run(&self.create_processor()?)
+    }
 
-        if !self.quiet {
-            env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(log_level))
-                .init();
+    /// Process one input file into `formatter`
+    fn process_file(
+        &self,
+        file: &std::path::Path,
+        processor: &Segmenter,
+        formatter: &mut Box<dyn crate::output::OutputFormatter>,
+        progress: &crate::progress::ProgressReporter,
+    ) -> Result<()> {
+        log::info!("Processing file: {}", file.display());
+        let started = std::time::Instant::now();
+
+        // Check if we should use streaming mode
+        let file_size_mb = crate::input::FileReader::file_size(file)? / (1024 * 1024);
+        let should_stream = self.stream || file_size_mb > 100; // Auto-stream for files > 100MB
+
+        if should_stream {
+            log::info!(
+                "Using streaming mode for {} ({}MB)",
+                file.display(),
+                file_size_mb
+            );
+            return self.process_file_streaming(file, processor, formatter, progress, started);
         }
 
+        // Read entire file content
+        let decoded = crate::input::FileReader::read_text_with_encoding(
+            file,
+            self.encoding.unwrap_or_default(),
+            self.invalid_utf8.unwrap_or_default(),
+        )?;
+        let path = file.display().to_string();
+        let document = Document {
+            id: &path,
+            path: &path,
+        };
+        self.process_text(&document, decoded, processor, formatter, progress, started)
+    }
+
+    /// Process the decoded text of one document into `formatter`
+    fn process_text(
+        &self,
+        document: &Document<'_>,
+        decoded: Decoded,
+        processor: &Segmenter,
+        formatter: &mut Box<dyn crate::output::OutputFormatter>,
+        progress: &crate::progress::ProgressReporter,
+        started: std::time::Instant,
+    ) -> Result<()> {
+        let segmented = segment(document.id, decoded, processor, progress, started)?;
+        write_document(document, segmented, processor, formatter)
+    }
+
+    /// Read and segment each of `files` on its own thread of `pool`, then
+    /// write their sentences in input order, recording each in `report`
+    fn process_concurrently(
+        &self,
+        files: &[PathBuf],
+        processor: &Segmenter,
+        pool: &rayon::ThreadPool,
+        formatter: &mut Box<dyn crate::output::OutputFormatter>,
+        progress: &crate::progress::ProgressReporter,
+        report: &mut BatchReport,
+    ) -> Result<()> {
+        use rayon::prelude::*;
+
+        let silent = crate::progress::ProgressReporter::new(true);
+        let results: Vec<Result<Segmented>> = pool.install(|| {
+            files
+                .par_iter()
+                .map(|file| {
+                    log::info!("Processing file: {}", file.display());
+                    let started = std::time::Instant::now();
+                    let decoded = crate::input::FileReader::read_text_with_encoding(
+                        file,
+                        self.encoding.unwrap_or_default(),
+                        self.invalid_utf8.unwrap_or_default(),
+                    )?;
+                    segment(
+                        &file.display().to_string(),
+                        decoded,
+                        processor,
+                        &silent,
+                        started,
+                    )
+                })
+                .collect()
+        });
+        for (file, result) in files.iter().zip(results) {
+            let path = file.display().to_string();
+            let document = Document {
+                id: &path,
+                path: &path,
+            };
+            let result = result
+                .and_then(|segmented| write_document(&document, segmented, processor, formatter));
+            report.record(file, result, self.fail_fast)?;
+            progress.file_completed(&file.file_name().unwrap_or_default().to_string_lossy());
+        }
         Ok(())
     }
 
-    /// Create appropriate output formatter based on format option
-    fn create_formatter(&self) -> Result<Box<dyn crate::output::OutputFormatter>> {
-        use std::io;
+    /// Process each regular file in a tar or zip archive as a document whose
+    /// id is its path in the archive, recording each member in `report`
+    fn process_archive(
+        &self,
+        archive: &Path,
+        kind: ArchiveKind,
+        processor: &Segmenter,
+        formatter: &mut Box<dyn crate::output::OutputFormatter>,
+        report: &mut BatchReport,
+    ) -> Result<()> {
+        log::info!("Processing archive: {}", archive.display());
+        let silent = crate::progress::ProgressReporter::new(true);
+        crate::input::archive::for_each_member(archive, kind, |name, bytes| {
+            let started = std::time::Instant::now();
+            let result = crate::input::FileReader::decode_text(
+                name,
+                &bytes,
+                self.encoding.unwrap_or_default(),
+                self.invalid_utf8.unwrap_or_default(),
+            )
+            .and_then(|decoded| {
+                let path = archive.join(name).display().to_string();
+                let document = Document {
+                    id: name,
+                    path: &path,
+                };
+                self.process_text(&document, decoded, processor, formatter, &silent, started)
+            });
+            report.record(&archive.join(name), result, self.fail_fast)
+        })
+    }
 
-        match self.format {
-            OutputFormat::Text => {
-                if let Some(output_path) = &self.output {
-                    let file = std::fs::File::create(output_path).with_context(|| {
-                        format!("Failed to create output file: {}", output_path.display())
-                    })?;
-                    Ok(Box::new(crate::output::TextFormatter::new(file)))
-                } else {
-                    Ok(Box::new(crate::output::TextFormatter::new(io::stdout())))
-                }
+    /// Process each input file into its own output file in `dir`, then keep
+    /// doing so for changed files in watch mode
+    fn run_per_file(&self, processor: &Segmenter, dir: &Path) -> Result<()> {
+        if self.input.iter().any(|input| input == "-") {
+            anyhow::bail!("--output-dir needs input files, not stdin");
+        }
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+
+        let files = crate::input::resolve_patterns(&self.input)?;
+        if let Some(archive) = files.iter().find(|f| ArchiveKind::from_path(f).is_some()) {
+            anyhow::bail!(
+                "--output-dir does not support archive inputs: {}",
+                archive.display()
+            );
+        }
+        self.check_output_names(&files, dir)?;
+        log::info!("Found {} files to process", files.len());
+        let mut progress = crate::progress::ProgressReporter::new(self.quiet);
+        match files.as_slice() {
+            [file] => progress.init_bytes(&file.display().to_string()),
+            _ => progress.init_files(files.len() as u64),
+        }
+        let mut report = BatchReport::default();
+        for file in &files {
+            let result = self.process_file_to_dir(file, processor, dir, &progress);
+            report.record(file, result, self.fail_fast)?;
+            progress.file_completed(&file.file_name().unwrap_or_default().to_string_lossy());
+        }
+        progress.finish();
+
+        if self.watch {
+            report.summarize(self.error_report.as_deref())?;
+            self.watch(processor, dir)?;
+        }
+        report.finish(self.error_report.as_deref())
+    }
+
+    /// The output file of `file` in `dir`
+    fn output_path(&self, file: &Path, dir: &Path) -> PathBuf {
+        dir.join(output_file_name(
+            file,
+            self.format.unwrap_or_default(),
+            self.output_compression.unwrap_or_default(),
+        ))
+    }
+
+    /// Refuse inputs whose output files would overwrite an input or each
+    /// other, before any output file is created
+    fn check_output_names(&self, files: &[PathBuf], dir: &Path) -> Result<()> {
+        let inputs: std::collections::HashSet<PathBuf> =
+            files.iter().filter_map(|f| f.canonicalize().ok()).collect();
+        let mut outputs = std::collections::HashMap::new();
+        for file in files {
+            let output = self.output_path(file, dir);
+            if output
+                .canonicalize()
+                .is_ok_and(|output| inputs.contains(&output))
+            {
+                anyhow::bail!(
+                    "--output-dir would overwrite the input {} with the output of {}",
+                    output.display(),
+                    file.display()
+                );
             }
-            OutputFormat::Json => {
-                if let Some(output_path) = &self.output {
-                    let file = std::fs::File::create(output_path).with_context(|| {
-                        format!("Failed to create output file: {}", output_path.display())
-                    })?;
-                    Ok(Box::new(crate::output::JsonFormatter::new(file)))
-                } else {
-                    Ok(Box::new(crate::output::JsonFormatter::new(io::stdout())))
+            if let Some(previous) = outputs.insert(output.clone(), file) {
+                anyhow::bail!(
+                    "{} and {} would both be written to {}",
+                    previous.display(),
+                    file.display(),
+                    output.display()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Process `file` into its output file in `dir`
+    fn process_file_to_dir(
+        &self,
+        file: &Path,
+        processor: &Segmenter,
+        dir: &Path,
+        progress: &crate::progress::ProgressReporter,
+    ) -> Result<()> {
+        let output = self.output_path(file, dir);
+        // Checked again for files that appear while watching
+        if output.canonicalize().ok() == Some(file.canonicalize()?) {
+            anyhow::bail!("--output-dir would overwrite the input {}", file.display());
+        }
+        let mut formatter = self.create_formatter(Some(&output))?;
+        let result = self
+            .process_file(file, processor, &mut formatter, progress)
+            .and_then(|()| formatter.finish());
+        if result.is_err() {
+            // Leave no partial output behind
+            drop(formatter);
+            let _ = std::fs::remove_file(&output);
+        }
+        result
+    }
+
+    /// Reprocess input files as they change until interrupted; an external
+    /// language configuration is reloaded when it changes, and its new
+    /// rules apply to the files reprocessed after that
+    fn watch(&self, processor: &Segmenter, dir: &Path) -> Result<()> {
+        use notify::{EventKind, RecursiveMode, Watcher};
+        use std::collections::BTreeSet;
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        // Watch the directories holding the inputs, so that files replaced
+        // by renaming (as editors save them) are still seen
+        let files = crate::input::resolve_patterns(&self.input)?;
+        let watched: BTreeSet<PathBuf> = files
+            .iter()
+            .filter_map(|file| file.canonicalize().ok()?.parent().map(Path::to_path_buf))
+            .collect();
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx).context("Failed to start watching")?;
+        for path in &watched {
+            watcher
+                .watch(path, RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch {}", path.display()))?;
+        }
+        let output_dir = dir.canonicalize()?;
+        if let Some(path) = &self.language_config {
+            let handle = processor.processor.clone();
+            let reloaded = path.display().to_string();
+            crate::language_source::watch_language_file(
+                path,
+                self.language_code.clone(),
+                move |language| match handle.reload(&language) {
+                    Ok(()) => log::info!("Reloaded language configuration {reloaded}"),
+                    Err(e) => log::error!("{reloaded}: {e}; keeping the previous rules"),
+                },
+            )?;
+        }
+        if !self.quiet {
+            eprintln!(
+                "Watching {} files for changes (Ctrl-C to stop)",
+                files.len()
+            );
+        }
+
+        let silent = crate::progress::ProgressReporter::new(true);
+        let mut changed = BTreeSet::new();
+        loop {
+            // Wait for a change, then collect the burst of events a save
+            // produces before reprocessing
+            let mut event = rx.recv().context("File watcher stopped")?;
+            loop {
+                match event {
+                    Ok(event) => {
+                        if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                            changed.extend(event.paths);
+                        }
+                    }
+                    Err(e) => log::warn!("Watch error: {e}"),
+                }
+                match rx.recv_timeout(Duration::from_millis(100)) {
+                    Ok(next) => event = next,
+                    Err(_) => break,
                 }
             }
-            OutputFormat::Markdown => {
-                if let Some(output_path) = &self.output {
-                    let file = std::fs::File::create(output_path).with_context(|| {
-                        format!("Failed to create output file: {}", output_path.display())
-                    })?;
-                    Ok(Box::new(crate::output::MarkdownFormatter::new(file)))
-                } else {
-                    Ok(Box::new(
-                        crate::output::MarkdownFormatter::new(io::stdout()),
-                    ))
+
+            // Patterns are resolved again so that new matching files count
+            let inputs = crate::input::resolve_patterns(&self.input).unwrap_or_default();
+            for file in inputs {
+                let Ok(path) = file.canonicalize() else {
+                    continue;
+                };
+                if !changed.contains(&path) || path.starts_with(&output_dir) {
+                    continue;
+                }
+                match self.process_file_to_dir(&file, processor, dir, &silent) {
+                    Ok(()) => log::info!("Reprocessed {}", file.display()),
+                    Err(e) => log::error!("Failed to reprocess {}: {e:#}", file.display()),
                 }
             }
+            changed.clear();
         }
     }
 
-    /// Create text processor with appropriate language rules
-    fn create_processor(&self) -> Result<sakurs_core::SentenceProcessor> {
-        use crate::language_source::LanguageSource;
-        use sakurs_core::{Config, SentenceProcessor};
+    /// Initialize logging based on verbosity level
+    fn init_logging(&self) -> Result<()> {
+        if !self.quiet {
+            crate::logging::init(self.verbose, self.log_format.unwrap_or_default());
+        }
 
-        // Determine language source
-        let language_source = match (&self.language, &self.language_config) {
-            (Some(lang), None) => LanguageSource::BuiltIn(*lang),
-            (None, Some(path)) => LanguageSource::External {
-                path: path.clone(),
-                language_code: self.language_code.clone(),
-            },
-            (None, None) => LanguageSource::BuiltIn(Language::English), // Default
-            (Some(_), Some(_)) => unreachable!(),                       // clap handles conflicts
-        };
+        Ok(())
+    }
 
-        log::info!("Using language source: {}", language_source.display_name());
+    /// Create appropriate output formatter based on format option, writing
+    /// to `output` (default: stdout)
+    fn create_formatter(
+        &self,
+        output: Option<&Path>,
+    ) -> Result<Box<dyn crate::output::OutputFormatter>> {
+        use std::io::{self, IsTerminal};
+
+        let format = self.format.unwrap_or_default();
+        let compression = self
+            .output_compression
+            .or(output.map(Compression::from_extension))
+            .unwrap_or_default();
+        if matches!(format, OutputFormat::Arrow | OutputFormat::Parquet) {
+            if compression != Compression::None {
+                anyhow::bail!("--output-compression does not apply to {format:?} output");
+            }
+            return self.create_columnar_formatter(output);
+        }
 
-        // Create processor based on language source
-        match language_source {
-            LanguageSource::BuiltIn(lang) => {
-                let language_code = lang.code();
+        if self.docs == DocsMode::Nul
+            && matches!(format, OutputFormat::Json | OutputFormat::Markdown)
+        {
+            anyhow::bail!(
+                "--docs nul needs an output format that separates documents: text, jsonl, arrow or parquet"
+            );
+        }
 
-                // Build configuration with thread option handling
-                let builder = Config::builder()
-                    .language(language_code)
-                    .map_err(|e| anyhow::anyhow!("Failed to set language: {e}"))?;
+        let writer = match output {
+            Some(output_path) => {
+                let file = std::fs::File::create(output_path).with_context(|| {
+                    format!("Failed to create output file: {}", output_path.display())
+                })?;
+                compression.writer(file)?
+            }
+            None if compression != Compression::None && io::stdout().is_terminal() => {
+                anyhow::bail!("Refusing to write compressed output to a terminal; use --output or redirect stdout")
+            }
+            None => compression.writer(io::stdout())?,
+        };
+        Ok(match format {
+            OutputFormat::Json => Box::new(crate::output::JsonFormatter::new(writer)),
+            OutputFormat::Jsonl => Box::new(crate::output::JsonlFormatter::new(writer)),
+            OutputFormat::Markdown => Box::new(crate::output::MarkdownFormatter::new(writer)),
+            _ if self.docs == DocsMode::Nul => {
+                Box::new(crate::output::TextFormatter::new(writer).with_document_terminator(0))
+            }
+            _ => Box::new(crate::output::TextFormatter::new(writer)),
+        })
+    }
 
-                let builder = self.configure_builder(builder)?;
+    /// Create the Arrow IPC or Parquet formatter
+    #[cfg(feature = "arrow")]
+    fn create_columnar_formatter(
+        &self,
+        output: Option<&Path>,
+    ) -> Result<Box<dyn crate::output::OutputFormatter>> {
+        use crate::output::{ArrowContainer, ArrowFormatter};
+        use std::io::{self, IsTerminal};
+
+        let container = match self.format.unwrap_or_default() {
+            OutputFormat::Parquet => ArrowContainer::Parquet,
+            _ => ArrowContainer::Ipc,
+        };
+        if let Some(output_path) = output {
+            let file = std::fs::File::create(output_path).with_context(|| {
+                format!("Failed to create output file: {}", output_path.display())
+            })?;
+            Ok(Box::new(ArrowFormatter::new(
+                io::BufWriter::new(file),
+                container,
+            )))
+        } else if io::stdout().is_terminal() {
+            anyhow::bail!("Refusing to write binary {container:?} output to a terminal; use --output or redirect stdout")
+        } else {
+            Ok(Box::new(ArrowFormatter::new(io::stdout(), container)))
+        }
+    }
 
-                let config = builder
-                    .build()
-                    .map_err(|e| anyhow::anyhow!("Failed to build processor config: {e}"))?;
+    #[cfg(not(feature = "arrow"))]
+    fn create_columnar_formatter(
+        &self,
+        _output: Option<&Path>,
+    ) -> Result<Box<dyn crate::output::OutputFormatter>> {
+        anyhow::bail!("sakurs was built without Arrow support (the `arrow` feature)")
+    }
 
-                SentenceProcessor::with_config(config)
-                    .map_err(|e| anyhow::anyhow!("Failed to create processor: {e}"))
+    /// Create the processor with the corrections of `--corrections`
+    fn create_segmenter(&self) -> Result<Segmenter> {
+        let corrections = match &self.corrections {
+            Some(path) => {
+                let corrections = sakurs_core::Corrections::from_file(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to load corrections: {e}"))?;
+                log::info!("Loaded {} boundary corrections", corrections.len());
+                corrections
             }
-            LanguageSource::External {
-                path,
-                language_code,
-            } => {
-                // Load external configuration
-                use sakurs_core::LanguageConfig;
+            None => sakurs_core::Corrections::new(),
+        };
+        let fingerprints = match &self.fingerprint {
+            Some(path) => {
+                let file = std::fs::File::create(path).with_context(|| {
+                    format!("Failed to create fingerprint file: {}", path.display())
+                })?;
+                Some(std::sync::Arc::new(std::sync::Mutex::new(
+                    std::io::LineWriter::new(file),
+                )))
+            }
+            None => None,
+        };
+        Ok(Segmenter {
+            processor: sakurs_core::ProcessorHandle::new(self.create_processor()?),
+            corrections: std::sync::Arc::new(corrections),
+            fingerprints,
+            dedupe: self.dedupe,
+            seen: Default::default(),
+        })
+    }
 
-                let language = LanguageConfig::from_file(&path, language_code.as_deref())
-                    .map_err(|e| anyhow::anyhow!("Failed to load external language config: {e}"))?;
+    /// Create text processor with appropriate language rules
+    fn create_processor(&self) -> Result<sakurs_core::SentenceProcessor> {
+        let language_source = self.language_source();
+        log::info!("Using language source: {}", language_source.display_name());
 
-                // Build configuration
-                let builder = Config::builder();
-                let builder = self.configure_builder(builder)?;
+        language_source.processor(self.configure_builder(sakurs_core::Config::builder())?)
+    }
 
-                let config = builder
-                    .build()
-                    .map_err(|e| anyhow::anyhow!("Failed to build processor config: {e}"))?;
+    /// Print the configuration the processor would be built with
+    fn dump_config(&self) -> Result<()> {
+        use crate::language_source::LanguageSource;
 
-                // Create processor with the custom language configuration
-                SentenceProcessor::with_language_config(config, &language)
-                    .map_err(|e| anyhow::anyhow!("Failed to create processor: {e}"))
-            }
+        let language_source = self.language_source();
+        let config =
+            language_source.config(self.configure_builder(sakurs_core::Config::builder())?)?;
+        if let LanguageSource::External { path, .. } = &language_source {
+            println!(
+                "# Language rules from {}; `language` does not apply",
+                path.display()
+            );
+        }
+        print!("{}", config.to_toml());
+        Ok(())
+    }
+
+    /// The language rules selected by the options
+    fn language_source(&self) -> crate::language_source::LanguageSource {
+        use crate::language_source::LanguageSource;
+
+        match (&self.language, &self.language_config) {
+            (Some(lang), None) => LanguageSource::BuiltIn(*lang),
+            (None, Some(path)) => LanguageSource::External {
+                path: path.clone(),
+                language_code: self.language_code.clone(),
+            },
+            (None, None) => LanguageSource::BuiltIn(Language::English), // Default
+            (Some(_), Some(_)) => unreachable!(),                       // clap handles conflicts
         }
     }
 
@@ -311,6 +1174,24 @@ impl ProcessArgs {
             builder = builder.threads(None); // Use all available threads
         }
 
+        if let Some(kb) = self.adaptive_threshold_kb {
+            if kb == 0 {
+                return Err(anyhow::anyhow!("Adaptive threshold must be greater than 0"));
+            }
+            builder = builder.adaptive_threshold(kb * 1024);
+        }
+        if let Some(profile) = self.perf_profile {
+            builder = builder.perf_profile(profile);
+        }
+        if let Some(count) = self.max_threads {
+            if count == 0 {
+                return Err(anyhow::anyhow!(
+                    "Maximum thread count must be greater than 0"
+                ));
+            }
+            builder = builder.max_threads(count);
+        }
+
         // Handle chunk size if specified
         if let Some(chunk_kb) = self.chunk_kb {
             if chunk_kb == 0 {
@@ -321,6 +1202,71 @@ impl ProcessArgs {
             builder = builder.chunk_size(chunk_size);
         }
 
+        if let Some(dir) = &self.chunk_cache {
+            let cache = sakurs_core::ChunkCache::on_disk(dir, CHUNK_CACHE_ENTRIES)
+                .with_context(|| format!("Failed to create chunk cache {}", dir.display()))?;
+            builder = builder.chunk_cache(cache);
+        }
+
+        // Input is decoded before it reaches the processor; the policy is
+        // recorded for --dump-config
+        builder = builder.invalid_utf8(self.invalid_utf8.unwrap_or_default());
+        if self.strip_ruby {
+            builder = builder.strip_ruby(true);
+        }
+        builder = builder.ellipsis(self.ellipsis.unwrap_or_default());
+        for regex in &self.ellipsis_exception {
+            builder = builder.ellipsis_exception(regex, false);
+        }
+        for regex in &self.ellipsis_boundary {
+            builder = builder.ellipsis_exception(regex, true);
+        }
+        if self.emoji_terminators {
+            builder = builder.emoji_terminators(true);
+        }
+        if let Some(profile) = self.profile {
+            builder = builder.profile(profile);
+        }
+        if let Some(chars) = self.min_sentence_chars {
+            builder = builder.min_sentence_chars(chars);
+        }
+        if let Some(chars) = self.max_sentence_chars {
+            builder = builder.max_sentence_chars(chars);
+        }
+        if let Some(bytes) = self.force_split_bytes {
+            builder = builder.force_split_bytes(bytes);
+        }
+        if let Some(chars) = self.long_sentence_chars {
+            builder = builder.long_sentence_chars(chars);
+        }
+        if self.enclosure_warnings {
+            builder = builder.enclosure_warnings(true);
+        }
+        if self.close_enclosures_at_paragraphs {
+            builder = builder.close_enclosures_at_paragraphs(true);
+        }
+        if let Some(depth) = self.max_enclosure_depth {
+            builder = builder.max_enclosure_depth(depth, self.depth_overflow.unwrap_or_default());
+        }
+        if self.infer_quote_direction {
+            builder = builder.infer_quote_direction(true);
+        }
+        for spec in &self.enclosure_boundaries {
+            let (ch, policy) = spec
+                .split_once('=')
+                .and_then(|(ch, policy)| {
+                    let mut chars = ch.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(ch), None) => Some((ch, policy)),
+                        _ => None,
+                    }
+                })
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Enclosure boundaries must be CHAR=POLICY, got: {spec}")
+                })?;
+            builder = builder.enclosure_boundaries(ch, policy.parse()?);
+        }
+
         // Note: adaptive mode now uses default configuration
         Ok(builder)
     }
@@ -329,72 +1275,412 @@ impl ProcessArgs {
     fn process_file_streaming(
         &self,
         file: &std::path::Path,
-        processor: &sakurs_core::SentenceProcessor,
+        processor: &Segmenter,
         formatter: &mut Box<dyn crate::output::OutputFormatter>,
+        progress: &crate::progress::ProgressReporter,
+        started: std::time::Instant,
     ) -> Result<()> {
         // For now, streaming mode uses the same processing as regular mode
         // but could be enhanced in the future to process chunks incrementally
         log::info!("Using streaming mode for large file: {}", file.display());
 
-        let content = crate::input::FileReader::read_text(file)?;
-        let result = processor
-            .process(sakurs_core::Input::from_text(content.clone()))
-            .map_err(|e| anyhow::anyhow!("Processing failed: {e}"))?;
+        let decoded = crate::input::FileReader::read_text_with_encoding(
+            file,
+            self.encoding.unwrap_or_default(),
+            self.invalid_utf8.unwrap_or_default(),
+        )?;
+        let path = file.display().to_string();
+        let result = processor.process(&path, &decoded, |p| progress.bytes_processed(p))?;
+        let content = decoded.text;
+
+        formatter.start_document(&Document {
+            id: &path,
+            path: &path,
+        })?;
+        let duplicates =
+            processor.write_sentences(&content, &result, &decoded.offsets, formatter)?;
+        log_document(&path, &content, &result, duplicates, started);
+        processor.record_fingerprint(&path, &result)
+    }
 
-        let mut last_offset = 0;
-        for boundary in &result.boundaries {
-            let sentence = &content[last_offset..boundary.offset];
-            formatter.format_sentence(sentence.trim(), last_offset)?;
-            last_offset = boundary.offset;
+    /// Process stdin
+    fn process_stdin(
+        &self,
+        processor: &Segmenter,
+        formatter: &mut Box<dyn crate::output::OutputFormatter>,
+    ) -> Result<()> {
+        let started = std::time::Instant::now();
+        let bytes = crate::compression::read_to_end(std::io::stdin().lock())
+            .context("Failed to read from stdin")?;
+        let decoded = crate::input::encoding::decode(
+            &bytes,
+            self.encoding.unwrap_or_default(),
+            self.invalid_utf8.unwrap_or_default(),
+        )
+        .context("Failed to decode stdin")?;
+        log::debug!("Decoded stdin as {}", decoded.encoding.name());
+        let buffer = &decoded.text;
+
+        if self.docs == DocsMode::Nul {
+            // A trailing NUL ends the last document rather than starting one;
+            // offsets are relative to the start of each document
+            let documents = buffer.strip_suffix('\0').unwrap_or(buffer);
+            let silent = crate::progress::ProgressReporter::new(true);
+            let mut doc_start = 0;
+            for (index, text) in documents.split('\0').enumerate() {
+                let started = std::time::Instant::now();
+                let doc_id = index.to_string();
+                let document = Document {
+                    id: &doc_id,
+                    path: "-",
+                };
+                // The document holding the first invalid sequence reports
+                // the invalid UTF-8 of all of stdin
+                let invalid_utf8 = match decoded.invalid_utf8 {
+                    Some(sakurs_core::Warning::InvalidUtf8 { offset, bytes })
+                        if (doc_start..=doc_start + text.len()).contains(&offset) =>
+                    {
+                        Some(sakurs_core::Warning::InvalidUtf8 {
+                            offset: offset - doc_start,
+                            bytes,
+                        })
+                    }
+                    _ => None,
+                };
+                let doc_text = Decoded {
+                    text: text.to_string(),
+                    encoding: decoded.encoding,
+                    invalid_utf8,
+                    offsets: decoded.offsets.rebase(doc_start),
+                };
+                self.process_text(&document, doc_text, processor, formatter, &silent, started)?;
+                doc_start += text.len() + 1;
+            }
+            return Ok(());
         }
 
-        // Don't forget the last sentence after the final boundary
-        if last_offset < content.len() {
-            let sentence = &content[last_offset..];
-            if !sentence.trim().is_empty() {
-                formatter.format_sentence(sentence.trim(), last_offset)?;
+        let result = processor.process("-", &decoded, |_| {})?;
+
+        formatter.start_document(&Document { id: "-", path: "-" })?;
+        let duplicates = processor.write_sentences(buffer, &result, &decoded.offsets, formatter)?;
+        log_document("-", buffer, &result, duplicates, started);
+        processor.record_fingerprint("-", &result)
+    }
+}
+
+/// A processor with the boundary corrections of the documents it segments
+struct Segmenter {
+    /// Swapped for one with the new rules when a watched language
+    /// configuration changes
+    processor: sakurs_core::ProcessorHandle,
+    corrections: std::sync::Arc<sakurs_core::Corrections>,
+    /// Where document fingerprints are written, with `--fingerprint`
+    fingerprints: Option<std::sync::Arc<std::sync::Mutex<std::io::LineWriter<std::fs::File>>>>,
+    /// Whether and across what repeated sentences are dropped
+    dedupe: Option<DedupeScope>,
+    /// The sentences written so far, with `--dedupe run`
+    seen: std::sync::Arc<std::sync::Mutex<sakurs_core::SentenceDeduplicator>>,
+}
+
+impl Segmenter {
+    /// Write the sentences of a processing result to `formatter`, dropping
+    /// repeated ones with `--dedupe`; returns how many were dropped
+    fn write_sentences(
+        &self,
+        text: &str,
+        result: &sakurs_core::Output,
+        offsets: &OffsetMap,
+        formatter: &mut Box<dyn crate::output::OutputFormatter>,
+    ) -> Result<usize> {
+        match self.dedupe {
+            None => output_sentences(text, result, offsets, formatter, None),
+            Some(DedupeScope::Document) => {
+                let mut seen = sakurs_core::SentenceDeduplicator::new();
+                output_sentences(text, result, offsets, formatter, Some(&mut seen))
+            }
+            Some(DedupeScope::Run) => {
+                let mut seen = self.seen.lock().expect("deduplicator lock poisoned");
+                output_sentences(text, result, offsets, formatter, Some(&mut seen))
             }
         }
+    }
 
+    /// Write the fingerprint of the document `id`'s segmentation, if
+    /// fingerprints are requested
+    fn record_fingerprint(&self, id: &str, output: &sakurs_core::Output) -> Result<()> {
+        use std::io::Write;
+
+        if let Some(fingerprints) = &self.fingerprints {
+            let mut fingerprints = fingerprints.lock().expect("fingerprint lock poisoned");
+            writeln!(fingerprints, "{:016x}  {id}", output.fingerprint())
+                .context("Failed to write fingerprint")?;
+        }
         Ok(())
     }
 
-    /// Process stdin
-    fn process_stdin(
+    /// Segment the decoded text of the document `id`, applying its
+    /// corrections, whose offsets refer to the input bytes
+    fn process(
         &self,
-        processor: &sakurs_core::SentenceProcessor,
-        formatter: &mut Box<dyn crate::output::OutputFormatter>,
-    ) -> Result<()> {
-        use std::io::Read;
+        id: &str,
+        decoded: &Decoded,
+        progress: impl Fn(sakurs_core::Progress) + Sync,
+    ) -> Result<sakurs_core::Output> {
+        let input = sakurs_core::Input::from_text(decoded.text.as_str());
+        let processor = self.processor.load();
+        let corrections = self.corrections.for_document(id);
+        let output = if corrections.is_empty() {
+            processor.process_with_progress(input, progress)
+        } else {
+            let corrections = corrections
+                .iter()
+                .map(|c| {
+                    let offset = decoded.text_offset(c.offset).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "{id}: correction offset {} is inside a character",
+                            c.offset
+                        )
+                    })?;
+                    Ok(sakurs_core::Correction { offset, ..*c })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            processor.process_with_corrections(input, &corrections)
+        };
+        let mut output = output.map_err(|e| anyhow::anyhow!("Processing failed: {e}"))?;
+        output.add_warnings(decoded.invalid_utf8);
+        for warning in &output.warnings {
+            log::warn!(
+                "{id}: {warning} at byte {}",
+                decoded.offsets.to_input(warning.offset())
+            );
+        }
+        Ok(output)
+    }
+}
 
-        let mut buffer = String::new();
-        std::io::stdin()
-            .read_to_string(&mut buffer)
-            .context("Failed to read from stdin")?;
+/// A document's decoded text with its sentence boundaries
+struct Segmented {
+    decoded: Decoded,
+    output: sakurs_core::Output,
+    started: std::time::Instant,
+}
+
+/// Segment the decoded text of the document `id` whose processing began at
+/// `started`
+fn segment(
+    id: &str,
+    decoded: Decoded,
+    processor: &Segmenter,
+    progress: &crate::progress::ProgressReporter,
+    started: std::time::Instant,
+) -> Result<Segmented> {
+    let output = processor.process(id, &decoded, |p| progress.bytes_processed(p))?;
+    Ok(Segmented {
+        decoded,
+        output,
+        started,
+    })
+}
+
+/// Write the sentences of a segmented document to `formatter`, and its
+/// fingerprint if requested
+fn write_document(
+    document: &Document<'_>,
+    segmented: Segmented,
+    processor: &Segmenter,
+    formatter: &mut Box<dyn crate::output::OutputFormatter>,
+) -> Result<()> {
+    let Segmented {
+        decoded,
+        output,
+        started,
+    } = segmented;
+    formatter.start_document(document)?;
+    let duplicates =
+        processor.write_sentences(&decoded.text, &output, &decoded.offsets, formatter)?;
+    formatter.end_document()?;
+    log_document(document.id, &decoded.text, &output, duplicates, started);
+    processor.record_fingerprint(document.id, &output)
+}
+
+/// Input bytes per thread at which a file is worth splitting across
+/// threads, as in the core's adaptive execution
+const BYTES_PER_THREAD: u64 = 256 * 1024;
+
+/// Files segmented together per job in one concurrent batch, which bounds
+/// the decoded text held before it is written
+const FILES_PER_JOB: usize = 4;
+
+/// Chunk states of a --chunk-cache held in memory in front of the
+/// directory
+const CHUNK_CACHE_ENTRIES: usize = 4096;
+
+/// A step of a multi-file run
+#[derive(Debug, PartialEq, Eq)]
+enum JobStep<'a> {
+    /// A file (or archive) processed by itself, with as many threads as its
+    /// size warrants
+    Alone(&'a Path),
+    /// Files processed concurrently, one thread each
+    Concurrent(&'a [PathBuf]),
+}
+
+/// Split `files` into steps for `jobs` concurrent jobs
+///
+/// A file that can keep every job busy by itself (`jobs` × 256 KiB) is
+/// processed alone with parallelism inside it, as are archives and files
+/// whose `size` is unknown; runs of smaller files are processed `jobs` at a
+/// time. With one job every file is processed alone, in order.
+fn plan_jobs(
+    files: &[PathBuf],
+    jobs: usize,
+    size: impl Fn(&Path) -> Option<u64>,
+) -> Vec<JobStep<'_>> {
+    let large = BYTES_PER_THREAD * jobs as u64;
+    let alone = |file: &Path| {
+        jobs == 1
+            || ArchiveKind::from_path(file).is_some()
+            || size(file).map_or(true, |size| size >= large)
+    };
+    let mut steps = Vec::new();
+    let mut batch_start = 0;
+    for (i, file) in files.iter().enumerate() {
+        let is_alone = alone(file);
+        if i > batch_start && (is_alone || i - batch_start == jobs * FILES_PER_JOB) {
+            steps.push(JobStep::Concurrent(&files[batch_start..i]));
+            batch_start = i;
+        }
+        if is_alone {
+            steps.push(JobStep::Alone(file));
+            batch_start = i + 1;
+        }
+    }
+    if batch_start < files.len() {
+        steps.push(JobStep::Concurrent(&files[batch_start..]));
+    }
+    steps
+}
 
-        let result = processor
-            .process(sakurs_core::Input::from_text(buffer.clone()))
-            .map_err(|e| anyhow::anyhow!("Processing failed: {e}"))?;
+/// Exit status when some input files failed and the rest were processed
+pub const PARTIAL_FAILURE_EXIT_CODE: i32 = 3;
 
-        let mut last_offset = 0;
-        for boundary in &result.boundaries {
-            let sentence = &buffer[last_offset..boundary.offset];
-            formatter.format_sentence(sentence.trim(), last_offset)?;
-            last_offset = boundary.offset;
+/// An input file that could not be processed
+#[derive(Debug, serde::Serialize)]
+struct FileFailure {
+    file: PathBuf,
+    error: String,
+}
+
+/// Outcome of a multi-file run, as written by `--error-report`
+#[derive(Debug, Default, serde::Serialize)]
+struct BatchReport {
+    processed: usize,
+    failed: Vec<FileFailure>,
+}
+
+impl BatchReport {
+    /// Record the result for `file`; a failure is logged and kept unless
+    /// `fail_fast` is set, in which case it is returned
+    fn record(&mut self, file: &Path, result: Result<()>, fail_fast: bool) -> Result<()> {
+        match result {
+            Ok(()) => self.processed += 1,
+            Err(e) if fail_fast => return Err(e),
+            Err(e) => {
+                log::error!(file:% = file.display(); "Failed to process {}: {e:#}", file.display());
+                self.failed.push(FileFailure {
+                    file: file.to_path_buf(),
+                    error: format!("{e:#}"),
+                });
+            }
         }
+        Ok(())
+    }
 
-        // Don't forget the last sentence after the final boundary
-        if last_offset < buffer.len() {
-            let sentence = &buffer[last_offset..];
-            if !sentence.trim().is_empty() {
-                formatter.format_sentence(sentence.trim(), last_offset)?;
+    /// Print a summary of the failures and write the report, if requested
+    fn summarize(&self, report_path: Option<&Path>) -> Result<()> {
+        if let Some(path) = report_path {
+            let json = serde_json::to_string_pretty(self)?;
+            std::fs::write(path, json + "\n")
+                .with_context(|| format!("Failed to write error report: {}", path.display()))?;
+        }
+        if !self.failed.is_empty() {
+            eprintln!(
+                "Failed to process {} of {} files:",
+                self.failed.len(),
+                self.processed + self.failed.len()
+            );
+            for failure in &self.failed {
+                eprintln!("  {}: {}", failure.file.display(), failure.error);
             }
         }
+        Ok(())
+    }
 
+    /// Summarize the run, then exit with [`PARTIAL_FAILURE_EXIT_CODE`] if any
+    /// file failed
+    fn finish(&self, report_path: Option<&Path>) -> Result<()> {
+        self.summarize(report_path)?;
+        if !self.failed.is_empty() {
+            std::process::exit(PARTIAL_FAILURE_EXIT_CODE);
+        }
         Ok(())
     }
 }
 
+/// Log the per-document record, with its size, boundary count, dropped
+/// duplicate sentences and timing as fields for `--log-format json`, and
+/// at `-vv` how its thread count and chunk size were chosen
+fn log_document(
+    name: &str,
+    text: &str,
+    result: &sakurs_core::Output,
+    duplicates: usize,
+    started: std::time::Instant,
+) {
+    let ms = |d: std::time::Duration| (d.as_secs_f64() * 1e6).round() / 1e3;
+    log::info!(
+        file = name,
+        bytes = text.len(),
+        boundaries = result.boundaries.len(),
+        duplicates = duplicates,
+        duration_ms = ms(started.elapsed()),
+        segment_ms = ms(result.metadata.duration);
+        "Processed {name}: {} boundaries",
+        result.boundaries.len()
+    );
+    let execution = &result.metadata.execution;
+    log::debug!(
+        file = name,
+        threads = execution.thread_count,
+        chunk_size = execution.chunk_size,
+        bytes_per_thread = execution.bytes_per_thread;
+        "Execution of {name}: {execution}"
+    );
+}
+
+/// The name of the output file for `input` in the format's extension, plus
+/// the compression's; a compressed input's own extension is dropped
+/// (doc.txt.gz -> doc.json)
+fn output_file_name(input: &Path, format: OutputFormat, compression: Compression) -> PathBuf {
+    let extension = match format {
+        OutputFormat::Text => "txt",
+        OutputFormat::Json => "json",
+        OutputFormat::Jsonl => "jsonl",
+        OutputFormat::Markdown => "md",
+        OutputFormat::Arrow => "arrow",
+        OutputFormat::Parquet => "parquet",
+    };
+    let mut stem = Path::new(input.file_name().unwrap_or(input.as_os_str()));
+    if Compression::from_extension(stem) != Compression::None {
+        stem = Path::new(stem.file_stem().unwrap_or_default());
+    }
+    let name = PathBuf::from(stem.file_stem().unwrap_or(stem.as_os_str()));
+    match compression.extension() {
+        Some(compressed) => name.with_extension(format!("{extension}.{compressed}")),
+        None => name.with_extension(extension),
+    }
+}
+
 /// Find a safe point to split text (prefer sentence boundary, then word boundary)
 #[allow(dead_code)]
 fn find_safe_split_point(text: &str, target: usize) -> usize {
@@ -434,22 +1720,74 @@ fn find_safe_split_point(text: &str, target: usize) -> usize {
     pos
 }
 
-/// Output sentences from processing result
-#[allow(dead_code)]
+/// Output the trimmed sentences of a processing result, each with the input
+/// byte range of its trimmed text (mapped through `offsets`) and the kind of
+/// its final boundary; text after the final boundary is the last sentence.
+/// Sentences `seen` before are dropped; returns how many were
 fn output_sentences(
     text: &str,
     result: &sakurs_core::Output,
+    offsets: &OffsetMap,
     formatter: &mut Box<dyn crate::output::OutputFormatter>,
-    base_offset: usize,
-) -> Result<()> {
+    mut seen: Option<&mut sakurs_core::SentenceDeduplicator>,
+) -> Result<usize> {
+    let mut dropped = 0;
     let mut last_offset = 0;
-    for boundary in &result.boundaries {
-        let sentence = &text[last_offset..boundary.offset];
-        formatter.format_sentence(sentence.trim(), base_offset + last_offset)?;
-        last_offset = boundary.offset;
+    let warnings: Vec<DocumentWarning> = result
+        .warnings
+        .iter()
+        .map(|warning| DocumentWarning {
+            kind: warning.kind().to_string(),
+            offset: offsets.to_input(warning.offset()),
+            message: warning.to_string(),
+        })
+        .collect();
+    let mut next_warning = 0;
+    // Warnings not yet given to a sentence: those of skipped sentences go
+    // to the next one written, or to the last one written at the end
+    let mut unattached = 0;
+    // The last sentence kept, with the range of its warnings; held back
+    // until it is known whether it is the last
+    let mut pending: Option<(&str, usize, sakurs_core::BoundaryKind, Range<usize>)> = None;
+    let mut write = |(text, start, kind, warned): (&str, usize, _, Range<usize>)| {
+        formatter.format_sentence(&Sentence {
+            text,
+            start: offsets.to_input(start),
+            end: offsets.to_input(start + text.len()),
+            kind,
+            warnings: &warnings[warned],
+        })
+    };
+    let ends = result.boundaries.iter().map(|b| (b.offset, b.kind));
+    let text_end = (text.len(), sakurs_core::BoundaryKind::EndOfText);
+    for (end, kind) in ends.chain(std::iter::once(text_end)) {
+        if end <= last_offset {
+            continue;
+        }
+        // Warnings up to the end of the text belong to its last sentence
+        next_warning += result.warnings[next_warning..]
+            .iter()
+            .take_while(|w| w.offset() < end || end == text.len())
+            .count();
+        let sentence = &text[last_offset..end];
+        let trimmed = sentence.trim();
+        if seen.as_mut().is_some_and(|seen| seen.is_duplicate(trimmed)) {
+            dropped += 1;
+        } else if !trimmed.is_empty() {
+            if let Some(sentence) = pending.take() {
+                write(sentence)?;
+            }
+            let start = last_offset + (sentence.len() - sentence.trim_start().len());
+            pending = Some((trimmed, start, kind, unattached..next_warning));
+            unattached = next_warning;
+        }
+        last_offset = end;
+    }
+    if let Some((text, start, kind, warned)) = pending {
+        write((text, start, kind, warned.start..warnings.len()))?;
     }
 
-    Ok(())
+    Ok(dropped)
 }
 
 #[cfg(test)]
@@ -578,4 +1916,52 @@ mod tests {
         assert!(text.is_char_boundary(split));
         assert!(split <= 20);
     }
+
+    #[test]
+    fn test_plan_jobs() {
+        let files: Vec<PathBuf> = ["a", "b", "big", "c", "d.tar", "e", "f", "g", "h", "i"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        let size = |file: &Path| match file.to_str() {
+            Some("big") => Some(BYTES_PER_THREAD * 2),
+            Some("g") => None,
+            _ => Some(100),
+        };
+
+        let paths = |step: &JobStep<'_>| match step {
+            JobStep::Alone(file) => vec![file.display().to_string()],
+            JobStep::Concurrent(batch) => batch.iter().map(|f| f.display().to_string()).collect(),
+        };
+        let plan = plan_jobs(&files, 2, size);
+        let kinds: Vec<_> = plan
+            .iter()
+            .map(|step| (matches!(step, JobStep::Alone(_)), paths(step).join(",")))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (false, "a,b".to_string()),
+                (true, "big".to_string()),
+                (false, "c".to_string()),
+                (true, "d.tar".to_string()),
+                (false, "e,f".to_string()),
+                (true, "g".to_string()),
+                (false, "h,i".to_string()),
+            ]
+        );
+
+        // Batches hold at most FILES_PER_JOB files per job
+        let many: Vec<PathBuf> = (0..10).map(|i| PathBuf::from(i.to_string())).collect();
+        let lens: Vec<_> = plan_jobs(&many, 2, |_| Some(1))
+            .iter()
+            .map(|step| paths(step).len())
+            .collect();
+        assert_eq!(lens, [8, 2]);
+
+        // One job processes every file alone
+        assert!(plan_jobs(&files, 1, size)
+            .iter()
+            .all(|step| matches!(step, JobStep::Alone(_))));
+    }
 }
@@ -0,0 +1,727 @@
+//! Serve command implementation: sentence splitting over HTTP
+
+use anyhow::{Context, Result};
+use clap::Args;
+use sakurs_core::{BoundaryKind, Config, LanguageConfig, ProcessorHandle, SentenceProcessor};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use super::process::Language;
+use crate::language_source::{load_language_file, watch_language_file};
+
+/// Arguments for the serve command
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Address to bind
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port to listen on (0 picks a free port)
+    #[arg(short, long, default_value = "8080")]
+    pub port: u16,
+
+    /// Default language when a request has no `language` parameter
+    #[arg(short, long, value_enum, conflicts_with = "language_config")]
+    pub language: Option<Language>,
+
+    /// Path to external language configuration file (TOML format); it
+    /// becomes the default language and is also selectable by its code.
+    /// Edits to the file are picked up without a restart
+    #[arg(short = 'c', long, value_name = "FILE", conflicts_with = "language")]
+    pub language_config: Option<PathBuf>,
+
+    /// Language code for external configuration (optional)
+    /// NOTE: Only used with --language-config
+    #[arg(long, requires = "language_config")]
+    pub language_code: Option<String>,
+
+    /// Number of request worker threads (default: number of CPUs)
+    #[arg(long, value_name = "COUNT")]
+    pub workers: Option<usize>,
+
+    /// Maximum request body size in MB
+    #[arg(long, default_value = "10", value_name = "SIZE_MB")]
+    pub max_body_mb: usize,
+
+    /// Increase verbosity
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+impl ServeArgs {
+    /// Execute the serve command
+    pub fn execute(&self) -> Result<()> {
+        crate::logging::init(self.verbose, crate::logging::LogFormat::Text);
+
+        let service = self.create_service()?;
+        #[cfg(feature = "metrics")]
+        let service = SplitService {
+            metrics: Some(install_metrics()?),
+            ..service
+        };
+        let service = Arc::new(service);
+        if let Some(path) = &self.language_config {
+            service.watch(path)?;
+        }
+        let address = format!("{}:{}", self.host, self.port);
+        let server = tiny_http::Server::http(&address)
+            .map_err(|e| anyhow::anyhow!("Failed to bind {address}: {e}"))?;
+        let bound = server
+            .server_addr()
+            .to_ip()
+            .context("Server is not bound to an IP address")?;
+        println!("Listening on http://{bound}");
+
+        let server = Arc::new(server);
+        let workers = self.workers.unwrap_or_else(num_cpus::get).max(1);
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let server = Arc::clone(&server);
+                let service = Arc::clone(&service);
+                std::thread::spawn(move || {
+                    for request in server.incoming_requests() {
+                        service.respond(request);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            let _ = handle.join();
+        }
+        Ok(())
+    }
+
+    /// Build the request handler from the command-line options
+    fn create_service(&self) -> Result<SplitService> {
+        SplitService::from_options(
+            self.language,
+            self.language_config.as_deref(),
+            self.language_code.as_deref(),
+            self.max_body_mb * 1024 * 1024,
+        )
+    }
+}
+
+/// Request body for `application/json` requests
+#[derive(Debug, Deserialize)]
+struct SplitRequest {
+    text: String,
+}
+
+/// One sentence in a `/split` response. Offsets delimit the trimmed
+/// sentence text in the request text.
+#[derive(Debug, Serialize, PartialEq)]
+struct SplitSentence {
+    /// The sentence text, without surrounding whitespace
+    text: String,
+    /// Byte offset of the sentence start
+    start: usize,
+    /// Byte offset of the sentence end (exclusive)
+    end: usize,
+    /// Character offset of the sentence start
+    char_start: usize,
+    /// Character offset of the sentence end (exclusive)
+    char_end: usize,
+    /// How the sentence ends: strong, weak, forced or end_of_text
+    kind: &'static str,
+}
+
+/// Response body for `/split`
+#[derive(Debug, Serialize)]
+struct SplitResponse {
+    language: String,
+    sentences: Vec<SplitSentence>,
+}
+
+/// Per-request processing options taken from the query string
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub(super) struct SplitOptions {
+    pub(super) threads: Option<usize>,
+    pub(super) chunk_kb: Option<usize>,
+}
+
+/// Largest chunk size a request may ask for, in KB
+const MAX_CHUNK_KB: usize = 64 * 1024;
+
+/// Most processors kept compiled at once
+const MAX_PROCESSORS: usize = 64;
+
+impl SplitOptions {
+    /// The options with the thread count capped at the available
+    /// parallelism and the chunk size at [`MAX_CHUNK_KB`]
+    fn clamped(&self) -> Self {
+        let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self {
+            threads: self.threads.map(|n| n.min(available)),
+            chunk_kb: self.chunk_kb.map(|kb| kb.min(MAX_CHUNK_KB)),
+        }
+    }
+}
+
+/// Processors by language code and options, with the order they were last
+/// used in; beyond [`MAX_PROCESSORS`] the least recently used are dropped
+#[derive(Default)]
+struct ProcessorCache {
+    entries: HashMap<(String, SplitOptions), (ProcessorHandle, u64)>,
+    /// Keys by the tick they were last used at
+    order: BTreeMap<u64, (String, SplitOptions)>,
+    tick: u64,
+}
+
+impl ProcessorCache {
+    fn get(&mut self, key: &(String, SplitOptions)) -> Option<Arc<SentenceProcessor>> {
+        let (handle, used) = self.entries.get_mut(key)?;
+        let key = self.order.remove(used)?;
+        self.tick += 1;
+        *used = self.tick;
+        self.order.insert(self.tick, key);
+        Some(handle.load())
+    }
+
+    fn insert(&mut self, key: (String, SplitOptions), handle: ProcessorHandle) {
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.insert(key.clone(), (handle, self.tick)) {
+            self.order.remove(&used);
+        }
+        self.order.insert(self.tick, key);
+        while self.entries.len() > MAX_PROCESSORS {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// The cached processors of the language `code`
+    fn of_language(&self, code: &str) -> Vec<&ProcessorHandle> {
+        self.entries
+            .iter()
+            .filter(|((cached, _), _)| cached == code)
+            .map(|(_, (handle, _))| handle)
+            .collect()
+    }
+}
+
+/// A response status and JSON body
+pub(super) type Reply = (u16, serde_json::Value);
+
+/// Request handling, independent of the transport; shared with `daemon`
+pub(super) struct SplitService {
+    default_language: String,
+    /// External language configurations by code
+    custom: RwLock<HashMap<String, LanguageConfig>>,
+    /// Compiled processors by language code and options; those of external
+    /// languages are swapped when their configuration is reloaded
+    processors: Mutex<ProcessorCache>,
+    max_body: usize,
+    /// Engine metrics rendered at `GET /metrics`
+    #[cfg(feature = "metrics")]
+    metrics: Option<metrics_exporter_prometheus::PrometheusHandle>,
+}
+
+impl SplitService {
+    pub(super) fn new(max_body: usize) -> Self {
+        Self {
+            default_language: Language::English.code().to_string(),
+            custom: RwLock::new(HashMap::new()),
+            processors: Mutex::new(ProcessorCache::default()),
+            max_body,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        }
+    }
+
+    /// Build the service from the language options shared by `serve` and
+    /// `daemon`
+    pub(super) fn from_options(
+        language: Option<Language>,
+        language_config: Option<&Path>,
+        language_code: Option<&str>,
+        max_body: usize,
+    ) -> Result<Self> {
+        let mut service = Self::new(max_body);
+        if let Some(path) = language_config {
+            let language = load_language_file(path, language_code)?;
+            let code = language.metadata.code.clone();
+            // Compile once up front so configuration errors surface at startup
+            service.processor(&code, &language, &SplitOptions::default())?;
+            service
+                .custom
+                .get_mut()
+                .unwrap()
+                .insert(code.clone(), language);
+            service.default_language = code;
+        } else if let Some(lang) = language {
+            service.default_language = lang.code().to_string();
+        }
+        log::info!("Default language: {}", service.default_language);
+        Ok(service)
+    }
+
+    /// Reload the default language's rules whenever `path` changes
+    pub(super) fn watch(self: &Arc<Self>, path: &Path) -> Result<()> {
+        // The code stays the one requests select the language by
+        let code = self.default_language.clone();
+        let reloading = Arc::clone(self);
+        watch_language_file(path, Some(code.clone()), move |language| {
+            match reloading.reload(&code, language) {
+                Ok(()) => log::info!("Reloaded language configuration for {code}"),
+                Err(e) => log::error!("{e:#}; keeping the previous rules for {code}"),
+            }
+        })
+    }
+
+    /// Serve one HTTP request
+    fn respond(&self, mut request: tiny_http::Request) {
+        let content_type = request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("Content-Type"))
+            .map(|h| h.value.as_str().to_string());
+        let method = request.method().as_str().to_string();
+        let url = request.url().to_string();
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            if method == "GET" && url.split('?').next() == Some("/metrics") {
+                log::info!("{method} {url} -> 200");
+                let header =
+                    tiny_http::Header::from_bytes("Content-Type", "text/plain; version=0.0.4")
+                        .expect("static header is valid");
+                let response =
+                    tiny_http::Response::from_string(metrics.render()).with_header(header);
+                if let Err(e) = request.respond(response) {
+                    log::warn!("Failed to send response: {e}");
+                }
+                return;
+            }
+        }
+
+        let mut body = Vec::new();
+        let read = request
+            .as_reader()
+            .take(self.max_body as u64 + 1)
+            .read_to_end(&mut body);
+        let (status, json) = match read {
+            Err(e) => error(400, format!("Failed to read request body: {e}")),
+            Ok(_) if body.len() > self.max_body => {
+                error(413, format!("Request body exceeds {} bytes", self.max_body))
+            }
+            Ok(_) => self.handle(&method, &url, content_type.as_deref(), &body),
+        };
+        log::info!("{method} {url} -> {status}");
+
+        let header = tiny_http::Header::from_bytes("Content-Type", "application/json")
+            .expect("static header is valid");
+        let response = tiny_http::Response::from_string(json.to_string())
+            .with_status_code(status)
+            .with_header(header);
+        if let Err(e) = request.respond(response) {
+            log::warn!("Failed to send response: {e}");
+        }
+    }
+
+    /// Route a request and produce the reply
+    fn handle(&self, method: &str, url: &str, content_type: Option<&str>, body: &[u8]) -> Reply {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        match (method, path) {
+            ("GET", "/health") => (200, serde_json::json!({ "status": "ok" })),
+            ("POST", "/split") => self.split(query, content_type, body),
+            (_, "/split") | (_, "/health") => error(405, format!("Method {method} not allowed")),
+            _ => error(404, format!("No route for {path}")),
+        }
+    }
+
+    /// Handle `POST /split`
+    fn split(&self, query: &str, content_type: Option<&str>, body: &[u8]) -> Reply {
+        let mut language = self.default_language.clone();
+        let mut options = SplitOptions::default();
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            let parse_count = |value: &str| -> std::result::Result<usize, Reply> {
+                match value.parse::<usize>() {
+                    Ok(n) if n > 0 => Ok(n),
+                    _ => Err(error(
+                        400,
+                        format!("{key} must be a positive integer, got {value:?}"),
+                    )),
+                }
+            };
+            match key.as_ref() {
+                "language" => language = value.into_owned(),
+                "threads" => match parse_count(&value) {
+                    Ok(n) => options.threads = Some(n),
+                    Err(reply) => return reply,
+                },
+                "chunk_kb" => match parse_count(&value) {
+                    Ok(n) => options.chunk_kb = Some(n),
+                    Err(reply) => return reply,
+                },
+                other => return error(400, format!("Unknown query parameter: {other}")),
+            }
+        }
+
+        let media_type = content_type
+            .and_then(|ct| ct.split(';').next())
+            .map(|ct| ct.trim().to_ascii_lowercase());
+        let text = match media_type.as_deref() {
+            Some("application/json") => match serde_json::from_slice::<SplitRequest>(body) {
+                Ok(request) => request.text,
+                Err(e) => return error(400, format!("Invalid JSON body: {e}")),
+            },
+            None | Some("text/plain") => match String::from_utf8(body.to_vec()) {
+                Ok(text) => text,
+                Err(e) => return error(400, format!("Body is not valid UTF-8: {e}")),
+            },
+            Some(other) => return error(415, format!("Unsupported content type: {other}")),
+        };
+
+        self.split_text(language, &options, &text)
+    }
+
+    /// Split `text`, replying with its sentences or an error; the thread
+    /// count and chunk size are clamped to what the server allows
+    pub(super) fn split_text(&self, language: String, options: &SplitOptions, text: &str) -> Reply {
+        let processor = match self.resolve(&language, &options.clamped()) {
+            Ok(processor) => processor,
+            Err(e) => return error(400, e.to_string()),
+        };
+        match processor.process(sakurs_core::Input::from_text(text)) {
+            Ok(output) => {
+                let ends: Vec<(usize, BoundaryKind)> = output
+                    .boundaries
+                    .iter()
+                    .map(|b| (b.offset, b.kind))
+                    .collect();
+                let response = SplitResponse {
+                    language,
+                    sentences: sentence_spans(text, &ends),
+                };
+                (
+                    200,
+                    serde_json::to_value(response).expect("response serializes"),
+                )
+            }
+            Err(e) => error(500, format!("Processing failed: {e}")),
+        }
+    }
+
+    /// The language of requests that name none
+    pub(super) fn default_language(&self) -> &str {
+        &self.default_language
+    }
+
+    /// Look up (or compile and cache) the processor for a language code
+    fn resolve(&self, language: &str, options: &SplitOptions) -> Result<Arc<SentenceProcessor>> {
+        // Held while compiling, so that a reload cannot miss the processor
+        let custom = self.custom.read().unwrap();
+        if let Some(config) = custom.get(language) {
+            return self.processor(language, config, options);
+        }
+        drop(custom);
+        let code = language.parse::<sakurs_core::Language>()?.code();
+        let key = (code.to_string(), options.clone());
+        if let Some(processor) = self.processors.lock().unwrap().get(&key) {
+            return Ok(processor);
+        }
+        let builder = Config::builder()
+            .language(code)
+            .map_err(|e| anyhow::anyhow!("Failed to set language: {e}"))?;
+        let config = apply_options(builder, options)?
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build processor config: {e}"))?;
+        let processor = ProcessorHandle::new(
+            SentenceProcessor::with_config(config)
+                .map_err(|e| anyhow::anyhow!("Failed to create processor: {e}"))?,
+        );
+        let current = processor.load();
+        self.processors.lock().unwrap().insert(key, processor);
+        Ok(current)
+    }
+
+    /// Look up (or compile and cache) the processor for an external
+    /// language configuration
+    fn processor(
+        &self,
+        code: &str,
+        language: &LanguageConfig,
+        options: &SplitOptions,
+    ) -> Result<Arc<SentenceProcessor>> {
+        let key = (code.to_string(), options.clone());
+        if let Some(processor) = self.processors.lock().unwrap().get(&key) {
+            return Ok(processor);
+        }
+        let config = apply_options(Config::builder(), options)?
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build processor config: {e}"))?;
+        let processor = ProcessorHandle::new(
+            SentenceProcessor::with_language_config(config, language)
+                .map_err(|e| anyhow::anyhow!("Failed to create processor: {e}"))?,
+        );
+        let current = processor.load();
+        self.processors.lock().unwrap().insert(key, processor);
+        Ok(current)
+    }
+
+    /// Swap in new rules for the external language `code`: requests being
+    /// processed finish with the previous rules, later ones use the new
+    ///
+    /// Every cached processor of the language is compiled anew before any
+    /// is swapped, so rules that fail to compile never go live.
+    fn reload(&self, code: &str, language: LanguageConfig) -> Result<()> {
+        let mut custom = self.custom.write().unwrap();
+        let processors = self.processors.lock().unwrap();
+        let handles = processors.of_language(code);
+        let compiled = handles
+            .iter()
+            .map(|handle| {
+                SentenceProcessor::with_language_config(handle.load().config().clone(), &language)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Failed to create processor: {e}"))?;
+        for (handle, processor) in handles.into_iter().zip(compiled) {
+            handle.store(processor);
+        }
+        custom.insert(code.to_string(), language);
+        Ok(())
+    }
+}
+
+/// Apply per-request options to a config builder
+fn apply_options(
+    builder: sakurs_core::ConfigBuilder,
+    options: &SplitOptions,
+) -> Result<sakurs_core::ConfigBuilder> {
+    let mut builder = builder;
+    if let Some(threads) = options.threads {
+        builder = builder.threads(Some(threads));
+    }
+    if let Some(chunk_kb) = options.chunk_kb {
+        let bytes = chunk_kb
+            .checked_mul(1024)
+            .with_context(|| format!("chunk_kb {chunk_kb} is too large"))?;
+        builder = builder.chunk_size(bytes);
+    }
+    Ok(builder)
+}
+
+/// Install the Prometheus recorder of the engine metrics, with a thread
+/// that keeps its histograms bounded
+#[cfg(feature = "metrics")]
+fn install_metrics() -> Result<metrics_exporter_prometheus::PrometheusHandle> {
+    let handle = metrics_exporter_prometheus::PrometheusBuilder::new()
+        .install_recorder()
+        .context("Failed to install the metrics recorder")?;
+    let upkeep = handle.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(5));
+        upkeep.run_upkeep();
+    });
+    Ok(handle)
+}
+
+/// An error reply
+pub(super) fn error(status: u16, message: String) -> Reply {
+    (status, serde_json::json!({ "error": message }))
+}
+
+/// Cut `text` at the boundary offsets into trimmed sentence spans, each
+/// with the kind of the boundary ending it; trailing text after the last
+/// boundary is the final sentence
+fn sentence_spans(text: &str, boundaries: &[(usize, BoundaryKind)]) -> Vec<SplitSentence> {
+    let mut spans = Vec::with_capacity(boundaries.len() + 1);
+    let mut start = 0;
+    let mut chars = 0;
+    for (end, kind) in boundaries
+        .iter()
+        .copied()
+        .chain(std::iter::once((text.len(), BoundaryKind::EndOfText)))
+    {
+        if end < start {
+            continue;
+        }
+        let segment = &text[start..end];
+        let leading = segment.len() - segment.trim_start().len();
+        let trimmed = segment.trim();
+        if !trimmed.is_empty() {
+            let char_start = chars + segment[..leading].chars().count();
+            let char_end = char_start + trimmed.chars().count();
+            spans.push(SplitSentence {
+                text: trimmed.to_string(),
+                start: start + leading,
+                end: start + leading + trimmed.len(),
+                char_start,
+                char_end,
+                kind: kind.as_str(),
+            });
+        }
+        chars += segment.chars().count();
+        start = end;
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> SplitService {
+        SplitService::new(1024)
+    }
+
+    #[test]
+    fn test_split_plain_text() {
+        let (status, body) = service().handle(
+            "POST",
+            "/split",
+            Some("text/plain; charset=utf-8"),
+            "Hello world. This is a test.".as_bytes(),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["language"], "en");
+        let sentences = body["sentences"].as_array().unwrap();
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(sentences[1]["text"], "This is a test.");
+        assert_eq!(sentences[1]["start"], 13);
+        assert_eq!(sentences[1]["end"], 28);
+    }
+
+    #[test]
+    fn test_split_json_with_language_query() {
+        let (status, body) = service().handle(
+            "POST",
+            "/split?language=ja",
+            Some("application/json"),
+            r#"{"text": "こんにちは。元気ですか？"}"#.as_bytes(),
+        );
+        assert_eq!(status, 200);
+        let sentences = body["sentences"].as_array().unwrap();
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(sentences[1]["char_start"], 6);
+        assert_eq!(sentences[1]["char_end"], 12);
+        assert_eq!(sentences[1]["start"], 18);
+    }
+
+    #[test]
+    fn test_split_rejects_bad_requests() {
+        let s = service();
+        assert_eq!(s.handle("POST", "/split?language=xx", None, b"Hi.").0, 400);
+        assert_eq!(s.handle("POST", "/split?threads=0", None, b"Hi.").0, 400);
+        assert_eq!(s.handle("POST", "/split?bogus=1", None, b"Hi.").0, 400);
+        assert_eq!(
+            s.handle("POST", "/split", Some("application/json"), b"{").0,
+            400
+        );
+        assert_eq!(s.handle("POST", "/split", Some("image/png"), b"").0, 415);
+        assert_eq!(s.handle("GET", "/split", None, b"").0, 405);
+        assert_eq!(s.handle("GET", "/nope", None, b"").0, 404);
+        assert_eq!(s.handle("GET", "/health", None, b"").0, 200);
+    }
+
+    #[test]
+    fn test_processors_are_cached() {
+        let s = service();
+        let options = SplitOptions::default();
+        let a = s.resolve("en", &options).unwrap();
+        let b = s.resolve("english", &options).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_request_options_are_clamped() {
+        let s = service();
+        let query = format!("/split?threads={}&chunk_kb={}", usize::MAX, usize::MAX);
+        let (status, body) = s.handle("POST", &query, None, b"Hi. Bye.");
+        assert_eq!(status, 200);
+        assert_eq!(body["sentences"].as_array().unwrap().len(), 2);
+        let clamped = SplitOptions {
+            threads: Some(usize::MAX),
+            chunk_kb: Some(usize::MAX),
+        }
+        .clamped();
+        assert_eq!(clamped.chunk_kb, Some(MAX_CHUNK_KB));
+        // The request was served by the processor of the clamped options
+        let key = ("en".to_string(), clamped);
+        assert!(s.processors.lock().unwrap().get(&key).is_some());
+    }
+
+    #[test]
+    fn test_processor_cache_is_bounded() {
+        let s = service();
+        let options = |kb| SplitOptions {
+            threads: None,
+            chunk_kb: Some(kb),
+        };
+        let first = s.resolve("en", &options(1)).unwrap();
+        for kb in 2..=MAX_PROCESSORS + 1 {
+            s.resolve("en", &options(kb)).unwrap();
+            // Keep the first processor recently used
+            assert!(Arc::ptr_eq(&first, &s.resolve("en", &options(1)).unwrap()));
+        }
+        assert_eq!(s.processors.lock().unwrap().entries.len(), MAX_PROCESSORS);
+        // The least recently used, chunk_kb=2, was dropped
+        assert!(s
+            .processors
+            .lock()
+            .unwrap()
+            .get(&("en".to_string(), options(2)))
+            .is_none());
+    }
+
+    #[test]
+    fn test_reload_swaps_custom_rules() {
+        use sakurs_core::api::language_config::RegexPattern;
+
+        let s = service();
+        let mut rules = LanguageConfig::embedded()
+            .into_iter()
+            .find(|config| config.metadata.code == "en")
+            .unwrap()
+            .clone();
+        rules.metadata.code = "custom".to_string();
+        s.custom
+            .write()
+            .unwrap()
+            .insert("custom".to_string(), rules.clone());
+        let count = |s: &SplitService, query: &str| {
+            let url = format!("/split?language=custom{query}");
+            let (_, body) = s.handle("POST", &url, None, b"Wow! Done.");
+            body["sentences"].as_array().unwrap().len()
+        };
+        assert_eq!(count(&s, ""), 2);
+        assert_eq!(count(&s, "&threads=2"), 2);
+        let before = s.resolve("custom", &SplitOptions::default()).unwrap();
+
+        rules.terminators.chars.retain(|&c| c != '!');
+        s.reload("custom", rules.clone()).unwrap();
+        assert_eq!(count(&s, ""), 1);
+        assert_eq!(count(&s, "&threads=2"), 1);
+        assert_eq!(count(&s, "&chunk_kb=4"), 1);
+        // Requests holding the previous processor keep its rules
+        let output = before
+            .process(sakurs_core::Input::from_text("Wow! Done."))
+            .unwrap();
+        assert_eq!(output.boundaries.len(), 2);
+
+        // Rules that fail to compile are rejected
+        rules.suppression.regex_patterns.push(RegexPattern {
+            pattern: "(".to_string(),
+            description: None,
+        });
+        assert!(s.reload("custom", rules).is_err());
+        assert_eq!(count(&s, ""), 1);
+    }
+
+    #[test]
+    fn test_sentence_spans_trim_and_keep_trailing_text() {
+        let text = "  One.  Two";
+        let spans = sentence_spans(text, &[(6, BoundaryKind::Weak)]);
+        assert_eq!(spans.len(), 2);
+        assert_eq!((spans[0].start, spans[0].end), (2, 6));
+        assert_eq!(spans[1].text, "Two");
+        assert_eq!((spans[0].kind, spans[1].kind), ("weak", "end_of_text"));
+        assert_eq!((spans[1].char_start, spans[1].char_end), (8, 11));
+    }
+}
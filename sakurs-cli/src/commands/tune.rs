@@ -0,0 +1,328 @@
+//! Tune command implementation: calibrate the parallelism settings on this
+//! machine
+
+use anyhow::{Context, Result};
+use clap::Args;
+use sakurs_core::{Config, Input, SentenceProcessor};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::config::{default_path, CliConfig};
+
+/// Arguments for the tune command
+#[derive(Debug, Args)]
+pub struct TuneArgs {
+    /// Configuration file to update (default: ~/.config/sakurs/config.toml)
+    #[arg(long, value_name = "FILE", env = "SAKURS_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Print the recommended settings without writing them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Size in MB of the largest text benchmarked
+    #[arg(long, default_value = "16", value_name = "MB")]
+    pub max_mb: usize,
+}
+
+/// Timed runs per measurement; the fastest counts
+const RUNS: usize = 3;
+
+/// Smallest text size benchmarked (16KB); each next size is four times larger
+const MIN_SIZE: usize = 16 * 1024;
+
+/// Fixed chunk sizes tried against adaptive chunk sizing, in KB
+const CHUNK_KB: [usize; 5] = [64, 128, 256, 512, 1024];
+
+/// How much faster a setting must be to be preferred over a cheaper one
+const MARGIN: f64 = 0.95;
+
+/// Prose benchmarked: abbreviations, numbers, quotes and parentheses keep
+/// the rules busy as in real text
+const SAMPLE: &str =
+    "Dr. Smith arrived at 3 p.m. on Jan. 5th. \"Is the report ready?\" she asked. \
+The U.S. team (led by Prof. Jones) had measured 3.14 units; nobody expected that! \
+Results were mixed... Further tests, e.g. at 2.5 GHz, are planned. ";
+
+/// Benchmark timings on this machine
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timings {
+    /// Time of the largest text per thread count
+    pub threads: Vec<(usize, Duration)>,
+    /// Per text size: time on one thread and on two
+    pub sizes: Vec<(usize, Duration, Duration)>,
+    /// Time of the largest text with adaptive chunk sizing
+    pub adaptive_chunks: Duration,
+    /// Time of the largest text per fixed chunk size in KB
+    pub chunks: Vec<(usize, Duration)>,
+}
+
+/// Settings recommended from [`Timings`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recommendation {
+    /// Text size per thread, unless more threads never pay off
+    pub adaptive_threshold_kb: Option<usize>,
+    /// Fixed chunk size, unless adaptive sizing is as fast
+    pub chunk_kb: Option<usize>,
+    /// Fewest threads within reach of the fastest run of the largest text
+    pub max_threads: usize,
+}
+
+impl Timings {
+    /// Benchmark segmentation of English prose up to `max_size` bytes
+    pub fn measure(max_size: usize) -> Result<Self> {
+        let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let largest = sample_text(max_size.max(MIN_SIZE));
+
+        let mut thread_counts: Vec<usize> = std::iter::successors(Some(1), |n| Some(n * 2))
+            .take_while(|&n| n < available)
+            .collect();
+        thread_counts.push(available);
+        let threads = thread_counts
+            .into_iter()
+            .map(|n| Ok((n, time(Config::builder().threads(Some(n)), &largest)?)))
+            .collect::<Result<_>>()?;
+
+        let sizes = std::iter::successors(Some(MIN_SIZE), |size| Some(size * 4))
+            .take_while(|&size| size <= largest.len())
+            .map(|size| {
+                let text = &largest[..floor_char_boundary(&largest, size)];
+                let sequential = time(Config::builder().threads(Some(1)), text)?;
+                let parallel = time(Config::builder().threads(Some(2)), text)?;
+                Ok((size, sequential, parallel))
+            })
+            .collect::<Result<_>>()?;
+
+        let all = || Config::builder().threads(Some(available));
+        let adaptive_chunks = time(all(), &largest)?;
+        let chunks = CHUNK_KB
+            .iter()
+            .map(|&kb| Ok((kb, time(all().chunk_size(kb * 1024), &largest)?)))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            threads,
+            sizes,
+            adaptive_chunks,
+            chunks,
+        })
+    }
+
+    /// The settings these timings call for
+    ///
+    /// Threads stop at the fewest within 5% of the fastest count. The
+    /// threshold is half the smallest size that two threads process at
+    /// least 5% faster than one, so adaptive execution starts using two
+    /// threads there. A fixed chunk size is chosen only when it beats
+    /// adaptive sizing by 5%.
+    pub fn recommend(&self) -> Recommendation {
+        let best = self
+            .threads
+            .iter()
+            .map(|&(_, t)| t)
+            .min()
+            .unwrap_or_default();
+        let max_threads = self
+            .threads
+            .iter()
+            .find(|&&(_, t)| t.as_secs_f64() * MARGIN <= best.as_secs_f64())
+            .map_or(1, |&(n, _)| n);
+
+        let adaptive_threshold_kb = (max_threads > 1)
+            .then(|| {
+                self.sizes
+                    .iter()
+                    .find(|&&(_, one, two)| two.as_secs_f64() <= one.as_secs_f64() * MARGIN)
+                    .map(|&(size, _, _)| (size / 2 / 1024).max(1))
+            })
+            .flatten();
+
+        let chunk_kb = self
+            .chunks
+            .iter()
+            .min_by_key(|&&(_, t)| t)
+            .filter(|&&(_, t)| t.as_secs_f64() <= self.adaptive_chunks.as_secs_f64() * MARGIN)
+            .map(|&(kb, _)| kb);
+
+        Recommendation {
+            adaptive_threshold_kb,
+            chunk_kb,
+            max_threads,
+        }
+    }
+}
+
+impl TuneArgs {
+    /// Execute the tune command
+    pub fn execute(&self) -> Result<()> {
+        let path = self
+            .config
+            .clone()
+            .or_else(default_path)
+            .context("Cannot locate the config directory; pass --config")?;
+
+        eprintln!("Benchmarking (up to {}MB of text)...", self.max_mb);
+        let timings = Timings::measure(self.max_mb * 1024 * 1024)?;
+        print_timings(&timings);
+        let recommendation = timings.recommend();
+
+        let mut config = if path.is_file() {
+            CliConfig::load(&path)?
+        } else {
+            CliConfig::default()
+        };
+        let process = &mut config.process;
+        process.adaptive_threshold_kb = recommendation.adaptive_threshold_kb;
+        process.chunk_kb = recommendation.chunk_kb;
+        process.max_threads = Some(recommendation.max_threads);
+
+        println!();
+        println!("Recommended settings:");
+        println!(
+            "  adaptive_threshold_kb = {}",
+            recommendation
+                .adaptive_threshold_kb
+                .map_or("default (parallelism does not pay off)".to_string(), |kb| {
+                    kb.to_string()
+                })
+        );
+        println!(
+            "  chunk_kb = {}",
+            recommendation
+                .chunk_kb
+                .map_or("adaptive".to_string(), |kb| kb.to_string())
+        );
+        println!("  max_threads = {}", recommendation.max_threads);
+        if let Some(threads) = process.threads {
+            println!(
+                "Note: threads = {threads} in the config fixes the thread count, so the \
+                 adaptive settings only apply when it is removed"
+            );
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(&path, config.to_toml()?)
+            .with_context(|| format!("Failed to write to {}", path.display()))?;
+        println!("✓ Wrote {}", path.display());
+        Ok(())
+    }
+}
+
+/// Print the measurements as throughput in MB/s
+fn print_timings(timings: &Timings) {
+    let largest = timings.sizes.last().map_or(0, |&(size, _, _)| size);
+    let mb_per_s = |bytes: usize, time: Duration| {
+        bytes as f64 / (1024.0 * 1024.0) / time.as_secs_f64().max(1e-9)
+    };
+    println!("{:>10}  {:>12}  {:>12}", "size", "1 thread", "2 threads");
+    for &(size, one, two) in &timings.sizes {
+        println!(
+            "{:>8}KB  {:>7.1} MB/s  {:>7.1} MB/s",
+            size / 1024,
+            mb_per_s(size, one),
+            mb_per_s(size, two)
+        );
+    }
+    println!();
+    for &(threads, time) in &timings.threads {
+        println!(
+            "{threads:>3} threads: {:>7.1} MB/s",
+            mb_per_s(largest, time)
+        );
+    }
+    println!();
+    println!(
+        "adaptive chunks: {:>7.1} MB/s",
+        mb_per_s(largest, timings.adaptive_chunks)
+    );
+    for &(kb, time) in &timings.chunks {
+        println!("{kb:>6}KB chunks: {:>7.1} MB/s", mb_per_s(largest, time));
+    }
+}
+
+/// The fastest of [`RUNS`] segmentations of `text` under `builder`'s settings
+fn time(builder: sakurs_core::ConfigBuilder, text: &str) -> Result<Duration> {
+    let processor = SentenceProcessor::with_config(builder.build()?)?;
+    let mut fastest = Duration::MAX;
+    for _ in 0..RUNS {
+        let started = Instant::now();
+        processor.process(Input::from_text(text))?;
+        fastest = fastest.min(started.elapsed());
+    }
+    Ok(fastest)
+}
+
+/// [`SAMPLE`] repeated to `len` bytes
+fn sample_text(len: usize) -> String {
+    let mut text = SAMPLE.repeat(len / SAMPLE.len() + 1);
+    text.truncate(floor_char_boundary(&text, len));
+    text
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_recommend() {
+        let timings = Timings {
+            threads: vec![(1, ms(400)), (2, ms(210)), (4, ms(120)), (8, ms(118))],
+            sizes: vec![
+                (16 * 1024, ms(1), ms(2)),
+                (64 * 1024, ms(4), ms(4)),
+                (256 * 1024, ms(16), ms(10)),
+                (1024 * 1024, ms(64), ms(35)),
+            ],
+            adaptive_chunks: ms(120),
+            chunks: vec![(64, ms(140)), (256, ms(119)), (1024, ms(125))],
+        };
+        assert_eq!(
+            timings.recommend(),
+            Recommendation {
+                adaptive_threshold_kb: Some(128),
+                chunk_kb: None,
+                max_threads: 4,
+            }
+        );
+
+        // One core: parallelism never pays off
+        let single = Timings {
+            threads: vec![(1, ms(100))],
+            chunks: vec![(64, ms(90))],
+            adaptive_chunks: ms(100),
+            ..timings
+        };
+        assert_eq!(
+            single.recommend(),
+            Recommendation {
+                adaptive_threshold_kb: None,
+                chunk_kb: Some(64),
+                max_threads: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sample_text_length() {
+        assert_eq!(sample_text(1000).len(), 1000);
+        assert_eq!(sample_text(MIN_SIZE).len(), MIN_SIZE);
+    }
+}
@@ -15,6 +15,7 @@ pub struct ValidateArgs {
 impl ValidateArgs {
     /// Execute the validate command
     pub fn execute(&self) -> Result<()> {
+        use sakurs_core::api::language_config::SCHEMA_VERSION;
         use sakurs_core::{Config, LanguageConfig, SentenceProcessor};
 
         println!(
@@ -25,19 +26,42 @@ impl ValidateArgs {
         // Load and schema-validate the configuration, then compile it the
         // same way processing would (this catches rule-level problems such
         // as invalid regexes or rules exceeding the judgment window).
-        let load = || -> std::result::Result<LanguageConfig, String> {
-            let config = LanguageConfig::from_file(&self.language_config, None)
-                .map_err(|e| e.to_string())?;
+        let load = || -> std::result::Result<_, String> {
+            let (config, report) =
+                LanguageConfig::from_file_with_report(&self.language_config, None)
+                    .map_err(|e| e.to_string())?;
             SentenceProcessor::with_language_config(Config::default(), &config)
                 .map_err(|e| e.to_string())?;
-            Ok(config)
+            Ok((config, report))
         };
 
         match load() {
-            Ok(config) => {
+            // Unknown keys are ignored when processing, which is how a typo
+            // silently changes nothing
+            Ok((_, report)) if !report.unknown.is_empty() => {
+                println!("✗ Configuration is invalid!");
+                for key in &report.unknown {
+                    println!("  Unknown key: {key}");
+                }
+                Err(anyhow::anyhow!(
+                    "Validation failed: {} unknown key(s)",
+                    report.unknown.len()
+                ))
+            }
+            Ok((config, report)) => {
                 println!("✓ Configuration is valid!");
                 println!("  Language code: {}", config.metadata.code);
                 println!("  Language name: {}", config.metadata.name);
+                println!("  Schema version: {}", report.version);
+                if !report.renamed.is_empty() {
+                    println!(
+                        "  Renamed keys, migrated on load (update the file and set \
+                         schema_version = {SCHEMA_VERSION}):"
+                    );
+                    for (old, new) in &report.renamed {
+                        println!("    {old} -> {new}");
+                    }
+                }
                 Ok(())
             }
             Err(e) => {
@@ -120,4 +144,37 @@ chars = ["."]
 
         assert!(args.execute().is_err());
     }
+
+    #[test]
+    fn test_validate_rejects_unknown_keys() {
+        let toml_content = r#"
+schema_version = 1
+
+[metadata]
+code = "test"
+name = "Test Language"
+
+[terminators]
+chars = ["."]
+colapse_runs = false
+
+[ellipsis]
+patterns = []
+
+[enclosures]
+pairs = []
+
+[suppression]
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", toml_content).unwrap();
+
+        let args = ValidateArgs {
+            language_config: temp_file.path().to_path_buf(),
+        };
+
+        let error = args.execute().unwrap_err();
+        assert!(error.to_string().contains("1 unknown key"));
+    }
 }
@@ -0,0 +1,172 @@
+//! Gzip and zstd compression of inputs and outputs
+//!
+//! Compressed inputs are recognized by their magic number, whatever their
+//! name, and decompressed while they are read. Outputs are compressed when
+//! `--output-compression` is given or the output file ends in `.gz`/`.zst`.
+
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+/// Magic number at the start of a gzip member
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// Magic number at the start of a zstd frame
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression of an input or output stream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Compression {
+    /// Uncompressed
+    #[default]
+    None,
+    /// gzip (.gz)
+    #[value(alias = "gz")]
+    Gzip,
+    /// Zstandard (.zst)
+    #[value(alias = "zst")]
+    Zstd,
+}
+
+impl Compression {
+    /// The compression of data starting with `bytes`, by its magic number
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(GZIP_MAGIC) {
+            Compression::Gzip
+        } else if bytes.starts_with(ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    /// The compression named by the extension of `path` (`.gz`, `.zst`)
+    pub fn from_extension(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// The file extension for the compression, without the dot
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gz"),
+            Compression::Zstd => Some("zst"),
+        }
+    }
+
+    /// Wrap `writer` so that what is written to it is compressed; the stream
+    /// is completed when the returned writer is dropped
+    pub fn writer<W: Write + Send + Sync + 'static>(
+        self,
+        writer: W,
+    ) -> Result<Box<dyn Write + Send + Sync>> {
+        Ok(match self {
+            Compression::None => Box::new(writer),
+            Compression::Gzip => Box::new(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            Compression::Zstd => Box::new(ZstdWriter(Some(
+                zstd::Encoder::new(writer, zstd::DEFAULT_COMPRESSION_LEVEL)
+                    .context("Failed to start zstd compression")?,
+            ))),
+        })
+    }
+}
+
+/// A zstd encoder that completes its frame when dropped, as the gzip
+/// encoder does
+struct ZstdWriter<W: Write>(Option<zstd::Encoder<'static, W>>);
+
+impl<W: Write> Write for ZstdWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.as_mut().expect("finished on drop").write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.as_mut().expect("finished on drop").flush()
+    }
+}
+
+impl<W: Write> Drop for ZstdWriter<W> {
+    fn drop(&mut self) {
+        if let Some(encoder) = self.0.take() {
+            let _ = encoder.finish();
+        }
+    }
+}
+
+/// Wrap `reader` so that gzip or zstd data is decompressed as it is read;
+/// other data is passed through
+pub fn reader<'a>(reader: impl Read + 'a) -> Result<Box<dyn Read + 'a>> {
+    let mut reader = BufReader::new(reader);
+    Ok(match Compression::detect(reader.fill_buf()?) {
+        Compression::None => Box::new(reader),
+        // Concatenated members, as written by `cat a.gz b.gz`, are one stream
+        Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+        Compression::Zstd => Box::new(zstd::Decoder::with_buffer(reader)?),
+    })
+}
+
+/// Read `reader` to the end, decompressing it if it is gzip or zstd data
+pub fn read_to_end(reader: impl Read) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    self::reader(reader)?
+        .read_to_end(&mut bytes)
+        .context("Failed to decompress input")?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// A writer whose bytes can be read back after it is dropped
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for compression in [Compression::None, Compression::Gzip, Compression::Zstd] {
+            let sink = Shared::default();
+            let mut writer = compression.writer(sink.clone()).unwrap();
+            writer.write_all(b"One. Two.").unwrap();
+            drop(writer);
+
+            let bytes = sink.0.lock().unwrap().clone();
+            assert_eq!(Compression::detect(&bytes), compression);
+            assert_eq!(read_to_end(bytes.as_slice()).unwrap(), b"One. Two.");
+        }
+    }
+
+    #[test]
+    fn test_from_extension() {
+        assert_eq!(
+            Compression::from_extension(Path::new("a.txt.gz")),
+            Compression::Gzip
+        );
+        assert_eq!(
+            Compression::from_extension(Path::new("a.zst")),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::from_extension(Path::new("a.txt")),
+            Compression::None
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! User configuration file for the CLI
+//!
+//! Defaults for `sakurs process` are read from `--config <FILE>` (or
+//! `$SAKURS_CONFIG`), else from `$XDG_CONFIG_HOME/sakurs/config.toml`
+//! (`~/.config/sakurs/config.toml`). Command-line flags override file values.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Template written by `sakurs config init`
+pub const TEMPLATE: &str = r#"# sakurs configuration
+#
+# Defaults for `sakurs process`; command-line flags override them.
+
+[process]
+# Output format: text, json, jsonl, markdown, arrow or parquet
+# format = "text"
+
+# Built-in language (en, ja, pt, it, ar, th, hi), or an external language
+# configuration file with an optional code
+# language = "en"
+# language_config = "/path/to/language.toml"
+# language_code = "xx"
+
+# Threads and chunk size (default: chosen per text)
+# threads = 4
+# chunk_kb = 256
+
+# When the thread count is chosen per text: text size per thread and the
+# most threads used (written by `sakurs tune`)
+# adaptive_threshold_kb = 256
+# max_threads = 8
+
+# Preset for a class of machine, overridden by the three settings above:
+# laptop, server-32c or container-2c
+# perf_profile = "laptop"
+
+# Input files processed at once
+# jobs = 4
+
+# Input encoding (utf-8, shift_jis, utf-16le, utf-16be, latin1, auto) and
+# handling of invalid UTF-8 (error, replace, skip)
+# encoding = "utf-8"
+# invalid_utf8 = "error"
+
+# Remove ruby annotations (Aozora Bunko 《》 readings, HTML <rt>) first
+# strip_ruby = false
+
+# Ellipsis policy: language, always, never or before-capital
+# ellipsis = "language"
+# emoji_terminators = false
+
+# Preset for a kind of text: social, legal or scientific
+# profile = "social"
+
+# Sentence length limits
+# min_sentence_chars = 5
+# max_sentence_chars = 500
+# force_split_bytes = 65536
+
+# Warn of sentences longer than this, and of unbalanced quotes and brackets
+# long_sentence_chars = 10000
+# enclosure_warnings = false
+
+# Close quotes and brackets still open at a blank line
+# close_enclosures_at_paragraphs = false
+
+# Deepest nesting tracked per bracket type, and what happens past it:
+# ignore, reset (at the next blank line) or warn
+# max_enclosure_depth = 32
+# depth_overflow = "ignore"
+
+# Judge straight quotes as opening or closing by the characters around them
+# infer_quote_direction = false
+
+# Drop sentences repeating an earlier one (case and whitespace ignored),
+# within each document or across the whole run
+# dedupe = "document"
+
+# Suppress progress output
+# quiet = false
+
+# Log format on stderr: text or json
+# log_format = "text"
+"#;
+
+/// Contents of the CLI configuration file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliConfig {
+    /// Defaults for `sakurs process`
+    pub process: ProcessDefaults,
+}
+
+/// Defaults for `sakurs process`, each overridden by its flag
+///
+/// Enumerated values are kept as the strings the flags accept and parsed
+/// when applied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessDefaults {
+    pub format: Option<String>,
+    pub language: Option<String>,
+    pub language_config: Option<PathBuf>,
+    pub language_code: Option<String>,
+    pub threads: Option<usize>,
+    pub adaptive_threshold_kb: Option<usize>,
+    pub max_threads: Option<usize>,
+    pub perf_profile: Option<String>,
+    pub jobs: Option<usize>,
+    pub chunk_kb: Option<usize>,
+    pub encoding: Option<String>,
+    pub invalid_utf8: Option<String>,
+    pub strip_ruby: Option<bool>,
+    pub ellipsis: Option<String>,
+    pub emoji_terminators: Option<bool>,
+    pub profile: Option<String>,
+    pub min_sentence_chars: Option<usize>,
+    pub max_sentence_chars: Option<usize>,
+    pub force_split_bytes: Option<usize>,
+    pub long_sentence_chars: Option<usize>,
+    pub enclosure_warnings: Option<bool>,
+    pub close_enclosures_at_paragraphs: Option<bool>,
+    pub max_enclosure_depth: Option<usize>,
+    pub depth_overflow: Option<String>,
+    pub infer_quote_direction: Option<bool>,
+    pub dedupe: Option<String>,
+    pub quiet: Option<bool>,
+    pub log_format: Option<String>,
+}
+
+impl CliConfig {
+    /// Read a configuration file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid config file: {}", path.display()))
+    }
+
+    /// Read the configuration at `explicit` (which must exist), else at the
+    /// default path if there is a file there; returns the file read, if any
+    pub fn discover(explicit: Option<&Path>) -> Result<(Self, Option<PathBuf>)> {
+        if let Some(path) = explicit {
+            return Ok((Self::load(path)?, Some(path.to_path_buf())));
+        }
+        match default_path().filter(|path| path.is_file()) {
+            Some(path) => Ok((Self::load(&path)?, Some(path))),
+            None => Ok((Self::default(), None)),
+        }
+    }
+
+    /// The configuration as TOML, listing only the keys that are set
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to serialize config")
+    }
+}
+
+/// The default configuration file path: `$XDG_CONFIG_HOME/sakurs/config.toml`,
+/// else `%APPDATA%\sakurs\config.toml` on Windows, else
+/// `~/.config/sakurs/config.toml`
+pub fn default_path() -> Option<PathBuf> {
+    let non_empty = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    let config_dir = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            if cfg!(windows) {
+                non_empty("APPDATA").map(PathBuf::from)
+            } else {
+                None
+            }
+        })
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("sakurs").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_parses_to_empty_config() {
+        let config: CliConfig = toml::from_str(TEMPLATE).unwrap();
+        assert_eq!(config, CliConfig::default());
+    }
+
+    #[test]
+    fn test_parse_and_serialize() {
+        let config: CliConfig =
+            toml::from_str("[process]\nformat = \"json\"\nthreads = 2\n").unwrap();
+        assert_eq!(config.process.format.as_deref(), Some("json"));
+        assert_eq!(config.process.threads, Some(2));
+        assert_eq!(
+            config.to_toml().unwrap(),
+            "[process]\nformat = \"json\"\nthreads = 2\n"
+        );
+
+        assert!(toml::from_str::<CliConfig>("[process]\nfromat = \"json\"\n").is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Tar and zip archives read member by member, without extracting them
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Kind of archive, by file name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    /// `.tar`, optionally gzip or zstd compressed (`.tar.gz`, `.tgz`,
+    /// `.tar.zst`, `.tzst`)
+    Tar,
+    /// `.zip`
+    Zip,
+}
+
+impl ArchiveKind {
+    /// The archive kind named by `path`, if it names an archive
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if [".tar", ".tar.gz", ".tgz", ".tar.zst", ".tzst"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        {
+            Some(ArchiveKind::Tar)
+        } else if name.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Call `member` with the path and contents of each regular file in the
+/// archive at `path`, in archive order
+///
+/// Members are read one at a time, so memory stays proportional to the
+/// largest member. An error from `member` stops the traversal.
+pub fn for_each_member(
+    path: &Path,
+    kind: ArchiveKind,
+    mut member: impl FnMut(&str, Vec<u8>) -> Result<()>,
+) -> Result<()> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let context = || format!("Failed to read archive: {}", path.display());
+    match kind {
+        ArchiveKind::Tar => {
+            let mut archive = tar::Archive::new(crate::compression::reader(file)?);
+            for entry in archive.entries().with_context(context)? {
+                let mut entry = entry.with_context(context)?;
+                if !entry.header().entry_type().is_file() {
+                    continue;
+                }
+                let name = entry.path().with_context(context)?.display().to_string();
+                let mut bytes = Vec::new();
+                entry.read_to_end(&mut bytes).with_context(context)?;
+                member(&name, bytes)?;
+            }
+        }
+        ArchiveKind::Zip => {
+            let mut archive = zip::ZipArchive::new(file).with_context(context)?;
+            for i in 0..archive.len() {
+                let mut entry = archive.by_index(i).with_context(context)?;
+                if !entry.is_file() {
+                    continue;
+                }
+                let name = entry.name().to_string();
+                let mut bytes = Vec::new();
+                entry.read_to_end(&mut bytes).with_context(context)?;
+                member(&name, bytes)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn members(path: &Path) -> Vec<(String, Vec<u8>)> {
+        let kind = ArchiveKind::from_path(path).unwrap();
+        let mut members = Vec::new();
+        for_each_member(path, kind, |name, bytes| {
+            members.push((name.to_string(), bytes));
+            Ok(())
+        })
+        .unwrap();
+        members
+    }
+
+    #[test]
+    fn test_from_path() {
+        for name in ["a.tar", "a.tar.gz", "a.TGZ", "a.tar.zst"] {
+            assert_eq!(
+                ArchiveKind::from_path(Path::new(name)),
+                Some(ArchiveKind::Tar)
+            );
+        }
+        assert_eq!(
+            ArchiveKind::from_path(Path::new("a.zip")),
+            Some(ArchiveKind::Zip)
+        );
+        assert_eq!(ArchiveKind::from_path(Path::new("a.txt.gz")), None);
+    }
+
+    #[test]
+    fn test_tar_gz_members() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("corpus.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        for (name, text) in [("docs/a.txt", "One. Two."), ("b.txt", "Three.")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(text.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, text.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        assert_eq!(
+            members(&path),
+            [
+                ("docs/a.txt".to_string(), b"One. Two.".to_vec()),
+                ("b.txt".to_string(), b"Three.".to_vec())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zip_members() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("corpus.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+        writer
+            .add_directory("docs/", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .start_file("docs/a.txt", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"One. Two.").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(
+            members(&path),
+            [("docs/a.txt".to_string(), b"One. Two.".to_vec())]
+        );
+    }
+}
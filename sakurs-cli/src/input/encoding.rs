@@ -0,0 +1,370 @@
+//! Input encoding detection and transcoding to UTF-8
+
+use anyhow::{anyhow, Result};
+use encoding_rs::{Encoding, SHIFT_JIS, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use sakurs_core::{InvalidUtf8, Warning};
+
+/// Encodings accepted by `--encoding`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum InputEncoding {
+    /// UTF-8 (a leading byte order mark is removed)
+    #[default]
+    #[value(name = "utf-8", alias = "utf8")]
+    Utf8,
+    /// Shift_JIS / Windows-31J (CP932)
+    #[value(
+        name = "shift_jis",
+        alias = "shift-jis",
+        alias = "sjis",
+        alias = "cp932"
+    )]
+    ShiftJis,
+    /// UTF-16 little endian
+    #[value(name = "utf-16le", alias = "utf16le")]
+    Utf16Le,
+    /// UTF-16 big endian
+    #[value(name = "utf-16be", alias = "utf16be")]
+    Utf16Be,
+    /// ISO-8859-1 (decoded as its Windows-1252 superset)
+    #[value(name = "latin1", alias = "iso-8859-1")]
+    Latin1,
+    /// Detect from the byte order mark, else UTF-8 if valid, else guess
+    /// from the byte statistics
+    Auto,
+}
+
+impl InputEncoding {
+    fn encoding(self) -> Option<&'static Encoding> {
+        match self {
+            InputEncoding::Utf8 => Some(UTF_8),
+            InputEncoding::ShiftJis => Some(SHIFT_JIS),
+            InputEncoding::Utf16Le => Some(UTF_16LE),
+            InputEncoding::Utf16Be => Some(UTF_16BE),
+            InputEncoding::Latin1 => Some(WINDOWS_1252),
+            InputEncoding::Auto => None,
+        }
+    }
+}
+
+/// Text decoded from the input bytes
+#[derive(Debug)]
+pub struct Decoded {
+    /// The text as UTF-8
+    pub text: String,
+    /// The encoding the bytes were decoded from
+    pub encoding: &'static Encoding,
+    /// The invalid UTF-8 replaced or skipped under `--invalid-utf8`, as one
+    /// warning at the first invalid sequence counting the bytes of all
+    pub invalid_utf8: Option<Warning>,
+    /// Where the text's byte offsets lie in the input bytes
+    pub offsets: OffsetMap,
+}
+
+impl Decoded {
+    /// The offset in the text of the input offset `offset`, if it falls on
+    /// a character boundary of the text
+    pub fn text_offset(&self, offset: usize) -> Option<usize> {
+        let points = &self.offsets.points;
+        let decoded = match points.partition_point(|&(_, input)| input <= offset) {
+            0 => offset,
+            i => {
+                let (decoded, input) = points[i - 1];
+                decoded + (offset - input)
+            }
+        };
+        (self.text.is_char_boundary(decoded) && self.offsets.to_input(decoded) == offset)
+            .then_some(decoded)
+    }
+}
+
+/// Maps byte offsets in decoded text to byte offsets in the input it was
+/// decoded from, past any byte order mark, transcoding and invalid UTF-8
+///
+/// Exact at character boundaries. UTF-8 input without a byte order mark or
+/// invalid bytes maps to itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffsetMap {
+    /// `(decoded, input)` offset pairs in increasing order; from each pair to
+    /// the next, both sides advance byte for byte
+    points: Vec<(usize, usize)>,
+}
+
+impl OffsetMap {
+    /// The input offset of the decoded offset `offset`
+    pub fn to_input(&self, offset: usize) -> usize {
+        match self
+            .points
+            .partition_point(|&(decoded, _)| decoded <= offset)
+        {
+            0 => offset,
+            i => {
+                let (decoded, input) = self.points[i - 1];
+                input + (offset - decoded)
+            }
+        }
+    }
+
+    /// The map of the decoded text from `start` on, with offsets on both
+    /// sides relative to where that text starts
+    pub fn rebase(&self, start: usize) -> Self {
+        let base = self.to_input(start);
+        let mut map = Self::default();
+        for &(decoded, input) in self.points.iter().filter(|&&(d, _)| d > start) {
+            map.push(decoded - start, input - base);
+        }
+        map
+    }
+
+    /// Record that `decoded` corresponds to `input`, unless the previous
+    /// point already implies it
+    fn push(&mut self, decoded: usize, input: usize) {
+        if self.to_input(decoded) != input {
+            self.points.push((decoded, input));
+        }
+    }
+
+    /// The map of text decoded from `body` (which follows `bom` bytes) by
+    /// `encoding`, found by feeding the decoder one byte at a time and
+    /// noting where its output grows
+    fn transcoded(encoding: &'static Encoding, bom: usize, body: &[u8]) -> Self {
+        let mut map = Self::default();
+        map.push(0, bom);
+        let mut decoder = encoding.new_decoder_without_bom_handling();
+        let mut out = String::new();
+        let mut decoded = 0;
+        for (i, byte) in body.iter().enumerate() {
+            out.clear();
+            out.reserve(
+                decoder
+                    .max_utf8_buffer_length_without_replacement(1)
+                    .unwrap_or(16),
+            );
+            let last = i + 1 == body.len();
+            // The body decoded without errors before, so none occur here
+            let _ = decoder.decode_to_string_without_replacement(
+                std::slice::from_ref(byte),
+                &mut out,
+                last,
+            );
+            if !out.is_empty() {
+                decoded += out.len();
+                map.push(decoded, bom + i + 1);
+            }
+        }
+        map
+    }
+
+    /// The map of UTF-8 `body` (which follows `bom` bytes) decoded under the
+    /// lossy `invalid_utf8` policy, as [`InvalidUtf8::decode`] does: one
+    /// replacement character per invalid sequence, or nothing
+    fn lossy_utf8(invalid_utf8: InvalidUtf8, bom: usize, body: &[u8]) -> Self {
+        let mut map = Self::default();
+        map.push(0, bom);
+        let (mut decoded, mut input) = (0, bom);
+        for chunk in body.utf8_chunks() {
+            decoded += chunk.valid().len();
+            input += chunk.valid().len() + chunk.invalid().len();
+            if !chunk.invalid().is_empty() {
+                if invalid_utf8 == InvalidUtf8::Replace {
+                    decoded += char::REPLACEMENT_CHARACTER.len_utf8();
+                }
+                map.push(decoded, input);
+            }
+        }
+        map
+    }
+}
+
+/// Decodes `bytes` to UTF-8 text.
+///
+/// Malformed input is an error rather than being replaced, so a wrong
+/// `--encoding` never silently produces garbage sentences. The one exception
+/// is UTF-8 input under a lossy `invalid_utf8` policy, for scraped text with
+/// stray binary junk.
+pub fn decode(bytes: &[u8], encoding: InputEncoding, invalid_utf8: InvalidUtf8) -> Result<Decoded> {
+    let encoding = match encoding.encoding() {
+        Some(encoding) => encoding,
+        None => detect(bytes),
+    };
+    let bom = match Encoding::for_bom(bytes) {
+        Some((bom, len)) if bom == encoding => len,
+        _ => 0,
+    };
+    let body = &bytes[bom..];
+    if encoding == UTF_8 && invalid_utf8 != InvalidUtf8::Error {
+        let (text, warning) = invalid_utf8.decode_with_warning(body.to_vec())?;
+        let offsets = if warning.is_some() {
+            OffsetMap::lossy_utf8(invalid_utf8, bom, body)
+        } else {
+            let mut offsets = OffsetMap::default();
+            offsets.push(0, bom);
+            offsets
+        };
+        return Ok(Decoded {
+            text,
+            encoding,
+            invalid_utf8: warning,
+            offsets,
+        });
+    }
+    let text = encoding
+        .decode_without_bom_handling_and_without_replacement(body)
+        .ok_or_else(|| anyhow!("Input is not valid {}", encoding.name()))?;
+    let offsets = if encoding == UTF_8 {
+        let mut offsets = OffsetMap::default();
+        offsets.push(0, bom);
+        offsets
+    } else {
+        OffsetMap::transcoded(encoding, bom, body)
+    };
+    Ok(Decoded {
+        text: text.into_owned(),
+        encoding,
+        invalid_utf8: None,
+        offsets,
+    })
+}
+
+/// Guesses the encoding of `bytes`: byte order mark first, then UTF-8 if the
+/// bytes are valid UTF-8, then a statistical guess.
+fn detect(bytes: &[u8]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        return UTF_8;
+    }
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    detector.guess(None, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_shift_jis() {
+        let (bytes, _, _) = SHIFT_JIS.encode("これは日本語です。テストです。");
+        let decoded = decode(&bytes, InputEncoding::ShiftJis, InvalidUtf8::Error).unwrap();
+        assert_eq!(decoded.text, "これは日本語です。テストです。");
+        assert_eq!(decoded.encoding, SHIFT_JIS);
+    }
+
+    #[test]
+    fn test_decode_utf16le_strips_bom() {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend("Hi. Bye.".encode_utf16().flat_map(u16::to_le_bytes));
+        let decoded = decode(&bytes, InputEncoding::Utf16Le, InvalidUtf8::Error).unwrap();
+        assert_eq!(decoded.text, "Hi. Bye.");
+    }
+
+    fn auto(bytes: &[u8]) -> (String, &'static Encoding) {
+        let decoded = decode(bytes, InputEncoding::Auto, InvalidUtf8::Error).unwrap();
+        (decoded.text, decoded.encoding)
+    }
+
+    #[test]
+    fn test_auto_detects_bom_utf8_and_shift_jis() {
+        let mut bom = vec![0xFE, 0xFF];
+        bom.extend("Hi.".encode_utf16().flat_map(u16::to_be_bytes));
+        assert_eq!(auto(&bom).1, UTF_16BE);
+
+        let (text, used) = auto("Café. 世界。".as_bytes());
+        assert_eq!((text.as_str(), used), ("Café. 世界。", UTF_8));
+
+        let source = "吾輩は猫である。名前はまだ無い。どこで生れたかとんと見当がつかぬ。";
+        let (bytes, _, _) = SHIFT_JIS.encode(source);
+        let (text, used) = auto(&bytes);
+        assert_eq!((text.as_str(), used), (source, SHIFT_JIS));
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_input() {
+        let err = decode(&[0x48, 0xFF, 0x49], InputEncoding::Utf8, InvalidUtf8::Error).unwrap_err();
+        assert!(err.to_string().contains("not valid UTF-8"));
+    }
+
+    #[test]
+    fn test_lossy_utf8_warns_of_invalid_bytes() {
+        let bytes = b"Hi.\xFF Bye.";
+        let decoded = decode(bytes, InputEncoding::Utf8, InvalidUtf8::Replace).unwrap();
+        assert_eq!(
+            (decoded.text.as_str(), decoded.invalid_utf8),
+            (
+                "Hi.\u{FFFD} Bye.",
+                Some(Warning::InvalidUtf8 {
+                    offset: 3,
+                    bytes: 1
+                })
+            )
+        );
+        let decoded = decode(bytes, InputEncoding::Utf8, InvalidUtf8::Skip).unwrap();
+        assert_eq!(
+            (decoded.text.as_str(), decoded.invalid_utf8),
+            (
+                "Hi. Bye.",
+                Some(Warning::InvalidUtf8 {
+                    offset: 3,
+                    bytes: 1
+                })
+            )
+        );
+
+        // Other encodings stay strict.
+        let sjis = [0x82, 0xA0, 0x82];
+        assert!(decode(&sjis, InputEncoding::ShiftJis, InvalidUtf8::Replace).is_err());
+    }
+
+    #[test]
+    fn test_offsets_map_to_input_bytes() {
+        let source = "a。b漢\u{1F600}.";
+        let (sjis, _, _) = SHIFT_JIS.encode("a。b漢.");
+        let decoded = decode(&sjis, InputEncoding::ShiftJis, InvalidUtf8::Error).unwrap();
+        // a(1) 。(3→2) b(1) 漢(3→2) .(1)
+        let ends: Vec<usize> = [1, 4, 5, 8, 9]
+            .iter()
+            .map(|&end| decoded.offsets.to_input(end))
+            .collect();
+        assert_eq!(ends, [1, 3, 4, 6, 7]);
+        // and back, except inside a character
+        assert_eq!(decoded.text_offset(3), Some(4));
+        assert_eq!(decoded.text_offset(2), None);
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        utf16.extend(source.encode_utf16().flat_map(u16::to_le_bytes));
+        let decoded = decode(&utf16, InputEncoding::Auto, InvalidUtf8::Error).unwrap();
+        assert_eq!(decoded.text, source);
+        let end = source.len();
+        assert_eq!(decoded.offsets.to_input(end), utf16.len());
+        assert_eq!(decoded.offsets.to_input(source.find('b').unwrap()), 2 + 4);
+
+        let decoded = decode(
+            b"\xEF\xBB\xBFHi.\xFF Bye.",
+            InputEncoding::Utf8,
+            InvalidUtf8::Skip,
+        )
+        .unwrap();
+        assert_eq!(decoded.text, "Hi. Bye.");
+        // Skipped bytes count as part of the text before them
+        assert_eq!(decoded.offsets.to_input(3), 7);
+        assert_eq!(decoded.offsets.to_input(8), 12);
+        assert_eq!(decoded.text_offset(7), Some(3));
+        assert_eq!(decoded.text_offset(12), Some(8));
+        assert_eq!(decoded.text_offset(1), None, "inside the BOM");
+        // From "." on: the skipped byte follows it
+        let rebased = decoded.offsets.rebase(2);
+        assert_eq!((rebased.to_input(1), rebased.to_input(6)), (2, 7));
+
+        let plain = decode(b"Hi.", InputEncoding::Utf8, InvalidUtf8::Error).unwrap();
+        assert_eq!(plain.offsets, OffsetMap::default());
+        assert_eq!(plain.text_offset(2), Some(2));
+    }
+
+    #[test]
+    fn test_decode_latin1() {
+        let bytes = [0x43, 0x61, 0x66, 0xE9, 0x2E];
+        let decoded = decode(&bytes, InputEncoding::Latin1, InvalidUtf8::Error).unwrap();
+        assert_eq!(decoded.text, "Café.");
+    }
+}
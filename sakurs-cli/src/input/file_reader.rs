@@ -4,6 +4,11 @@ use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
 
+use sakurs_core::InvalidUtf8;
+
+use super::encoding::Decoded;
+use super::InputEncoding;
+
 /// File reader with UTF-8 validation
 pub struct FileReader;
 
@@ -16,6 +21,34 @@ impl FileReader {
         Ok(content)
     }
 
+    /// Read a file in `encoding`, transcoded to UTF-8; gzip and zstd files
+    /// are decompressed first
+    pub fn read_text_with_encoding(
+        path: &Path,
+        encoding: InputEncoding,
+        invalid_utf8: InvalidUtf8,
+    ) -> Result<Decoded> {
+        let bytes = fs::File::open(path)
+            .map_err(anyhow::Error::from)
+            .and_then(crate::compression::read_to_end)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        Self::decode_text(&path.display().to_string(), &bytes, encoding, invalid_utf8)
+    }
+
+    /// Decode the bytes of the document `name` (a file or archive member)
+    /// in `encoding` to UTF-8
+    pub fn decode_text(
+        name: &str,
+        bytes: &[u8],
+        encoding: InputEncoding,
+        invalid_utf8: InvalidUtf8,
+    ) -> Result<Decoded> {
+        let decoded = super::encoding::decode(bytes, encoding, invalid_utf8)
+            .with_context(|| format!("Failed to decode file: {name}"))?;
+        log::debug!("Decoded {name} as {}", decoded.encoding.name());
+        Ok(decoded)
+    }
+
     /// Get file size in bytes
     pub fn file_size(path: &Path) -> Result<u64> {
         let metadata = fs::metadata(path)
@@ -1,7 +1,11 @@
 //! Input handling module
 
+pub mod archive;
+pub mod encoding;
 pub mod file_reader;
 pub mod glob_resolver;
 
+pub use archive::ArchiveKind;
+pub use encoding::InputEncoding;
 pub use file_reader::FileReader;
 pub use glob_resolver::resolve_patterns;
@@ -1,7 +1,8 @@
 //! Language source management for CLI
 
 use crate::commands::process::Language;
-use std::path::PathBuf;
+use sakurs_core::LanguageConfig;
+use std::path::{Path, PathBuf};
 
 /// Source of language rules
 #[derive(Debug, Clone)]
@@ -36,12 +37,154 @@ impl LanguageSource {
     }
 }
 
+impl LanguageSource {
+    /// Build the processing configuration for this language source on top
+    /// of `builder`, which carries the non-language options (threads, chunk
+    /// size, ...); an external source's rules are not part of it
+    pub fn config(
+        &self,
+        builder: sakurs_core::ConfigBuilder,
+    ) -> anyhow::Result<sakurs_core::Config> {
+        let builder = match self {
+            LanguageSource::BuiltIn(lang) => builder
+                .language(lang.code())
+                .map_err(|e| anyhow::anyhow!("Failed to set language: {e}"))?,
+            LanguageSource::External { .. } => builder,
+        };
+        builder
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build processor config: {e}"))
+    }
+
+    /// Build a processor for this language source on top of `builder`,
+    /// which carries the non-language options (threads, chunk size, ...)
+    pub fn processor(
+        &self,
+        builder: sakurs_core::ConfigBuilder,
+    ) -> anyhow::Result<sakurs_core::SentenceProcessor> {
+        use sakurs_core::SentenceProcessor;
+
+        let config = self.config(builder)?;
+        match self {
+            LanguageSource::BuiltIn(_) => SentenceProcessor::with_config(config)
+                .map_err(|e| anyhow::anyhow!("Failed to create processor: {e}")),
+            LanguageSource::External {
+                path,
+                language_code,
+            } => {
+                let language = load_language_file(path, language_code.as_deref())?;
+                SentenceProcessor::with_language_config(config, &language)
+                    .map_err(|e| anyhow::anyhow!("Failed to create processor: {e}"))
+            }
+        }
+    }
+}
+
+impl LanguageSource {
+    /// Load the language configuration of this source
+    pub fn language_config(&self) -> anyhow::Result<LanguageConfig> {
+        match self {
+            LanguageSource::BuiltIn(lang) => LanguageConfig::embedded()
+                .into_iter()
+                .find(|config| config.metadata.code == lang.code())
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("No built-in configuration for {}", lang.code())),
+            LanguageSource::External {
+                path,
+                language_code,
+            } => load_language_file(path, language_code.as_deref()),
+        }
+    }
+}
+
+/// Load an external language configuration, optionally overriding its
+/// code, warning about keys renamed since the file's schema version (which
+/// are migrated) and keys sakurs does not know (which are ignored)
+pub fn load_language_file(
+    path: &Path,
+    language_code: Option<&str>,
+) -> anyhow::Result<LanguageConfig> {
+    let (config, report) = LanguageConfig::from_file_with_report(path, language_code)
+        .map_err(|e| anyhow::anyhow!("Failed to load external language config: {e}"))?;
+    for (old, new) in &report.renamed {
+        log::warn!("{}: `{old}` is now `{new}`", path.display());
+    }
+    for key in &report.unknown {
+        log::warn!("{}: unknown key `{key}` ignored", path.display());
+    }
+    Ok(config)
+}
+
+/// Load an external language configuration again whenever its file
+/// changes, on a background thread, passing each version that loads to
+/// `reload`; a version that fails to load is logged and skipped
+pub fn watch_language_file(
+    path: &Path,
+    language_code: Option<String>,
+    mut reload: impl FnMut(LanguageConfig) + Send + 'static,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use notify::{EventKind, RecursiveMode, Watcher};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    // Watch the directory holding the file, so that a file replaced by
+    // renaming (as editors save it) is still seen
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    let dir = path
+        .parent()
+        .with_context(|| format!("No directory holds {}", path.display()))?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start watching")?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", dir.display()))?;
+
+    std::thread::spawn(move || {
+        // Watching stops when the watcher is dropped
+        let _watcher = watcher;
+        // Wait for a change, then collect the burst of events a save
+        // produces before reloading
+        while let Ok(mut event) = rx.recv() {
+            let mut changed = false;
+            loop {
+                match event {
+                    Ok(event) => {
+                        changed |=
+                            matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                                && event.paths.contains(&path);
+                    }
+                    Err(e) => log::warn!("Watch error: {e}"),
+                }
+                match rx.recv_timeout(Duration::from_millis(100)) {
+                    Ok(next) => event = next,
+                    Err(_) => break,
+                }
+            }
+            if changed {
+                match load_language_file(&path, language_code.as_deref()) {
+                    Ok(language) => reload(language),
+                    Err(e) => log::error!("{e:#}; keeping the previous rules"),
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
 impl Language {
     /// Convert to string representation
     pub fn as_str(&self) -> &'static str {
         match self {
             Language::English => "English",
             Language::Japanese => "Japanese",
+            Language::Portuguese => "Portuguese",
+            Language::Italian => "Italian",
+            Language::Arabic => "Arabic",
+            Language::Thai => "Thai",
+            Language::Hindi => "Hindi",
         }
     }
 
@@ -50,6 +193,11 @@ impl Language {
         match self {
             Language::English => "en",
             Language::Japanese => "ja",
+            Language::Portuguese => "pt",
+            Language::Italian => "it",
+            Language::Arabic => "ar",
+            Language::Thai => "th",
+            Language::Hindi => "hi",
         }
     }
 }
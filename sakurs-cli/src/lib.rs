@@ -3,10 +3,14 @@
 //! This library provides the command-line interface for the Sakurs
 //! sentence boundary detection system.
 
+pub mod alignment;
 pub mod commands;
+pub mod compression;
+pub mod config;
 pub mod error;
 pub mod input;
 pub mod language_source;
+pub mod logging;
 pub mod output;
 pub mod progress;
 
@@ -0,0 +1,72 @@
+//! Leveled logging to stderr, as text or JSON lines
+//!
+//! The level follows `-v` (warn, info, debug, trace) unless `RUST_LOG` is
+//! set. Records carry key-value fields (`file`, `duration_ms`, ...); JSON
+//! lines put them next to `timestamp`, `level`, `target` and `message` so
+//! that batch-pipeline monitoring can ingest them without parsing text.
+
+use std::io::Write;
+
+use log::kv::{self, VisitSource};
+use serde_json::{Map, Value};
+
+/// Format of log records on stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Install the global logger
+pub fn init(verbose: u8, format: LogFormat) {
+    let level = match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let mut builder =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level));
+    if format == LogFormat::Json {
+        builder.format(|buf, record| {
+            let mut line = Map::new();
+            line.insert(
+                "timestamp".into(),
+                buf.timestamp_millis().to_string().into(),
+            );
+            line.insert("level".into(), record.level().as_str().into());
+            line.insert("target".into(), record.target().into());
+            line.insert("message".into(), record.args().to_string().into());
+            let _ = record.key_values().visit(&mut Fields(&mut line));
+            writeln!(buf, "{}", Value::Object(line))
+        });
+    }
+    builder.init();
+}
+
+/// Collects a record's key-value fields into a JSON object
+struct Fields<'a>(&'a mut Map<String, Value>);
+
+impl<'kvs> VisitSource<'kvs> for Fields<'_> {
+    fn visit_pair(&mut self, key: kv::Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.insert(key.to_string(), json_value(&value));
+        Ok(())
+    }
+}
+
+fn json_value(value: &kv::Value<'_>) -> Value {
+    if let Some(n) = value.to_u64() {
+        n.into()
+    } else if let Some(n) = value.to_i64() {
+        n.into()
+    } else if let Some(b) = value.to_bool() {
+        b.into()
+    } else if let Some(x) = value.to_f64() {
+        x.into()
+    } else {
+        value.to_string().into()
+    }
+}
@@ -4,10 +4,23 @@
 //! Sakurs sentence boundary detection system based on the Δ-Stack Monoid algorithm.
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use sakurs_cli::commands::{Commands, ListCommands};
 use sakurs_cli::CliResult;
 
+#[cfg(feature = "mimalloc")]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+// mimalloc takes precedence when both allocator features are enabled
+#[cfg(all(
+    feature = "jemalloc",
+    not(feature = "mimalloc"),
+    not(target_env = "msvc")
+))]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
 /// Sakurs - High-performance sentence boundary detection
 ///
 /// A parallel text processing tool based on the Δ-Stack Monoid algorithm
@@ -36,27 +49,21 @@ fn main() -> CliResult<()> {
         Commands::Process(args) => args.execute(),
         Commands::Validate(args) => args.execute(),
         Commands::GenerateConfig(args) => args.execute(),
+        Commands::Serve(args) => args.execute(),
+        Commands::Daemon(args) => args.execute(),
+        Commands::Diff(args) => args.execute(),
+        Commands::Evaluate(args) => args.execute(),
+        Commands::LearnAbbrev(args) => args.execute(),
+        Commands::Tune(args) => args.execute(),
         Commands::List { subcommand } => execute_list(subcommand),
+        Commands::Config { subcommand } => subcommand.execute(),
+        Commands::Completions(args) => args.execute(Cli::command()),
     }
 }
 
 /// Execute list subcommands
 fn execute_list(subcommand: ListCommands) -> Result<()> {
-    match subcommand {
-        ListCommands::Languages => {
-            println!("Available languages:");
-            println!("  - english (English language rules)");
-            println!("  - japanese (Japanese language rules)");
-            Ok(())
-        }
-        ListCommands::Formats => {
-            println!("Available output formats:");
-            println!("  - text (Plain text, one sentence per line)");
-            println!("  - json (JSON array with sentence metadata)");
-            println!("  - markdown (Markdown formatted output)");
-            Ok(())
-        }
-    }
+    subcommand.execute()
 }
 
 #[cfg(test)]
@@ -84,7 +91,7 @@ mod tests {
 
     #[test]
     fn test_execute_list_languages() {
-        let result = execute_list(ListCommands::Languages);
+        let result = execute_list(ListCommands::Languages { language_dir: None });
         assert!(result.is_ok());
     }
 
@@ -101,7 +108,7 @@ mod tests {
         // but we can test the execute functions directly
 
         // Test all list subcommands
-        assert!(execute_list(ListCommands::Languages).is_ok());
+        assert!(execute_list(ListCommands::Languages { language_dir: None }).is_ok());
         assert!(execute_list(ListCommands::Formats).is_ok());
     }
 }
@@ -0,0 +1,72 @@
+//! Arrow IPC and Parquet output formatters
+
+use super::{Document, OutputFormatter, Sentence};
+use anyhow::{Context, Result};
+use sakurs_core::SentenceBatchBuilder;
+use std::io::Write;
+
+/// Columnar file container written by [`ArrowFormatter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowContainer {
+    /// Arrow IPC file format (`.arrow`, also known as Feather v2)
+    Ipc,
+    /// Apache Parquet
+    Parquet,
+}
+
+/// Columnar formatter - collects sentences into one record batch with the
+/// columns doc_id, sentence_index, text, start and end, and writes it as an
+/// Arrow IPC or Parquet file on finish
+pub struct ArrowFormatter<W: Write> {
+    writer: Option<W>,
+    container: ArrowContainer,
+    builder: SentenceBatchBuilder,
+}
+
+impl<W: Write> ArrowFormatter<W> {
+    /// Create a new formatter writing `container` to `writer`
+    pub fn new(writer: W, container: ArrowContainer) -> Self {
+        Self {
+            writer: Some(writer),
+            container,
+            builder: SentenceBatchBuilder::new(),
+        }
+    }
+}
+
+impl<W: Write + Send + Sync> OutputFormatter for ArrowFormatter<W> {
+    fn start_document(&mut self, document: &Document<'_>) -> Result<()> {
+        self.builder.start_document(document.id);
+        Ok(())
+    }
+
+    fn format_sentence(&mut self, sentence: &Sentence<'_>) -> Result<()> {
+        self.builder
+            .push(sentence.text, sentence.start, sentence.end);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let Some(mut writer) = self.writer.take() else {
+            return Ok(());
+        };
+        let batch = self.builder.finish()?;
+        match self.container {
+            ArrowContainer::Ipc => {
+                let mut ipc = arrow_ipc::writer::FileWriter::try_new(&mut writer, &batch.schema())
+                    .context("Failed to start Arrow IPC output")?;
+                ipc.write(&batch)?;
+                ipc.finish()?;
+            }
+            ArrowContainer::Parquet => {
+                let mut parquet =
+                    parquet::arrow::ArrowWriter::try_new(&mut writer, batch.schema(), None)
+                        .context("Failed to start Parquet output")?;
+                parquet.write(&batch)?;
+                parquet.close()?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
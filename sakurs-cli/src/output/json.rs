@@ -1,6 +1,6 @@
 //! JSON output formatter
 
-use super::OutputFormatter;
+use super::{Document, DocumentWarning, OutputFormatter, Sentence};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::io::Write;
@@ -9,17 +9,30 @@ use std::io::Write;
 pub struct JsonFormatter<W: Write> {
     writer: W,
     sentences: Vec<SentenceData>,
+    doc_path: String,
+    doc_index: Option<usize>,
 }
 
 /// Data structure for JSON output
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SentenceData {
+    /// Path of the input file holding the sentence, or "-" for stdin
+    pub doc_path: String,
+    /// Position of the document among the documents of the run, from 0
+    pub doc_index: usize,
+    /// Position of the sentence among all sentences of the run, from 0
+    pub sentence_id: usize,
     /// The sentence text
     pub text: String,
-    /// Starting offset in the original text
+    /// Byte offset of the sentence in the input file, before decoding
     pub offset: usize,
-    /// Length of the sentence
+    /// Length of the sentence in bytes of the input file
     pub length: usize,
+    /// How the sentence ends: strong, weak, forced or end_of_text
+    pub kind: String,
+    /// Problems noticed in the sentence's text, if any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<DocumentWarning>,
 }
 
 impl<W: Write> JsonFormatter<W> {
@@ -28,16 +41,29 @@ impl<W: Write> JsonFormatter<W> {
         Self {
             writer,
             sentences: Vec::new(),
+            doc_path: String::new(),
+            doc_index: None,
         }
     }
 }
 
 impl<W: Write + Send + Sync> OutputFormatter for JsonFormatter<W> {
-    fn format_sentence(&mut self, sentence: &str, offset: usize) -> Result<()> {
+    fn start_document(&mut self, document: &Document<'_>) -> Result<()> {
+        document.path.clone_into(&mut self.doc_path);
+        self.doc_index = Some(self.doc_index.map_or(0, |index| index + 1));
+        Ok(())
+    }
+
+    fn format_sentence(&mut self, sentence: &Sentence<'_>) -> Result<()> {
         self.sentences.push(SentenceData {
-            text: sentence.trim().to_string(),
-            offset,
-            length: sentence.len(),
+            doc_path: self.doc_path.clone(),
+            doc_index: self.doc_index.unwrap_or_default(),
+            sentence_id: self.sentences.len(),
+            text: sentence.text.to_string(),
+            offset: sentence.start,
+            length: sentence.end - sentence.start,
+            kind: sentence.kind.as_str().to_string(),
+            warnings: sentence.warnings.to_vec(),
         });
         Ok(())
     }
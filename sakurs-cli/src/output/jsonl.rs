@@ -0,0 +1,97 @@
+//! JSON Lines output formatter
+
+use super::{Document, DocumentWarning, OutputFormatter, Sentence};
+use anyhow::Result;
+use serde::Serialize;
+use std::io::Write;
+
+/// JSON Lines formatter - outputs one JSON object per sentence, tagged with
+/// its document, as soon as the sentence is found
+pub struct JsonlFormatter<W: Write> {
+    writer: W,
+    doc_id: String,
+    doc_path: String,
+    doc_index: Option<usize>,
+    sentence_index: usize,
+    sentence_id: usize,
+}
+
+/// One line of JSON Lines output: the columns of the Arrow output, plus
+/// the document's path and position, the sentence's position in the run, how
+/// it ends and any warnings about its text
+#[derive(Debug, Serialize)]
+struct SentenceRecord<'a> {
+    doc_id: &'a str,
+    doc_path: &'a str,
+    doc_index: usize,
+    sentence_id: usize,
+    sentence_index: usize,
+    text: &'a str,
+    start: usize,
+    end: usize,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    warnings: &'a [DocumentWarning],
+}
+
+impl<W: Write> JsonlFormatter<W> {
+    /// Create a new JSON Lines formatter
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            doc_id: String::new(),
+            doc_path: String::new(),
+            doc_index: None,
+            sentence_index: 0,
+            sentence_id: 0,
+        }
+    }
+
+    /// Number the current document's sentences from `sentences` on,
+    /// continuing the output of an interrupted single-document run
+    pub fn starting_at(mut self, sentences: usize) -> Self {
+        self.sentence_index = sentences;
+        self.sentence_id = sentences;
+        self
+    }
+}
+
+impl<W: Write + Send + Sync> OutputFormatter for JsonlFormatter<W> {
+    fn start_document(&mut self, document: &Document<'_>) -> Result<()> {
+        document.id.clone_into(&mut self.doc_id);
+        document.path.clone_into(&mut self.doc_path);
+        self.doc_index = Some(self.doc_index.map_or(0, |index| index + 1));
+        self.sentence_index = 0;
+        Ok(())
+    }
+
+    fn format_sentence(&mut self, sentence: &Sentence<'_>) -> Result<()> {
+        let record = SentenceRecord {
+            doc_id: &self.doc_id,
+            doc_path: &self.doc_path,
+            doc_index: self.doc_index.unwrap_or_default(),
+            sentence_id: self.sentence_id,
+            sentence_index: self.sentence_index,
+            text: sentence.text,
+            start: sentence.start,
+            end: sentence.end,
+            kind: sentence.kind.as_str(),
+            warnings: sentence.warnings,
+        };
+        serde_json::to_writer(&mut self.writer, &record)?;
+        writeln!(self.writer)?;
+        self.sentence_index += 1;
+        self.sentence_id += 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
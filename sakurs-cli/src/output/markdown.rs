@@ -1,6 +1,6 @@
 //! Markdown output formatter
 
-use super::OutputFormatter;
+use super::{OutputFormatter, Sentence};
 use anyhow::Result;
 use std::io::Write;
 
@@ -21,9 +21,9 @@ impl<W: Write> MarkdownFormatter<W> {
 }
 
 impl<W: Write + Send + Sync> OutputFormatter for MarkdownFormatter<W> {
-    fn format_sentence(&mut self, sentence: &str, _offset: usize) -> Result<()> {
+    fn format_sentence(&mut self, sentence: &Sentence<'_>) -> Result<()> {
         self.sentence_count += 1;
-        writeln!(self.writer, "{}. {}", self.sentence_count, sentence.trim())?;
+        writeln!(self.writer, "{}. {}", self.sentence_count, sentence.text)?;
         Ok(())
     }
 
@@ -1,20 +1,87 @@
 //! Output formatting module
 
 use anyhow::Result;
+use sakurs_core::BoundaryKind;
+use serde::{Deserialize, Serialize};
+
+/// An input document
+#[derive(Debug, Clone, Copy)]
+pub struct Document<'a> {
+    /// Identifier: the file path, the member path for an archive member,
+    /// "-" for stdin, or the index of a NUL-separated document
+    pub id: &'a str,
+    /// Path of the file the document was read from (`archive/member` for an
+    /// archive member), or "-" for stdin
+    pub path: &'a str,
+}
+
+/// A sentence of the current document
+#[derive(Debug, Clone, Copy)]
+pub struct Sentence<'a> {
+    /// The sentence text, without surrounding whitespace
+    pub text: &'a str,
+    /// Byte offset of the text in the document as read, before decoding
+    /// (after decompression)
+    pub start: usize,
+    /// Byte offset of the end of the text (exclusive) in the document as read
+    pub end: usize,
+    /// How the sentence ends
+    pub kind: BoundaryKind,
+    /// Problems noticed in the text from the end of the previous sentence
+    /// written to the end of this one, or to the end of the document for
+    /// its last sentence
+    pub warnings: &'a [DocumentWarning],
+}
+
+/// A problem noticed in a document, such as an unclosed quote or invalid
+/// UTF-8
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentWarning {
+    /// What kind of problem: unclosed_enclosure, unmatched_closer,
+    /// depth_exceeded, long_sentence, forced_split or invalid_utf8
+    pub kind: String,
+    /// Byte offset of the problem in the document as read, before decoding
+    pub offset: usize,
+    /// The problem in words
+    pub message: String,
+}
 
 /// Trait for output formatters
 pub trait OutputFormatter: Send + Sync {
+    /// Begin a new input document
+    fn start_document(&mut self, _document: &Document<'_>) -> Result<()> {
+        Ok(())
+    }
+
     /// Format and output a single sentence
-    fn format_sentence(&mut self, sentence: &str, offset: usize) -> Result<()>;
+    fn format_sentence(&mut self, sentence: &Sentence<'_>) -> Result<()>;
+
+    /// End the current input document
+    fn end_document(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Write out the sentences formatted so far, for formats whose output is
+    /// valid at any sentence (one record per line); others do nothing until
+    /// [`finish`](Self::finish)
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
 
     /// Finalize output (e.g., close JSON array)
     fn finish(&mut self) -> Result<()>;
 }
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod json;
+pub mod jsonl;
 pub mod markdown;
 pub mod text;
 
+#[cfg(feature = "arrow")]
+pub use arrow::{ArrowContainer, ArrowFormatter};
 pub use json::JsonFormatter;
+pub use jsonl::JsonlFormatter;
 pub use markdown::MarkdownFormatter;
 pub use text::TextFormatter;
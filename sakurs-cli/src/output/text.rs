@@ -1,18 +1,29 @@
 //! Plain text output formatter
 
-use super::OutputFormatter;
+use super::{OutputFormatter, Sentence};
 use anyhow::Result;
 use std::io::{self, Write};
 
 /// Plain text formatter - outputs one sentence per line
 pub struct TextFormatter<W: Write> {
     writer: W,
+    document_terminator: Option<u8>,
 }
 
 impl<W: Write> TextFormatter<W> {
     /// Create a new text formatter
     pub fn new(writer: W) -> Self {
-        Self { writer }
+        Self {
+            writer,
+            document_terminator: None,
+        }
+    }
+
+    /// End each document with `terminator` (such as NUL), so that the
+    /// documents can be told apart
+    pub fn with_document_terminator(mut self, terminator: u8) -> Self {
+        self.document_terminator = Some(terminator);
+        self
     }
 }
 
@@ -24,8 +35,20 @@ impl TextFormatter<io::Stdout> {
 }
 
 impl<W: Write + Send + Sync> OutputFormatter for TextFormatter<W> {
-    fn format_sentence(&mut self, sentence: &str, _offset: usize) -> Result<()> {
-        writeln!(self.writer, "{}", sentence.trim())?;
+    fn format_sentence(&mut self, sentence: &Sentence<'_>) -> Result<()> {
+        writeln!(self.writer, "{}", sentence.text)?;
+        Ok(())
+    }
+
+    fn end_document(&mut self) -> Result<()> {
+        if let Some(terminator) = self.document_terminator {
+            self.writer.write_all(&[terminator])?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
         Ok(())
     }
 
@@ -1,12 +1,15 @@
 //! Progress reporting module
 
 use indicatif::{ProgressBar, ProgressStyle};
+use sakurs_core::Progress;
 use std::time::Duration;
 
 /// Progress reporter for file processing
 pub struct ProgressReporter {
     progress_bar: Option<ProgressBar>,
     quiet: bool,
+    /// Whether the bar counts bytes of a single input rather than files
+    by_bytes: bool,
 }
 
 impl ProgressReporter {
@@ -15,6 +18,7 @@ impl ProgressReporter {
         Self {
             progress_bar: None,
             quiet,
+            by_bytes: false,
         }
     }
 
@@ -36,11 +40,52 @@ impl ProgressReporter {
         self.progress_bar = Some(pb);
     }
 
+    /// Initialize a byte progress bar for a single input, with percentage
+    /// and ETA; its length is set by the first [`bytes_processed`] report
+    ///
+    /// [`bytes_processed`]: Self::bytes_processed
+    pub fn init_bytes(&mut self, filename: &str) {
+        if self.quiet {
+            return;
+        }
+
+        let pb = ProgressBar::new(0);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "[{elapsed_precise}] {bar:40.cyan/blue} {bytes}/{total_bytes} \
+                     ({percent}%, ETA {eta}) {msg}",
+                )
+                .unwrap()
+                .progress_chars("##-"),
+        );
+        pb.set_message(filename.to_string());
+        pb.enable_steady_tick(Duration::from_millis(100));
+
+        self.progress_bar = Some(pb);
+        self.by_bytes = true;
+    }
+
+    /// Update a byte progress bar from the processor's report
+    pub fn bytes_processed(&self, progress: Progress) {
+        if let Some(pb) = self.progress_bar.as_ref().filter(|_| self.by_bytes) {
+            pb.set_length(progress.total_bytes as u64);
+            // Parallel chunks may report out of order
+            if progress.processed_bytes as u64 > pb.position() {
+                pb.set_position(progress.processed_bytes as u64);
+            }
+        }
+    }
+
     /// Update progress for a completed file
     pub fn file_completed(&self, filename: &str) {
         if let Some(pb) = &self.progress_bar {
             pb.set_message(format!("Processed: {filename}"));
-            pb.inc(1);
+            if self.by_bytes {
+                pb.set_position(pb.length().unwrap_or_default());
+            } else {
+                pb.inc(1);
+            }
         }
     }
 
@@ -40,6 +40,207 @@ fn test_process_japanese_text() {
         ));
 }
 
+#[test]
+fn test_process_shift_jis_input() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sjis.txt");
+    // "これはテストです。日本語です。" in Shift_JIS
+    let sjis: &[u8] = &[
+        0x82, 0xB1, 0x82, 0xEA, 0x82, 0xCD, 0x83, 0x65, 0x83, 0x58, 0x83, 0x67, 0x82, 0xC5, 0x82,
+        0xB7, 0x81, 0x42, 0x93, 0xFA, 0x96, 0x7B, 0x8C, 0xEA, 0x82, 0xC5, 0x82, 0xB7, 0x81, 0x42,
+    ];
+    fs::write(&path, sjis).unwrap();
+
+    for encoding in ["shift_jis", "auto"] {
+        Command::cargo_bin("sakurs")
+            .unwrap()
+            .args(["process", "-l", "japanese", "--encoding", encoding, "-i"])
+            .arg(&path)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("これはテストです。\n日本語です。"));
+    }
+
+    // Without --encoding the bytes are rejected as invalid UTF-8
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-l", "japanese", "-i"])
+        .arg(&path)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_process_invalid_utf8_skip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("dirty.txt");
+    fs::write(&path, b"First one.\x00\xFF\xFE Second one.").unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--invalid-utf8", "skip", "-i"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Second one."));
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i"])
+        .arg(&path)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_process_reports_warnings() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("dirty.txt");
+    fs::write(
+        &path,
+        b"Hello world. This one runs on for quite a while. He said \"hi.\nBad \xFF byte.",
+    )
+    .unwrap();
+
+    // On stderr, and with the sentences of JSON output, at input offsets
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-f", "json", "--invalid-utf8", "replace"])
+        .args(["--long-sentence-chars", "30", "--enclosure-warnings", "-i"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("suspiciously long sentence of 35 characters at byte 13"));
+    assert!(stderr.contains("unclosed '\"' at byte 57"));
+    assert!(stderr.contains("invalid UTF-8 (1 byte in all) at byte 66"));
+    let sentences: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(sentences[0].get("warnings").is_none());
+    assert_eq!(sentences[1]["warnings"][0]["kind"], "long_sentence");
+    let kinds: Vec<&str> = sentences[2]["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["unclosed_enclosure", "invalid_utf8"]);
+    assert_eq!(sentences[2]["warnings"][1]["offset"], 66);
+
+    // Forced splits are always reported
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args([
+            "process",
+            "-i",
+            "-",
+            "-f",
+            "jsonl",
+            "--max-sentence-chars",
+            "10",
+        ])
+        .write_stdin("A sentence far longer than ten characters.")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""kind":"forced_split""#))
+        .stderr(predicate::str::contains("sentence force-split for length"));
+}
+
+#[test]
+fn test_process_keeps_warnings_of_dropped_sentences() {
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "-f", "jsonl", "--dedupe"])
+        .args(["--long-sentence-chars", "20"])
+        .write_stdin("Far too long a sentence. Far too long a sentence. Short. ")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let sentences: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(sentences.len(), 2);
+    // The duplicate's warning goes with the next sentence written
+    assert_eq!(sentences[1]["text"], "Short.");
+    assert_eq!(sentences[1]["warnings"][0]["offset"], 25);
+
+    // ...or with the last one, when no sentence follows
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "-f", "jsonl", "--dedupe"])
+        .args(["--long-sentence-chars", "20"])
+        .write_stdin("Far too long a sentence. Far too long a sentence.")
+        .output()
+        .unwrap();
+    let sentence: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sentence["warnings"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_process_ellipsis_policy() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ellipsis.txt");
+    fs::write(&path, "He waited... Then left. So um... Yes.").unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--ellipsis", "never", "-i"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("He waited... Then left.\nSo um... Yes.\n");
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--ellipsis", "always"])
+        .args(["--ellipsis-exception", r"waited\.\.\.", "-i"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("He waited... Then left.\nSo um...\nYes.\n");
+}
+
+#[test]
+fn test_process_social_profile() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("posts.txt");
+    fs::write(
+        &path,
+        "cc @jane.doe see example.com/a.b now 😂😂 Lol\nok bye",
+    )
+    .unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--profile", "social", "-i"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("cc @jane.doe see example.com/a.b now 😂😂\nLol\nok bye\n");
+}
+
+#[test]
+fn test_process_sentence_length_limits() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("lengths.txt");
+    fs::write(
+        &path,
+        "Ok. We left early. Then it rained all day long, so we stayed in.",
+    )
+    .unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--min-sentence-chars", "5"])
+        .args(["--max-sentence-chars", "30", "-i"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("Ok. We left early.\nThen it rained all day long,\nso we stayed in.\n");
+}
+
 #[test]
 fn test_json_output() {
     let mut cmd = Command::cargo_bin("sakurs").unwrap();
@@ -135,8 +336,8 @@ fn test_list_languages() {
 
     cmd.assert()
         .success()
-        .stdout(predicate::str::contains("english"))
-        .stdout(predicate::str::contains("japanese"));
+        .stdout(predicate::str::contains("English"))
+        .stdout(predicate::str::contains("Japanese"));
 }
 
 #[test]
@@ -228,3 +429,1083 @@ fn test_chunk_kb_invalid_zero() {
         "Chunk size must be greater than 0",
     ));
 }
+
+#[test]
+fn test_serve_split_over_http() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::process::{Command as StdCommand, Stdio};
+
+    let mut child = StdCommand::new(assert_cmd::cargo::cargo_bin("sakurs"))
+        .args(["serve", "--port", "0", "--workers", "1"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut line)
+        .unwrap();
+    let address = line
+        .trim()
+        .trim_start_matches("Listening on http://")
+        .to_string();
+
+    let body = "こんにちは。これは文です。";
+    let mut stream = TcpStream::connect(&address).unwrap();
+    write!(
+        stream,
+        "POST /split?language=ja HTTP/1.1\r\nHost: {address}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    child.kill().unwrap();
+    let _ = child.wait();
+
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(
+        response.contains(r#""text":"これは文です。""#),
+        "{response}"
+    );
+    assert!(response.contains(r#""language":"ja""#), "{response}");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_daemon_split_over_unix_socket() {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command as StdCommand, Stdio};
+
+    let dir = TempDir::new().unwrap();
+    let socket = dir.path().join("sakurs.sock");
+    let mut child = StdCommand::new(assert_cmd::cargo::cargo_bin("sakurs"))
+        .args(["daemon", "--socket"])
+        .arg(&socket)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut line)
+        .unwrap();
+    assert!(line.starts_with("Listening on"), "{line}");
+
+    let send = |args: &[&str], input: &str| {
+        Command::cargo_bin("sakurs")
+            .unwrap()
+            .args(["daemon", "--send", "--socket"])
+            .arg(&socket)
+            .args(args)
+            .write_stdin(input)
+            .assert()
+    };
+    send(&[], "Hello world. This is a test.")
+        .success()
+        .stdout("Hello world.\nThis is a test.\n");
+    send(&["-l", "ja"], "こんにちは。これは文です。")
+        .success()
+        .stdout("こんにちは。\nこれは文です。\n");
+
+    // A second daemon on the same socket is refused
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["daemon", "--socket"])
+        .arg(&socket)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Another daemon is listening"));
+
+    child.kill().unwrap();
+    let _ = child.wait();
+}
+
+#[cfg(feature = "arrow")]
+fn columnar_rows(batch: &arrow_array::RecordBatch) -> Vec<(String, u32, String, u64, u64)> {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{UInt32Type, UInt64Type};
+
+    let doc_id = batch.column(0).as_string::<i32>();
+    let index = batch.column(1).as_primitive::<UInt32Type>();
+    let text = batch.column(2).as_string::<i32>();
+    let start = batch.column(3).as_primitive::<UInt64Type>();
+    let end = batch.column(4).as_primitive::<UInt64Type>();
+    (0..batch.num_rows())
+        .map(|i| {
+            (
+                doc_id.value(i).to_string(),
+                index.value(i),
+                text.value(i).to_string(),
+                start.value(i),
+                end.value(i),
+            )
+        })
+        .collect()
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_arrow_and_parquet_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("a.txt");
+    let second = temp_dir.path().join("b.txt");
+    fs::write(&first, "One.  Two?").unwrap();
+    fs::write(&second, " Three.").unwrap();
+
+    let expected = vec![
+        (first.display().to_string(), 0, "One.".to_string(), 0, 4),
+        (first.display().to_string(), 1, "Two?".to_string(), 6, 10),
+        (second.display().to_string(), 0, "Three.".to_string(), 1, 7),
+    ];
+
+    let arrow_path = temp_dir.path().join("out.arrow");
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-f", "arrow", "-i"])
+        .arg(&first)
+        .arg("-i")
+        .arg(&second)
+        .arg("-o")
+        .arg(&arrow_path)
+        .assert()
+        .success();
+    let reader =
+        arrow_ipc::reader::FileReader::try_new(fs::File::open(&arrow_path).unwrap(), None).unwrap();
+    let rows: Vec<_> = reader
+        .flat_map(|batch| columnar_rows(&batch.unwrap()))
+        .collect();
+    assert_eq!(rows, expected);
+
+    let parquet_path = temp_dir.path().join("out.parquet");
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-f", "parquet", "-i"])
+        .arg(&first)
+        .arg("-i")
+        .arg(&second)
+        .arg("-o")
+        .arg(&parquet_path)
+        .assert()
+        .success();
+    let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReader::try_new(
+        fs::File::open(&parquet_path).unwrap(),
+        1024,
+    )
+    .unwrap();
+    let rows: Vec<_> = reader
+        .flat_map(|batch| columnar_rows(&batch.unwrap()))
+        .collect();
+    assert_eq!(rows, expected);
+}
+
+#[test]
+fn test_diff_against_sentence_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("input.txt");
+    let sentences = temp_dir.path().join("sentences.txt");
+    fs::write(&input, "Dr. Smith left. He came back. It rained.").unwrap();
+    fs::write(&sentences, "Dr.\nSmith left. He came back.\nIt rained.\n").unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["diff", "--exit-code", "--context", "6", "-i"])
+        .arg(&input)
+        .arg("--left-sentences")
+        .arg(&sentences)
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "1 only in left, 1 only in right, 1 shared",
+        ))
+        .stdout(predicate::str::contains("-        3  Dr.| Smith"))
+        .stdout(predicate::str::contains("+       15   left.| He ca"));
+
+    // The process output of the same rules matches exactly
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i"])
+        .arg(&input)
+        .output()
+        .unwrap();
+    fs::write(&sentences, output.stdout).unwrap();
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["diff", "--exit-code", "-i"])
+        .arg(&input)
+        .arg("--right-sentences")
+        .arg(&sentences)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "0 only in left, 0 only in right, 2 shared",
+        ));
+}
+
+#[test]
+fn test_evaluate_against_gold() {
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("input.txt");
+    let gold = temp_dir.path().join("gold.txt");
+    fs::write(&input, "Dr. Smith left. He came back. It rained.").unwrap();
+    fs::write(&gold, "Dr. Smith left.\nHe came back. It rained.\n").unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["evaluate", "--context", "5", "-i"])
+        .arg(&input)
+        .arg("-g")
+        .arg(&gold)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Precision: 0.5000 (1/2)"))
+        .stdout(predicate::str::contains("Recall:    1.0000 (1/1)"))
+        .stdout(predicate::str::contains("F1:        0.6667"))
+        .stdout(predicate::str::contains(
+            "False positives (1):\n        29  back.| It r",
+        ));
+
+    // Character offsets of the same gold annotation
+    fs::write(&gold, "15\n40\n").unwrap();
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .args([
+            "evaluate",
+            "--gold-format",
+            "char-offsets",
+            "-f",
+            "json",
+            "-i",
+        ])
+        .arg(&input)
+        .arg("-g")
+        .arg(&gold)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["recall"], 1.0);
+    assert_eq!(report["false_positives"][0]["offset"], 29);
+}
+
+#[test]
+fn test_learn_abbrev_writes_config_fragment() {
+    let temp_dir = TempDir::new().unwrap();
+    let corpus = temp_dir.path().join("corpus.txt");
+    let mut text = "We walked home along the river and talked about the weather. \
+                    The weather was fine and the river was calm when we got home. "
+        .repeat(20);
+    text.push_str("Ask Dr. Lee about Pt. 3 and pt. 4 for the river vs. the lake. ");
+    text.push_str("Dr. Kim saw Pt. 5 vs. the season. ");
+    fs::write(&corpus, text).unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["learn-abbrev", "-i"])
+        .arg(&corpus)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[abbreviations]\nlearned = [\n"))
+        .stdout(predicate::str::contains(
+            "    \"Pt\", # score 0.69, 3/3 with period\n",
+        ))
+        .stdout(predicate::str::contains("\"Dr\""));
+
+    // Abbreviations English already knows are left out
+    let fragment = temp_dir.path().join("learned.toml");
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args([
+            "learn-abbrev",
+            "-l",
+            "english",
+            "--category",
+            "clinical",
+            "-i",
+        ])
+        .arg(&corpus)
+        .arg("-o")
+        .arg(&fragment)
+        .assert()
+        .success();
+    let parsed: toml::Table = toml::from_str(&fs::read_to_string(&fragment).unwrap()).unwrap();
+    let clinical = parsed["abbreviations"]["clinical"].as_array().unwrap();
+    assert_eq!(clinical.len(), 1);
+    assert_eq!(clinical[0].as_str(), Some("Pt"));
+}
+
+#[test]
+fn test_process_with_config_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("input.txt");
+    fs::write(&input, "Ok. We left early.").unwrap();
+    let config_dir = temp_dir.path().join("xdg");
+    fs::create_dir_all(config_dir.join("sakurs")).unwrap();
+    fs::write(
+        config_dir.join("sakurs/config.toml"),
+        "[process]\nformat = \"markdown\"\nmin_sentence_chars = 5\n",
+    )
+    .unwrap();
+
+    // The default config file supplies the options
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .args(["process", "-i"])
+        .arg(&input)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("1. Ok. We left early."));
+
+    // Flags override the file
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .args(["process", "-f", "text", "--min-sentence-chars", "1", "-i"])
+        .arg(&input)
+        .assert()
+        .success()
+        .stdout("Ok.\nWe left early.\n");
+
+    // A switch the file turns on is turned off by its --no- form, and the
+    // last of the pair given wins
+    let switches = temp_dir.path().join("switches.toml");
+    fs::write(&switches, "[process]\nemoji_terminators = true\n").unwrap();
+    let emoji = temp_dir.path().join("emoji.txt");
+    fs::write(&emoji, "That was wild 😂 Next one.").unwrap();
+    for (flags, expected) in [
+        (&[][..], "That was wild 😂\nNext one.\n"),
+        (
+            &["--no-emoji-terminators"][..],
+            "That was wild 😂 Next one.\n",
+        ),
+        (
+            &["--no-emoji-terminators", "--emoji-terminators"][..],
+            "That was wild 😂\nNext one.\n",
+        ),
+    ] {
+        Command::cargo_bin("sakurs")
+            .unwrap()
+            .args(["process", "-f", "text", "--config"])
+            .arg(&switches)
+            .args(flags)
+            .arg("-i")
+            .arg(&emoji)
+            .assert()
+            .success()
+            .stdout(expected);
+    }
+
+    // --config replaces the default file
+    let other = temp_dir.path().join("other.toml");
+    fs::write(&other, "[process]\nformat = \"json\"\n").unwrap();
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .args(["process", "--config"])
+        .arg(&other)
+        .arg("-i")
+        .arg(&input)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("["));
+
+    fs::write(&other, "[process]\nformat = \"xml\"\n").unwrap();
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--config"])
+        .arg(&other)
+        .arg("-i")
+        .arg(&input)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("config `format`"));
+}
+
+#[test]
+fn test_config_init_and_show() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("xdg");
+    let sakurs = || {
+        let mut command = Command::cargo_bin("sakurs").unwrap();
+        command
+            .env("XDG_CONFIG_HOME", &config_dir)
+            .env_remove("SAKURS_CONFIG");
+        command
+    };
+
+    sakurs()
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No config file"));
+    sakurs().args(["config", "init"]).assert().success();
+    assert!(config_dir.join("sakurs/config.toml").is_file());
+    // An existing file is kept unless forced
+    sakurs().args(["config", "init"]).assert().failure();
+    sakurs()
+        .args(["config", "init", "--force"])
+        .assert()
+        .success();
+
+    fs::write(
+        config_dir.join("sakurs/config.toml"),
+        "[process]\nprofile = \"legal\"\n",
+    )
+    .unwrap();
+    sakurs()
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("profile = \"legal\""));
+}
+
+#[test]
+fn test_completions() {
+    for shell in ["bash", "zsh", "fish"] {
+        Command::cargo_bin("sakurs")
+            .unwrap()
+            .args(["completions", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("sakurs"))
+            // Language codes complete, not only the full names
+            .stdout(predicate::str::contains("jpn"));
+    }
+    // PowerShell scripts complete options but not their values
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["completions", "powershell"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--language"));
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_list_languages_with_language_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let english = fs::read_to_string("../sakurs-core/configs/languages/english.toml").unwrap();
+    fs::write(
+        temp_dir.path().join("legal_en.toml"),
+        english
+            .replacen("code = \"en\"", "code = \"en-legal\"", 1)
+            .replacen("name = \"English\"", "name = \"Legal English\"", 1),
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("broken.toml"), "not toml").unwrap();
+    fs::write(temp_dir.path().join("notes.txt"), "ignored").unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["list", "languages"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ja     Japanese"))
+        .stdout(predicate::str::contains("built-in"))
+        .stdout(predicate::str::contains("en-legal").not());
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["list", "languages", "--language-dir"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("en-legal Legal English"))
+        .stdout(predicate::str::contains("legal_en.toml"))
+        .stderr(predicate::str::contains("broken.toml"));
+}
+
+#[test]
+fn test_process_output_dir_refuses_overwrites() {
+    let temp_dir = TempDir::new().unwrap();
+    let docs = temp_dir.path().join("docs");
+    fs::create_dir_all(docs.join("a")).unwrap();
+    fs::create_dir_all(docs.join("b")).unwrap();
+    let input = docs.join("a.txt");
+    fs::write(&input, "Hello there. How are you?").unwrap();
+
+    // Text output next to the input would truncate it
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .arg("process")
+        .arg("-i")
+        .arg(&input)
+        .arg("--output-dir")
+        .arg(&docs)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("would overwrite the input"));
+    assert_eq!(
+        fs::read_to_string(&input).unwrap(),
+        "Hello there. How are you?"
+    );
+
+    // Inputs of the same name in different directories would collide
+    fs::write(docs.join("a/x.txt"), "First.").unwrap();
+    fs::write(docs.join("b/x.txt"), "Second.").unwrap();
+    let output_dir = temp_dir.path().join("out");
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .arg("process")
+        .arg("-i")
+        .arg(docs.join("a/x.txt"))
+        .arg("-i")
+        .arg(docs.join("b/x.txt"))
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("would both be written to"));
+    assert!(!output_dir.join("x.txt").exists());
+}
+
+#[test]
+fn test_process_output_dir_and_watch() {
+    use std::time::{Duration, Instant};
+
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("doc.txt");
+    let output_dir = temp_dir.path().join("out");
+    fs::write(&input, "Hello there. How are you?").unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .arg("process")
+        .arg("-i")
+        .arg(&input)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .args(["-f", "json", "-q"])
+        .assert()
+        .success();
+    let output = output_dir.join("doc.json");
+    assert!(fs::read_to_string(&output)
+        .unwrap()
+        .contains("How are you?"));
+
+    // --watch rewrites the output when the input changes
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("sakurs"))
+        .arg("process")
+        .arg("-i")
+        .arg(&input)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .args(["-f", "json", "-q", "--watch"])
+        .spawn()
+        .unwrap();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut updated = false;
+    while Instant::now() < deadline {
+        // Written repeatedly, as the watcher may not be running yet
+        std::thread::sleep(Duration::from_millis(500));
+        fs::write(&input, "First one. Second one.").unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+        if fs::read_to_string(&output).is_ok_and(|json| json.contains("Second one.")) {
+            updated = true;
+            break;
+        }
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(updated, "output was not rewritten");
+}
+
+#[test]
+fn test_process_watch_reloads_language_config() {
+    use std::time::{Duration, Instant};
+
+    let rules = |terminators: &str| {
+        format!(
+            "[metadata]\ncode = \"xx\"\nname = \"Test\"\n\n[terminators]\nchars = {terminators}\n\n\
+             [ellipsis]\npatterns = []\n\n[enclosures]\npairs = []\n\n[suppression]\n"
+        )
+    };
+    let temp_dir = TempDir::new().unwrap();
+    let config = temp_dir.path().join("rules.toml");
+    let input = temp_dir.path().join("doc.txt");
+    let output_dir = temp_dir.path().join("out");
+    fs::write(&config, rules(r#"[".", "!"]"#)).unwrap();
+    fs::write(&input, "Wow! Done.").unwrap();
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("sakurs"))
+        .arg("process")
+        .arg("-i")
+        .arg(&input)
+        .arg("--language-config")
+        .arg(&config)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .args(["-f", "json", "-q", "--watch"])
+        .spawn()
+        .unwrap();
+    let output = output_dir.join("doc.json");
+
+    // Files reprocessed after the rules change use the new rules
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut reloaded = false;
+    while Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(500));
+        fs::write(&config, rules(r#"["."]"#)).unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+        fs::write(&input, "Wow! Done.").unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+        if fs::read_to_string(&output).is_ok_and(|json| json.contains("Wow! Done.")) {
+            reloaded = true;
+            break;
+        }
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(reloaded, "new rules were not applied");
+}
+
+#[test]
+fn test_process_log_format_json() {
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .arg("process")
+        .arg("-i")
+        .arg(fixture_path("english-sample.txt"))
+        .args(["--log-format", "json", "-v"])
+        .env_remove("RUST_LOG")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let records: Vec<serde_json::Value> = String::from_utf8(output.stderr)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(records
+        .iter()
+        .all(|r| r["level"].is_string() && r["message"].is_string()));
+    let file = records
+        .iter()
+        .find(|r| r.get("boundaries").is_some())
+        .expect("a per-file record");
+    assert!(file["file"]
+        .as_str()
+        .unwrap()
+        .ends_with("english-sample.txt"));
+    assert!(file["boundaries"].as_u64().unwrap() > 0);
+    assert!(file["duration_ms"].is_number());
+}
+
+#[test]
+fn test_process_partial_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("a.txt"), "First file. Done.").unwrap();
+    fs::write(temp_dir.path().join("b.txt"), b"Bad \xff bytes.").unwrap();
+    fs::write(temp_dir.path().join("c.txt"), "Third file.").unwrap();
+    let pattern = temp_dir.path().join("*.txt");
+    let report = temp_dir.path().join("report.json");
+
+    // The other files are still processed, and the failure is reported
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .arg("process")
+        .arg("-i")
+        .arg(&pattern)
+        .arg("--error-report")
+        .arg(&report)
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains("First file."))
+        .stdout(predicate::str::contains("Third file."))
+        .stderr(predicate::str::contains("Failed to process 1 of 3 files"));
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(report["processed"], 2);
+    assert!(report["failed"][0]["file"]
+        .as_str()
+        .unwrap()
+        .ends_with("b.txt"));
+    assert!(report["failed"][0]["error"].is_string());
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .arg("process")
+        .arg("-i")
+        .arg(&pattern)
+        .arg("--fail-fast")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Third file.").not());
+}
+
+#[test]
+fn test_process_compressed_input_and_output() {
+    use std::io::Write;
+
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("corpus.txt.gz");
+    let mut encoder = flate2::write::GzEncoder::new(
+        fs::File::create(&input).unwrap(),
+        flate2::Compression::default(),
+    );
+    encoder.write_all(b"Packed text. Still found.").unwrap();
+    encoder.finish().unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .arg("process")
+        .arg("-i")
+        .arg(&input)
+        .assert()
+        .success()
+        .stdout("Packed text.\nStill found.\n");
+
+    // The output is compressed by its extension or by the flag
+    for (name, flag) in [("out.txt.gz", None), ("out.txt", Some("zstd"))] {
+        let output = temp_dir.path().join(name);
+        let mut command = Command::cargo_bin("sakurs").unwrap();
+        command
+            .arg("process")
+            .arg("-i")
+            .arg(&input)
+            .arg("-o")
+            .arg(&output);
+        if let Some(compression) = flag {
+            command.args(["--output-compression", compression]);
+        }
+        command.assert().success();
+        let bytes = sakurs_cli::compression::read_to_end(fs::File::open(&output).unwrap()).unwrap();
+        assert_eq!(bytes, b"Packed text.\nStill found.\n", "{name}");
+    }
+}
+
+#[test]
+fn test_process_archive_members() {
+    use std::io::Write;
+
+    let temp_dir = TempDir::new().unwrap();
+    let archive = temp_dir.path().join("corpus.zip");
+    let mut writer = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+    for (name, text) in [
+        ("docs/a.txt", &b"First doc. Second sentence."[..]),
+        ("docs/bad.txt", b"Bad \xff bytes."),
+        ("docs/b.txt", b"Other doc."),
+    ] {
+        writer
+            .start_file(name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(text).unwrap();
+    }
+    writer.finish().unwrap();
+
+    // Each member is a document; a bad member is reported like a bad file
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .arg("process")
+        .arg("-i")
+        .arg(&archive)
+        .args(["-f", "jsonl"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    let records: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let ids: Vec<(&str, u64)> = records
+        .iter()
+        .map(|r| {
+            (
+                r["doc_id"].as_str().unwrap(),
+                r["sentence_index"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        ids,
+        [("docs/a.txt", 0), ("docs/a.txt", 1), ("docs/b.txt", 0)]
+    );
+    assert_eq!(records[1]["text"], "Second sentence.");
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("docs/bad.txt"));
+}
+
+#[test]
+fn test_process_nul_delimited_documents() {
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "--docs", "nul"])
+        .write_stdin("Dr. Smith left. He waved.\0Second doc.\0")
+        .assert()
+        .success()
+        .stdout("Dr. Smith left.\nHe waved.\n\0Second doc.\n\0");
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "--docs", "nul", "-f", "jsonl"])
+        .write_stdin("First doc.\0Second doc.")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""doc_id":"1""#));
+
+    // Formats without document boundaries are refused
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "--docs", "nul", "-f", "json"])
+        .write_stdin("First doc.\0Second doc.")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_process_with_corrections() {
+    let temp_dir = TempDir::new().unwrap();
+    let corrections = temp_dir.path().join("corrections.toml");
+    // Document "0" keeps "Pt." attached and splits before "stable", document
+    // "1" has no corrections
+    fs::write(
+        &corrections,
+        r#"
+[[correction]]
+document = "0"
+offset = 3
+action = "reject"
+
+[[correction]]
+document = "0"
+offset = 13
+action = "accept"
+"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "--docs", "nul", "--corrections"])
+        .arg(&corrections)
+        .write_stdin("Pt. seen and stable now. Done.\0Pt. seen again.")
+        .assert()
+        .success()
+        .stdout("Pt. seen and\nstable now.\nDone.\n\0Pt.\nseen again.\n\0");
+
+    // Offsets must fall on a character boundary of the document
+    fs::write(
+        &corrections,
+        "[[correction]]\ndocument = \"-\"\noffset = 2\naction = \"accept\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "--corrections"])
+        .arg(&corrections)
+        .write_stdin("あ。い。")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("offset 2 is inside a character"));
+}
+
+#[test]
+fn test_process_dump_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = temp_dir.path().join("config.toml");
+    fs::write(
+        &config,
+        "[process]\nlanguage = \"ja\"\nmin_sentence_chars = 4\n",
+    )
+    .unwrap();
+
+    // The file's options and the flags, resolved over the defaults; no
+    // input is needed
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--dump-config", "--chunk-kb", "64", "--config"])
+        .arg(&config)
+        .args(["--enclosure-boundaries", "「=dialogue"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("language = \"ja\"\n"))
+        .stdout(predicate::str::contains("chunk_size = 65536\n"))
+        .stdout(predicate::str::contains("min_sentence_chars = 4\n"))
+        .stdout(predicate::str::contains("invalid_utf8 = \"error\"\n"))
+        .stdout(predicate::str::contains("\"「\" = \"dialogue\""));
+}
+
+#[test]
+fn test_process_perf_profile() {
+    // The preset's values, with explicit flags taking precedence
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--dump-config", "--perf-profile", "container-2c"])
+        .args(["--chunk-kb", "64"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("max_threads = 2\n"))
+        .stdout(predicate::str::contains("adaptive_threshold = 1048576\n"))
+        .stdout(predicate::str::contains("chunk_size = 65536\n"));
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "--dump-config", "--perf-profile", "desktop"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "laptop, server-32c or container-2c",
+        ));
+}
+
+#[test]
+fn test_process_writes_fingerprints() {
+    let temp_dir = TempDir::new().unwrap();
+    let fingerprints = |threads: &str, text: &str| {
+        let path = temp_dir.path().join("fingerprints.txt");
+        Command::cargo_bin("sakurs")
+            .unwrap()
+            .args(["process", "-i", "-", "--docs", "nul", "--threads", threads])
+            .arg("--fingerprint")
+            .arg(&path)
+            .write_stdin(text)
+            .assert()
+            .success();
+        fs::read_to_string(&path).unwrap()
+    };
+
+    let first = fingerprints("1", "One. Two.\0Three. Four.");
+    let lines: Vec<_> = first.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("  0") && lines[1].ends_with("  1"));
+    assert_eq!(lines[0].split_once("  ").unwrap().0.len(), 16);
+    // The same segmentation on more threads fingerprints the same
+    assert_eq!(fingerprints("4", "One. Two.\0Three. Four."), first);
+    // Other boundaries do not
+    let changed = fingerprints("1", "One. Two.\0Three four.");
+    assert_eq!(changed.lines().next(), Some(lines[0]));
+    assert_ne!(changed.lines().nth(1), Some(lines[1]));
+}
+
+#[test]
+fn test_json_output_boundary_kinds() {
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "-f", "json"])
+        .write_stdin("Stop! It is 3.5 m long. No terminator")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let sentences: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let kinds: Vec<&str> = sentences
+        .iter()
+        .map(|s| s["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["strong", "weak", "end_of_text"]);
+}
+
+#[test]
+fn test_jsonl_document_fields_and_input_offsets() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("a.txt");
+    let second = temp_dir.path().join("b.txt");
+    // "あ。い。" in Shift_JIS: two bytes per character
+    fs::write(&first, b"\x82\xa0\x81\x42\x82\xa2\x81\x42").unwrap();
+    fs::write(&second, "  Hi! Bye!").unwrap();
+
+    let output = Command::cargo_bin("sakurs")
+        .unwrap()
+        .args([
+            "process",
+            "-l",
+            "ja",
+            "--encoding",
+            "shift_jis",
+            "-f",
+            "jsonl",
+        ])
+        .arg("-i")
+        .arg(&first)
+        .arg("-i")
+        .arg(&second)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let records: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let rows: Vec<_> = records
+        .iter()
+        .map(|r| {
+            (
+                r["doc_path"].as_str().unwrap().to_string(),
+                r["doc_index"].as_u64().unwrap(),
+                r["sentence_id"].as_u64().unwrap(),
+                r["start"].as_u64().unwrap(),
+                r["end"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    // Offsets are bytes of each file as written, not of the decoded text
+    let (a, b) = (first.display().to_string(), second.display().to_string());
+    assert_eq!(
+        rows,
+        [
+            (a.clone(), 0, 0, 0, 4),
+            (a, 0, 1, 4, 8),
+            (b.clone(), 1, 2, 2, 5),
+            (b, 1, 3, 6, 10),
+        ]
+    );
+    assert_eq!(records[1]["text"], "い。");
+    // How each sentence ends, as in JSON output
+    let kinds: Vec<&str> = records
+        .iter()
+        .map(|r| r["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["weak", "weak", "strong", "strong"]);
+}
+
+#[test]
+fn test_process_files_concurrently_keeps_input_order() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut files = Vec::new();
+    for i in 0..12 {
+        let path = temp_dir.path().join(format!("doc{i:02}.txt"));
+        fs::write(&path, format!("Document {i}. It has two sentences.")).unwrap();
+        files.push(path);
+    }
+
+    let run = |jobs: &str| {
+        let mut cmd = Command::cargo_bin("sakurs").unwrap();
+        cmd.args(["process", "-f", "jsonl", "--jobs", jobs]);
+        for file in &files {
+            cmd.arg("-i").arg(file);
+        }
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let sequential = run("1");
+    assert_eq!(sequential.lines().count(), 24);
+    assert_eq!(run("4"), sequential);
+}
+
+#[test]
+fn test_process_dedupe() {
+    let input = "Click here. Read more. click  HERE.\0Read more. New text.\0";
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "--docs", "nul", "--dedupe"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout("Click here.\nRead more.\n\0Read more.\nNew text.\n\0");
+
+    Command::cargo_bin("sakurs")
+        .unwrap()
+        .args(["process", "-i", "-", "--docs", "nul", "--dedupe", "run"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout("Click here.\nRead more.\n\0New text.\n\0");
+}
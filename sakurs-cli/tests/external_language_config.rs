@@ -1,5 +1,7 @@
 //! Integration tests for external language configuration
 
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use assert_cmd::Command;
 use predicates::prelude::*;
 use std::fs;
@@ -1,7 +1,7 @@
 //! Profiling benchmark to determine optimal thresholds for adaptive processing
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use sakurs_core::{Config, Input, SentenceProcessor};
+use sakurs_core::{Config, Input, PerfProfile, SentenceProcessor};
 use std::hint::black_box;
 
 /// Generate test text of specified size
@@ -141,11 +141,45 @@ fn bench_crossover_points(c: &mut Criterion) {
     group.finish();
 }
 
+/// Compare the machine-class presets with the defaults on this machine
+fn bench_perf_profiles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("perf_profiles");
+    group.sample_size(10);
+
+    let presets = [
+        None,
+        Some(PerfProfile::Laptop),
+        Some(PerfProfile::Server32c),
+        Some(PerfProfile::Container2c),
+    ];
+    for size_kb in [100, 1000, 10000] {
+        let text = generate_text(size_kb);
+        group.throughput(Throughput::Bytes(text.len() as u64));
+
+        for preset in presets {
+            let mut builder = Config::builder();
+            if let Some(preset) = preset {
+                builder = builder.perf_profile(preset);
+            }
+            let processor = SentenceProcessor::with_config(builder.build().unwrap()).unwrap();
+            let name = preset.map_or("default", PerfProfile::name);
+            group.bench_with_input(
+                BenchmarkId::new(name, format!("{}KB", size_kb)),
+                &text,
+                |b, text| b.iter(|| processor.process(Input::from_text(black_box(text)))),
+            );
+        }
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_sequential_by_size,
     bench_parallel_by_size,
     bench_chunk_size_impact,
-    bench_crossover_points
+    bench_crossover_points,
+    bench_perf_profiles
 );
 criterion_main!(benches);
@@ -0,0 +1,195 @@
+//! Heap allocations per input byte of each executor.
+//!
+//! A counting global allocator wraps the system's and tallies every
+//! allocation on every thread while one executor segments the same text,
+//! after a warm-up run that pays for one-time setup (thread pools, lazily
+//! built tables). Counts, unlike timings, are stable across machines, so
+//! they can gate regressions:
+//!
+//! ```bash
+//! cargo bench --bench allocations
+//! # Fail if any executor makes more than 0.01 allocations per byte
+//! SAKURS_ALLOC_BUDGET=0.01 cargo bench --bench allocations
+//! ```
+
+use sakurs_core::{Config, Input, SentenceProcessor};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::Cursor;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+mod common;
+
+/// The system allocator, counting what passes through it
+struct Counting;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// A named run of one executor over the benchmark's input
+type Executor<'a> = (&'static str, Box<dyn Fn() + 'a>);
+
+const SIZE: usize = 1024 * 1024;
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Allocations and bytes allocated by `run`
+fn count(run: impl Fn()) -> (u64, u64) {
+    run();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    run();
+    (
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        BYTES.load(Ordering::Relaxed) - bytes,
+    )
+}
+
+fn processor(threads: Option<usize>, read_ahead: bool) -> SentenceProcessor {
+    let config = Config::builder()
+        .language("en")
+        .expect("language config should load")
+        .chunk_size(CHUNK_SIZE)
+        .threads(threads)
+        .read_ahead(read_ahead)
+        .build()
+        .expect("config should validate");
+    SentenceProcessor::with_config(config).expect("processor should build")
+}
+
+fn main() -> ExitCode {
+    // `cargo bench` passes `--bench`; `cargo test --benches` does not, and
+    // then one small run checks that the benchmark works
+    let size = if std::env::args().any(|arg| arg == "--bench") {
+        SIZE
+    } else {
+        16 * 1024
+    };
+    let budget: Option<f64> = std::env::var("SAKURS_ALLOC_BUDGET").ok().map(|budget| {
+        budget
+            .parse()
+            .expect("SAKURS_ALLOC_BUDGET should be a number")
+    });
+
+    let text = common::sample_text("en", size);
+    let dir = tempfile::tempdir().expect("temporary directory");
+    let path = dir.path().join("input.txt");
+    std::fs::write(&path, &text).expect("input file should be written");
+
+    let sequential = processor(Some(1), false);
+    let parallel = processor(Some(4), false);
+    let adaptive = processor(None, false);
+    let read_ahead = processor(Some(4), true);
+    let executors: [Executor; 7] = [
+        (
+            "sequential",
+            Box::new(|| {
+                sequential.process(Input::from_text(text.as_str())).unwrap();
+            }),
+        ),
+        (
+            "parallel_4t",
+            Box::new(|| {
+                parallel.process(Input::from_text(text.as_str())).unwrap();
+            }),
+        ),
+        (
+            "adaptive",
+            Box::new(|| {
+                adaptive.process(Input::from_text(text.as_str())).unwrap();
+            }),
+        ),
+        (
+            "read_ahead_4t",
+            Box::new(|| {
+                read_ahead.process(Input::from_file(&path)).unwrap();
+            }),
+        ),
+        (
+            "stream",
+            Box::new(|| {
+                let mut stream = sequential.stream();
+                for piece in common::chunks(&text, CHUNK_SIZE) {
+                    stream.feed_spans(piece);
+                }
+                stream.finish_spans();
+            }),
+        ),
+        (
+            "reader",
+            Box::new(|| {
+                let reader = Cursor::new(text.clone().into_bytes());
+                for span in sequential.sentences_from_reader(reader) {
+                    span.unwrap();
+                }
+            }),
+        ),
+        (
+            "pipeline",
+            Box::new(|| {
+                let reader = Cursor::new(text.clone().into_bytes());
+                for span in sequential.pipeline_from_reader(reader).unwrap() {
+                    span.unwrap();
+                }
+            }),
+        ),
+    ];
+
+    println!(
+        "{} bytes of input, chunks of {CHUNK_SIZE} bytes",
+        text.len()
+    );
+    println!(
+        "{:<16} {:>12} {:>14} {:>12} {:>12}",
+        "executor", "allocations", "bytes", "allocs/byte", "bytes/byte"
+    );
+    let mut over_budget = Vec::new();
+    for (name, run) in &executors {
+        let (allocations, bytes) = count(run);
+        let per_byte = allocations as f64 / text.len() as f64;
+        println!(
+            "{name:<16} {allocations:>12} {bytes:>14} {per_byte:>12.5} {:>12.2}",
+            bytes as f64 / text.len() as f64
+        );
+        if budget.is_some_and(|budget| per_byte > budget) {
+            over_budget.push(*name);
+        }
+    }
+
+    if over_budget.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!(
+            "Over the budget of {} allocations per byte: {}",
+            budget.unwrap_or_default(),
+            over_budget.join(", ")
+        );
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,67 @@
+//! Sample texts shared by the benchmarks
+//!
+//! Each language has a paragraph mixing the constructs the rules spend time
+//! on (abbreviations, numbers, quotes, parentheses, contractions), repeated
+//! to the size a benchmark needs.
+
+// Each benchmark uses only some of the helpers
+#![allow(dead_code)]
+
+/// English sample paragraph
+pub const EN_UNIT: &str = "Dr. Smith met Mr. Jones at the U.S. embassy at 3 p.m. today. \
+He said \"Hello there. It's me.\" and (quietly) left the room! \
+Was it 3.5 km away? Nobody knew... Items: 1) bread, 2) milk. \
+The company Inc. didn't report its Q3 results, e.g. revenue. ";
+
+/// Japanese sample paragraph
+pub const JA_UNIT: &str = "彼は「こんにちは。元気ですか？」と言った。今日は良い天気です。\
+（注：これは例です。）次の文は短い。彼女は『それは素晴らしい』と答えた！\
+明日も晴れるでしょうか？……本当に。午後3時に会議が3.5時間ありました。";
+
+/// The sample paragraph of `lang` ("en" or "ja")
+pub fn unit(lang: &str) -> &'static str {
+    match lang {
+        "ja" => JA_UNIT,
+        _ => EN_UNIT,
+    }
+}
+
+/// The sample paragraph of `lang` repeated to `size` bytes, cut at a
+/// character boundary
+pub fn sample_text(lang: &str, size: usize) -> String {
+    let unit = unit(lang);
+    let mut text = unit.repeat(size / unit.len() + 1);
+    text.truncate(floor_char_boundary(&text, size));
+    text
+}
+
+/// `text` cut into pieces of about `chunk_size` bytes at character
+/// boundaries
+pub fn chunks(text: &str, chunk_size: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + chunk_size.max(1)).min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        pieces.push(&text[start..end]);
+        start = end;
+    }
+    pieces
+}
+
+/// Byte offsets of every occurrence of any of `chars` in `text`
+pub fn offsets_of(text: &str, chars: &[char]) -> Vec<usize> {
+    text.char_indices()
+        .filter(|(_, ch)| chars.contains(ch))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
@@ -0,0 +1,124 @@
+//! Micro-benchmarks of the algorithm's phases in isolation.
+//!
+//! Standard numbers for performance work on the scan (`scan_chunk`), the
+//! monoid reduce over chunk states, enclosure suppression and abbreviation
+//! trie lookups, on English and Japanese samples. Needs the internal entry
+//! points of the `bench-internals` feature:
+//!
+//! ```bash
+//! cargo bench -p sakurs-core --features bench-internals --bench phases -- --save-baseline before
+//! # ... change ...
+//! cargo bench -p sakurs-core --features bench-internals --bench phases -- --baseline before
+//! ```
+
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sakurs_core::internals::{self, Rules};
+use std::hint::black_box;
+use std::time::Duration;
+
+const LANGUAGES: [&str; 2] = ["en", "ja"];
+
+/// Text per benchmark; large enough to span many chunks
+const TEXT_SIZE: usize = 256 * 1024;
+
+fn rules(lang: &str) -> Rules {
+    Rules::for_language(lang).expect("built-in language should compile")
+}
+
+fn configure<'a>(
+    c: &'a mut Criterion,
+    name: &str,
+) -> criterion::BenchmarkGroup<'a, criterion::measurement::WallTime> {
+    let mut group = c.benchmark_group(name);
+    group
+        .sample_size(20)
+        .measurement_time(Duration::from_secs(4))
+        .warm_up_time(Duration::from_secs(1));
+    group
+}
+
+fn bench_scan_chunk(c: &mut Criterion) {
+    let mut group = configure(c, "scan_chunk");
+    for lang in LANGUAGES {
+        let rules = rules(lang);
+        for chunk_kb in [4usize, 64] {
+            let text = common::sample_text(lang, chunk_kb * 1024);
+            group.throughput(Throughput::Bytes(text.len() as u64));
+            group.bench_with_input(
+                BenchmarkId::new(lang, format!("chunk_{chunk_kb}k")),
+                &text,
+                |b, t| b.iter(|| internals::scan_chunk(black_box(t), &rules)),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_reduce(c: &mut Criterion) {
+    let mut group = configure(c, "monoid_reduce");
+    for lang in LANGUAGES {
+        let rules = rules(lang);
+        let text = common::sample_text(lang, TEXT_SIZE);
+        for chunk_kb in [1usize, 16] {
+            let states: Vec<_> = common::chunks(&text, chunk_kb * 1024)
+                .into_iter()
+                .map(|chunk| internals::scan_chunk(chunk, &rules))
+                .collect();
+            assert!(internals::reduce(&states, &rules).boundary_count() > 0);
+            group.throughput(Throughput::Elements(states.len() as u64));
+            group.bench_with_input(
+                BenchmarkId::new(lang, format!("{}_states", states.len())),
+                &states,
+                |b, s| b.iter(|| internals::reduce(black_box(s), &rules)),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_enclosure_suppression(c: &mut Criterion) {
+    let mut group = configure(c, "enclosure_suppression");
+    for lang in LANGUAGES {
+        let rules = rules(lang);
+        let text = common::sample_text(lang, TEXT_SIZE);
+        let offsets = common::offsets_of(&text, &['\'', '’', '(', ')', '（', '）']);
+        group.throughput(Throughput::Elements(offsets.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(lang), &offsets, |b, o| {
+            b.iter(|| {
+                o.iter()
+                    .filter(|&&offset| rules.suppresses_enclosure(&text, offset))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_abbreviation_lookup(c: &mut Criterion) {
+    let mut group = configure(c, "abbreviation_lookup");
+    for lang in LANGUAGES {
+        let rules = rules(lang);
+        let text = common::sample_text(lang, TEXT_SIZE);
+        let periods = common::offsets_of(&text, &['.']);
+        group.throughput(Throughput::Elements(periods.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(lang), &periods, |b, p| {
+            b.iter(|| {
+                p.iter()
+                    .filter_map(|&period| rules.abbreviation_before(&text, period))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_scan_chunk,
+    bench_reduce,
+    bench_enclosure_suppression,
+    bench_abbreviation_lookup
+);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+//! Parallel executor throughput with reused scan buffers.
+//!
+//! A `SentenceProcessor` keeps the candidate buffers of its last run and
+//! hands them to the next one, so repeated calls scan into warm allocations
+//! instead of growing fresh vectors per chunk. Many small chunks make the
+//! allocation share of the scan phase visible:
+//!
+//! ```bash
+//! cargo bench --bench scan_arena -- --save-baseline before
+//! # ... change ...
+//! cargo bench --bench scan_arena -- --baseline before
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sakurs_core::{Config, Input, SentenceProcessor};
+use std::time::Duration;
+
+const UNIT: &str = "The quick brown fox jumps over the lazy dog. Dr. Smith said \"Hello.\" \
+Then (after a pause) he left! Was it 3.5 p.m.? Nobody knew. ";
+
+fn processor(chunk_size: usize, threads: usize) -> SentenceProcessor {
+    let config = Config::builder()
+        .language("en")
+        .expect("language config should load")
+        .chunk_size(chunk_size)
+        .threads(Some(threads))
+        .build()
+        .expect("config should validate");
+    SentenceProcessor::with_config(config).expect("processor should build")
+}
+
+fn bench_parallel_executor(c: &mut Criterion) {
+    let size = 2 * 1024 * 1024;
+    let text = UNIT.repeat(size / UNIT.len());
+
+    let mut group = c.benchmark_group("scan_arena");
+    group
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(8))
+        .warm_up_time(Duration::from_secs(1));
+    group.throughput(Throughput::Bytes(text.len() as u64));
+
+    // Timings are only comparable if every chunking yields the same result
+    processor(64 * 1024, 4)
+        .verify_determinism(&UNIT.repeat(4), 64)
+        .expect("benchmark input should segment deterministically");
+
+    for chunk_kb in [4usize, 16, 64] {
+        let p = processor(chunk_kb * 1024, 4);
+        group.bench_with_input(
+            BenchmarkId::new("parallel_4t", format!("chunk_{chunk_kb}k")),
+            &text,
+            |b, t| {
+                b.iter(|| {
+                    p.process(Input::from_text(t.as_str()))
+                        .expect("processing should succeed")
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_executor);
+criterion_main!(benches);
@@ -0,0 +1,333 @@
+//! Compiles the bundled language configurations into static tables.
+//!
+//! Each `configs/languages/*.toml` file is parsed here, at build time, and
+//! written to `$OUT_DIR/embedded_languages.rs` as a `LanguageTable` (see
+//! `src/domain/language/config/table.rs`), so the library never parses TOML
+//! for a bundled language. Keys the configuration schema defaults are filled
+//! in with the same defaults as its serde attributes.
+
+use std::env;
+use std::fmt::{Display, Write as _};
+use std::fs;
+use std::path::Path;
+
+use toml::{Table, Value};
+
+/// Bundled languages as (code, file under `configs/languages`)
+const LANGUAGES: [(&str, &str); 7] = [
+    ("en", "english.toml"),
+    ("ja", "japanese.toml"),
+    ("pt", "portuguese.toml"),
+    ("it", "italian.toml"),
+    ("ar", "arabic.toml"),
+    ("th", "thai.toml"),
+    ("hi", "hindi.toml"),
+];
+
+/// Keys of `[sentence_starters]` that are settings rather than categories
+const STARTER_SETTINGS: [&str; 4] = [
+    "require_following_space",
+    "min_word_length",
+    "threshold",
+    "uppercase_fallback",
+];
+
+fn main() {
+    let mut languages = LANGUAGES;
+    languages.sort_by_key(|&(code, _)| code);
+
+    let mut out = String::from("// Generated by build.rs from configs/languages; do not edit.\n\n");
+    writeln!(
+        out,
+        "static EMBEDDED: [LanguageTable; {}] = [",
+        languages.len()
+    )
+    .unwrap();
+    for (code, file) in languages {
+        let path = Path::new("configs/languages").join(file);
+        println!("cargo:rerun-if-changed={}", path.display());
+        let source = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+        let config: Table = toml::from_str(&source)
+            .unwrap_or_else(|e| panic!("failed to parse {code} config: {e}"));
+        let found = str_at(&config, "metadata", "code");
+        assert_eq!(
+            found, code,
+            "config code mismatch in {file}: expected {code}, got {found}"
+        );
+        write_language(&mut out, &config);
+    }
+    out.push_str("];\n");
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_languages.rs");
+    fs::write(dest, out).unwrap();
+}
+
+fn write_language(out: &mut String, config: &Table) {
+    let terminators = section(config, "terminators");
+    let ellipsis = section(config, "ellipsis");
+    let enclosures = section(config, "enclosures");
+    let suppression = section(config, "suppression");
+
+    out.push_str("    LanguageTable {\n");
+    field(
+        out,
+        "code",
+        format!("{:?}", str_at(config, "metadata", "code")),
+    );
+    field(
+        out,
+        "name",
+        format!("{:?}", str_at(config, "metadata", "name")),
+    );
+
+    field(out, "terminators", chars(terminators.get("chars")));
+    let patterns = tables(terminators.get("patterns"))
+        .map(|p| format!("({:?}, {:?})", str_of(&p["pattern"]), str_of(&p["name"])));
+    field(out, "terminator_patterns", slice(patterns));
+    field(
+        out,
+        "ordinal_indicators",
+        chars(terminators.get("ordinal_indicators")),
+    );
+    field(
+        out,
+        "collapse_runs",
+        flag(terminators, "collapse_runs", true),
+    );
+    field(out, "emoji", flag(terminators, "emoji", false));
+
+    field(
+        out,
+        "ellipsis_treat_as_boundary",
+        flag(ellipsis, "treat_as_boundary", true),
+    );
+    field(out, "ellipsis_patterns", strs(ellipsis.get("patterns")));
+    let rules = tables(ellipsis.get("context_rules")).map(|r| {
+        format!(
+            "({:?}, {})",
+            str_of(&r["condition"]),
+            r["boundary"].as_bool().unwrap()
+        )
+    });
+    field(out, "ellipsis_context_rules", slice(rules));
+    let exceptions = tables(ellipsis.get("exceptions")).map(|e| {
+        format!(
+            "({:?}, {})",
+            str_of(&e["regex"]),
+            e["boundary"].as_bool().unwrap()
+        )
+    });
+    field(out, "ellipsis_exceptions", slice(exceptions));
+
+    let pairs = tables(enclosures.get("pairs")).map(|p| {
+        let boundaries = match p.get("boundaries").map(str_of) {
+            None | Some("suppress") => "Suppress",
+            Some("allow") => "Allow",
+            Some("strong_only") => "StrongOnly",
+            Some("dialogue") => "Dialogue",
+            Some(other) => panic!("unknown enclosure boundaries {other:?}"),
+        };
+        format!(
+            "({:?}, {:?}, {}, EnclosureBoundaries::{boundaries})",
+            char_of(&p["open"]),
+            char_of(&p["close"]),
+            flag(p, "symmetric", false)
+        )
+    });
+    field(out, "enclosures", slice(pairs));
+
+    let fast = tables(suppression.get("fast_patterns")).map(|p| {
+        format!(
+            "({:?}, {}, {}, {})",
+            char_of(&p["char"]),
+            flag(p, "line_start", false),
+            optional_str(p.get("before")),
+            optional_str(p.get("after"))
+        )
+    });
+    field(out, "fast_patterns", slice(fast));
+    let regexes = tables(suppression.get("regex_patterns")).map(|p| {
+        format!(
+            "({:?}, {})",
+            str_of(&p["pattern"]),
+            optional_str(p.get("description"))
+        )
+    });
+    field(out, "regex_patterns", slice(regexes));
+    field(
+        out,
+        "trailing_patterns",
+        strs(suppression.get("trailing_patterns")),
+    );
+    field(out, "web_tokens", flag(suppression, "web_tokens", false));
+    field(out, "citations", flag(suppression, "citations", false));
+    let apostrophes = match suppression.get("apostrophes").and_then(Value::as_table) {
+        Some(a) => format!(
+            "Some(ApostropheTable {{ chars: {}, contractions: {}, possessives: {}, \
+             years: {}, elided_starts: {}, elided_ends: {} }})",
+            a.get("chars")
+                .map_or_else(|| format!("&{:?}", ['\'', '\u{2019}']), |c| chars(Some(c))),
+            flag(a, "contractions", true),
+            flag(a, "possessives", true),
+            flag(a, "years", true),
+            strs(a.get("elided_starts")),
+            strs(a.get("elided_ends"))
+        ),
+        None => "None".to_string(),
+    };
+    field(out, "apostrophes", apostrophes);
+
+    let abbreviations = config
+        .get("abbreviations")
+        .and_then(Value::as_table)
+        .map(abbreviation_categories)
+        .unwrap_or_else(|| "&[]".to_string());
+    field(out, "abbreviations", abbreviations);
+
+    let starters = match config.get("sentence_starters").and_then(Value::as_table) {
+        Some(s) => format!(
+            "Some(StarterTable {{ categories: {}, require_following_space: {}, \
+             min_word_length: {}, threshold: {:?}, uppercase_fallback: {:?} }})",
+            starter_categories(s),
+            flag(s, "require_following_space", true),
+            s.get("min_word_length")
+                .and_then(Value::as_integer)
+                .unwrap_or(1),
+            float(s, "threshold").unwrap_or(0.5),
+            float(s, "uppercase_fallback")
+        ),
+        None => "None".to_string(),
+    };
+    field(out, "sentence_starters", starters);
+
+    let hypothesis = match config.get("hypothesis").and_then(Value::as_table) {
+        Some(h) => format!(
+            "Some(HypothesisTable {{ strategy: {:?}, min_chars: {}, \
+             sentence_final_particles: {}, discourse_markers: {}, \
+             continuation_markers: {} }})",
+            str_of(&h["strategy"]),
+            h.get("min_chars").and_then(Value::as_integer).unwrap_or(8),
+            strs(h.get("sentence_final_particles")),
+            strs(h.get("discourse_markers")),
+            strs(h.get("continuation_markers"))
+        ),
+        None => "None".to_string(),
+    };
+    field(out, "hypothesis", hypothesis);
+    out.push_str("    },\n");
+}
+
+fn field(out: &mut String, name: &str, value: impl Display) {
+    writeln!(out, "        {name}: {value},").unwrap();
+}
+
+fn section<'a>(config: &'a Table, name: &str) -> &'a Table {
+    config
+        .get(name)
+        .and_then(Value::as_table)
+        .unwrap_or_else(|| panic!("missing [{name}] section"))
+}
+
+fn str_at<'a>(config: &'a Table, section_name: &str, key: &str) -> &'a str {
+    str_of(&section(config, section_name)[key])
+}
+
+fn str_of(value: &Value) -> &str {
+    value
+        .as_str()
+        .unwrap_or_else(|| panic!("expected a string, got {value}"))
+}
+
+fn char_of(value: &Value) -> char {
+    let s = str_of(value);
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => ch,
+        _ => panic!("expected a single character, got {s:?}"),
+    }
+}
+
+fn flag(table: &Table, key: &str, default: bool) -> bool {
+    table.get(key).and_then(Value::as_bool).unwrap_or(default)
+}
+
+/// A number; TOML integers are accepted as serde accepts them for `f64`
+fn float(table: &Table, key: &str) -> Option<f64> {
+    table
+        .get(key)
+        .map(|v| v.as_float().or(v.as_integer().map(|i| i as f64)).unwrap())
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn tables(value: Option<&Value>) -> impl Iterator<Item = &Table> {
+    array(value)
+        .iter()
+        .map(|v| v.as_table().expect("expected an inline table"))
+}
+
+fn slice(items: impl Iterator<Item = String>) -> String {
+    format!("&[{}]", items.collect::<Vec<_>>().join(", "))
+}
+
+fn chars(value: Option<&Value>) -> String {
+    slice(array(value).iter().map(|v| format!("{:?}", char_of(v))))
+}
+
+fn strs(value: Option<&Value>) -> String {
+    slice(array(value).iter().map(|v| format!("{:?}", str_of(v))))
+}
+
+fn optional_str(value: Option<&Value>) -> String {
+    match value {
+        Some(v) => format!("Some({:?})", str_of(v)),
+        None => "None".to_string(),
+    }
+}
+
+/// The `[abbreviations]` categories sorted by name, each entry as
+/// `(abbr, options)`; a plain string has no options, an inline table has
+/// `Some((case_sensitive, followed_by))`
+fn abbreviation_categories(table: &Table) -> String {
+    let mut names: Vec<&String> = table.keys().collect();
+    names.sort();
+    slice(names.into_iter().map(|name| {
+        let entries = array(table.get(name)).iter().map(|entry| match entry {
+            Value::Table(rule) => format!(
+                "({:?}, Some(({}, {})))",
+                str_of(&rule["abbr"]),
+                flag(rule, "case_sensitive", false),
+                optional_str(rule.get("followed_by"))
+            ),
+            plain => format!("({:?}, None)", str_of(plain)),
+        });
+        format!("({name:?}, {})", slice(entries))
+    }))
+}
+
+/// The `[sentence_starters]` categories sorted by name, each word as
+/// `(word, weight)`; a plain string has no weight
+fn starter_categories(table: &Table) -> String {
+    let mut names: Vec<&String> = table
+        .keys()
+        .filter(|k| !STARTER_SETTINGS.contains(&k.as_str()))
+        .collect();
+    names.sort();
+    slice(names.into_iter().map(|name| {
+        let entries = array(table.get(name)).iter().map(|entry| match entry {
+            Value::Table(starter) => format!(
+                "({:?}, Some({:?}))",
+                str_of(&starter["word"]),
+                float(starter, "weight").expect("a weighted starter needs a weight")
+            ),
+            plain => format!("({:?}, None)", str_of(plain)),
+        });
+        format!("({name:?}, {})", slice(entries))
+    }))
+}
@@ -0,0 +1,158 @@
+//! Sentences as Arrow record batches (`arrow` feature)
+
+use std::sync::Arc;
+
+use arrow_array::builder::{ArrayBuilder, StringBuilder, UInt32Builder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+
+use crate::api::Output;
+
+/// Accumulates sentences from one or more documents into a [`RecordBatch`]
+/// with the columns `doc_id` (utf8), `sentence_index` (uint32, counted per
+/// document), `text` (utf8), `start` and `end` (uint64 byte offsets of the
+/// trimmed sentence in its document)
+///
+/// The layout is the same one the CLI writes for `--format arrow|parquet`,
+/// so the files load directly into DuckDB, Polars or pandas.
+///
+/// ```rust
+/// use sakurs_core::{Input, SentenceBatchBuilder, SentenceProcessor};
+///
+/// let text = "Hello world. How are you?";
+/// let output = SentenceProcessor::new().process(Input::from_text(text)).unwrap();
+///
+/// let mut builder = SentenceBatchBuilder::new();
+/// builder.push_output("greeting.txt", text, &output);
+/// let batch = builder.finish().unwrap();
+/// assert_eq!(batch.num_rows(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct SentenceBatchBuilder {
+    doc_id: StringBuilder,
+    sentence_index: UInt32Builder,
+    text: StringBuilder,
+    start: UInt64Builder,
+    end: UInt64Builder,
+    current_doc: String,
+    next_index: u32,
+}
+
+impl SentenceBatchBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The schema of the batches this builder produces
+    pub fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("doc_id", DataType::Utf8, false),
+            Field::new("sentence_index", DataType::UInt32, false),
+            Field::new("text", DataType::Utf8, false),
+            Field::new("start", DataType::UInt64, false),
+            Field::new("end", DataType::UInt64, false),
+        ]))
+    }
+
+    /// Begin a new document; following sentences are numbered from 0
+    pub fn start_document(&mut self, doc_id: &str) {
+        doc_id.clone_into(&mut self.current_doc);
+        self.next_index = 0;
+    }
+
+    /// Append one sentence of the current document, spanning `start..end`
+    pub fn push(&mut self, text: &str, start: usize, end: usize) {
+        self.doc_id.append_value(&self.current_doc);
+        self.sentence_index.append_value(self.next_index);
+        self.text.append_value(text);
+        self.start.append_value(start as u64);
+        self.end.append_value(end as u64);
+        self.next_index += 1;
+    }
+
+    /// Append every sentence of `output` as a new document. Sentences are
+    /// trimmed, whitespace-only spans are skipped, and text after the last
+    /// boundary counts as a final sentence.
+    pub fn push_output(&mut self, doc_id: &str, text: &str, output: &Output) {
+        self.start_document(doc_id);
+        let mut last = 0;
+        let ends = output.boundaries.iter().map(|b| b.offset);
+        for end in ends.chain(std::iter::once(text.len())) {
+            if end <= last {
+                continue;
+            }
+            let span = &text[last..end];
+            let trimmed = span.trim();
+            if !trimmed.is_empty() {
+                let start = last + (span.len() - span.trim_start().len());
+                self.push(trimmed, start, start + trimmed.len());
+            }
+            last = end;
+        }
+    }
+
+    /// Number of sentences appended so far
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Whether no sentences have been appended
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Build the batch, leaving the builder empty for reuse
+    pub fn finish(&mut self) -> Result<RecordBatch, ArrowError> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(self.doc_id.finish()),
+            Arc::new(self.sentence_index.finish()),
+            Arc::new(self.text.finish()),
+            Arc::new(self.start.finish()),
+            Arc::new(self.end.finish()),
+        ];
+        RecordBatch::try_new(Self::schema(), columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Input, SentenceProcessor};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{UInt32Type, UInt64Type};
+
+    #[test]
+    fn test_batch_columns_and_offsets() {
+        let processor = SentenceProcessor::new();
+        let mut builder = SentenceBatchBuilder::new();
+        for (doc_id, text) in [("a", "One.  Two? "), ("b", "  Three")] {
+            let output = processor.process(Input::from_text(text)).unwrap();
+            builder.push_output(doc_id, text, &output);
+        }
+        let batch = builder.finish().unwrap();
+
+        assert_eq!(batch.schema(), SentenceBatchBuilder::schema());
+        let doc_ids: Vec<_> = batch
+            .column(0)
+            .as_string::<i32>()
+            .iter()
+            .flatten()
+            .collect();
+        let indices = batch.column(1).as_primitive::<UInt32Type>().values();
+        let texts: Vec<_> = batch
+            .column(2)
+            .as_string::<i32>()
+            .iter()
+            .flatten()
+            .collect();
+        let starts = batch.column(3).as_primitive::<UInt64Type>().values();
+        let ends = batch.column(4).as_primitive::<UInt64Type>().values();
+        assert_eq!(doc_ids, ["a", "a", "b"]);
+        assert_eq!(indices.as_ref(), [0, 1, 0]);
+        assert_eq!(texts, ["One.", "Two?", "Three"]);
+        assert_eq!(starts.as_ref(), [0, 6, 2]);
+        assert_eq!(ends.as_ref(), [4, 10, 7]);
+        assert!(builder.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+//! Async processing of readers on a Tokio runtime (`async` feature)
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::mpsc;
+
+use crate::api::{Error, Result, SentenceProcessor, SentenceSpan, SentenceStream};
+
+/// Sentences buffered between the reading task and the consumer; a slow
+/// consumer stops the reader once this many are waiting.
+const CHANNEL_CAPACITY: usize = 256;
+
+impl SentenceProcessor {
+    /// Segment an async reader, yielding sentences as they become final.
+    ///
+    /// The reader is consumed in chunks of the configured chunk size by a
+    /// task spawned on the current Tokio runtime; segmentation runs on the
+    /// blocking pool, so async worker threads are never held up by it.
+    /// Sentences arrive through a bounded channel, giving backpressure. The
+    /// sentences cover the whole input, including trailing text without a
+    /// final terminator. Input must be UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a Tokio runtime.
+    pub fn process_async<R>(&self, reader: R) -> impl Stream<Item = Result<SentenceSpan>> + Send
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let stream = self.stream();
+        let read_size = self.config().chunk_size;
+        tokio::spawn(async move {
+            if let Err(e) = pump(reader, stream, read_size, &tx).await {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+        SpanReceiver { rx }
+    }
+}
+
+/// Reads `reader` to the end, feeding `stream` and forwarding sentences.
+/// Returns early (successfully) when the consumer has gone away.
+async fn pump<R: AsyncRead + Unpin>(
+    mut reader: R,
+    mut stream: SentenceStream,
+    read_size: usize,
+    tx: &mpsc::Sender<Result<SentenceSpan>>,
+) -> Result<()> {
+    let mut buf = vec![0u8; read_size.max(4)];
+    // Bytes of a UTF-8 sequence split across reads
+    let mut carry: Vec<u8> = Vec::new();
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .await
+            .map_err(|e| Error::Infrastructure(format!("Failed to read from reader: {e}")))?;
+        if n == 0 {
+            if !carry.is_empty() {
+                return Err(Error::Infrastructure(
+                    "Invalid UTF-8 encoding: incomplete sequence at end of input".into(),
+                ));
+            }
+            let spans = blocking(move || stream.finish_spans()).await?;
+            forward(tx, spans).await;
+            return Ok(());
+        }
+
+        carry.extend_from_slice(&buf[..n]);
+        let valid = match std::str::from_utf8(&carry) {
+            Ok(_) => carry.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                return Err(Error::Infrastructure(format!(
+                    "Invalid UTF-8 encoding: {e}"
+                )))
+            }
+        };
+        let rest = carry.split_off(valid);
+        let piece = String::from_utf8(std::mem::replace(&mut carry, rest))
+            .expect("validated as UTF-8 above");
+
+        let (returned, spans) = blocking(move || {
+            let spans = stream.feed_spans(&piece);
+            (stream, spans)
+        })
+        .await?;
+        stream = returned;
+        if !forward(tx, spans).await {
+            return Ok(());
+        }
+    }
+}
+
+/// Runs CPU-bound segmentation on the blocking pool.
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::Infrastructure(format!("Segmentation task failed: {e}")))
+}
+
+/// Sends sentences to the consumer; false once the consumer has gone away.
+async fn forward(tx: &mpsc::Sender<Result<SentenceSpan>>, spans: Vec<SentenceSpan>) -> bool {
+    for span in spans {
+        if tx.send(Ok(span)).await.is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Consumer side of [`SentenceProcessor::process_async`].
+struct SpanReceiver {
+    rx: mpsc::Receiver<Result<SentenceSpan>>,
+}
+
+impl Stream for SpanReceiver {
+    type Item = Result<SentenceSpan>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
@@ -1,12 +1,105 @@
 //! Configuration API for sentence processing
 
-use crate::api::{Error, Language};
+use crate::api::filter::BoundaryFilters;
+use crate::api::output::OffsetUnits;
+use crate::api::tokens::SharedEstimator;
+use crate::api::{
+    BoundaryFilter, ChunkPolicy, DepthOverflow, Error, InvalidUtf8, Language, PerfProfile, Profile,
+    TokenEstimator,
+};
+#[cfg(feature = "normalization")]
+use crate::api::{Normalization, OffsetSpace};
+use crate::application::{BalanceOptions, ChunkCache, DepthCap};
+use crate::domain::language::config::{
+    ContextRule, EnclosureBoundaries, ExceptionPattern, LanguageConfig,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// Default configuration constants
 pub mod defaults {
     /// Default chunk size in bytes (256KB)
     pub const CHUNK_SIZE: usize = 256 * 1024;
+    /// Default text bytes per thread of adaptive execution (256KB)
+    pub const ADAPTIVE_THRESHOLD: usize = 256 * 1024;
+    /// Default pieces queued between the read and scan stages of a
+    /// pipelined reader
+    pub const READ_BUFFERS: usize = 4;
+    /// Default sentence batches queued between the scan and emit stages of
+    /// a pipelined reader
+    pub const EMIT_BUFFERS: usize = 4;
+    /// Default length in characters past which a sentence is reported as
+    /// suspiciously long
+    pub const LONG_SENTENCE_CHARS: usize = 10_000;
+}
+
+/// How ellipses ("…", "...") are judged
+///
+/// Fiction uses ellipses mostly for trailing speech that ends a sentence,
+/// academic text mostly for elisions inside one; the language configuration
+/// only provides a default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EllipsisPolicy {
+    /// Use the language configuration's `[ellipsis]` rules
+    #[default]
+    Language,
+    /// Every ellipsis ends a sentence
+    Always,
+    /// No ellipsis ends a sentence
+    Never,
+    /// An ellipsis ends a sentence only when the next letter is a capital
+    BeforeCapital,
+}
+
+impl FromStr for EllipsisPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "language" => Ok(EllipsisPolicy::Language),
+            "always" => Ok(EllipsisPolicy::Always),
+            "never" => Ok(EllipsisPolicy::Never),
+            "before-capital" | "capital" => Ok(EllipsisPolicy::BeforeCapital),
+            _ => Err(Error::Configuration(format!(
+                "ellipsis policy must be language, always, never or before-capital, got: {s}"
+            ))),
+        }
+    }
+}
+
+impl FromStr for EnclosureBoundaries {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "suppress" => Ok(EnclosureBoundaries::Suppress),
+            "allow" => Ok(EnclosureBoundaries::Allow),
+            "strong_only" | "strong" => Ok(EnclosureBoundaries::StrongOnly),
+            "dialogue" => Ok(EnclosureBoundaries::Dialogue),
+            _ => Err(Error::Configuration(format!(
+                "enclosure boundaries must be suppress, allow, strong_only or dialogue, got: {s}"
+            ))),
+        }
+    }
+}
+
+impl FromStr for DepthOverflow {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "ignore" => Ok(DepthOverflow::Ignore),
+            "reset" | "reset-at-blank-line" => Ok(DepthOverflow::ResetAtBlankLine),
+            "warn" => Ok(DepthOverflow::Warn),
+            _ => Err(Error::Configuration(format!(
+                "depth overflow policy must be ignore, reset or warn, got: {s}"
+            ))),
+        }
+    }
 }
 
 /// Processing configuration
@@ -15,6 +108,79 @@ pub struct Config {
     pub(crate) language: Language,
     pub(crate) chunk_size: usize,      // in bytes
     pub(crate) threads: Option<usize>, // None = all available threads
+    /// Text bytes per thread when the thread count is chosen per text;
+    /// shorter texts run sequentially
+    pub(crate) adaptive_threshold: usize,
+    /// Most threads used when the thread count is chosen per text (None =
+    /// all available threads)
+    pub(crate) max_threads: Option<usize>,
+    /// Choose the chunk size per text from a sample of it (parallel runs
+    /// only); `chunk_size` is used otherwise. On unless a chunk size is set.
+    pub(crate) adaptive_chunking: bool,
+    pub(crate) chunk_policy: ChunkPolicy,
+    /// Pieces read ahead of the scan stage of a pipelined reader
+    pub(crate) read_buffers: usize,
+    /// Sentence batches scanned ahead of the consumer of a pipelined reader
+    pub(crate) emit_buffers: usize,
+    /// Read files block by block on a thread of their own, scanning the
+    /// blocks read while the next is read
+    pub(crate) read_ahead: bool,
+    /// Scanned chunks reused across texts and processors
+    pub(crate) chunk_cache: Option<ChunkCache>,
+    /// Extra offset units reported for each boundary (off by default: each
+    /// costs a pass over the text)
+    pub(crate) offset_units: OffsetUnits,
+    pub(crate) invalid_utf8: InvalidUtf8,
+    /// Remove ruby annotations (Aozora Bunko, HTML) before segmentation
+    pub(crate) strip_ruby: bool,
+    /// Normalization applied before segmentation (None = off)
+    #[cfg(feature = "normalization")]
+    pub(crate) normalization: Option<Normalization>,
+    #[cfg(feature = "normalization")]
+    pub(crate) offset_space: OffsetSpace,
+    pub(crate) ellipsis: EllipsisPolicy,
+    /// Ellipsis exceptions tried before the policy and the language's own
+    /// exceptions
+    pub(crate) ellipsis_exceptions: Vec<ExceptionPattern>,
+    /// Boundaries let through inside enclosure types, by opening or closing
+    /// character, overriding the language's pairs
+    pub(crate) enclosure_boundaries: Vec<(char, EnclosureBoundaries)>,
+    /// Whether emoji end sentences (None = as the language configures)
+    pub(crate) emoji_terminators: Option<bool>,
+    /// Whether URLs, handles and hashtags are protected (None = as the
+    /// language configures)
+    pub(crate) web_tokens: Option<bool>,
+    /// End a sentence at every line break after text
+    pub(crate) line_breaks: bool,
+    /// Preset whose rules are applied before the explicit overrides
+    pub(crate) profile: Option<Profile>,
+    /// Sentences shorter than this (in characters, trimmed) are merged into
+    /// the previous one
+    pub(crate) min_sentence_chars: Option<usize>,
+    /// Sentences longer than this (in characters) are force-split
+    pub(crate) max_sentence_chars: Option<usize>,
+    /// Spans longer than this (in bytes) are force-split
+    pub(crate) force_split_bytes: Option<usize>,
+    /// Report the candidates that did not become boundaries
+    pub(crate) include_rejected_candidates: bool,
+    /// Report unbalanced quotes and brackets
+    pub(crate) enclosure_warnings: bool,
+    /// Sentences longer than this (in characters) are reported
+    pub(crate) long_sentence_chars: usize,
+    /// Close the enclosures still open at the end of a paragraph
+    pub(crate) close_enclosures_at_paragraphs: bool,
+    /// Deepest nesting tracked per enclosure type (None = unlimited)
+    pub(crate) max_enclosure_depth: Option<DepthCap>,
+    /// Judge straight quotes as opening or closing by their neighbours
+    pub(crate) infer_quote_direction: bool,
+    /// Report sentences whose normalized text repeats an earlier one
+    pub(crate) dedupe: bool,
+    /// Spans in which no boundary is placed
+    pub(crate) suppression_regexes: Vec<Regex>,
+    /// User filters run on every candidate after the rules
+    pub(crate) filters: BoundaryFilters,
+    /// Estimates the token count of each sentence
+    pub(crate) token_estimator: Option<SharedEstimator>,
 }
 
 impl Default for Config {
@@ -23,6 +189,41 @@ impl Default for Config {
             language: Language::default(),
             chunk_size: defaults::CHUNK_SIZE,
             threads: None,
+            adaptive_threshold: defaults::ADAPTIVE_THRESHOLD,
+            max_threads: None,
+            adaptive_chunking: true,
+            chunk_policy: ChunkPolicy::default(),
+            read_buffers: defaults::READ_BUFFERS,
+            emit_buffers: defaults::EMIT_BUFFERS,
+            read_ahead: false,
+            chunk_cache: None,
+            offset_units: OffsetUnits::default(),
+            invalid_utf8: InvalidUtf8::default(),
+            strip_ruby: false,
+            #[cfg(feature = "normalization")]
+            normalization: None,
+            #[cfg(feature = "normalization")]
+            offset_space: OffsetSpace::default(),
+            ellipsis: EllipsisPolicy::default(),
+            ellipsis_exceptions: Vec::new(),
+            enclosure_boundaries: Vec::new(),
+            emoji_terminators: None,
+            web_tokens: None,
+            line_breaks: false,
+            profile: None,
+            min_sentence_chars: None,
+            max_sentence_chars: None,
+            force_split_bytes: None,
+            include_rejected_candidates: false,
+            enclosure_warnings: false,
+            long_sentence_chars: defaults::LONG_SENTENCE_CHARS,
+            close_enclosures_at_paragraphs: false,
+            max_enclosure_depth: None,
+            infer_quote_direction: false,
+            dedupe: false,
+            suppression_regexes: Vec::new(),
+            filters: BoundaryFilters::default(),
+            token_estimator: None,
         }
     }
 }
@@ -39,6 +240,41 @@ impl Config {
             language: Language::default(),
             chunk_size: 8 * 1024, // 8KB chunks
             threads: None,
+            adaptive_threshold: defaults::ADAPTIVE_THRESHOLD,
+            max_threads: None,
+            adaptive_chunking: false,
+            chunk_policy: ChunkPolicy::default(),
+            read_buffers: defaults::READ_BUFFERS,
+            emit_buffers: defaults::EMIT_BUFFERS,
+            read_ahead: false,
+            chunk_cache: None,
+            offset_units: OffsetUnits::default(),
+            invalid_utf8: InvalidUtf8::default(),
+            strip_ruby: false,
+            #[cfg(feature = "normalization")]
+            normalization: None,
+            #[cfg(feature = "normalization")]
+            offset_space: OffsetSpace::default(),
+            ellipsis: EllipsisPolicy::default(),
+            ellipsis_exceptions: Vec::new(),
+            enclosure_boundaries: Vec::new(),
+            emoji_terminators: None,
+            web_tokens: None,
+            line_breaks: false,
+            profile: None,
+            min_sentence_chars: None,
+            max_sentence_chars: None,
+            force_split_bytes: None,
+            include_rejected_candidates: false,
+            enclosure_warnings: false,
+            long_sentence_chars: defaults::LONG_SENTENCE_CHARS,
+            close_enclosures_at_paragraphs: false,
+            max_enclosure_depth: None,
+            infer_quote_direction: false,
+            dedupe: false,
+            suppression_regexes: Vec::new(),
+            filters: BoundaryFilters::default(),
+            token_estimator: None,
         }
     }
 
@@ -48,6 +284,41 @@ impl Config {
             language: Language::default(),
             chunk_size: 512 * 1024, // 512KB chunks
             threads: None,          // Use all available cores
+            adaptive_threshold: defaults::ADAPTIVE_THRESHOLD,
+            max_threads: None,
+            adaptive_chunking: false,
+            chunk_policy: ChunkPolicy::default(),
+            read_buffers: defaults::READ_BUFFERS,
+            emit_buffers: defaults::EMIT_BUFFERS,
+            read_ahead: false,
+            chunk_cache: None,
+            offset_units: OffsetUnits::default(),
+            invalid_utf8: InvalidUtf8::default(),
+            strip_ruby: false,
+            #[cfg(feature = "normalization")]
+            normalization: None,
+            #[cfg(feature = "normalization")]
+            offset_space: OffsetSpace::default(),
+            ellipsis: EllipsisPolicy::default(),
+            ellipsis_exceptions: Vec::new(),
+            enclosure_boundaries: Vec::new(),
+            emoji_terminators: None,
+            web_tokens: None,
+            line_breaks: false,
+            profile: None,
+            min_sentence_chars: None,
+            max_sentence_chars: None,
+            force_split_bytes: None,
+            include_rejected_candidates: false,
+            enclosure_warnings: false,
+            long_sentence_chars: defaults::LONG_SENTENCE_CHARS,
+            close_enclosures_at_paragraphs: false,
+            max_enclosure_depth: None,
+            infer_quote_direction: false,
+            dedupe: false,
+            suppression_regexes: Vec::new(),
+            filters: BoundaryFilters::default(),
+            token_estimator: None,
         }
     }
 
@@ -57,6 +328,41 @@ impl Config {
             language: Language::default(),
             chunk_size: 32 * 1024, // 32KB chunks
             threads: Some(2),      // Limited parallelism
+            adaptive_threshold: defaults::ADAPTIVE_THRESHOLD,
+            max_threads: None,
+            adaptive_chunking: false,
+            chunk_policy: ChunkPolicy::default(),
+            read_buffers: defaults::READ_BUFFERS,
+            emit_buffers: defaults::EMIT_BUFFERS,
+            read_ahead: false,
+            chunk_cache: None,
+            offset_units: OffsetUnits::default(),
+            invalid_utf8: InvalidUtf8::default(),
+            strip_ruby: false,
+            #[cfg(feature = "normalization")]
+            normalization: None,
+            #[cfg(feature = "normalization")]
+            offset_space: OffsetSpace::default(),
+            ellipsis: EllipsisPolicy::default(),
+            ellipsis_exceptions: Vec::new(),
+            enclosure_boundaries: Vec::new(),
+            emoji_terminators: None,
+            web_tokens: None,
+            line_breaks: false,
+            profile: None,
+            min_sentence_chars: None,
+            max_sentence_chars: None,
+            force_split_bytes: None,
+            include_rejected_candidates: false,
+            enclosure_warnings: false,
+            long_sentence_chars: defaults::LONG_SENTENCE_CHARS,
+            close_enclosures_at_paragraphs: false,
+            max_enclosure_depth: None,
+            infer_quote_direction: false,
+            dedupe: false,
+            suppression_regexes: Vec::new(),
+            filters: BoundaryFilters::default(),
+            token_estimator: None,
         }
     }
 
@@ -75,9 +381,129 @@ impl Config {
                 ));
             }
         }
+        if self.adaptive_threshold == 0 {
+            return Err(Error::Configuration(
+                "adaptive_threshold must be greater than 0".into(),
+            ));
+        }
+        if self.max_threads == Some(0) {
+            return Err(Error::Configuration(
+                "max_threads must be greater than 0".into(),
+            ));
+        }
+        if self.read_buffers == 0 || self.emit_buffers == 0 {
+            return Err(Error::Configuration(
+                "stream buffer counts must be greater than 0".into(),
+            ));
+        }
+
+        if self.max_sentence_chars == Some(0) {
+            return Err(Error::Configuration(
+                "max_sentence_chars must be greater than 0".into(),
+            ));
+        }
+        if self.long_sentence_chars == 0 {
+            return Err(Error::Configuration(
+                "long_sentence_chars must be greater than 0".into(),
+            ));
+        }
+        if self.force_split_bytes == Some(0) {
+            return Err(Error::Configuration(
+                "force_split_bytes must be greater than 0".into(),
+            ));
+        }
+        if self
+            .max_enclosure_depth
+            .is_some_and(|cap| cap.max_depth == 0)
+        {
+            return Err(Error::Configuration(
+                "max_enclosure_depth must be greater than 0".into(),
+            ));
+        }
+        if let (Some(min), Some(max)) = (self.min_sentence_chars, self.max_sentence_chars) {
+            if min > max {
+                return Err(Error::Configuration(format!(
+                    "min_sentence_chars ({min}) must not exceed max_sentence_chars ({max})"
+                )));
+            }
+        }
+
+        for exception in &self.ellipsis_exceptions {
+            regex::Regex::new(&exception.regex).map_err(|e| {
+                Error::Configuration(format!("invalid ellipsis exception regex: {e}"))
+            })?;
+        }
 
         Ok(())
     }
+
+    /// What the enclosure balance pass changes, if it runs at all
+    pub(crate) fn balance_options(&self) -> Option<BalanceOptions> {
+        let options = BalanceOptions {
+            close_at_paragraphs: self.close_enclosures_at_paragraphs,
+            depth_cap: self.max_enclosure_depth,
+            infer_quote_direction: self.infer_quote_direction,
+        };
+        (self.enclosure_warnings || options != BalanceOptions::default()).then_some(options)
+    }
+
+    /// The language configuration with this configuration's rule overrides
+    /// applied (borrowed unchanged when there are none)
+    pub(crate) fn apply_rule_overrides<'a>(
+        &self,
+        language: &'a LanguageConfig,
+    ) -> Result<Cow<'a, LanguageConfig>, Error> {
+        if self.ellipsis == EllipsisPolicy::Language
+            && self.ellipsis_exceptions.is_empty()
+            && self.enclosure_boundaries.is_empty()
+            && self.emoji_terminators.is_none()
+            && self.web_tokens.is_none()
+            && self.profile.is_none()
+        {
+            return Ok(Cow::Borrowed(language));
+        }
+        let mut language = language.clone();
+        for &(ch, boundaries) in &self.enclosure_boundaries {
+            let pair = language
+                .enclosures
+                .pairs
+                .iter_mut()
+                .find(|p| p.open == ch || p.close == ch)
+                .ok_or_else(|| {
+                    Error::Configuration(format!(
+                        "{ch:?} is not an enclosure character of language '{}'",
+                        language.metadata.code
+                    ))
+                })?;
+            pair.boundaries = boundaries;
+        }
+        if let Some(profile) = self.profile {
+            profile.apply(&mut language);
+        }
+        if let Some(emoji) = self.emoji_terminators {
+            language.terminators.emoji = emoji;
+        }
+        if let Some(web_tokens) = self.web_tokens {
+            language.suppression.web_tokens = web_tokens;
+        }
+        let ellipsis = &mut language.ellipsis;
+        // A policy replaces the language's rules, including its exceptions
+        if self.ellipsis != EllipsisPolicy::Language {
+            ellipsis.treat_as_boundary = self.ellipsis == EllipsisPolicy::Always;
+            ellipsis.context_rules.clear();
+            ellipsis.exceptions.clear();
+        }
+        if self.ellipsis == EllipsisPolicy::BeforeCapital {
+            ellipsis.context_rules.push(ContextRule {
+                condition: "followed_by_capital".into(),
+                boundary: true,
+            });
+        }
+        ellipsis
+            .exceptions
+            .splice(0..0, self.ellipsis_exceptions.iter().cloned());
+        Ok(Cow::Owned(language))
+    }
 }
 
 /// Fluent builder for configuration
@@ -86,6 +512,40 @@ pub struct ConfigBuilder {
     language: Option<String>,
     chunk_size: Option<usize>,
     threads: Option<usize>,
+    adaptive_threshold: Option<usize>,
+    max_threads: Option<usize>,
+    perf_profile: Option<PerfProfile>,
+    chunk_policy: Option<ChunkPolicy>,
+    stream_buffers: Option<(usize, usize)>,
+    read_ahead: bool,
+    chunk_cache: Option<ChunkCache>,
+    offset_units: OffsetUnits,
+    invalid_utf8: InvalidUtf8,
+    strip_ruby: bool,
+    #[cfg(feature = "normalization")]
+    normalization: Option<Normalization>,
+    #[cfg(feature = "normalization")]
+    offset_space: OffsetSpace,
+    ellipsis: EllipsisPolicy,
+    ellipsis_exceptions: Vec<ExceptionPattern>,
+    enclosure_boundaries: Vec<(char, EnclosureBoundaries)>,
+    emoji_terminators: Option<bool>,
+    web_tokens: Option<bool>,
+    line_breaks: Option<bool>,
+    profile: Option<Profile>,
+    min_sentence_chars: Option<usize>,
+    max_sentence_chars: Option<usize>,
+    force_split_bytes: Option<usize>,
+    include_rejected_candidates: bool,
+    enclosure_warnings: bool,
+    long_sentence_chars: Option<usize>,
+    close_enclosures_at_paragraphs: bool,
+    max_enclosure_depth: Option<DepthCap>,
+    infer_quote_direction: bool,
+    dedupe: bool,
+    suppression_regexes: Vec<String>,
+    filters: BoundaryFilters,
+    token_estimator: Option<SharedEstimator>,
 }
 
 impl ConfigBuilder {
@@ -100,18 +560,344 @@ impl ConfigBuilder {
         Ok(self)
     }
 
-    /// Set the chunk size in bytes
+    /// Set the chunk size in bytes (disables adaptive chunk sizing)
     pub fn chunk_size(mut self, bytes: usize) -> Self {
         self.chunk_size = Some(bytes);
         self
     }
 
+    /// Set where chunk cut points may fall (default: any character
+    /// boundary). Boundaries are the same under every policy.
+    pub fn chunk_policy(mut self, policy: ChunkPolicy) -> Self {
+        self.chunk_policy = Some(policy);
+        self
+    }
+
+    /// Also report UTF-16 code unit offsets (`Boundary::utf16_offset`), as
+    /// used by JavaScript strings and the Language Server Protocol
+    pub fn utf16_offsets(mut self, enabled: bool) -> Self {
+        self.offset_units.utf16 = enabled;
+        self
+    }
+
+    /// Also report extended grapheme cluster offsets
+    /// (`Boundary::grapheme_offset`), i.e. user-perceived characters
+    pub fn grapheme_offsets(mut self, enabled: bool) -> Self {
+        self.offset_units.grapheme = enabled;
+        self
+    }
+
+    /// Set how invalid UTF-8 in byte, file and reader input is handled
+    /// (default: error). The count of replaced or skipped bytes is reported
+    /// in `ProcessingStats::invalid_utf8_bytes`; offsets refer to the
+    /// decoded text.
+    pub fn invalid_utf8(mut self, policy: InvalidUtf8) -> Self {
+        self.invalid_utf8 = policy;
+        self
+    }
+
+    /// Remove ruby (furigana) annotations before segmentation (default:
+    /// off): Aozora Bunko readings (`漢字《かんじ》`, `｜東京《とうきょう》`)
+    /// and HTML `<rt>`/`<rp>` elements with their `<ruby>` tags, so readings
+    /// are not tracked as enclosures. Offsets still refer to the text as
+    /// given, and sentences include their annotations. Applies to
+    /// `SentenceProcessor::process`; streams see the text as fed.
+    pub fn strip_ruby(mut self, enabled: bool) -> Self {
+        self.strip_ruby = enabled;
+        self
+    }
+
+    /// Normalize the text to NFC or NFKC before segmentation, so decomposed
+    /// accents and full-width punctuation match the rule tables. Applies to
+    /// `SentenceProcessor::process`; streams see the text as fed.
+    #[cfg(feature = "normalization")]
+    pub fn normalization(mut self, form: Normalization) -> Self {
+        self.normalization = Some(form);
+        self
+    }
+
+    /// Set whether boundary offsets refer to the original text (default) or
+    /// the normalized text (also without its ruby, with `strip_ruby`); has
+    /// no effect without `normalization` or `strip_ruby`
+    #[cfg(feature = "normalization")]
+    pub fn offset_space(mut self, space: OffsetSpace) -> Self {
+        self.offset_space = space;
+        self
+    }
+
+    /// Set how ellipses are judged, overriding the language configuration
+    /// (default: the language's rules)
+    pub fn ellipsis(mut self, policy: EllipsisPolicy) -> Self {
+        self.ellipsis = policy;
+        self
+    }
+
+    /// Add an ellipsis exception: where `regex` matches within 20 bytes of
+    /// an ellipsis, it is a boundary if `boundary` is true and not one
+    /// otherwise. Exceptions are tried in the order added, before the policy.
+    pub fn ellipsis_exception(mut self, regex: impl Into<String>, boundary: bool) -> Self {
+        self.ellipsis_exceptions.push(ExceptionPattern {
+            regex: regex.into(),
+            boundary,
+        });
+        self
+    }
+
+    /// Set which boundaries the enclosure type opened or closed by `ch`
+    /// lets through inside it, overriding the language configuration: with
+    /// [`EnclosureBoundaries::Allow`] for `'('`, a long parenthetical aside
+    /// is split into its sentences, and with [`EnclosureBoundaries::Dialogue`]
+    /// for `'「'`, so is each line of dialogue in a Japanese novel. Fails at
+    /// processor creation when no enclosure pair of the language uses `ch`.
+    pub fn enclosure_boundaries(mut self, ch: char, boundaries: EnclosureBoundaries) -> Self {
+        self.enclosure_boundaries.push((ch, boundaries));
+        self
+    }
+
+    /// Set whether emoji end sentences ("That was wild 😂😂 Next"),
+    /// overriding the language configuration (off in the bundled languages)
+    pub fn emoji_terminators(mut self, enabled: bool) -> Self {
+        self.emoji_terminators = Some(enabled);
+        self
+    }
+
+    /// Set whether periods and other terminators inside URLs, email
+    /// addresses, @mentions, #hashtags and domains ("@john.doe",
+    /// "example.com/a?b=1") are ignored, overriding the language
+    /// configuration (off in the bundled languages)
+    pub fn protect_web_tokens(mut self, enabled: bool) -> Self {
+        self.web_tokens = Some(enabled);
+        self
+    }
+
+    /// Set whether every line break after text ends a sentence, as in chat
+    /// messages and posts (default: off). Applies to
+    /// `SentenceProcessor::process`; streams judge line breaks as text.
+    pub fn line_break_boundaries(mut self, enabled: bool) -> Self {
+        self.line_breaks = Some(enabled);
+        self
+    }
+
+    /// Apply a preset for a kind of text; options set explicitly, before or
+    /// after, take precedence over the profile's
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Merge sentences with fewer than `chars` characters (surrounding
+    /// whitespace aside) into the previous sentence, or into the next one
+    /// at the start of the text. Applies to `SentenceProcessor::process`.
+    pub fn min_sentence_chars(mut self, chars: usize) -> Self {
+        self.min_sentence_chars = Some(chars);
+        self
+    }
+
+    /// Force-split sentences longer than `chars` characters, each cut after
+    /// the last sentence terminator within the limit (a rejected candidate
+    /// such as "approx. 5"), else after clause punctuation, else at a space,
+    /// else at the limit. The inserted boundaries have
+    /// `BoundaryKind::Forced`. Applies to `SentenceProcessor::process`.
+    pub fn max_sentence_chars(mut self, chars: usize) -> Self {
+        self.max_sentence_chars = Some(chars);
+        self
+    }
+
+    /// Force-split spans longer than `bytes` that the rules left whole, such
+    /// as machine-generated text without periods, cutting at the clause
+    /// punctuation or space nearest the limit. The inserted boundaries have
+    /// `BoundaryKind::Forced`. Applies to `SentenceProcessor::process`.
+    pub fn force_split_bytes(mut self, bytes: usize) -> Self {
+        self.force_split_bytes = Some(bytes);
+        self
+    }
+
+    /// Report the terminators that did not become boundaries in
+    /// [`Output::rejected_candidates`](crate::Output::rejected_candidates),
+    /// each with the reason (abbreviation, decimal, inside quotes or
+    /// brackets, ...), for reviewing near-misses. Costs a second pass over
+    /// the text. Applies to `SentenceProcessor::process`.
+    pub fn include_rejected_candidates(mut self, include: bool) -> Self {
+        self.include_rejected_candidates = include;
+        self
+    }
+
+    /// Report the quotes and brackets left unbalanced in
+    /// [`Output::warnings`](crate::Output::warnings): openers never closed
+    /// and closers without an opener. Costs a second pass over the text.
+    /// Applies to `SentenceProcessor::process`.
+    pub fn enclosure_warnings(mut self, enabled: bool) -> Self {
+        self.enclosure_warnings = enabled;
+        self
+    }
+
+    /// Report sentences longer than `chars` characters in
+    /// [`Output::warnings`](crate::Output::warnings) (default:
+    /// [`defaults::LONG_SENTENCE_CHARS`]), which usually means a missed
+    /// boundary or text that is not prose. Sentences force-split by
+    /// [`max_sentence_chars`](Self::max_sentence_chars) never exceed that
+    /// limit. Applies to `SentenceProcessor::process`.
+    pub fn long_sentence_chars(mut self, chars: usize) -> Self {
+        self.long_sentence_chars = Some(chars);
+        self
+    }
+
+    /// Close the quotes and brackets still open at the end of a paragraph
+    /// (a blank line), so that one unclosed quote does not keep every later
+    /// terminator inside it (default: off). Boundaries before the first such
+    /// paragraph end are unchanged; with
+    /// [`enclosure_warnings`](Self::enclosure_warnings), each closed
+    /// enclosure is reported. Costs a second pass over the text. Applies to
+    /// `SentenceProcessor::process`.
+    pub fn close_enclosures_at_paragraphs(mut self, enabled: bool) -> Self {
+        self.close_enclosures_at_paragraphs = enabled;
+        self
+    }
+
+    /// Track at most `depth` nested enclosures of each bracket type, so that
+    /// pathological input such as thousands of unmatched `(` cannot keep the
+    /// rest of the document inside brackets; `overflow` says what happens to
+    /// openers past the cap. Each excursion past it is reported as a
+    /// `Warning::DepthExceeded` in
+    /// [`Output::warnings`](crate::Output::warnings). Quotes that open and
+    /// close with the same character never nest and are not capped. Costs a
+    /// second pass over the text. Applies to `SentenceProcessor::process`.
+    pub fn max_enclosure_depth(mut self, depth: usize, overflow: DepthOverflow) -> Self {
+        self.max_enclosure_depth = Some(DepthCap {
+            max_depth: depth,
+            overflow,
+        });
+        self
+    }
+
+    /// Judge each straight quote (one that opens and closes with the same
+    /// character, such as `"`) by its neighbours instead of only toggling it
+    /// (default: off). A quote after whitespace, an opening bracket or a
+    /// dash and before a word opens (`said—"Stop`); one after a word and
+    /// before whitespace or punctuation closes (`stop," he`). An opening
+    /// quote while its type is already open, or a closing one while it is
+    /// not, resynchronizes the quote instead of inverting every later
+    /// quotation; with [`enclosure_warnings`](Self::enclosure_warnings) it is
+    /// reported. Costs a second pass over the text. Applies to
+    /// `SentenceProcessor::process`.
+    pub fn infer_quote_direction(mut self, enabled: bool) -> Self {
+        self.infer_quote_direction = enabled;
+        self
+    }
+
+    /// Report the sentences whose text repeats an earlier sentence of the
+    /// document in [`Output::duplicates`](crate::Output::duplicates) and
+    /// count them in the statistics (default: off). Sentences are compared
+    /// by a hash of their text with case folded and whitespace collapsed,
+    /// so `Hello  world.` repeats `hello world.`; blank text never counts.
+    /// [`SentenceProcessor::process_batch`](crate::SentenceProcessor::process_batch)
+    /// compares across the whole batch. Boundaries are unchanged.
+    pub fn dedupe(mut self, enabled: bool) -> Self {
+        self.dedupe = enabled;
+        self
+    }
+
+    /// Forbid boundaries inside the matches of these regular expressions,
+    /// such as ticket IDs (`[A-Z]+-\d+\.`) or course codes: a terminator
+    /// inside a match does not end a sentence. Patterns are matched against
+    /// the whole text and may span any length. Applies to
+    /// `SentenceProcessor::process`.
+    pub fn suppression_regex(mut self, patterns: Vec<String>) -> Self {
+        self.suppression_regexes.extend(patterns);
+        self
+    }
+
+    /// Register a filter that can veto or force each candidate after the
+    /// language's rules, for domain logic the TOML rules cannot express.
+    /// Filters run in registration order, each seeing the verdict left by
+    /// the previous ones. Applies to `SentenceProcessor::process`.
+    pub fn boundary_filter(mut self, filter: impl BoundaryFilter + 'static) -> Self {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Estimate the token count of every sentence with `estimator`, into
+    /// [`Output::token_estimates`](crate::Output::token_estimates), for
+    /// packing sentences into language model contexts with
+    /// [`chunk_sentences`](crate::chunk_sentences) (default: none).
+    /// [`BpeEstimator`](crate::BpeEstimator) approximates GPT-style
+    /// tokenizers; a closure can call a real tokenizer. Boundaries are
+    /// unchanged.
+    pub fn token_estimator(mut self, estimator: impl TokenEstimator + 'static) -> Self {
+        self.token_estimator = Some(SharedEstimator::new(estimator));
+        self
+    }
+
     /// Set the number of threads (None = all available)
     pub fn threads(mut self, count: Option<usize>) -> Self {
         self.threads = count;
         self
     }
 
+    /// Set the text bytes per thread when the thread count is chosen per
+    /// text (default: 256KB); shorter texts run sequentially
+    pub fn adaptive_threshold(mut self, bytes: usize) -> Self {
+        self.adaptive_threshold = Some(bytes);
+        self
+    }
+
+    /// Set the most threads used when the thread count is chosen per text
+    /// (default: all available)
+    pub fn max_threads(mut self, count: usize) -> Self {
+        self.max_threads = Some(count);
+        self
+    }
+
+    /// Apply the chunk size, thread limit and adaptive threshold of a
+    /// machine class (default: none); [`chunk_size`](Self::chunk_size),
+    /// [`adaptive_threshold`](Self::adaptive_threshold) and
+    /// [`max_threads`](Self::max_threads), before or after, take precedence
+    /// over the preset's
+    pub fn perf_profile(mut self, profile: PerfProfile) -> Self {
+        self.perf_profile = Some(profile);
+        self
+    }
+
+    /// Set how many pieces a pipelined reader reads ahead of its scan
+    /// stage, and how many sentence batches it scans ahead of the consumer
+    /// (default: 4 and 4)
+    ///
+    /// See [`SentenceProcessor::pipeline_from_reader`](crate::SentenceProcessor::pipeline_from_reader):
+    /// a slow consumer stalls the stages once the queues are full, so the
+    /// memory in flight stays within about `read + emit` pieces of the
+    /// chunk size.
+    pub fn stream_buffers(mut self, read: usize, emit: usize) -> Self {
+        self.stream_buffers = Some((read, emit));
+        self
+    }
+
+    /// Read file inputs ahead on a thread of their own (default: off)
+    ///
+    /// The file is read in blocks of the chunk size into two buffers that
+    /// take turns, and each block is scanned as soon as it is read, so disk
+    /// reads overlap with scanning instead of preceding it. Boundaries are
+    /// unchanged, but the chunk size is never chosen per text. Inputs that
+    /// are ruby-stripped or normalized are read whole as before, since
+    /// those passes need the whole text.
+    pub fn read_ahead(mut self, enabled: bool) -> Self {
+        self.read_ahead = enabled;
+        self
+    }
+
+    /// Take the states of chunks scanned before from `cache`, and add
+    /// those of new chunks to it (default: none)
+    ///
+    /// Entries are keyed by the rules' fingerprint and the chunk's content,
+    /// so one cache can serve processors with different rules, and
+    /// boundaries never change. Reprocessing mostly unchanged texts with
+    /// the same chunk size then skips the unchanged chunks; an
+    /// [on-disk](ChunkCache::on_disk) cache carries this over to later
+    /// runs.
+    pub fn chunk_cache(mut self, cache: ChunkCache) -> Self {
+        self.chunk_cache = Some(cache);
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> Result<Config, Error> {
         let mut config = Config::default();
@@ -120,13 +906,72 @@ impl ConfigBuilder {
             config.language = Language::from_str(&lang_code)?;
         }
 
-        if let Some(size) = self.chunk_size {
+        let preset = self.perf_profile;
+        if let Some(size) = self.chunk_size.or(preset.map(PerfProfile::chunk_size)) {
             config.chunk_size = size;
+            config.adaptive_chunking = false;
         }
 
         if self.threads.is_some() {
             config.threads = self.threads;
         }
+        if let Some(bytes) = self
+            .adaptive_threshold
+            .or(preset.map(PerfProfile::adaptive_threshold))
+        {
+            config.adaptive_threshold = bytes;
+        }
+        config.max_threads = self.max_threads.or(preset.map(PerfProfile::max_threads));
+
+        if let Some(policy) = self.chunk_policy {
+            config.chunk_policy = policy;
+        }
+        if let Some((read, emit)) = self.stream_buffers {
+            config.read_buffers = read;
+            config.emit_buffers = emit;
+        }
+        config.read_ahead = self.read_ahead;
+        config.chunk_cache = self.chunk_cache;
+        config.offset_units = self.offset_units;
+        config.invalid_utf8 = self.invalid_utf8;
+        config.strip_ruby = self.strip_ruby;
+        #[cfg(feature = "normalization")]
+        {
+            config.normalization = self.normalization;
+            config.offset_space = self.offset_space;
+        }
+        config.ellipsis = self.ellipsis;
+        config.ellipsis_exceptions = self.ellipsis_exceptions;
+        config.enclosure_boundaries = self.enclosure_boundaries;
+        config.emoji_terminators = self.emoji_terminators;
+        config.web_tokens = self.web_tokens;
+        config.line_breaks = self
+            .line_breaks
+            .unwrap_or_else(|| self.profile.is_some_and(Profile::line_break_boundaries));
+        config.profile = self.profile;
+        config.min_sentence_chars = self.min_sentence_chars;
+        config.max_sentence_chars = self.max_sentence_chars;
+        config.force_split_bytes = self.force_split_bytes;
+        config.include_rejected_candidates = self.include_rejected_candidates;
+        config.enclosure_warnings = self.enclosure_warnings;
+        if let Some(chars) = self.long_sentence_chars {
+            config.long_sentence_chars = chars;
+        }
+        config.close_enclosures_at_paragraphs = self.close_enclosures_at_paragraphs;
+        config.max_enclosure_depth = self.max_enclosure_depth;
+        config.infer_quote_direction = self.infer_quote_direction;
+        config.dedupe = self.dedupe;
+        config.suppression_regexes = self
+            .suppression_regexes
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| {
+                    Error::Configuration(format!("invalid suppression regex {pattern:?}: {e}"))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        config.filters = self.filters;
+        config.token_estimator = self.token_estimator;
 
         config.validate()?;
         Ok(config)
@@ -142,9 +987,48 @@ mod tests {
         let config = Config::default();
         assert_eq!(config.chunk_size, defaults::CHUNK_SIZE);
         assert!(config.threads.is_none());
+        assert!(config.adaptive_chunking);
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_explicit_chunk_size_disables_adaptive_chunking() {
+        assert!(Config::builder().build().unwrap().adaptive_chunking);
+        let config = Config::builder().chunk_size(64 * 1024).build().unwrap();
+        assert!(!config.adaptive_chunking);
+        assert!(!Config::large_text().adaptive_chunking);
+    }
+
+    #[test]
+    fn test_perf_profile_yields_to_explicit_options() {
+        let config = Config::builder()
+            .perf_profile(PerfProfile::Container2c)
+            .build()
+            .unwrap();
+        assert_eq!(config.max_threads, Some(2));
+        assert_eq!(config.adaptive_threshold, 1024 * 1024);
+        assert!(!config.adaptive_chunking);
+
+        let config = Config::builder()
+            .max_threads(3)
+            .perf_profile(PerfProfile::Laptop)
+            .chunk_size(8 * 1024)
+            .build()
+            .unwrap();
+        assert_eq!(config.max_threads, Some(3));
+        assert_eq!(config.chunk_size, 8 * 1024);
+        assert_eq!(config.adaptive_threshold, 512 * 1024);
+
+        for profile in [
+            PerfProfile::Laptop,
+            PerfProfile::Server32c,
+            PerfProfile::Container2c,
+        ] {
+            assert_eq!(profile.name().parse::<PerfProfile>().unwrap(), profile);
+        }
+        assert!("desktop".parse::<PerfProfile>().is_err());
+    }
+
     #[test]
     fn test_config_validation() {
         // Invalid chunk size
@@ -160,6 +1044,16 @@ mod tests {
             ..Default::default()
         };
         assert!(config.validate().is_err());
+        assert!(Config::builder().adaptive_threshold(0).build().is_err());
+        assert!(Config::builder().max_threads(0).build().is_err());
+
+        // Invalid sentence length limits
+        assert!(Config::builder().max_sentence_chars(0).build().is_err());
+        assert!(Config::builder()
+            .min_sentence_chars(20)
+            .max_sentence_chars(10)
+            .build()
+            .is_err());
     }
 
     #[test]
@@ -0,0 +1,292 @@
+//! TOML form of a [`Config`]
+//!
+//! Keys are the [`ConfigBuilder`](crate::ConfigBuilder) options, in bytes
+//! where the builder takes bytes; an absent key keeps the builder's
+//! default.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::config::defaults;
+use crate::api::{
+    ChunkPolicy, Config, DepthOverflow, EllipsisPolicy, Error, InvalidUtf8, PerfProfile, Profile,
+};
+#[cfg(feature = "normalization")]
+use crate::api::{Normalization, OffsetSpace};
+use crate::domain::language::config::{EnclosureBoundaries, ExceptionPattern};
+
+/// The options of a configuration, as written in TOML
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    language: Option<String>,
+    /// Absent when the chunk size is chosen per text
+    chunk_size: Option<usize>,
+    chunk_policy: ChunkPolicy,
+    threads: Option<usize>,
+    adaptive_threshold: Option<usize>,
+    max_threads: Option<usize>,
+    /// Read only: the preset's values are written as the options above
+    #[serde(skip_serializing)]
+    perf_profile: Option<PerfProfile>,
+    read_buffers: Option<usize>,
+    emit_buffers: Option<usize>,
+    read_ahead: bool,
+    utf16_offsets: bool,
+    grapheme_offsets: bool,
+    invalid_utf8: InvalidUtf8,
+    strip_ruby: bool,
+    #[cfg(feature = "normalization")]
+    normalization: Option<Normalization>,
+    #[cfg(feature = "normalization")]
+    offset_space: OffsetSpace,
+    ellipsis: EllipsisPolicy,
+    emoji_terminators: Option<bool>,
+    web_tokens: Option<bool>,
+    line_breaks: Option<bool>,
+    profile: Option<Profile>,
+    min_sentence_chars: Option<usize>,
+    max_sentence_chars: Option<usize>,
+    force_split_bytes: Option<usize>,
+    include_rejected_candidates: bool,
+    enclosure_warnings: bool,
+    long_sentence_chars: Option<usize>,
+    close_enclosures_at_paragraphs: bool,
+    max_enclosure_depth: Option<usize>,
+    depth_overflow: Option<DepthOverflow>,
+    infer_quote_direction: bool,
+    dedupe: bool,
+    suppression_regexes: Vec<String>,
+    /// Policies by opening or closing character
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    enclosure_boundaries: BTreeMap<String, EnclosureBoundaries>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ellipsis_exceptions: Vec<ExceptionPattern>,
+}
+
+impl Config {
+    /// The configuration as TOML, which [`Config::from_toml`] reads back
+    ///
+    /// Every option is written, defaults included, except those left to
+    /// the language configuration or chosen per text (such as the thread
+    /// count when unset). Filters registered with
+    /// [`ConfigBuilder::boundary_filter`](crate::ConfigBuilder::boundary_filter),
+    /// token estimators and chunk caches are code and are not written.
+    ///
+    /// ```rust
+    /// use sakurs_core::Config;
+    ///
+    /// let config = Config::builder().min_sentence_chars(5).build().unwrap();
+    /// let toml = config.to_toml();
+    /// assert!(toml.contains("min_sentence_chars = 5"));
+    /// assert_eq!(Config::from_toml(&toml).unwrap().to_toml(), toml);
+    /// ```
+    pub fn to_toml(&self) -> String {
+        let file = ConfigFile {
+            language: Some(self.language.code().to_string()),
+            chunk_size: (!self.adaptive_chunking).then_some(self.chunk_size),
+            chunk_policy: self.chunk_policy,
+            threads: self.threads,
+            adaptive_threshold: Some(self.adaptive_threshold),
+            max_threads: self.max_threads,
+            perf_profile: None,
+            read_buffers: Some(self.read_buffers),
+            emit_buffers: Some(self.emit_buffers),
+            read_ahead: self.read_ahead,
+            utf16_offsets: self.offset_units.utf16,
+            grapheme_offsets: self.offset_units.grapheme,
+            invalid_utf8: self.invalid_utf8,
+            strip_ruby: self.strip_ruby,
+            #[cfg(feature = "normalization")]
+            normalization: self.normalization,
+            #[cfg(feature = "normalization")]
+            offset_space: self.offset_space,
+            ellipsis: self.ellipsis,
+            emoji_terminators: self.emoji_terminators,
+            web_tokens: self.web_tokens,
+            line_breaks: Some(self.line_breaks),
+            profile: self.profile,
+            min_sentence_chars: self.min_sentence_chars,
+            max_sentence_chars: self.max_sentence_chars,
+            force_split_bytes: self.force_split_bytes,
+            include_rejected_candidates: self.include_rejected_candidates,
+            enclosure_warnings: self.enclosure_warnings,
+            long_sentence_chars: Some(self.long_sentence_chars),
+            close_enclosures_at_paragraphs: self.close_enclosures_at_paragraphs,
+            max_enclosure_depth: self.max_enclosure_depth.map(|cap| cap.max_depth),
+            depth_overflow: self.max_enclosure_depth.map(|cap| cap.overflow),
+            infer_quote_direction: self.infer_quote_direction,
+            dedupe: self.dedupe,
+            suppression_regexes: self
+                .suppression_regexes
+                .iter()
+                .map(|regex| regex.as_str().to_string())
+                .collect(),
+            enclosure_boundaries: self
+                .enclosure_boundaries
+                .iter()
+                .map(|&(ch, boundaries)| (ch.to_string(), boundaries))
+                .collect(),
+            ellipsis_exceptions: self.ellipsis_exceptions.clone(),
+        };
+        toml::to_string(&file).expect("configuration options serialize to TOML")
+    }
+
+    /// Read a configuration written by [`Config::to_toml`], or by hand with
+    /// any of its keys
+    ///
+    /// Fails with [`Error::Configuration`] on an unknown key, a value of
+    /// the wrong type, or options the builder rejects.
+    pub fn from_toml(toml: &str) -> Result<Config, Error> {
+        let file: ConfigFile = toml::from_str(toml)
+            .map_err(|e| Error::Configuration(format!("invalid configuration TOML: {e}")))?;
+
+        let mut builder = Config::builder()
+            .chunk_policy(file.chunk_policy)
+            .threads(file.threads)
+            .read_ahead(file.read_ahead)
+            .utf16_offsets(file.utf16_offsets)
+            .grapheme_offsets(file.grapheme_offsets)
+            .invalid_utf8(file.invalid_utf8)
+            .strip_ruby(file.strip_ruby)
+            .ellipsis(file.ellipsis)
+            .include_rejected_candidates(file.include_rejected_candidates)
+            .enclosure_warnings(file.enclosure_warnings)
+            .close_enclosures_at_paragraphs(file.close_enclosures_at_paragraphs)
+            .infer_quote_direction(file.infer_quote_direction)
+            .dedupe(file.dedupe)
+            .suppression_regex(file.suppression_regexes);
+        if let Some(code) = file.language {
+            builder = builder.language(code)?;
+        }
+        if let Some(bytes) = file.chunk_size {
+            builder = builder.chunk_size(bytes);
+        }
+        if let Some(bytes) = file.adaptive_threshold {
+            builder = builder.adaptive_threshold(bytes);
+        }
+        if let Some(count) = file.max_threads {
+            builder = builder.max_threads(count);
+        }
+        if let Some(chars) = file.long_sentence_chars {
+            builder = builder.long_sentence_chars(chars);
+        }
+        if let Some(profile) = file.perf_profile {
+            builder = builder.perf_profile(profile);
+        }
+        if file.read_buffers.is_some() || file.emit_buffers.is_some() {
+            builder = builder.stream_buffers(
+                file.read_buffers.unwrap_or(defaults::READ_BUFFERS),
+                file.emit_buffers.unwrap_or(defaults::EMIT_BUFFERS),
+            );
+        }
+        #[cfg(feature = "normalization")]
+        {
+            if let Some(form) = file.normalization {
+                builder = builder.normalization(form);
+            }
+            builder = builder.offset_space(file.offset_space);
+        }
+        if let Some(enabled) = file.emoji_terminators {
+            builder = builder.emoji_terminators(enabled);
+        }
+        if let Some(enabled) = file.web_tokens {
+            builder = builder.protect_web_tokens(enabled);
+        }
+        if let Some(enabled) = file.line_breaks {
+            builder = builder.line_break_boundaries(enabled);
+        }
+        if let Some(profile) = file.profile {
+            builder = builder.profile(profile);
+        }
+        if let Some(chars) = file.min_sentence_chars {
+            builder = builder.min_sentence_chars(chars);
+        }
+        if let Some(chars) = file.max_sentence_chars {
+            builder = builder.max_sentence_chars(chars);
+        }
+        if let Some(bytes) = file.force_split_bytes {
+            builder = builder.force_split_bytes(bytes);
+        }
+        if let Some(depth) = file.max_enclosure_depth {
+            let overflow = file.depth_overflow.unwrap_or_default();
+            builder = builder.max_enclosure_depth(depth, overflow);
+        }
+        for (key, boundaries) in file.enclosure_boundaries {
+            let mut chars = key.chars();
+            let (Some(ch), None) = (chars.next(), chars.next()) else {
+                return Err(Error::Configuration(format!(
+                    "enclosure_boundaries keys must be single characters, got: {key:?}"
+                )));
+            };
+            builder = builder.enclosure_boundaries(ch, boundaries);
+        }
+        for exception in file.ellipsis_exceptions {
+            builder = builder.ellipsis_exception(exception.regex, exception.boundary);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let config = Config::builder()
+            .language("ja")
+            .unwrap()
+            .chunk_size(4096)
+            .threads(Some(2))
+            .grapheme_offsets(true)
+            .invalid_utf8(InvalidUtf8::Replace)
+            .ellipsis(EllipsisPolicy::BeforeCapital)
+            .ellipsis_exception(r"\.\.\.$", true)
+            .enclosure_boundaries('「', EnclosureBoundaries::Dialogue)
+            .profile(Profile::Legal)
+            .max_enclosure_depth(3, DepthOverflow::ResetAtBlankLine)
+            .suppression_regex(vec![r"[A-Z]+-\d+\.".into()])
+            .build()
+            .unwrap();
+        let toml = config.to_toml();
+        assert!(toml.contains("depth_overflow = \"reset_at_blank_line\""));
+        assert!(toml.contains("\"「\" = \"dialogue\""));
+
+        let read = Config::from_toml(&toml).unwrap();
+        assert_eq!(read.to_toml(), toml);
+        assert_eq!(read.language, config.language);
+        assert_eq!(read.chunk_size, 4096);
+        assert!(!read.adaptive_chunking);
+        assert_eq!(read.max_enclosure_depth, config.max_enclosure_depth);
+
+        // Absent keys keep the defaults
+        let read = Config::from_toml("").unwrap();
+        assert_eq!(read.to_toml(), Config::default().to_toml());
+        assert!(read.adaptive_chunking);
+
+        // A preset is written as the options it sets
+        let read = Config::from_toml("perf_profile = \"server-32c\"").unwrap();
+        assert_eq!(read.max_threads, Some(32));
+        assert!(read.to_toml().contains("max_threads = 32"));
+        assert!(!read.to_toml().contains("perf_profile"));
+    }
+
+    #[test]
+    fn test_from_toml_rejects_bad_options() {
+        for toml in [
+            "chunk_sise = 4096",
+            "threads = \"two\"",
+            "ellipsis = \"sometimes\"",
+            "threads = 0",
+            "emit_buffers = 0",
+            "[enclosure_boundaries]\n\"「」\" = \"allow\"",
+        ] {
+            assert!(
+                matches!(Config::from_toml(toml), Err(Error::Configuration(_))),
+                "{toml}"
+            );
+        }
+    }
+}
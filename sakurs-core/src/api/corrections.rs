@@ -0,0 +1,233 @@
+//! Boundary corrections: per-document overrides that accept or reject a
+//! boundary at a given offset, applied after every rule, filter and
+//! post-processing step
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::api::{Error, RejectionReason};
+
+/// What a correction does at its offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CorrectionAction {
+    /// There is a boundary at the offset
+    Accept,
+    /// There is no boundary at the offset
+    Reject,
+}
+
+/// A boundary override at a byte offset of one document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Correction {
+    /// Byte offset of the boundary, just after its terminator, as in
+    /// [`Boundary::offset`](crate::Boundary::offset)
+    pub offset: usize,
+    /// Whether the offset is a boundary
+    pub action: CorrectionAction,
+}
+
+impl Correction {
+    /// A boundary at `offset`
+    pub fn accept(offset: usize) -> Self {
+        Self {
+            offset,
+            action: CorrectionAction::Accept,
+        }
+    }
+
+    /// No boundary at `offset`
+    pub fn reject(offset: usize) -> Self {
+        Self {
+            offset,
+            action: CorrectionAction::Reject,
+        }
+    }
+}
+
+/// Corrections for a set of documents, keyed by document id
+///
+/// Corrections fix recurring errors in specific documents without changing
+/// the rules for every text: pass a document's corrections to
+/// [`SentenceProcessor::process_with_corrections`](crate::SentenceProcessor::process_with_corrections).
+/// A corrections file lists one `[[correction]]` table per override:
+///
+/// ```rust
+/// use sakurs_core::{Correction, Corrections, Input, SentenceProcessor};
+///
+/// let corrections: Corrections = r#"
+///     [[correction]]
+///     document = "notes/visit-12.txt"
+///     offset = 3
+///     action = "reject"
+/// "#
+/// .parse()
+/// .unwrap();
+/// assert_eq!(
+///     corrections.for_document("notes/visit-12.txt"),
+///     [Correction::reject(3)]
+/// );
+///
+/// let output = SentenceProcessor::new()
+///     .process_with_corrections(
+///         Input::from_text("Pt. seen today. No fever. Stable."),
+///         corrections.for_document("notes/visit-12.txt"),
+///     )
+///     .unwrap();
+/// let offsets: Vec<usize> = output.boundaries.iter().map(|b| b.offset).collect();
+/// assert_eq!(offsets, [15, 25, 33]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Corrections {
+    /// Each document's corrections, sorted by offset, one per offset
+    documents: HashMap<String, Vec<Correction>>,
+}
+
+/// The TOML layout of a corrections file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CorrectionsFile {
+    #[serde(default)]
+    correction: Vec<CorrectionEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CorrectionEntry {
+    document: String,
+    offset: usize,
+    action: CorrectionAction,
+}
+
+impl Corrections {
+    /// An empty set of corrections
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a corrections file (TOML)
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            Error::Configuration(format!(
+                "Failed to read corrections file '{}': {e}",
+                path.display()
+            ))
+        })?;
+        content.parse().map_err(|e| match e {
+            Error::Configuration(msg) => Error::Configuration(format!("{}: {msg}", path.display())),
+            e => e,
+        })
+    }
+
+    /// Add a correction for `document`, replacing any at the same offset
+    pub fn insert(&mut self, document: impl Into<String>, correction: Correction) {
+        let corrections = self.documents.entry(document.into()).or_default();
+        match corrections.binary_search_by_key(&correction.offset, |c| c.offset) {
+            Ok(i) => corrections[i] = correction,
+            Err(i) => corrections.insert(i, correction),
+        }
+    }
+
+    /// The corrections of `document`, sorted by offset (empty if it has none)
+    pub fn for_document(&self, document: &str) -> &[Correction] {
+        self.documents.get(document).map_or(&[], Vec::as_slice)
+    }
+
+    /// Number of corrections across all documents
+    pub fn len(&self) -> usize {
+        self.documents.values().map(Vec::len).sum()
+    }
+
+    /// Whether there are no corrections
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+}
+
+impl FromStr for Corrections {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let file: CorrectionsFile = toml::from_str(s)
+            .map_err(|e| Error::Configuration(format!("invalid corrections: {e}")))?;
+        let mut corrections = Self::new();
+        for entry in file.correction {
+            let conflict = corrections
+                .for_document(&entry.document)
+                .iter()
+                .any(|c| c.offset == entry.offset && c.action != entry.action);
+            if conflict {
+                return Err(Error::Configuration(format!(
+                    "corrections both accept and reject offset {} of '{}'",
+                    entry.offset, entry.document
+                )));
+            }
+            corrections.insert(
+                entry.document,
+                Correction {
+                    offset: entry.offset,
+                    action: entry.action,
+                },
+            );
+        }
+        Ok(corrections)
+    }
+}
+
+/// Apply `corrections` (sorted by offset) to the final sorted `boundaries`
+/// over `text`, keeping the sorted `forced` boundaries, the candidates'
+/// verdicts and the `detected` boundaries consistent with them
+pub(crate) fn apply(
+    corrections: &[Correction],
+    text: &str,
+    boundaries: &mut Vec<usize>,
+    forced: &mut Vec<usize>,
+    candidates: &mut [(usize, Result<(), RejectionReason>)],
+    detected: Option<&mut Vec<usize>>,
+) -> Result<(), Error> {
+    if corrections.is_empty() {
+        return Ok(());
+    }
+    if let Some(c) = corrections
+        .iter()
+        .find(|c| c.offset == 0 || c.offset > text.len() || !text.is_char_boundary(c.offset))
+    {
+        return Err(Error::InvalidInput(format!(
+            "correction offset {} is not a character boundary inside the text ({} bytes)",
+            c.offset,
+            text.len()
+        )));
+    }
+
+    let rejected: Vec<usize> = corrections
+        .iter()
+        .filter(|c| c.action == CorrectionAction::Reject)
+        .map(|c| c.offset)
+        .collect();
+    let is_rejected = |offset: &usize| rejected.binary_search(offset).is_ok();
+    boundaries.retain(|offset| !is_rejected(offset));
+    forced.retain(|offset| !is_rejected(offset));
+    if let Some(detected) = detected {
+        detected.retain(|offset| !is_rejected(offset));
+    }
+    for correction in corrections {
+        if correction.action == CorrectionAction::Accept {
+            if let Err(i) = boundaries.binary_search(&correction.offset) {
+                boundaries.insert(i, correction.offset);
+            }
+        }
+    }
+
+    for (offset, verdict) in candidates.iter_mut() {
+        if let Ok(i) = corrections.binary_search_by_key(offset, |c| c.offset) {
+            *verdict = match corrections[i].action {
+                CorrectionAction::Accept => Ok(()),
+                CorrectionAction::Reject => Err(RejectionReason::Corrected),
+            };
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Detection of repeated sentences
+//!
+//! Sentences are compared by a 64-bit FNV-1a hash of their normalized text:
+//! words split on whitespace, lowercased and joined by single spaces. Only
+//! the hashes are kept, so memory grows with the number of distinct
+//! sentences rather than with their length.
+
+use std::collections::HashSet;
+
+use crate::api::fingerprint::Fnv;
+use crate::api::Boundary;
+
+/// Remembers the sentences seen so far, to flag the ones that repeat
+///
+/// Two sentences repeat each other when they are equal once case is folded
+/// and whitespace collapsed, so `Hello  world.` repeats `hello world.`.
+/// Blank sentences are never duplicates. This is the check behind
+/// [`ConfigBuilder::dedupe`](crate::ConfigBuilder::dedupe), for callers
+/// that split text in pieces or across several calls.
+///
+/// ```rust
+/// use sakurs_core::SentenceDeduplicator;
+///
+/// let mut seen = SentenceDeduplicator::new();
+/// assert!(!seen.is_duplicate("Click here."));
+/// assert!(!seen.is_duplicate("Read more."));
+/// assert!(seen.is_duplicate(" click  HERE.\n"));
+/// assert_eq!(seen.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SentenceDeduplicator {
+    seen: HashSet<u64>,
+}
+
+impl SentenceDeduplicator {
+    /// Start with no sentences seen
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `sentence` repeats a sentence seen before; it is remembered
+    /// either way
+    pub fn is_duplicate(&mut self, sentence: &str) -> bool {
+        hash(sentence).is_some_and(|hash| !self.seen.insert(hash))
+    }
+
+    /// Number of distinct sentences seen
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether no sentence has been seen
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+/// Hash of the normalized text of `sentence`, `None` when it is blank
+fn hash(sentence: &str) -> Option<u64> {
+    let mut words = sentence.split_whitespace();
+    let first = words.next()?;
+    let mut hash = Fnv::new();
+    let mut write = |word: &str| {
+        for c in word.chars().flat_map(char::to_lowercase) {
+            hash.write_bytes(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+    };
+    write(first);
+    for word in words {
+        write(" ");
+        write(word);
+    }
+    Some(hash.finish())
+}
+
+/// Indexes of the sentences of `text` that repeat an earlier sentence seen
+/// by `seen`; sentence `i` ends at `boundaries[i]`, and sentence
+/// `boundaries.len()` is the text after the last boundary
+pub(crate) fn duplicates(
+    text: &str,
+    boundaries: &[Boundary],
+    seen: &mut SentenceDeduplicator,
+) -> Vec<usize> {
+    let ends = boundaries
+        .iter()
+        .map(|boundary| boundary.offset)
+        .chain(std::iter::once(text.len()));
+    let mut start = 0;
+    let mut duplicates = Vec::new();
+    for (index, end) in ends.enumerate() {
+        if seen.is_duplicate(&text[start..end]) {
+            duplicates.push(index);
+        }
+        start = end;
+    }
+    duplicates
+}
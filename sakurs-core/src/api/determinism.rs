@@ -0,0 +1,162 @@
+//! Checking that boundaries do not depend on how the text is cut
+
+use std::fmt;
+
+use crate::application::{DeltaStackProcessor, ExecutionMode};
+
+/// Threads used by the parallel runs of a determinism check
+const PARALLEL_THREADS: usize = 4;
+
+/// Random piece splittings streamed per chunk size
+const RANDOM_SPLITS: u64 = 2;
+
+/// A run of [`SentenceProcessor::verify_determinism`](crate::SentenceProcessor::verify_determinism)
+/// whose boundaries differ from sequential processing of the whole text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeterminismViolation {
+    /// How the text was processed, e.g. `parallel, chunk size 7, 4 threads`
+    pub run: String,
+    /// Boundary byte offsets of sequential processing in a single chunk
+    pub expected: Vec<usize>,
+    /// Boundary byte offsets of the run
+    pub actual: Vec<usize>,
+}
+
+impl DeterminismViolation {
+    /// The first boundary offset present in one result but not the other
+    pub fn first_difference(&self) -> Option<usize> {
+        let mismatch = self
+            .expected
+            .iter()
+            .zip(&self.actual)
+            .find(|(expected, actual)| expected != actual)
+            .map(|(&expected, &actual)| expected.min(actual));
+        mismatch.or_else(|| {
+            let shorter = self.expected.len().min(self.actual.len());
+            self.expected
+                .get(shorter)
+                .or_else(|| self.actual.get(shorter))
+                .copied()
+        })
+    }
+}
+
+impl fmt::Display for DeterminismViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: boundaries differ from sequential processing",
+            self.run
+        )?;
+        if let Some(offset) = self.first_difference() {
+            write!(f, " at byte {offset}")?;
+        }
+        write!(f, " (expected {:?}, got {:?})", self.expected, self.actual)
+    }
+}
+
+impl std::error::Error for DeterminismViolation {}
+
+/// Compare the boundaries of `text` cut every way up to `max_chunk_size`
+/// bytes against a sequential single-chunk run (see
+/// [`SentenceProcessor::verify_determinism`](crate::SentenceProcessor::verify_determinism))
+pub(crate) fn verify(
+    processor: &DeltaStackProcessor,
+    text: &str,
+    max_chunk_size: usize,
+) -> Result<(), DeterminismViolation> {
+    let expected = batch(
+        &processor.with_chunk_size(text.len().max(1)),
+        text,
+        ExecutionMode::Sequential,
+    );
+    let check = |run: String, actual: Vec<usize>| {
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(DeterminismViolation {
+                run,
+                expected: expected.clone(),
+                actual,
+            })
+        }
+    };
+
+    for chunk_size in 1..=max_chunk_size.max(1) {
+        let chunked = processor.with_chunk_size(chunk_size);
+        check(
+            format!("sequential, chunk size {chunk_size}"),
+            batch(&chunked, text, ExecutionMode::Sequential),
+        )?;
+        check(
+            format!("parallel, chunk size {chunk_size}, {PARALLEL_THREADS} threads"),
+            batch(
+                &chunked,
+                text,
+                ExecutionMode::Parallel {
+                    threads: Some(PARALLEL_THREADS),
+                },
+            ),
+        )?;
+        check(
+            format!("streamed in pieces of {chunk_size} bytes"),
+            streamed(&chunked, text, |_| chunk_size),
+        )?;
+        for seed in 0..RANDOM_SPLITS {
+            let mut rng = XorShift::new(chunk_size as u64 * RANDOM_SPLITS + seed);
+            check(
+                format!("streamed in random pieces (chunk size {chunk_size}, seed {seed})"),
+                streamed(&chunked, text, |_| {
+                    1 + rng.next() as usize % (2 * chunk_size)
+                }),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn batch(processor: &DeltaStackProcessor, text: &str, mode: ExecutionMode) -> Vec<usize> {
+    processor
+        .process_with_progress(text, mode, None)
+        .expect("segmenting a string should not fail")
+        .boundaries
+}
+
+/// Feed `text` in pieces of `piece_len(start)` bytes, each extended to the
+/// next character boundary
+fn streamed(
+    processor: &DeltaStackProcessor,
+    text: &str,
+    mut piece_len: impl FnMut(usize) -> usize,
+) -> Vec<usize> {
+    let mut stream = processor.stream();
+    let mut boundaries = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + piece_len(start).max(1)).min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        boundaries.extend(stream.feed(&text[start..end]));
+        start = end;
+    }
+    boundaries.extend(stream.finish());
+    boundaries
+}
+
+/// Small seeded generator for reproducible split points
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // The state must not be zero
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -0,0 +1,179 @@
+//! User-supplied boundary filters: domain logic run on every candidate after
+//! the language's rules
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::api::RejectionReason;
+
+/// A filter's decision on a boundary candidate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterDecision {
+    /// Leave the verdict as it is
+    Keep,
+    /// The candidate is not a boundary
+    Veto,
+    /// The candidate is a boundary
+    Force,
+}
+
+/// A boundary candidate with its context, as seen by a [`BoundaryFilter`]
+#[derive(Debug, Clone, Copy)]
+pub struct BoundaryCandidate<'a> {
+    text: &'a str,
+    offset: usize,
+    is_boundary: bool,
+    reason: Option<RejectionReason>,
+}
+
+impl<'a> BoundaryCandidate<'a> {
+    /// The whole text being segmented
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Byte offset just after the candidate, where the boundary would be
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The character that triggered the candidate (a terminator such as '.')
+    pub fn terminator(&self) -> char {
+        self.before()
+            .chars()
+            .next_back()
+            .expect("a candidate follows its trigger character")
+    }
+
+    /// The text before the boundary position, including the terminator
+    pub fn before(&self) -> &'a str {
+        &self.text[..self.offset]
+    }
+
+    /// The text after the boundary position
+    pub fn after(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    /// Whether the candidate is a boundary so far: by the language's rules,
+    /// or by the decision of an earlier filter
+    pub fn is_boundary(&self) -> bool {
+        self.is_boundary
+    }
+
+    /// Why the candidate is not a boundary so far
+    pub fn reason(&self) -> Option<RejectionReason> {
+        self.reason
+    }
+}
+
+/// Domain logic that vetoes or forces boundary candidates
+///
+/// Filters see every terminator candidate, accepted or rejected by the
+/// language's rules, in text order. Closures taking a
+/// [`BoundaryCandidate`] implement the trait.
+///
+/// ```rust
+/// use sakurs_core::{Config, FilterDecision, Input, SentenceProcessor};
+///
+/// // Never split after a ticket number such as "REQ-12."
+/// let config = Config::builder()
+///     .boundary_filter(|c: &sakurs_core::BoundaryCandidate| {
+///         let word = c.before().rsplit(' ').next().unwrap_or_default();
+///         if word.starts_with("REQ-") && c.after().starts_with(" see") {
+///             FilterDecision::Veto
+///         } else {
+///             FilterDecision::Keep
+///         }
+///     })
+///     .build()
+///     .unwrap();
+/// let processor = SentenceProcessor::with_config(config).unwrap();
+/// let output = processor
+///     .process(Input::from_text("Filed as REQ-12. see notes. Done."))
+///     .unwrap();
+/// assert_eq!(output.boundaries.len(), 2);
+/// ```
+pub trait BoundaryFilter: Send + Sync {
+    /// Decide on one candidate
+    fn filter(&self, candidate: &BoundaryCandidate<'_>) -> FilterDecision;
+}
+
+impl<F> BoundaryFilter for F
+where
+    F: Fn(&BoundaryCandidate<'_>) -> FilterDecision + Send + Sync,
+{
+    fn filter(&self, candidate: &BoundaryCandidate<'_>) -> FilterDecision {
+        self(candidate)
+    }
+}
+
+/// The filters registered on a configuration, run in registration order
+#[derive(Clone, Default)]
+pub(crate) struct BoundaryFilters(Vec<Arc<dyn BoundaryFilter>>);
+
+impl BoundaryFilters {
+    pub(crate) fn push(&mut self, filter: Arc<dyn BoundaryFilter>) {
+        self.0.push(filter);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Run the filters over `candidates` (sorted, each with the rules'
+    /// verdict), updating the verdicts and the sorted `boundaries`
+    pub(crate) fn apply(
+        &self,
+        text: &str,
+        candidates: &mut [(usize, Result<(), RejectionReason>)],
+        boundaries: &mut Vec<usize>,
+    ) {
+        let mut vetoed = Vec::new();
+        let mut forced = Vec::new();
+        for (offset, verdict) in candidates.iter_mut() {
+            let mut candidate = BoundaryCandidate {
+                text,
+                offset: *offset,
+                is_boundary: verdict.is_ok(),
+                reason: verdict.err(),
+            };
+            for filter in &self.0 {
+                match filter.filter(&candidate) {
+                    FilterDecision::Veto if candidate.is_boundary => {
+                        candidate.is_boundary = false;
+                        candidate.reason = Some(RejectionReason::Filter);
+                    }
+                    FilterDecision::Keep | FilterDecision::Veto => {}
+                    FilterDecision::Force => {
+                        candidate.is_boundary = true;
+                        candidate.reason = None;
+                    }
+                }
+            }
+            let was_boundary = verdict.is_ok();
+            *verdict = candidate.reason.map_or(Ok(()), Err);
+            if candidate.is_boundary != was_boundary {
+                if candidate.is_boundary {
+                    forced.push(*offset);
+                } else {
+                    vetoed.push(*offset);
+                }
+            }
+        }
+        if !vetoed.is_empty() {
+            // Candidates are in text order, so `vetoed` is sorted
+            boundaries.retain(|offset| vetoed.binary_search(offset).is_err());
+        }
+        if !forced.is_empty() {
+            boundaries.extend(forced);
+            boundaries.sort_unstable();
+        }
+    }
+}
+
+impl fmt::Debug for BoundaryFilters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BoundaryFilters({})", self.0.len())
+    }
+}
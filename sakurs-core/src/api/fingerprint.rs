@@ -0,0 +1,150 @@
+//! Stable fingerprints of segmentation results
+//!
+//! A fingerprint hashes the sakurs version, the effective language rules,
+//! the configuration options that can change boundaries, and the
+//! boundaries themselves, with 64-bit FNV-1a: unlike the standard
+//! library's hasher it is the same on every platform and run, so
+//! fingerprints can be stored and compared later.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Write};
+
+use crate::api::{Boundary, Config};
+use crate::domain::language::config::{LanguageConfig, SentenceStarterConfig};
+
+/// 64-bit FNV-1a
+pub(crate) struct Fnv(u64);
+
+impl Fnv {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Write for Fnv {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Fingerprint of the rules a processor applies: the sakurs version,
+/// `language` with the configuration's overrides applied, and the options
+/// of `config` that can move a boundary
+///
+/// Execution settings (chunking, threads) are left out, as boundaries do
+/// not depend on them; user filters count only by number.
+pub(crate) fn rules(config: &Config, language: &LanguageConfig) -> u64 {
+    let Config {
+        // Execution settings and extra offset units
+        language: _,
+        chunk_size: _,
+        threads: _,
+        adaptive_threshold: _,
+        max_threads: _,
+        adaptive_chunking: _,
+        chunk_policy: _,
+        read_buffers: _,
+        emit_buffers: _,
+        read_ahead: _,
+        chunk_cache: _,
+        offset_units: _,
+        include_rejected_candidates: _,
+        enclosure_warnings: _,
+        long_sentence_chars: _,
+        dedupe: _,
+        token_estimator: _,
+        // Rule overrides, applied to `language`
+        ellipsis: _,
+        ellipsis_exceptions: _,
+        enclosure_boundaries: _,
+        emoji_terminators: _,
+        web_tokens: _,
+        profile: _,
+        invalid_utf8,
+        strip_ruby,
+        #[cfg(feature = "normalization")]
+        normalization,
+        #[cfg(feature = "normalization")]
+        offset_space,
+        line_breaks,
+        min_sentence_chars,
+        max_sentence_chars,
+        force_split_bytes,
+        close_enclosures_at_paragraphs,
+        max_enclosure_depth,
+        infer_quote_direction,
+        suppression_regexes,
+        filters,
+    } = config;
+    let LanguageConfig {
+        metadata,
+        terminators,
+        ellipsis,
+        enclosures,
+        suppression,
+        abbreviations,
+        sentence_starters,
+        hypothesis,
+    } = language;
+
+    let mut hash = Fnv::new();
+    // Writing to the hasher cannot fail
+    let _ = write!(hash, "sakurs {}", env!("CARGO_PKG_VERSION"));
+    let _ = write!(
+        hash,
+        "{metadata:?}{terminators:?}{ellipsis:?}{enclosures:?}{suppression:?}{hypothesis:?}"
+    );
+    // Map entries in a fixed order
+    let _ = write!(hash, "{:?}", sorted(&abbreviations.categories));
+    if let Some(SentenceStarterConfig {
+        categories,
+        threshold,
+        uppercase_fallback,
+        require_following_space,
+        min_word_length,
+    }) = sentence_starters
+    {
+        let _ = write!(
+            hash,
+            "{:?}{threshold:?}{uppercase_fallback:?}{require_following_space:?}{min_word_length:?}",
+            sorted(categories)
+        );
+    }
+    let _ = write!(
+        hash,
+        "{invalid_utf8:?}{strip_ruby:?}{line_breaks:?}{min_sentence_chars:?}\
+         {max_sentence_chars:?}{force_split_bytes:?}{close_enclosures_at_paragraphs:?}\
+         {max_enclosure_depth:?}{infer_quote_direction:?}{suppression_regexes:?}{filters:?}"
+    );
+    #[cfg(feature = "normalization")]
+    let _ = write!(hash, "{normalization:?}{offset_space:?}");
+    hash.finish()
+}
+
+/// Fingerprint of `boundaries` found with the rules fingerprinted `rules`
+pub(crate) fn output(rules: u64, boundaries: &[Boundary]) -> u64 {
+    let mut hash = Fnv::new();
+    hash.write_bytes(&rules.to_le_bytes());
+    for boundary in boundaries {
+        hash.write_bytes(&(boundary.offset as u64).to_le_bytes());
+        hash.write_bytes(boundary.kind.as_str().as_bytes());
+        hash.write_bytes(b"\0");
+    }
+    hash.finish()
+}
+
+/// The entries of `map` in key order
+fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> BTreeMap<&K, &V> {
+    map.iter().collect()
+}
@@ -0,0 +1,71 @@
+//! A processor whose rules can be replaced while it is in use
+
+use std::sync::{Arc, RwLock};
+
+use crate::api::{Error, LanguageConfig, SentenceProcessor};
+
+/// A shared [`SentenceProcessor`] that can be swapped atomically, for
+/// long-running services that pick up edited language rules without a
+/// restart
+///
+/// Each call [`load`](ProcessorHandle::load)s the current processor and
+/// keeps it to the end, so a document is never segmented with a mix of old
+/// and new rules; calls that load after a
+/// [`store`](ProcessorHandle::store) use the new processor. Clones share
+/// the processor.
+///
+/// ```rust
+/// use sakurs_core::{Input, LanguageConfig, ProcessorHandle, SentenceProcessor};
+///
+/// let handle = ProcessorHandle::new(SentenceProcessor::with_language("en").unwrap());
+/// let before = handle.load();
+///
+/// let mut rules = LanguageConfig::embedded()
+///     .into_iter()
+///     .find(|config| config.metadata.code == "en")
+///     .unwrap()
+///     .clone();
+/// rules.terminators.chars.retain(|&c| c != '!');
+/// handle.reload(&rules).unwrap();
+///
+/// let text = "Wow! Done.";
+/// assert_eq!(before.process(Input::from_text(text)).unwrap().boundaries.len(), 2);
+/// assert_eq!(handle.load().process(Input::from_text(text)).unwrap().boundaries.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct ProcessorHandle {
+    current: Arc<RwLock<Arc<SentenceProcessor>>>,
+}
+
+impl ProcessorHandle {
+    /// Share `processor`
+    pub fn new(processor: SentenceProcessor) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(processor))),
+        }
+    }
+
+    /// The current processor
+    pub fn load(&self) -> Arc<SentenceProcessor> {
+        let current = self.current.read().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(&current)
+    }
+
+    /// Replace the processor for later calls, returning the previous one
+    pub fn store(&self, processor: SentenceProcessor) -> Arc<SentenceProcessor> {
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        std::mem::replace(&mut *current, Arc::new(processor))
+    }
+
+    /// Replace the language rules, keeping the current processor's
+    /// [`Config`](crate::Config)
+    ///
+    /// The new processor is compiled before anything is swapped: rules that
+    /// fail to compile leave the current processor in place.
+    pub fn reload(&self, language: &LanguageConfig) -> Result<(), Error> {
+        let config = self.load().config().clone();
+        let processor = SentenceProcessor::with_language_config(config, language)?;
+        self.store(processor);
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+//! Text kept segmented while it is edited
+
+use crate::application::DeltaStackDocument;
+
+/// A text re-segmented edit by edit, as an editor integration needs.
+///
+/// Pass it to [`SentenceProcessor::resegment`](crate::SentenceProcessor::resegment)
+/// with each edit. The text is cached in scanned chunks of a few KiB, and an
+/// edit only rescans the chunks it touches, so the cost of an edit barely
+/// grows with the document. The output always equals processing the edited
+/// text from scratch.
+///
+/// ```rust
+/// use sakurs_core::{IncrementalText, SentenceProcessor};
+///
+/// let processor = SentenceProcessor::new();
+/// let mut text = IncrementalText::new("Hello world. This is a test.");
+/// let output = processor.resegment(&mut text, 0..0, "").unwrap();
+/// assert_eq!(output.boundaries.len(), 2);
+///
+/// // Type "Dr. Smith came. " at the start of the second sentence
+/// let output = processor.resegment(&mut text, 13..13, "Dr. Smith came. ").unwrap();
+/// assert_eq!(text.text(), "Hello world. Dr. Smith came. This is a test.");
+/// assert_eq!(output.boundaries.len(), 3);
+/// ```
+#[derive(Default)]
+pub struct IncrementalText {
+    pub(crate) text: String,
+    /// Scanned chunks of `text`, made by the processor that last
+    /// re-segmented it
+    pub(crate) document: Option<DeltaStackDocument>,
+}
+
+impl IncrementalText {
+    /// A text to re-segment, scanned on its first
+    /// [`resegment`](crate::SentenceProcessor::resegment)
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            document: None,
+        }
+    }
+
+    /// The current text, with every edit applied
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
@@ -1,7 +1,89 @@
 //! Input abstraction for unified API
 
+use crate::domain::types::Warning;
+use serde::{Deserialize, Serialize};
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How invalid UTF-8 in byte, file and reader input is handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvalidUtf8 {
+    /// Fail on the first invalid sequence
+    #[default]
+    Error,
+    /// Replace each invalid sequence with U+FFFD
+    Replace,
+    /// Drop invalid sequences
+    Skip,
+}
+
+impl InvalidUtf8 {
+    /// Decode `bytes` as UTF-8, returning the text and the number of invalid
+    /// bytes that were replaced or skipped. Valid input is not copied.
+    pub fn decode(self, bytes: Vec<u8>) -> Result<(String, usize), crate::api::Error> {
+        let (text, warning) = self.decode_with_warning(bytes)?;
+        let invalid = match warning {
+            Some(Warning::InvalidUtf8 { bytes, .. }) => bytes,
+            _ => 0,
+        };
+        Ok((text, invalid))
+    }
+
+    /// Decode `bytes` as UTF-8 like [`decode`](Self::decode), returning a
+    /// [`Warning::InvalidUtf8`] at the first invalid sequence if any were
+    /// replaced or skipped
+    pub fn decode_with_warning(
+        self,
+        bytes: Vec<u8>,
+    ) -> Result<(String, Option<Warning>), crate::api::Error> {
+        let bytes = match String::from_utf8(bytes) {
+            Ok(text) => return Ok((text, None)),
+            Err(e) if self == InvalidUtf8::Error => {
+                return Err(crate::api::Error::Infrastructure(format!(
+                    "Invalid UTF-8 encoding: {}",
+                    e.utf8_error()
+                )))
+            }
+            Err(e) => e.into_bytes(),
+        };
+
+        let mut text = String::with_capacity(bytes.len());
+        let mut invalid = 0;
+        let mut first = None;
+        for chunk in bytes.utf8_chunks() {
+            text.push_str(chunk.valid());
+            if !chunk.invalid().is_empty() {
+                first.get_or_insert(text.len());
+                invalid += chunk.invalid().len();
+                if self == InvalidUtf8::Replace {
+                    text.push(char::REPLACEMENT_CHARACTER);
+                }
+            }
+        }
+        let warning = first.map(|offset| Warning::InvalidUtf8 {
+            offset,
+            bytes: invalid,
+        });
+        Ok((text, warning))
+    }
+}
+
+impl FromStr for InvalidUtf8 {
+    type Err = crate::api::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "error" => Ok(InvalidUtf8::Error),
+            "replace" => Ok(InvalidUtf8::Replace),
+            "skip" => Ok(InvalidUtf8::Skip),
+            _ => Err(crate::api::Error::Configuration(format!(
+                "invalid_utf8 must be error, replace or skip, got: {s}"
+            ))),
+        }
+    }
+}
 
 /// Unified input abstraction for various data sources
 pub enum Input {
@@ -76,9 +158,21 @@ impl Input {
     }
 
     /// Get text content from input
+    #[cfg(test)]
     pub(crate) fn into_text(self) -> Result<String, crate::api::Error> {
-        let bytes = self.into_bytes()?;
-        String::from_utf8(bytes)
-            .map_err(|e| crate::api::Error::Infrastructure(format!("Invalid UTF-8 encoding: {e}")))
+        self.into_text_with(InvalidUtf8::Error)
+            .map(|(text, _)| text)
+    }
+
+    /// Get text content from input, with a warning if invalid UTF-8 bytes
+    /// were replaced or skipped under `invalid_utf8`
+    pub(crate) fn into_text_with(
+        self,
+        invalid_utf8: InvalidUtf8,
+    ) -> Result<(String, Option<Warning>), crate::api::Error> {
+        match self {
+            Input::Text(text) => Ok((text, None)),
+            input => invalid_utf8.decode_with_warning(input.into_bytes()?),
+        }
     }
 }
@@ -12,6 +12,16 @@ pub enum Language {
     English,
     /// Japanese language with specific punctuation rules
     Japanese,
+    /// Portuguese language rules
+    Portuguese,
+    /// Italian language rules
+    Italian,
+    /// Arabic language rules
+    Arabic,
+    /// Thai language rules (space-delimited sentences)
+    Thai,
+    /// Hindi language rules
+    Hindi,
 }
 
 impl Language {
@@ -20,6 +30,11 @@ impl Language {
         match code.trim().to_lowercase().as_str() {
             "en" | "eng" | "english" => Language::English,
             "ja" | "jpn" | "japanese" => Language::Japanese,
+            "pt" | "por" | "portuguese" => Language::Portuguese,
+            "it" | "ita" | "italian" => Language::Italian,
+            "ar" | "ara" | "arabic" => Language::Arabic,
+            "th" | "tha" | "thai" => Language::Thai,
+            "hi" | "hin" | "hindi" => Language::Hindi,
             _ => Language::English, // Default to English
         }
     }
@@ -29,6 +44,11 @@ impl Language {
         match self {
             Language::English => "en",
             Language::Japanese => "ja",
+            Language::Portuguese => "pt",
+            Language::Italian => "it",
+            Language::Arabic => "ar",
+            Language::Thai => "th",
+            Language::Hindi => "hi",
         }
     }
 
@@ -37,6 +57,11 @@ impl Language {
         match self {
             Language::English => "English",
             Language::Japanese => "Japanese",
+            Language::Portuguese => "Portuguese",
+            Language::Italian => "Italian",
+            Language::Arabic => "Arabic",
+            Language::Thai => "Thai",
+            Language::Hindi => "Hindi",
         }
     }
 }
@@ -54,6 +79,11 @@ impl FromStr for Language {
         match s.trim().to_lowercase().as_str() {
             "en" | "eng" | "english" => Ok(Language::English),
             "ja" | "jpn" | "japanese" => Ok(Language::Japanese),
+            "pt" | "por" | "portuguese" => Ok(Language::Portuguese),
+            "it" | "ita" | "italian" => Ok(Language::Italian),
+            "ar" | "ara" | "arabic" => Ok(Language::Arabic),
+            "th" | "tha" | "thai" => Ok(Language::Thai),
+            "hi" | "hin" | "hindi" => Ok(Language::Hindi),
             _ => Err(Error::InvalidLanguage(format!("Unsupported language: {s}"))),
         }
     }
@@ -0,0 +1,151 @@
+//! Engine metrics through the `metrics` facade
+//!
+//! Each successful [`SentenceProcessor`](crate::SentenceProcessor) call
+//! records, labelled with the language code:
+//!
+//! - `sakurs_documents_total`: inputs processed
+//! - `sakurs_bytes_total`: bytes of text processed
+//! - `sakurs_boundaries_total`: sentence boundaries returned
+//! - `sakurs_stage_seconds`: latency of each `stage`: `segment` (reading,
+//!   ruby stripping, normalization and the Δ-Stack engine), `enclosures`
+//!   (the balance check), `postprocess` (filters, length limits,
+//!   corrections and output) and `total`
+//!
+//! Nothing is recorded until the application installs a recorder, such as
+//! a Prometheus or OpenTelemetry exporter.
+
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use metrics::{counter, describe_counter, describe_histogram, histogram, Unit};
+
+/// Latencies of the stages of one call
+pub(crate) struct Stages {
+    start: Instant,
+    last: Instant,
+    elapsed: Vec<(&'static str, Duration)>,
+}
+
+impl Stages {
+    /// Start timing a call that started at `start`
+    pub(crate) fn start(start: Instant) -> Self {
+        Self {
+            start,
+            last: start,
+            elapsed: Vec::with_capacity(3),
+        }
+    }
+
+    /// End `stage`, which ran since the previous stage ended
+    pub(crate) fn end(&mut self, stage: &'static str) {
+        let now = Instant::now();
+        self.elapsed.push((stage, now - self.last));
+        self.last = now;
+    }
+
+    /// Record the call with the global recorder
+    pub(crate) fn record(self, language: &str, bytes: usize, boundaries: usize) {
+        static DESCRIBE: Once = Once::new();
+        DESCRIBE.call_once(describe);
+
+        let language = language.to_string();
+        counter!("sakurs_documents_total", "language" => language.clone()).increment(1);
+        counter!("sakurs_bytes_total", "language" => language.clone()).increment(bytes as u64);
+        counter!("sakurs_boundaries_total", "language" => language.clone())
+            .increment(boundaries as u64);
+        let total = ("total", self.start.elapsed());
+        for (stage, elapsed) in self.elapsed.into_iter().chain([total]) {
+            histogram!("sakurs_stage_seconds", "language" => language.clone(), "stage" => stage)
+                .record(elapsed);
+        }
+    }
+}
+
+/// Describe the metrics to the recorder
+fn describe() {
+    describe_counter!("sakurs_documents_total", "Inputs segmented into sentences");
+    describe_counter!(
+        "sakurs_bytes_total",
+        Unit::Bytes,
+        "Bytes of text segmented into sentences"
+    );
+    describe_counter!("sakurs_boundaries_total", "Sentence boundaries returned");
+    describe_histogram!(
+        "sakurs_stage_seconds",
+        Unit::Seconds,
+        "Latency of each processing stage"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    use crate::{Config, Input, SentenceProcessor};
+
+    /// Counters by name, and the stage of every histogram registration
+    #[derive(Default)]
+    struct TestRecorder {
+        counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+        stages: Mutex<Vec<String>>,
+    }
+
+    impl TestRecorder {
+        fn counter(&self, name: &str) -> u64 {
+            self.counters.lock().unwrap()[name].load(Ordering::Relaxed)
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            assert!(key
+                .labels()
+                .any(|l| l.key() == "language" && l.value() == "ja"));
+            let mut counters = self.counters.lock().unwrap();
+            Counter::from_arc(counters.entry(key.name().to_string()).or_default().clone())
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            let stage = key.labels().find(|l| l.key() == "stage").unwrap();
+            self.stages.lock().unwrap().push(stage.value().to_string());
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn test_process_records_metrics() {
+        let config = Config::builder().language("ja").unwrap().build().unwrap();
+        let processor = SentenceProcessor::with_config(config).unwrap();
+        let text = "今日は晴れ。明日は雨。";
+        let recorder = TestRecorder::default();
+        metrics::with_local_recorder(&recorder, || {
+            processor.process(Input::from_text(text)).unwrap();
+            processor.process(Input::from_text(text)).unwrap();
+        });
+
+        assert_eq!(recorder.counter("sakurs_documents_total"), 2);
+        assert_eq!(
+            recorder.counter("sakurs_bytes_total"),
+            2 * text.len() as u64
+        );
+        assert_eq!(recorder.counter("sakurs_boundaries_total"), 4);
+        assert_eq!(
+            recorder.stages.lock().unwrap()[..4],
+            ["segment", "enclosures", "postprocess", "total"]
+        );
+    }
+}
@@ -4,30 +4,78 @@
 //! that hides internal implementation details and provides a consistent API
 //! for both CLI and Python bindings.
 
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "async")]
+mod async_stream;
 mod config;
+mod config_file;
+mod corrections;
+mod dedupe;
+mod determinism;
 mod error;
+mod filter;
+mod fingerprint;
+mod handle;
+mod incremental;
 mod input;
 mod language;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "normalization")]
+mod normalize;
 mod output;
+mod perf_profile;
+mod pipeline;
+mod postprocess;
 mod processor;
+mod profile;
+mod read_ahead;
+mod ruby;
+mod stream;
+mod tokens;
 
 #[cfg(test)]
 mod tests;
 
-pub use crate::domain::language::config::LanguageConfig;
+pub use crate::application::{
+    ChunkCache, ChunkCacheStats, ChunkPolicy, DepthOverflow, ExecutionDecision, ExecutionMode,
+    ExecutionReason, Progress,
+};
+pub use crate::domain::language::config::{EnclosureBoundaries, LanguageConfig};
+#[cfg(feature = "arrow")]
+pub use arrow::SentenceBatchBuilder;
 
 /// The language configuration schema (the TOML file structure), for
 /// constructing configurations programmatically (used by the bindings).
 pub mod language_config {
     pub use crate::domain::language::config::{
-        AbbreviationConfig, ContextRule, EllipsisConfig, EnclosureConfig, EnclosurePair,
-        ExceptionPattern, FastPattern, LanguageConfig, MetadataConfig, RegexPattern,
-        SentenceStarterConfig, SuppressionConfig, TerminatorConfig, TerminatorPattern,
+        AbbreviationConfig, AbbreviationEntry, AbbreviationRule, ApostropheConfig, ContextRule,
+        EllipsisConfig, EnclosureBoundaries, EnclosureConfig, EnclosurePair, ExceptionPattern,
+        FastPattern, HypothesisConfig, LanguageConfig, MetadataConfig, RegexPattern, SchemaReport,
+        SentenceStarterConfig, StarterEntry, SuppressionConfig, TerminatorConfig,
+        TerminatorPattern, ABBREVIATION_CONDITIONS, SCHEMA_VERSION,
     };
 }
-pub use config::{Config, ConfigBuilder};
+pub use config::{Config, ConfigBuilder, EllipsisPolicy};
+pub use corrections::{Correction, CorrectionAction, Corrections};
+pub use dedupe::SentenceDeduplicator;
+pub use determinism::DeterminismViolation;
 pub use error::{Error, Result};
-pub use input::Input;
+pub use filter::{BoundaryCandidate, BoundaryFilter, FilterDecision};
+pub use handle::ProcessorHandle;
+pub use incremental::IncrementalText;
+pub use input::{Input, InvalidUtf8};
 pub use language::Language;
-pub use output::{Boundary, Output, ProcessingMetadata, ProcessingStats};
+#[cfg(feature = "normalization")]
+pub use normalize::{Normalization, OffsetSpace};
+pub use output::{
+    Boundary, BoundaryKind, Output, ProcessingMetadata, ProcessingStats, RejectedCandidate,
+    RejectionReason, SentenceWindow, Warning,
+};
+pub use perf_profile::PerfProfile;
+pub use pipeline::{ExecutionMetrics, QueueMetrics, ReaderPipeline};
 pub use processor::SentenceProcessor;
+pub use profile::Profile;
+pub use stream::{SentenceSpan, SentenceStream, StreamCheckpoint};
+pub use tokens::{chunk_sentences, BpeEstimator, TokenEstimator, WhitespaceEstimator};
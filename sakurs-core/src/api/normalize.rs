@@ -0,0 +1,141 @@
+//! Unicode normalization before segmentation (`normalization` feature)
+//!
+//! Rule tables list precomposed, half-width forms; decomposed accents and
+//! full-width compatibility punctuation slip past them. Normalizing first
+//! makes such text match, and an [`OffsetMap`] translates boundaries back to
+//! the caller's text when offsets are wanted in original coordinates.
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{is_nfc_quick, is_nfkc_quick, IsNormalized, UnicodeNormalization};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Normalization form applied before segmentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Normalization {
+    /// Canonical composition: composes decomposed accents
+    Nfc,
+    /// Compatibility composition: also folds full-width punctuation,
+    /// ligatures and similar compatibility characters
+    Nfkc,
+}
+
+/// Text that boundary offsets refer to when normalization is enabled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OffsetSpace {
+    /// The text as given
+    #[default]
+    Original,
+    /// The normalized text
+    Normalized,
+}
+
+/// A grapheme cluster that normalization changed
+#[derive(Debug, Clone, Copy)]
+struct ChangedSpan {
+    norm_start: usize,
+    norm_end: usize,
+    orig_start: usize,
+    orig_end: usize,
+}
+
+/// Maps byte offsets in normalized text back to the original text
+#[derive(Debug, Default)]
+pub(crate) struct OffsetMap {
+    /// Changed clusters in text order; unchanged text in between is shifted
+    /// by a constant
+    spans: Vec<ChangedSpan>,
+}
+
+impl OffsetMap {
+    /// Original offset of normalized offset `offset`. An offset inside a
+    /// changed cluster maps to the end of the original cluster, so a
+    /// boundary never splits a character.
+    pub(crate) fn to_original(&self, offset: usize) -> usize {
+        let i = self.spans.partition_point(|s| s.norm_start <= offset);
+        match i.checked_sub(1).map(|i| self.spans[i]) {
+            None => offset,
+            Some(span) if offset == span.norm_start => span.orig_start,
+            Some(span) if offset < span.norm_end => span.orig_end,
+            Some(span) => span.orig_end + (offset - span.norm_end),
+        }
+    }
+}
+
+/// Normalizes `text` to `form`, or returns `None` if it is already
+/// normalized.
+///
+/// Each extended grapheme cluster is normalized on its own. Composition
+/// does not cross cluster boundaries in practice, and it gives the offset
+/// map cluster granularity.
+pub(crate) fn normalize(text: &str, form: Normalization) -> Option<(String, OffsetMap)> {
+    let quick = match form {
+        Normalization::Nfc => is_nfc_quick(text.chars()),
+        Normalization::Nfkc => is_nfkc_quick(text.chars()),
+    };
+    if quick == IsNormalized::Yes {
+        return None;
+    }
+
+    let mut normalized = String::with_capacity(text.len());
+    let mut map = OffsetMap::default();
+    for (orig_start, cluster) in text.grapheme_indices(true) {
+        let norm_start = normalized.len();
+        match form {
+            Normalization::Nfc => normalized.extend(cluster.nfc()),
+            Normalization::Nfkc => normalized.extend(cluster.nfkc()),
+        }
+        if normalized[norm_start..] != *cluster {
+            map.spans.push(ChangedSpan {
+                norm_start,
+                norm_end: normalized.len(),
+                orig_start,
+                orig_end: orig_start + cluster.len(),
+            });
+        }
+    }
+    if map.spans.is_empty() {
+        return None;
+    }
+    Some((normalized, map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_already_normalized_text_is_untouched() {
+        assert!(normalize("Café. Done.", Normalization::Nfc).is_none());
+        assert!(normalize("Cafe\u{301}. Done.", Normalization::Nfc).is_some());
+    }
+
+    #[test]
+    fn test_offsets_map_back_to_original() {
+        // "e" + combining acute (3 bytes) composes to "é" (2 bytes)
+        let text = "Cafe\u{301}. Next.";
+        let (normalized, map) = normalize(text, Normalization::Nfc).unwrap();
+        assert_eq!(normalized, "Café. Next.");
+        assert_eq!(map.to_original(0), 0);
+        assert_eq!(map.to_original(3), 3);
+        assert_eq!(map.to_original(5), text.find('.').unwrap());
+        assert_eq!(map.to_original(normalized.len()), text.len());
+    }
+
+    #[test]
+    fn test_nfkc_folds_full_width_punctuation() {
+        // Full-width "！" (3 bytes) becomes "!" (1 byte); "…" becomes "..."
+        let text = "Wow！ Wait… ok";
+        let (normalized, map) = normalize(text, Normalization::Nfkc).unwrap();
+        assert_eq!(normalized, "Wow! Wait... ok");
+        assert_eq!(map.to_original(4), "Wow！".len());
+        // Offsets inside an expanded character snap to its end
+        let ellipsis = text.find('…').unwrap();
+        assert_eq!(
+            map.to_original(normalized.find("..").unwrap() + 1),
+            ellipsis + 3
+        );
+        assert_eq!(map.to_original(normalized.len()), text.len());
+    }
+}
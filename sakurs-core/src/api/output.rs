@@ -1,12 +1,42 @@
 //! Output types for unified API
 
+use std::ops::Range;
 use std::time::Duration;
 
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::api::fingerprint;
+use crate::application::ExecutionDecision;
+
+pub use crate::domain::types::{RejectionReason, Warning};
+
 /// Processing output with rich metadata
 #[derive(Debug, Clone)]
 pub struct Output {
     /// Sentence boundaries found
     pub boundaries: Vec<Boundary>,
+    /// Candidates that did not become boundaries, in text order; empty
+    /// unless enabled with
+    /// [`ConfigBuilder::include_rejected_candidates`](crate::ConfigBuilder::include_rejected_candidates)
+    pub rejected_candidates: Vec<RejectedCandidate>,
+    /// Problems noticed in the text, sorted by offset: invalid UTF-8 that
+    /// was replaced or skipped, forced splits, and sentences longer than
+    /// [`ConfigBuilder::long_sentence_chars`](crate::ConfigBuilder::long_sentence_chars).
+    /// Unbalanced enclosures are reported only when enabled with
+    /// [`ConfigBuilder::enclosure_warnings`](crate::ConfigBuilder::enclosure_warnings),
+    /// except for the [`Warning::DepthExceeded`] of a
+    /// [`ConfigBuilder::max_enclosure_depth`](crate::ConfigBuilder::max_enclosure_depth) cap
+    pub warnings: Vec<Warning>,
+    /// Indexes of the sentences that repeat an earlier sentence, in order;
+    /// sentence `i` ends at `boundaries[i]`, and sentence
+    /// `boundaries.len()` is the text after the last boundary. Empty unless
+    /// enabled with [`ConfigBuilder::dedupe`](crate::ConfigBuilder::dedupe)
+    pub duplicates: Vec<usize>,
+    /// Approximate token count of each sentence, numbered as in
+    /// [`duplicates`](Self::duplicates) (so one more than the boundaries);
+    /// empty unless an estimator is set with
+    /// [`ConfigBuilder::token_estimator`](crate::ConfigBuilder::token_estimator)
+    pub token_estimates: Vec<usize>,
     /// Processing metadata
     pub metadata: ProcessingMetadata,
 }
@@ -18,6 +48,124 @@ pub struct Boundary {
     pub offset: usize,
     /// Character offset in the original text
     pub char_offset: usize,
+    /// UTF-16 code unit offset, if enabled with
+    /// [`ConfigBuilder::utf16_offsets`](crate::ConfigBuilder::utf16_offsets)
+    pub utf16_offset: Option<usize>,
+    /// Extended grapheme cluster offset, if enabled with
+    /// [`ConfigBuilder::grapheme_offsets`](crate::ConfigBuilder::grapheme_offsets).
+    /// Clusters are counted within each sentence.
+    pub grapheme_offset: Option<usize>,
+    /// How the boundary was found
+    pub kind: BoundaryKind,
+}
+
+/// How a boundary was found
+///
+/// The variants and their [`as_str`](Self::as_str) names are stable: the
+/// bindings and the CLI's JSON output expose them as they are.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BoundaryKind {
+    /// A terminator that only ends sentences: `!`, `?` and their full-width
+    /// forms, multi-character patterns such as "!?", or an emoji after one
+    Strong,
+    /// A boundary the rules accepted in context: a period or 。 that is not
+    /// a decimal point, an abbreviation followed by a sentence starter, an
+    /// ellipsis, a line break, a Thai space, a candidate forced by a
+    /// [`BoundaryFilter`](crate::BoundaryFilter), or an offset accepted by a
+    /// [`Correction`](crate::Correction)
+    #[default]
+    Weak,
+    /// Inserted to split an overlong span that the rules left whole
+    /// (`max_sentence_chars`, `force_split_bytes`)
+    Forced,
+    /// The end of the text, closing a final sentence that has no terminator.
+    /// Never in [`Output::boundaries`]; the bindings and the CLI report it
+    /// for the trailing sentence they build from the rest of the text.
+    EndOfText,
+}
+
+impl BoundaryKind {
+    /// The kind's name as exposed by the bindings and the CLI: `"strong"`,
+    /// `"weak"`, `"forced"` or `"end_of_text"`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BoundaryKind::Strong => "strong",
+            BoundaryKind::Weak => "weak",
+            BoundaryKind::Forced => "forced",
+            BoundaryKind::EndOfText => "end_of_text",
+        }
+    }
+}
+
+impl std::fmt::Display for BoundaryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A boundary candidate the rules rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RejectedCandidate {
+    /// Byte offset just after the candidate, where the boundary would be
+    pub offset: usize,
+    /// Why the candidate is not a boundary
+    pub reason: RejectionReason,
+}
+
+/// Optional offset units computed for each boundary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct OffsetUnits {
+    pub utf16: bool,
+    pub grapheme: bool,
+}
+
+/// A position in the text measured in every enabled unit, advanced one
+/// sentence at a time
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct OffsetCursor {
+    /// Not saved with a stream checkpoint; the resuming processor's config
+    /// sets it
+    #[serde(skip)]
+    units: OffsetUnits,
+    pub chars: usize,
+    utf16: usize,
+    graphemes: usize,
+}
+
+impl OffsetCursor {
+    pub(crate) fn new(units: OffsetUnits) -> Self {
+        Self {
+            units,
+            ..Self::default()
+        }
+    }
+
+    /// The same position, measured in `units` from now on
+    pub(crate) fn with_units(self, units: OffsetUnits) -> Self {
+        Self { units, ..self }
+    }
+
+    /// Moves the cursor past `segment`, the text following its position.
+    pub(crate) fn advance(&mut self, segment: &str) {
+        self.chars += segment.chars().count();
+        if self.units.utf16 {
+            self.utf16 += segment.encode_utf16().count();
+        }
+        if self.units.grapheme {
+            self.graphemes += segment.graphemes(true).count();
+        }
+    }
+
+    /// The boundary at byte `offset`, the cursor's position.
+    pub(crate) fn boundary(&self, offset: usize) -> Boundary {
+        Boundary {
+            offset,
+            char_offset: self.chars,
+            utf16_offset: self.units.utf16.then_some(self.utf16),
+            grapheme_offset: self.units.grapheme.then_some(self.graphemes),
+            kind: BoundaryKind::Weak,
+        }
+    }
 }
 
 /// Metadata about the processing
@@ -29,8 +177,14 @@ pub struct ProcessingMetadata {
     pub strategy_used: String,
     /// Number of chunks processed
     pub chunks_processed: usize,
+    /// Threads and chunk size used, and why: how the adaptive policy judged
+    /// the text size against the threshold and the threads available
+    pub execution: ExecutionDecision,
     /// Additional statistics
     pub stats: ProcessingStats,
+    /// Fingerprint of the sakurs version, language rules and configuration
+    /// that produced the boundaries (see [`Output::fingerprint`])
+    pub config_fingerprint: u64,
 }
 
 /// Additional processing statistics
@@ -44,28 +198,47 @@ pub struct ProcessingStats {
     pub sentence_count: usize,
     /// Average sentence length in characters
     pub avg_sentence_length: f32,
+    /// Invalid UTF-8 bytes replaced or skipped while decoding the input
+    pub invalid_utf8_bytes: usize,
+    /// Sentences that repeat an earlier sentence (see
+    /// [`Output::duplicates`]); zero unless deduplication is enabled
+    pub duplicate_sentences: usize,
 }
 
 impl Output {
-    /// Create output from delta stack processing result
+    /// Create output from delta stack processing result; `forced` lists the
+    /// (sorted) boundaries inserted by forced splitting
     pub(crate) fn from_delta_stack_result(
         result: crate::application::DeltaStackResult,
+        forced: &[usize],
         text: &str,
         duration: Duration,
+        units: OffsetUnits,
     ) -> Self {
-        // Calculate character offsets for each byte boundary (and the total
-        // character count, avoiding a second full pass over the text)
-        let (char_boundaries, total_chars) = Self::calculate_char_offsets(text, &result.boundaries);
-
+        // Measure each inter-boundary segment once (the standard library's
+        // word-at-a-time char counter is much faster than a char-by-char
+        // walk); the final segment yields the total character count.
+        // Boundaries are sorted and lie on character boundaries.
+        let mut cursor = OffsetCursor::new(units);
+        let mut prev = 0;
+        let strong = result.strong;
         let boundaries = result
             .boundaries
             .into_iter()
-            .zip(char_boundaries)
-            .map(|(offset, char_offset)| Boundary {
-                offset,
-                char_offset,
+            .map(|offset| {
+                cursor.advance(&text[prev..offset]);
+                prev = offset;
+                let mut boundary = cursor.boundary(offset);
+                if forced.binary_search(&offset).is_ok() {
+                    boundary.kind = BoundaryKind::Forced;
+                } else if strong.binary_search(&offset).is_ok() {
+                    boundary.kind = BoundaryKind::Strong;
+                }
+                boundary
             })
             .collect::<Vec<_>>();
+        cursor.advance(&text[prev..]);
+        let total_chars = cursor.chars;
 
         let sentence_count = boundaries.len();
         let avg_sentence_length = if sentence_count > 0 {
@@ -75,46 +248,147 @@ impl Output {
         };
 
         // Determine strategy used based on thread count
-        let strategy_used = if result.thread_count > 1 {
-            format!("parallel ({} threads)", result.thread_count)
+        let strategy_used = if result.execution.thread_count > 1 {
+            format!("parallel ({} threads)", result.execution.thread_count)
         } else {
             "sequential".to_string()
         };
 
         Self {
             boundaries,
+            rejected_candidates: Vec::new(),
+            warnings: Vec::new(),
+            duplicates: Vec::new(),
+            token_estimates: Vec::new(),
             metadata: ProcessingMetadata {
                 duration,
                 strategy_used,
                 chunks_processed: result.chunk_count,
+                execution: result.execution,
                 stats: ProcessingStats {
                     bytes_processed: text.len(),
                     chars_processed: total_chars,
                     sentence_count,
                     avg_sentence_length,
+                    invalid_utf8_bytes: 0,
+                    duplicate_sentences: 0,
                 },
+                config_fingerprint: 0,
             },
         }
     }
 
-    /// Calculate character offsets from byte offsets.
+    /// Add `warnings` to [`warnings`](Self::warnings), keeping them sorted
+    /// by offset; for callers that decode or check the text themselves
+    pub fn add_warnings(&mut self, warnings: impl IntoIterator<Item = Warning>) {
+        self.warnings.extend(warnings);
+        self.warnings.sort_by_key(Warning::offset);
+    }
+
+    /// A stable hash of the boundaries and of the sakurs version, language
+    /// rules and configuration that produced them
     ///
-    /// `byte_offsets` must be sorted ascending and lie on character
-    /// boundaries (guaranteed by the boundary merge step). Counting each
-    /// inter-boundary segment with the standard library's optimized
-    /// word-at-a-time counter is much faster than a char-by-char walk.
-    /// Returns the character offset for each byte offset plus the total
-    /// character count.
-    fn calculate_char_offsets(text: &str, byte_offsets: &[usize]) -> (Vec<usize>, usize) {
-        let mut char_offsets = Vec::with_capacity(byte_offsets.len());
-        let mut chars = 0usize;
-        let mut prev = 0usize;
-        for &off in byte_offsets {
-            chars += text[prev..off].chars().count();
-            char_offsets.push(chars);
-            prev = off;
-        }
-        let total = chars + text[prev..].chars().count();
-        (char_offsets, total)
+    /// Re-segmenting the same text with the same version and configuration
+    /// gives the same fingerprint on any machine, whatever the chunk size
+    /// or thread count, so corpus pipelines can store it to cache results
+    /// and to verify a later run. Format it with `{:016x}` for storage.
+    ///
+    /// ```rust
+    /// use sakurs_core::{Config, Input, SentenceProcessor};
+    ///
+    /// let text = "One. Two. Three.";
+    /// let sequential = SentenceProcessor::new().process(Input::from_text(text)).unwrap();
+    /// let config = Config::builder().threads(Some(4)).chunk_size(4).build().unwrap();
+    /// let parallel = SentenceProcessor::with_config(config)
+    ///     .unwrap()
+    ///     .process(Input::from_text(text))
+    ///     .unwrap();
+    /// assert_eq!(sequential.fingerprint(), parallel.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        fingerprint::output(self.metadata.config_fingerprint, &self.boundaries)
     }
+
+    /// Overlapping groups of `size` consecutive sentences, one starting
+    /// every `stride` sentences, with the span each covers, for building
+    /// embedding windows with shared context
+    ///
+    /// Sentences are numbered as in [`duplicates`](Self::duplicates); the
+    /// text after the last boundary is a sentence when there is any. The
+    /// last window ends at the last sentence and may be shorter than
+    /// `size`; a `stride` larger than `size` leaves sentences out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` or `stride` is 0.
+    ///
+    /// ```rust
+    /// use sakurs_core::{Input, SentenceProcessor};
+    ///
+    /// let text = "One. Two. Three. Four.";
+    /// let output = SentenceProcessor::new().process(Input::from_text(text)).unwrap();
+    /// let windows: Vec<_> = output
+    ///     .windows(3, 2)
+    ///     .map(|window| &text[window.start..window.end])
+    ///     .collect();
+    /// assert_eq!(windows, ["One. Two. Three.", " Three. Four."]);
+    /// ```
+    pub fn windows(&self, size: usize, stride: usize) -> impl Iterator<Item = SentenceWindow> + '_ {
+        assert!(size > 0, "window size must be greater than 0");
+        assert!(stride > 0, "window stride must be greater than 0");
+        let stats = &self.metadata.stats;
+        let trailing = self
+            .boundaries
+            .last()
+            .map_or(stats.bytes_processed > 0, |b| {
+                b.offset < stats.bytes_processed
+            });
+        let count = self.boundaries.len() + usize::from(trailing);
+        // Where sentence `i` starts and ends, in bytes and characters
+        let start = |i: usize| match i.checked_sub(1) {
+            Some(prev) => (
+                self.boundaries[prev].offset,
+                self.boundaries[prev].char_offset,
+            ),
+            None => (0, 0),
+        };
+        let end = |i: usize| match self.boundaries.get(i) {
+            Some(boundary) => (boundary.offset, boundary.char_offset),
+            None => (stats.bytes_processed, stats.chars_processed),
+        };
+        let starts = (0..count).step_by(stride);
+        let mut done = false;
+        starts.map_while(move |first| {
+            if done {
+                return None;
+            }
+            let last = (first + size).min(count);
+            done = last == count;
+            let (start, char_start) = start(first);
+            let (end, char_end) = end(last - 1);
+            Some(SentenceWindow {
+                sentences: first..last,
+                start,
+                end,
+                char_start,
+                char_end,
+            })
+        })
+    }
+}
+
+/// A group of consecutive sentences of an [`Output`], from
+/// [`Output::windows`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceWindow {
+    /// Indexes of the sentences, numbered as in [`Output::duplicates`]
+    pub sentences: Range<usize>,
+    /// Byte offset of the start of the first sentence
+    pub start: usize,
+    /// Byte offset of the end of the last sentence
+    pub end: usize,
+    /// Character offset of the start of the first sentence
+    pub char_start: usize,
+    /// Character offset of the end of the last sentence
+    pub char_end: usize,
 }
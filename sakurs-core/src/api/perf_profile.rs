@@ -0,0 +1,90 @@
+//! Parallelism presets for classes of machines
+
+use crate::api::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Chunk size, thread limit and adaptive threshold suited to a class of
+/// machine
+///
+/// The defaults suit a desktop with a handful of cores. Elsewhere they
+/// cost: a laptop wakes every core for mid-sized texts, a 32-core server
+/// keeps most cores idle until texts reach megabytes, and a container
+/// limited to 2 CPUs by quota may still see every host core and start a
+/// thread per core. The `perf_profiles` group of the `adaptive_profiling`
+/// benchmark compares each preset with the defaults on the machine it runs
+/// on, and `sakurs tune` calibrates one machine in particular. Options set
+/// explicitly on the builder take precedence over the preset's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PerfProfile {
+    /// 4 to 8 cores sharing a power budget: more text per thread, at most
+    /// 4 threads, smaller chunks for the smaller caches
+    Laptop,
+    /// Many cores: threads from 128KB of text per thread, up to 32
+    #[serde(rename = "server-32c")]
+    Server32c,
+    /// A container limited to 2 CPUs, whatever the host has: at most 2
+    /// threads, and only for texts of 1MB per thread
+    #[serde(rename = "container-2c")]
+    Container2c,
+}
+
+impl PerfProfile {
+    /// Chunk size in bytes
+    pub fn chunk_size(self) -> usize {
+        match self {
+            PerfProfile::Laptop => 128 * 1024,
+            PerfProfile::Server32c | PerfProfile::Container2c => 256 * 1024,
+        }
+    }
+
+    /// Text bytes per thread when the thread count is chosen per text
+    pub fn adaptive_threshold(self) -> usize {
+        match self {
+            PerfProfile::Laptop => 512 * 1024,
+            PerfProfile::Server32c => 128 * 1024,
+            PerfProfile::Container2c => 1024 * 1024,
+        }
+    }
+
+    /// Most threads used when the thread count is chosen per text
+    pub fn max_threads(self) -> usize {
+        match self {
+            PerfProfile::Laptop => 4,
+            PerfProfile::Server32c => 32,
+            PerfProfile::Container2c => 2,
+        }
+    }
+
+    /// The name [`FromStr`] accepts
+    pub fn name(self) -> &'static str {
+        match self {
+            PerfProfile::Laptop => "laptop",
+            PerfProfile::Server32c => "server-32c",
+            PerfProfile::Container2c => "container-2c",
+        }
+    }
+}
+
+impl fmt::Display for PerfProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PerfProfile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "laptop" => Ok(PerfProfile::Laptop),
+            "server-32c" | "server" => Ok(PerfProfile::Server32c),
+            "container-2c" | "container" => Ok(PerfProfile::Container2c),
+            _ => Err(Error::Configuration(format!(
+                "performance profile must be laptop, server-32c or container-2c, got: {s}"
+            ))),
+        }
+    }
+}
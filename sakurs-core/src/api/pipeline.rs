@@ -0,0 +1,310 @@
+//! Pipelined segmentation of a reader
+//!
+//! A read stage and a scan stage run on threads of their own, and the
+//! consumer iterating over the sentences is the emit stage. Bounded queues
+//! join the stages, so a consumer slower than the input (a network peer,
+//! say) fills the queue in front of it and stalls the stages upstream in
+//! turn, instead of letting scanned text pile up in memory. The queues
+//! count what passes through them, for [`ExecutionMetrics`].
+
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Read};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use crate::api::stream::incomplete_tail;
+use crate::api::{Error, InvalidUtf8, Result, SentenceSpan, SentenceStream};
+
+/// Queue depths and stalls of a [`ReaderPipeline`], from
+/// [`ReaderPipeline::metrics`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionMetrics {
+    /// Bytes taken from the reader so far
+    pub bytes_read: u64,
+    /// Pieces read and waiting for the scan stage
+    pub read_queue: QueueMetrics,
+    /// Sentence batches scanned and waiting for the consumer
+    pub emit_queue: QueueMetrics,
+}
+
+/// The state of one queue between two stages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueMetrics {
+    /// Most items the queue holds, as set with
+    /// [`ConfigBuilder::stream_buffers`](crate::ConfigBuilder::stream_buffers)
+    pub capacity: usize,
+    /// Items waiting now
+    pub depth: usize,
+    /// Most items that have waited at once
+    pub peak_depth: usize,
+    /// Times the stage feeding the queue found it full and had to wait for
+    /// the stage after it
+    pub full_waits: u64,
+}
+
+/// Counters of one queue, shared by its two ends
+struct QueueCounters {
+    capacity: usize,
+    sent: AtomicU64,
+    received: AtomicU64,
+    peak_depth: AtomicUsize,
+    full_waits: AtomicU64,
+}
+
+impl QueueCounters {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            sent: AtomicU64::new(0),
+            received: AtomicU64::new(0),
+            peak_depth: AtomicUsize::new(0),
+            full_waits: AtomicU64::new(0),
+        }
+    }
+
+    /// Items waiting; the two counts are bumped just after the queue
+    /// operations, so a momentary reading is clamped to the capacity
+    fn depth(&self) -> usize {
+        let sent = self.sent.load(Ordering::Acquire);
+        let received = self.received.load(Ordering::Acquire);
+        (sent.saturating_sub(received) as usize).min(self.capacity)
+    }
+
+    fn metrics(&self) -> QueueMetrics {
+        QueueMetrics {
+            capacity: self.capacity,
+            depth: self.depth(),
+            peak_depth: self.peak_depth.load(Ordering::Relaxed),
+            full_waits: self.full_waits.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Sending end of a counted queue
+struct Sender<T> {
+    tx: mpsc::SyncSender<T>,
+    counters: Arc<QueueCounters>,
+}
+
+impl<T> Sender<T> {
+    /// Queue `item`, waiting while the queue is full; false once the
+    /// receiving stage has gone
+    fn send(&self, item: T) -> bool {
+        let sent = match self.tx.try_send(item) {
+            Ok(()) => true,
+            Err(TrySendError::Full(item)) => {
+                self.counters.full_waits.fetch_add(1, Ordering::Relaxed);
+                self.tx.send(item).is_ok()
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        };
+        if sent {
+            self.counters.sent.fetch_add(1, Ordering::AcqRel);
+            let depth = self.counters.depth();
+            self.counters.peak_depth.fetch_max(depth, Ordering::Relaxed);
+        }
+        sent
+    }
+}
+
+/// Receiving end of a counted queue
+struct Receiver<T> {
+    rx: mpsc::Receiver<T>,
+    counters: Arc<QueueCounters>,
+}
+
+impl<T> Receiver<T> {
+    /// The next item, waiting for one; `None` once the sending stage has
+    /// stopped
+    fn recv(&self) -> Option<T> {
+        let item = self.rx.recv().ok()?;
+        self.counters.received.fetch_add(1, Ordering::AcqRel);
+        Some(item)
+    }
+}
+
+fn queue<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::sync_channel(capacity);
+    let counters = Arc::new(QueueCounters::new(capacity));
+    (
+        Sender {
+            tx,
+            counters: Arc::clone(&counters),
+        },
+        Receiver { rx, counters },
+    )
+}
+
+/// Sentences of a reader segmented by a pipeline of threads, created by
+/// [`SentenceProcessor::pipeline_from_reader`](crate::SentenceProcessor::pipeline_from_reader)
+///
+/// Yields the same sentences as
+/// [`sentences_from_reader`](crate::SentenceProcessor::sentences_from_reader),
+/// while the next pieces are read and scanned ahead of the consumer. After
+/// an error the iterator ends. Dropping it stops the stages once they next
+/// touch a queue; a read already blocked in the reader finishes first.
+pub struct ReaderPipeline {
+    /// `None` once the sentences or an error have all been returned
+    batches: Option<Receiver<Result<Vec<SentenceSpan>>>>,
+    scan: Option<JoinHandle<()>>,
+    ready: VecDeque<SentenceSpan>,
+    bytes_read: Arc<AtomicU64>,
+    read_queue: Arc<QueueCounters>,
+    emit_queue: Arc<QueueCounters>,
+}
+
+impl ReaderPipeline {
+    pub(crate) fn new<R: Read + Send + 'static>(
+        reader: R,
+        stream: SentenceStream,
+        read_size: usize,
+        invalid_utf8: InvalidUtf8,
+        (read_buffers, emit_buffers): (usize, usize),
+    ) -> Result<Self> {
+        let (pieces_tx, pieces_rx) = queue(read_buffers);
+        let (batches_tx, batches_rx) = queue(emit_buffers);
+        let read_queue = Arc::clone(&pieces_tx.counters);
+        let emit_queue = Arc::clone(&batches_tx.counters);
+        let bytes_read = Arc::new(AtomicU64::new(0));
+
+        let counted = Arc::clone(&bytes_read);
+        let read = spawn("sakurs-read", move || {
+            read_stage(reader, read_size.max(4), &pieces_tx, &counted)
+        })?;
+        let scan = spawn("sakurs-scan", move || {
+            scan_stage(&pieces_rx, read, stream, invalid_utf8, &batches_tx)
+        })?;
+        Ok(Self {
+            batches: Some(batches_rx),
+            scan: Some(scan),
+            ready: VecDeque::new(),
+            bytes_read,
+            read_queue,
+            emit_queue,
+        })
+    }
+
+    /// Bytes read and queue depths so far
+    pub fn metrics(&self) -> ExecutionMetrics {
+        ExecutionMetrics {
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            read_queue: self.read_queue.metrics(),
+            emit_queue: self.emit_queue.metrics(),
+        }
+    }
+}
+
+impl Iterator for ReaderPipeline {
+    type Item = Result<SentenceSpan>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(span) = self.ready.pop_front() {
+                return Some(Ok(span));
+            }
+            match self.batches.as_ref()?.recv() {
+                Some(Ok(spans)) => self.ready.extend(spans),
+                Some(Err(e)) => {
+                    self.batches = None;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.batches = None;
+                    return join(self.scan.take()?, "scan").err().map(Err);
+                }
+            }
+        }
+    }
+}
+
+fn spawn(name: &str, stage: impl FnOnce() + Send + 'static) -> Result<JoinHandle<()>> {
+    std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(stage)
+        .map_err(|e| Error::Infrastructure(format!("Failed to start {name} thread: {e}")))
+}
+
+/// Wait for a stage that has stopped sending, failing if it panicked
+fn join(handle: JoinHandle<()>, stage: &str) -> Result<()> {
+    handle
+        .join()
+        .map_err(|_| Error::Infrastructure(format!("The {stage} stage of the stream panicked")))
+}
+
+/// Read pieces of up to `read_size` bytes until the reader is exhausted,
+/// fails, or the scan stage has gone
+fn read_stage(
+    mut reader: impl Read,
+    read_size: usize,
+    pieces: &Sender<Result<Vec<u8>>>,
+    bytes_read: &AtomicU64,
+) {
+    loop {
+        let mut buf = vec![0; read_size];
+        let n = loop {
+            match reader.read(&mut buf) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    pieces.send(Err(Error::Infrastructure(format!(
+                        "Failed to read from reader: {e}"
+                    ))));
+                    return;
+                }
+            }
+        };
+        if n == 0 {
+            return;
+        }
+        buf.truncate(n);
+        bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+        if !pieces.send(Ok(buf)) {
+            return;
+        }
+    }
+}
+
+/// Decode the pieces and feed them to the stream, sending on the sentences
+/// each completes; the stream is finished once the read stage has stopped
+fn scan_stage(
+    pieces: &Receiver<Result<Vec<u8>>>,
+    read: JoinHandle<()>,
+    mut stream: SentenceStream,
+    invalid_utf8: InvalidUtf8,
+    batches: &Sender<Result<Vec<SentenceSpan>>>,
+) {
+    // Bytes of a UTF-8 sequence split across pieces
+    let mut carry = Vec::new();
+    while let Some(piece) = pieces.recv() {
+        let spans = piece.and_then(|bytes| {
+            carry.extend_from_slice(&bytes);
+            let cut = carry.len() - incomplete_tail(&carry);
+            let rest = carry.split_off(cut);
+            let (text, _) = invalid_utf8.decode(std::mem::replace(&mut carry, rest))?;
+            Ok(stream.feed_spans(&text))
+        });
+        match spans {
+            Ok(spans) if spans.is_empty() => {}
+            Ok(spans) => {
+                if !batches.send(Ok(spans)) {
+                    return;
+                }
+            }
+            Err(e) => {
+                batches.send(Err(e));
+                return;
+            }
+        }
+    }
+
+    let spans = join(read, "read")
+        .and_then(|()| invalid_utf8.decode(carry))
+        .map(|(rest, _)| {
+            let mut spans = stream.feed_spans(&rest);
+            spans.extend(stream.finish_spans());
+            spans
+        });
+    batches.send(spans);
+}
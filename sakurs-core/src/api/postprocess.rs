@@ -0,0 +1,232 @@
+//! Boundary post-processing applied by `SentenceProcessor::process`:
+//! suppression spans, line break boundaries and sentence length limits
+
+use regex::Regex;
+
+use crate::api::{Config, RejectionReason, Warning};
+
+/// Sentence-ending punctuation a forced split prefers to cut after
+const FORCED_SPLIT_TERMINATORS: &[char] = &['.', '!', '?', '。', '！', '？', '…', '؟', '।'];
+
+/// Clause punctuation a forced split cuts after when no terminator is near
+const FORCED_SPLIT_CLAUSES: &[char] = &[',', ';', ':', '、', '，', '；', '：', '،'];
+
+/// Apply the configured post-processing to sorted boundaries over `text`,
+/// returning the (sorted) boundaries inserted by forced splitting
+pub(crate) fn apply(config: &Config, text: &str, boundaries: &mut Vec<usize>) -> Vec<usize> {
+    if config.line_breaks {
+        add_line_break_boundaries(text, boundaries);
+    }
+    if let Some(min) = config.min_sentence_chars {
+        merge_short_sentences(text, boundaries, min);
+    }
+    let mut forced = Vec::new();
+    if let Some(max) = config.max_sentence_chars {
+        split_long_sentences(text, boundaries, &mut forced, Limit::Chars(max));
+    }
+    if let Some(max) = config.force_split_bytes {
+        split_long_sentences(text, boundaries, &mut forced, Limit::Bytes(max));
+        forced.sort_unstable();
+    }
+    forced
+}
+
+/// Remove the boundaries whose terminator lies inside a match of one of
+/// `regexes`, marking the matching `candidates` as suppressed
+pub(crate) fn suppress_spans(
+    regexes: &[Regex],
+    text: &str,
+    boundaries: &mut Vec<usize>,
+    candidates: &mut [(usize, Result<(), RejectionReason>)],
+) {
+    if regexes.is_empty() {
+        return;
+    }
+    let mut spans: Vec<(usize, usize)> = regexes
+        .iter()
+        .flat_map(|regex| regex.find_iter(text).map(|m| (m.start(), m.end())))
+        .collect();
+    spans.sort_unstable();
+    // Merge overlapping spans so that only the last one starting before an
+    // offset can contain it
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    // A boundary at `offset` follows its terminator, which is inside a span
+    // when start < offset <= end
+    let suppressed = |offset: usize| {
+        let i = merged.partition_point(|&(start, _)| start < offset);
+        i > 0 && offset <= merged[i - 1].1
+    };
+    boundaries.retain(|&offset| !suppressed(offset));
+    for (offset, verdict) in candidates {
+        if verdict.is_ok() && suppressed(*offset) {
+            *verdict = Err(RejectionReason::Suppressed);
+        }
+    }
+}
+
+/// Maximum sentence length for forced splitting
+#[derive(Debug, Clone, Copy)]
+enum Limit {
+    Chars(usize),
+    Bytes(usize),
+}
+
+impl Limit {
+    /// Byte offset in `sentence` at which it exceeds the limit, if it does
+    fn exceeded_at(self, sentence: &str) -> Option<usize> {
+        match self {
+            Limit::Chars(max) => sentence.char_indices().nth(max).map(|(i, _)| i),
+            Limit::Bytes(max) if sentence.len() > max => {
+                // The last character boundary within the limit, keeping at
+                // least one character
+                let cut = (1..=max)
+                    .rev()
+                    .find(|&i| sentence.is_char_boundary(i))
+                    .unwrap_or(0);
+                Some(if cut == 0 {
+                    sentence.chars().next().map_or(0, char::len_utf8)
+                } else {
+                    cut
+                })
+            }
+            Limit::Bytes(_) => None,
+        }
+    }
+}
+
+/// Add a boundary after every line break that ends a line with text since
+/// the previous boundary (CRLF counts as one break)
+pub(crate) fn add_line_break_boundaries(text: &str, boundaries: &mut Vec<usize>) {
+    let mut merged = Vec::with_capacity(boundaries.len());
+    let mut existing = boundaries.iter().copied().peekable();
+    let mut last = 0;
+    for (i, c) in text.char_indices() {
+        if !matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}') {
+            continue;
+        }
+        let end = i + c.len_utf8();
+        if c == '\r' && text[end..].starts_with('\n') {
+            continue;
+        }
+        while let Some(boundary) = existing.next_if(|&b| b <= end) {
+            merged.push(boundary);
+            last = boundary;
+        }
+        if last < i && !text[last..i].trim().is_empty() {
+            merged.push(end);
+            last = end;
+        }
+    }
+    merged.extend(existing);
+    *boundaries = merged;
+}
+
+/// Merge sentences with fewer than `min` non-whitespace-trimmed characters
+/// into the previous sentence (the first one into the next)
+fn merge_short_sentences(text: &str, boundaries: &mut Vec<usize>, min: usize) {
+    let is_short = |sentence: &str| sentence.trim().chars().count() < min;
+    let mut merged: Vec<usize> = Vec::with_capacity(boundaries.len());
+    for &boundary in boundaries.iter() {
+        let start = merged.last().copied().unwrap_or(0);
+        if !is_short(&text[start..boundary]) {
+            merged.push(boundary);
+        } else if let Some(last) = merged.last_mut() {
+            *last = boundary;
+        }
+    }
+    let rest = &text[merged.last().copied().unwrap_or(0)..];
+    if !rest.trim().is_empty() && is_short(rest) {
+        merged.pop();
+    }
+    *boundaries = merged;
+}
+
+/// Split sentences longer than `limit` (leading whitespace aside), each cut
+/// at the latest terminator, else clause punctuation, else space within the
+/// limit, or at the limit itself; the cuts are appended to `forced`
+fn split_long_sentences(
+    text: &str,
+    boundaries: &mut Vec<usize>,
+    forced: &mut Vec<usize>,
+    limit: Limit,
+) {
+    let mut split = Vec::with_capacity(boundaries.len());
+    let mut start = 0;
+    // The text after the last boundary is checked too, without adding a
+    // boundary at its end
+    let tail = (boundaries.last() != Some(&text.len())).then_some((text.len(), false));
+    for (end, is_boundary) in boundaries.iter().map(|&b| (b, true)).chain(tail) {
+        loop {
+            let sentence_start =
+                start + (text[start..end].len() - text[start..end].trim_start().len());
+            let Some(exceeded) = limit.exceeded_at(&text[sentence_start..end]) else {
+                break;
+            };
+            let cut = forced_cut(&text[sentence_start..sentence_start + exceeded]);
+            start = sentence_start + cut;
+            split.push(start);
+            forced.push(start);
+        }
+        if is_boundary {
+            split.push(end);
+        }
+        start = end;
+    }
+    *boundaries = split;
+}
+
+/// Warn of the sentences between sorted `boundaries` longer than `max`
+/// characters (surrounding whitespace aside), the text after the last
+/// boundary included
+pub(crate) fn long_sentences(text: &str, boundaries: &[usize], max: usize) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let mut start = 0;
+    for end in boundaries.iter().copied().chain([text.len()]) {
+        let segment = &text[start..end];
+        // A sentence of at most `max` bytes cannot have more characters
+        if segment.len() > max {
+            let sentence = segment.trim();
+            let chars = sentence.chars().count();
+            if chars > max {
+                warnings.push(Warning::LongSentence {
+                    offset: start + (segment.len() - segment.trim_start().len()),
+                    chars,
+                });
+            }
+        }
+        start = end;
+    }
+    warnings
+}
+
+/// Byte offset at which to cut `span`, which starts with a non-whitespace
+/// character: after its last terminator followed by whitespace, else after
+/// its last clause punctuation, else before its last whitespace run, else
+/// at its end
+fn forced_cut(span: &str) -> usize {
+    let after = |i: usize, c: char| i + c.len_utf8();
+    let followed_by_space = |i: usize, c: char| {
+        span[after(i, c)..]
+            .chars()
+            .next()
+            .is_some_and(char::is_whitespace)
+    };
+    let chars = || span.char_indices().rev();
+    chars()
+        .find(|&(i, c)| FORCED_SPLIT_TERMINATORS.contains(&c) && followed_by_space(i, c))
+        .or_else(|| chars().find(|(_, c)| FORCED_SPLIT_CLAUSES.contains(c)))
+        .map(|(i, c)| after(i, c))
+        .or_else(|| {
+            chars()
+                .find(|&(i, c)| c.is_whitespace() && i > 0)
+                .map(|(i, _)| span[..i].trim_end().len())
+                .filter(|&cut| cut > 0)
+        })
+        .unwrap_or(span.len())
+}
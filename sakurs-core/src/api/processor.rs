@@ -1,16 +1,33 @@
 //! Main sentence processor implementation
 
 use std::io::Read;
+use std::ops::Range;
 use std::time::Instant;
 
-use crate::api::{Config, Error, Input, Language, Output};
-use crate::application::{DeltaStackProcessor, ExecutionMode, ProcessorConfig};
-use crate::domain::language::config::LanguageConfig;
+use crate::api::read_ahead::ReadAhead;
+use crate::api::stream::ReaderSentences;
+#[cfg(feature = "normalization")]
+use crate::api::OffsetSpace;
+use crate::api::{
+    corrections, dedupe, determinism, fingerprint, postprocess, ruby, Config, Correction,
+    DeterminismViolation, Error, IncrementalText, Input, Output, ReaderPipeline, RejectedCandidate,
+    RejectionReason, SentenceDeduplicator, SentenceSpan, SentenceStream, StreamCheckpoint, Warning,
+};
+use crate::application::{
+    AdaptivePolicy, DeltaStackProcessor, DeltaStackResult, ExecutionMode, ProcessorConfig,
+    Progress, ProgressCallback,
+};
+use crate::domain::language::config::{get_language_config, LanguageConfig};
 
 /// Unified sentence processor with clean API
 pub struct SentenceProcessor {
     processor: DeltaStackProcessor,
     config: Config,
+    /// Fingerprint of the rules, reported in every output
+    fingerprint: u64,
+    /// Language code the metrics are labelled with
+    #[cfg(feature = "metrics")]
+    language: String,
 }
 
 impl SentenceProcessor {
@@ -21,23 +38,29 @@ impl SentenceProcessor {
 
     /// Create a processor with custom configuration
     pub fn with_config(config: Config) -> Result<Self, Error> {
-        let processor_config = Self::build_processor_config(&config)?;
-        let code = match config.language {
-            Language::English => "en",
-            Language::Japanese => "ja",
-        };
-        let processor = DeltaStackProcessor::from_language_code(processor_config, code)?;
-
-        Ok(Self { processor, config })
+        let language = get_language_config(config.language.code())
+            .map_err(|e| Error::InvalidLanguage(e.to_string()))?;
+        Self::with_language_config(config, language)
     }
 
     /// Create a processor with a custom language configuration (e.g. loaded
     /// from an external TOML file via [`LanguageConfig::from_file`])
     pub fn with_language_config(config: Config, language: &LanguageConfig) -> Result<Self, Error> {
         let processor_config = Self::build_processor_config(&config)?;
-        let processor = DeltaStackProcessor::from_language_config(processor_config, language)?;
+        let language = config.apply_rule_overrides(language)?;
+        let mut processor = DeltaStackProcessor::from_language_config(processor_config, &language)?;
+        let fingerprint = fingerprint::rules(&config, &language);
+        if let Some(cache) = &config.chunk_cache {
+            processor = processor.with_chunk_cache(cache.clone(), fingerprint);
+        }
 
-        Ok(Self { processor, config })
+        Ok(Self {
+            processor,
+            config,
+            fingerprint,
+            #[cfg(feature = "metrics")]
+            language: language.metadata.code.clone(),
+        })
     }
 
     /// Create a processor for a specific language
@@ -48,10 +71,78 @@ impl SentenceProcessor {
 
     /// Process input and return sentence boundaries
     pub fn process(&self, input: Input) -> Result<Output, Error> {
-        let start = Instant::now();
+        self.process_inner(input, None, &[])
+    }
 
-        // Convert input to text
-        let text = input.into_text()?;
+    /// Process input with a document's corrections applied over every rule,
+    /// filter and sentence length limit: each accepted offset is a boundary
+    /// and each rejected offset is not
+    ///
+    /// `corrections` are typically one document's entries of a
+    /// [`Corrections`](crate::Corrections) file. Fails with
+    /// [`Error::InvalidInput`] if an offset is not a character boundary
+    /// inside the text, as happens when the text changed since the
+    /// corrections were written.
+    pub fn process_with_corrections(
+        &self,
+        input: Input,
+        corrections: &[Correction],
+    ) -> Result<Output, Error> {
+        self.process_inner(input, None, corrections)
+    }
+
+    /// Process input, reporting how many bytes of the text have been scanned
+    /// to `progress` as the work advances
+    ///
+    /// Meant for progress bars over large single inputs. `progress` is
+    /// called from the worker threads, possibly concurrently; the last report
+    /// has `processed_bytes == total_bytes`.
+    ///
+    /// ```rust
+    /// use sakurs_core::{Input, SentenceProcessor};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let scanned = AtomicUsize::new(0);
+    /// let output = SentenceProcessor::new()
+    ///     .process_with_progress(Input::from_text("One. Two."), |p| {
+    ///         scanned.fetch_max(p.processed_bytes, Ordering::Relaxed);
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(output.boundaries.len(), 2);
+    /// assert_eq!(scanned.into_inner(), 9);
+    /// ```
+    pub fn process_with_progress(
+        &self,
+        input: Input,
+        progress: impl Fn(Progress) + Sync,
+    ) -> Result<Output, Error> {
+        self.process_inner(input, Some(&progress), &[])
+    }
+
+    fn process_inner(
+        &self,
+        input: Input,
+        progress: Option<ProgressCallback<'_>>,
+        corrections: &[Correction],
+    ) -> Result<Output, Error> {
+        let (text, mut output) = self.segment(input, progress, corrections)?;
+        if self.config.dedupe {
+            mark_duplicates(&text, &mut output, &mut SentenceDeduplicator::new());
+        }
+        Ok(output)
+    }
+
+    /// Segment `input`, returning the text the boundaries refer to with the
+    /// output
+    fn segment(
+        &self,
+        input: Input,
+        progress: Option<ProgressCallback<'_>>,
+        corrections: &[Correction],
+    ) -> Result<(String, Output), Error> {
+        let start = Instant::now();
+        #[cfg(feature = "metrics")]
+        let mut stages = crate::api::metrics::Stages::start(start);
 
         // Determine execution mode based on configuration
         let mode = if let Some(threads) = self.config.threads {
@@ -66,12 +157,309 @@ impl SentenceProcessor {
             ExecutionMode::Adaptive
         };
 
-        // Process using the processor
-        let result = self.processor.process(&text, mode)?;
+        // Process using the processor, overlapping the reads of a file with
+        // scanning when asked to
+        let (text, result, invalid_utf8) = match input {
+            Input::File(path) if self.reads_ahead() => {
+                let mut blocks =
+                    ReadAhead::open(&path, self.config.chunk_size, self.config.invalid_utf8)?;
+                let len = blocks.len();
+                let (text, result) =
+                    self.processor
+                        .process_pieces(&mut blocks, mode, len, progress)?;
+                (text, result, blocks.invalid_utf8())
+            }
+            input => {
+                let (text, invalid_utf8) = input.into_text_with(self.config.invalid_utf8)?;
+                let (text, result) = self.process_stripped(text, mode, progress)?;
+                (text, result, invalid_utf8)
+            }
+        };
+        #[cfg(feature = "metrics")]
+        stages.end("segment");
+        let mut output = self.finish(
+            &text,
+            result,
+            corrections,
+            start,
+            #[cfg(feature = "metrics")]
+            &mut stages,
+        )?;
+        if let Some(warning @ Warning::InvalidUtf8 { bytes, .. }) = invalid_utf8 {
+            output.metadata.stats.invalid_utf8_bytes = bytes;
+            output.add_warnings([warning]);
+        }
+        #[cfg(feature = "metrics")]
+        {
+            stages.end("postprocess");
+            stages.record(&self.language, text.len(), output.boundaries.len());
+        }
+        Ok((text, output))
+    }
+
+    /// Whether file inputs are scanned as they are read: the passes that
+    /// rewrite the text need all of it first
+    fn reads_ahead(&self) -> bool {
+        #[cfg(feature = "normalization")]
+        if self.config.normalization.is_some() {
+            return false;
+        }
+        self.config.read_ahead && !self.config.strip_ruby
+    }
+
+    /// Turn the segmentation of `text` into the output: enclosure checks,
+    /// suppression, filters, forced boundaries and corrections
+    fn finish(
+        &self,
+        text: &str,
+        mut result: DeltaStackResult,
+        corrections: &[Correction],
+        start: Instant,
+        #[cfg(feature = "metrics")] stages: &mut crate::api::metrics::Stages,
+    ) -> Result<Output, Error> {
+        let mut warnings = match self.config.balance_options() {
+            Some(options) => self.processor.check_enclosures(text, options, &mut result),
+            None => Vec::new(),
+        };
+        #[cfg(feature = "metrics")]
+        stages.end("enclosures");
+        let filters = &self.config.filters;
+        let include_rejected = self.config.include_rejected_candidates;
+        let mut candidates = if include_rejected || !filters.is_empty() {
+            self.processor.candidates(text, &result.boundaries)
+        } else {
+            Vec::new()
+        };
+        postprocess::suppress_spans(
+            &self.config.suppression_regexes,
+            text,
+            &mut result.boundaries,
+            &mut candidates,
+        );
+        filters.apply(text, &mut candidates, &mut result.boundaries);
+        let mut detected = include_rejected.then(|| result.boundaries.clone());
+        let mut forced = postprocess::apply(&self.config, text, &mut result.boundaries);
+        corrections::apply(
+            corrections,
+            text,
+            &mut result.boundaries,
+            &mut forced,
+            &mut candidates,
+            detected.as_mut(),
+        )?;
+        let rejected_candidates = detected
+            .map(|detected| rejected_candidates(candidates, &detected, &result.boundaries))
+            .unwrap_or_default();
+        if !self.config.enclosure_warnings {
+            // A depth cap reports its own warnings
+            warnings.retain(|w| matches!(w, Warning::DepthExceeded { .. }));
+        }
+        warnings.extend(postprocess::long_sentences(
+            text,
+            &result.boundaries,
+            self.config.long_sentence_chars,
+        ));
+        warnings.extend(forced.iter().map(|&offset| Warning::ForcedSplit { offset }));
 
         // Convert to public output format
         let duration = start.elapsed();
-        Ok(Output::from_delta_stack_result(result, &text, duration))
+        let mut output = Output::from_delta_stack_result(
+            result,
+            &forced,
+            text,
+            duration,
+            self.config.offset_units,
+        );
+        output.rejected_candidates = rejected_candidates;
+        output.add_warnings(warnings);
+        output.metadata.config_fingerprint = self.fingerprint;
+        if let Some(estimator) = &self.config.token_estimator {
+            output.token_estimates = estimator.sentences(text, &output.boundaries);
+        }
+        Ok(output)
+    }
+
+    /// Segment `text` after the configured ruby stripping and normalization,
+    /// returning the text the boundaries refer to
+    fn process_stripped(
+        &self,
+        text: String,
+        mode: ExecutionMode,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<(String, DeltaStackResult), Error> {
+        let stripped = self.config.strip_ruby.then(|| ruby::strip(&text)).flatten();
+        let Some((stripped, map)) = stripped else {
+            return self.process_normalized(text, mode, progress);
+        };
+
+        let (processed, mut result) = self.process_normalized(stripped, mode, progress)?;
+        #[cfg(feature = "normalization")]
+        if self.config.offset_space == OffsetSpace::Normalized {
+            return Ok((processed, result));
+        }
+        drop(processed);
+        for offset in result.boundaries.iter_mut().chain(&mut result.strong) {
+            *offset = map.to_original(*offset);
+        }
+        Ok((text, result))
+    }
+
+    /// Segment `text` after the configured normalization, returning the text
+    /// the boundaries refer to
+    #[cfg(feature = "normalization")]
+    fn process_normalized(
+        &self,
+        text: String,
+        mode: ExecutionMode,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<(String, DeltaStackResult), Error> {
+        let normalized = self
+            .config
+            .normalization
+            .and_then(|form| crate::api::normalize::normalize(&text, form));
+        let Some((normalized, map)) = normalized else {
+            let result = self
+                .processor
+                .process_with_progress(&text, mode, progress)?;
+            return Ok((text, result));
+        };
+
+        let mut result = self
+            .processor
+            .process_with_progress(&normalized, mode, progress)?;
+        match self.config.offset_space {
+            OffsetSpace::Normalized => Ok((normalized, result)),
+            OffsetSpace::Original => {
+                for offset in result.boundaries.iter_mut().chain(&mut result.strong) {
+                    *offset = map.to_original(*offset);
+                }
+                // Boundaries inside one changed cluster map to its end
+                result.boundaries.dedup();
+                result.strong.dedup();
+                Ok((text, result))
+            }
+        }
+    }
+
+    /// Segment `text` as given (no normalization in this build)
+    #[cfg(not(feature = "normalization"))]
+    fn process_normalized(
+        &self,
+        text: String,
+        mode: ExecutionMode,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<(String, DeltaStackResult), Error> {
+        let result = self
+            .processor
+            .process_with_progress(&text, mode, progress)?;
+        Ok((text, result))
+    }
+
+    /// Apply an edit to `text`, replacing the bytes in `edit_range` with
+    /// `replacement`, and return the boundaries of the edited text
+    ///
+    /// Only the cached chunks the edit touches are rescanned (see
+    /// [`IncrementalText`]); the output equals that of [`process`](Self::process)
+    /// on the edited text. An empty range with an empty replacement
+    /// re-segments the text unchanged. A text last re-segmented by another
+    /// processor is rescanned in full.
+    ///
+    /// Fails with [`Error::InvalidInput`] if `edit_range` does not lie on
+    /// character boundaries within the text, leaving it unchanged, and with
+    /// [`Error::Unsupported`] when the configuration strips ruby or
+    /// normalizes text.
+    pub fn resegment(
+        &self,
+        text: &mut IncrementalText,
+        edit_range: Range<usize>,
+        replacement: &str,
+    ) -> Result<Output, Error> {
+        let start = Instant::now();
+        #[cfg(feature = "metrics")]
+        let mut stages = crate::api::metrics::Stages::start(start);
+
+        #[cfg(feature = "normalization")]
+        let normalizes = self.config.normalization.is_some();
+        #[cfg(not(feature = "normalization"))]
+        let normalizes = false;
+        if self.config.strip_ruby || normalizes {
+            return Err(Error::Unsupported(
+                "resegment does not support ruby stripping or normalization".to_string(),
+            ));
+        }
+        let Range {
+            start: from,
+            end: to,
+        } = edit_range;
+        if from > to
+            || to > text.text.len()
+            || !text.text.is_char_boundary(from)
+            || !text.text.is_char_boundary(to)
+        {
+            return Err(Error::InvalidInput(format!(
+                "Edit range {from}..{to} is not on character boundaries within the text ({} bytes)",
+                text.text.len()
+            )));
+        }
+
+        text.text.replace_range(from..to, replacement);
+        let document = match &mut text.document {
+            Some(document) if self.processor.owns(document) => {
+                document.edit(&text.text, from..to, replacement.len());
+                document
+            }
+            document => document.insert(self.processor.document(&text.text)),
+        };
+        let result = document.result();
+        #[cfg(feature = "metrics")]
+        stages.end("segment");
+
+        let mut output = self.finish(
+            &text.text,
+            result,
+            &[],
+            start,
+            #[cfg(feature = "metrics")]
+            &mut stages,
+        )?;
+        if self.config.dedupe {
+            mark_duplicates(&text.text, &mut output, &mut SentenceDeduplicator::new());
+        }
+        #[cfg(feature = "metrics")]
+        {
+            stages.end("postprocess");
+            stages.record(&self.language, text.text.len(), output.boundaries.len());
+        }
+        Ok(output)
+    }
+
+    /// Process many independent documents, in parallel across documents
+    /// when the `parallel` feature is enabled
+    ///
+    /// Outputs are returned in input order; the first error aborts the batch.
+    /// With [`ConfigBuilder::dedupe`](crate::ConfigBuilder::dedupe), a
+    /// sentence repeating one of an earlier document counts as a duplicate.
+    pub fn process_batch<S: AsRef<str> + Sync>(&self, texts: &[S]) -> Result<Vec<Output>, Error> {
+        let segment = |text: &S| self.segment(Input::from_text(text.as_ref()), None, &[]);
+        #[cfg(feature = "parallel")]
+        let segmented: Vec<_> = {
+            use rayon::prelude::*;
+            texts.par_iter().map(segment).collect::<Result<_, _>>()?
+        };
+        #[cfg(not(feature = "parallel"))]
+        let segmented: Vec<_> = texts.iter().map(segment).collect::<Result<_, _>>()?;
+
+        // Duplicates are looked for across the batch, in input order
+        let mut seen = self.config.dedupe.then(SentenceDeduplicator::new);
+        Ok(segmented
+            .into_iter()
+            .map(|(text, mut output)| {
+                if let Some(seen) = &mut seen {
+                    mark_duplicates(&text, &mut output, seen);
+                }
+                output
+            })
+            .collect())
     }
 
     /// Process input from a reader stream
@@ -82,6 +470,139 @@ impl SentenceProcessor {
         self.process(Input::from_reader(reader))
     }
 
+    /// Segment a reader lazily, yielding sentences as they become final
+    ///
+    /// The reader is consumed in pieces of the configured chunk size as the
+    /// iterator is advanced, so memory stays constant however large the
+    /// input: multi-GB files can be segmented without loading them. The
+    /// sentences cover the whole input, including trailing text without a
+    /// final terminator, and match those of [`stream`](Self::stream) on the
+    /// same text. Invalid UTF-8 is handled by the configured
+    /// [`InvalidUtf8`](crate::InvalidUtf8) policy; after an error the
+    /// iterator ends.
+    ///
+    /// ```rust
+    /// use sakurs_core::SentenceProcessor;
+    ///
+    /// let reader = std::io::Cursor::new("Hello world. This is a test");
+    /// let sentences: Vec<String> = SentenceProcessor::new()
+    ///     .sentences_from_reader(reader)
+    ///     .map(|span| span.map(|span| span.text.trim().to_string()))
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(sentences, ["Hello world.", "This is a test"]);
+    /// ```
+    pub fn sentences_from_reader<R: Read>(
+        &self,
+        reader: R,
+    ) -> impl Iterator<Item = Result<SentenceSpan, Error>> {
+        ReaderSentences::new(
+            reader,
+            self.stream(),
+            self.config.chunk_size,
+            self.config.invalid_utf8,
+        )
+    }
+
+    /// Segment a reader on a pipeline of threads: one reads pieces of the
+    /// configured chunk size, another scans them, and the caller takes the
+    /// sentences from the returned iterator
+    ///
+    /// The sentences are those of
+    /// [`sentences_from_reader`](Self::sentences_from_reader), but reading
+    /// and scanning overlap with the caller's own work. The stages are
+    /// joined by queues of
+    /// [`ConfigBuilder::stream_buffers`](crate::ConfigBuilder::stream_buffers)
+    /// entries: a slow caller stalls the stages rather than letting them
+    /// run ahead, so memory stays bounded, and
+    /// [`ReaderPipeline::metrics`] reports how full the queues are. Fails
+    /// only if a thread cannot be started.
+    ///
+    /// ```rust
+    /// use sakurs_core::SentenceProcessor;
+    ///
+    /// let reader = std::io::Cursor::new("Hello world. This is a test");
+    /// let mut sentences = SentenceProcessor::new().pipeline_from_reader(reader).unwrap();
+    /// assert_eq!(sentences.next().unwrap().unwrap().text, "Hello world.");
+    /// assert_eq!(sentences.metrics().bytes_read, 27);
+    /// assert_eq!(sentences.count(), 1);
+    /// ```
+    pub fn pipeline_from_reader<R: Read + Send + 'static>(
+        &self,
+        reader: R,
+    ) -> Result<ReaderPipeline, Error> {
+        ReaderPipeline::new(
+            reader,
+            self.stream(),
+            self.config.chunk_size,
+            self.config.invalid_utf8,
+            (self.config.read_buffers, self.config.emit_buffers),
+        )
+    }
+
+    /// Start an incremental stream: feed text in pieces and receive
+    /// boundaries as they become final, in memory proportional to the piece
+    /// size rather than the whole text
+    pub fn stream(&self) -> SentenceStream {
+        SentenceStream::new(
+            self.processor.stream(),
+            self.config.offset_units,
+            self.fingerprint,
+        )
+    }
+
+    /// Continue a stream saved with [`SentenceStream::checkpoint`], possibly
+    /// by another process
+    ///
+    /// Fails with [`Error::InvalidInput`] if the checkpoint was taken by a
+    /// processor with other rules or configuration, or by an incompatible
+    /// version of sakurs.
+    pub fn resume_stream(&self, checkpoint: StreamCheckpoint) -> Result<SentenceStream, Error> {
+        SentenceStream::resume(
+            checkpoint,
+            self.config.offset_units,
+            self.fingerprint,
+            |state| self.processor.stream_from(state),
+        )
+    }
+
+    /// Check the determinism guarantee on `text`: boundaries must not depend
+    /// on how the text is cut into chunks or pieces
+    ///
+    /// Sequential processing of the whole text in one chunk is the
+    /// reference. For every chunk size from 1 to `max_chunk_size` bytes, the
+    /// text is processed sequentially, in parallel, and streamed in pieces of
+    /// that size and at seeded random split points; the first run whose
+    /// boundary offsets differ from the reference is returned. Chunk sizes
+    /// falling inside a character are rounded up to the character boundary.
+    ///
+    /// Only the segmentation engine is compared: the configured
+    /// normalization, filters and sentence length limits run after it on
+    /// whole texts and are not involved. Intended for tests of custom
+    /// language configurations and for benchmark inputs; the cost grows with
+    /// `text.len() * max_chunk_size`.
+    ///
+    /// ```rust
+    /// use sakurs_core::SentenceProcessor;
+    ///
+    /// let processor = SentenceProcessor::new();
+    /// processor
+    ///     .verify_determinism("Dr. Smith said \"Hi.\" Then he left! Ok?", 16)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the engine fails on `text`, which does not happen for
+    /// valid configurations.
+    pub fn verify_determinism(
+        &self,
+        text: &str,
+        max_chunk_size: usize,
+    ) -> Result<(), DeterminismViolation> {
+        determinism::verify(&self.processor, text, max_chunk_size)
+    }
+
     /// Get the current configuration
     pub fn config(&self) -> &Config {
         &self.config
@@ -91,6 +612,12 @@ impl SentenceProcessor {
     fn build_processor_config(config: &Config) -> Result<ProcessorConfig, Error> {
         Ok(ProcessorConfig {
             chunk_size: config.chunk_size,
+            adaptive_chunking: config.adaptive_chunking,
+            chunk_policy: config.chunk_policy,
+            adaptive: AdaptivePolicy {
+                bytes_per_thread: config.adaptive_threshold,
+                max_threads: config.max_threads,
+            },
         })
     }
 }
@@ -100,3 +627,38 @@ impl Default for SentenceProcessor {
         Self::new()
     }
 }
+
+/// Record the sentences of `output` that repeat one seen before
+fn mark_duplicates(text: &str, output: &mut Output, seen: &mut SentenceDeduplicator) {
+    output.duplicates = dedupe::duplicates(text, &output.boundaries, seen);
+    output.metadata.stats.duplicate_sentences = output.duplicates.len();
+}
+
+/// The `candidates` that are not among the final `boundaries`: those the
+/// rules or filters rejected and the `detected` boundaries that
+/// post-processing merged away
+fn rejected_candidates(
+    candidates: Vec<(usize, Result<(), RejectionReason>)>,
+    detected: &[usize],
+    boundaries: &[usize],
+) -> Vec<RejectedCandidate> {
+    let mut rejected: Vec<RejectedCandidate> = candidates
+        .into_iter()
+        .filter_map(|(offset, verdict)| {
+            verdict
+                .err()
+                .map(|reason| RejectedCandidate { offset, reason })
+        })
+        .collect();
+    rejected.extend(
+        detected
+            .iter()
+            .filter(|offset| boundaries.binary_search(offset).is_err())
+            .map(|&offset| RejectedCandidate {
+                offset,
+                reason: RejectionReason::ShortSentence,
+            }),
+    );
+    rejected.sort_by_key(|candidate| candidate.offset);
+    rejected
+}
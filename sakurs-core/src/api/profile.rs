@@ -0,0 +1,113 @@
+//! Presets bundling the rules suited to a kind of text
+
+use crate::api::Error;
+use crate::domain::language::config::{AbbreviationEntry, LanguageConfig};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Court rules and codes ("Fed. R. Civ. P.", "U.S.C.", "Pub. L."), matched
+/// case-insensitively
+const LEGAL_RULES: &[&str] = &[
+    "Fed", "R", "Civ", "Crim", "App", "Evid", "Bankr", "P", "U.S.C", "C.F.R", "Stat", "Pub", "L",
+    "Reg", "Admin", "Proc", "Const", "amend", "subd", "para", "sec", "tit", "Ann", "Supp",
+];
+
+/// Reporters, courts and abbreviated words in case names ("F. Supp.",
+/// "S. Ct.", "Cir.", "Dep't")
+const LEGAL_REPORTERS: &[&str] = &[
+    "F", "N.E", "N.W", "S.E", "S.W", "Ct", "Cir", "Dist", "Cal", "N.Y", "Tex", "Fla", "L.Ed", "v",
+    "Ass'n", "Bros", "Dep't", "Gov't", "Comm'n", "Int'l", "Nat'l", "Mfg",
+];
+
+/// Citation signals and short forms ("Id.", "cert.", "aff'd")
+const LEGAL_SIGNALS: &[&str] = &["Id", "Ibid", "nn", "cert", "aff'd", "rev'd"];
+
+/// Cross-references, citation forms, Latin abbreviations and units common in
+/// scientific writing ("et al.", "Fig. 3", "Eq. (2)", "cf.", "ca.", "resp.")
+const SCIENTIFIC_ABBREVIATIONS: &[&str] = &[
+    "et al", "Fig", "Figs", "Eq", "Eqs", "Ref", "Refs", "Tab", "Sect", "Suppl", "Ch", "App", "cf",
+    "ca", "resp", "approx", "viz", "ibid", "pp", "p", "ed", "eds", "vol", "no", "Nat", "Natl",
+    "Acad", "Sci", "Proc", "Biol", "Chem", "Phys", "Med", "Res", "Lett", "J", "spp", "sp", "var",
+    "subsp", "wt", "conc", "s.d", "n.s",
+];
+
+/// Presets bundling the options suited to a kind of text
+///
+/// A profile adjusts the language's rules; options set explicitly on the
+/// builder take precedence over the profile's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Profile {
+    /// Posts and chat messages: emoji end sentences, URLs, @mentions and
+    /// #hashtags are never split, and every line break ends a sentence
+    Social,
+    /// Court documents and briefs: citations ("Fed. R. Civ. P. 12(b)(6)",
+    /// "42 U.S.C. § 1983", "Smith v. Jones", "Id. at 5") and numbered
+    /// subsections are never split
+    Legal,
+    /// Papers and abstracts: "et al.", "Fig. 3", "Eq. (2)", "e.g.," and
+    /// "resp." do not end sentences, nor do periods inside DOIs, arXiv
+    /// identifiers and decimals without a leading zero ("p < .05")
+    Scientific,
+}
+
+impl Profile {
+    /// Add the profile's rules to a language configuration
+    pub(crate) fn apply(self, language: &mut LanguageConfig) {
+        match self {
+            Profile::Social => {
+                language.terminators.emoji = true;
+                language.suppression.web_tokens = true;
+            }
+            Profile::Legal => {
+                language.suppression.citations = true;
+                language
+                    .abbreviations
+                    .categories
+                    .entry("legal".into())
+                    .or_default()
+                    .extend(
+                        [LEGAL_RULES, LEGAL_REPORTERS, LEGAL_SIGNALS]
+                            .concat()
+                            .into_iter()
+                            .map(AbbreviationEntry::from),
+                    );
+            }
+            Profile::Scientific => {
+                language.suppression.citations = true;
+                language.suppression.web_tokens = true;
+                language
+                    .abbreviations
+                    .categories
+                    .entry("scientific".into())
+                    .or_default()
+                    .extend(
+                        SCIENTIFIC_ABBREVIATIONS
+                            .iter()
+                            .copied()
+                            .map(AbbreviationEntry::from),
+                    );
+            }
+        }
+    }
+
+    /// Whether the profile ends a sentence at every line break
+    pub(crate) fn line_break_boundaries(self) -> bool {
+        self == Profile::Social
+    }
+}
+
+impl FromStr for Profile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "social" => Ok(Profile::Social),
+            "legal" => Ok(Profile::Legal),
+            "scientific" | "science" => Ok(Profile::Scientific),
+            _ => Err(Error::Configuration(format!(
+                "profile must be social, legal or scientific, got: {s}"
+            ))),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Double-buffered read-ahead of files
+//!
+//! A thread reads the file block by block into two buffers that take turns:
+//! while the caller decodes and hands out one block, the next is being read,
+//! so the disk stays busy while the blocks already read are scanned.
+
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::path::Path;
+use std::sync::mpsc;
+
+use crate::api::stream::incomplete_tail;
+use crate::api::{Error, InvalidUtf8, Result};
+use crate::domain::types::Warning;
+
+/// Buffers taking turns between the reading thread and the caller
+const BUFFERS: usize = 2;
+
+/// The text of a file, decoded block by block as it is read ahead
+pub(crate) struct ReadAhead {
+    /// Blocks read; an empty block ends the file
+    filled: mpsc::Receiver<std::io::Result<Vec<u8>>>,
+    /// Blocks decoded, returned to the reading thread
+    free: mpsc::Sender<Vec<u8>>,
+    invalid_utf8: InvalidUtf8,
+    /// Bytes of a UTF-8 sequence split across blocks
+    carry: Vec<u8>,
+    /// Bytes of text decoded so far
+    decoded: usize,
+    invalid: Option<Warning>,
+    len: usize,
+    path: String,
+    done: bool,
+}
+
+impl ReadAhead {
+    /// Start reading `path` in blocks of `block_size` bytes
+    pub(crate) fn open(path: &Path, block_size: usize, invalid_utf8: InvalidUtf8) -> Result<Self> {
+        let display = path.display().to_string();
+        let failed = |e: std::io::Error| {
+            Error::Infrastructure(format!("Failed to read file {display}: {e}"))
+        };
+        let mut file = File::open(path).map_err(failed)?;
+        let len = file.metadata().map_err(failed)?.len() as usize;
+
+        let block_size = block_size.max(4);
+        let (free_tx, free_rx) = mpsc::channel();
+        for _ in 0..BUFFERS {
+            free_tx
+                .send(Vec::with_capacity(block_size))
+                .expect("receiver is alive");
+        }
+        let (filled_tx, filled_rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("sakurs-read-ahead".to_string())
+            .spawn(move || {
+                // Stops at the end of the file, on an error, or once the
+                // caller has gone
+                while let Ok(mut block) = free_rx.recv() {
+                    block.resize(block_size, 0);
+                    let read = read_full(&mut file, &mut block).map(|n| {
+                        block.truncate(n);
+                        block
+                    });
+                    let last = !matches!(&read, Ok(block) if !block.is_empty());
+                    if filled_tx.send(read).is_err() || last {
+                        return;
+                    }
+                }
+            })
+            .map_err(failed)?;
+
+        Ok(Self {
+            filled: filled_rx,
+            free: free_tx,
+            invalid_utf8,
+            carry: Vec::new(),
+            decoded: 0,
+            invalid: None,
+            len,
+            path: display,
+            done: false,
+        })
+    }
+
+    /// Size of the file when it was opened
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The invalid UTF-8 replaced or skipped so far, as one warning at the
+    /// first invalid sequence
+    pub(crate) fn invalid_utf8(&self) -> Option<Warning> {
+        self.invalid
+    }
+
+    fn decode(&mut self, bytes: Vec<u8>) -> Result<String> {
+        if self.invalid_utf8 == InvalidUtf8::Error {
+            // Report the position in the file rather than in the block
+            if let Err(e) = std::str::from_utf8(&bytes) {
+                let index = self.decoded + e.valid_up_to();
+                let problem = match e.error_len() {
+                    Some(len) => {
+                        format!("invalid utf-8 sequence of {len} bytes from index {index}")
+                    }
+                    None => format!("incomplete utf-8 byte sequence from index {index}"),
+                };
+                return Err(Error::Infrastructure(format!(
+                    "Invalid UTF-8 encoding: {problem}"
+                )));
+            }
+        }
+        let (text, warning) = self.invalid_utf8.decode_with_warning(bytes)?;
+        if let Some(Warning::InvalidUtf8 { offset, bytes }) = warning {
+            self.invalid = Some(match self.invalid {
+                Some(Warning::InvalidUtf8 {
+                    offset: first,
+                    bytes: before,
+                }) => Warning::InvalidUtf8 {
+                    offset: first,
+                    bytes: before + bytes,
+                },
+                _ => Warning::InvalidUtf8 {
+                    offset: self.decoded + offset,
+                    bytes,
+                },
+            });
+        }
+        self.decoded += text.len();
+        Ok(text)
+    }
+}
+
+impl Iterator for ReadAhead {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let block = match self.filled.recv() {
+            Ok(Ok(block)) => block,
+            Ok(Err(e)) => {
+                self.done = true;
+                return Some(Err(Error::Infrastructure(format!(
+                    "Failed to read file {}: {e}",
+                    self.path
+                ))));
+            }
+            Err(_) => {
+                self.done = true;
+                return Some(Err(Error::Infrastructure(format!(
+                    "Reading {} stopped unexpectedly",
+                    self.path
+                ))));
+            }
+        };
+        if block.is_empty() {
+            self.done = true;
+            let rest = std::mem::take(&mut self.carry);
+            return (!rest.is_empty()).then(|| self.decode(rest));
+        }
+
+        self.carry.extend_from_slice(&block);
+        // The thread has gone if it hit the end of the file already
+        let _ = self.free.send(block);
+        let cut = self.carry.len() - incomplete_tail(&self.carry);
+        let rest = self.carry.split_off(cut);
+        let piece = std::mem::replace(&mut self.carry, rest);
+        let text = self.decode(piece);
+        if text.is_err() {
+            self.done = true;
+        }
+        Some(text)
+    }
+}
+
+/// Read until `buf` is full or the reader is exhausted
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
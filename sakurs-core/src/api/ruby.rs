@@ -0,0 +1,201 @@
+//! Ruby (furigana) annotation stripping before segmentation
+//!
+//! Aozora Bunko texts give readings inline, `漢字《かんじ》`, with `｜`
+//! marking where the annotated text starts when it is not a run of kanji
+//! (`｜東京タワー《とうきょうタワー》`). HTML marks them up as
+//! `<ruby>漢字<rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby>`. A reading is not
+//! part of the sentence, and its brackets would be tracked as an enclosure;
+//! [`strip`] removes the annotations, and a [`RubyMap`] translates
+//! boundaries back to the original text.
+
+/// Longest reading recognized between `《` and `》`, in characters; a
+/// longer bracket is left as text.
+const MAX_READING_CHARS: usize = 64;
+
+/// An annotation removed from the text
+#[derive(Debug, Clone, Copy)]
+struct Removal {
+    /// Offset in the stripped text
+    at: usize,
+    /// Bytes removed up to and including this annotation
+    shift: usize,
+    /// Whether the annotation belongs to the text before it (a reading, a
+    /// closing tag) rather than the text after it (`｜`, an opening tag)
+    trailing: bool,
+}
+
+/// Maps byte offsets in stripped text back to the original text
+#[derive(Debug, Default)]
+pub(crate) struct RubyMap {
+    /// Removals in text order
+    removals: Vec<Removal>,
+}
+
+impl RubyMap {
+    /// Original offset of stripped offset `offset`. A boundary where
+    /// annotations were removed stays after the readings of the text before
+    /// it and before the markers of the text after it.
+    pub(crate) fn to_original(&self, offset: usize) -> usize {
+        let i = self
+            .removals
+            .partition_point(|r| r.at < offset || r.at == offset && r.trailing);
+        offset + i.checked_sub(1).map_or(0, |i| self.removals[i].shift)
+    }
+}
+
+/// Removes ruby annotations from `text`, or returns `None` if it has none.
+///
+/// A `《…》` reading must close on its line; a `｜` is removed only when a
+/// reading follows it on its line. HTML `<ruby>`, `<rb>` and `<rtc>` tags
+/// are removed with their contents kept, `<rt>` and `<rp>` elements with
+/// their contents (an `<rt>` without a closing tag ends at the next `<rt>`,
+/// `<rp>` or `</ruby>`). Other markup is left alone.
+pub(crate) fn strip(text: &str) -> Option<(String, RubyMap)> {
+    if !text.contains(['《', '<']) {
+        return None;
+    }
+    let mut stripped = String::with_capacity(text.len());
+    let mut map = RubyMap::default();
+    let mut kept = 0;
+    let mut pos = 0;
+    while let Some(found) = text[pos..].find(['｜', '《', '<']) {
+        let start = pos + found;
+        let removal = match text[start..].chars().next() {
+            Some('｜') => bar_end(text, start).map(|end| (end, false)),
+            Some('《') => reading_end(text, start).map(|end| (end, true)),
+            _ => ruby_tag_end(text, start),
+        };
+        match removal {
+            Some((end, trailing)) => {
+                stripped.push_str(&text[kept..start]);
+                let shift = map.removals.last().map_or(0, |r| r.shift) + end - start;
+                map.removals.push(Removal {
+                    at: stripped.len(),
+                    shift,
+                    trailing,
+                });
+                kept = end;
+                pos = end;
+            }
+            None => pos = start + 1,
+        }
+        while !text.is_char_boundary(pos) {
+            pos += 1;
+        }
+    }
+    if map.removals.is_empty() {
+        return None;
+    }
+    stripped.push_str(&text[kept..]);
+    Some((stripped, map))
+}
+
+/// End of the `｜` at `start` when a reading follows it on its line
+fn bar_end(text: &str, start: usize) -> Option<usize> {
+    let end = start + '｜'.len_utf8();
+    let base = text[end..].find(['《', '｜', '\n'])?;
+    (text[end + base..].starts_with('《') && reading_end(text, end + base).is_some()).then_some(end)
+}
+
+/// End of the reading whose `《` is at `start`
+fn reading_end(text: &str, start: usize) -> Option<usize> {
+    let inner = start + '《'.len_utf8();
+    for (i, (offset, ch)) in text[inner..].char_indices().enumerate() {
+        match ch {
+            '》' if i > 0 => return Some(inner + offset + ch.len_utf8()),
+            '》' | '《' | '\n' => return None,
+            _ if i >= MAX_READING_CHARS => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// End of the ruby markup starting with the `<` at `start`, and whether it
+/// belongs to the text before it
+fn ruby_tag_end(text: &str, start: usize) -> Option<(usize, bool)> {
+    let (name, closing, end) = tag_at(text, start)?;
+    match name.as_str() {
+        "ruby" | "rb" | "rtc" => Some((end, closing)),
+        "rt" | "rp" if closing => Some((end, true)),
+        "rt" | "rp" => {
+            let mut pos = end;
+            while let Some(found) = text[pos..].find('<') {
+                let next = pos + found;
+                match tag_at(text, next) {
+                    Some((ref n, true, after)) if *n == name => return Some((after, true)),
+                    Some((ref n, false, _)) if n == "rt" || n == "rp" => return Some((next, true)),
+                    Some((ref n, true, _)) if n == "ruby" => return Some((next, true)),
+                    _ => pos = next + 1,
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// The lowercased name of the tag at `start`, whether it is a closing tag,
+/// and its end
+fn tag_at(text: &str, start: usize) -> Option<(String, bool, usize)> {
+    let rest = text[start..].strip_prefix('<')?;
+    let (closing, rest) = match rest.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let name_len = rest
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(rest.len());
+    let after_name = &rest[name_len..];
+    if name_len == 0 || !after_name.starts_with(['>', ' ', '\t', '\n', '/']) {
+        return None;
+    }
+    let close = after_name.find(['>', '<'])?;
+    if !after_name[close..].starts_with('>') {
+        return None;
+    }
+    let end = text.len() - after_name.len() + close + 1;
+    Some((rest[..name_len].to_ascii_lowercase(), closing, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aozora_readings_are_removed() {
+        let text = "漢字《かんじ》を｜東京タワー《とうきょうタワー》で読む。";
+        let (stripped, map) = strip(text).unwrap();
+        assert_eq!(stripped, "漢字を東京タワーで読む。");
+        assert_eq!(map.to_original(0), 0);
+        // After the base: past its reading
+        assert_eq!(map.to_original("漢字".len()), text.find('を').unwrap());
+        // Before an annotated word: before its bar
+        assert_eq!(map.to_original("漢字を".len()), text.find('｜').unwrap());
+        assert_eq!(map.to_original(stripped.len()), text.len());
+    }
+
+    #[test]
+    fn test_brackets_that_are_not_readings_stay() {
+        assert!(strip("《朝日新聞\n》と｜だけ").is_none());
+        assert!(strip("《》").is_none());
+        let (stripped, _) = strip("a｜b《c》｜d").unwrap();
+        assert_eq!(stripped, "ab｜d");
+    }
+
+    #[test]
+    fn test_html_ruby_markup_is_removed() {
+        let text = "<p>彼は<ruby>明日<rp>(</rp><rt>あした</rt><rp>)</rp></ruby>来る。</p>";
+        let (stripped, map) = strip(text).unwrap();
+        assert_eq!(stripped, "<p>彼は明日来る。</p>");
+        assert_eq!(
+            map.to_original("<p>彼は明日".len()),
+            text.find("来").unwrap()
+        );
+
+        // Unclosed <rt> elements end at the next one or at </ruby>
+        let (stripped, _) = strip("<RUBY>漢<rt>かん字<rt>じ</ruby>です").unwrap();
+        assert_eq!(stripped, "漢です");
+        assert!(strip("a < b <rtx> <b>").is_none());
+    }
+}
@@ -0,0 +1,353 @@
+//! Incremental processing of text supplied in pieces
+
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Read};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::output::{OffsetCursor, OffsetUnits};
+use crate::api::{Boundary, Error, InvalidUtf8, Result};
+use crate::application::{DeltaStackStream, StreamState};
+
+/// Format version of [`StreamCheckpoint`], bumped when its contents change
+const CHECKPOINT_VERSION: u32 = 1;
+
+/// Push-based sentence segmentation over text that arrives in pieces.
+///
+/// Created by [`SentenceProcessor::stream`](crate::SentenceProcessor::stream).
+/// Boundaries are reported as soon as they are final, and the boundaries of
+/// a whole stream equal those of processing the concatenated text at once,
+/// however the pieces are cut. Memory stays proportional to the piece size
+/// plus the text since the last reported boundary.
+///
+/// ```rust
+/// use sakurs_core::SentenceProcessor;
+///
+/// let processor = SentenceProcessor::new();
+/// let mut stream = processor.stream();
+/// let mut boundaries = stream.feed("Hello world. This is ");
+/// boundaries.extend(stream.feed("a test."));
+/// boundaries.extend(stream.finish());
+/// assert_eq!(boundaries.len(), 2);
+/// ```
+pub struct SentenceStream {
+    inner: DeltaStackStream,
+    tail: TailText,
+    /// Fingerprint of the processor's rules, recorded in checkpoints
+    fingerprint: u64,
+}
+
+impl SentenceStream {
+    pub(crate) fn new(inner: DeltaStackStream, units: OffsetUnits, fingerprint: u64) -> Self {
+        Self {
+            inner,
+            tail: TailText {
+                text: String::new(),
+                offset: 0,
+                cursor: OffsetCursor::new(units),
+            },
+            fingerprint,
+        }
+    }
+
+    /// Continues the stream saved in `checkpoint` with `resume`, which
+    /// rebuilds the executor from its state; fails if the checkpoint comes
+    /// from a processor with a different fingerprint.
+    pub(crate) fn resume(
+        checkpoint: StreamCheckpoint,
+        units: OffsetUnits,
+        fingerprint: u64,
+        resume: impl FnOnce(StreamState) -> DeltaStackStream,
+    ) -> Result<Self> {
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(Error::InvalidInput(format!(
+                "Stream checkpoint format {} is not supported (expected {CHECKPOINT_VERSION})",
+                checkpoint.version
+            )));
+        }
+        if checkpoint.fingerprint != fingerprint {
+            return Err(Error::InvalidInput(
+                "Stream checkpoint was taken with different rules or configuration".to_string(),
+            ));
+        }
+        if checkpoint.tail_offset + checkpoint.tail.len() != checkpoint.state.bytes_fed() {
+            return Err(Error::InvalidInput(
+                "Stream checkpoint is inconsistent".to_string(),
+            ));
+        }
+        Ok(Self {
+            inner: resume(checkpoint.state),
+            tail: TailText {
+                text: checkpoint.tail,
+                offset: checkpoint.tail_offset,
+                cursor: checkpoint.cursor.with_units(units),
+            },
+            fingerprint,
+        })
+    }
+
+    /// Saves the stream as of the text fed so far, to continue later with
+    /// [`SentenceProcessor::resume_stream`](crate::SentenceProcessor::resume_stream)
+    pub fn checkpoint(&self) -> StreamCheckpoint {
+        StreamCheckpoint {
+            version: CHECKPOINT_VERSION,
+            fingerprint: self.fingerprint,
+            state: self.inner.state(),
+            tail: self.tail.text.clone(),
+            tail_offset: self.tail.offset,
+            cursor: self.tail.cursor,
+        }
+    }
+
+    /// Appends `text` and returns the boundaries that became final, in
+    /// stream-global offsets.
+    pub fn feed(&mut self, text: &str) -> Vec<Boundary> {
+        self.tail.text.push_str(text);
+        let offsets = self.inner.feed(text);
+        self.tail.locate(offsets)
+    }
+
+    /// Ends the stream and returns the remaining boundaries.
+    pub fn finish(mut self) -> Vec<Boundary> {
+        let offsets = self.inner.finish();
+        self.tail.locate(offsets)
+    }
+
+    /// Like [`Self::feed`], but returns the completed sentences with their
+    /// text.
+    pub fn feed_spans(&mut self, text: &str) -> Vec<SentenceSpan> {
+        self.tail.text.push_str(text);
+        let offsets = self.inner.feed(text);
+        self.tail.spans(offsets)
+    }
+
+    /// Like [`Self::finish`], but returns the remaining sentences with their
+    /// text. Trailing text without a final terminator becomes the last
+    /// sentence, so the spans of a stream cover all of its text.
+    pub fn finish_spans(mut self) -> Vec<SentenceSpan> {
+        let mut offsets = self.inner.finish();
+        let end = self.tail.offset + self.tail.text.len();
+        if offsets.last() != Some(&end) && !self.tail.text.is_empty() {
+            offsets.push(end);
+        }
+        self.tail.spans(offsets)
+    }
+
+    /// Total bytes fed so far.
+    pub fn bytes_processed(&self) -> usize {
+        self.inner.bytes_fed()
+    }
+}
+
+/// A [`SentenceStream`] saved mid-stream, to continue after a restart.
+///
+/// Taken with [`SentenceStream::checkpoint`] and resumed with
+/// [`SentenceProcessor::resume_stream`](crate::SentenceProcessor::resume_stream)
+/// by a processor with the same rules and configuration: feeding the resumed
+/// stream the text after [`bytes_processed`](Self::bytes_processed) gives the
+/// boundaries the original stream would have. Store it with any serde
+/// format. Besides a small fixed-size state, it holds the text since the
+/// last reported boundary.
+///
+/// ```rust
+/// use sakurs_core::SentenceProcessor;
+///
+/// let processor = SentenceProcessor::new();
+/// let mut stream = processor.stream();
+/// let mut boundaries = stream.feed("Hello world. This is ");
+/// let checkpoint = stream.checkpoint();
+/// drop(stream);
+///
+/// let mut stream = processor.resume_stream(checkpoint).unwrap();
+/// boundaries.extend(stream.feed("a test."));
+/// boundaries.extend(stream.finish());
+/// assert_eq!(boundaries.len(), 2);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamCheckpoint {
+    version: u32,
+    fingerprint: u64,
+    state: StreamState,
+    /// Text after the last reported boundary
+    tail: String,
+    tail_offset: usize,
+    cursor: OffsetCursor,
+}
+
+impl StreamCheckpoint {
+    /// Bytes of text fed to the stream before the checkpoint; the resumed
+    /// stream continues from this offset
+    pub fn bytes_processed(&self) -> usize {
+        self.state.bytes_fed()
+    }
+}
+
+/// A sentence produced by incremental processing: its text and where it
+/// sits in the stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceSpan {
+    /// Byte offset of the sentence start
+    pub start: usize,
+    /// Byte offset just past the sentence end (the boundary)
+    pub end: usize,
+    /// Character offset of the sentence start
+    pub char_start: usize,
+    /// Character offset just past the sentence end
+    pub char_end: usize,
+    /// Sentence text, including surrounding whitespace
+    pub text: String,
+}
+
+/// Pull-based sentence segmentation of a reader, created by
+/// [`SentenceProcessor::sentences_from_reader`](crate::SentenceProcessor::sentences_from_reader).
+pub(crate) struct ReaderSentences<R> {
+    reader: R,
+    /// `None` once the reader is exhausted or has failed
+    stream: Option<SentenceStream>,
+    invalid_utf8: InvalidUtf8,
+    buf: Vec<u8>,
+    /// Bytes of a UTF-8 sequence split across reads
+    carry: Vec<u8>,
+    ready: VecDeque<SentenceSpan>,
+}
+
+impl<R: Read> ReaderSentences<R> {
+    pub(crate) fn new(
+        reader: R,
+        stream: SentenceStream,
+        read_size: usize,
+        invalid_utf8: InvalidUtf8,
+    ) -> Self {
+        Self {
+            reader,
+            stream: Some(stream),
+            invalid_utf8,
+            buf: vec![0; read_size.max(4)],
+            carry: Vec::new(),
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Reads the next piece and feeds it to the stream, finishing the stream
+    /// at the end of the reader.
+    fn read_piece(&mut self, mut stream: SentenceStream) -> Result<()> {
+        let n = loop {
+            match self.reader.read(&mut self.buf) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    return Err(Error::Infrastructure(format!(
+                        "Failed to read from reader: {e}"
+                    )))
+                }
+            }
+        };
+        if n == 0 {
+            let (rest, _) = self.invalid_utf8.decode(std::mem::take(&mut self.carry))?;
+            let mut spans = stream.feed_spans(&rest);
+            spans.extend(stream.finish_spans());
+            self.ready.extend(spans);
+            return Ok(());
+        }
+
+        self.carry.extend_from_slice(&self.buf[..n]);
+        let cut = self.carry.len() - incomplete_tail(&self.carry);
+        let rest = self.carry.split_off(cut);
+        let (piece, _) = self
+            .invalid_utf8
+            .decode(std::mem::replace(&mut self.carry, rest))?;
+        self.ready.extend(stream.feed_spans(&piece));
+        self.stream = Some(stream);
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for ReaderSentences<R> {
+    type Item = Result<SentenceSpan>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(span) = self.ready.pop_front() {
+                return Some(Ok(span));
+            }
+            let stream = self.stream.take()?;
+            if let Err(e) = self.read_piece(stream) {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Length of the UTF-8 sequence cut off at the end of `bytes`, if any
+pub(super) fn incomplete_tail(bytes: &[u8]) -> usize {
+    for back in 1..=bytes.len().min(3) {
+        let byte = bytes[bytes.len() - back];
+        // Skip continuation bytes back to the lead byte
+        if byte & 0xC0 != 0x80 {
+            let width = match byte {
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => 1,
+            };
+            return if width > back { back } else { 0 };
+        }
+    }
+    0
+}
+
+/// Text after the last reported boundary, kept for character offsets.
+struct TailText {
+    text: String,
+    /// Byte offset of `text` in the stream.
+    offset: usize,
+    /// Position of `text` in the stream in the other offset units.
+    cursor: OffsetCursor,
+}
+
+impl TailText {
+    /// Converts ascending byte offsets into boundaries and drops the text
+    /// before the last one.
+    fn locate(&mut self, offsets: Vec<usize>) -> Vec<Boundary> {
+        let mut boundaries = Vec::with_capacity(offsets.len());
+        let mut prev = 0;
+        for offset in offsets {
+            let local = offset - self.offset;
+            self.cursor.advance(&self.text[prev..local]);
+            boundaries.push(self.cursor.boundary(offset));
+            prev = local;
+        }
+        self.advance(prev);
+        boundaries
+    }
+
+    /// Converts ascending byte offsets into the sentences ending at them and
+    /// drops their text.
+    fn spans(&mut self, offsets: Vec<usize>) -> Vec<SentenceSpan> {
+        let mut spans = Vec::with_capacity(offsets.len());
+        let mut prev = 0;
+        for offset in offsets {
+            let local = offset - self.offset;
+            let text = &self.text[prev..local];
+            let char_start = self.cursor.chars;
+            self.cursor.advance(text);
+            spans.push(SentenceSpan {
+                start: self.offset + prev,
+                end: offset,
+                char_start,
+                char_end: self.cursor.chars,
+                text: text.to_string(),
+            });
+            prev = local;
+        }
+        self.advance(prev);
+        spans
+    }
+
+    /// Drops the first `bytes` of the text, which the cursor has already
+    /// moved past.
+    fn advance(&mut self, bytes: usize) {
+        self.text.drain(..bytes);
+        self.offset += bytes;
+    }
+}
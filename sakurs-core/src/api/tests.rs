@@ -73,6 +73,1324 @@ mod api_tests {
         }
     }
 
+    #[test]
+    fn test_invalid_utf8_replace_and_skip() {
+        let bytes = b"Hello \xFF\xFEworld. Bye \xC3.".to_vec();
+        let process = |policy| {
+            let config = Config::builder().invalid_utf8(policy).build().unwrap();
+            SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_bytes(bytes.clone()))
+                .unwrap()
+        };
+
+        let replaced = process(InvalidUtf8::Replace);
+        assert_eq!(replaced.metadata.stats.invalid_utf8_bytes, 3);
+        assert_eq!(replaced.boundaries.len(), 2);
+        // Offsets refer to the decoded text; each invalid sequence becomes
+        // one U+FFFD: "Hello \u{FFFD}\u{FFFD}world."
+        assert_eq!(replaced.boundaries[0].offset, 18);
+
+        // One warning at the first invalid sequence counts them all
+        assert_eq!(
+            replaced.warnings,
+            [Warning::InvalidUtf8 {
+                offset: 6,
+                bytes: 3
+            }]
+        );
+
+        let skipped = process(InvalidUtf8::Skip);
+        assert_eq!(skipped.metadata.stats.invalid_utf8_bytes, 3);
+        assert_eq!(skipped.boundaries[0].offset, "Hello world.".len());
+        assert_eq!(skipped.warnings[0].offset(), 6);
+
+        let clean = SentenceProcessor::new()
+            .process(Input::from_bytes(b"Hello world.".to_vec()))
+            .unwrap();
+        assert_eq!(clean.metadata.stats.invalid_utf8_bytes, 0);
+        assert!(clean.warnings.is_empty());
+        assert_eq!("skip".parse::<InvalidUtf8>().unwrap(), InvalidUtf8::Skip);
+        assert!("lossy".parse::<InvalidUtf8>().is_err());
+    }
+
+    #[test]
+    fn test_ellipsis_policies() {
+        let text = "He waited... then left. It ended… The end. So um... Yes.";
+        let sentences = |config: ConfigBuilder| -> Vec<String> {
+            let output = SentenceProcessor::with_config(config.build().unwrap())
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            let mut start = 0;
+            output
+                .boundaries
+                .iter()
+                .map(|b| {
+                    let sentence = text[start..b.offset].trim().to_string();
+                    start = b.offset;
+                    sentence
+                })
+                .collect()
+        };
+
+        // English: boundary before a capital, except after a hesitation
+        assert_eq!(
+            sentences(Config::builder()),
+            [
+                "He waited... then left.",
+                "It ended…",
+                "The end.",
+                "So um... Yes."
+            ]
+        );
+        assert_eq!(
+            sentences(Config::builder().ellipsis(EllipsisPolicy::Never)),
+            [
+                "He waited... then left.",
+                "It ended… The end.",
+                "So um... Yes."
+            ]
+        );
+        assert_eq!(
+            sentences(Config::builder().ellipsis(EllipsisPolicy::Always)),
+            [
+                "He waited...",
+                "then left.",
+                "It ended…",
+                "The end.",
+                "So um...",
+                "Yes."
+            ]
+        );
+        assert_eq!(
+            sentences(Config::builder().ellipsis(EllipsisPolicy::BeforeCapital)),
+            [
+                "He waited... then left.",
+                "It ended…",
+                "The end.",
+                "So um...",
+                "Yes."
+            ]
+        );
+        // Exceptions added through the config win over the policy
+        assert_eq!(
+            sentences(
+                Config::builder()
+                    .ellipsis(EllipsisPolicy::Always)
+                    .ellipsis_exception(r"waited\.\.\.", false)
+            ),
+            [
+                "He waited... then left.",
+                "It ended…",
+                "The end.",
+                "So um...",
+                "Yes."
+            ]
+        );
+
+        assert_eq!(
+            "before-capital".parse::<EllipsisPolicy>().unwrap(),
+            EllipsisPolicy::BeforeCapital
+        );
+        assert!("sometimes".parse::<EllipsisPolicy>().is_err());
+        assert!(Config::builder()
+            .ellipsis_exception("(", true)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_sentence_length_limits() {
+        let sentences = |config: ConfigBuilder, text: &str| -> Vec<String> {
+            let output = SentenceProcessor::with_config(config.build().unwrap())
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            let mut start = 0;
+            let mut sentences: Vec<String> = output
+                .boundaries
+                .iter()
+                .map(|b| {
+                    let sentence = text[start..b.offset].trim().to_string();
+                    start = b.offset;
+                    sentence
+                })
+                .collect();
+            if !text[start..].trim().is_empty() {
+                sentences.push(text[start..].trim().to_string());
+            }
+            sentences
+        };
+
+        // Short fragments join the previous sentence (the first, the next)
+        assert_eq!(
+            sentences(
+                Config::builder().min_sentence_chars(8),
+                "No. We left early. Ok. Then it rained. Yes."
+            ),
+            ["No. We left early. Ok.", "Then it rained. Yes."]
+        );
+
+        // Overlong sentences are cut at the last rejected terminator, then
+        // clause punctuation, then a space within the limit
+        let text = "The sample weighed approx. 5 kg after drying, and the \
+                    residue weighed less than one gram in every trial";
+        assert_eq!(
+            sentences(Config::builder().max_sentence_chars(40), text),
+            [
+                "The sample weighed approx.",
+                "5 kg after drying,",
+                "and the residue weighed less than one",
+                "gram in every trial"
+            ]
+        );
+        assert_eq!(
+            sentences(
+                Config::builder().max_sentence_chars(4),
+                "これは長い文です。"
+            ),
+            ["これは長", "い文です", "。"]
+        );
+    }
+
+    #[test]
+    fn test_forced_split_of_unpunctuated_text() {
+        let text = "token stream without any periods, generated by a machine \
+                    and never ending 気づけばここまで、まだまだ続く";
+        let processor = SentenceProcessor::with_config(
+            Config::builder().force_split_bytes(40).build().unwrap(),
+        )
+        .unwrap();
+        let output = processor.process(Input::from_text(text)).unwrap();
+        let offsets: Vec<usize> = output.boundaries.iter().map(|b| b.offset).collect();
+        assert_eq!(offsets, [33, 73, 101]);
+        assert_eq!(&text[..33], "token stream without any periods,");
+        assert_eq!(&text[73..101], " 気づけばここまで、");
+        assert!(output
+            .boundaries
+            .iter()
+            .all(|b| b.kind == BoundaryKind::Forced));
+        let warnings: Vec<Warning> = [33, 73, 101]
+            .into_iter()
+            .map(|offset| Warning::ForcedSplit { offset })
+            .collect();
+        assert_eq!(output.warnings, warnings);
+
+        // Rule boundaries keep their kind; spans within the limit are untouched
+        let output = processor
+            .process(Input::from_text("A short one. Another."))
+            .unwrap();
+        assert_eq!(output.boundaries.len(), 2);
+        assert!(output
+            .boundaries
+            .iter()
+            .all(|b| b.kind == BoundaryKind::Weak));
+        assert!(output.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_long_sentence_warnings() {
+        let text = "Short. A sentence of forty characters or so here. 長い長い文。";
+        let config = Config::builder().long_sentence_chars(20).build().unwrap();
+        let output = SentenceProcessor::with_config(config)
+            .unwrap()
+            .process(Input::from_text(text))
+            .unwrap();
+        assert_eq!(
+            output.warnings,
+            [Warning::LongSentence {
+                offset: 7,
+                chars: 42
+            }]
+        );
+        assert_eq!(
+            output.warnings[0].to_string(),
+            "suspiciously long sentence of 42 characters"
+        );
+        assert_eq!(output.warnings[0].kind(), "long_sentence");
+
+        // Counted in characters, not bytes, and the default is generous
+        let config = Config::builder().long_sentence_chars(6).build().unwrap();
+        let output = SentenceProcessor::with_config(config)
+            .unwrap()
+            .process(Input::from_text("長い長い文。"))
+            .unwrap();
+        assert!(output.warnings.is_empty());
+        let output = SentenceProcessor::new()
+            .process(Input::from_text(text))
+            .unwrap();
+        assert!(output.warnings.is_empty());
+
+        assert!(Config::builder().long_sentence_chars(0).build().is_err());
+    }
+
+    #[test]
+    fn test_rejected_candidates() {
+        let text = "Dr. Smith paid 3.50 dollars. He said \"Stop. Now.\" and left. Ok.";
+        let rejected = |builder: ConfigBuilder| {
+            let processor = SentenceProcessor::with_config(builder.build().unwrap()).unwrap();
+            let output = processor.process(Input::from_text(text)).unwrap();
+            output
+                .rejected_candidates
+                .iter()
+                .map(|c| (&text[..c.offset], c.reason))
+                .collect::<Vec<_>>()
+        };
+
+        // Off by default
+        assert!(rejected(Config::builder()).is_empty());
+        assert_eq!(
+            rejected(Config::builder().include_rejected_candidates(true)),
+            [
+                ("Dr.", RejectionReason::Abbreviation),
+                ("Dr. Smith paid 3.", RejectionReason::Decimal),
+                (
+                    "Dr. Smith paid 3.50 dollars. He said \"Stop.",
+                    RejectionReason::InsideEnclosure
+                ),
+                (
+                    "Dr. Smith paid 3.50 dollars. He said \"Stop. Now.",
+                    RejectionReason::InsideEnclosure
+                ),
+            ]
+        );
+        // Boundaries merged away by post-processing are reported too
+        let merged = rejected(
+            Config::builder()
+                .include_rejected_candidates(true)
+                .min_sentence_chars(5),
+        );
+        assert_eq!(
+            merged.last(),
+            Some(&(
+                "Dr. Smith paid 3.50 dollars. He said \"Stop. Now.\" and left.",
+                RejectionReason::ShortSentence
+            ))
+        );
+    }
+
+    #[test]
+    fn test_boundary_filters() {
+        let text = "Call Dr. Lee at 10 p.m. today. Ticket #12. see log. Done.";
+        let run = |builder: ConfigBuilder| {
+            let processor = SentenceProcessor::with_config(builder.build().unwrap()).unwrap();
+            processor.process(Input::from_text(text)).unwrap()
+        };
+        let sentences = |output: &Output| {
+            let mut start = 0;
+            output
+                .boundaries
+                .iter()
+                .map(|b| {
+                    let sentence = text[start..b.offset].trim();
+                    start = b.offset;
+                    sentence
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Force a boundary after a rejected "p.m." before a lowercase word,
+        // veto one before a lowercase continuation
+        let output = run(Config::builder()
+            .boundary_filter(|c: &BoundaryCandidate| {
+                if c.before().ends_with("p.m.") && c.after().starts_with(" today") {
+                    FilterDecision::Force
+                } else {
+                    FilterDecision::Keep
+                }
+            })
+            .boundary_filter(|c: &BoundaryCandidate| {
+                let lowercase_next = c.after().trim_start().starts_with(char::is_lowercase);
+                if c.is_boundary() && c.terminator() == '.' && lowercase_next {
+                    FilterDecision::Veto
+                } else {
+                    FilterDecision::Keep
+                }
+            })
+            .include_rejected_candidates(true));
+        assert_eq!(
+            sentences(&output),
+            [
+                "Call Dr. Lee at 10 p.m. today.",
+                "Ticket #12. see log.",
+                "Done."
+            ]
+        );
+        // The later filter overrides the earlier one
+        assert!(output
+            .rejected_candidates
+            .iter()
+            .any(|c| text[..c.offset].ends_with("p.m.") && c.reason == RejectionReason::Filter));
+        assert!(output
+            .rejected_candidates
+            .iter()
+            .any(|c| text[..c.offset].ends_with("#12.") && c.reason == RejectionReason::Filter));
+
+        // A single forcing filter adds the boundary
+        let output = run(Config::builder().boundary_filter(|c: &BoundaryCandidate| {
+            if c.before().ends_with("p.m.") {
+                FilterDecision::Force
+            } else {
+                FilterDecision::Keep
+            }
+        }));
+        assert_eq!(sentences(&output)[0], "Call Dr. Lee at 10 p.m.");
+    }
+
+    #[test]
+    fn test_suppression_regex() {
+        let text = "Fixed in ABC-1234. and CS 101. Both shipped. Next.";
+        let boundaries = |builder: ConfigBuilder| {
+            let processor = SentenceProcessor::with_config(builder.build().unwrap()).unwrap();
+            let output = processor.process(Input::from_text(text)).unwrap();
+            output
+                .boundaries
+                .iter()
+                .map(|b| b.offset)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(boundaries(Config::builder()), [18, 30, 44, 50]);
+        let suppressed = Config::builder().suppression_regex(vec![
+            r"[A-Z]+-\d+\.".to_string(),
+            r"\bCS \d{3}\.".to_string(),
+        ]);
+        assert_eq!(boundaries(suppressed), [44, 50]);
+
+        // Suppressed candidates are reported as such
+        let processor = SentenceProcessor::with_config(
+            Config::builder()
+                .suppression_regex(vec![r"[A-Z]+-\d+\.".to_string()])
+                .include_rejected_candidates(true)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let output = processor.process(Input::from_text(text)).unwrap();
+        assert_eq!(
+            output.rejected_candidates,
+            [RejectedCandidate {
+                offset: 18,
+                reason: RejectionReason::Suppressed
+            }]
+        );
+
+        let invalid = Config::builder()
+            .suppression_regex(vec!["(unclosed".to_string()])
+            .build();
+        assert!(matches!(invalid, Err(Error::Configuration(_))));
+    }
+
+    #[test]
+    fn test_process_with_corrections() {
+        let text = "Fixed in ABC-1234. and CS 101. Both shipped. Next.";
+        let processor = SentenceProcessor::with_config(
+            Config::builder()
+                .boundary_filter(|c: &BoundaryCandidate| {
+                    if c.before().ends_with("shipped.") {
+                        FilterDecision::Veto
+                    } else {
+                        FilterDecision::Keep
+                    }
+                })
+                .include_rejected_candidates(true)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let offsets = |output: &Output| {
+            output
+                .boundaries
+                .iter()
+                .map(|b| b.offset)
+                .collect::<Vec<_>>()
+        };
+        let process = |corrections: &[Correction]| {
+            processor
+                .process_with_corrections(Input::from_text(text), corrections)
+                .unwrap()
+        };
+
+        assert_eq!(offsets(&process(&[])), [18, 30, 50]);
+        // Corrections override the rules and the filters, and an accepted
+        // offset need not be a candidate
+        let output = process(&[
+            Correction::reject(18),
+            Correction::accept(22),
+            Correction::accept(44),
+        ]);
+        assert_eq!(offsets(&output), [22, 30, 44, 50]);
+        assert_eq!(
+            output.rejected_candidates,
+            [RejectedCandidate {
+                offset: 18,
+                reason: RejectionReason::Corrected
+            }]
+        );
+
+        // Corrections also win over sentence length limits, which merge
+        // every other sentence of the text away
+        let processor = SentenceProcessor::with_config(
+            Config::builder().min_sentence_chars(20).build().unwrap(),
+        )
+        .unwrap();
+        let output = processor.process(Input::from_text(text)).unwrap();
+        assert_eq!(offsets(&output), [50]);
+        let output = processor
+            .process_with_corrections(Input::from_text(text), &[Correction::accept(44)])
+            .unwrap();
+        assert_eq!(offsets(&output), [44, 50]);
+
+        // Offsets outside the text or inside a character are errors
+        for offset in [0, 51] {
+            let result = processor
+                .process_with_corrections(Input::from_text(text), &[Correction::reject(offset)]);
+            assert!(matches!(result, Err(Error::InvalidInput(_))));
+        }
+        let result = processor
+            .process_with_corrections(Input::from_text("Café. Ok."), &[Correction::accept(4)]);
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_enclosure_warnings_and_paragraph_repair() {
+        let text = "He said \"Stop. Go home.\n\nThe next day came. It rained.) Fine (really. Done.";
+        let process = |close: bool, chunk_size: usize| {
+            let config = Config::builder()
+                .enclosure_warnings(true)
+                .close_enclosures_at_paragraphs(close)
+                .chunk_size(chunk_size)
+                .threads(Some(2))
+                .build()
+                .unwrap();
+            SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap()
+        };
+        let offsets = |output: &Output| {
+            output
+                .boundaries
+                .iter()
+                .map(|b| b.offset)
+                .collect::<Vec<_>>()
+        };
+        let warnings = [
+            Warning::UnclosedEnclosure { offset: 8, ch: '"' },
+            Warning::UnmatchedCloser {
+                offset: 54,
+                ch: ')',
+            },
+            Warning::UnclosedEnclosure {
+                offset: 61,
+                ch: '(',
+            },
+        ];
+
+        // The unclosed quote swallows every later boundary
+        let output = process(false, 1024);
+        assert!(output.boundaries.is_empty());
+        assert_eq!(output.warnings, warnings);
+
+        // Closed at the blank line, it only affects its own paragraph, for
+        // every chunking
+        for chunk_size in [1, 7, 1024] {
+            let output = process(true, chunk_size);
+            assert_eq!(offsets(&output), [43, 54, 69, 75]);
+            assert_eq!(output.warnings, warnings);
+        }
+
+        // Without dangling enclosures the repair changes nothing, and
+        // warnings are off by default
+        let balanced = "He said \"Stop.\" Then left.\n\nNext (day). Done.";
+        let plain = SentenceProcessor::new()
+            .process(Input::from_text(balanced))
+            .unwrap();
+        assert!(plain.warnings.is_empty());
+        let repaired = SentenceProcessor::with_config(
+            Config::builder()
+                .close_enclosures_at_paragraphs(true)
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+        .process(Input::from_text(balanced))
+        .unwrap();
+        assert_eq!(offsets(&repaired), offsets(&plain));
+        assert!(repaired.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_max_enclosure_depth() {
+        let process = |text: &str, overflow: Option<DepthOverflow>, chunk_size: usize| {
+            let mut builder = Config::builder().chunk_size(chunk_size).threads(Some(2));
+            if let Some(overflow) = overflow {
+                builder = builder.max_enclosure_depth(2, overflow);
+            }
+            let output = SentenceProcessor::with_config(builder.build().unwrap())
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            let offsets: Vec<usize> = output.boundaries.iter().map(|b| b.offset).collect();
+            (offsets, output.warnings)
+        };
+        let exceeded = vec![Warning::DepthExceeded { offset: 2, ch: '(' }];
+
+        // Openers past the cap do not count, so two closers end the brackets
+        let nested = "((((((see note. And more.)) Then it ended. Done.";
+        assert_eq!(process(nested, None, 1024), (vec![], vec![]));
+        for chunk_size in [1, 5, 1024] {
+            assert_eq!(
+                process(nested, Some(DepthOverflow::Ignore), chunk_size),
+                (vec![42, 48], exceeded.clone())
+            );
+        }
+        assert_eq!(
+            process(nested, Some(DepthOverflow::Warn), 1024),
+            (vec![], exceeded.clone())
+        );
+
+        // Or the overflowing type is closed at the next blank line
+        let unclosed = "(((( list of items.\n\nNext one. Last.";
+        assert_eq!(
+            process(unclosed, Some(DepthOverflow::Ignore), 1024),
+            (vec![], exceeded.clone())
+        );
+        for chunk_size in [1, 5, 1024] {
+            assert_eq!(
+                process(unclosed, Some(DepthOverflow::ResetAtBlankLine), chunk_size),
+                (vec![30, 36], exceeded.clone())
+            );
+        }
+
+        assert_eq!(
+            "reset".parse::<DepthOverflow>().unwrap(),
+            DepthOverflow::ResetAtBlankLine
+        );
+        assert!("drop".parse::<DepthOverflow>().is_err());
+        assert!(Config::builder()
+            .max_enclosure_depth(0, DepthOverflow::Warn)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_infer_quote_direction() {
+        let process = |text: &str, infer: bool, chunk_size: usize| {
+            let config = Config::builder()
+                .chunk_size(chunk_size)
+                .threads(Some(2))
+                .infer_quote_direction(infer)
+                .enclosure_warnings(true)
+                .build()
+                .unwrap();
+            let output = SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            let offsets: Vec<usize> = output.boundaries.iter().map(|b| b.offset).collect();
+            (offsets, output.warnings)
+        };
+
+        // A stray closing quote no longer opens a quotation
+        let stray = "It ended.\" Then it rained. She said \"go.\" He left. Done.";
+        assert_eq!(process(stray, false, 1024).0, vec![9, 40]);
+        for chunk_size in [1, 5, 1024] {
+            assert_eq!(
+                process(stray, true, chunk_size),
+                (
+                    vec![9, 26, 50, 56],
+                    vec![Warning::UnmatchedCloser { offset: 9, ch: '"' }]
+                )
+            );
+        }
+
+        // An opening quote while one is open replaces the unclosed one
+        let unclosed = "He said \"stop. It rained. She said \"go home.\" Then he left. Done.";
+        assert_eq!(process(unclosed, false, 1024).0, vec![44]);
+        assert_eq!(
+            process(unclosed, true, 7),
+            (
+                vec![59, 65],
+                vec![Warning::UnclosedEnclosure { offset: 8, ch: '"' }]
+            )
+        );
+
+        // Balanced quotes, including one after a dash, are unchanged
+        let dash = "He paused\u{2014}\"Wait. Stop.\" Then he left. Done.";
+        assert_eq!(process(dash, true, 5), process(dash, false, 5));
+        assert_eq!(process(dash, true, 5).1, vec![]);
+    }
+
+    #[test]
+    fn test_enclosure_boundaries() {
+        let text =
+            "The plan failed (we had tried twice. Nobody knew why! It was odd.) and we left. Done.";
+        let process = |boundaries: EnclosureBoundaries, chunk_size: usize| {
+            let config = Config::builder()
+                .enclosure_boundaries(')', boundaries)
+                .chunk_size(chunk_size)
+                .threads(Some(2))
+                .build()
+                .unwrap();
+            let output = SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            output
+                .boundaries
+                .iter()
+                .map(|b| b.offset)
+                .collect::<Vec<_>>()
+        };
+        for chunk_size in [1, 5, 1024] {
+            assert_eq!(
+                process(EnclosureBoundaries::Suppress, chunk_size),
+                vec![79, 85]
+            );
+            assert_eq!(
+                process(EnclosureBoundaries::Allow, chunk_size),
+                vec![36, 53, 65, 79, 85]
+            );
+            assert_eq!(
+                process(EnclosureBoundaries::StrongOnly, chunk_size),
+                vec![53, 79, 85]
+            );
+        }
+
+        // The language configuration sets the same policy per pair
+        let mut language = crate::domain::language::config::get_language_config("en")
+            .unwrap()
+            .clone();
+        for pair in &mut language.enclosures.pairs {
+            if pair.open == '(' {
+                pair.boundaries = "allow".parse().unwrap();
+            }
+        }
+        let output = SentenceProcessor::with_language_config(Config::default(), &language)
+            .unwrap()
+            .process(Input::from_text(text))
+            .unwrap();
+        let offsets: Vec<usize> = output.boundaries.iter().map(|b| b.offset).collect();
+        assert_eq!(offsets, vec![36, 53, 65, 79, 85]);
+
+        let config = Config::builder()
+            .enclosure_boundaries('|', EnclosureBoundaries::Allow)
+            .build()
+            .unwrap();
+        assert!(SentenceProcessor::with_config(config).is_err());
+    }
+
+    #[test]
+    fn test_dialogue_boundaries() {
+        let sentences = |text: &str, chunk_size: usize| {
+            let config = Config::builder()
+                .language("ja")
+                .unwrap()
+                .enclosure_boundaries('「', EnclosureBoundaries::Dialogue)
+                .chunk_size(chunk_size)
+                .threads(Some(2))
+                .build()
+                .unwrap();
+            let output = SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            let mut start = 0;
+            output
+                .boundaries
+                .iter()
+                .map(|b| {
+                    let sentence = text[start..b.offset].trim().to_string();
+                    start = b.offset;
+                    sentence
+                })
+                .collect::<Vec<_>>()
+        };
+        for chunk_size in [1, 5, 1024] {
+            // The closing bracket stays with the last sentence of each line
+            assert_eq!(
+                sentences("「はい。そうです。」\n「行こう！」「待って。」", chunk_size),
+                vec!["「はい。", "そうです。」", "「行こう！」", "「待って。」"]
+            );
+            // A quotation continued by narration stays in its sentence
+            assert_eq!(
+                sentences("彼は「はい。」と言った。次だ。", chunk_size),
+                vec!["彼は「はい。」と言った。", "次だ。"]
+            );
+        }
+
+        let mut language = crate::domain::language::config::get_language_config("en")
+            .unwrap()
+            .clone();
+        for pair in &mut language.enclosures.pairs {
+            if pair.symmetric {
+                pair.boundaries = EnclosureBoundaries::Dialogue;
+            }
+        }
+        assert!(SentenceProcessor::with_language_config(Config::default(), &language).is_err());
+    }
+
+    #[test]
+    fn test_strip_ruby() {
+        let sentences = |text: &str, strip: bool, chunk_size: usize| {
+            let config = Config::builder()
+                .language("ja")
+                .unwrap()
+                .strip_ruby(strip)
+                .chunk_size(chunk_size)
+                .threads(Some(2))
+                .build()
+                .unwrap();
+            let output = SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            let mut start = 0;
+            output
+                .boundaries
+                .iter()
+                .map(|b| {
+                    let sentence = text[start..b.offset].to_string();
+                    start = b.offset;
+                    sentence
+                })
+                .collect::<Vec<_>>()
+        };
+        let aozora =
+            "吾輩《わがはい》は猫である。｜何処《どこ》で生れたか見当がつかぬ。終わり。《おわり》";
+        let html = "彼は<ruby>明日<rp>(</rp><rt>あした</rt><rp>)</rp></ruby>来る。待とう。";
+        for chunk_size in [1, 5, 1024] {
+            // Offsets refer to the text as given: annotations stay with
+            // the text they annotate
+            assert_eq!(
+                sentences(aozora, true, chunk_size),
+                vec![
+                    "吾輩《わがはい》は猫である。",
+                    "｜何処《どこ》で生れたか見当がつかぬ。",
+                    "終わり。《おわり》"
+                ]
+            );
+            assert_eq!(
+                sentences(html, true, chunk_size),
+                vec![
+                    "彼は<ruby>明日<rp>(</rp><rt>あした</rt><rp>)</rp></ruby>来る。",
+                    "待とう。"
+                ]
+            );
+        }
+        // Without stripping, the reading is left out of its sentence
+        assert_eq!(sentences(aozora, false, 1024).last().unwrap(), "終わり。");
+    }
+
+    #[test]
+    fn test_output_fingerprint() {
+        let fingerprint = |builder: crate::api::ConfigBuilder, text: &str| {
+            let config = builder.build().unwrap();
+            SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap()
+                .fingerprint()
+        };
+        let text = "Dr. Smith arrived. He said \"Hi.\" Then he left! Really?";
+        let base = fingerprint(Config::builder(), text);
+        assert_eq!(base, fingerprint(Config::builder(), text));
+
+        // Execution settings and extra offset units do not enter it
+        for chunk_size in [1, 7] {
+            let builder = Config::builder().chunk_size(chunk_size).threads(Some(3));
+            assert_eq!(fingerprint(builder, text), base);
+        }
+        let builder = Config::builder().utf16_offsets(true).grapheme_offsets(true);
+        assert_eq!(fingerprint(builder, text), base);
+
+        // Other boundaries, or the same ones under other rules, do
+        assert_ne!(fingerprint(Config::builder(), "Dr. Smith arrived."), base);
+        let builder = Config::builder().min_sentence_chars(12);
+        assert_ne!(fingerprint(builder, text), base);
+        let builder = Config::builder().language("ja").unwrap();
+        assert_ne!(fingerprint(builder, text), base);
+    }
+
+    #[test]
+    fn test_processor_handle_reload() {
+        use crate::api::language_config::RegexPattern;
+        use std::sync::Arc;
+
+        let config = Config::builder().min_sentence_chars(4).build().unwrap();
+        let handle = ProcessorHandle::new(SentenceProcessor::with_config(config).unwrap());
+        let shared = handle.clone();
+        let before = handle.load();
+
+        // The configuration is kept, and clones see the new rules
+        let mut rules = crate::domain::language::config::get_language_config("ja")
+            .unwrap()
+            .clone();
+        handle.reload(&rules).unwrap();
+        let after = shared.load();
+        assert!(!Arc::ptr_eq(&before, &after));
+        assert_eq!(after.config().min_sentence_chars, Some(4));
+        let output = after
+            .process(Input::from_text("今日は晴れ。明日は雨。"))
+            .unwrap();
+        assert_eq!(output.boundaries.len(), 2);
+
+        // Rules that do not compile leave the processor in place
+        rules.suppression.regex_patterns.push(RegexPattern {
+            pattern: "(".to_string(),
+            description: None,
+        });
+        assert!(handle.reload(&rules).is_err());
+        assert!(Arc::ptr_eq(&handle.load(), &after));
+
+        let previous = handle.store(SentenceProcessor::new());
+        assert!(Arc::ptr_eq(&previous, &after));
+    }
+
+    #[test]
+    fn test_token_estimates_and_chunks() {
+        let text = "The cat sat. It purred loudly, then slept. 猫が寝た。";
+        let processor = SentenceProcessor::with_language("en").unwrap();
+        let output = processor.process(Input::from_text(text)).unwrap();
+        assert!(output.token_estimates.is_empty());
+
+        let config = Config::builder()
+            .token_estimator(WhitespaceEstimator)
+            .build()
+            .unwrap();
+        let processor = SentenceProcessor::with_config(config).unwrap();
+        let output = processor.process(Input::from_text(text)).unwrap();
+        assert_eq!(output.token_estimates.len(), output.boundaries.len() + 1);
+        assert_eq!(output.token_estimates[..2], [3, 5]);
+
+        // Tokens of a sentence split by the boundaries add up to the whole
+        let config = Config::builder()
+            .token_estimator(BpeEstimator)
+            .build()
+            .unwrap();
+        let processor = SentenceProcessor::with_config(config).unwrap();
+        let output = processor.process(Input::from_text(text)).unwrap();
+        let total: usize = output.token_estimates.iter().sum();
+        assert_eq!(total, BpeEstimator.estimate(text));
+
+        assert!(chunk_sentences(&[], 10).is_empty());
+        assert_eq!(chunk_sentences(&[5, 5, 0, 5], 10), [0..3, 3..4]);
+        assert_eq!(chunk_sentences(&[20, 0, 1], 10), [0..2, 2..3]);
+    }
+
+    #[test]
+    fn test_output_windows() {
+        let text = "One. Two. Three. Four. Five";
+        let output = SentenceProcessor::new()
+            .process(Input::from_text(text))
+            .unwrap();
+        let spans = |size, stride| {
+            output
+                .windows(size, stride)
+                .map(|w| (w.sentences, &text[w.start..w.end]))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            spans(2, 1),
+            [
+                (0..2, "One. Two."),
+                (1..3, " Two. Three."),
+                (2..4, " Three. Four."),
+                (3..5, " Four. Five"),
+            ]
+        );
+        // The last window may be short; a wide stride skips sentences
+        assert_eq!(spans(2, 2)[2], (4..5, " Five"));
+        assert_eq!(spans(1, 3), [(0..1, "One."), (3..4, " Four.")]);
+        assert_eq!(spans(10, 1), [(0..5, text)]);
+
+        let window = output.windows(2, 2).nth(1).unwrap();
+        assert_eq!((window.char_start, window.char_end), (9, 22));
+
+        // Text ending on a boundary has no trailing sentence; empty text
+        // has no sentences
+        let output = SentenceProcessor::new()
+            .process(Input::from_text("One. Two."))
+            .unwrap();
+        assert_eq!(output.windows(1, 1).count(), 2);
+        let output = SentenceProcessor::new()
+            .process(Input::from_text(""))
+            .unwrap();
+        assert_eq!(output.windows(1, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_dedupe_marks_repeated_sentences() {
+        let text = "Click here. Read more. click  HERE. Read more.\nDone";
+        let processor = SentenceProcessor::with_language("en").unwrap();
+        let output = processor.process(Input::from_text(text)).unwrap();
+        assert!(output.duplicates.is_empty());
+        assert_eq!(output.metadata.stats.duplicate_sentences, 0);
+
+        let config = Config::builder().dedupe(true).build().unwrap();
+        let processor = SentenceProcessor::with_config(config).unwrap();
+        let deduped = processor.process(Input::from_text(text)).unwrap();
+        assert_eq!(deduped.duplicates, [2, 3]);
+        assert_eq!(deduped.metadata.stats.duplicate_sentences, 2);
+        // Boundaries are unchanged
+        let offsets = |output: &Output| {
+            output
+                .boundaries
+                .iter()
+                .map(|b| b.offset)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(offsets(&deduped), offsets(&output));
+
+        // A batch is deduplicated across its documents
+        let outputs = processor
+            .process_batch(&["One. Two.", "Two. Three.", "one."])
+            .unwrap();
+        let duplicates: Vec<_> = outputs.iter().map(|o| o.duplicates.clone()).collect();
+        assert_eq!(duplicates, [vec![], vec![0], vec![0]]);
+    }
+
+    #[test]
+    fn test_apostrophes_do_not_open_quotes() {
+        let text = "We were goin' home in the '90s. The students' bus played rock 'n' roll. \
+                    They sang 'tis the season. Done.";
+        for chunk_size in [1, 7, 1024] {
+            let config = Config::builder()
+                .chunk_size(chunk_size)
+                .threads(Some(2))
+                .enclosure_warnings(true)
+                .build()
+                .unwrap();
+            let output = SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            let offsets: Vec<usize> = output.boundaries.iter().map(|b| b.offset).collect();
+            assert_eq!(offsets, vec![31, 71, 98, 104], "chunk size {chunk_size}");
+            assert_eq!(output.warnings, vec![]);
+        }
+    }
+
+    #[test]
+    fn test_corrections_file() {
+        let corrections: Corrections = r#"
+            [[correction]]
+            document = "a.txt"
+            offset = 30
+            action = "reject"
+
+            [[correction]]
+            document = "a.txt"
+            offset = 12
+            action = "accept"
+
+            [[correction]]
+            document = "b.txt"
+            offset = 4
+            action = "reject"
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(corrections.len(), 3);
+        assert_eq!(
+            corrections.for_document("a.txt"),
+            [Correction::accept(12), Correction::reject(30)]
+        );
+        assert!(corrections.for_document("c.txt").is_empty());
+
+        let conflicting = r#"
+            [[correction]]
+            document = "a.txt"
+            offset = 12
+            action = "accept"
+
+            [[correction]]
+            document = "a.txt"
+            offset = 12
+            action = "reject"
+        "#
+        .parse::<Corrections>();
+        assert!(matches!(conflicting, Err(Error::Configuration(msg)) if msg.contains("offset 12")));
+        let unknown_action = "[[correction]]\ndocument = \"a\"\noffset = 1\naction = \"skip\"";
+        assert!(unknown_action.parse::<Corrections>().is_err());
+    }
+
+    #[test]
+    fn test_process_with_progress() {
+        let text = "First sentence here. Second one follows. ".repeat(200);
+        let config = Config::builder()
+            .chunk_size(512)
+            .threads(Some(4))
+            .build()
+            .unwrap();
+        let processor = SentenceProcessor::with_config(config).unwrap();
+
+        let reports = std::sync::Mutex::new(Vec::new());
+        let output = processor
+            .process_with_progress(Input::from_text(text.clone()), |progress| {
+                reports.lock().unwrap().push(progress)
+            })
+            .unwrap();
+        let mut reports = reports.into_inner().unwrap();
+        assert_eq!(output.boundaries.len(), 400);
+        assert!(reports.len() > 1);
+        assert!(reports.iter().all(|p| p.total_bytes == text.len()));
+        reports.sort_by_key(|p| p.processed_bytes);
+        assert_eq!(reports.last().unwrap().processed_bytes, text.len());
+        // Each chunk is counted once
+        reports.dedup();
+        assert_eq!(reports.len(), output.metadata.chunks_processed);
+    }
+
+    #[test]
+    fn test_sentences_from_reader() {
+        /// Returns at most 5 bytes per read, splitting UTF-8 sequences
+        struct Trickle<'a>(&'a [u8]);
+
+        impl std::io::Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let n = buf.len().min(5).min(self.0.len());
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        let text = "Dr. Smith arrived. 「こんにちは。」と言った。 Then he left. Trailing".repeat(3);
+        let processor = SentenceProcessor::new();
+        let expected: Vec<usize> = processor
+            .process(Input::from_text(text.as_str()))
+            .unwrap()
+            .boundaries
+            .iter()
+            .map(|b| b.offset)
+            .collect();
+
+        let spans: Vec<SentenceSpan> = processor
+            .sentences_from_reader(Trickle(text.as_bytes()))
+            .collect::<Result<_>>()
+            .unwrap();
+        let ends: Vec<usize> = spans.iter().map(|s| s.end).collect();
+        assert_eq!(ends[..ends.len() - 1], expected[..]);
+        assert_eq!(ends.last(), Some(&text.len()));
+        assert_eq!(
+            spans.iter().map(|s| s.text.as_str()).collect::<String>(),
+            text
+        );
+
+        // Invalid UTF-8 follows the configured policy
+        let bytes = b"Bad \xff byte. Fine.";
+        let mut errors = processor.sentences_from_reader(&bytes[..]);
+        assert!(matches!(errors.next(), Some(Err(Error::Infrastructure(_)))));
+        assert!(errors.next().is_none());
+
+        let config = Config::builder()
+            .invalid_utf8(InvalidUtf8::Replace)
+            .build()
+            .unwrap();
+        let lossy = SentenceProcessor::with_config(config).unwrap();
+        let texts: Vec<String> = lossy
+            .sentences_from_reader(&bytes[..])
+            .map(|span| span.unwrap().text)
+            .collect();
+        assert_eq!(texts, ["Bad \u{fffd} byte.", " Fine."]);
+    }
+
+    #[test]
+    fn test_read_ahead_matches_whole_file_reads() {
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+        let text = [
+            "「こんにちは。」と彼は言った。Dr. Smith left! Bad ".as_bytes(),
+            b"\xff byte. End",
+        ]
+        .concat();
+        let bytes = text.repeat(20);
+        std::io::Write::write_all(&mut temp_file, &bytes).unwrap();
+
+        for (threads, invalid_utf8) in [(1, InvalidUtf8::Replace), (4, InvalidUtf8::Skip)] {
+            let builder = || {
+                Config::builder()
+                    .language("ja")
+                    .unwrap()
+                    .chunk_size(16)
+                    .threads(Some(threads))
+                    .invalid_utf8(invalid_utf8)
+            };
+            let whole = SentenceProcessor::with_config(builder().build().unwrap())
+                .unwrap()
+                .process(Input::from_file(temp_file.path()))
+                .unwrap();
+            let ahead = SentenceProcessor::with_config(builder().read_ahead(true).build().unwrap())
+                .unwrap()
+                .process(Input::from_file(temp_file.path()))
+                .unwrap();
+            let offsets = |output: &Output| -> Vec<(usize, usize, BoundaryKind)> {
+                output
+                    .boundaries
+                    .iter()
+                    .map(|b| (b.offset, b.char_offset, b.kind))
+                    .collect()
+            };
+            assert_eq!(offsets(&ahead), offsets(&whole));
+            assert!(!ahead.boundaries.is_empty());
+            assert_eq!(ahead.metadata.stats.invalid_utf8_bytes, 20);
+            assert_eq!(ahead.warnings, whole.warnings);
+        }
+
+        // Invalid UTF-8 is still an error by default
+        let config = Config::builder().read_ahead(true).build().unwrap();
+        let result = SentenceProcessor::with_config(config)
+            .unwrap()
+            .process(Input::from_file(temp_file.path()));
+        assert!(matches!(result, Err(Error::Infrastructure(_))));
+
+        // ...reported at its offset in the file, not in the block holding it
+        let builder = || Config::builder().language("ja").unwrap().chunk_size(16);
+        let error = |config: Config| {
+            SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_file(temp_file.path()))
+                .unwrap_err()
+                .to_string()
+        };
+        let whole = error(builder().build().unwrap());
+        assert!(whole.contains(" from index 65"), "{whole}");
+        assert_eq!(error(builder().read_ahead(true).build().unwrap()), whole);
+    }
+
+    #[test]
+    fn test_chunk_cache_is_shared_across_rules() {
+        let cache = ChunkCache::in_memory(100);
+        let mut misses = Vec::new();
+        let text = "Dr. Smith arrived. 「こんにちは。」と言った。 Then he left.".repeat(4);
+        for language in ["en", "ja", "en"] {
+            let builder = || Config::builder().language(language).unwrap().chunk_size(32);
+            let expected = SentenceProcessor::with_config(builder().build().unwrap())
+                .unwrap()
+                .process(Input::from_text(text.as_str()))
+                .unwrap();
+            let cached = builder().chunk_cache(cache.clone()).build().unwrap();
+            let output = SentenceProcessor::with_config(cached)
+                .unwrap()
+                .process(Input::from_text(text.as_str()))
+                .unwrap();
+            let offsets = |output: &Output| -> Vec<usize> {
+                output.boundaries.iter().map(|b| b.offset).collect()
+            };
+            assert_eq!(offsets(&output), offsets(&expected), "{language}");
+            misses.push(cache.stats().misses);
+        }
+        // The chunks of the second English run were all cached
+        assert!(misses[0] > 0 && misses[1] > misses[0], "{misses:?}");
+        assert_eq!(misses[2], misses[1]);
+    }
+
+    #[test]
+    fn test_boundary_kinds() {
+        let text = "Stop! It is 3.5 m long. Really?! Fine";
+        for threads in [1, 4] {
+            let config = Config::builder()
+                .chunk_size(8)
+                .threads(Some(threads))
+                .build()
+                .unwrap();
+            let output = SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            let kinds: Vec<(usize, BoundaryKind)> = output
+                .boundaries
+                .iter()
+                .map(|b| (b.offset, b.kind))
+                .collect();
+            assert_eq!(
+                kinds,
+                [
+                    (5, BoundaryKind::Strong),
+                    (23, BoundaryKind::Weak),
+                    (32, BoundaryKind::Strong)
+                ]
+            );
+        }
+        assert_eq!(BoundaryKind::EndOfText.to_string(), "end_of_text");
+    }
+
+    #[test]
+    fn test_verify_determinism() {
+        let processor = SentenceProcessor::with_language("ja").unwrap();
+        processor
+            .verify_determinism("彼は「はい。」と言った。本当？Dr. X", 32)
+            .unwrap();
+
+        let violation = crate::api::DeterminismViolation {
+            run: "parallel, chunk size 3, 4 threads".into(),
+            expected: vec![5, 12],
+            actual: vec![5, 11, 12],
+        };
+        assert_eq!(violation.first_difference(), Some(11));
+        assert!(violation
+            .to_string()
+            .starts_with("parallel, chunk size 3, 4 threads: boundaries differ from sequential processing at byte 11"));
+        let missing = crate::api::DeterminismViolation {
+            actual: vec![5],
+            ..violation
+        };
+        assert_eq!(missing.first_difference(), Some(12));
+    }
+
+    #[test]
+    fn test_adaptive_threshold_and_max_threads() {
+        let text = "One sentence here. ".repeat(4096);
+        let config = Config::builder()
+            .adaptive_threshold(1024)
+            .max_threads(1)
+            .build()
+            .unwrap();
+        let output = SentenceProcessor::with_config(config)
+            .unwrap()
+            .process(Input::from_text(text.as_str()))
+            .unwrap();
+        assert_eq!(output.metadata.strategy_used, "sequential");
+        assert_eq!(output.boundaries.len(), 4096);
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn test_nfkc_normalization_offsets() {
+        let text = "Really！ Yes.";
+        let plain = SentenceProcessor::new()
+            .process(Input::from_text(text))
+            .unwrap();
+        assert_eq!(plain.boundaries.len(), 1);
+
+        let process = |space| {
+            let config = Config::builder()
+                .normalization(Normalization::Nfkc)
+                .offset_space(space)
+                .build()
+                .unwrap();
+            let output = SentenceProcessor::with_config(config)
+                .unwrap()
+                .process(Input::from_text(text))
+                .unwrap();
+            output
+                .boundaries
+                .iter()
+                .map(|b| b.offset)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            process(OffsetSpace::Original),
+            vec!["Really！".len(), text.len()]
+        );
+        assert_eq!(
+            process(OffsetSpace::Normalized),
+            vec!["Really!".len(), "Really! Yes.".len()]
+        );
+    }
+
     #[test]
     fn test_config_defaults() {
         let default_config = Config::default();
@@ -123,6 +1441,47 @@ mod api_tests {
         assert_eq!(output.boundaries[1].char_offset, 9); // After "世界。"
     }
 
+    #[test]
+    fn test_utf16_and_grapheme_offsets_are_opt_in() {
+        let text = "🇯🇵 Cafe\u{301} opens. 😀 Bye.";
+
+        let output = SentenceProcessor::new()
+            .process(Input::from_text(text))
+            .unwrap();
+        assert!(output.boundaries[0].utf16_offset.is_none());
+        assert!(output.boundaries[0].grapheme_offset.is_none());
+
+        let config = Config::builder()
+            .utf16_offsets(true)
+            .grapheme_offsets(true)
+            .build()
+            .unwrap();
+        let processor = SentenceProcessor::with_config(config).unwrap();
+        let output = processor.process(Input::from_text(text)).unwrap();
+        let first = &output.boundaries[0];
+        let prefix = &text[..first.offset];
+        assert_eq!(first.char_offset, 15);
+        assert_eq!(first.utf16_offset, Some(prefix.encode_utf16().count()));
+        assert_eq!(first.utf16_offset, Some(17));
+        assert_eq!(first.grapheme_offset, Some(13));
+
+        // Streaming reports the same offsets.
+        let mut stream = processor.stream();
+        let mut streamed = stream.feed(&text[..10]);
+        streamed.extend(stream.feed(&text[10..]));
+        streamed.extend(stream.finish());
+        let batch: Vec<_> = output
+            .boundaries
+            .iter()
+            .map(|b| (b.offset, b.utf16_offset, b.grapheme_offset))
+            .collect();
+        let streamed: Vec<_> = streamed
+            .iter()
+            .map(|b| (b.offset, b.utf16_offset, b.grapheme_offset))
+            .collect();
+        assert_eq!(streamed, batch);
+    }
+
     #[test]
     fn test_config_builder() {
         let config = Config::builder()
@@ -150,6 +1509,7 @@ mod api_tests {
     }
 
     #[test]
+    #[allow(clippy::io_other_error)]
     fn test_process_stream_error_handling() {
         use std::io::{self, Read};
 
@@ -230,6 +1590,76 @@ mod api_tests {
             adaptive_result.boundaries.len()
         );
     }
+
+    #[test]
+    fn test_process_batch_matches_process() {
+        let texts = [
+            "One. Two.",
+            "",
+            "Dr. Smith arrived. He sat down.",
+            "No terminator",
+        ];
+        let processor = SentenceProcessor::new();
+
+        let outputs = processor.process_batch(&texts).unwrap();
+        assert_eq!(outputs.len(), texts.len());
+        for (text, output) in texts.iter().zip(&outputs) {
+            let single = processor.process(Input::from_text(*text)).unwrap();
+            let offsets: Vec<_> = output.boundaries.iter().map(|b| b.offset).collect();
+            let expected: Vec<_> = single.boundaries.iter().map(|b| b.offset).collect();
+            assert_eq!(offsets, expected, "{text:?}");
+        }
+        assert!(processor.process_batch::<&str>(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resegment_matches_process() {
+        let processor = SentenceProcessor::new();
+        let mut text =
+            IncrementalText::new("「こんにちは。」と言った。 Dr. Smith left. ".repeat(400));
+        processor.resegment(&mut text, 0..0, "").unwrap();
+        let edits = [
+            (0..0, "Start here. "),
+            (9000..9000, "(an aside. "),
+            (15000..15012, ""),
+            (20000..20000, ") Closed! "),
+            (5..5, "「"),
+        ];
+        for (range, replacement) in edits {
+            let at = |i: usize| (i..).find(|&i| text.text().is_char_boundary(i)).unwrap();
+            let range = at(range.start)..at(range.end);
+            let output = processor.resegment(&mut text, range, replacement).unwrap();
+            let expected = processor.process(Input::from_text(text.text())).unwrap();
+            let offsets = |output: &Output| -> Vec<(usize, usize, BoundaryKind)> {
+                output
+                    .boundaries
+                    .iter()
+                    .map(|b| (b.offset, b.char_offset, b.kind))
+                    .collect()
+            };
+            assert_eq!(offsets(&output), offsets(&expected));
+        }
+    }
+
+    #[test]
+    fn test_resegment_rejects_bad_ranges_and_configs() {
+        let processor = SentenceProcessor::new();
+        let mut text = IncrementalText::new("日本語. Text.");
+        for range in [1..1, 3..5, 0..100] {
+            assert!(matches!(
+                processor.resegment(&mut text, range, "x"),
+                Err(Error::InvalidInput(_))
+            ));
+        }
+        assert_eq!(text.text(), "日本語. Text.");
+
+        let config = Config::builder().strip_ruby(true).build().unwrap();
+        let processor = SentenceProcessor::with_config(config).unwrap();
+        assert!(matches!(
+            processor.resegment(&mut text, 0..0, ""),
+            Err(Error::Unsupported(_))
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -917,8 +2347,6 @@ mod language_tests {
                 "zh",
                 "ko",
                 "ru",
-                "ar",
-                "hi",
                 "123",
                 "!@#",
                 "english-US", // Not exact match
@@ -1058,6 +2486,11 @@ mod language_tests {
                 match lang {
                     Language::English => assert_eq!(lang.code(), "en"),
                     Language::Japanese => assert_eq!(lang.code(), "ja"),
+                    Language::Portuguese => assert_eq!(lang.code(), "pt"),
+                    Language::Italian => assert_eq!(lang.code(), "it"),
+                    Language::Arabic => assert_eq!(lang.code(), "ar"),
+                    Language::Thai => assert_eq!(lang.code(), "th"),
+                    Language::Hindi => assert_eq!(lang.code(), "hi"),
                 }
             }
         }
@@ -1123,6 +2556,11 @@ mod language_tests {
             let result = match lang {
                 Language::English => "en",
                 Language::Japanese => "ja",
+                Language::Portuguese => "pt",
+                Language::Italian => "it",
+                Language::Arabic => "ar",
+                Language::Thai => "th",
+                Language::Hindi => "hi",
             };
 
             assert_eq!(result, "en");
@@ -1132,6 +2570,11 @@ mod language_tests {
                 match lang {
                     Language::English => "Hello",
                     Language::Japanese => "こんにちは",
+                    Language::Portuguese => "Olá",
+                    Language::Italian => "Ciao",
+                    Language::Arabic => "مرحبا",
+                    Language::Thai => "สวัสดี",
+                    Language::Hindi => "नमस्ते",
                 }
             }
 
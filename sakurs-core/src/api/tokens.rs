@@ -0,0 +1,156 @@
+//! Approximate token counts per sentence, for packing sentences into the
+//! context of a language model
+//!
+//! Estimates avoid running a real tokenizer over every sentence: they are
+//! meant for sizing chunks with some headroom, not for billing.
+
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::api::Boundary;
+
+/// Estimates how many tokens a model's tokenizer splits a text into
+///
+/// [`WhitespaceEstimator`] and [`BpeEstimator`] are built in; closures
+/// taking the text implement the trait, so a real tokenizer can be plugged
+/// in with [`ConfigBuilder::token_estimator`](crate::ConfigBuilder::token_estimator).
+///
+/// ```rust
+/// use sakurs_core::{Config, Input, SentenceProcessor};
+///
+/// // Count characters, as some embedding models' limits do
+/// let config = Config::builder()
+///     .token_estimator(|text: &str| text.trim().chars().count())
+///     .build()
+///     .unwrap();
+/// let processor = SentenceProcessor::with_config(config).unwrap();
+/// let output = processor.process(Input::from_text("Hi. Bye now.")).unwrap();
+/// assert_eq!(output.token_estimates, [3, 8, 0]);
+/// ```
+pub trait TokenEstimator: Send + Sync {
+    /// The approximate number of tokens in `text`
+    fn estimate(&self, text: &str) -> usize;
+}
+
+impl<F> TokenEstimator for F
+where
+    F: Fn(&str) -> usize + Send + Sync,
+{
+    fn estimate(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// One token per whitespace-separated word
+///
+/// Cheap and predictable, but it undercounts: tokenizers split long and
+/// rare words and punctuation, and text written without spaces (Japanese,
+/// Thai) counts as a single word per run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WhitespaceEstimator;
+
+impl TokenEstimator for WhitespaceEstimator {
+    fn estimate(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+}
+
+/// An approximation of byte-pair-encoding tokenizers such as those of GPT
+/// models: a run of letters and digits counts one token per four bytes of
+/// UTF-8 (rounded up), and every other character but whitespace counts one
+///
+/// English prose comes out close to the usual four characters per token;
+/// a kanji or kana counts three quarters of a token.
+///
+/// ```rust
+/// use sakurs_core::{BpeEstimator, TokenEstimator};
+///
+/// assert_eq!(BpeEstimator.estimate("Hello, world!"), 6);
+/// assert_eq!(BpeEstimator.estimate("   "), 0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BpeEstimator;
+
+impl TokenEstimator for BpeEstimator {
+    fn estimate(&self, text: &str) -> usize {
+        let mut tokens = 0;
+        let mut word_bytes = 0;
+        for c in text.chars() {
+            if c.is_alphanumeric() {
+                word_bytes += c.len_utf8();
+                continue;
+            }
+            tokens += word_bytes.div_ceil(4);
+            word_bytes = 0;
+            if !c.is_whitespace() {
+                tokens += 1;
+            }
+        }
+        tokens + word_bytes.div_ceil(4)
+    }
+}
+
+/// The estimator registered on a configuration
+#[derive(Clone)]
+pub(crate) struct SharedEstimator(Arc<dyn TokenEstimator>);
+
+impl SharedEstimator {
+    pub(crate) fn new(estimator: impl TokenEstimator + 'static) -> Self {
+        Self(Arc::new(estimator))
+    }
+
+    /// The estimate of each sentence of `text`, which end at the
+    /// `boundaries` and at the end of the text
+    pub(crate) fn sentences(&self, text: &str, boundaries: &[Boundary]) -> Vec<usize> {
+        let ends = boundaries
+            .iter()
+            .map(|boundary| boundary.offset)
+            .chain(std::iter::once(text.len()));
+        let mut start = 0;
+        ends.map(|end| {
+            let estimate = self.0.estimate(&text[start..end]);
+            start = end;
+            estimate
+        })
+        .collect()
+    }
+}
+
+impl fmt::Debug for SharedEstimator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedEstimator")
+    }
+}
+
+/// Group consecutive sentences into chunks of at most `max_tokens` tokens,
+/// given the token count of each sentence; returns the range of sentence
+/// indexes of each chunk
+///
+/// Sentences are added to the current chunk while they fit and start a new
+/// chunk otherwise, so chunks keep sentence order and never split a
+/// sentence. A sentence longer than `max_tokens` by itself is a chunk of
+/// its own. Sentences of zero tokens join the chunk before them.
+///
+/// ```rust
+/// use sakurs_core::chunk_sentences;
+///
+/// assert_eq!(chunk_sentences(&[3, 4, 2, 9, 1], 8), [0..2, 2..3, 3..4, 4..5]);
+/// ```
+pub fn chunk_sentences(token_counts: &[usize], max_tokens: usize) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0;
+    for (index, &count) in token_counts.iter().enumerate() {
+        if index > start && count > 0 && tokens + count > max_tokens {
+            chunks.push(start..index);
+            start = index;
+            tokens = 0;
+        }
+        tokens += count;
+    }
+    if start < token_counts.len() {
+        chunks.push(start..token_counts.len());
+    }
+    chunks
+}
@@ -0,0 +1,304 @@
+//! Enclosure balance: a sequential pass that reports quotes and brackets
+//! left unbalanced and, on request, closes the ones still open at the end of
+//! a paragraph, caps how deep they nest or infers which way a straight quote
+//! faces
+//!
+//! The Δ-Stack engine carries enclosure depth across the whole text, so one
+//! unclosed quote early in a document keeps every later candidate inside an
+//! enclosure. Closing dangling enclosures at blank lines bounds the damage to
+//! one paragraph, a depth cap keeps pathological input (thousands of
+//! unmatched `(`) from burying the rest of the document, and a straight
+//! quote that plainly opens or closes resynchronizes a quote type the engine
+//! can only toggle. The pass sees the whole text at once and judges every
+//! candidate on the same ±k window as the engine, so its result does not
+//! depend on chunking.
+
+use crate::domain::state::{
+    window_around, CompiledRules, EnclosureSlot, Judge, Judgment, TerminatorKind, WINDOW_CHARS,
+};
+use crate::domain::types::{DepthVec, Warning};
+use serde::{Deserialize, Serialize};
+
+use super::delta_stack::DeltaStackResult;
+
+/// What happens when an opener would nest an enclosure type deeper than
+/// the cap set with
+/// [`ConfigBuilder::max_enclosure_depth`](crate::ConfigBuilder::max_enclosure_depth)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepthOverflow {
+    /// Openers beyond the cap are not counted, so the matching closers
+    /// bring the depth back to zero early (the default)
+    #[default]
+    Ignore,
+    /// Openers beyond the cap are counted, and the enclosure type is closed
+    /// at the next blank line
+    ResetAtBlankLine,
+    /// Nothing changes; only the warning is reported
+    Warn,
+}
+
+/// A cap on the nesting depth of each asymmetric enclosure type (symmetric
+/// quotes never nest)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DepthCap {
+    pub max_depth: usize,
+    pub overflow: DepthOverflow,
+}
+
+/// What the balance pass may change besides reporting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct BalanceOptions {
+    /// Close every enclosure still open at a blank line
+    pub close_at_paragraphs: bool,
+    pub depth_cap: Option<DepthCap>,
+    /// Judge symmetric quotes by their neighbours (see [`facing`])
+    pub infer_quote_direction: bool,
+}
+
+/// Which way a symmetric quote faces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Facing {
+    Open,
+    Close,
+}
+
+/// Characters after which a quote begins a quotation: openers and dashes
+const OPENING_CONTEXT: &[char] = &[
+    '(', '[', '{', '<', '«', '‹', '“', '‘', '„', '‚', '-', '‐', '–', '—', '―', '/',
+];
+
+/// Characters before which a quote ends a quotation: closers, dashes and
+/// punctuation
+const CLOSING_CONTEXT: &[char] = &[
+    ')', ']', '}', '>', '»', '›', '”', '’', '-', '‐', '–', '—', '―', '.', ',', ';', ':', '!', '?',
+    '…',
+];
+
+/// Which way a quote between `before` and `after` faces, if its neighbours
+/// tell: one after whitespace, an opener or a dash and before a word opens
+/// (`—"Stop`), one after a word and before whitespace or punctuation closes
+/// (`stop",`); `None` at the start or end of text counts as whitespace.
+fn facing(before: Option<char>, after: Option<char>) -> Option<Facing> {
+    let opens_after = |c: char| c.is_whitespace() || OPENING_CONTEXT.contains(&c);
+    let closes_before = |c: char| c.is_whitespace() || CLOSING_CONTEXT.contains(&c);
+    if before.map_or(true, opens_after) && after.is_some_and(|c| !closes_before(c)) {
+        Some(Facing::Open)
+    } else if before.is_some_and(|c| !opens_after(c)) && after.map_or(true, closes_before) {
+        Some(Facing::Close)
+    } else {
+        None
+    }
+}
+
+/// Open enclosures and depth/parity at one point of the pass
+struct Enclosures {
+    depths: DepthVec,
+    parity: u32,
+    /// Opener offsets and characters per asymmetric type, innermost last
+    openers: Vec<Vec<(usize, char)>>,
+    /// The opening occurrence per symmetric type while its parity is odd
+    toggles: [Option<(usize, char)>; 32],
+    /// Asymmetric types past the cap since they last came back within it
+    overflowed: Vec<bool>,
+}
+
+impl Enclosures {
+    fn new(rules: &CompiledRules) -> Self {
+        let types = rules.asym_type_count();
+        Self {
+            depths: DepthVec::from_elem(0, types),
+            parity: 0,
+            openers: vec![Vec::new(); types],
+            toggles: [None; 32],
+            overflowed: vec![false; types],
+        }
+    }
+
+    /// Applies one enclosure character, returning false if the depth cap or
+    /// the quote's facing made it count differently from the engine
+    fn apply(
+        &mut self,
+        slot: EnclosureSlot,
+        offset: usize,
+        ch: char,
+        cap: Option<DepthCap>,
+        facing: Option<Facing>,
+        warnings: &mut Vec<Warning>,
+    ) -> bool {
+        match slot {
+            EnclosureSlot::Asym { index, delta } => {
+                let i = index as usize;
+                if delta > 0 {
+                    if let Some(cap) = cap.filter(|cap| self.depths[i] >= cap.max_depth as i32) {
+                        // One warning per excursion past the cap
+                        if !self.overflowed[i] {
+                            self.overflowed[i] = true;
+                            warnings.push(Warning::DepthExceeded { offset, ch });
+                        }
+                        if cap.overflow == DepthOverflow::Ignore {
+                            return false;
+                        }
+                    }
+                    self.openers[i].push((offset, ch));
+                } else if self.openers[i].pop().is_none() {
+                    warnings.push(Warning::UnmatchedCloser { offset, ch });
+                }
+                self.depths[i] += i32::from(delta);
+                if cap.is_some_and(|cap| self.depths[i] < cap.max_depth as i32) {
+                    self.overflowed[i] = false;
+                }
+            }
+            EnclosureSlot::Sym { bit } => {
+                let open = &mut self.toggles[bit as usize];
+                match (facing, *open) {
+                    // An opening quote while one is open: the earlier one
+                    // was never closed, and this one takes its place
+                    (Some(Facing::Open), Some((earlier, earlier_ch))) => {
+                        warnings.push(Warning::UnclosedEnclosure {
+                            offset: earlier,
+                            ch: earlier_ch,
+                        });
+                        *open = Some((offset, ch));
+                        return false;
+                    }
+                    (Some(Facing::Close), None) => {
+                        warnings.push(Warning::UnmatchedCloser { offset, ch });
+                        return false;
+                    }
+                    (_, Some(_)) => *open = None,
+                    (_, None) => *open = Some((offset, ch)),
+                }
+                self.parity ^= 1 << bit;
+            }
+        }
+        true
+    }
+
+    /// Whether any enclosure is open here
+    fn inside(&self) -> bool {
+        self.parity != 0 || self.depths.iter().any(|&d| d > 0)
+    }
+
+    /// Reports the open enclosures of asymmetric type `i` and closes them
+    fn close_type(&mut self, i: usize, warnings: &mut Vec<Warning>) {
+        warnings.extend(
+            self.openers[i]
+                .drain(..)
+                .map(|(offset, ch)| Warning::UnclosedEnclosure { offset, ch }),
+        );
+        self.depths[i] = 0;
+        self.overflowed[i] = false;
+    }
+
+    /// Reports every open enclosure and starts over at depth zero
+    fn close_all(&mut self, warnings: &mut Vec<Warning>) {
+        for i in 0..self.openers.len() {
+            self.close_type(i, warnings);
+        }
+        warnings.extend(
+            self.toggles
+                .iter_mut()
+                .filter_map(Option::take)
+                .map(|(offset, ch)| Warning::UnclosedEnclosure { offset, ch }),
+        );
+        self.parity = 0;
+    }
+}
+
+/// Reports the unbalanced enclosures of `text`, sorted by offset.
+///
+/// With `options.close_at_paragraphs`, enclosures still open at a paragraph
+/// end (a blank line or U+2029) are reported and closed there; with a depth
+/// cap, openers past it are handled by its [`DepthOverflow`]; with
+/// `options.infer_quote_direction`, a symmetric quote that faces the way its
+/// type is already open is reported instead of toggling it. The
+/// boundaries in `result` from the first point where either changes the
+/// depth on are recomputed; otherwise `result` is left unchanged.
+pub(crate) fn check(
+    rules: &CompiledRules,
+    text: &str,
+    options: BalanceOptions,
+    result: &mut DeltaStackResult,
+) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let mut open = Enclosures::new(rules);
+    let reset_overflow = options
+        .depth_cap
+        .is_some_and(|cap| cap.overflow == DepthOverflow::ResetAtBlankLine);
+    // Offset of the first change to the engine's depths; candidates after it
+    // are judged here instead of taken from the engine
+    let mut repaired_from: Option<usize> = None;
+    let mut boundaries = Vec::new();
+    let mut strong = Vec::new();
+    // Line breaks since the last non-whitespace character
+    let mut breaks = 0;
+
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '\n' => breaks += 1,
+            '\u{2029}' => breaks += 2,
+            c if c.is_whitespace() => {}
+            _ => breaks = 0,
+        }
+        if breaks >= 2 {
+            if options.close_at_paragraphs && open.inside() {
+                open.close_all(&mut warnings);
+                repaired_from.get_or_insert(i);
+            } else if reset_overflow && open.overflowed.contains(&true) {
+                for t in 0..open.overflowed.len() {
+                    if open.overflowed[t] {
+                        open.close_type(t, &mut warnings);
+                    }
+                }
+                repaired_from.get_or_insert(i);
+            }
+        }
+
+        let class = rules.classify(ch);
+        if let Some(enc) = class.enclosure {
+            let real = !enc.suppressible || {
+                let (window, pos) = window_around(text, i, WINDOW_CHARS);
+                !rules.suppress_enclosure(window, pos, ch)
+            };
+            let facing = match enc.slot {
+                EnclosureSlot::Sym { .. } if options.infer_quote_direction => facing(
+                    text[..i].chars().next_back(),
+                    text[i + ch.len_utf8()..].chars().next(),
+                ),
+                _ => None,
+            };
+            if real && !open.apply(enc.slot, i, ch, options.depth_cap, facing, &mut warnings) {
+                repaired_from.get_or_insert(i);
+            }
+        }
+
+        if class.terminator && repaired_from.is_some() {
+            let offset = i + ch.len_utf8();
+            let (window, pos) = window_around(text, offset, WINDOW_CHARS);
+            if let Judgment::Boundary(flags) = rules.judge(window, pos, TerminatorKind::Char(ch)) {
+                // The engine's test (see `delta_stack::is_boundary`)
+                if rules.permits_boundary(&open.depths, open.parity, flags.is_strong) {
+                    boundaries.push(offset);
+                    if flags.is_strong {
+                        strong.push(offset);
+                    }
+                }
+            }
+        }
+    }
+    open.close_all(&mut warnings);
+    warnings.sort_by_key(Warning::offset);
+
+    if let Some(from) = repaired_from {
+        // Every boundary up to the first change is the engine's; the pass
+        // judged only terminators past it
+        let keep = result.boundaries.partition_point(|&b| b <= from);
+        result.boundaries.truncate(keep);
+        result.boundaries.extend(boundaries);
+        let keep = result.strong.partition_point(|&b| b <= from);
+        result.strong.truncate(keep);
+        result.strong.extend(strong);
+    }
+    warnings
+}
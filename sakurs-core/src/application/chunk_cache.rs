@@ -0,0 +1,332 @@
+//! Partial states of chunks already scanned, looked up by content.
+//!
+//! A chunk's partial state depends on nothing but its text and the rules,
+//! so a corpus processed again with the same rules (a nightly pipeline over
+//! mostly unchanged documents) can take the states of unchanged chunks from
+//! a cache instead of rescanning them. The key is the rules' fingerprint
+//! with the chunk's length and a 64-bit hash of its bytes. Entries live in
+//! an LRU in memory and, optionally, as files in a directory that outlives
+//! the process; a file that cannot be read counts as a miss.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::domain::state::PartialState;
+
+/// A cache of scanned chunks, shared by the processors it is given to
+///
+/// Clones share the same entries. Set it with
+/// [`ConfigBuilder::chunk_cache`](crate::ConfigBuilder::chunk_cache);
+/// boundaries are the same with or without it.
+#[derive(Clone)]
+pub struct ChunkCache {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    capacity: usize,
+    directory: Option<PathBuf>,
+    memory: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Lookups of a [`ChunkCache`] so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkCacheStats {
+    /// Chunks whose state was taken from the cache
+    pub hits: u64,
+    /// Chunks scanned because the cache had no state for them
+    pub misses: u64,
+    /// States held in memory
+    pub entries: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    rules: u64,
+    len: usize,
+    hash: u64,
+}
+
+impl Key {
+    fn file_name(&self) -> String {
+        format!("{:016x}-{:016x}-{}.json", self.rules, self.hash, self.len)
+    }
+}
+
+/// States by key, with the order they were last used in
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<Key, (PartialState, u64)>,
+    /// Keys by the tick they were last used at
+    order: BTreeMap<u64, Key>,
+    tick: u64,
+}
+
+impl Lru {
+    fn get(&mut self, key: &Key) -> Option<PartialState> {
+        let (state, used) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        self.tick += 1;
+        *used = self.tick;
+        self.order.insert(self.tick, *key);
+        Some(state.clone())
+    }
+
+    fn insert(&mut self, key: Key, state: PartialState, capacity: usize) {
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.insert(key, (state, self.tick)) {
+            self.order.remove(&used);
+        }
+        self.order.insert(self.tick, key);
+        while self.entries.len() > capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+impl ChunkCache {
+    /// A cache holding the states of up to `capacity` chunks in memory,
+    /// evicting the least recently used
+    pub fn in_memory(capacity: usize) -> Self {
+        Self::with_directory(capacity, None)
+    }
+
+    /// A cache that also keeps every state as a file in `directory`,
+    /// created if missing, so later processes start warm; `capacity` states
+    /// are held in memory in front of it
+    ///
+    /// Fails if the directory cannot be created. Nothing is ever removed
+    /// from it: delete the files to reclaim the space.
+    pub fn on_disk(directory: impl Into<PathBuf>, capacity: usize) -> std::io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self::with_directory(capacity, Some(directory)))
+    }
+
+    fn with_directory(capacity: usize, directory: Option<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                capacity,
+                directory,
+                memory: Mutex::new(Lru::default()),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Hits, misses and entries in memory so far
+    pub fn stats(&self) -> ChunkCacheStats {
+        ChunkCacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            entries: self.memory().entries.len(),
+        }
+    }
+
+    /// The directory of an on-disk cache
+    pub fn directory(&self) -> Option<&Path> {
+        self.inner.directory.as_deref()
+    }
+
+    /// The state of `chunk` under the rules fingerprinted `rules`, scanning
+    /// it with `scan` (and caching the result) when it is not cached
+    pub(crate) fn get_or_scan(
+        &self,
+        rules: u64,
+        chunk: &str,
+        scan: impl FnOnce() -> PartialState,
+    ) -> PartialState {
+        let key = Key {
+            rules,
+            len: chunk.len(),
+            hash: content_hash(chunk.as_bytes()),
+        };
+        if let Some(state) = self.memory().get(&key) {
+            self.inner.hits.fetch_add(1, Ordering::Relaxed);
+            return state;
+        }
+        if let Some(state) = self.read_file(&key) {
+            self.inner.hits.fetch_add(1, Ordering::Relaxed);
+            self.memory()
+                .insert(key, state.clone(), self.inner.capacity);
+            return state;
+        }
+
+        self.inner.misses.fetch_add(1, Ordering::Relaxed);
+        let state = scan();
+        self.write_file(&key, &state);
+        self.memory()
+            .insert(key, state.clone(), self.inner.capacity);
+        state
+    }
+
+    fn memory(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.inner.memory.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn read_file(&self, key: &Key) -> Option<PartialState> {
+        let path = self.inner.directory.as_ref()?.join(key.file_name());
+        let json = std::fs::read(path).ok()?;
+        let state: PartialState = serde_json::from_slice(&json).ok()?;
+        (state.chunk_len == key.len).then_some(state)
+    }
+
+    /// Save the state of a chunk; a cache that cannot be written to only
+    /// loses its benefit, so failures are ignored
+    fn write_file(&self, key: &Key, state: &PartialState) {
+        let Some(directory) = &self.inner.directory else {
+            return;
+        };
+        let Ok(json) = serde_json::to_vec(state) else {
+            return;
+        };
+        // Written under a unique name, then renamed into place, so that
+        // concurrent writers and readers never see a partial file
+        let path = directory.join(key.file_name());
+        let temp = directory.join(format!(
+            "{}.{}.{:?}.tmp",
+            key.file_name(),
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        if std::fs::write(&temp, json).is_err() || std::fs::rename(&temp, path).is_err() {
+            let _ = std::fs::remove_file(&temp);
+        }
+    }
+}
+
+impl fmt::Debug for ChunkCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkCache")
+            .field("capacity", &self.inner.capacity)
+            .field("directory", &self.inner.directory)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// 64-bit hash of `bytes`, the same on every platform and run: FNV-1a over
+/// 8-byte words (much faster than bytewise on chunk-sized input), with the
+/// high bits folded back down after each multiply so every input bit
+/// reaches every output bit
+fn content_hash(bytes: &[u8]) -> u64 {
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut mix = |word: u64| {
+        hash = (hash ^ word).wrapping_mul(PRIME);
+        hash ^= hash >> 32;
+    };
+    let mut words = bytes.chunks_exact(8);
+    for word in &mut words {
+        mix(u64::from_le_bytes(word.try_into().expect("8-byte chunk")));
+    }
+    let mut last = [0u8; 8];
+    last[..words.remainder().len()].copy_from_slice(words.remainder());
+    mix(u64::from_le_bytes(last));
+    mix(bytes.len() as u64);
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{config::ProcessorConfig, DeltaStackProcessor, ExecutionMode};
+
+    fn processor(cache: &ChunkCache) -> DeltaStackProcessor {
+        DeltaStackProcessor::from_language_code(ProcessorConfig::default(), "en")
+            .unwrap()
+            .with_chunk_size(16)
+            .with_chunk_cache(cache.clone(), 7)
+    }
+
+    #[test]
+    fn test_cached_states_give_the_same_boundaries() {
+        let cache = ChunkCache::in_memory(1000);
+        let text = "Hello world. This is (a test. Really.) Dr. Smith left! Yes.".repeat(4);
+        let uncached = DeltaStackProcessor::from_language_code(ProcessorConfig::default(), "en")
+            .unwrap()
+            .with_chunk_size(16)
+            .process(&text, ExecutionMode::Sequential)
+            .unwrap();
+
+        let first = processor(&cache)
+            .process(&text, ExecutionMode::Sequential)
+            .unwrap();
+        let misses = cache.stats().misses;
+        assert!(misses > 0);
+        let second = processor(&cache)
+            .process(&text, ExecutionMode::Parallel { threads: Some(2) })
+            .unwrap();
+        assert_eq!(cache.stats().misses, misses);
+        assert_eq!(cache.stats().hits, first.chunk_count as u64 * 2 - misses);
+        for result in [first, second] {
+            assert_eq!(result.boundaries, uncached.boundaries);
+            assert_eq!(result.strong, uncached.strong);
+        }
+    }
+
+    #[test]
+    fn test_least_recently_used_states_are_evicted() {
+        let cache = ChunkCache::in_memory(2);
+        let scan = |text: &str| {
+            let processor = processor(&cache);
+            processor.process(text, ExecutionMode::Sequential).unwrap();
+        };
+        // One chunk each
+        scan("First one.");
+        scan("Second one.");
+        scan("First one.");
+        scan("Third one.");
+        assert_eq!(cache.stats().entries, 2);
+        scan("First one.");
+        assert_eq!(cache.stats().hits, 2);
+        scan("Second one.");
+        assert_eq!(cache.stats().misses, 4);
+    }
+
+    #[test]
+    fn test_on_disk_cache_outlives_the_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = "Hello world. This is a test. Another sentence here.";
+        let expected = processor(&ChunkCache::in_memory(0))
+            .process(text, ExecutionMode::Sequential)
+            .unwrap();
+
+        let cache = ChunkCache::on_disk(dir.path(), 0).unwrap();
+        processor(&cache)
+            .process(text, ExecutionMode::Sequential)
+            .unwrap();
+        assert_eq!(cache.stats().hits, 0);
+
+        // A new cache over the same directory, with a damaged file
+        let cache = ChunkCache::on_disk(dir.path(), 0).unwrap();
+        let damaged = std::fs::read_dir(dir.path()).unwrap().next().unwrap();
+        std::fs::write(damaged.unwrap().path(), "{").unwrap();
+        let result = processor(&cache)
+            .process(text, ExecutionMode::Sequential)
+            .unwrap();
+        assert_eq!(result.boundaries, expected.boundaries);
+        assert_eq!(cache.stats().misses, 1);
+        assert_eq!(cache.stats().hits, result.chunk_count as u64 - 1);
+    }
+
+    #[test]
+    fn test_content_hash_sees_every_byte() {
+        let base = content_hash(b"0123456789abcdefg");
+        for i in 0..17 {
+            let mut bytes = *b"0123456789abcdefg";
+            bytes[i] ^= 0x80;
+            assert_ne!(content_hash(&bytes), base, "byte {i}");
+        }
+        assert_ne!(content_hash(b"ab"), content_hash(b"ab\0"));
+    }
+}
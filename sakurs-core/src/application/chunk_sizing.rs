@@ -0,0 +1,146 @@
+//! Adaptive chunk sizing from a sample of the input
+//!
+//! A fixed chunk size fits some texts poorly: dense punctuation (dialogue,
+//! abbreviation-heavy prose) puts many judged candidates in every chunk,
+//! while long legal sentences leave chunks with few sentences and
+//! proportionally more pending work at the seams. [`TextProfile`] samples the
+//! start of the text and derives a chunk size that keeps the per-chunk work
+//! roughly constant. Chunking never changes the result (sequential
+//! equivalence); only throughput depends on it. Unlike overlap-based
+//! chunkers there is no overlap to tune: cross-chunk context is the fixed
+//! judgment window `k`.
+
+use crate::domain::state::{scan_chunk_into, CandidateVec, CompiledRules};
+
+use super::delta_stack::is_boundary;
+
+/// Bytes sampled from the start of the text
+pub const SAMPLE_BYTES: usize = 64 * 1024;
+
+/// Smallest chunk size chosen adaptively
+pub const MIN_CHUNK_SIZE: usize = 32 * 1024;
+
+/// Largest chunk size chosen adaptively
+pub const MAX_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Terminator candidates per chunk that amortize the per-chunk overhead
+/// (task dispatch, state combine, seam resolution)
+const TARGET_TERMINATORS_PER_CHUNK: usize = 2048;
+
+/// Sentences per chunk below which seam handling becomes a noticeable share
+/// of the work
+const MIN_SENTENCES_PER_CHUNK: usize = 256;
+
+/// Chunks per thread, so uneven chunks still balance across the pool
+const CHUNKS_PER_THREAD: usize = 4;
+
+/// Sentence length and punctuation density estimated from a text sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextProfile {
+    /// Bytes sampled
+    pub sample_bytes: usize,
+    /// Sentence boundaries found in the sample
+    pub sentences: usize,
+    /// Terminator characters in the sample, boundaries or not
+    pub terminators: usize,
+}
+
+impl TextProfile {
+    /// Profiles the first [`SAMPLE_BYTES`] of `text`.
+    pub(crate) fn sample(text: &str, rules: &CompiledRules) -> Self {
+        let mut end = text.len().min(SAMPLE_BYTES);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let sample = &text[..end];
+
+        let terminators = sample
+            .chars()
+            .filter(|&ch| rules.classify(ch).terminator)
+            .count();
+        let (state, _) =
+            scan_chunk_into(sample, rules, CandidateVec::new()).resolve_edges_full(rules);
+        let sentences = state
+            .boundaries
+            .iter()
+            .filter(|c| is_boundary(rules, c))
+            .count();
+
+        Self {
+            sample_bytes: sample.len(),
+            sentences,
+            terminators,
+        }
+    }
+
+    /// Average sentence length in bytes (the whole sample if it holds no
+    /// boundary)
+    pub fn avg_sentence_bytes(&self) -> usize {
+        self.sample_bytes / self.sentences.max(1)
+    }
+
+    /// Average distance between terminator characters in bytes
+    pub fn bytes_per_terminator(&self) -> usize {
+        self.sample_bytes / self.terminators.max(1)
+    }
+
+    /// Chunk size for a text of `text_len` bytes processed on `threads`
+    /// threads: large enough to amortize per-chunk overhead given the
+    /// observed density, small enough to give every thread several chunks,
+    /// and within [`MIN_CHUNK_SIZE`]..=[`MAX_CHUNK_SIZE`].
+    pub fn chunk_size(&self, text_len: usize, threads: usize) -> usize {
+        let by_density = (TARGET_TERMINATORS_PER_CHUNK * self.bytes_per_terminator())
+            .max(MIN_SENTENCES_PER_CHUNK * self.avg_sentence_bytes());
+        let by_balance = text_len / (threads.max(1) * CHUNKS_PER_THREAD);
+        by_density
+            .min(by_balance)
+            .clamp(MIN_CHUNK_SIZE, MAX_CHUNK_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(code: &str, unit: &str) -> TextProfile {
+        let rules = CompiledRules::from_code(code).unwrap();
+        TextProfile::sample(&unit.repeat(SAMPLE_BYTES / unit.len() + 1), &rules)
+    }
+
+    #[test]
+    fn dense_punctuation_gets_smaller_chunks_than_long_sentences() {
+        let dialogue = profile("ja", "はい。本当？ええ！そうだ。");
+        let legal = profile(
+            "en",
+            "The Licensee shall, within thirty days of receipt of written notice \
+             from the Licensor specifying the nature of any breach of the obligations \
+             set out in this Agreement and the remedy required, cure such breach in \
+             full at its own cost and expense without recourse to the Licensor. ",
+        );
+        assert!(dialogue.avg_sentence_bytes() < legal.avg_sentence_bytes());
+
+        let text_len = 1 << 30;
+        assert!(dialogue.chunk_size(text_len, 8) < legal.chunk_size(text_len, 8));
+    }
+
+    #[test]
+    fn chunk_size_is_clamped_and_balanced() {
+        let p = profile("en", "Dr. Smith left. He was late. ");
+        assert_eq!(p.chunk_size(1024, 4), MIN_CHUNK_SIZE);
+        assert!(p.chunk_size(1 << 30, 1) <= MAX_CHUNK_SIZE);
+
+        // Every thread gets several chunks when the text allows it.
+        let text_len = 16 * 1024 * 1024;
+        assert!(p.chunk_size(text_len, 16) * 16 * CHUNKS_PER_THREAD <= text_len);
+    }
+
+    #[test]
+    fn sample_stops_at_a_char_boundary() {
+        let rules = CompiledRules::from_code("ja").unwrap();
+        let text = "あ".repeat(SAMPLE_BYTES);
+        let p = TextProfile::sample(&text, &rules);
+        assert!(p.sample_bytes <= SAMPLE_BYTES);
+        assert_eq!(p.sample_bytes % 3, 0);
+        assert_eq!(p.sentences, 0);
+    }
+}
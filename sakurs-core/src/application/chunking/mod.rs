@@ -1,15 +1,84 @@
 //! Text chunking for parallel processing.
 //!
 //! Chunks are strictly contiguous, borrowed slices of the input snapped
-//! forward to UTF-8 character boundaries — no copying, no overlap, no
-//! word-boundary search. Deferred judgment makes cut placement irrelevant to
-//! correctness (see `docs/DELTA_STACK_ALGORITHM.md`), so the only constraint
-//! is slice validity.
+//! forward to UTF-8 character boundaries — no copying, no overlap. Deferred
+//! judgment makes cut placement irrelevant to correctness (see
+//! `docs/DELTA_STACK_ALGORITHM.md`), so the only constraint is slice
+//! validity. A [`ChunkPolicy`] can additionally snap cuts to whitespace or
+//! grapheme-cluster boundaries, which keeps words and clusters inside one
+//! chunk and leaves fewer pending items for the combine step to resolve.
+
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::GraphemeCursor;
+
+/// How far past the target a cut may move while searching for whitespace
+/// before falling back to the next grapheme boundary.
+const MAX_WHITESPACE_SNAP: usize = 1024;
+
+/// Where chunk cut points may fall.
+///
+/// Boundaries are identical under every policy; the policy only affects how
+/// much cross-chunk work the combine step does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkPolicy {
+    /// Any UTF-8 character boundary (cheapest; the default)
+    #[default]
+    CharBoundary,
+    /// Just after the next whitespace character, within 1KB of the target
+    /// size; the next grapheme boundary if there is none (e.g. unspaced
+    /// Japanese)
+    Whitespace,
+    /// The next extended grapheme-cluster boundary
+    Grapheme,
+}
+
+impl ChunkPolicy {
+    /// Moves a character-boundary cut at `end` forward to a cut allowed by
+    /// the policy, never past the end of `text`.
+    fn snap(self, text: &str, end: usize) -> usize {
+        match self {
+            ChunkPolicy::CharBoundary => end,
+            ChunkPolicy::Whitespace => {
+                let limit = (end + MAX_WHITESPACE_SNAP).min(text.len());
+                text[end..]
+                    .char_indices()
+                    .take_while(|&(i, _)| end + i < limit)
+                    .find(|&(_, ch)| ch.is_whitespace())
+                    .map(|(i, ch)| end + i + ch.len_utf8())
+                    .unwrap_or_else(|| next_grapheme_boundary(text, end))
+            }
+            ChunkPolicy::Grapheme => next_grapheme_boundary(text, end),
+        }
+    }
+}
+
+/// The first extended grapheme-cluster boundary at or after `pos`.
+fn next_grapheme_boundary(text: &str, pos: usize) -> usize {
+    let mut cursor = GraphemeCursor::new(pos, text.len(), true);
+    if cursor
+        .is_boundary(text, 0)
+        .expect("whole text supplied as one chunk")
+    {
+        return pos;
+    }
+    cursor
+        .next_boundary(text, 0)
+        .expect("whole text supplied as one chunk")
+        .unwrap_or(text.len())
+}
 
 /// Splits `text` into contiguous spans of roughly `chunk_size` bytes, each
 /// end snapped forward to the next character boundary. `chunk_size` is
 /// clamped to at least one byte; the final span may be shorter.
+#[cfg(test)]
 pub(crate) fn chunk_spans(text: &str, chunk_size: usize) -> Vec<&str> {
+    chunk_spans_with(text, chunk_size, ChunkPolicy::CharBoundary)
+}
+
+/// [`chunk_spans`] with cut points snapped forward as `policy` requires;
+/// spans may exceed `chunk_size` by the snap distance.
+pub(crate) fn chunk_spans_with(text: &str, chunk_size: usize, policy: ChunkPolicy) -> Vec<&str> {
     let chunk_size = chunk_size.max(1);
     let mut spans = Vec::with_capacity(text.len() / chunk_size + 1);
     let mut start = 0;
@@ -18,6 +87,9 @@ pub(crate) fn chunk_spans(text: &str, chunk_size: usize) -> Vec<&str> {
         while end < text.len() && !text.is_char_boundary(end) {
             end += 1;
         }
+        if end < text.len() {
+            end = policy.snap(text, end);
+        }
         spans.push(&text[start..end]);
         start = end;
     }
@@ -54,4 +126,44 @@ mod tests {
     fn zero_chunk_size_is_clamped() {
         assert_eq!(chunk_spans("ab", 0), vec!["a", "b"]);
     }
+
+    #[test]
+    fn whitespace_policy_cuts_after_spaces() {
+        let text = "Dr. Smith met Mr. Jones at the U.S. embassy.";
+        let spans = chunk_spans_with(text, 5, ChunkPolicy::Whitespace);
+        assert_eq!(
+            spans,
+            vec![
+                "Dr. Smith ",
+                "met Mr. ",
+                "Jones ",
+                "at the ",
+                "U.S. ",
+                "embas",
+                "sy."
+            ]
+        );
+    }
+
+    #[test]
+    fn whitespace_policy_falls_back_without_spaces() {
+        let text = "あいうえお".repeat(400);
+        let spans = chunk_spans_with(&text, 16, ChunkPolicy::Whitespace);
+        assert_eq!(spans.concat(), text);
+        assert!(spans[0].len() < 32);
+    }
+
+    #[test]
+    fn grapheme_policy_keeps_clusters_whole() {
+        // "e" + combining acute, and a flag made of two regional indicators
+        let text = "cafe\u{301} 🇯🇵🇯🇵 ok";
+        for chunk_size in 1..text.len() {
+            let spans = chunk_spans_with(text, chunk_size, ChunkPolicy::Grapheme);
+            assert_eq!(spans.concat(), text);
+            for s in &spans {
+                assert!(!s.starts_with('\u{301}'), "chunk_size={chunk_size}");
+                assert!(s.chars().filter(|c| ('🇦'..='🇿').contains(c)).count() % 2 == 0);
+            }
+        }
+    }
 }
@@ -5,17 +5,31 @@
 
 use thiserror::Error;
 
+use super::chunking::ChunkPolicy;
+use super::execution_mode::AdaptivePolicy;
+
 /// Configuration options for text processing
 #[derive(Debug, Clone)]
 pub struct ProcessorConfig {
     /// Target size for each chunk in bytes
     pub chunk_size: usize,
+    /// Derive the chunk size from a sample of each text for parallel runs
+    /// (see [`TextProfile`](super::chunk_sizing::TextProfile)), falling back
+    /// to `chunk_size` for sequential ones
+    pub adaptive_chunking: bool,
+    /// Where chunk cut points may fall
+    pub chunk_policy: ChunkPolicy,
+    /// How adaptive runs choose their thread count
+    pub adaptive: AdaptivePolicy,
 }
 
 impl Default for ProcessorConfig {
     fn default() -> Self {
         Self {
             chunk_size: 256 * 1024, // 256KB chunks
+            adaptive_chunking: false,
+            chunk_policy: ChunkPolicy::default(),
+            adaptive: AdaptivePolicy::default(),
         }
     }
 }
@@ -1,50 +1,84 @@
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 use rayon::prelude::*;
 
 use crate::{
     application::{
-        chunking::chunk_spans,
+        chunk_sizing::TextProfile,
+        chunking::{chunk_spans_with, ChunkPolicy},
         config::{ProcessingError, ProcessingResult, ProcessorConfig},
     },
-    domain::language::config::{get_language_config, LanguageConfig},
+    domain::language::config::LanguageConfig,
     domain::state::{
-        adjust_for_toggles, rebase_candidate, scan_chunk, Candidate, CandidateVec, CompiledRules,
-        PartialState, ToggleVec,
+        adjust_for_toggles, apply_slot_to_totals, rebase_candidate, scan_chunk_into, window_around,
+        Candidate, CandidateVec, CompiledRules, EnclosureSlot, PartialState, ScanArena,
+        TerminatorKind, WINDOW_CHARS,
     },
-    domain::types::DepthVec,
+    domain::types::{DepthVec, RejectionReason, Warning},
 };
 
-use super::execution_mode::ExecutionMode;
+use super::balance::BalanceOptions;
+use super::chunk_cache::ChunkCache;
+use super::execution_mode::{AdaptivePolicy, ExecutionDecision, ExecutionMode};
+use super::incremental::DeltaStackDocument;
+use super::streaming::{DeltaStackStream, StreamState};
 
 /// Result of delta-stack processing with metadata
 pub struct DeltaStackResult {
     pub boundaries: Vec<usize>,
+    /// The boundaries judged strong (`!`, `?`, terminator patterns), sorted;
+    /// the others are weak
+    pub strong: Vec<usize>,
     pub chunk_count: usize,
-    pub thread_count: usize,
+    /// Threads and chunk size used, and why
+    pub execution: ExecutionDecision,
 }
 
+/// Progress through one text, reported as its chunks are scanned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Bytes of the text scanned so far
+    pub processed_bytes: usize,
+    /// Length of the text in bytes
+    pub total_bytes: usize,
+}
+
+/// Receiver of [`Progress`] reports, called from the scanning threads
+pub type ProgressCallback<'a> = &'a (dyn Fn(Progress) + Sync);
+
 /// Core implementation of the Δ-Stack Monoid algorithm
 ///
 /// Runs the three phases described in `docs/DELTA_STACK_ALGORITHM.md`:
 /// 1. Scan: chunks are scanned in parallel into partial states
-/// 2. Prefix: the states are folded left-to-right, accumulating depth/parity
-///    and resolving pending items from neighboring context, then the text
-///    edges are resolved
+/// 2. Combine: the states are reduced (as a parallel tree when multi-threaded),
+///    resolving pending items from neighboring context, then the text edges
+///    are resolved and each chunk's starting depth/parity is prefix-summed
 /// 3. Reduce: candidates outside every enclosure become boundaries
 pub struct DeltaStackProcessor {
-    rules: Arc<CompiledRules>,
-    chunk_size: usize,
+    pub(super) rules: Arc<CompiledRules>,
+    pub(super) chunk_size: usize,
+    adaptive_chunking: bool,
+    pub(super) chunk_policy: ChunkPolicy,
+    pub(super) adaptive: AdaptivePolicy,
+    /// Candidate buffers left over from the previous run. A run takes the
+    /// arena for its duration; concurrent runs start from an empty one.
+    arena: Mutex<ScanArena>,
+    /// Cache of scanned chunks, with the fingerprint of the rules
+    cache: Option<(ChunkCache, u64)>,
 }
 
 impl DeltaStackProcessor {
     /// Creates a processor for an embedded language code (e.g. "en", "ja").
+    #[cfg(test)]
     pub fn from_language_code(
         config: ProcessorConfig,
         code: &str,
     ) -> Result<Self, ProcessingError> {
-        let language = get_language_config(code).map_err(|e| ProcessingError::InvalidConfig {
-            reason: e.to_string(),
+        let language = crate::domain::language::config::get_language_config(code).map_err(|e| {
+            ProcessingError::InvalidConfig {
+                reason: e.to_string(),
+            }
         })?;
         Self::from_language_config(config, language)
     }
@@ -56,29 +90,169 @@ impl DeltaStackProcessor {
         language: &LanguageConfig,
     ) -> Result<Self, ProcessingError> {
         let rules =
-            CompiledRules::from_config(language).map_err(|e| ProcessingError::InvalidConfig {
+            CompiledRules::shared(language).map_err(|e| ProcessingError::InvalidConfig {
                 reason: e.to_string(),
             })?;
         Ok(Self {
-            rules: Arc::new(rules),
+            rules,
             chunk_size: config.chunk_size,
+            adaptive_chunking: config.adaptive_chunking,
+            chunk_policy: config.chunk_policy,
+            adaptive: config.adaptive,
+            arena: Mutex::new(ScanArena::default()),
+            cache: None,
         })
     }
 
-    /// Main processing method that executes the Δ-Stack Monoid algorithm
+    /// A processor with the same rules that cuts text into chunks of exactly
+    /// `chunk_size` bytes (rounded up to a character boundary), without
+    /// adaptive resizing.
+    pub fn with_chunk_size(&self, chunk_size: usize) -> Self {
+        Self {
+            rules: Arc::clone(&self.rules),
+            chunk_size,
+            adaptive_chunking: false,
+            chunk_policy: ChunkPolicy::CharBoundary,
+            adaptive: self.adaptive,
+            arena: Mutex::new(ScanArena::default()),
+            cache: self.cache.clone(),
+        }
+    }
+
+    /// Takes the states of chunks already scanned under the rules
+    /// fingerprinted `rules` from `cache`, and adds those of the others.
+    pub fn with_chunk_cache(mut self, cache: ChunkCache, rules: u64) -> Self {
+        self.cache = Some((cache, rules));
+        self
+    }
+
+    /// Scans one chunk, or takes its state from the chunk cache.
+    pub(super) fn scan_chunk(&self, chunk: &str, buf: CandidateVec) -> PartialState {
+        match &self.cache {
+            Some((cache, rules)) => {
+                cache.get_or_scan(*rules, chunk, || scan_chunk_into(chunk, &self.rules, buf))
+            }
+            None => scan_chunk_into(chunk, &self.rules, buf),
+        }
+    }
+
+    /// Starts a stream over text supplied in pieces (see [`DeltaStackStream`]).
+    pub fn stream(&self) -> DeltaStackStream {
+        DeltaStackStream::new(Arc::clone(&self.rules), self.chunk_size, self.chunk_policy)
+    }
+
+    /// Continues a stream from its [`StreamState`], which must come from a
+    /// processor with the same rules.
+    pub fn stream_from(&self, state: StreamState) -> DeltaStackStream {
+        DeltaStackStream::resume(
+            Arc::clone(&self.rules),
+            self.chunk_size,
+            self.chunk_policy,
+            state,
+        )
+    }
+
+    /// Scans `text` into a document that can be re-segmented after edits
+    /// (see [`DeltaStackDocument`]).
+    pub fn document(&self, text: &str) -> DeltaStackDocument {
+        DeltaStackDocument::new(
+            Arc::clone(&self.rules),
+            self.chunk_size,
+            self.chunk_policy,
+            text,
+        )
+    }
+
+    /// Whether `document` was scanned by a processor with these rules
+    pub(crate) fn owns(&self, document: &DeltaStackDocument) -> bool {
+        document.uses(&self.rules)
+    }
+
+    /// Every terminator candidate in `text` with its verdict, given
+    /// `boundaries`, the sorted result of
+    /// [`process_with_progress`](Self::process_with_progress) on the same text
+    ///
+    /// Candidates are re-judged sequentially; one the rules accept but that
+    /// is missing from `boundaries` lies inside an enclosure.
+    pub fn candidates(
+        &self,
+        text: &str,
+        boundaries: &[usize],
+    ) -> Vec<(usize, Result<(), RejectionReason>)> {
+        let rules = self.rules.as_ref();
+        text.char_indices()
+            .filter(|&(_, ch)| rules.classify(ch).terminator)
+            .map(|(i, ch)| {
+                let offset = i + ch.len_utf8();
+                let (window, pos) = window_around(text, offset, WINDOW_CHARS);
+                let verdict = match rules.explain(window, pos, TerminatorKind::Char(ch)) {
+                    Err(reason) => Err(reason),
+                    Ok(_) if boundaries.binary_search(&offset).is_err() => {
+                        Err(RejectionReason::InsideEnclosure)
+                    }
+                    Ok(_) => Ok(()),
+                };
+                (offset, verdict)
+            })
+            .collect()
+    }
+
+    /// The quotes and brackets `text` leaves unbalanced, given `result`, the
+    /// output of [`process_with_progress`](Self::process_with_progress) on
+    /// the same text
+    ///
+    /// When `options` close enclosures at blank lines or cap their depth,
+    /// the boundaries of `result` after the first change are recomputed.
+    pub(crate) fn check_enclosures(
+        &self,
+        text: &str,
+        options: BalanceOptions,
+        result: &mut DeltaStackResult,
+    ) -> Vec<Warning> {
+        super::balance::check(&self.rules, text, options, result)
+    }
+
+    /// [`process_with_progress`](Self::process_with_progress) without
+    /// progress reports
+    #[cfg(test)]
     pub fn process(&self, text: &str, mode: ExecutionMode) -> ProcessingResult<DeltaStackResult> {
+        self.process_with_progress(text, mode, None)
+    }
+
+    /// Main processing method that executes the Δ-Stack Monoid algorithm,
+    /// reporting to `progress` after each chunk is scanned
+    ///
+    /// Chunks scanned in parallel report in the order they finish, so
+    /// `processed_bytes` only grows when read under a lock; the last chunk
+    /// reports `total_bytes`. The combine and reduce phases that follow take
+    /// a small fraction of the time.
+    pub fn process_with_progress(
+        &self,
+        text: &str,
+        mode: ExecutionMode,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> ProcessingResult<DeltaStackResult> {
         if text.is_empty() {
             return Ok(DeltaStackResult {
                 boundaries: Vec::new(),
+                strong: Vec::new(),
                 chunk_count: 0,
-                thread_count: 1,
+                execution: ExecutionDecision::sequential(0, self.chunk_size),
             });
         }
 
-        let chunks = chunk_spans(text, self.chunk_size);
+        let rules = self.rules.as_ref();
+        let mut execution = mode.decide(text.len(), &self.adaptive);
+        let thread_count = execution.thread_count;
+        let chunk_size = if self.adaptive_chunking && thread_count > 1 {
+            TextProfile::sample(text, rules).chunk_size(text.len(), thread_count)
+        } else {
+            self.chunk_size
+        };
+        execution.chunk_size = chunk_size;
+        let chunks = chunk_spans_with(text, chunk_size, self.chunk_policy);
         let chunk_count = chunks.len();
 
-        let thread_count = mode.determine_thread_count(text.len());
         let pool = if thread_count > 1 {
             Some(
                 rayon::ThreadPoolBuilder::new()
@@ -91,134 +265,230 @@ impl DeltaStackProcessor {
         } else {
             None
         };
-        let rules = self.rules.as_ref();
+        let mut arena = self.take_arena();
+        let buffers = arena.buffers(chunk_count);
 
         // Phase 1: scan chunks into partial states (parallel when warranted).
-        let mut states: Vec<PartialState> = if let Some(pool) = &pool {
-            pool.install(|| {
-                chunks
-                    .par_iter()
-                    .map(|chunk| scan_chunk(chunk, rules))
-                    .collect()
-            })
-        } else {
-            chunks
-                .iter()
-                .map(|chunk| scan_chunk(chunk, rules))
-                .collect()
-        };
-
-        // Phase 2: prefix fold over aggregates. The confirmed candidates are
-        // taken out of the states first, so the sequential fold only touches
-        // per-chunk totals, context buffers, and pending items; the bulk is
-        // rebased and filtered in parallel below. Seam-resolved candidates
-        // surface in the accumulator and are collected as extras; resolved
-        // enclosure toggles are routed to every chunk whose prefix was
-        // recorded before the toggle was known (later chunks receive them
-        // through the cumulative prefix itself).
-        let mut bulk: Vec<CandidateVec> = Vec::with_capacity(chunk_count);
-        let mut chunk_starts: Vec<usize> = Vec::with_capacity(chunk_count);
-        let mut prefix: Vec<(DepthVec, u32)> = Vec::with_capacity(chunk_count);
-        let mut toggles_by_chunk: Vec<ToggleVec> = vec![ToggleVec::new(); chunk_count];
-        let mut acc = PartialState::identity();
-        for (i, state) in states.iter_mut().enumerate() {
-            bulk.push(std::mem::take(&mut state.boundaries));
-            chunk_starts.push(acc.chunk_len);
-            prefix.push((acc.deltas.clone(), acc.parity));
-            let toggles = acc.absorb(state, rules);
-            assign_toggles(&mut toggles_by_chunk, &chunk_starts, toggles, i);
-        }
-        // Seam-resolved candidates stay in the accumulator through edge
-        // resolution: that is where boundary-of-text enclosure toggles are
-        // applied to them (a toggle resolved at a later step always sits
-        // after every earlier-confirmed candidate, but BOF/EOF toggles do
-        // not).
-        let (mut acc, edge_toggles) = acc.resolve_edges_full(rules);
-        assign_toggles(
-            &mut toggles_by_chunk,
-            &chunk_starts,
-            edge_toggles,
-            chunk_count - 1,
-        );
-        let extras: Vec<Candidate> = acc.boundaries.drain(..).collect();
-
-        // Phase 3: reduce — rebase each chunk's candidates to text-global
-        // coordinates, apply the toggles positioned before them, and keep
-        // candidates outside every enclosure: clamped depth for asymmetric
-        // types, even parity for symmetric types. Embarrassingly parallel.
-        let reduce_chunk = |i: usize| -> Vec<usize> {
-            let (deltas, parity) = &prefix[i];
-            let toggles = &toggles_by_chunk[i];
-            bulk[i]
-                .iter()
-                .filter_map(|c| {
-                    let mut c = rebase_candidate(c, chunk_starts[i], deltas, *parity);
-                    adjust_for_toggles(
-                        &mut c.local_depths,
-                        &mut c.local_parity,
-                        c.local_offset,
-                        toggles,
-                    );
-                    is_boundary(&c).then_some(c.local_offset)
-                })
-                .collect()
+        let scanned = AtomicUsize::new(0);
+        let scan = |(chunk, buf): (&&str, CandidateVec)| {
+            let state = self.scan_chunk(chunk, buf);
+            if let Some(report) = progress {
+                report(Progress {
+                    processed_bytes: scanned.fetch_add(chunk.len(), Ordering::Relaxed)
+                        + chunk.len(),
+                    total_bytes: text.len(),
+                });
+            }
+            state
         };
-        let per_chunk: Vec<Vec<usize>> = if let Some(pool) = &pool {
-            pool.install(|| (0..chunk_count).into_par_iter().map(reduce_chunk).collect())
+        let mut states: Vec<PartialState> = if let Some(pool) = &pool {
+            pool.install(|| chunks.par_iter().zip(buffers).map(scan).collect())
         } else {
-            (0..chunk_count).map(reduce_chunk).collect()
+            chunks.iter().zip(buffers).map(scan).collect()
         };
 
-        // Merge: per-chunk results are globally ordered by construction; the
-        // few seam/edge extras are merged in by offset.
-        let mut extra_offsets: Vec<usize> = extras
-            .iter()
-            .filter(|c| is_boundary(c))
-            .map(|c| c.local_offset)
+        // The confirmed candidates are taken out of the states first, so
+        // combining only touches per-chunk totals, context buffers, and
+        // pending items (see `combine_and_reduce`)
+        let bulk: Vec<CandidateVec> = states
+            .iter_mut()
+            .map(|state| std::mem::take(&mut state.boundaries))
             .collect();
-        extra_offsets.sort_unstable();
-        let total: usize = per_chunk.iter().map(Vec::len).sum::<usize>() + extra_offsets.len();
-        let mut boundaries = Vec::with_capacity(total);
-        let mut extras_iter = extra_offsets.into_iter().peekable();
-        for chunk_offsets in per_chunk {
-            for off in chunk_offsets {
-                while extras_iter.peek().is_some_and(|&e| e < off) {
-                    boundaries.push(extras_iter.next().unwrap());
-                }
-                boundaries.push(off);
-            }
+        let slices: Vec<&[Candidate]> = bulk.iter().map(|buf| buf.as_slice()).collect();
+        let (boundaries, strong) = combine_and_reduce(rules, states, &slices, pool.as_ref());
+
+        for buf in bulk {
+            arena.recycle(buf);
         }
-        boundaries.extend(extras_iter);
-        boundaries.dedup();
+        self.return_arena(arena);
 
         Ok(DeltaStackResult {
             boundaries,
+            strong,
             chunk_count,
-            thread_count,
+            execution,
         })
     }
-}
 
-/// A candidate is a sentence boundary iff it sits outside every enclosure.
-fn is_boundary(c: &Candidate) -> bool {
-    c.local_parity == 0 && c.local_depths.iter().all(|&d| d <= 0)
+    fn take_arena(&self) -> ScanArena {
+        std::mem::take(&mut *self.arena.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn return_arena(&self, arena: ScanArena) {
+        *self.arena.lock().unwrap_or_else(|e| e.into_inner()) = arena;
+    }
 }
 
-/// Routes resolved enclosure toggles to the chunks whose recorded prefix
-/// predates them: from the chunk containing the toggle through chunk `upto`
-/// (the step at which it resolved). Later chunks see the toggle through the
-/// cumulative prefix instead.
-fn assign_toggles(
-    by_chunk: &mut [ToggleVec],
-    chunk_starts: &[usize],
-    toggles: ToggleVec,
-    upto: usize,
-) {
-    for (q, slot) in toggles {
-        let from = chunk_starts[..=upto].partition_point(|&s| s <= q) - 1;
-        for list in &mut by_chunk[from..=upto] {
-            list.push((q, slot));
+/// Phases 2 and 3 over the scanned states of consecutive chunks, whose
+/// confirmed candidates were taken out into `bulk` (one entry per state);
+/// returns the boundaries and, of those, the strong ones
+pub(super) fn combine_and_reduce(
+    rules: &CompiledRules,
+    states: Vec<PartialState>,
+    bulk: &[&[Candidate]],
+    pool: Option<&rayon::ThreadPool>,
+) -> (Vec<usize>, Vec<usize>) {
+    // Phase 2: combine the aggregates; the bulk of the candidates is rebased
+    // and filtered in parallel below. The states are reduced pairwise as a
+    // tree when running in parallel (the combine is associative, so any
+    // parenthesization yields the same result) and folded left to right
+    // otherwise. Seam-resolved candidates surface in the root state and
+    // are collected as extras; resolved enclosure toggles are collected
+    // in text-global coordinates.
+    let chunk_count = states.len();
+    let mut chunk_starts: Vec<usize> = Vec::with_capacity(chunk_count);
+    let mut chunk_totals: Vec<(DepthVec, u32)> = Vec::with_capacity(chunk_count);
+    let mut start = 0;
+    for state in &states {
+        chunk_starts.push(start);
+        chunk_totals.push((state.deltas.clone(), state.parity));
+        start += state.chunk_len;
+    }
+    let leaves: Vec<Subtotal> = states
+        .into_iter()
+        .zip(chunk_starts.iter().copied())
+        .map(|(state, start)| Subtotal {
+            start,
+            state,
+            toggles: Vec::new(),
+        })
+        .collect();
+    let root = if let Some(pool) = pool {
+        pool.install(|| {
+            leaves
+                .into_par_iter()
+                .reduce_with(|left, right| left.merge(right, rules))
+        })
+    } else {
+        leaves
+            .into_iter()
+            .reduce(|left, right| left.merge(right, rules))
+    }
+    .expect("non-empty text has at least one chunk");
+
+    // Seam-resolved candidates stay in the root state through edge
+    // resolution: that is where boundary-of-text enclosure toggles are
+    // applied to them (a toggle resolved at a combine always sits after
+    // every candidate confirmed on its left, but BOF/EOF toggles do not).
+    let (mut acc, edge_toggles) = root.state.resolve_edges_full(rules);
+    let mut toggles = root.toggles;
+    toggles.extend(edge_toggles);
+    toggles.sort_unstable_by_key(|&(q, _)| q);
+    let extras: Vec<Candidate> = acc.boundaries.drain(..).collect();
+
+    // Prefix scan: the depth/parity in effect at each chunk start is the
+    // sum of the preceding chunks' totals plus every toggle positioned
+    // before it. Toggles inside a chunk are applied per candidate below.
+    let mut prefix: Vec<(DepthVec, u32)> = Vec::with_capacity(chunk_count);
+    let mut deltas = DepthVec::new();
+    let mut parity = 0;
+    let mut next_toggle = 0;
+    for (i, (chunk_deltas, chunk_parity)) in chunk_totals.iter().enumerate() {
+        while toggles
+            .get(next_toggle)
+            .is_some_and(|&(q, _)| q < chunk_starts[i])
+        {
+            apply_slot_to_totals(&mut deltas, &mut parity, toggles[next_toggle].1);
+            next_toggle += 1;
         }
+        prefix.push((deltas.clone(), parity));
+        if chunk_deltas.len() > deltas.len() {
+            deltas.resize(chunk_deltas.len(), 0);
+        }
+        for (d, cd) in deltas.iter_mut().zip(chunk_deltas) {
+            *d += cd;
+        }
+        parity ^= chunk_parity;
+    }
+
+    // Phase 3: reduce — rebase each chunk's candidates to text-global
+    // coordinates, apply the toggles positioned before them, and keep
+    // candidates outside every enclosure: clamped depth for asymmetric
+    // types, even parity for symmetric types. Embarrassingly parallel.
+    let reduce_chunk = |i: usize| -> Vec<(usize, bool)> {
+        let (deltas, parity) = &prefix[i];
+        let chunk_end = chunk_starts.get(i + 1).copied().unwrap_or(usize::MAX);
+        let toggles = &toggles[toggles.partition_point(|&(q, _)| q < chunk_starts[i])
+            ..toggles.partition_point(|&(q, _)| q < chunk_end)];
+        bulk[i]
+            .iter()
+            .filter_map(|c| {
+                let mut c = rebase_candidate(c, chunk_starts[i], deltas, *parity);
+                adjust_for_toggles(
+                    &mut c.local_depths,
+                    &mut c.local_parity,
+                    c.local_offset,
+                    toggles,
+                );
+                is_boundary(rules, &c).then_some((c.local_offset, c.flags.is_strong))
+            })
+            .collect()
+    };
+    let per_chunk: Vec<Vec<(usize, bool)>> = if let Some(pool) = pool {
+        pool.install(|| (0..chunk_count).into_par_iter().map(reduce_chunk).collect())
+    } else {
+        (0..chunk_count).map(reduce_chunk).collect()
+    };
+
+    // Merge: per-chunk results are globally ordered by construction; the
+    // few seam/edge extras are merged in by offset.
+    let mut extra_offsets: Vec<(usize, bool)> = extras
+        .iter()
+        .filter(|c| is_boundary(rules, c))
+        .map(|c| (c.local_offset, c.flags.is_strong))
+        .collect();
+    extra_offsets.sort_unstable();
+    let total: usize = per_chunk.iter().map(Vec::len).sum::<usize>() + extra_offsets.len();
+    let mut judged: Vec<(usize, bool)> = Vec::with_capacity(total);
+    let mut extras_iter = extra_offsets.into_iter().peekable();
+    for chunk_offsets in per_chunk {
+        for (off, strong) in chunk_offsets {
+            while extras_iter.peek().is_some_and(|&(e, _)| e < off) {
+                judged.push(extras_iter.next().unwrap());
+            }
+            judged.push((off, strong));
+        }
+    }
+    judged.extend(extras_iter);
+    // A duplicate is strong if either copy is, whichever side of a seam
+    // it was judged on
+    judged.dedup_by(|(off, strong), (kept_off, kept_strong)| {
+        *kept_strong |= *strong && off == kept_off;
+        off == kept_off
+    });
+    let boundaries = judged.iter().map(|&(off, _)| off).collect();
+    let strong = judged
+        .iter()
+        .filter(|&&(_, strong)| strong)
+        .map(|&(off, _)| off)
+        .collect();
+    (boundaries, strong)
+}
+
+/// A candidate is a sentence boundary iff every enclosure it sits inside
+/// lets it through (by default, iff it sits outside every enclosure).
+pub(super) fn is_boundary(rules: &CompiledRules, c: &Candidate) -> bool {
+    rules.permits_boundary(&c.local_depths, c.local_parity, c.flags.is_strong)
+}
+
+/// A combined run of adjacent chunks during the reduce tree.
+struct Subtotal {
+    /// Byte offset of the run in the text
+    start: usize,
+    /// Combined state of the run, in run-local coordinates
+    state: PartialState,
+    /// Enclosure toggles resolved inside the run, in text-global coordinates
+    toggles: Vec<(usize, EnclosureSlot)>,
+}
+
+impl Subtotal {
+    /// Combines with the run immediately to the right.
+    fn merge(mut self, right: Subtotal, rules: &CompiledRules) -> Self {
+        let seam = self.state.absorb(&right.state, rules);
+        self.toggles
+            .extend(seam.into_iter().map(|(q, slot)| (q + self.start, slot)));
+        self.toggles.extend(right.toggles);
+        self
     }
 }
 
@@ -236,7 +506,7 @@ mod tests {
         let result = processor.process("", ExecutionMode::Sequential).unwrap();
         assert!(result.boundaries.is_empty());
         assert_eq!(result.chunk_count, 0);
-        assert_eq!(result.thread_count, 1);
+        assert_eq!(result.execution.thread_count, 1);
     }
 
     #[test]
@@ -247,7 +517,7 @@ mod tests {
         assert_eq!(result.boundaries.len(), 1);
         assert_eq!(result.boundaries[0], 19); // Position after the period
         assert_eq!(result.chunk_count, 1);
-        assert_eq!(result.thread_count, 1);
+        assert_eq!(result.execution.thread_count, 1);
     }
 
     #[test]
@@ -262,8 +532,31 @@ mod tests {
 
         assert_eq!(seq_result.boundaries, par_result.boundaries);
         assert_eq!(seq_result.chunk_count, par_result.chunk_count);
-        assert_eq!(seq_result.thread_count, 1);
-        assert_eq!(par_result.thread_count, 2);
+        assert_eq!(seq_result.execution.thread_count, 1);
+        assert_eq!(par_result.execution.thread_count, 2);
+    }
+
+    #[test]
+    fn test_tree_reduce_matches_fold_over_many_chunks() {
+        let processor = DeltaStackProcessor::from_language_code(
+            ProcessorConfig {
+                chunk_size: 64,
+                ..ProcessorConfig::default()
+            },
+            "en",
+        )
+        .unwrap();
+        let text = "He said \"Dr. Smith (of the U.S. team) left. Really?\" Yes. 'Quoted.' Done! "
+            .repeat(200);
+
+        let seq_result = processor.process(&text, ExecutionMode::Sequential).unwrap();
+        let par_result = processor
+            .process(&text, ExecutionMode::Parallel { threads: Some(4) })
+            .unwrap();
+
+        assert!(seq_result.chunk_count > 100);
+        assert!(!seq_result.boundaries.is_empty());
+        assert_eq!(seq_result.boundaries, par_result.boundaries);
     }
 
     #[test]
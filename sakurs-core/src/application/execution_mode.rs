@@ -31,32 +31,174 @@ pub enum ExecutionMode {
     Adaptive,
 }
 
+/// How the thread count is chosen from the text size when it is not fixed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptivePolicy {
+    /// Text bytes per thread; shorter texts run sequentially
+    pub bytes_per_thread: usize,
+    /// Most threads used (None = available parallelism)
+    pub max_threads: Option<usize>,
+}
+
+impl Default for AdaptivePolicy {
+    fn default() -> Self {
+        Self {
+            bytes_per_thread: 256 * 1024, // 256KB per thread
+            max_threads: None,
+        }
+    }
+}
+
+/// Why an [`ExecutionDecision`] has the thread count it has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionReason {
+    /// The mode fixes the thread count: sequential, or parallel with a count
+    Fixed,
+    /// The text is shorter than the text size per thread, so one thread
+    BelowThreshold,
+    /// One thread per text size per thread, within the limits
+    SizeBased,
+    /// As many threads as the configured maximum, which the size called for
+    /// more than
+    MaxThreads,
+    /// As many threads as are available, which the size called for more
+    /// than
+    AvailableThreads,
+}
+
+/// How a text was executed and why: the thread count, the chunk size, and
+/// the inputs they were chosen from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionDecision {
+    /// The mode asked for
+    pub mode: ExecutionMode,
+    /// Bytes of text the decision was made for
+    pub text_len: usize,
+    /// Threads the text was scanned on
+    pub thread_count: usize,
+    /// Chunk size in bytes, chosen per text when adaptive chunk sizing is on
+    pub chunk_size: usize,
+    /// Why the thread count is what it is
+    pub reason: ExecutionReason,
+    /// Text bytes per thread of the adaptive policy
+    pub bytes_per_thread: usize,
+    /// Most threads of the adaptive policy (None = all available)
+    pub max_threads: Option<usize>,
+    /// Threads available on the machine, when they were consulted (only
+    /// texts long enough for more than one thread consult them)
+    pub available_threads: Option<usize>,
+}
+
+impl ExecutionDecision {
+    /// A run on one thread, as the mode fixes, in chunks of `chunk_size`
+    pub(crate) fn sequential(text_len: usize, chunk_size: usize) -> Self {
+        ExecutionDecision {
+            chunk_size,
+            ..ExecutionMode::Sequential.decide(text_len, &AdaptivePolicy::default())
+        }
+    }
+
+    /// Text bytes per available thread, when they were consulted
+    pub fn bytes_per_core(&self) -> Option<usize> {
+        self.available_threads
+            .map(|available| self.text_len / available.max(1))
+    }
+}
+
+impl std::fmt::Display for ExecutionDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self.mode {
+            ExecutionMode::Sequential => "sequential",
+            ExecutionMode::Parallel { .. } => "parallel",
+            ExecutionMode::Adaptive => "adaptive",
+        };
+        let threads = self.thread_count;
+        let plural = if threads == 1 { "" } else { "s" };
+        write!(f, "{mode}, {threads} thread{plural}: ")?;
+        let wanted = self.text_len / self.bytes_per_thread.max(1);
+        match self.reason {
+            ExecutionReason::Fixed => write!(f, "fixed by the configuration")?,
+            ExecutionReason::BelowThreshold => write!(
+                f,
+                "{} bytes is below the threshold of {} bytes per thread",
+                self.text_len, self.bytes_per_thread
+            )?,
+            ExecutionReason::SizeBased => write!(
+                f,
+                "one per {} bytes of {}",
+                self.bytes_per_thread, self.text_len
+            )?,
+            ExecutionReason::MaxThreads => write!(
+                f,
+                "{wanted} wanted at {} bytes per thread, capped by max_threads",
+                self.bytes_per_thread
+            )?,
+            ExecutionReason::AvailableThreads => write!(
+                f,
+                "{wanted} wanted at {} bytes per thread, capped by the threads available",
+                self.bytes_per_thread
+            )?,
+        }
+        if let (Some(available), Some(per_core)) = (self.available_threads, self.bytes_per_core()) {
+            write!(f, "; {available} available, {per_core} bytes per core")?;
+        }
+        write!(f, "; chunks of {} bytes", self.chunk_size)
+    }
+}
+
 impl ExecutionMode {
     /// Determines the actual number of threads to use based on the mode and text size
-    pub fn determine_thread_count(&self, text_len: usize) -> usize {
+    pub fn determine_thread_count(&self, text_len: usize, policy: &AdaptivePolicy) -> usize {
+        self.decide(text_len, policy).thread_count
+    }
+
+    /// Choose the thread count for a text of `text_len` bytes, with the
+    /// reason; the chunk size is left at 0 for the processor to fill in
+    /// once it is chosen
+    pub fn decide(&self, text_len: usize, policy: &AdaptivePolicy) -> ExecutionDecision {
+        let mut decision = ExecutionDecision {
+            mode: *self,
+            text_len,
+            thread_count: 1,
+            chunk_size: 0,
+            reason: ExecutionReason::Fixed,
+            bytes_per_thread: policy.bytes_per_thread,
+            max_threads: policy.max_threads,
+            available_threads: None,
+        };
         match self {
-            ExecutionMode::Sequential => 1,
-            ExecutionMode::Parallel { threads: Some(n) } => *n,
+            ExecutionMode::Sequential => {}
+            ExecutionMode::Parallel { threads: Some(n) } => decision.thread_count = *n,
             ExecutionMode::Parallel { threads: None } | ExecutionMode::Adaptive => {
-                Self::calculate_optimal_threads(text_len)
+                Self::calculate_optimal_threads(&mut decision)
             }
         }
+        decision
     }
 
-    /// Calculates the optimal number of threads based on text size
-    /// This preserves the existing heuristics from UnifiedProcessor
-    fn calculate_optimal_threads(text_len: usize) -> usize {
-        const MIN_CHUNK_SIZE: usize = 256 * 1024; // 256KB per thread
+    /// Calculates the optimal number of threads based on text size: one per
+    /// `bytes_per_thread`, up to the available parallelism
+    fn calculate_optimal_threads(decision: &mut ExecutionDecision) {
+        if decision.text_len < decision.bytes_per_thread {
+            decision.reason = ExecutionReason::BelowThreshold;
+            return;
+        }
+        let available_parallelism = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        decision.available_threads = Some(available_parallelism);
 
-        if text_len < MIN_CHUNK_SIZE {
-            1
+        let size_based_threads = (decision.text_len / decision.bytes_per_thread).max(1);
+        let (limit, capped) = match decision.max_threads {
+            Some(max) if max < available_parallelism => (max.max(1), ExecutionReason::MaxThreads),
+            _ => (available_parallelism, ExecutionReason::AvailableThreads),
+        };
+        if size_based_threads > limit {
+            decision.thread_count = limit;
+            decision.reason = capped;
         } else {
-            let available_parallelism = std::thread::available_parallelism()
-                .map(|n| n.get())
-                .unwrap_or(1);
-
-            let size_based_threads = (text_len / MIN_CHUNK_SIZE).max(1);
-            size_based_threads.min(available_parallelism)
+            decision.thread_count = size_based_threads;
+            decision.reason = ExecutionReason::SizeBased;
         }
     }
 }
@@ -68,27 +210,81 @@ mod tests {
     #[test]
     fn test_sequential_mode() {
         let mode = ExecutionMode::Sequential;
-        assert_eq!(mode.determine_thread_count(1_000_000), 1);
+        assert_eq!(
+            mode.determine_thread_count(1_000_000, &AdaptivePolicy::default()),
+            1
+        );
     }
 
     #[test]
     fn test_parallel_mode_with_threads() {
         let mode = ExecutionMode::Parallel { threads: Some(4) };
-        assert_eq!(mode.determine_thread_count(1_000_000), 4);
+        assert_eq!(
+            mode.determine_thread_count(1_000_000, &AdaptivePolicy::default()),
+            4
+        );
     }
 
     #[test]
     fn test_adaptive_mode_small_text() {
         let mode = ExecutionMode::Adaptive;
         // Small text should use single thread
-        assert_eq!(mode.determine_thread_count(100_000), 1);
+        assert_eq!(
+            mode.determine_thread_count(100_000, &AdaptivePolicy::default()),
+            1
+        );
     }
 
     #[test]
     fn test_adaptive_mode_large_text() {
         let mode = ExecutionMode::Adaptive;
         // Large text should use multiple threads
-        let thread_count = mode.determine_thread_count(10_000_000);
+        let thread_count = mode.determine_thread_count(10_000_000, &AdaptivePolicy::default());
         assert!(thread_count > 1);
     }
+
+    #[test]
+    fn test_adaptive_policy_threshold_and_cap() {
+        let mode = ExecutionMode::Adaptive;
+        let low = AdaptivePolicy {
+            bytes_per_thread: 1024,
+            max_threads: None,
+        };
+        assert_eq!(mode.determine_thread_count(1023, &low), 1);
+
+        let capped = AdaptivePolicy {
+            max_threads: Some(1),
+            ..low
+        };
+        assert_eq!(mode.determine_thread_count(10_000_000, &capped), 1);
+    }
+
+    #[test]
+    fn test_decision_reasons() {
+        let policy = AdaptivePolicy {
+            bytes_per_thread: 1024,
+            max_threads: Some(1),
+        };
+        let small = ExecutionMode::Adaptive.decide(1023, &policy);
+        assert_eq!(small.reason, ExecutionReason::BelowThreshold);
+        assert_eq!(small.available_threads, None);
+        assert!(small.to_string().contains("below the threshold of 1024"));
+
+        let capped = ExecutionMode::Adaptive.decide(10_000, &policy);
+        assert_eq!(capped.thread_count, 1);
+        let available = capped.available_threads.unwrap();
+        // A single-core machine is capped by its one thread instead
+        if available > 1 {
+            assert_eq!(capped.reason, ExecutionReason::MaxThreads);
+            assert!(capped.to_string().contains("9 wanted"));
+        }
+        assert_eq!(capped.bytes_per_core(), Some(10_000 / available));
+
+        let fixed = ExecutionMode::Parallel { threads: Some(3) }.decide(10, &policy);
+        assert_eq!(
+            (fixed.thread_count, fixed.reason),
+            (3, ExecutionReason::Fixed)
+        );
+        assert!(fixed.to_string().starts_with("parallel, 3 threads: fixed"));
+    }
 }
@@ -0,0 +1,221 @@
+//! Re-segmentation of a text as it is edited.
+//!
+//! A chunk's partial state depends on the chunk's text alone, so a text kept
+//! as a sequence of scanned chunks only needs the chunks an edit touches
+//! rescanned; every other state is reused as is, wherever the edit moved it.
+//! The combine and reduce phases then run over all states again, which costs
+//! time in the number of chunks and candidates but reads no text. Output is
+//! identical to
+//! [`DeltaStackProcessor::process_with_progress`](super::DeltaStackProcessor::process_with_progress)
+//! on the edited text, since cut placement never affects the result.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::{
+    application::chunking::{chunk_spans_with, ChunkPolicy},
+    domain::state::{scan_chunk_into, Candidate, CandidateVec, CompiledRules, PartialState},
+};
+
+use super::delta_stack::{combine_and_reduce, DeltaStackResult};
+use super::execution_mode::ExecutionDecision;
+
+/// Largest chunk of a document: every edit rescans at least one chunk, so
+/// chunks stay far smaller than the ones sized for parallel runs
+const MAX_CHUNK_SIZE: usize = 8 * 1024;
+
+/// A text scanned chunk by chunk, kept up to date under edits; created by
+/// [`super::DeltaStackProcessor::document`].
+pub struct DeltaStackDocument {
+    rules: Arc<CompiledRules>,
+    chunk_size: usize,
+    chunk_policy: ChunkPolicy,
+    /// Scanned chunks in text order, each in chunk-local coordinates
+    chunks: Vec<PartialState>,
+}
+
+impl DeltaStackDocument {
+    pub(crate) fn new(
+        rules: Arc<CompiledRules>,
+        chunk_size: usize,
+        chunk_policy: ChunkPolicy,
+        text: &str,
+    ) -> Self {
+        let mut document = Self {
+            rules,
+            chunk_size: chunk_size.min(MAX_CHUNK_SIZE),
+            chunk_policy,
+            chunks: Vec::new(),
+        };
+        document.chunks = document.scan(text);
+        document
+    }
+
+    /// Whether the cached states were scanned with `rules`
+    pub(crate) fn uses(&self, rules: &Arc<CompiledRules>) -> bool {
+        Arc::ptr_eq(&self.rules, rules)
+    }
+
+    /// Updates the chunks after the bytes in `range` of the previous text
+    /// were replaced by `inserted` bytes, giving `text`; returns the number
+    /// of chunks rescanned.
+    ///
+    /// The chunks overlapping `range` are rescanned (an insertion touches
+    /// the chunk it lands in), together with the chunks after them while the
+    /// rescanned span is under half a chunk, so repeated deletions do not
+    /// leave slivers behind.
+    pub fn edit(&mut self, text: &str, range: Range<usize>, inserted: usize) -> usize {
+        if self.chunks.is_empty() {
+            self.chunks = self.scan(text);
+            return self.chunks.len();
+        }
+
+        // First and last chunks touched, and where the first one starts
+        let mut first = 0;
+        let mut span_start = 0;
+        while first + 1 < self.chunks.len()
+            && span_start + self.chunks[first].chunk_len <= range.start
+        {
+            span_start += self.chunks[first].chunk_len;
+            first += 1;
+        }
+        let mut last = first;
+        let mut span_end = span_start + self.chunks[first].chunk_len;
+        while span_end < range.end && last + 1 < self.chunks.len() {
+            last += 1;
+            span_end += self.chunks[last].chunk_len;
+        }
+        let mut new_end = span_end - range.len() + inserted;
+        while new_end - span_start < self.chunk_size / 2 && last + 1 < self.chunks.len() {
+            last += 1;
+            new_end += self.chunks[last].chunk_len;
+        }
+
+        let rescanned = self.scan(&text[span_start..new_end]);
+        let count = rescanned.len();
+        self.chunks.splice(first..=last, rescanned);
+        count
+    }
+
+    /// Boundaries of the current text, as
+    /// [`process_with_progress`](super::DeltaStackProcessor::process_with_progress)
+    /// finds them
+    pub fn result(&self) -> DeltaStackResult {
+        if self.chunks.is_empty() {
+            return DeltaStackResult {
+                boundaries: Vec::new(),
+                strong: Vec::new(),
+                chunk_count: 0,
+                execution: ExecutionDecision::sequential(0, self.chunk_size),
+            };
+        }
+
+        // The combine consumes the states; only the candidate buffers are
+        // left out of the copies, read in place instead
+        let states = self
+            .chunks
+            .iter()
+            .map(|state| PartialState {
+                boundaries: CandidateVec::new(),
+                pending: state.pending.clone(),
+                pending_enc: state.pending_enc.clone(),
+                deltas: state.deltas.clone(),
+                parity: state.parity,
+                head_ctx: state.head_ctx.clone(),
+                tail_ctx: state.tail_ctx.clone(),
+                chunk_len: state.chunk_len,
+            })
+            .collect();
+        let bulk: Vec<&[Candidate]> = self
+            .chunks
+            .iter()
+            .map(|state| state.boundaries.as_slice())
+            .collect();
+        let (boundaries, strong) = combine_and_reduce(&self.rules, states, &bulk, None);
+        DeltaStackResult {
+            boundaries,
+            strong,
+            chunk_count: self.chunks.len(),
+            execution: ExecutionDecision::sequential(
+                self.chunks.iter().map(|state| state.chunk_len).sum(),
+                self.chunk_size,
+            ),
+        }
+    }
+
+    fn scan(&self, text: &str) -> Vec<PartialState> {
+        chunk_spans_with(text, self.chunk_size, self.chunk_policy)
+            .into_iter()
+            .map(|chunk| scan_chunk_into(chunk, &self.rules, CandidateVec::new()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::{config::ProcessorConfig, DeltaStackProcessor, ExecutionMode};
+
+    /// Apply each edit in turn and compare with a fresh run over the result
+    fn check_edits(text: &str, edits: &[(std::ops::Range<usize>, &str)]) {
+        let processor = DeltaStackProcessor::from_language_code(ProcessorConfig::default(), "en")
+            .unwrap()
+            .with_chunk_size(16);
+        let mut text = text.to_string();
+        let mut document = processor.document(&text);
+        for (range, replacement) in edits {
+            text.replace_range(range.clone(), replacement);
+            document.edit(&text, range.clone(), replacement.len());
+            let expected = processor.process(&text, ExecutionMode::Sequential).unwrap();
+            let actual = document.result();
+            assert_eq!(actual.boundaries, expected.boundaries, "{text:?}");
+            assert_eq!(actual.strong, expected.strong, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_edits_match_fresh_processing() {
+        let text = "Hello world. This is (a test. Really.) Dr. Smith left! Did he? Yes.";
+        check_edits(
+            text,
+            &[
+                (5..5, " there"),
+                (20..22, ""),
+                (0..0, "Start. "),
+                (30..45, "x"),
+                (60..60, " More text here."),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_edits_at_chunk_seams_and_ends() {
+        let text = "One two three. Four five six. Seven eight nine. Ten.";
+        let len = text.len();
+        check_edits(
+            text,
+            &[
+                (16..16, "("),
+                (32..32, ")"),
+                (len + 2..len + 2, " Eleven."),
+                (0..16, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_deleting_everything_and_starting_over() {
+        let text = "Short one. Another.";
+        check_edits(text, &[(0..text.len(), ""), (0..0, "Fresh text. Again!")]);
+    }
+
+    #[test]
+    fn test_edit_rescans_only_nearby_chunks() {
+        let processor = DeltaStackProcessor::from_language_code(ProcessorConfig::default(), "en")
+            .unwrap()
+            .with_chunk_size(16);
+        let mut text = "Some sentence here. ".repeat(50);
+        let mut document = processor.document(&text);
+        text.replace_range(500..500, "New one. ");
+        assert!(document.edit(&text, 500..500, 9) <= 2);
+    }
+}
@@ -26,11 +26,23 @@
 //! let result = processor.process(Input::from_text(text)).unwrap();
 //! ```
 
+pub(crate) mod balance;
+pub mod chunk_cache;
+pub mod chunk_sizing;
 pub(crate) mod chunking;
 pub mod config;
 pub mod delta_stack;
 pub mod execution_mode;
+pub mod incremental;
+mod overlapped;
+pub mod streaming;
 
+pub use balance::DepthOverflow;
+pub(crate) use balance::{BalanceOptions, DepthCap};
+pub use chunk_cache::{ChunkCache, ChunkCacheStats};
+pub use chunking::ChunkPolicy;
 pub use config::ProcessorConfig;
-pub use delta_stack::{DeltaStackProcessor, DeltaStackResult};
-pub use execution_mode::ExecutionMode;
+pub use delta_stack::{DeltaStackProcessor, DeltaStackResult, Progress, ProgressCallback};
+pub use execution_mode::{AdaptivePolicy, ExecutionDecision, ExecutionMode, ExecutionReason};
+pub use incremental::DeltaStackDocument;
+pub use streaming::{DeltaStackStream, StreamState};
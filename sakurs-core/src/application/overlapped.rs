@@ -0,0 +1,194 @@
+//! Scanning overlapped with reading.
+//!
+//! When text arrives in pieces (a file read block by block), the chunks of
+//! each piece are handed to the thread pool as soon as the piece is there,
+//! so reading the next piece and scanning the previous ones run at the same
+//! time instead of one after the other. The states of all pieces then go
+//! through the usual combine and reduce; since cut placement never affects
+//! the result, the boundaries equal those of
+//! [`DeltaStackProcessor::process_with_progress`] on the whole text.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use crate::{
+    application::{chunking::chunk_spans_with, config::ProcessingError},
+    domain::state::{Candidate, CandidateVec, PartialState},
+};
+
+use super::delta_stack::{combine_and_reduce, DeltaStackProcessor, DeltaStackResult};
+use super::{ExecutionDecision, ExecutionMode, Progress, ProgressCallback};
+
+impl DeltaStackProcessor {
+    /// Process the text made of `pieces`, scanning each piece while the
+    /// next is produced; returns the whole text with its result
+    ///
+    /// `expected_len` is the length the text is expected to have (a file's
+    /// size, say): it chooses the thread count in adaptive modes and is the
+    /// total of the progress reports. Pieces are scanned with the configured
+    /// chunk size, never chosen per text. The first error of `pieces` ends
+    /// processing.
+    pub fn process_pieces<E: From<ProcessingError>>(
+        &self,
+        pieces: impl Iterator<Item = Result<String, E>>,
+        mode: ExecutionMode,
+        expected_len: usize,
+        progress: Option<ProgressCallback<'_>>,
+    ) -> Result<(String, DeltaStackResult), E> {
+        let execution = ExecutionDecision {
+            chunk_size: self.chunk_size,
+            ..mode.decide(expected_len, &self.adaptive)
+        };
+        let thread_count = execution.thread_count;
+        let rules = self.rules.as_ref();
+        let scanned = AtomicUsize::new(0);
+        let scan = |piece: &str| -> Vec<PartialState> {
+            chunk_spans_with(piece, self.chunk_size, self.chunk_policy)
+                .into_iter()
+                .map(|chunk| {
+                    let state = self.scan_chunk(chunk, CandidateVec::new());
+                    if let Some(report) = progress {
+                        report(Progress {
+                            processed_bytes: scanned.fetch_add(chunk.len(), Ordering::Relaxed)
+                                + chunk.len(),
+                            total_bytes: expected_len,
+                        });
+                    }
+                    state
+                })
+                .collect()
+        };
+
+        let pool = if thread_count > 1 {
+            Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(thread_count)
+                    .build()
+                    .map_err(|e| ProcessingError::InvalidConfig {
+                        reason: format!("Failed to create thread pool: {e}"),
+                    })?,
+            )
+        } else {
+            None
+        };
+        // Pieces with their states, in text order
+        let mut scanned_pieces: Vec<(String, Vec<PartialState>)> = Vec::new();
+        if let Some(pool) = &pool {
+            let (tx, rx) = mpsc::channel();
+            pool.in_place_scope(|scope| {
+                for (index, piece) in pieces.enumerate() {
+                    let piece = piece?;
+                    let tx = tx.clone();
+                    let scan = &scan;
+                    scope.spawn(move |_| {
+                        let states = scan(&piece);
+                        // The receiver outlives the scope
+                        let _ = tx.send((index, piece, states));
+                    });
+                }
+                Ok::<_, E>(())
+            })?;
+            drop(tx);
+            let mut indexed: Vec<_> = rx.into_iter().collect();
+            indexed.sort_unstable_by_key(|&(index, ..)| index);
+            scanned_pieces = indexed
+                .into_iter()
+                .map(|(_, piece, states)| (piece, states))
+                .collect();
+        } else {
+            for piece in pieces {
+                let piece = piece?;
+                let states = scan(&piece);
+                scanned_pieces.push((piece, states));
+            }
+        }
+
+        let mut text = String::with_capacity(scanned_pieces.iter().map(|(p, _)| p.len()).sum());
+        let mut states = Vec::new();
+        for (piece, piece_states) in scanned_pieces {
+            text.push_str(&piece);
+            states.extend(piece_states);
+        }
+        let chunk_count = states.len();
+        if text.is_empty() {
+            return Ok((
+                text,
+                DeltaStackResult {
+                    boundaries: Vec::new(),
+                    strong: Vec::new(),
+                    chunk_count: 0,
+                    execution: ExecutionDecision::sequential(0, self.chunk_size),
+                },
+            ));
+        }
+
+        let bulk: Vec<CandidateVec> = states
+            .iter_mut()
+            .map(|state| std::mem::take(&mut state.boundaries))
+            .collect();
+        let slices: Vec<&[Candidate]> = bulk.iter().map(|buf| buf.as_slice()).collect();
+        let (boundaries, strong) = combine_and_reduce(rules, states, &slices, pool.as_ref());
+        Ok((
+            text,
+            DeltaStackResult {
+                boundaries,
+                strong,
+                chunk_count,
+                execution,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::{
+        config::{ProcessingError, ProcessorConfig},
+        DeltaStackProcessor, ExecutionMode,
+    };
+
+    #[test]
+    fn test_pieces_match_whole_text() {
+        let processor = DeltaStackProcessor::from_language_code(ProcessorConfig::default(), "ja")
+            .unwrap()
+            .with_chunk_size(16);
+        let text =
+            "「こんにちは。」と彼は言った。（今日は晴れ！明日は？）Dr. Smith left. End".repeat(8);
+        let expected = processor.process(&text, ExecutionMode::Sequential).unwrap();
+
+        let chars: Vec<char> = text.chars().collect();
+        for (piece_chars, mode) in [
+            (3, ExecutionMode::Sequential),
+            (40, ExecutionMode::Parallel { threads: Some(4) }),
+        ] {
+            let pieces = chars
+                .chunks(piece_chars)
+                .map(|piece| Ok::<_, ProcessingError>(piece.iter().collect::<String>()));
+            let (joined, result) = processor
+                .process_pieces(pieces, mode, text.len(), None)
+                .unwrap();
+            assert_eq!(joined, text);
+            assert_eq!(result.boundaries, expected.boundaries);
+            assert_eq!(result.strong, expected.strong);
+        }
+    }
+
+    #[test]
+    fn test_piece_error_ends_processing() {
+        let processor =
+            DeltaStackProcessor::from_language_code(ProcessorConfig::default(), "en").unwrap();
+        for mode in [
+            ExecutionMode::Sequential,
+            ExecutionMode::Parallel { threads: Some(2) },
+        ] {
+            let pieces = [
+                Ok("One. ".to_string()),
+                Err(ProcessingError::InvalidConfig {
+                    reason: "read failed".into(),
+                }),
+            ];
+            let result = processor.process_pieces(pieces.into_iter(), mode, 10, None);
+            assert!(result.is_err());
+        }
+    }
+}
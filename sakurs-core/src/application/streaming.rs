@@ -0,0 +1,221 @@
+//! Incremental driver for text that arrives in pieces.
+//!
+//! The prefix fold of the Δ-Stack Monoid is already a left-to-right
+//! accumulation, so it doubles as a streaming executor: each piece is scanned
+//! and absorbed into the accumulator as it arrives, and every candidate the
+//! accumulator has confirmed is final — pending items only ever sit within
+//! `k` characters of a text edge, after every confirmed candidate. The one
+//! exception is the start of text, whose pending items lack left context for
+//! good; they are settled as soon as the head buffer fills (see
+//! [`PartialState::resolve_text_start`]). Output is identical to
+//! [`DeltaStackProcessor::process_with_progress`](super::DeltaStackProcessor::process_with_progress)
+//! on the concatenated text, however the pieces are cut.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    application::chunking::{chunk_spans_with, ChunkPolicy},
+    domain::state::{scan_chunk_into, CandidateVec, CompiledRules, PartialState},
+};
+
+use super::delta_stack::is_boundary;
+
+/// Streaming form of [`super::DeltaStackProcessor`]: feed text pieces in
+/// order, collect boundaries as they become final.
+pub struct DeltaStackStream {
+    rules: Arc<CompiledRules>,
+    chunk_size: usize,
+    chunk_policy: ChunkPolicy,
+    acc: PartialState,
+    text_start_settled: bool,
+    /// Candidate buffer reused by every chunk scan
+    scratch: CandidateVec,
+}
+
+/// What a [`DeltaStackStream`] carries from one piece to the next: the
+/// accumulated state with its held-back candidates and context. Enough to
+/// resume the stream in another process.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamState {
+    acc: PartialState,
+    text_start_settled: bool,
+}
+
+impl StreamState {
+    /// Total bytes fed to the stream the state was taken from.
+    pub fn bytes_fed(&self) -> usize {
+        self.acc.chunk_len
+    }
+}
+
+impl DeltaStackStream {
+    pub(crate) fn new(
+        rules: Arc<CompiledRules>,
+        chunk_size: usize,
+        chunk_policy: ChunkPolicy,
+    ) -> Self {
+        Self::resume(
+            rules,
+            chunk_size,
+            chunk_policy,
+            StreamState {
+                acc: PartialState::identity(),
+                text_start_settled: false,
+            },
+        )
+    }
+
+    /// Continues the stream whose state was `state`, taken with
+    /// [`Self::state`] by a processor with the same rules.
+    pub(crate) fn resume(
+        rules: Arc<CompiledRules>,
+        chunk_size: usize,
+        chunk_policy: ChunkPolicy,
+        state: StreamState,
+    ) -> Self {
+        Self {
+            rules,
+            chunk_size,
+            chunk_policy,
+            acc: state.acc,
+            text_start_settled: state.text_start_settled,
+            scratch: CandidateVec::new(),
+        }
+    }
+
+    /// The state to resume this stream from, as of the text fed so far.
+    pub fn state(&self) -> StreamState {
+        StreamState {
+            acc: self.acc.clone(),
+            text_start_settled: self.text_start_settled,
+        }
+    }
+
+    /// Appends `text` to the stream and returns the boundaries (global byte
+    /// offsets, ascending) that became final. Boundaries within `k`
+    /// characters of the current end are held back until more text arrives
+    /// or the stream is finished.
+    pub fn feed(&mut self, text: &str) -> Vec<usize> {
+        let rules = self.rules.as_ref();
+        for chunk in chunk_spans_with(text, self.chunk_size, self.chunk_policy) {
+            let mut state = scan_chunk_into(chunk, rules, std::mem::take(&mut self.scratch));
+            self.acc.absorb(&state, rules);
+            state.boundaries.clear();
+            self.scratch = state.boundaries;
+        }
+        if !self.text_start_settled {
+            if !self.acc.head_ctx.is_full() {
+                return Vec::new();
+            }
+            self.acc.resolve_text_start(rules);
+            self.text_start_settled = true;
+        }
+        self.drain_boundaries()
+    }
+
+    /// Ends the stream, returning the boundaries still held back.
+    pub fn finish(mut self) -> Vec<usize> {
+        let acc = std::mem::replace(&mut self.acc, PartialState::identity());
+        (self.acc, _) = acc.resolve_edges_full(self.rules.as_ref());
+        self.drain_boundaries()
+    }
+
+    /// Total bytes fed so far.
+    pub fn bytes_fed(&self) -> usize {
+        self.acc.chunk_len
+    }
+
+    fn drain_boundaries(&mut self) -> Vec<usize> {
+        self.acc
+            .boundaries
+            .drain(..)
+            .filter(|c| is_boundary(&self.rules, c))
+            .map(|c| c.local_offset)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::{DeltaStackProcessor, ExecutionMode, ProcessorConfig};
+
+    fn processor(code: &str) -> DeltaStackProcessor {
+        DeltaStackProcessor::from_language_code(ProcessorConfig::default(), code).unwrap()
+    }
+
+    fn streamed(processor: &DeltaStackProcessor, text: &str, piece: usize) -> Vec<usize> {
+        let mut stream = processor.stream();
+        let mut boundaries = Vec::new();
+        let mut start = 0;
+        while start < text.len() {
+            let mut end = (start + piece).min(text.len());
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+            boundaries.extend(stream.feed(&text[start..end]));
+            start = end;
+        }
+        boundaries.extend(stream.finish());
+        boundaries
+    }
+
+    #[test]
+    fn matches_batch_for_every_piece_size() {
+        let cases = [
+            ("en", "He said (\"Stop. Now.\") and left. Dr. Smith arrived... Then Mr. Jones spoke! 'Twas the night. It's 3.14 today. Really?"),
+            ("ja", "「こんにちは。元気？」と彼は言った。今日は晴れです！（注：これは例です。）本当に…そうですか。"),
+        ];
+        for (code, sentence) in cases {
+            let p = processor(code);
+            let text = sentence.repeat(4);
+            let batch = p
+                .process(&text, ExecutionMode::Sequential)
+                .unwrap()
+                .boundaries;
+            for piece in [1, 3, 7, 16, 64, 200, text.len()] {
+                assert_eq!(streamed(&p, &text, piece), batch, "{code} piece={piece}");
+            }
+        }
+    }
+
+    #[test]
+    fn emits_boundaries_before_the_end() {
+        let p = processor("en");
+        let mut stream = p.stream();
+        let early = stream.feed(&"This is a sentence. ".repeat(20));
+        assert!(!early.is_empty());
+        assert!(early.windows(2).all(|w| w[0] < w[1]));
+        let rest = stream.finish();
+        assert_eq!(early.len() + rest.len(), 20);
+    }
+
+    #[test]
+    fn resumes_from_a_state_mid_stream() {
+        let p = processor("en");
+        let text =
+            "He said (\"Stop. Now.\") and left. Dr. Smith arrived... Then he spoke! ".repeat(6);
+        let (head, tail) = text.split_at(100);
+        let mut stream = p.stream();
+        let mut boundaries = stream.feed(head);
+        let state = stream.state();
+        drop(stream);
+
+        let mut resumed = p.stream_from(state);
+        assert_eq!(resumed.bytes_fed(), 100);
+        boundaries.extend(resumed.feed(tail));
+        boundaries.extend(resumed.finish());
+        let batch = p.process(&text, ExecutionMode::Sequential).unwrap();
+        assert_eq!(boundaries, batch.boundaries);
+    }
+
+    #[test]
+    fn short_text_is_held_until_finish() {
+        let p = processor("en");
+        let mut stream = p.stream();
+        assert!(stream.feed("Hi. Bye.").is_empty());
+        assert_eq!(stream.bytes_fed(), 8);
+        assert_eq!(stream.finish(), vec![3, 8]);
+    }
+}
@@ -1,58 +1,36 @@
+use super::table::{embedded_index, embedded_tables};
 use super::types::LanguageConfig;
 use crate::domain::error::DomainError;
-use std::collections::HashMap;
 use std::sync::OnceLock;
 
-static LANGUAGE_CONFIGS: OnceLock<HashMap<String, LanguageConfig>> = OnceLock::new();
+/// Configurations of the bundled languages, built from their static tables on
+/// first use, in the tables' (code) order
+static LANGUAGE_CONFIGS: OnceLock<Vec<LanguageConfig>> = OnceLock::new();
 
-macro_rules! embed_language_config {
-    ($code:expr, $path:expr) => {
-        ($code, include_str!($path))
-    };
+fn configs() -> &'static [LanguageConfig] {
+    LANGUAGE_CONFIGS.get_or_init(|| embedded_tables().iter().map(|t| t.to_config()).collect())
 }
 
-fn load_embedded_configs() -> Result<HashMap<String, LanguageConfig>, DomainError> {
-    let mut configs = HashMap::new();
-
-    let embedded_configs = [
-        embed_language_config!("en", "../../../../configs/languages/english.toml"),
-        embed_language_config!("ja", "../../../../configs/languages/japanese.toml"),
-    ];
-
-    for (code, toml_content) in embedded_configs {
-        let config: LanguageConfig = toml::from_str(toml_content).map_err(|e| {
-            DomainError::ConfigurationError(format!("Failed to parse {code} config: {e}"))
-        })?;
-
-        // Validate that the config code matches
-        if config.metadata.code != code {
-            return Err(DomainError::ConfigurationError(format!(
-                "Config code mismatch: expected {}, got {}",
-                code, config.metadata.code
-            )));
-        }
-
-        configs.insert(code.to_string(), config);
-    }
-
-    Ok(configs)
+pub fn get_language_config(code: &str) -> Result<&'static LanguageConfig, DomainError> {
+    embedded_index(code)
+        .map(|index| &configs()[index])
+        .ok_or_else(|| DomainError::UnsupportedLanguage(code.to_string()))
 }
 
-pub fn get_language_config(code: &str) -> Result<&'static LanguageConfig, DomainError> {
-    let configs = LANGUAGE_CONFIGS
-        .get_or_init(|| load_embedded_configs().expect("Failed to load embedded language configs"));
+/// Every embedded language configuration, sorted by code
+pub fn embedded_language_configs() -> Vec<&'static LanguageConfig> {
+    configs().iter().collect()
+}
 
-    configs
-        .get(code)
-        .ok_or_else(|| DomainError::UnsupportedLanguage(code.to_string()))
+/// Position of `config` among the embedded configurations, if it is one of
+/// them (rather than, say, a copy or an external file with the same code)
+pub(crate) fn embedded_position(config: &LanguageConfig) -> Option<usize> {
+    embedded_index(&config.metadata.code).filter(|&index| std::ptr::eq(&configs()[index], config))
 }
 
 #[cfg(test)]
 pub fn list_available_languages() -> Vec<&'static str> {
-    let configs = LANGUAGE_CONFIGS
-        .get_or_init(|| load_embedded_configs().expect("Failed to load embedded language configs"));
-
-    configs.keys().map(|s| s.as_str()).collect()
+    embedded_tables().iter().map(|t| t.code).collect()
 }
 
 #[cfg(test)]
@@ -89,14 +67,29 @@ mod tests {
         let languages = list_available_languages();
         assert!(languages.contains(&"en"));
         assert!(languages.contains(&"ja"));
-        assert_eq!(languages.len(), 2);
+        assert!(languages.contains(&"pt"));
+        assert!(languages.contains(&"it"));
+        assert!(languages.contains(&"ar"));
+        assert!(languages.contains(&"th"));
+        assert!(languages.contains(&"hi"));
+        assert_eq!(languages.len(), 7);
     }
 
     #[test]
     fn test_list_available_languages_sorted() {
         let mut languages = list_available_languages();
         languages.sort();
-        assert_eq!(languages, vec!["en", "ja"]);
+        assert_eq!(languages, vec!["ar", "en", "hi", "it", "ja", "pt", "th"]);
+    }
+
+    #[test]
+    fn test_embedded_language_configs_sorted() {
+        let codes: Vec<&str> = embedded_language_configs()
+            .iter()
+            .map(|config| config.metadata.code.as_str())
+            .collect();
+        assert_eq!(codes, ["ar", "en", "hi", "it", "ja", "pt", "th"]);
+        assert!(embedded_language_configs()[1].abbreviation_count() > 0);
     }
 
     #[test]
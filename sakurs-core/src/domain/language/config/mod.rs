@@ -1,7 +1,11 @@
 mod loader;
+mod schema;
+mod table;
 mod types;
 
-pub use loader::get_language_config;
+pub(crate) use loader::embedded_position;
 #[cfg(test)]
 pub(crate) use loader::list_available_languages;
+pub use loader::{embedded_language_configs, get_language_config};
+pub use schema::{SchemaReport, SCHEMA_VERSION};
 pub use types::*;
@@ -0,0 +1,248 @@
+//! Versions of the language configuration format
+//!
+//! A language file names the version of the format it was written for in a
+//! top-level `schema_version` key; files without one predate the key and
+//! are version 1. Keys renamed by later versions are migrated when the file
+//! is loaded, and keys the format does not define are reported instead of
+//! being silently ignored.
+
+use toml::{Table, Value};
+
+use super::types::LanguageConfig;
+
+/// Version of the language configuration format this release reads and
+/// writes
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Keys renamed by a version of the format
+struct Migration {
+    /// The version that renamed the keys
+    version: u32,
+    /// Dotted paths of the old keys and their new names; a path through an
+    /// array of tables renames the key in every element
+    renames: &'static [(&'static str, &'static str)],
+}
+
+/// Key renames of every version after the first, oldest first
+const MIGRATIONS: &[Migration] = &[];
+
+/// What loading a language file found out about its keys
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaReport {
+    /// The `schema_version` the file was written for
+    pub version: u32,
+    /// Keys renamed since that version, as `(old, new)` dotted paths; they
+    /// were migrated
+    pub renamed: Vec<(String, String)>,
+    /// Keys the format does not define, as dotted paths; they were ignored
+    pub unknown: Vec<String>,
+}
+
+impl SchemaReport {
+    /// Whether the file uses the current keys only
+    pub fn is_clean(&self) -> bool {
+        self.renamed.is_empty() && self.unknown.is_empty()
+    }
+}
+
+/// Parse a language file, migrating it from the version it was written for
+pub(super) fn parse(content: &str) -> Result<(LanguageConfig, SchemaReport), String> {
+    parse_with(content, MIGRATIONS)
+}
+
+fn parse_with(
+    content: &str,
+    migrations: &[Migration],
+) -> Result<(LanguageConfig, SchemaReport), String> {
+    let mut table: Table = toml::from_str(content).map_err(|e| e.to_string())?;
+    let version = match table.remove("schema_version") {
+        None => 1,
+        Some(Value::Integer(version)) if version >= 1 => u32::try_from(version).unwrap_or(u32::MAX),
+        Some(value) => {
+            return Err(format!(
+                "schema_version must be a positive integer, got {value}"
+            ))
+        }
+    };
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "schema_version {version} is newer than this release of sakurs reads \
+             ({SCHEMA_VERSION}); upgrade sakurs to use this file"
+        ));
+    }
+
+    let mut renamed = Vec::new();
+    for migration in migrations.iter().filter(|m| m.version > version) {
+        for &(old, new) in migration.renames {
+            let path: Vec<&str> = old.split('.').collect();
+            if rename(&mut table, &path, new) {
+                let new = match old.rsplit_once('.') {
+                    Some((parent, _)) => format!("{parent}.{new}"),
+                    None => new.to_string(),
+                };
+                renamed.push((old.to_string(), new));
+            }
+        }
+    }
+
+    let mut unknown = Vec::new();
+    let collect = |path: serde_ignored::Path<'_>| unknown.push(dotted(&path));
+    let config = if renamed.is_empty() {
+        // From the text itself, so errors point at a line
+        let deserializer = toml::Deserializer::parse(content).map_err(|e| e.to_string())?;
+        serde_ignored::deserialize(deserializer, collect).map_err(|e| e.to_string())?
+    } else {
+        serde_ignored::deserialize(Value::Table(table), collect).map_err(|e| e.to_string())?
+    };
+    unknown.retain(|key| key != "schema_version");
+
+    let report = SchemaReport {
+        version,
+        renamed,
+        unknown,
+    };
+    Ok((config, report))
+}
+
+/// Rename the key at `path` to `new`, in every element of the arrays of
+/// tables along the way, unless `new` is already set; whether any key was
+/// renamed
+fn rename(table: &mut Table, path: &[&str], new: &str) -> bool {
+    match path {
+        [] => false,
+        [old] => {
+            if table.contains_key(new) {
+                return false;
+            }
+            match table.remove(*old) {
+                Some(value) => {
+                    table.insert(new.to_string(), value);
+                    true
+                }
+                None => false,
+            }
+        }
+        [key, rest @ ..] => match table.get_mut(*key) {
+            Some(Value::Table(inner)) => rename(inner, rest, new),
+            Some(Value::Array(items)) => {
+                let mut renamed = false;
+                for inner in items.iter_mut().filter_map(Value::as_table_mut) {
+                    renamed |= rename(inner, rest, new);
+                }
+                renamed
+            }
+            _ => false,
+        },
+    }
+}
+
+/// `path` as dotted keys, with array elements by index
+fn dotted(path: &serde_ignored::Path<'_>) -> String {
+    use serde_ignored::Path;
+
+    let (parent, segment) = match path {
+        Path::Root => return String::new(),
+        Path::Seq { parent, index } => (parent, index.to_string()),
+        Path::Map { parent, key } => (parent, key.clone()),
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => return dotted(parent),
+    };
+    match dotted(parent) {
+        parent if parent.is_empty() => segment,
+        parent => format!("{parent}.{segment}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = r#"
+[metadata]
+code = "xx"
+name = "Test"
+
+[terminators]
+chars = ["."]
+
+[ellipsis]
+patterns = ["..."]
+
+[enclosures]
+pairs = [{ open = "(", close = ")" }]
+
+[suppression]
+"#;
+
+    #[test]
+    fn test_parse_reports_unknown_keys() {
+        let content = format!(
+            "schema_version = 1\ncolour = \"red\"\n{MINIMAL}\
+             [sentence_starters]\nthreshhold = [\"Then\"]\n"
+        );
+        let content = content.replace("close = \")\"", "close = \")\", symetric = false");
+        let (config, report) = parse(&content).unwrap();
+        assert_eq!(config.metadata.code, "xx");
+        assert_eq!(report.version, 1);
+        assert!(report.renamed.is_empty());
+        // Unknown sentence starter keys are categories
+        assert_eq!(
+            report.unknown,
+            ["colour", "enclosures.pairs.0.symetric"].map(String::from)
+        );
+        assert!(!report.is_clean());
+
+        let (_, report) = parse(MINIMAL).unwrap();
+        assert_eq!(report.version, 1);
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn test_parse_rejects_unreadable_versions() {
+        for version in ["0", "\"1\"", &(SCHEMA_VERSION + 1).to_string()] {
+            let content = format!("schema_version = {version}\n{MINIMAL}");
+            assert!(parse(&content).is_err(), "{version}");
+        }
+    }
+
+    #[test]
+    fn test_parse_migrates_renamed_keys() {
+        let migrations = [
+            Migration {
+                version: 1,
+                renames: &[("metadata.label", "name")],
+            },
+            Migration {
+                version: 2,
+                renames: &[
+                    ("metadata.title", "name"),
+                    ("enclosures.pairs.mirrored", "symmetric"),
+                    ("missing.key", "other"),
+                ],
+            },
+        ];
+        let content = MINIMAL
+            .replace("name = ", "title = ")
+            .replace("close = \")\"", "close = \")\", mirrored = true");
+        let (config, report) = parse_with(&content, &migrations).unwrap();
+        assert_eq!(config.metadata.name, "Test");
+        assert!(config.enclosures.pairs[0].symmetric);
+        assert_eq!(
+            report.renamed,
+            [
+                ("metadata.title", "metadata.name"),
+                ("enclosures.pairs.mirrored", "enclosures.pairs.symmetric"),
+            ]
+            .map(|(old, new)| (old.to_string(), new.to_string()))
+        );
+        assert!(report.unknown.is_empty());
+
+        // A file written for the version that renamed a key keeps it as is
+        let content = format!("schema_version = 1\n{MINIMAL}")
+            .replace("name = \"Test\"", "name = \"Test\"\nlabel = \"Test\"");
+        let (_, report) = parse_with(&content, &migrations).unwrap();
+        assert!(report.renamed.is_empty());
+        assert_eq!(report.unknown, ["metadata.label"]);
+    }
+}
@@ -0,0 +1,312 @@
+//! Static tables of the bundled language configurations
+//!
+//! `build.rs` parses `configs/languages/*.toml` at build time and emits one
+//! [`LanguageTable`] per language into `EMBEDDED`, sorted by code; looking a
+//! language up is a binary search and turning it into a [`LanguageConfig`]
+//! copies strings without parsing anything.
+
+use super::types::{
+    AbbreviationConfig, AbbreviationEntry, AbbreviationRule, ApostropheConfig, ContextRule,
+    EllipsisConfig, EnclosureBoundaries, EnclosureConfig, EnclosurePair, ExceptionPattern,
+    FastPattern, HypothesisConfig, LanguageConfig, MetadataConfig, RegexPattern,
+    SentenceStarterConfig, StarterEntry, SuppressionConfig, TerminatorConfig, TerminatorPattern,
+};
+
+/// Named starter lists, sorted by name; each entry is `(word, weight)`
+/// with the weight of an inline-table entry
+type StarterCategories = &'static [(&'static str, &'static [(&'static str, Option<f64>)])];
+
+/// Named abbreviation lists, sorted by name; each entry is `(abbr, options)`
+/// with `Some((case_sensitive, followed_by))` for an inline-table entry
+type AbbreviationCategories = &'static [(
+    &'static str,
+    &'static [(&'static str, Option<(bool, Option<&'static str>)>)],
+)];
+
+/// A bundled language configuration as static data; fields mirror
+/// [`LanguageConfig`] with defaults already applied
+pub(crate) struct LanguageTable {
+    pub code: &'static str,
+    name: &'static str,
+    terminators: &'static [char],
+    /// (pattern, name)
+    terminator_patterns: &'static [(&'static str, &'static str)],
+    ordinal_indicators: &'static [char],
+    collapse_runs: bool,
+    emoji: bool,
+    ellipsis_treat_as_boundary: bool,
+    ellipsis_patterns: &'static [&'static str],
+    /// (condition, boundary)
+    ellipsis_context_rules: &'static [(&'static str, bool)],
+    /// (regex, boundary)
+    ellipsis_exceptions: &'static [(&'static str, bool)],
+    /// (open, close, symmetric)
+    enclosures: &'static [(char, char, bool, EnclosureBoundaries)],
+    /// (char, line_start, before, after)
+    fast_patterns: &'static [(char, bool, Option<&'static str>, Option<&'static str>)],
+    /// (pattern, description)
+    regex_patterns: &'static [(&'static str, Option<&'static str>)],
+    trailing_patterns: &'static [&'static str],
+    web_tokens: bool,
+    citations: bool,
+    apostrophes: Option<ApostropheTable>,
+    abbreviations: AbbreviationCategories,
+    sentence_starters: Option<StarterTable>,
+    hypothesis: Option<HypothesisTable>,
+}
+
+/// `[sentence_starters]` of a [`LanguageTable`]
+struct StarterTable {
+    categories: StarterCategories,
+    require_following_space: bool,
+    min_word_length: usize,
+    threshold: f64,
+    uppercase_fallback: Option<f64>,
+}
+
+/// `[suppression.apostrophes]` of a [`LanguageTable`]
+struct ApostropheTable {
+    chars: &'static [char],
+    contractions: bool,
+    possessives: bool,
+    years: bool,
+    elided_starts: &'static [&'static str],
+    elided_ends: &'static [&'static str],
+}
+
+/// `[hypothesis]` of a [`LanguageTable`]
+struct HypothesisTable {
+    strategy: &'static str,
+    min_chars: usize,
+    sentence_final_particles: &'static [&'static str],
+    discourse_markers: &'static [&'static str],
+    continuation_markers: &'static [&'static str],
+}
+
+include!(concat!(env!("OUT_DIR"), "/embedded_languages.rs"));
+
+/// Every bundled language, sorted by code
+pub(crate) fn embedded_tables() -> &'static [LanguageTable] {
+    &EMBEDDED
+}
+
+/// Position of the bundled language with this code in [`embedded_tables`]
+pub(crate) fn embedded_index(code: &str) -> Option<usize> {
+    EMBEDDED.binary_search_by(|table| table.code.cmp(code)).ok()
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+fn starter_categories(
+    categories: StarterCategories,
+) -> std::collections::HashMap<String, Vec<StarterEntry>> {
+    categories
+        .iter()
+        .map(|(name, entries)| {
+            let entries = entries
+                .iter()
+                .map(|&(word, weight)| match weight {
+                    None => StarterEntry::from(word),
+                    Some(weight) => StarterEntry::Weighted {
+                        word: word.to_string(),
+                        weight,
+                    },
+                })
+                .collect();
+            (name.to_string(), entries)
+        })
+        .collect()
+}
+
+fn abbreviation_categories(
+    categories: AbbreviationCategories,
+) -> std::collections::HashMap<String, Vec<AbbreviationEntry>> {
+    categories
+        .iter()
+        .map(|(name, entries)| {
+            let entries = entries
+                .iter()
+                .map(|&(abbr, options)| match options {
+                    None => AbbreviationEntry::from(abbr),
+                    Some((case_sensitive, followed_by)) => {
+                        AbbreviationEntry::Rule(AbbreviationRule {
+                            abbr: abbr.to_string(),
+                            case_sensitive,
+                            followed_by: followed_by.map(str::to_string),
+                        })
+                    }
+                })
+                .collect();
+            (name.to_string(), entries)
+        })
+        .collect()
+}
+
+impl LanguageTable {
+    /// The configuration this table was generated from
+    pub(crate) fn to_config(&self) -> LanguageConfig {
+        LanguageConfig {
+            metadata: MetadataConfig {
+                code: self.code.to_string(),
+                name: self.name.to_string(),
+            },
+            terminators: TerminatorConfig {
+                chars: self.terminators.to_vec(),
+                patterns: self
+                    .terminator_patterns
+                    .iter()
+                    .map(|(pattern, name)| TerminatorPattern {
+                        pattern: pattern.to_string(),
+                        name: name.to_string(),
+                    })
+                    .collect(),
+                ordinal_indicators: self.ordinal_indicators.to_vec(),
+                collapse_runs: self.collapse_runs,
+                emoji: self.emoji,
+            },
+            ellipsis: EllipsisConfig {
+                treat_as_boundary: self.ellipsis_treat_as_boundary,
+                patterns: strings(self.ellipsis_patterns),
+                context_rules: self
+                    .ellipsis_context_rules
+                    .iter()
+                    .map(|&(condition, boundary)| ContextRule {
+                        condition: condition.to_string(),
+                        boundary,
+                    })
+                    .collect(),
+                exceptions: self
+                    .ellipsis_exceptions
+                    .iter()
+                    .map(|&(regex, boundary)| ExceptionPattern {
+                        regex: regex.to_string(),
+                        boundary,
+                    })
+                    .collect(),
+            },
+            enclosures: EnclosureConfig {
+                pairs: self
+                    .enclosures
+                    .iter()
+                    .map(|&(open, close, symmetric, boundaries)| EnclosurePair {
+                        open,
+                        close,
+                        symmetric,
+                        boundaries,
+                    })
+                    .collect(),
+            },
+            suppression: SuppressionConfig {
+                fast_patterns: self
+                    .fast_patterns
+                    .iter()
+                    .map(|&(char, line_start, before, after)| FastPattern {
+                        char,
+                        line_start,
+                        before: before.map(str::to_string),
+                        after: after.map(str::to_string),
+                    })
+                    .collect(),
+                regex_patterns: self
+                    .regex_patterns
+                    .iter()
+                    .map(|&(pattern, description)| RegexPattern {
+                        pattern: pattern.to_string(),
+                        description: description.map(str::to_string),
+                    })
+                    .collect(),
+                trailing_patterns: strings(self.trailing_patterns),
+                web_tokens: self.web_tokens,
+                citations: self.citations,
+                apostrophes: self.apostrophes.as_ref().map(|a| ApostropheConfig {
+                    chars: a.chars.to_vec(),
+                    contractions: a.contractions,
+                    possessives: a.possessives,
+                    years: a.years,
+                    elided_starts: strings(a.elided_starts),
+                    elided_ends: strings(a.elided_ends),
+                }),
+            },
+            abbreviations: AbbreviationConfig {
+                categories: abbreviation_categories(self.abbreviations),
+            },
+            sentence_starters: self
+                .sentence_starters
+                .as_ref()
+                .map(|s| SentenceStarterConfig {
+                    categories: starter_categories(s.categories),
+                    require_following_space: s.require_following_space,
+                    min_word_length: s.min_word_length,
+                    threshold: s.threshold,
+                    uppercase_fallback: s.uppercase_fallback,
+                }),
+            hypothesis: self.hypothesis.as_ref().map(|h| HypothesisConfig {
+                strategy: h.strategy.to_string(),
+                min_chars: h.min_chars,
+                sentence_final_particles: strings(h.sentence_final_particles),
+                discourse_markers: strings(h.discourse_markers),
+                continuation_markers: strings(h.continuation_markers),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bundled language files, to check the tables against the TOML parser
+    const SOURCES: [(&str, &str); 7] = [
+        (
+            "ar",
+            include_str!("../../../../configs/languages/arabic.toml"),
+        ),
+        (
+            "en",
+            include_str!("../../../../configs/languages/english.toml"),
+        ),
+        (
+            "hi",
+            include_str!("../../../../configs/languages/hindi.toml"),
+        ),
+        (
+            "it",
+            include_str!("../../../../configs/languages/italian.toml"),
+        ),
+        (
+            "ja",
+            include_str!("../../../../configs/languages/japanese.toml"),
+        ),
+        (
+            "pt",
+            include_str!("../../../../configs/languages/portuguese.toml"),
+        ),
+        (
+            "th",
+            include_str!("../../../../configs/languages/thai.toml"),
+        ),
+    ];
+
+    #[test]
+    fn test_tables_match_parsed_configs() {
+        let codes: Vec<&str> = embedded_tables().iter().map(|t| t.code).collect();
+        assert_eq!(codes, SOURCES.map(|(code, _)| code));
+        for (code, source) in SOURCES {
+            let parsed: LanguageConfig = toml::from_str(source).unwrap();
+            let generated = embedded_tables()[embedded_index(code).unwrap()].to_config();
+            assert_eq!(
+                toml::Value::try_from(&generated).unwrap(),
+                toml::Value::try_from(&parsed).unwrap(),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_embedded_index_lookup() {
+        assert_eq!(embedded_tables()[embedded_index("ja").unwrap()].code, "ja");
+        assert!(embedded_index("xx").is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use super::schema::{SchemaReport, SCHEMA_VERSION};
 use crate::domain::error::DomainError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -13,6 +14,10 @@ pub struct LanguageConfig {
     pub abbreviations: AbbreviationConfig,
     #[serde(default)]
     pub sentence_starters: Option<SentenceStarterConfig>,
+    /// Boundary hypothesis strategy for scripts that do not rely on terminal
+    /// punctuation (absent: terminator-driven detection only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hypothesis: Option<HypothesisConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +31,19 @@ pub struct TerminatorConfig {
     pub chars: Vec<char>,
     #[serde(default)]
     pub patterns: Vec<TerminatorPattern>,
+    /// Characters that mark an ordinal number when they follow a period
+    /// after a digit ("1.º", "2.ª"); such a period is not a boundary
+    #[serde(default)]
+    pub ordinal_indicators: Vec<char>,
+    /// A run of terminators ("!!!", "?!?", "...!") ends one sentence, at its
+    /// last character
+    #[serde(default = "default_true")]
+    pub collapse_runs: bool,
+    /// Emoji sequences end a sentence when followed by a capital letter or
+    /// the end of text ("That was wild 😂😂 Next"), and join a sentence whose
+    /// terminator they follow ("Wow! 😂")
+    #[serde(default)]
+    pub emoji: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,6 +86,51 @@ pub struct EnclosurePair {
     pub close: char,
     #[serde(default)]
     pub symmetric: bool,
+    /// Whether sentences end inside the pair
+    #[serde(default)]
+    pub boundaries: EnclosureBoundaries,
+}
+
+/// Which boundaries an enclosure type lets through inside it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnclosureBoundaries {
+    /// No sentence ends inside (the default)
+    #[default]
+    Suppress,
+    /// Sentences end inside as they do outside: long parenthetical asides
+    /// are split into their sentences
+    Allow,
+    /// Only strong terminators (`!`, `?`, multi-character patterns) end a
+    /// sentence inside
+    StrongOnly,
+    /// Dialogue in fiction (「…」 in Japanese novels): sentences end inside
+    /// as they do outside, except that the closing bracket after the last
+    /// sentence's terminator belongs to that sentence and ends it where
+    /// the dialogue line ends. Asymmetric pairs only.
+    Dialogue,
+}
+
+impl EnclosureBoundaries {
+    /// Whether a boundary with this strength may end a sentence inside
+    pub fn permits(self, strong: bool) -> bool {
+        match self {
+            EnclosureBoundaries::Suppress => false,
+            EnclosureBoundaries::Allow | EnclosureBoundaries::Dialogue => true,
+            EnclosureBoundaries::StrongOnly => strong,
+        }
+    }
+
+    /// The configuration value: `suppress`, `allow`, `strong_only` or
+    /// `dialogue`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EnclosureBoundaries::Suppress => "suppress",
+            EnclosureBoundaries::Allow => "allow",
+            EnclosureBoundaries::StrongOnly => "strong_only",
+            EnclosureBoundaries::Dialogue => "dialogue",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,6 +139,80 @@ pub struct SuppressionConfig {
     pub fast_patterns: Vec<FastPattern>,
     #[serde(default)]
     pub regex_patterns: Vec<RegexPattern>,
+    /// Kaomoji and symbols that trail a sentence ("楽しかった！(笑)", "♪"):
+    /// directly after a terminator, any run of them belongs to its sentence,
+    /// which then ends after the run
+    #[serde(default)]
+    pub trailing_patterns: Vec<String>,
+    /// No boundaries inside URLs, DOIs, email addresses, @mentions and
+    /// #hashtags, nor at a period glued to a lowercase letter or digit
+    /// ("example.com", "p < .05")
+    #[serde(default)]
+    pub web_tokens: bool,
+    /// Citations and cross-references: an abbreviation followed by a number,
+    /// section sign or parenthesis ("Fed. R. Civ. P. 12", "U.S.C. § 1983",
+    /// "Fig. 3", "et al. (2020)"), a period followed by a comma or semicolon
+    /// ("Educ., 347 U.S.", "e.g.,") and the period of a line-start
+    /// enumerator ("1.", "a.", "iv.") are not boundaries
+    #[serde(default)]
+    pub citations: bool,
+    /// Apostrophes that are not quotation marks ("don't", "students'",
+    /// "'90s", "goin'"); absent, only the fast and regex patterns apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apostrophes: Option<ApostropheConfig>,
+}
+
+/// Apostrophe handling, the `[suppression.apostrophes]` section: an
+/// enclosure character listed in `chars` is not tracked as a quotation mark
+/// where one of the enabled rules matches
+///
+/// ```toml
+/// [suppression.apostrophes]
+/// elided_starts = ["em", "tis", "n"]   # "'em", "'tis", "rock 'n' roll"
+/// elided_ends = ["goin", "nothin", "n"] # "goin'", "nothin'"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApostropheConfig {
+    /// The apostrophe characters
+    #[serde(default = "default_apostrophes")]
+    pub chars: Vec<char>,
+    /// After a letter or digit and before a letter: "don't", "O'Brien",
+    /// "1990's"
+    #[serde(default = "default_true")]
+    pub contractions: bool,
+    /// After a word ending in "s" and before whitespace: "the students'
+    /// books", "James' car"
+    #[serde(default = "default_true")]
+    pub possessives: bool,
+    /// At the start of a word, before exactly two digits: "'90s", "class of
+    /// '05"
+    #[serde(default = "default_true")]
+    pub years: bool,
+    /// Words whose elided start an apostrophe marks ("'em", "'tis"),
+    /// matched case-insensitively as whole words
+    #[serde(default)]
+    pub elided_starts: Vec<String>,
+    /// Words whose elided end an apostrophe marks ("goin'", "nothin'"),
+    /// matched case-insensitively as whole words
+    #[serde(default)]
+    pub elided_ends: Vec<String>,
+}
+
+impl Default for ApostropheConfig {
+    fn default() -> Self {
+        Self {
+            chars: default_apostrophes(),
+            contractions: true,
+            possessives: true,
+            years: true,
+            elided_starts: Vec::new(),
+            elided_ends: Vec::new(),
+        }
+    }
+}
+
+fn default_apostrophes() -> Vec<char> {
+    vec!['\'', '\u{2019}']
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,7 +236,72 @@ pub struct RegexPattern {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AbbreviationConfig {
     #[serde(flatten)]
-    pub categories: HashMap<String, Vec<String>>,
+    pub categories: HashMap<String, Vec<AbbreviationEntry>>,
+}
+
+/// Conditions an abbreviation's `followed_by` can require of the first
+/// non-whitespace character after its period
+pub const ABBREVIATION_CONDITIONS: [&str; 1] = ["digit"];
+
+/// One abbreviation, written without its final period. Whitespace inside an
+/// abbreviation ("U. S", "p. m") matches any run of whitespace in the text.
+///
+/// A plain string matches case-insensitively wherever it is followed by a
+/// period; an inline table restricts the match:
+///
+/// ```toml
+/// common = ["etc", { abbr = "No", case_sensitive = true, followed_by = "digit" }]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AbbreviationEntry {
+    Plain(String),
+    Rule(AbbreviationRule),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbbreviationRule {
+    pub abbr: String,
+    /// Match the letter case exactly
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Only an abbreviation when the text after its period starts with this
+    /// (see [`ABBREVIATION_CONDITIONS`]), e.g. "No. 5" but not "said no."
+    #[serde(default)]
+    pub followed_by: Option<String>,
+}
+
+impl AbbreviationEntry {
+    /// The abbreviation text
+    pub fn abbr(&self) -> &str {
+        match self {
+            Self::Plain(abbr) => abbr,
+            Self::Rule(rule) => &rule.abbr,
+        }
+    }
+
+    pub fn case_sensitive(&self) -> bool {
+        matches!(self, Self::Rule(rule) if rule.case_sensitive)
+    }
+
+    pub fn followed_by(&self) -> Option<&str> {
+        match self {
+            Self::Plain(_) => None,
+            Self::Rule(rule) => rule.followed_by.as_deref(),
+        }
+    }
+}
+
+impl From<&str> for AbbreviationEntry {
+    fn from(abbr: &str) -> Self {
+        Self::Plain(abbr.to_string())
+    }
+}
+
+impl From<String> for AbbreviationEntry {
+    fn from(abbr: String) -> Self {
+        Self::Plain(abbr)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,7 +309,17 @@ pub struct SentenceStarterConfig {
     /// Categories of sentence starter words
     /// Words should be written exactly as they should be matched (case-sensitive)
     #[serde(flatten)]
-    pub categories: HashMap<String, Vec<String>>,
+    pub categories: HashMap<String, Vec<StarterEntry>>,
+
+    /// Lowest weight that makes an abbreviation followed by the word a
+    /// (weak) boundary (default: 0.5)
+    #[serde(default = "default_starter_threshold")]
+    pub threshold: f64,
+
+    /// Weight of a capitalized word that is not a listed starter; when
+    /// unset, such words never end a sentence after an abbreviation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uppercase_fallback: Option<f64>,
 
     /// Whether to require whitespace after the sentence starter (default: true)
     /// When true, "The patient" matches but "Theater" does not
@@ -119,6 +331,33 @@ pub struct SentenceStarterConfig {
     pub min_word_length: usize,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HypothesisConfig {
+    /// Strategy name; `"space"` hypothesizes a boundary at a space between
+    /// two runs of non-Latin script text
+    pub strategy: String,
+
+    /// Minimum length, in characters, of the text run before a space for the
+    /// space alone to be taken as a boundary (default: 8)
+    #[serde(default = "default_min_chars")]
+    pub min_chars: usize,
+
+    /// Words that end a sentence when they precede the space (polite
+    /// particles and the like); such a space is a strong boundary
+    #[serde(default)]
+    pub sentence_final_particles: Vec<String>,
+
+    /// Words that open a new sentence when they follow the space; such a
+    /// space is a strong boundary
+    #[serde(default)]
+    pub discourse_markers: Vec<String>,
+
+    /// Words that continue the current sentence when they follow the space
+    /// (conjunctions, relative markers); such a space is never a boundary
+    #[serde(default)]
+    pub continuation_markers: Vec<String>,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -127,13 +366,84 @@ fn default_one() -> usize {
     1
 }
 
+fn default_starter_threshold() -> f64 {
+    0.5
+}
+
+/// One sentence starter: a plain word has weight 1, an inline table gives
+/// the weight, from 0 to 1, how reliably the word begins a sentence:
+///
+/// ```toml
+/// adverbs = ["However", { word = "So", weight = 0.4 }]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StarterEntry {
+    Plain(String),
+    Weighted { word: String, weight: f64 },
+}
+
+impl StarterEntry {
+    pub fn word(&self) -> &str {
+        match self {
+            Self::Plain(word) | Self::Weighted { word, .. } => word,
+        }
+    }
+
+    pub fn weight(&self) -> f64 {
+        match self {
+            Self::Plain(_) => 1.0,
+            Self::Weighted { weight, .. } => *weight,
+        }
+    }
+}
+
+impl From<&str> for StarterEntry {
+    fn from(word: &str) -> Self {
+        Self::Plain(word.to_string())
+    }
+}
+
+impl From<String> for StarterEntry {
+    fn from(word: String) -> Self {
+        Self::Plain(word)
+    }
+}
+
+fn default_min_chars() -> usize {
+    8
+}
+
 impl LanguageConfig {
+    /// The built-in language configurations, sorted by code
+    pub fn embedded() -> Vec<&'static LanguageConfig> {
+        super::embedded_language_configs()
+    }
+
+    /// Number of abbreviations across all categories
+    pub fn abbreviation_count(&self) -> usize {
+        self.abbreviations.categories.values().map(Vec::len).sum()
+    }
+
     /// Loads a language configuration from an external TOML file, optionally
     /// overriding the language code, and validates it.
+    ///
+    /// Keys renamed since the file's `schema_version` are migrated and
+    /// unknown keys are ignored; [`LanguageConfig::from_file_with_report`]
+    /// lists both.
     pub fn from_file(
         path: &std::path::Path,
         language_code: Option<&str>,
     ) -> Result<Self, DomainError> {
+        Self::from_file_with_report(path, language_code).map(|(config, _)| config)
+    }
+
+    /// Like [`LanguageConfig::from_file`], also returning what was found
+    /// about the file's keys
+    pub fn from_file_with_report(
+        path: &std::path::Path,
+        language_code: Option<&str>,
+    ) -> Result<(Self, SchemaReport), DomainError> {
         let content = std::fs::read_to_string(path).map_err(|e| {
             DomainError::ConfigurationError(format!(
                 "Failed to read file '{}': {}",
@@ -142,7 +452,7 @@ impl LanguageConfig {
             ))
         })?;
 
-        let mut config: LanguageConfig = toml::from_str(&content).map_err(|e| {
+        let (mut config, report) = super::schema::parse(&content).map_err(|e| {
             DomainError::ConfigurationError(format!(
                 "Failed to parse TOML from '{}': {}",
                 path.display(),
@@ -155,7 +465,23 @@ impl LanguageConfig {
         }
 
         config.validate()?;
-        Ok(config)
+        Ok((config, report))
+    }
+
+    /// Parses and validates a language configuration written in TOML,
+    /// migrating keys renamed since its `schema_version`
+    pub fn from_toml(content: &str) -> Result<(Self, SchemaReport), DomainError> {
+        let (config, report) = super::schema::parse(content)
+            .map_err(|e| DomainError::ConfigurationError(format!("Failed to parse TOML: {e}")))?;
+        config.validate()?;
+        Ok((config, report))
+    }
+
+    /// The configuration as TOML, stamped with the current
+    /// [`SCHEMA_VERSION`]
+    pub fn to_toml(&self) -> String {
+        let body = toml::to_string_pretty(self).expect("language configurations serialize to TOML");
+        format!("schema_version = {SCHEMA_VERSION}\n\n{body}")
     }
 
     /// Validate the language configuration
@@ -190,6 +516,20 @@ impl LanguageConfig {
             })?;
         }
 
+        if let Some(ref apostrophes) = self.suppression.apostrophes {
+            let words = apostrophes
+                .elided_starts
+                .iter()
+                .chain(&apostrophes.elided_ends);
+            for word in words {
+                if word.is_empty() || !word.chars().all(char::is_alphabetic) {
+                    return Err(DomainError::ConfigurationError(format!(
+                        "Elided apostrophe word '{word}' must be one or more letters"
+                    )));
+                }
+            }
+        }
+
         // Validate regex patterns in ellipsis exceptions
         for exception in &self.ellipsis.exceptions {
             regex::Regex::new(&exception.regex).map_err(|e| {
@@ -207,6 +547,23 @@ impl LanguageConfig {
                     "Abbreviation category '{category}' cannot be empty"
                 )));
             }
+            for entry in abbreviations {
+                if entry.abbr().trim().is_empty() {
+                    return Err(DomainError::ConfigurationError(format!(
+                        "Abbreviation category '{category}' contains an empty abbreviation"
+                    )));
+                }
+                if let Some(condition) = entry.followed_by() {
+                    if !ABBREVIATION_CONDITIONS.contains(&condition) {
+                        return Err(DomainError::ConfigurationError(format!(
+                            "Unknown followed_by condition '{condition}' for abbreviation '{}' \
+                             (expected one of: {})",
+                            entry.abbr(),
+                            ABBREVIATION_CONDITIONS.join(", ")
+                        )));
+                    }
+                }
+            }
         }
 
         // It's OK to have no abbreviation categories at all
@@ -237,6 +594,39 @@ impl LanguageConfig {
                         "Sentence starter category '{category}' cannot be empty"
                     )));
                 }
+                for entry in words {
+                    if !(0.0..=1.0).contains(&entry.weight()) {
+                        return Err(DomainError::ConfigurationError(format!(
+                            "Sentence starter '{}' has weight {}, expected 0 to 1",
+                            entry.word(),
+                            entry.weight()
+                        )));
+                    }
+                }
+            }
+            let weights = [
+                ("threshold", Some(sentence_starters.threshold)),
+                ("uppercase_fallback", sentence_starters.uppercase_fallback),
+            ];
+            for (name, value) in weights {
+                if let Some(value) = value.filter(|v| !(0.0..=1.0).contains(v)) {
+                    return Err(DomainError::ConfigurationError(format!(
+                        "sentence_starters.{name} is {value}, expected 0 to 1"
+                    )));
+                }
+            }
+        }
+
+        // Validate the hypothesis strategy if present
+        if let Some(ref hypothesis) = self.hypothesis {
+            if !crate::domain::language::hypothesis::STRATEGIES
+                .contains(&hypothesis.strategy.as_str())
+            {
+                return Err(DomainError::ConfigurationError(format!(
+                    "Unknown hypothesis strategy '{}' (expected one of: {})",
+                    hypothesis.strategy,
+                    crate::domain::language::hypothesis::STRATEGIES.join(", ")
+                )));
             }
         }
 
@@ -487,6 +877,95 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_abbreviation_entries() {
+        let toml_str = r#"
+            [metadata]
+            code = "test"
+            name = "Test Language"
+
+            [terminators]
+            chars = ["."]
+
+            [ellipsis]
+            patterns = []
+
+            [enclosures]
+            pairs = []
+
+            [suppression]
+
+            [abbreviations]
+            common = ["etc", { abbr = "No", case_sensitive = true, followed_by = "digit" }]
+            odd = [{ abbr = "Fig", followed_by = "uppercase" }]
+        "#;
+
+        let mut config: LanguageConfig = toml::from_str(toml_str).unwrap();
+        let common = &config.abbreviations.categories["common"];
+        assert_eq!(common[0], AbbreviationEntry::from("etc"));
+        assert_eq!(common[1].abbr(), "No");
+        assert!(common[1].case_sensitive());
+        assert_eq!(common[1].followed_by(), Some("digit"));
+        assert_eq!(config.abbreviation_count(), 3);
+
+        match config.validate() {
+            Err(DomainError::ConfigurationError(msg)) => {
+                assert!(msg.contains("Unknown followed_by condition 'uppercase'"));
+            }
+            _ => panic!("Expected ConfigurationError for an unknown condition"),
+        }
+        config.abbreviations.categories.remove("odd");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_weighted_sentence_starters() {
+        let toml_str = r#"
+            [metadata]
+            code = "test"
+            name = "Test Language"
+
+            [terminators]
+            chars = ["."]
+
+            [ellipsis]
+            patterns = []
+
+            [enclosures]
+            pairs = []
+
+            [suppression]
+
+            [abbreviations]
+
+            [sentence_starters]
+            uppercase_fallback = 0.3
+            common = ["The", { word = "So", weight = 0.4 }]
+        "#;
+
+        let mut config: LanguageConfig = toml::from_str(toml_str).unwrap();
+        let starters = config.sentence_starters.as_mut().unwrap();
+        assert_eq!(starters.threshold, 0.5);
+        assert_eq!(starters.uppercase_fallback, Some(0.3));
+        let common = &starters.categories["common"];
+        assert_eq!(common[0], StarterEntry::from("The"));
+        assert_eq!(common[0].weight(), 1.0);
+        assert_eq!((common[1].word(), common[1].weight()), ("So", 0.4));
+        assert!(config.validate().is_ok());
+
+        let starters = config.sentence_starters.as_mut().unwrap();
+        starters.categories.get_mut("common").unwrap()[1] = StarterEntry::Weighted {
+            word: "So".into(),
+            weight: 1.5,
+        };
+        match config.validate() {
+            Err(DomainError::ConfigurationError(msg)) => {
+                assert!(msg.contains("Sentence starter 'So' has weight 1.5"));
+            }
+            _ => panic!("Expected ConfigurationError for an out-of-range weight"),
+        }
+    }
+
     #[test]
     fn test_language_config_validate_empty_sentence_starters() {
         let toml_str = r#"
@@ -553,4 +1032,35 @@ mod tests {
             _ => panic!("Expected ConfigurationError for empty sentence starter category"),
         }
     }
+
+    #[test]
+    fn test_language_config_toml_round_trip() {
+        for config in LanguageConfig::embedded() {
+            let toml = config.to_toml();
+            assert!(toml.starts_with(&format!("schema_version = {SCHEMA_VERSION}\n")));
+
+            let (read, report) = LanguageConfig::from_toml(&toml).unwrap();
+            assert_eq!(report.version, SCHEMA_VERSION);
+            assert!(report.is_clean(), "{report:?}");
+            assert_eq!(
+                read.abbreviations.categories,
+                config.abbreviations.categories
+            );
+            assert_eq!(
+                format!("{:?}", read.terminators),
+                format!("{:?}", config.terminators)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bundled_language_files_use_current_keys() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("configs/languages");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let (_, report) = LanguageConfig::from_file_with_report(&path, None).unwrap();
+            assert_eq!(report.version, SCHEMA_VERSION, "{}", path.display());
+            assert!(report.is_clean(), "{}: {report:?}", path.display());
+        }
+    }
 }
@@ -0,0 +1,192 @@
+//! Boundary hypothesis strategies.
+//!
+//! Terminator-driven detection only considers characters such as `.` or `。`.
+//! Scripts like Thai mark sentences mainly with spaces and discourse markers
+//! instead, so a language can name a strategy (the TOML `[hypothesis]`
+//! section) that proposes additional candidates at its own trigger
+//! characters. A strategy is a pure function of the text around the trigger,
+//! read within its declared reach, which keeps it compatible with the
+//! deferred-judgment windows of the pipeline.
+
+use super::config::HypothesisConfig;
+use crate::domain::error::DomainError;
+use crate::domain::types::BoundaryFlags;
+use std::fmt;
+
+/// Names accepted by the `strategy` key of the `[hypothesis]` section.
+pub(crate) const STRATEGIES: &[&str] = &["space"];
+
+/// A source of boundary hypotheses beyond terminator characters.
+pub(crate) trait BoundaryHypothesis: fmt::Debug + Send + Sync {
+    /// Characters at which the strategy proposes a boundary.
+    fn triggers(&self) -> &[char];
+
+    /// Context the strategy reads on each side of a trigger, in characters.
+    fn reach(&self) -> usize;
+
+    /// Decides a hypothesis: `preceding` ends just before the trigger,
+    /// `following` starts just after it. Returns the boundary flags, or
+    /// `None` when the trigger does not end a sentence.
+    fn evaluate(&self, preceding: &str, following: &str) -> Option<BoundaryFlags>;
+}
+
+/// Builds the strategy a configuration names.
+pub(crate) fn from_config(
+    config: &HypothesisConfig,
+) -> Result<Box<dyn BoundaryHypothesis>, DomainError> {
+    match config.strategy.as_str() {
+        "space" => Ok(Box::new(SpaceHypothesis::new(config)?)),
+        other => Err(DomainError::ConfigurationError(format!(
+            "Unknown hypothesis strategy '{}' (expected one of: {})",
+            other,
+            STRATEGIES.join(", ")
+        ))),
+    }
+}
+
+/// Boundaries at spaces between runs of script text, as in Thai, where words
+/// are written without spaces and a space separates phrases or sentences.
+///
+/// Only the last space of a run is considered, and only when non-Latin,
+/// non-numeric text sits on both sides. A continuation marker after the
+/// space rules the boundary out; a sentence-final particle before it or a
+/// discourse marker after it makes it strong; otherwise the space is a weak
+/// boundary once the text run before it reaches `min_chars`.
+#[derive(Debug)]
+struct SpaceHypothesis {
+    min_chars: usize,
+    reach: usize,
+    sentence_final_particles: Vec<String>,
+    discourse_markers: Vec<String>,
+    continuation_markers: Vec<String>,
+}
+
+impl SpaceHypothesis {
+    fn new(config: &HypothesisConfig) -> Result<Self, DomainError> {
+        if config.min_chars == 0 {
+            return Err(DomainError::ConfigurationError(
+                "hypothesis min_chars must be at least 1".into(),
+            ));
+        }
+        let longest_word = config
+            .sentence_final_particles
+            .iter()
+            .chain(&config.discourse_markers)
+            .chain(&config.continuation_markers)
+            .map(|w| w.chars().count())
+            .max()
+            .unwrap_or(0);
+        Ok(Self {
+            min_chars: config.min_chars,
+            reach: config.min_chars.max(longest_word),
+            sentence_final_particles: config.sentence_final_particles.clone(),
+            discourse_markers: config.discourse_markers.clone(),
+            continuation_markers: config.continuation_markers.clone(),
+        })
+    }
+}
+
+/// Text of the script the strategy targets: anything but ASCII, whitespace,
+/// digits and bidi/format marks.
+fn is_script_char(c: char) -> bool {
+    !c.is_ascii()
+        && !c.is_whitespace()
+        && !c.is_numeric()
+        && !('\u{2000}'..='\u{206F}').contains(&c)
+}
+
+impl BoundaryHypothesis for SpaceHypothesis {
+    fn triggers(&self) -> &[char] {
+        &[' ']
+    }
+
+    fn reach(&self) -> usize {
+        self.reach
+    }
+
+    fn evaluate(&self, preceding: &str, following: &str) -> Option<BoundaryFlags> {
+        let before = preceding.chars().next_back()?;
+        let after = following.chars().next()?;
+        if !is_script_char(before) || !is_script_char(after) {
+            return None;
+        }
+
+        if self
+            .continuation_markers
+            .iter()
+            .any(|m| following.starts_with(m.as_str()))
+        {
+            return None;
+        }
+        if self
+            .sentence_final_particles
+            .iter()
+            .any(|p| preceding.ends_with(p.as_str()))
+            || self
+                .discourse_markers
+                .iter()
+                .any(|m| following.starts_with(m.as_str()))
+        {
+            return Some(BoundaryFlags::STRONG);
+        }
+
+        let run = preceding
+            .chars()
+            .rev()
+            .take(self.min_chars)
+            .take_while(|c| !c.is_whitespace())
+            .count();
+        (run >= self.min_chars).then_some(BoundaryFlags::WEAK)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn space(min_chars: usize) -> Box<dyn BoundaryHypothesis> {
+        from_config(&HypothesisConfig {
+            strategy: "space".into(),
+            min_chars,
+            sentence_final_particles: vec!["ครับ".into()],
+            discourse_markers: vec!["ดังนั้น".into()],
+            continuation_markers: vec!["และ".into()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn space_between_long_runs_is_weak() {
+        let h = space(4);
+        assert_eq!(
+            h.evaluate("วันนี้อากาศดี", "พรุ่งนี้ฝนตก"),
+            Some(BoundaryFlags::WEAK)
+        );
+        assert_eq!(h.evaluate("ดีมาก", "x"), None, "Latin text follows");
+        assert_eq!(h.evaluate("ปี 2567", "เป็น"), None, "digit precedes");
+        assert_eq!(h.evaluate("คน ดี", "มาก"), None, "run too short");
+    }
+
+    #[test]
+    fn markers_and_particles() {
+        let h = space(100);
+        assert_eq!(h.evaluate("ขอบคุณครับ", "วันนี้"), Some(BoundaryFlags::STRONG));
+        assert_eq!(
+            h.evaluate("ฝนตก", "ดังนั้นเราจึงอยู่บ้าน"),
+            Some(BoundaryFlags::STRONG)
+        );
+        assert_eq!(h.evaluate("ขอบคุณครับ", "และลาก่อน"), None);
+    }
+
+    #[test]
+    fn unknown_strategy_is_rejected() {
+        let config = HypothesisConfig {
+            strategy: "guess".into(),
+            min_chars: 8,
+            sentence_final_particles: vec![],
+            discourse_markers: vec![],
+            continuation_markers: vec![],
+        };
+        assert!(from_config(&config).is_err());
+    }
+}
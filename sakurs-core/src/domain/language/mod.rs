@@ -4,6 +4,9 @@
 //! `configs/languages/`) that are compiled into the judgment oracles of the
 //! deferred-judgment pipeline (`domain::state`). The [`config`] module
 //! provides the configuration schema, the embedded bundled languages, and
-//! loading from external files.
+//! loading from external files. The [`hypothesis`] module holds the
+//! boundary hypothesis strategies for scripts that mark sentences by other
+//! means than terminal punctuation.
 
 pub mod config;
+pub(crate) mod hypothesis;
@@ -0,0 +1,159 @@
+//! Apostrophe rules compiled from `[suppression.apostrophes]`.
+//!
+//! An apostrophe that is also a quotation mark ("'" in English) must not
+//! toggle the quote parity when it marks a contraction, a possessive, a
+//! shortened year or an elided word. Every rule looks at most one word to
+//! either side of the apostrophe, so [`ApostropheRules::reach`] bounds the
+//! window it needs.
+
+use crate::domain::language::config::ApostropheConfig;
+
+/// The enabled apostrophe rules of a language.
+#[derive(Debug)]
+pub(crate) struct ApostropheRules {
+    chars: Vec<char>,
+    contractions: bool,
+    possessives: bool,
+    years: bool,
+    /// Lowercased words
+    elided_starts: Vec<String>,
+    elided_ends: Vec<String>,
+}
+
+impl ApostropheRules {
+    pub(crate) fn from_config(config: &ApostropheConfig) -> Self {
+        let lowercase = |words: &[String]| words.iter().map(|w| w.to_lowercase()).collect();
+        Self {
+            chars: config.chars.clone(),
+            contractions: config.contractions,
+            possessives: config.possessives,
+            years: config.years,
+            elided_starts: lowercase(&config.elided_starts),
+            elided_ends: lowercase(&config.elided_ends),
+        }
+    }
+
+    /// The apostrophe characters.
+    pub(crate) fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    /// Characters on either side of the apostrophe the rules read: a listed
+    /// word, the character past it, and the apostrophe itself.
+    pub(crate) fn reach(&self) -> usize {
+        let longest_word = self
+            .elided_starts
+            .iter()
+            .chain(&self.elided_ends)
+            .map(|w| w.chars().count())
+            .max()
+            .unwrap_or(0);
+        // A year reads two digits and the character after them
+        longest_word.max(3) + 2
+    }
+
+    /// Whether the apostrophe `ch` between `preceding` and `following` is
+    /// not a quotation mark.
+    pub(crate) fn suppresses(&self, preceding: &str, ch: char, following: &str) -> bool {
+        if !self.chars.contains(&ch) {
+            return false;
+        }
+        let before = preceding.chars().next_back();
+        let after = following.chars().next();
+        let word_before = before.is_some_and(char::is_alphanumeric);
+
+        if self.contractions && word_before && after.is_some_and(char::is_alphabetic) {
+            return true;
+        }
+        if self.possessives && after.is_some_and(char::is_whitespace) {
+            let mut back = preceding.chars().rev();
+            if matches!(back.next(), Some('s' | 'S'))
+                && back.next().is_some_and(char::is_alphanumeric)
+            {
+                return true;
+            }
+        }
+        if self.years && !word_before {
+            let mut ahead = following.chars();
+            let two_digits = ahead.by_ref().take(2).filter(char::is_ascii_digit).count() == 2;
+            if two_digits && !ahead.next().is_some_and(|c| c.is_ascii_digit()) {
+                return true;
+            }
+        }
+        if !self.elided_starts.is_empty() && !before.is_some_and(char::is_alphabetic) {
+            let word = following.split(|c: char| !c.is_alphabetic()).next();
+            if word.is_some_and(|w| listed(&self.elided_starts, w)) {
+                return true;
+            }
+        }
+        if !self.elided_ends.is_empty() && !after.is_some_and(char::is_alphabetic) {
+            let word = preceding.rsplit(|c: char| !c.is_alphabetic()).next();
+            if word.is_some_and(|w| listed(&self.elided_ends, w)) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Whether `word` is one of the lowercased `words`, ignoring case.
+fn listed(words: &[String], word: &str) -> bool {
+    !word.is_empty() && words.iter().any(|w| *w == word.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> ApostropheRules {
+        ApostropheRules::from_config(&ApostropheConfig {
+            elided_starts: vec!["em".into(), "n".into()],
+            elided_ends: vec!["goin".into(), "n".into()],
+            ..ApostropheConfig::default()
+        })
+    }
+
+    /// Whether the first apostrophe of `text` is suppressed
+    fn suppressed(rules: &ApostropheRules, text: &str) -> bool {
+        let pos = text.find(['\'', '\u{2019}']).unwrap();
+        let ch = text[pos..].chars().next().unwrap();
+        rules.suppresses(&text[..pos], ch, &text[pos + ch.len_utf8()..])
+    }
+
+    #[test]
+    fn each_rule_recognizes_its_apostrophes() {
+        let rules = rules();
+        assert!(suppressed(&rules, "don't go"));
+        assert!(suppressed(&rules, "don\u{2019}t go"));
+        assert!(suppressed(&rules, "the 1990's"));
+        assert!(suppressed(&rules, "the students' books"));
+        assert!(suppressed(&rules, "James' car"));
+        assert!(suppressed(&rules, "in the '90s"));
+        assert!(suppressed(&rules, "class of '05"));
+        assert!(suppressed(&rules, "tell 'Em so"));
+        assert!(suppressed(&rules, "rock 'n' roll"));
+        assert!(suppressed(&rules, "n' roll"));
+        assert!(suppressed(&rules, "goin' home"));
+
+        assert!(!suppressed(&rules, "he said 'go home'"));
+        assert!(!suppressed(&rules, "in '1990"));
+        assert!(!suppressed(&rules, "'emphasis'"));
+        assert!(
+            !suppressed(&rules, "the bus'"),
+            "possessives need whitespace after"
+        );
+    }
+
+    #[test]
+    fn disabled_rules_do_not_apply() {
+        let rules = ApostropheRules::from_config(&ApostropheConfig {
+            contractions: false,
+            possessives: false,
+            years: false,
+            ..ApostropheConfig::default()
+        });
+        assert!(!suppressed(&rules, "don't go"));
+        assert!(!suppressed(&rules, "the students' books"));
+        assert!(!suppressed(&rules, "in the '90s"));
+    }
+}
@@ -6,13 +6,15 @@
 //! trait, which keeps the monoid algebra independent of any concrete language
 //! rules — associativity holds for *every* pure judge.
 
+use serde::{Deserialize, Serialize};
+
 use crate::domain::types::{BoundaryFlags, DepthVec};
 
 /// The terminator character that produced a candidate, carried so the
 /// judgment can be re-invoked on a reconstructed window later. Multi-character
 /// patterns and ellipses are re-detected from the window content itself,
 /// which keeps the kind chunk-invariant by construction.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) enum TerminatorKind {
     /// A single terminator character (e.g. `.`, `。`).
     Char(char),
@@ -45,7 +47,7 @@ pub(crate) trait Judge {
 
 /// The depth/parity effect an enclosure character has if it turns out to be a
 /// real enclosure (not suppressed).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) enum EnclosureSlot {
     /// Asymmetric type: add `delta` (+1 opener / −1 closer) to net depth
     /// `index`.
@@ -59,7 +61,7 @@ pub(crate) enum EnclosureSlot {
 /// suppression decision can be made on a full window. Resolving it as a real
 /// enclosure retroactively applies [`EnclosureSlot`] to every candidate and
 /// state total positioned after it.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct PendingEnclosure {
     /// Byte position of the enclosure character, relative to the state's
     /// start.
@@ -72,7 +74,7 @@ pub(crate) struct PendingEnclosure {
 
 /// A linguistically confirmed candidate. Only the structural check against
 /// global enclosure depth/parity remains for the reduce phase.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Candidate {
     /// Byte offset just after the terminator, relative to the state's start.
     pub local_offset: usize,
@@ -88,7 +90,7 @@ pub(crate) struct Candidate {
 
 /// A candidate within `k` characters of a state edge, carried unjudged until
 /// a combine (or edge resolution) supplies the missing context.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct PendingCandidate {
     /// Byte offset just after the terminator, relative to the state's start.
     pub local_offset: usize,
@@ -79,7 +79,7 @@ impl ContextBuf {
 
     /// True when the buffer holds its full [`CONTEXT_CHARS`] characters, which
     /// implies it covers only a prefix/suffix of its span rather than all of it.
-    fn is_full(&self) -> bool {
+    pub(crate) fn is_full(&self) -> bool {
         self.char_count() == CONTEXT_CHARS
     }
 
@@ -130,6 +130,25 @@ impl PartialEq for ContextBuf {
 
 impl Eq for ContextBuf {}
 
+/// Serialized as its text; a longer text than the buffer holds is rejected
+impl serde::Serialize for ContextBuf {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ContextBuf {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        if text.chars().count() > CONTEXT_CHARS {
+            return Err(serde::de::Error::custom(format!(
+                "context buffer holds at most {CONTEXT_CHARS} characters"
+            )));
+        }
+        Ok(Self::from_str(&text))
+    }
+}
+
 impl std::fmt::Debug for ContextBuf {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "ContextBuf({:?})", self.as_str())
@@ -12,6 +12,7 @@
 //! associativity holds for every pure judge and is verified by property tests
 //! in this module.
 
+mod apostrophe;
 mod candidate;
 mod compiled;
 mod context;
@@ -22,9 +23,10 @@ pub(crate) use candidate::{
 };
 pub(crate) use compiled::CompiledRules;
 pub(crate) use context::{window_around, ContextBuf, CONTEXT_CHARS, WINDOW_CHARS};
-pub(crate) use scanner::scan_chunk;
+pub(crate) use scanner::{scan_chunk_into, ScanArena};
 
 use crate::domain::types::DepthVec;
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
 /// Candidates per state; spills to the heap for large chunks.
@@ -43,7 +45,7 @@ pub(crate) type PendingEncVec = SmallVec<[PendingEnclosure; 2]>;
 pub(crate) type ToggleVec = SmallVec<[(usize, EnclosureSlot); 4]>;
 
 /// Parsing state of a text span under the Δ-Stack Monoid algorithm.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct PartialState {
     /// Linguistically confirmed candidates (B), sorted by offset.
     pub boundaries: CandidateVec,
@@ -252,22 +254,42 @@ impl PartialState {
     /// [`Self::resolve_edges`], additionally returning the enclosure toggles
     /// it applied (for candidates the driver keeps outside the state).
     pub(crate) fn resolve_edges_full<J: Judge>(mut self, judge: &J) -> (Self, ToggleVec) {
+        let toggles = self.resolve_pending_where(judge, |_, _| true);
+        (self, toggles)
+    }
+
+    /// Resolves the pending items that lack left context only because they
+    /// sit within k characters of the text start, for a state known to begin
+    /// at the start of text. Once the head buffer is full, each such item's
+    /// clipped window is final — it is exactly the window
+    /// [`Self::resolve_edges_full`] would use — so the streaming driver can
+    /// settle them early and emit boundaries before the text ends. Items near
+    /// the right edge stay pending. Returns the applied enclosure toggles.
+    pub(crate) fn resolve_text_start<J: Judge>(&mut self, judge: &J) -> ToggleVec {
+        debug_assert!(
+            self.head_ctx.is_full(),
+            "text-start items are settled only once the head buffer is full"
+        );
+        self.resolve_pending_where(judge, |state, p| state.chars_before(p) < WINDOW_CHARS)
+    }
+
+    /// Resolves the pending items at offsets accepted by `select` against the
+    /// clipped windows of the edge buffers. Enclosures resolve before
+    /// candidates, as in combine.
+    fn resolve_pending_where<J: Judge>(
+        &mut self,
+        judge: &J,
+        select: impl Fn(&Self, usize) -> bool,
+    ) -> ToggleVec {
         let pending_enc = std::mem::take(&mut self.pending_enc);
         let mut toggles = ToggleVec::new();
         for pe in pending_enc {
             let p = pe.local_offset;
-            // Pick the buffer whose coverage contains the clipped window:
-            // items lacking left context sit within k characters of the text
-            // start (window ⊆ head buffer), all others lack right context and
-            // sit within k characters of the end (⊆ tail buffer).
-            let (buf, buf_start) = if self.chars_before(p) < WINDOW_CHARS {
-                (self.head_ctx.as_str(), 0)
-            } else {
-                let tail_start = self.chunk_len - self.tail_ctx.byte_len();
-                debug_assert!(p >= tail_start, "pending enclosure outside both buffers");
-                (self.tail_ctx.as_str(), tail_start)
-            };
-            let (window, pos) = window_around(buf, p - buf_start, WINDOW_CHARS);
+            if !select(self, p) {
+                self.pending_enc.push(pe);
+                continue;
+            }
+            let (window, pos) = self.edge_window(p);
             if !judge.suppress_enclosure(window, pos, pe.ch) {
                 toggles.push((p, pe.slot));
             }
@@ -297,20 +319,34 @@ impl PartialState {
         let pending = std::mem::take(&mut self.pending);
         for pc in pending {
             let p = pc.local_offset;
-            let (buf, buf_start) = if self.chars_before(p) < WINDOW_CHARS {
-                (self.head_ctx.as_str(), 0)
-            } else {
-                let tail_start = self.chunk_len - self.tail_ctx.byte_len();
-                debug_assert!(p >= tail_start, "pending candidate outside both buffers");
-                (self.tail_ctx.as_str(), tail_start)
-            };
-            let (window, pos) = window_around(buf, p - buf_start, WINDOW_CHARS);
+            if !select(self, p) {
+                self.pending.push(pc);
+                continue;
+            }
+            let (window, pos) = self.edge_window(p);
             if let Judgment::Boundary(flags) = judge.judge(window, pos, pc.kind) {
                 self.boundaries.push(pc.confirm(flags));
             }
         }
         self.boundaries.sort_unstable_by_key(|c| c.local_offset);
-        (self, toggles)
+        toggles
+    }
+
+    /// The clipped ±k window around a pending item at an edge of the text.
+    ///
+    /// Picks the buffer whose coverage contains the clipped window: items
+    /// lacking left context sit within k characters of the text start
+    /// (window ⊆ head buffer), all others lack right context and sit within
+    /// k characters of the end (⊆ tail buffer).
+    fn edge_window(&self, p: usize) -> (&str, usize) {
+        let (buf, buf_start) = if self.chars_before(p) < WINDOW_CHARS {
+            (self.head_ctx.as_str(), 0)
+        } else {
+            let tail_start = self.chunk_len - self.tail_ctx.byte_len();
+            debug_assert!(p >= tail_start, "pending item outside both buffers");
+            (self.tail_ctx.as_str(), tail_start)
+        };
+        window_around(buf, p - buf_start, WINDOW_CHARS)
     }
 }
 
@@ -376,7 +412,7 @@ fn insert_sorted(boundaries: &mut CandidateVec, c: Candidate) {
 }
 
 /// Applies a confirmed enclosure's effect to a state's depth/parity totals.
-fn apply_slot_to_totals(deltas: &mut DepthVec, parity: &mut u32, slot: EnclosureSlot) {
+pub(crate) fn apply_slot_to_totals(deltas: &mut DepthVec, parity: &mut u32, slot: EnclosureSlot) {
     match slot {
         EnclosureSlot::Asym { index, delta } => {
             let i = index as usize;
@@ -7,14 +7,68 @@
 //! pending. Per-character work is a table lookup plus depth/parity updates.
 
 use super::{
-    window_around, Candidate, CompiledRules, ContextBuf, EnclosureSlot, Judge, Judgment,
-    PartialState, PendingCandidate, PendingEnclosure, TerminatorKind, WINDOW_CHARS,
+    window_around, Candidate, CandidateVec, CompiledRules, ContextBuf, EnclosureSlot, Judge,
+    Judgment, PartialState, PendingCandidate, PendingEnclosure, TerminatorKind, WINDOW_CHARS,
 };
 use crate::domain::types::DepthVec;
 
+/// Spilled candidate buffers an arena keeps between runs; enough for the
+/// chunks of a few MB of text at typical chunk sizes without pinning memory
+/// proportional to the largest text ever processed.
+const MAX_RETAINED_BUFFERS: usize = 64;
+
+/// Scratch buffers reused across [`scan_chunk_into`] calls.
+///
+/// Candidate vectors are the only per-chunk heap allocation of the scan
+/// (contexts and depth records are inline). Executors hand the vectors back
+/// with [`Self::recycle`] once their candidates are consumed, so the next
+/// scans start with warm capacity instead of regrowing from empty.
+#[derive(Debug, Default)]
+pub(crate) struct ScanArena {
+    free: Vec<CandidateVec>,
+}
+
+impl ScanArena {
+    /// An empty candidate buffer, reusing a recycled allocation if one is
+    /// available.
+    pub(crate) fn buffer(&mut self) -> CandidateVec {
+        self.free.pop().unwrap_or_default()
+    }
+
+    /// `n` empty candidate buffers (see [`Self::buffer`]).
+    pub(crate) fn buffers(&mut self, n: usize) -> Vec<CandidateVec> {
+        (0..n).map(|_| self.buffer()).collect()
+    }
+
+    /// Returns a buffer for reuse. Buffers that never left inline storage
+    /// carry no allocation and are dropped.
+    pub(crate) fn recycle(&mut self, mut buf: CandidateVec) {
+        if buf.spilled() && self.free.len() < MAX_RETAINED_BUFFERS {
+            buf.clear();
+            self.free.push(buf);
+        }
+    }
+}
+
 /// Scans one chunk into a partial state.
+///
+/// The drivers use [`scan_chunk_into`] with arena buffers; this wrapper is
+/// what the tests exercise.
+#[cfg(test)]
 pub(crate) fn scan_chunk(text: &str, rules: &CompiledRules) -> PartialState {
+    scan_chunk_into(text, rules, CandidateVec::new())
+}
+
+/// [`scan_chunk`] collecting confirmed candidates into `boundaries`, an
+/// empty buffer typically taken from a [`ScanArena`].
+pub(crate) fn scan_chunk_into(
+    text: &str,
+    rules: &CompiledRules,
+    boundaries: CandidateVec,
+) -> PartialState {
+    debug_assert!(boundaries.is_empty(), "scan buffer must start empty");
     let mut state = PartialState::identity();
+    state.boundaries = boundaries;
     state.deltas.resize(rules.asym_type_count(), 0);
     state.chunk_len = text.len();
     state.head_ctx = ContextBuf::head_of(text);
@@ -169,6 +223,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn recycled_buffers_scan_identically() {
+        let text = "Dr. Smith left. He said \"Hi.\" Then (quietly) he sat. Done! ".repeat(20);
+        let fresh = scan_chunk(&text, &EN);
+        assert!(fresh.boundaries.spilled());
+
+        let mut arena = ScanArena::default();
+        arena.recycle(fresh.boundaries.clone());
+        arena.recycle(CandidateVec::new());
+        assert_eq!(arena.free.len(), 1, "inline buffers are not retained");
+
+        let buf = arena.buffer();
+        assert!(buf.is_empty() && buf.capacity() >= fresh.boundaries.len());
+        assert_eq!(scan_chunk_into(&text, &EN, buf), fresh);
+    }
+
     fn en_soup() -> impl Strategy<Value = String> {
         let token = prop::sample::select(vec![
             "the", "He", "said", "Dr.", "U.S.", "Mr.", "3.5", "approx", "Smith", "don't", ". ",
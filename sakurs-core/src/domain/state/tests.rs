@@ -25,6 +25,7 @@ const ASYM_CLOSE: EnclosureSlot = EnclosureSlot::Asym {
 };
 
 /// Toy scanner following the real scanner's contract.
+#[allow(clippy::explicit_counter_loop)]
 fn toy_scan<J: Judge>(text: &str, judge: &J) -> PartialState {
     let mut state = PartialState::identity();
     state.deltas.push(0);
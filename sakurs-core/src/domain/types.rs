@@ -1,9 +1,10 @@
 //! Shared type definitions for the domain layer.
 
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
 /// Classification flags for sentence boundaries
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct BoundaryFlags {
     /// Strong boundary (e.g., `!`, `?`, multi-character terminator patterns)
     pub is_strong: bool,
@@ -22,6 +23,116 @@ impl BoundaryFlags {
     };
 }
 
+/// Why a boundary candidate (a terminator or other trigger character) did
+/// not become a sentence boundary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RejectionReason {
+    /// A known abbreviation ("Dr.", "U.S.", "et al.")
+    Abbreviation,
+    /// A decimal point or ordinal ("3.14", "1.º")
+    Decimal,
+    /// An ellipsis the ellipsis rules keep inside the sentence
+    Ellipsis,
+    /// Not the last character of a terminator run ("!!!", "...", "!?") or
+    /// followed by trailing symbols that end the sentence instead
+    TerminatorRun,
+    /// Inside quotes or brackets that are still open
+    InsideEnclosure,
+    /// Inside a URL, DOI, email address, @mention or #hashtag
+    WebToken,
+    /// Part of a citation or numbered subsection ("Educ., 347", "1.")
+    Citation,
+    /// An emoji that neither follows a terminator nor precedes a new sentence
+    Emoji,
+    /// A trigger the boundary hypothesis rejected (a space in Thai), or a
+    /// character no rule treats as a terminator on its own
+    NotTerminator,
+    /// Merged into the previous sentence by `min_sentence_chars`
+    ShortSentence,
+    /// Inside a match of a user suppression regex
+    Suppressed,
+    /// Vetoed by a user-registered boundary filter
+    Filter,
+    /// Rejected by a correction of the document
+    Corrected,
+}
+
+/// A problem with the text noticed while segmenting it; boundaries near it
+/// may be wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Warning {
+    /// An opening quote or bracket at byte `offset` that is never closed, or
+    /// not before the blank line where it was closed (dangling enclosures
+    /// closed at paragraph ends, or a type past its depth cap reset there)
+    UnclosedEnclosure { offset: usize, ch: char },
+    /// A closing bracket at byte `offset` without an opener, or a straight
+    /// quote facing as one while its type is not open
+    UnmatchedCloser { offset: usize, ch: char },
+    /// An opener at byte `offset` that nests its enclosure type deeper than
+    /// the configured maximum depth; reported once until the depth drops
+    /// back within it
+    DepthExceeded { offset: usize, ch: char },
+    /// A sentence starting at byte `offset` longer than the configured
+    /// warning length, at `chars` characters; often a missed boundary or
+    /// text that is not prose
+    LongSentence { offset: usize, chars: usize },
+    /// A boundary at byte `offset` inserted by forced splitting rather than
+    /// found by the rules
+    ForcedSplit { offset: usize },
+    /// Invalid UTF-8 replaced or skipped while decoding the input: `bytes`
+    /// invalid bytes in all, the first at byte `offset` of the decoded text
+    InvalidUtf8 { offset: usize, bytes: usize },
+}
+
+impl Warning {
+    /// Byte offset of the character the warning is about
+    pub fn offset(&self) -> usize {
+        match *self {
+            Warning::UnclosedEnclosure { offset, .. }
+            | Warning::UnmatchedCloser { offset, .. }
+            | Warning::DepthExceeded { offset, .. }
+            | Warning::LongSentence { offset, .. }
+            | Warning::ForcedSplit { offset }
+            | Warning::InvalidUtf8 { offset, .. } => offset,
+        }
+    }
+
+    /// The variant's name in snake case, as used in JSON output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Warning::UnclosedEnclosure { .. } => "unclosed_enclosure",
+            Warning::UnmatchedCloser { .. } => "unmatched_closer",
+            Warning::DepthExceeded { .. } => "depth_exceeded",
+            Warning::LongSentence { .. } => "long_sentence",
+            Warning::ForcedSplit { .. } => "forced_split",
+            Warning::InvalidUtf8 { .. } => "invalid_utf8",
+        }
+    }
+}
+
+impl std::fmt::Display for Warning {
+    /// Describes the problem without its offset
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::UnclosedEnclosure { ch, .. } => write!(f, "unclosed {ch:?}"),
+            Warning::UnmatchedCloser { ch, .. } => write!(f, "unmatched {ch:?}"),
+            Warning::DepthExceeded { ch, .. } => {
+                write!(f, "{ch:?} nested past the maximum enclosure depth")
+            }
+            Warning::LongSentence { chars, .. } => {
+                write!(f, "suspiciously long sentence of {chars} characters")
+            }
+            Warning::ForcedSplit { .. } => write!(f, "sentence force-split for length"),
+            Warning::InvalidUtf8 { bytes, .. } => {
+                let plural = if *bytes == 1 { "" } else { "s" };
+                write!(f, "invalid UTF-8 ({bytes} byte{plural} in all)")
+            }
+        }
+    }
+}
+
 /// Optimized vector for local depths
 /// Enclosure depth rarely exceeds 8 levels
 pub type DepthVec = SmallVec<[i32; 8]>;
@@ -0,0 +1,69 @@
+//! The algorithm's phases as standalone calls, for the micro-benchmarks in
+//! `benches/phases.rs`
+//!
+//! Enabled by the `bench-internals` feature. Not part of the stable API:
+//! these follow the internals and may change in any release.
+
+use crate::api::Error;
+use crate::domain::language::config::get_language_config;
+use crate::domain::state::{
+    scan_chunk_into, window_around, CandidateVec, CompiledRules, Judge, PartialState, WINDOW_CHARS,
+};
+
+/// The compiled rules of a language
+pub struct Rules(CompiledRules);
+
+impl Rules {
+    /// The rules of a built-in language ("en", "ja", ...)
+    pub fn for_language(code: &str) -> Result<Self, Error> {
+        let language =
+            get_language_config(code).map_err(|e| Error::InvalidLanguage(e.to_string()))?;
+        CompiledRules::from_config(language)
+            .map(Self)
+            .map_err(|e| Error::Configuration(e.to_string()))
+    }
+
+    /// Byte length of the longest abbreviation ending at the period at byte
+    /// offset `period` of `text`, as the trie lookup of the rules finds it
+    pub fn abbreviation_before(&self, text: &str, period: usize) -> Option<usize> {
+        self.0
+            .abbreviation_ends_at(text, period)
+            .map(|(length, _)| length)
+    }
+
+    /// Whether the enclosure character at byte offset `offset` of `text` is
+    /// suppressed (an apostrophe in a contraction, a list marker's
+    /// parenthesis, ...) rather than opening or closing an enclosure
+    pub fn suppresses_enclosure(&self, text: &str, offset: usize) -> bool {
+        let Some(ch) = text[offset..].chars().next() else {
+            return false;
+        };
+        let (window, pos) = window_around(text, offset, WINDOW_CHARS);
+        self.0.suppress_enclosure(window, pos, ch)
+    }
+}
+
+/// The partial state of a scanned span of text
+pub struct State(PartialState);
+
+impl State {
+    /// Boundary candidates confirmed so far
+    pub fn boundary_count(&self) -> usize {
+        self.0.boundaries.len()
+    }
+}
+
+/// Scan one chunk into its partial state (the scan phase)
+pub fn scan_chunk(text: &str, rules: &Rules) -> State {
+    State(scan_chunk_into(text, &rules.0, CandidateVec::new()))
+}
+
+/// Fold the states of consecutive chunks with the monoid operation, then
+/// resolve the items left pending at the text edges (the combine phase)
+pub fn reduce(states: &[State], rules: &Rules) -> State {
+    let mut acc = PartialState::identity();
+    for state in states {
+        acc.absorb(&state.0, &rules.0);
+    }
+    State(acc.resolve_edges_full(&rules.0).0)
+}
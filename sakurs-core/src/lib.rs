@@ -15,6 +15,14 @@
 //! sakurs-core = "0.2"
 //! ```
 //!
+//! # Determinism
+//!
+//! Boundaries depend only on the text and the configuration, never on the
+//! execution: sequential, parallel and streaming processing return the
+//! same byte offsets for any chunk size, thread count or split of a stream
+//! into pieces. [`SentenceProcessor::verify_determinism`] checks this on a
+//! given text.
+//!
 //! # Example
 //!
 //! ```rust
@@ -34,8 +42,22 @@
 pub mod api;
 pub(crate) mod application;
 pub(crate) mod domain;
+#[cfg(feature = "bench-internals")]
+#[doc(hidden)]
+pub mod internals;
 
+#[cfg(feature = "arrow")]
+pub use api::SentenceBatchBuilder;
 pub use api::{
-    Boundary, Config, ConfigBuilder, Error as ApiError, Input, Language, LanguageConfig, Output,
-    ProcessingMetadata, ProcessingStats, SentenceProcessor,
+    chunk_sentences, Boundary, BoundaryCandidate, BoundaryFilter, BoundaryKind, BpeEstimator,
+    ChunkCache, ChunkCacheStats, ChunkPolicy, Config, ConfigBuilder, Correction, CorrectionAction,
+    Corrections, DepthOverflow, DeterminismViolation, EllipsisPolicy, EnclosureBoundaries,
+    Error as ApiError, ExecutionDecision, ExecutionMetrics, ExecutionMode, ExecutionReason,
+    FilterDecision, IncrementalText, Input, InvalidUtf8, Language, LanguageConfig, Output,
+    PerfProfile, ProcessingMetadata, ProcessingStats, ProcessorHandle, Profile, Progress,
+    QueueMetrics, ReaderPipeline, RejectedCandidate, RejectionReason, SentenceDeduplicator,
+    SentenceProcessor, SentenceSpan, SentenceStream, SentenceWindow, StreamCheckpoint,
+    TokenEstimator, Warning, WhitespaceEstimator,
 };
+#[cfg(feature = "normalization")]
+pub use api::{Normalization, OffsetSpace};
@@ -0,0 +1,113 @@
+//! Integration tests for the Arabic language pack
+//!
+//! Covers the Arabic question mark and full stop, single-letter
+//! abbreviations, and a small corpus of mixed right-to-left / left-to-right
+//! text (Latin words, ASCII numbers, bidi marks) through the public API.
+
+mod common;
+
+use sakurs_core::{Language, SentenceProcessor};
+
+fn sentences(text: &str) -> Vec<String> {
+    common::sentences(&SentenceProcessor::with_language("ar").unwrap(), text)
+}
+
+#[test]
+fn test_arabic_terminators() {
+    assert_eq!(
+        sentences("مرحبا بكم. كيف حالكم؟ أنا بخير! انتهى الدرس۔ إلى اللقاء"),
+        [
+            "مرحبا بكم.",
+            "كيف حالكم؟",
+            "أنا بخير!",
+            "انتهى الدرس۔",
+            "إلى اللقاء"
+        ]
+    );
+}
+
+#[test]
+fn test_arabic_comma_is_not_a_terminator() {
+    assert_eq!(
+        sentences("نعم، جربته، وكان ممتازا. شكرا لك؛ سأعود غدا."),
+        ["نعم، جربته، وكان ممتازا.", "شكرا لك؛ سأعود غدا."]
+    );
+}
+
+#[test]
+fn test_arabic_emphatic_patterns() {
+    assert_eq!(
+        sentences("هذا جيد!؟ حقا؟! ربما..."),
+        ["هذا جيد!؟", "حقا؟!", "ربما..."]
+    );
+}
+
+#[test]
+fn test_arabic_abbreviations() {
+    // "د." (doctor) before a name; "م." (AD) after a year, with a boundary
+    // only when a sentence starter follows
+    assert_eq!(
+        sentences("قال د. أحمد إن الموعد تغير. ولد عام 1950 م. وتوفي عام 2020 م. ثم نشرت كتبه."),
+        [
+            "قال د. أحمد إن الموعد تغير.",
+            "ولد عام 1950 م. وتوفي عام 2020 م.",
+            "ثم نشرت كتبه."
+        ]
+    );
+}
+
+#[test]
+fn test_mixed_rtl_ltr_corpus() {
+    let cases: [(&str, &[&str]); 4] = [
+        (
+            "استخدمت Python 3.12 في المشروع. هل جربت Rust؟ نعم، جربته.",
+            &[
+                "استخدمت Python 3.12 في المشروع.",
+                "هل جربت Rust؟",
+                "نعم، جربته.",
+            ],
+        ),
+        (
+            "The meeting (الاجتماع) starts at 10.30 today. هل أنت جاهز؟ Yes!",
+            &[
+                "The meeting (الاجتماع) starts at 10.30 today.",
+                "هل أنت جاهز؟",
+                "Yes!",
+            ],
+        ),
+        (
+            "قال «هل أنت متأكد؟ لا أظن.» ثم سكت. Version 2.0 is out.",
+            &["قال «هل أنت متأكد؟ لا أظن.» ثم سكت.", "Version 2.0 is out."],
+        ),
+        (
+            "سعر السهم 12.5 دولار (انظر الجدول 3). The price rose? نعم!",
+            &[
+                "سعر السهم 12.5 دولار (انظر الجدول 3).",
+                "The price rose?",
+                "نعم!",
+            ],
+        ),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(sentences(text), expected, "{text}");
+    }
+}
+
+#[test]
+fn test_bidi_marks_after_abbreviation() {
+    // A right-to-left mark between an abbreviation and the next word must
+    // not be mistaken for the end of the text
+    assert_eq!(
+        sentences("قال د.\u{200F} أحمد كلاما. ثم غادر."),
+        ["قال د.\u{200F} أحمد كلاما.", "ثم غادر."]
+    );
+}
+
+#[test]
+fn test_language_code_and_name() {
+    let language: Language = "ar".parse().unwrap();
+    assert_eq!(language.code(), "ar");
+    assert_eq!(language.name(), "Arabic");
+    assert_eq!("arabic".parse::<Language>().unwrap(), Language::Arabic);
+    assert!(SentenceProcessor::with_language("arabic").is_ok());
+}
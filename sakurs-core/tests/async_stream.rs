@@ -0,0 +1,56 @@
+//! Tests for the async API (`async` feature)
+#![cfg(feature = "async")]
+
+use std::io::Cursor;
+
+use futures_util::StreamExt;
+use sakurs_core::{Input, SentenceProcessor, SentenceSpan};
+use tokio::io::AsyncRead;
+
+async fn collect<R>(processor: &SentenceProcessor, reader: R) -> Vec<SentenceSpan>
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    let mut stream = Box::pin(processor.process_async(reader));
+    let mut spans = Vec::new();
+    while let Some(span) = stream.next().await {
+        spans.push(span.expect("stream should not fail"));
+    }
+    spans
+}
+
+#[tokio::test]
+async fn test_process_async_matches_batch() {
+    let config = sakurs_core::Config::builder()
+        .language("ja")
+        .unwrap()
+        .chunk_size(7)
+        .build()
+        .unwrap();
+    let processor = SentenceProcessor::with_config(config).unwrap();
+    let text = "「こんにちは。」と彼は言った。今日は晴れです！明日は".repeat(20);
+
+    let spans = collect(&processor, Cursor::new(text.clone().into_bytes())).await;
+
+    let batch = processor.process(Input::from_text(text.as_str())).unwrap();
+    let ends: Vec<usize> = spans.iter().map(|s| s.end).collect();
+    let mut expected: Vec<usize> = batch.boundaries.iter().map(|b| b.offset).collect();
+    expected.push(text.len());
+    assert_eq!(ends, expected);
+
+    let rebuilt: String = spans.iter().map(|s| s.text.as_str()).collect();
+    assert_eq!(rebuilt, text);
+    let last = spans.last().unwrap();
+    assert_eq!(last.char_end, text.chars().count());
+}
+
+#[tokio::test]
+async fn test_process_async_rejects_invalid_utf8() {
+    let processor = SentenceProcessor::new();
+    let mut stream = Box::pin(processor.process_async(Cursor::new(b"Hello. \xff world.".to_vec())));
+    let mut saw_error = false;
+    while let Some(item) = stream.next().await {
+        saw_error |= item.is_err();
+    }
+    assert!(saw_error);
+}
@@ -0,0 +1,113 @@
+//! Boundary invariants over arbitrary UTF-8 input.
+//!
+//! Whatever the text, the output must be well formed: sequential and
+//! parallel execution agree, every offset falls on a character boundary,
+//! boundaries strictly increase, the reported character and UTF-16 offsets
+//! match the byte offsets, and the sentence spans concatenate back to the
+//! input. `chunk_invariance.rs` checks invariance over realistic corpora;
+//! these properties use unstructured text, where cross-chunk drift and
+//! offset bugs tend to hide.
+
+use proptest::prelude::*;
+use sakurs_core::{Config, Input, Output, SentenceProcessor};
+
+const LANGUAGES: &[&str] = &["en", "ja", "pt", "it", "ar", "th", "hi"];
+
+/// Characters that drive boundary decisions: terminators, enclosures,
+/// apostrophes, digits, whitespace, and multi-byte letters and clusters.
+const BIASED_TEXT: &str =
+    "[a-zA-Z0-9 .!?…,;:'\"()\\[\\]「」『』（）。！？、\n\t\u{0301}🇯🇵éĀ؟।กข ]{0,400}";
+
+fn process(text: &str, lang: &str, chunk_size: usize, threads: usize) -> Output {
+    let config = Config::builder()
+        .language(lang)
+        .expect("language config should load")
+        .chunk_size(chunk_size)
+        .threads(Some(threads))
+        .utf16_offsets(true)
+        .build()
+        .expect("config should validate");
+    SentenceProcessor::with_config(config)
+        .expect("processor should build")
+        .process(Input::from_text(text))
+        .expect("processing should succeed")
+}
+
+/// Check every invariant of one output against its input text.
+fn check_well_formed(text: &str, output: &Output) -> Result<(), TestCaseError> {
+    let mut previous = 0;
+    let mut spans = Vec::new();
+    for boundary in &output.boundaries {
+        let offset = boundary.offset;
+        prop_assert!(
+            offset > previous,
+            "boundaries must strictly increase: {} after {}",
+            offset,
+            previous
+        );
+        prop_assert!(
+            offset <= text.len(),
+            "offset {} past end {}",
+            offset,
+            text.len()
+        );
+        prop_assert!(
+            text.is_char_boundary(offset),
+            "offset {} is not a char boundary",
+            offset
+        );
+        prop_assert_eq!(boundary.char_offset, text[..offset].chars().count());
+        prop_assert_eq!(
+            boundary.utf16_offset,
+            Some(text[..offset].encode_utf16().count())
+        );
+        spans.push(&text[previous..offset]);
+        previous = offset;
+    }
+    spans.push(&text[previous..]);
+    prop_assert_eq!(spans.concat(), text);
+    Ok(())
+}
+
+fn offsets(output: &Output) -> Vec<usize> {
+    output.boundaries.iter().map(|b| b.offset).collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig { cases: 64, ..ProptestConfig::default() })]
+
+    /// Text dense in punctuation, enclosures and multi-byte characters.
+    #[test]
+    fn biased_text_is_well_formed_and_mode_independent(
+        text in BIASED_TEXT,
+        lang in prop::sample::select(LANGUAGES),
+        chunk_size in prop::sample::select(vec![64usize, 100, 256]),
+        threads in prop::sample::select(vec![2usize, 4]),
+    ) {
+        let sequential = process(&text, lang, text.len() + 1024, 1);
+        check_well_formed(&text, &sequential)?;
+        let parallel = process(&text, lang, chunk_size, threads);
+        check_well_formed(&text, &parallel)?;
+        prop_assert_eq!(
+            offsets(&parallel),
+            offsets(&sequential),
+            "modes diverged: lang={}, chunk_size={}, threads={}",
+            lang,
+            chunk_size,
+            threads
+        );
+    }
+
+    /// Any Unicode string, including controls and unassigned code points.
+    #[test]
+    fn arbitrary_text_is_well_formed_and_mode_independent(
+        text in any::<String>(),
+        lang in prop::sample::select(LANGUAGES),
+    ) {
+        let sequential = process(&text, lang, text.len() + 1024, 1);
+        check_well_formed(&text, &sequential)?;
+        let parallel = process(&text, lang, 64, 2);
+        check_well_formed(&text, &parallel)?;
+        prop_assert_eq!(offsets(&parallel), offsets(&sequential), "lang={}", lang);
+    }
+}
@@ -9,14 +9,26 @@
 //! (v0.2.0) makes boundary decisions independent of where chunks are cut.
 
 use proptest::prelude::*;
-use sakurs_core::{Config, Input, SentenceProcessor};
+use sakurs_core::{ChunkPolicy, Config, Input, SentenceProcessor};
 
 /// Returns boundary byte offsets for the given configuration.
 fn boundaries(text: &str, lang: &str, chunk_size: usize, threads: usize) -> Vec<usize> {
+    boundaries_with(text, lang, chunk_size, threads, ChunkPolicy::CharBoundary)
+}
+
+/// [`boundaries`] with an explicit chunk cut policy.
+fn boundaries_with(
+    text: &str,
+    lang: &str,
+    chunk_size: usize,
+    threads: usize,
+    policy: ChunkPolicy,
+) -> Vec<usize> {
     let config = Config::builder()
         .language(lang)
         .expect("language config should load")
         .chunk_size(chunk_size)
+        .chunk_policy(policy)
         .threads(Some(threads))
         .build()
         .expect("config should validate");
@@ -99,6 +111,48 @@ fn japanese_brackets_are_chunk_invariant() {
     }
 }
 
+/// Thai space hypotheses are judged on the same windows as terminators, so
+/// spaces near chunk edges are deferred like any other candidate.
+#[test]
+fn thai_space_hypotheses_are_chunk_invariant() {
+    let unit = "วันนี้อากาศดีมากเลย เราไปเที่ยวทะเลกันดีกว่า ขอบคุณมากครับ \
+ฝนตกหนักทั้งวัน ดังนั้นเราจึงอยู่บ้าน ผมชอบกินข้าวผัด และชอบกินต้มยำด้วย ";
+    let text = unit.repeat(40); // ~16KB
+    let expected = reference(&text, "th");
+    assert!(!expected.is_empty());
+    for chunk_size in [1024, 2048, 4096, 8192] {
+        for threads in [1, 2] {
+            let got = boundaries(&text, "th", chunk_size, threads);
+            assert_eq!(
+                got, expected,
+                "boundaries diverged at chunk_size={chunk_size}, threads={threads}"
+            );
+        }
+    }
+}
+
+/// Snapping cuts to whitespace or grapheme boundaries moves chunk edges but
+/// never changes the result.
+#[test]
+fn chunk_policies_are_invariant() {
+    let en =
+        "Dr. Smith met Mr. Jones at the U.S. embassy. He said \"Don't go.\" Then left! ".repeat(60);
+    let ja = "彼は「こんにちは」と言った。カフェ\u{301}で🇯🇵の旗を見た。本当？".repeat(60);
+    for (text, lang) in [(en.as_str(), "en"), (ja.as_str(), "ja")] {
+        let expected = reference(text, lang);
+        assert!(!expected.is_empty());
+        for policy in [ChunkPolicy::Whitespace, ChunkPolicy::Grapheme] {
+            for chunk_size in [64, 333, 1024] {
+                let got = boundaries_with(text, lang, chunk_size, 2, policy);
+                assert_eq!(
+                    got, expected,
+                    "boundaries diverged: policy={policy:?}, chunk_size={chunk_size}"
+                );
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Property tests over generated corpora
 // ---------------------------------------------------------------------------
@@ -122,6 +176,25 @@ const JA_FRAGMENTS: &[&str] = &[
     "皆で公園へ行きました!",
 ];
 
+/// Boundary offsets from feeding `text` to a stream in `piece`-byte pieces
+/// (snapped forward to character boundaries).
+fn streamed(text: &str, lang: &str, piece: usize) -> Vec<usize> {
+    let processor = SentenceProcessor::with_language(lang).expect("processor should build");
+    let mut stream = processor.stream();
+    let mut offsets = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + piece).min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        offsets.extend(stream.feed(&text[start..end]).iter().map(|b| b.offset));
+        start = end;
+    }
+    offsets.extend(stream.finish().iter().map(|b| b.offset));
+    offsets
+}
+
 fn build_text(fragments: &[&str], indices: &[usize], trim_trailing: bool) -> String {
     let mut text: String = indices
         .iter()
@@ -177,4 +250,18 @@ proptest! {
             chunk_size, threads, text
         );
     }
+
+    /// Feeding text to a stream in arbitrary pieces must match batch
+    /// processing of the whole text.
+    #[test]
+    fn streamed_pieces_match_batch(
+        indices in prop::collection::vec(0usize..EN_FRAGMENTS.len() + JA_FRAGMENTS.len(), 3..40),
+        piece in prop::sample::select(vec![1usize, 7, 33, 64, 100, 4096]),
+        trim_trailing in any::<bool>(),
+    ) {
+        let en = build_text(EN_FRAGMENTS, &indices, trim_trailing);
+        prop_assert_eq!(streamed(&en, "en", piece), reference(&en, "en"), "piece={}", piece);
+        let ja = build_text(JA_FRAGMENTS, &indices, trim_trailing);
+        prop_assert_eq!(streamed(&ja, "ja", piece), reference(&ja, "ja"), "piece={}", piece);
+    }
 }
@@ -0,0 +1,33 @@
+//! Helpers shared by the integration tests
+
+// Each test crate uses some of these
+#![allow(dead_code)]
+
+use sakurs_core::{Input, SentenceProcessor};
+
+/// Offsets of the boundaries `processor` finds in `text`
+pub fn boundaries(processor: &SentenceProcessor, text: &str) -> Vec<usize> {
+    processor
+        .process(Input::from_text(text))
+        .unwrap()
+        .boundaries
+        .iter()
+        .map(|b| b.offset)
+        .collect()
+}
+
+/// The trimmed sentences `processor` finds in `text`; text after the last
+/// boundary is the final sentence
+pub fn sentences(processor: &SentenceProcessor, text: &str) -> Vec<String> {
+    let mut start = 0;
+    let mut sentences = Vec::new();
+    for offset in boundaries(processor, text) {
+        sentences.push(text[start..offset].trim().to_string());
+        start = offset;
+    }
+    let rest = text[start..].trim();
+    if !rest.is_empty() {
+        sentences.push(rest.to_string());
+    }
+    sentences
+}
@@ -28,6 +28,22 @@ fn test_english_abbreviations() {
     assert_eq!(result.boundaries[0].offset, 64); // Only after the final period
 }
 
+#[test]
+fn test_english_conditional_and_spaced_abbreviations() {
+    let processor = SentenceProcessor::with_language("en").unwrap();
+
+    // "No." is an abbreviation only before a number
+    let text = "See No. 5 for details. I said no. Then I left.";
+    let result = processor.process(Input::from_text(text)).unwrap();
+    let offsets: Vec<usize> = result.boundaries.iter().map(|b| b.offset).collect();
+    assert_eq!(offsets, vec![22, 33, 46]);
+
+    // Spaced abbreviations match across the whitespace
+    let text = "The U. S. market opened at 9 a. m. today.";
+    let result = processor.process(Input::from_text(text)).unwrap();
+    assert_eq!(result.boundaries.len(), 1);
+}
+
 #[test]
 fn test_english_ellipsis_handling() {
     let processor = SentenceProcessor::with_language("en").unwrap();
@@ -185,7 +201,7 @@ fn test_edge_cases_with_configurable() {
     // Only punctuation - ellipsis followed by compound punctuation
     let text = "...!?";
     let result = processor.process(Input::from_text(text)).unwrap();
-    assert_eq!(result.boundaries.len(), 2); // One after "...", one after "!?"
+    assert_eq!(result.boundaries.len(), 1); // One run, ending after "!?"
 
     // Test compound punctuation alone
     let text2 = "What!?";
@@ -0,0 +1,96 @@
+//! Cross-chunk determinism: boundaries are byte-for-byte identical however
+//! the text is cut.
+//!
+//! `chunk_invariance.rs` samples realistic chunk sizes over large texts;
+//! these tests sweep every chunk size from 1 byte to the whole text, plus
+//! random stream split points, through
+//! [`SentenceProcessor::verify_determinism`]. Short texts keep the sweep
+//! exhaustive: every cut lands at every position, including inside
+//! abbreviations, numbers, ellipses, quotes and multi-byte characters.
+
+use proptest::prelude::*;
+use sakurs_core::SentenceProcessor;
+
+fn assert_deterministic(text: &str, lang: &str) {
+    let processor = SentenceProcessor::with_language(lang).expect("processor should build");
+    if let Err(violation) = processor.verify_determinism(text, text.len()) {
+        panic!("{violation}\ntext: {text:?}");
+    }
+}
+
+#[test]
+fn english_edge_cases_are_deterministic() {
+    for text in [
+        "Dr. Smith met Mr. Jones at the U.S. embassy. They talked.",
+        "The value is 3.14 exactly. Version 2.0.1 shipped! Was it at 3 p.m.? Yes.",
+        "Wait... what happened next?! Nobody knew. . . Really.",
+        "He said \"Hello there. It is me.\" and left. She (quietly) agreed.",
+        "'Stop.' \"Why?\" (Because.) [Done.] Then more text",
+        "Line one.\r\nLine two!\n\nLine three? ",
+        "e.g. this, i.e. that. Ends here.",
+        "",
+        ".",
+        "No terminator at all",
+    ] {
+        assert_deterministic(text, "en");
+    }
+}
+
+#[test]
+fn japanese_edge_cases_are_deterministic() {
+    for text in [
+        "彼は「こんにちは。元気？」と言った。今日は良い天気です。",
+        "彼女は『それは素晴らしい』と答えた！明日も晴れるでしょうか？",
+        "（注：これは例です。）次の文。……本当に？",
+        "カフェ\u{301}で🇯🇵の旗を見た。終わり",
+    ] {
+        assert_deterministic(text, "ja");
+    }
+}
+
+#[test]
+fn other_languages_are_deterministic() {
+    for (text, lang) in [
+        ("วันนี้อากาศดีมากเลย เราไปเที่ยวทะเลกันดีกว่า ขอบคุณมากครับ", "th"),
+        (
+            "O Sr. Silva chegou às 10h. Ele disse: «Olá.» Tudo bem?",
+            "pt",
+        ),
+        ("Il sig. Rossi è arrivato. Ha detto «Ciao.» Va bene?", "it"),
+        ("مرحبا بكم. كيف حالك؟ أنا بخير!", "ar"),
+        ("यह एक वाक्य है। क्या यह दूसरा है? हाँ!", "hi"),
+    ] {
+        assert_deterministic(text, lang);
+    }
+}
+
+const FRAGMENTS: &[&str] = &[
+    "The quick brown fox. ",
+    "He said \"Hi. Bye.\" and left. ",
+    "Dr. Brown (of the U.S. Navy) came. ",
+    "It cost 3.50 dollars! ",
+    "Wait... what? ",
+    "Done?! ",
+    "彼は「はい。」と言った。",
+    "本当？",
+];
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(24))]
+
+    /// Random fragment sequences, checked at every chunk size up to 64
+    /// bytes and at random stream split points.
+    #[test]
+    fn generated_text_is_deterministic(
+        indices in prop::collection::vec(0..FRAGMENTS.len(), 1..8),
+        trim_trailing in any::<bool>(),
+    ) {
+        let mut text: String = indices.iter().map(|&i| FRAGMENTS[i]).collect();
+        if trim_trailing {
+            text.truncate(text.trim_end().len());
+        }
+        let processor = SentenceProcessor::with_language("en").expect("processor should build");
+        let result = processor.verify_determinism(&text, 64);
+        prop_assert!(result.is_ok(), "{}", result.unwrap_err());
+    }
+}
@@ -0,0 +1,66 @@
+//! Integration tests for the Hindi language pack
+//!
+//! Covers the danda and double danda terminators, Devanagari digits in
+//! decimal suppression, and abbreviations through the public API.
+
+mod common;
+
+use sakurs_core::{Language, SentenceProcessor};
+
+fn sentences(text: &str) -> Vec<String> {
+    common::sentences(&SentenceProcessor::with_language("hi").unwrap(), text)
+}
+
+#[test]
+fn test_danda_terminators() {
+    assert_eq!(
+        sentences("मैं घर जा रहा हूँ। क्या तुम आओगे? हाँ, मैं आऊँगा! धन्यवाद॥ फिर मिलेंगे"),
+        [
+            "मैं घर जा रहा हूँ।",
+            "क्या तुम आओगे?",
+            "हाँ, मैं आऊँगा!",
+            "धन्यवाद॥",
+            "फिर मिलेंगे"
+        ]
+    );
+}
+
+#[test]
+fn test_typed_double_danda() {
+    assert_eq!(
+        sentences("राम वन को गए।। सीता भी साथ गईं।।"),
+        ["राम वन को गए।।", "सीता भी साथ गईं।।"]
+    );
+}
+
+#[test]
+fn test_devanagari_decimals() {
+    assert_eq!(
+        sentences("पाई का मान ३.१४ है। तापमान 36.6 डिग्री था। कुल १२.५० रुपये लगे।"),
+        [
+            "पाई का मान ३.१४ है।",
+            "तापमान 36.6 डिग्री था।",
+            "कुल १२.५० रुपये लगे।"
+        ]
+    );
+}
+
+#[test]
+fn test_abbreviations() {
+    assert_eq!(
+        sentences("डॉ. शर्मा और प्रो. वर्मा आए। यह सन् 1857 ई. की बात है। फिर क्या हुआ?"),
+        [
+            "डॉ. शर्मा और प्रो. वर्मा आए।",
+            "यह सन् 1857 ई. की बात है।",
+            "फिर क्या हुआ?"
+        ]
+    );
+}
+
+#[test]
+fn test_language_code_and_name() {
+    let language: Language = "hi".parse().unwrap();
+    assert_eq!(language.code(), "hi");
+    assert_eq!(language.name(), "Hindi");
+    assert!(SentenceProcessor::with_language("hindi").is_ok());
+}
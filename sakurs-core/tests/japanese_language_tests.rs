@@ -110,3 +110,63 @@ fn test_japanese_special_characters() {
     let boundary_count = result.boundaries.len();
     assert_eq!(boundary_count, 2);
 }
+
+#[test]
+fn test_japanese_trailing_kaomoji_and_symbols() {
+    let config = Config::builder().language("ja").unwrap().build().unwrap();
+    let processor = SentenceProcessor::with_config(config).unwrap();
+    let split = |text: &str| -> Vec<String> {
+        let result = processor.process(Input::from_text(text)).unwrap();
+        let mut start = 0;
+        result
+            .boundaries
+            .iter()
+            .map(|b| {
+                let sentence = text[start..b.offset].to_string();
+                start = b.offset;
+                sentence
+            })
+            .collect()
+    };
+
+    assert_eq!(
+        split("楽しかった！(笑)明日も行く。またね。♪☆最高！！ｗｗｗ"),
+        [
+            "楽しかった！(笑)",
+            "明日も行く。",
+            "またね。♪☆",
+            "最高！！ｗｗｗ"
+        ]
+    );
+    assert_eq!(
+        split("疲れた。（泣）でも頑張る(^_^)。"),
+        ["疲れた。（泣）", "でも頑張る(^_^)。"]
+    );
+    // A parenthetical that is not a configured pattern starts the next sentence
+    assert_eq!(
+        split("終わった。(詳細は後で)了解。"),
+        ["終わった。", "(詳細は後で)了解。"]
+    );
+}
+
+#[test]
+fn test_japanese_trailing_patterns_across_chunks() {
+    let text = "楽しかった！(笑)明日も行く。またね。♪☆最高！！ｗｗｗ".repeat(50);
+    let boundaries = |builder: sakurs_core::ConfigBuilder| {
+        let config = builder.language("ja").unwrap().build().unwrap();
+        SentenceProcessor::with_config(config)
+            .unwrap()
+            .process(Input::from_text(text.as_str()))
+            .unwrap()
+            .boundaries
+            .iter()
+            .map(|b| b.offset)
+            .collect::<Vec<_>>()
+    };
+    let sequential = boundaries(Config::builder().threads(Some(1)));
+    assert_eq!(sequential.len(), 50 * 4);
+    for chunk_size in [40, 64, 99] {
+        let parallel = boundaries(Config::builder().chunk_size(chunk_size).threads(Some(4)));
+        assert_eq!(parallel, sequential, "chunk size {chunk_size}");
+    }
+}
@@ -0,0 +1,90 @@
+//! Integration tests for the legal profile
+//!
+//! `Profile::Legal` adds court-rule, reporter and signal abbreviations and
+//! keeps citations ("Fed. R. Civ. P. 12(b)(6)", "42 U.S.C. § 1983") and
+//! numbered subsections in one sentence.
+
+mod common;
+
+use sakurs_core::{Config, ConfigBuilder, Profile, SentenceProcessor};
+
+fn processor(builder: ConfigBuilder) -> SentenceProcessor {
+    SentenceProcessor::with_config(builder.build().unwrap()).unwrap()
+}
+
+fn boundaries(builder: ConfigBuilder, text: &str) -> Vec<usize> {
+    common::boundaries(&processor(builder), text)
+}
+
+fn sentences(builder: ConfigBuilder, text: &str) -> Vec<String> {
+    common::sentences(&processor(builder), text)
+}
+
+fn legal() -> ConfigBuilder {
+    Config::builder().profile(Profile::Legal)
+}
+
+#[test]
+fn test_citations_stay_together() {
+    let text = "Defendant moved to dismiss under Fed. R. Civ. P. 12(b)(6). \
+                The claim arises under 42 U.S.C. § 1983. \
+                See Brown v. Board of Educ., 347 U.S. 483 (1954). \
+                Id. at 495. The motion is denied.";
+    assert_eq!(
+        sentences(legal(), text),
+        [
+            "Defendant moved to dismiss under Fed. R. Civ. P. 12(b)(6).",
+            "The claim arises under 42 U.S.C. § 1983.",
+            "See Brown v. Board of Educ., 347 U.S. 483 (1954).",
+            "Id. at 495.",
+            "The motion is denied."
+        ]
+    );
+    // Without the profile the rule citation is split
+    assert!(sentences(Config::builder(), text).len() > 5);
+}
+
+#[test]
+fn test_reporters_and_short_forms() {
+    assert_eq!(
+        sentences(
+            legal(),
+            "Smith v. Jones, 123 F. Supp. 2d 456 (S.D.N.Y. 2000), aff'd, 45 F.3d 1 (2d Cir. 2001). It was reversed."
+        ),
+        [
+            "Smith v. Jones, 123 F. Supp. 2d 456 (S.D.N.Y. 2000), aff'd, 45 F.3d 1 (2d Cir. 2001).",
+            "It was reversed."
+        ]
+    );
+}
+
+#[test]
+fn test_numbered_subsections() {
+    let text = "1. Plaintiff filed suit.\n2. Defendant answered.\n  iv. Costs are taxed.";
+    assert_eq!(
+        sentences(legal(), text),
+        [
+            "1. Plaintiff filed suit.",
+            "2. Defendant answered.",
+            "iv. Costs are taxed."
+        ]
+    );
+    // Mid-line numbers still end sentences
+    assert_eq!(
+        sentences(legal(), "The vote was 5. It passed."),
+        ["The vote was 5.", "It passed."]
+    );
+}
+
+#[test]
+fn test_legal_profile_matches_across_chunks() {
+    let text = "1. Relief is sought under Fed. R. Civ. P. 12(b)(6) and 42 U.S.C. § 1983.\n\
+                2. See Smith v. Jones, 123 F. Supp. 2d 456. Id. at 457.\n"
+        .repeat(30);
+    let sequential = boundaries(legal().threads(Some(1)), &text);
+    for chunk_size in [37, 64, 101] {
+        let parallel = boundaries(legal().chunk_size(chunk_size).threads(Some(4)), &text);
+        assert_eq!(parallel, sequential, "chunk size {chunk_size}");
+    }
+    assert_eq!(sequential.len(), 30 * 3);
+}
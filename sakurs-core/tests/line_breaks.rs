@@ -0,0 +1,79 @@
+//! Line break handling: Windows CRLF, old Mac CR and the Unicode line and
+//! paragraph separators (U+2028, U+2029) behave like LF, both for the
+//! boundary rules and for whitespace trimming.
+
+mod common;
+
+use common::sentences;
+use sakurs_core::{Config, Input, LanguageConfig, SentenceProcessor};
+
+const SEPARATORS: [&str; 4] = ["\r\n", "\r", "\u{2028}", "\u{2029}"];
+
+#[test]
+fn separators_split_like_lf() {
+    let cases = [
+        (
+            "en",
+            "Hello world.\nThis is a test.\nDr.\nSmith came.\nThe U.S.\nThe end",
+        ),
+        (
+            "en",
+            "Wait...\nThen more.\nIt is 5 p.m.\nWe left.\n(a) one.\n(b) two.",
+        ),
+        ("ja", "これは本です。\nそれは猫です！\n「元気？」\nはい。"),
+    ];
+    for (lang, text) in cases {
+        let processor = SentenceProcessor::with_language(lang).unwrap();
+        let expected = sentences(&processor, text);
+        for sep in SEPARATORS {
+            let converted = text.replace('\n', sep);
+            let got: Vec<String> = sentences(&processor, &converted)
+                .into_iter()
+                .map(|s| s.replace(sep, "\n"))
+                .collect();
+            assert_eq!(got, expected, "{lang} with {sep:?}");
+        }
+    }
+}
+
+#[test]
+fn crlf_is_trimmed_from_sentences() {
+    let processor = SentenceProcessor::with_language("en").unwrap();
+    let text = "First line.\r\nSecond line.\r\n\r\nThird line.\u{2029}Fourth line.\r\n";
+    assert_eq!(
+        sentences(&processor, text),
+        ["First line.", "Second line.", "Third line.", "Fourth line."]
+    );
+}
+
+#[test]
+fn line_start_rules_see_every_line_break() {
+    // An opening paren at a line start is a list marker, not an enclosure
+    let config: LanguageConfig = toml::from_str(
+        r#"
+        [metadata]
+        code = "xx"
+        name = "Test"
+        [terminators]
+        chars = ["."]
+        [ellipsis]
+        patterns = []
+        [enclosures]
+        pairs = [{ open = "(", close = ")" }]
+        [suppression]
+        fast_patterns = [{ char = "(", line_start = true }]
+        "#,
+    )
+    .unwrap();
+    let processor = SentenceProcessor::with_language_config(Config::default(), &config).unwrap();
+
+    for sep in ["\n"].into_iter().chain(SEPARATORS) {
+        let text = format!("This is a long introduction.{sep}(Item one. Item two.");
+        let count = processor
+            .process(Input::from_text(text))
+            .unwrap()
+            .boundaries
+            .len();
+        assert_eq!(count, 3, "line start after {sep:?}");
+    }
+}
@@ -0,0 +1,124 @@
+//! Integration tests for the Portuguese and Italian language packs
+//!
+//! Both languages are pure configuration (`configs/languages/*.toml`); these
+//! tests exercise their abbreviations, ordinals and quotation marks through
+//! the public API.
+
+mod common;
+
+use sakurs_core::{Language, SentenceProcessor};
+
+fn sentences(lang: &str, text: &str) -> Vec<String> {
+    common::sentences(&SentenceProcessor::with_language(lang).unwrap(), text)
+}
+
+#[test]
+fn test_portuguese_abbreviations() {
+    assert_eq!(
+        sentences(
+            "pt",
+            "O Sr. Silva e a Dra. Costa chegaram. Veja a pág. seguinte do relatório. Tudo certo!"
+        ),
+        [
+            "O Sr. Silva e a Dra. Costa chegaram.",
+            "Veja a pág. seguinte do relatório.",
+            "Tudo certo!"
+        ]
+    );
+}
+
+#[test]
+fn test_portuguese_abbreviation_before_sentence_starter() {
+    assert_eq!(
+        sentences(
+            "pt",
+            "Compramos frutas, legumes etc. Depois fomos para casa."
+        ),
+        ["Compramos frutas, legumes etc.", "Depois fomos para casa."]
+    );
+}
+
+#[test]
+fn test_portuguese_ordinals() {
+    assert_eq!(
+        sentences(
+            "pt",
+            "Ele ficou em 1.º lugar na 2.ª edição. Ela ficou em 3.º lugar."
+        ),
+        [
+            "Ele ficou em 1.º lugar na 2.ª edição.",
+            "Ela ficou em 3.º lugar."
+        ]
+    );
+}
+
+#[test]
+fn test_portuguese_guillemets_and_elision() {
+    assert_eq!(
+        sentences(
+            "pt",
+            "Ele disse «Vou embora. Até logo.» e saiu. Bebeu um copo d'água. Fim."
+        ),
+        [
+            "Ele disse «Vou embora. Até logo.» e saiu.",
+            "Bebeu um copo d'água.",
+            "Fim."
+        ]
+    );
+}
+
+#[test]
+fn test_italian_abbreviations() {
+    assert_eq!(
+        sentences(
+            "it",
+            "Il Sig. Rossi e la Dott.ssa Bianchi sono arrivati. Ho comprato pane, latte ecc. Poi sono tornato."
+        ),
+        [
+            "Il Sig. Rossi e la Dott.ssa Bianchi sono arrivati.",
+            "Ho comprato pane, latte ecc.",
+            "Poi sono tornato."
+        ]
+    );
+}
+
+#[test]
+fn test_italian_company_and_ordinals() {
+    assert_eq!(
+        sentences(
+            "it",
+            "Lavora alla Fiat S.p.A. da anni. È arrivato 1.° alla gara. Bravo!"
+        ),
+        [
+            "Lavora alla Fiat S.p.A. da anni.",
+            "È arrivato 1.° alla gara.",
+            "Bravo!"
+        ]
+    );
+}
+
+#[test]
+fn test_italian_apostrophes_do_not_suppress_boundaries() {
+    assert_eq!(
+        sentences(
+            "it",
+            "Aspetta un po' qui. L'uomo è andato via. Dov'è? Non lo so."
+        ),
+        [
+            "Aspetta un po' qui.",
+            "L'uomo è andato via.",
+            "Dov'è?",
+            "Non lo so."
+        ]
+    );
+}
+
+#[test]
+fn test_language_codes_and_names() {
+    for (code, name) in [("pt", "Portuguese"), ("it", "Italian")] {
+        let language: Language = code.parse().unwrap();
+        assert_eq!(language.code(), code);
+        assert_eq!(language.name(), name);
+        assert!(SentenceProcessor::with_language(name.to_lowercase()).is_ok());
+    }
+}
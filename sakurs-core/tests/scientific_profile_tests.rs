@@ -0,0 +1,82 @@
+//! Integration tests for the scientific profile
+//!
+//! `Profile::Scientific` adds cross-reference and Latin abbreviations and
+//! keeps citations, DOIs, arXiv identifiers and decimals without a leading
+//! zero inside their sentences.
+
+mod common;
+
+use sakurs_core::{Config, ConfigBuilder, Profile, SentenceProcessor};
+
+/// A structured abstract in the style of a PubMed record
+const ABSTRACT: &str = "BACKGROUND: Prior cohorts (Smith et al. (2019); Lee et al., 2020) \
+reported a mean decline of ca. 2.5 mmol/L, resp. 3.1 mmol/L in older adults. \
+METHODS: We enrolled 412 patients (aged 45.2 ± 8.1 years), cf. Fig. 1 and Eq. (2). \
+Effects were tested at p < .05, e.g., for HbA1c and LDL-C. \
+RESULTS: HbA1c fell by 0.8% (95% CI 0.5-1.1; Fig. 3A), i.e., more than expected. \
+Data are available at doi:10.1016/j.cell.2020.01.001 and arXiv:2101.00001v2. \
+CONCLUSIONS: The effect was robust.";
+
+fn processor(builder: ConfigBuilder) -> SentenceProcessor {
+    SentenceProcessor::with_config(builder.build().unwrap()).unwrap()
+}
+
+fn boundaries(builder: ConfigBuilder, text: &str) -> Vec<usize> {
+    common::boundaries(&processor(builder), text)
+}
+
+fn sentences(builder: ConfigBuilder, text: &str) -> Vec<String> {
+    common::sentences(&processor(builder), text)
+}
+
+fn scientific() -> ConfigBuilder {
+    Config::builder().profile(Profile::Scientific)
+}
+
+#[test]
+fn test_pubmed_abstract() {
+    assert_eq!(
+        sentences(scientific(), ABSTRACT),
+        [
+            "BACKGROUND: Prior cohorts (Smith et al. (2019); Lee et al., 2020) \
+             reported a mean decline of ca. 2.5 mmol/L, resp. 3.1 mmol/L in older adults.",
+            "METHODS: We enrolled 412 patients (aged 45.2 ± 8.1 years), cf. Fig. 1 and Eq. (2).",
+            "Effects were tested at p < .05, e.g., for HbA1c and LDL-C.",
+            "RESULTS: HbA1c fell by 0.8% (95% CI 0.5-1.1; Fig. 3A), i.e., more than expected.",
+            "Data are available at doi:10.1016/j.cell.2020.01.001 and arXiv:2101.00001v2.",
+            "CONCLUSIONS: The effect was robust."
+        ]
+    );
+    // Without the profile the cross-references and identifiers are split
+    assert!(sentences(Config::builder(), ABSTRACT).len() > 6);
+}
+
+#[test]
+fn test_cross_references() {
+    assert_eq!(
+        sentences(
+            scientific(),
+            "As shown by Smith et al. (2020) in Fig. 3, see also Figs. 4-5. Results follow."
+        ),
+        [
+            "As shown by Smith et al. (2020) in Fig. 3, see also Figs. 4-5.",
+            "Results follow."
+        ]
+    );
+    // An abbreviation before a new sentence still ends it
+    assert_eq!(
+        sentences(scientific(), "We thank Smith et al. The work was funded."),
+        ["We thank Smith et al.", "The work was funded."]
+    );
+}
+
+#[test]
+fn test_scientific_profile_matches_across_chunks() {
+    let text = format!("{ABSTRACT} ").repeat(20);
+    let sequential = boundaries(scientific().threads(Some(1)), &text);
+    for chunk_size in [37, 64, 101] {
+        let parallel = boundaries(scientific().chunk_size(chunk_size).threads(Some(4)), &text);
+        assert_eq!(parallel, sequential, "chunk size {chunk_size}");
+    }
+    assert_eq!(sequential.len(), 20 * 6);
+}
@@ -0,0 +1,92 @@
+//! Integration tests for the social profile
+//!
+//! `Profile::Social` bundles emoji terminators, protection of URLs, handles
+//! and hashtags, and a boundary at every line break after text.
+
+mod common;
+
+use sakurs_core::{Config, ConfigBuilder, Profile, SentenceProcessor};
+
+fn processor(builder: ConfigBuilder) -> SentenceProcessor {
+    SentenceProcessor::with_config(builder.build().unwrap()).unwrap()
+}
+
+fn boundaries(builder: ConfigBuilder, text: &str) -> Vec<usize> {
+    common::boundaries(&processor(builder), text)
+}
+
+fn sentences(builder: ConfigBuilder, text: &str) -> Vec<String> {
+    common::sentences(&processor(builder), text)
+}
+
+fn social() -> ConfigBuilder {
+    Config::builder().profile(Profile::Social)
+}
+
+#[test]
+fn test_web_tokens_are_not_split() {
+    let text = "Read https://example.com/a.b?x=1. Ping @john.doe now. See www.test.org! Big #day.one today.";
+    assert_eq!(
+        sentences(social(), text),
+        [
+            "Read https://example.com/a.b?x=1.",
+            "Ping @john.doe now.",
+            "See www.test.org!",
+            "Big #day.one today."
+        ]
+    );
+    // Without the profile every period is a candidate
+    assert!(sentences(Config::builder(), text).len() > 4);
+}
+
+#[test]
+fn test_line_breaks_end_sentences() {
+    assert_eq!(
+        sentences(social(), "lol same\r\nwhere r u\n\nomw 🏃 See you. bye\n"),
+        ["lol same", "where r u", "omw 🏃", "See you.", "bye"]
+    );
+    // A line already ended by a boundary gets no second one
+    assert_eq!(boundaries(social(), "Done.\nNext\n"), [5, 11]);
+}
+
+#[test]
+fn test_explicit_options_override_the_profile() {
+    let text = "lol same\nwhere r u 😂 ok";
+    assert_eq!(
+        sentences(
+            social()
+                .line_break_boundaries(false)
+                .emoji_terminators(false),
+            text
+        ),
+        [text]
+    );
+    assert_eq!(
+        sentences(Config::builder().line_break_boundaries(true), text),
+        ["lol same", "where r u 😂 ok"]
+    );
+    assert_eq!(
+        sentences(
+            Config::builder().protect_web_tokens(true),
+            "Mail a@b.co now."
+        ),
+        ["Mail a@b.co now."]
+    );
+}
+
+#[test]
+fn test_profile_from_str() {
+    assert_eq!("Social".parse::<Profile>().unwrap(), Profile::Social);
+    assert!("tweets".parse::<Profile>().is_err());
+}
+
+#[test]
+fn test_social_profile_matches_across_chunks() {
+    let text = "Ping @john.doe at https://x.io/a.b?c=1 now!! 😂😂 new line\nok. ".repeat(40);
+    let sequential = boundaries(social().threads(Some(1)), &text);
+    for chunk_size in [37, 64, 101] {
+        let parallel = boundaries(social().chunk_size(chunk_size).threads(Some(4)), &text);
+        assert_eq!(parallel, sequential, "chunk size {chunk_size}");
+    }
+    assert_eq!(sequential.len(), 40 * 3);
+}
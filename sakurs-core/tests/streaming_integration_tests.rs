@@ -234,3 +234,173 @@ fn test_streaming_metadata() {
     assert!(result.metadata.duration.as_nanos() > 0);
     assert!(result.metadata.chunks_processed > 0);
 }
+
+#[test]
+fn test_sentence_stream_matches_batch_offsets() {
+    let processor = SentenceProcessor::with_language("ja").unwrap();
+    let text = "「こんにちは。」と彼は言った。今日は晴れです！明日は雨でしょうか？".repeat(10);
+
+    let batch = processor.process(Input::from_text(text.as_str())).unwrap();
+
+    let mut stream = processor.stream();
+    let mut streamed = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    for piece in chars.chunks(5) {
+        let piece: String = piece.iter().collect();
+        streamed.extend(stream.feed(&piece));
+    }
+    assert_eq!(stream.bytes_processed(), text.len());
+    streamed.extend(stream.finish());
+
+    let expected: Vec<(usize, usize)> = batch
+        .boundaries
+        .iter()
+        .map(|b| (b.offset, b.char_offset))
+        .collect();
+    let got: Vec<(usize, usize)> = streamed.iter().map(|b| (b.offset, b.char_offset)).collect();
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn test_stream_resumes_from_serialized_checkpoint() {
+    let processor = SentenceProcessor::with_language("ja").unwrap();
+    let text =
+        "「こんにちは。」と彼は言った。（今日は晴れです！明日は？）雨でしょうか。".repeat(10);
+    let batch = processor.process(Input::from_text(text.as_str())).unwrap();
+
+    // Checkpoint after every piece, resuming each time from the JSON alone
+    let mut streamed = Vec::new();
+    let mut checkpoint = processor.stream().checkpoint();
+    let chars: Vec<char> = text.chars().collect();
+    for piece in chars.chunks(7) {
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        let mut stream = processor
+            .resume_stream(serde_json::from_str(&json).unwrap())
+            .unwrap();
+        streamed.extend(stream.feed(&piece.iter().collect::<String>()));
+        checkpoint = stream.checkpoint();
+    }
+    assert_eq!(checkpoint.bytes_processed(), text.len());
+    streamed.extend(processor.resume_stream(checkpoint).unwrap().finish());
+
+    let expected: Vec<(usize, usize)> = batch
+        .boundaries
+        .iter()
+        .map(|b| (b.offset, b.char_offset))
+        .collect();
+    let got: Vec<(usize, usize)> = streamed.iter().map(|b| (b.offset, b.char_offset)).collect();
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn test_checkpoint_from_other_rules_is_rejected() {
+    let mut stream = SentenceProcessor::with_language("ja").unwrap().stream();
+    stream.feed("こんにちは。");
+    let result = SentenceProcessor::new().resume_stream(stream.checkpoint());
+    assert!(matches!(
+        result,
+        Err(sakurs_core::ApiError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_pipeline_matches_sentences_from_reader() {
+    let config = Config::builder()
+        .language("ja")
+        .unwrap()
+        .chunk_size(16)
+        .stream_buffers(2, 1)
+        .build()
+        .unwrap();
+    let processor = SentenceProcessor::with_config(config).unwrap();
+    let text = "「こんにちは。」と彼は言った。Dr. Smith left! 明日は雨でしょうか？ Tail".repeat(20);
+
+    let expected: Vec<_> = processor
+        .sentences_from_reader(ChunkedReader::new(&text, 7))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let mut pipeline = processor
+        .pipeline_from_reader(ChunkedReader::new(&text, 7))
+        .unwrap();
+    let got: Vec<_> = pipeline.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(got, expected);
+
+    let metrics = pipeline.metrics();
+    assert_eq!(metrics.bytes_read, text.len() as u64);
+    assert_eq!(
+        (metrics.read_queue.capacity, metrics.emit_queue.capacity),
+        (2, 1)
+    );
+    assert_eq!((metrics.read_queue.depth, metrics.emit_queue.depth), (0, 0));
+    assert!(metrics.read_queue.peak_depth <= 2 && metrics.emit_queue.peak_depth <= 1);
+}
+
+#[test]
+fn test_pipeline_stalls_behind_a_slow_consumer() {
+    /// Endless text
+    struct Endless;
+
+    impl Read for Endless {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            for (i, byte) in buf.iter_mut().enumerate() {
+                *byte = b"Word. "[i % 6];
+            }
+            Ok(buf.len() / 6 * 6)
+        }
+    }
+
+    let config = Config::builder()
+        .chunk_size(60)
+        .stream_buffers(3, 2)
+        .build()
+        .unwrap();
+    let processor = SentenceProcessor::with_config(config).unwrap();
+    let mut pipeline = processor.pipeline_from_reader(Endless).unwrap();
+    assert!(pipeline.next().unwrap().is_ok());
+
+    // Without the consumer, both queues fill and the reader stops
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    let metrics = loop {
+        let metrics = pipeline.metrics();
+        if metrics.read_queue.depth == 3 && metrics.emit_queue.depth == 2 {
+            break metrics;
+        }
+        assert!(std::time::Instant::now() < deadline, "{metrics:?}");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    };
+    // The queued pieces and batches, plus the piece each stage holds, the
+    // batch being consumed and a piece that completed no sentence
+    assert!(metrics.bytes_read <= (3 + 2 + 4) * 60, "{metrics:?}");
+    assert!(metrics.read_queue.full_waits > 0 || metrics.emit_queue.full_waits > 0);
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    assert_eq!(pipeline.metrics().bytes_read, metrics.bytes_read);
+
+    assert_eq!(pipeline.take(100).filter(|span| span.is_ok()).count(), 100);
+}
+
+#[test]
+fn test_pipeline_reports_errors() {
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+
+    let processor = SentenceProcessor::new();
+    let mut pipeline = processor.pipeline_from_reader(Failing).unwrap();
+    assert!(matches!(
+        pipeline.next(),
+        Some(Err(sakurs_core::ApiError::Infrastructure(message))) if message.contains("disk on fire")
+    ));
+    assert!(pipeline.next().is_none());
+
+    let mut pipeline = processor
+        .pipeline_from_reader(io::Cursor::new(b"Bad \xff byte.".to_vec()))
+        .unwrap();
+    assert!(matches!(pipeline.next(), Some(Err(_))));
+    assert!(pipeline.next().is_none());
+
+    assert!(Config::builder().stream_buffers(0, 4).build().is_err());
+}
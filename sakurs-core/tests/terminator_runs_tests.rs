@@ -0,0 +1,112 @@
+//! Integration tests for terminator runs, interrobangs and emoji terminators
+//!
+//! A run of terminators ("!!!", "?!?", "...!") ends one sentence at its last
+//! character; emoji terminate sentences only when enabled, either in the
+//! language configuration or with `ConfigBuilder::emoji_terminators`.
+
+mod common;
+
+use common::sentences as split;
+use sakurs_core::{Config, ConfigBuilder, Input, LanguageConfig, SentenceProcessor};
+
+fn sentences(builder: ConfigBuilder, text: &str) -> Vec<String> {
+    let processor = SentenceProcessor::with_config(builder.build().unwrap()).unwrap();
+    split(&processor, text)
+}
+
+#[test]
+fn test_repeated_punctuation_is_one_boundary() {
+    assert_eq!(
+        sentences(
+            Config::builder(),
+            "Wow!!! Really?!? No way?!! Wait...! Fine."
+        ),
+        ["Wow!!!", "Really?!?", "No way?!!", "Wait...!", "Fine."]
+    );
+    assert_eq!(
+        sentences(
+            Config::builder().language("ja").unwrap(),
+            "すごい！！！本当？！？はい。。了解!!"
+        ),
+        ["すごい！！！", "本当？！？", "はい。。", "了解!!"]
+    );
+}
+
+#[test]
+fn test_interrobang() {
+    assert_eq!(
+        sentences(Config::builder(), "You did what‽ I did. Really‽‽ Yes."),
+        ["You did what‽", "I did.", "Really‽‽", "Yes."]
+    );
+}
+
+#[test]
+fn test_runs_can_be_left_uncollapsed() {
+    let config: LanguageConfig = toml::from_str(
+        r#"
+        [metadata]
+        code = "xx"
+        name = "Test"
+        [terminators]
+        chars = [".", "!", "?"]
+        collapse_runs = false
+        [ellipsis]
+        patterns = []
+        [enclosures]
+        pairs = []
+        [suppression]
+        fast_patterns = []
+        "#,
+    )
+    .unwrap();
+    let processor = SentenceProcessor::with_language_config(Config::default(), &config).unwrap();
+    assert_eq!(split(&processor, "Wow!! Fine."), ["Wow!", "!", "Fine."]);
+}
+
+#[test]
+fn test_emoji_are_ignored_by_default() {
+    let text = "That was wild 😂😂 Next one.";
+    assert_eq!(sentences(Config::builder(), text), [text]);
+}
+
+#[test]
+fn test_sentence_final_emoji() {
+    let emoji = || Config::builder().emoji_terminators(true);
+    assert_eq!(
+        sentences(emoji(), "That was wild 😂😂 Next one. Hot 🔥🔥 today."),
+        ["That was wild 😂😂", "Next one.", "Hot 🔥🔥 today."]
+    );
+    // Emoji after a terminator stay with its sentence
+    assert_eq!(
+        sentences(emoji(), "Wow! 😂 See you!!👋🏽 Bye."),
+        ["Wow! 😂", "See you!!👋🏽", "Bye."]
+    );
+    // Joined sequences and flags are one emoji; the last one ends the text
+    assert_eq!(
+        sentences(emoji(), "Family 👨\u{200D}👩\u{200D}👧 Time 🇯🇵"),
+        ["Family 👨\u{200D}👩\u{200D}👧", "Time 🇯🇵"]
+    );
+}
+
+#[test]
+fn test_emoji_terminators_match_across_chunks() {
+    let text = "That was wild 😂😂 Next one. Wow! 😂 See you!!👋🏽 Bye. ".repeat(40);
+    let process = |builder: ConfigBuilder| {
+        let processor =
+            SentenceProcessor::with_config(builder.emoji_terminators(true).build().unwrap())
+                .unwrap();
+        processor
+            .process(Input::from_text(text.as_str()))
+            .unwrap()
+            .boundaries
+            .iter()
+            .map(|b| b.offset)
+            .collect::<Vec<_>>()
+    };
+    let sequential = process(Config::builder().threads(Some(1)));
+    for chunk_size in [37, 64, 101] {
+        let parallel = process(Config::builder().chunk_size(chunk_size).threads(Some(4)));
+        assert_eq!(parallel, sequential, "chunk size {chunk_size}");
+    }
+    assert_eq!(sequential.len(), 40 * 5);
+}
@@ -0,0 +1,120 @@
+//! Integration tests for the Thai language pack
+//!
+//! Thai has no terminal punctuation: sentences are found by the `space`
+//! boundary hypothesis strategy (see the `[hypothesis]` section of
+//! `configs/languages/thai.toml`), refined by particles and markers.
+
+mod common;
+
+use common::sentences;
+use sakurs_core::{Config, Language, LanguageConfig, SentenceProcessor};
+
+fn thai(text: &str) -> Vec<String> {
+    sentences(&SentenceProcessor::with_language("th").unwrap(), text)
+}
+
+#[test]
+fn test_space_between_long_runs() {
+    assert_eq!(
+        thai("วันนี้อากาศดีมากเลย เราไปเที่ยวทะเลกันดีกว่า"),
+        ["วันนี้อากาศดีมากเลย", "เราไปเที่ยวทะเลกันดีกว่า"]
+    );
+}
+
+#[test]
+fn test_particles_and_discourse_markers() {
+    // "ดังนั้น" opens a sentence even after a run shorter than min_chars
+    assert_eq!(
+        thai("ขอบคุณครับ พรุ่งนี้เจอกัน ฝนตก ดังนั้นเราจึงอยู่บ้าน"),
+        ["ขอบคุณครับ", "พรุ่งนี้เจอกัน", "ฝนตก", "ดังนั้นเราจึงอยู่บ้าน"]
+    );
+}
+
+#[test]
+fn test_continuation_markers_and_short_runs() {
+    assert_eq!(
+        thai("ผมชอบกินข้าวผัด และชอบกินต้มยำด้วย"),
+        ["ผมชอบกินข้าวผัด และชอบกินต้มยำด้วย"]
+    );
+    assert_eq!(
+        thai("เด็กๆ เล่นกันอยู่ในสนามหญ้าหน้าบ้าน"),
+        ["เด็กๆ เล่นกันอยู่ในสนามหญ้าหน้าบ้าน"]
+    );
+}
+
+#[test]
+fn test_numbers_abbreviations_and_terminators() {
+    // The period is not a terminator, and a space next to digits or
+    // punctuation is not a hypothesis
+    assert_eq!(
+        thai("ในปี พ.ศ. 2567 ประเทศไทยมีนักท่องเที่ยวจำนวนมาก คุณเคยไปไหม? ไปแล้วครับ"),
+        [
+            "ในปี พ.ศ. 2567 ประเทศไทยมีนักท่องเที่ยวจำนวนมาก",
+            "คุณเคยไปไหม?",
+            "ไปแล้วครับ"
+        ]
+    );
+}
+
+#[test]
+fn test_spaces_inside_quotes_are_not_boundaries() {
+    assert_eq!(
+        thai("เขาพูดว่า “วันนี้ฉันเหนื่อยมากจริงๆ อยากนอนพักผ่อน” แล้วก็เดินออกไป"),
+        ["เขาพูดว่า “วันนี้ฉันเหนื่อยมากจริงๆ อยากนอนพักผ่อน” แล้วก็เดินออกไป"]
+    );
+}
+
+#[test]
+fn test_custom_hypothesis_config() {
+    let config: LanguageConfig = toml::from_str(
+        r#"
+        [metadata]
+        code = "xx"
+        name = "Test"
+        [terminators]
+        chars = ["!"]
+        [ellipsis]
+        patterns = []
+        [enclosures]
+        pairs = []
+        [suppression]
+        [hypothesis]
+        strategy = "space"
+        min_chars = 3
+        "#,
+    )
+    .unwrap();
+    let processor = SentenceProcessor::with_language_config(Config::default(), &config).unwrap();
+    assert_eq!(sentences(&processor, "สวัสดี ลาก่อน"), ["สวัสดี", "ลาก่อน"]);
+}
+
+#[test]
+fn test_unknown_strategy_is_rejected() {
+    let config: LanguageConfig = toml::from_str(
+        r#"
+        [metadata]
+        code = "xx"
+        name = "Test"
+        [terminators]
+        chars = ["!"]
+        [ellipsis]
+        patterns = []
+        [enclosures]
+        pairs = []
+        [suppression]
+        [hypothesis]
+        strategy = "guess"
+        "#,
+    )
+    .unwrap();
+    assert!(config.validate().is_err());
+    assert!(SentenceProcessor::with_language_config(Config::default(), &config).is_err());
+}
+
+#[test]
+fn test_language_code_and_name() {
+    let language: Language = "th".parse().unwrap();
+    assert_eq!(language.code(), "th");
+    assert_eq!(language.name(), "Thai");
+    assert!(SentenceProcessor::with_language("thai").is_ok());
+}
@@ -88,3 +88,13 @@ fn balanced_parentheses_still_suppress() {
         "boundary inside parentheses must stay suppressed: {b:?}"
     );
 }
+
+/// A suppression regex only suppresses the delimiters it covers: the
+/// "(6)" of "12(b)(6)" used to suppress the ")" of "(b)" too, leaving "(b"
+/// open for the rest of the text.
+#[test]
+fn en_adjacent_numbered_reference_does_not_poison() {
+    let text = "Dismissed under Rule 12(b)(6). The claim fails. So it ends.";
+    let b = boundaries(text, "en");
+    assert_eq!(b, [30, 47, 59]);
+}
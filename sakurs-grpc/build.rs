@@ -0,0 +1,8 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Prefer a protoc from the environment, as prost-build does
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+    tonic_prost_build::compile_protos("proto/sakurs/v1/sakurs.proto")?;
+    Ok(())
+}
@@ -0,0 +1,365 @@
+//! gRPC service for Sakurs sentence boundary detection
+//!
+//! [`SplitterService`] implements the `sakurs.v1.SentenceSplitter` service of
+//! `proto/sakurs/v1/sakurs.proto`: `Split` for whole texts and the
+//! bidirectional `SplitStream` for documents sent in pieces. The
+//! `sakurs-grpc` binary serves it; applications with their own tonic server
+//! can add it next to their other services:
+//!
+//! ```no_run
+//! use sakurs_grpc::proto::sentence_splitter_server::SentenceSplitterServer;
+//! use sakurs_grpc::SplitterService;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! tonic::transport::Server::builder()
+//!     .add_service(SentenceSplitterServer::new(SplitterService::new("en")?))
+//!     .serve("127.0.0.1:50051".parse()?)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use sakurs_core::{
+    ApiError, BoundaryKind, Config, LanguageConfig, SentenceProcessor, SentenceSpan,
+};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status, Streaming};
+
+use proto::sentence_splitter_server::SentenceSplitter;
+use proto::{
+    ListLanguagesRequest, ListLanguagesResponse, Sentence, SplitOptions, SplitRequest,
+    SplitResponse, SplitStreamRequest, SplitStreamResponse,
+};
+
+/// Messages generated from `proto/sakurs/v1/sakurs.proto`
+pub mod proto {
+    tonic::include_proto!("sakurs.v1");
+}
+
+/// Largest chunk size a request may ask for, in KB
+const MAX_CHUNK_KB: u32 = 64 * 1024;
+
+/// Most processors kept compiled at once
+const MAX_PROCESSORS: usize = 64;
+
+/// Answers of a `SplitStream` call queued for sending; once this many are
+/// waiting, the server stops reading the request stream
+const STREAM_BUFFER: usize = 16;
+
+/// The `SentenceSplitter` service
+#[derive(Clone)]
+pub struct SplitterService {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    default_language: String,
+    /// Compiled processors by language code, threads and chunk size
+    processors: Mutex<ProcessorCache>,
+}
+
+type ProcessorKey = (String, Option<u32>, Option<u32>);
+
+/// Processors by key, with the order they were last used in; beyond
+/// [`MAX_PROCESSORS`] the least recently used are dropped
+#[derive(Default)]
+struct ProcessorCache {
+    entries: HashMap<ProcessorKey, (Arc<SentenceProcessor>, u64)>,
+    /// Keys by the tick they were last used at
+    order: BTreeMap<u64, ProcessorKey>,
+    tick: u64,
+}
+
+impl ProcessorCache {
+    fn get(&mut self, key: &ProcessorKey) -> Option<Arc<SentenceProcessor>> {
+        let (processor, used) = self.entries.get_mut(key)?;
+        let key = self.order.remove(used)?;
+        self.tick += 1;
+        *used = self.tick;
+        self.order.insert(self.tick, key);
+        Some(Arc::clone(processor))
+    }
+
+    fn insert(&mut self, key: ProcessorKey, processor: Arc<SentenceProcessor>) {
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.insert(key.clone(), (processor, self.tick)) {
+            self.order.remove(&used);
+        }
+        self.order.insert(self.tick, key);
+        while self.entries.len() > MAX_PROCESSORS {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+impl SplitterService {
+    /// A service splitting requests without a language as `default_language`
+    ///
+    /// Fails if `default_language` has no built-in rules.
+    pub fn new(default_language: &str) -> Result<Self, ApiError> {
+        let service = Self {
+            inner: Arc::new(Inner {
+                default_language: default_language.to_string(),
+                processors: Mutex::new(ProcessorCache::default()),
+            }),
+        };
+        service.processor(None)?;
+        Ok(service)
+    }
+
+    /// Look up (or compile and cache) the processor for request options;
+    /// returns it with the language code it uses. The thread count is
+    /// capped at the available parallelism and the chunk size at
+    /// [`MAX_CHUNK_KB`].
+    fn processor(
+        &self,
+        options: Option<&SplitOptions>,
+    ) -> Result<(String, Arc<SentenceProcessor>), ApiError> {
+        let (language, threads, chunk_kb) = match options {
+            Some(options) if !options.language.is_empty() => {
+                (options.language.clone(), options.threads, options.chunk_kb)
+            }
+            Some(options) => (
+                self.inner.default_language.clone(),
+                options.threads,
+                options.chunk_kb,
+            ),
+            None => (self.inner.default_language.clone(), None, None),
+        };
+        let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let threads = threads.map(|n| n.min(u32::try_from(available).unwrap_or(u32::MAX)));
+        let chunk_kb = chunk_kb.map(|kb| kb.min(MAX_CHUNK_KB));
+        let key = (language.clone(), threads, chunk_kb);
+        if let Some(processor) = self.inner.processors.lock().unwrap().get(&key) {
+            return Ok((language, processor));
+        }
+
+        let mut builder = Config::builder().language(&language)?;
+        if let Some(threads) = threads {
+            builder = builder.threads(Some(threads as usize));
+        }
+        if let Some(chunk_kb) = chunk_kb {
+            let bytes = (chunk_kb as usize).checked_mul(1024).ok_or_else(|| {
+                ApiError::Configuration(format!("chunk_kb {chunk_kb} is too large"))
+            })?;
+            builder = builder.chunk_size(bytes);
+        }
+        let processor = Arc::new(SentenceProcessor::with_config(builder.build()?)?);
+        self.inner
+            .processors
+            .lock()
+            .unwrap()
+            .insert(key, Arc::clone(&processor));
+        Ok((language, processor))
+    }
+}
+
+#[tonic::async_trait]
+impl SentenceSplitter for SplitterService {
+    async fn split(
+        &self,
+        request: Request<SplitRequest>,
+    ) -> Result<Response<SplitResponse>, Status> {
+        let SplitRequest { text, options } = request.into_inner();
+        let (language, processor) = self.processor(options.as_ref()).map_err(to_status)?;
+        // Splitting is CPU-bound; keep it off the async workers
+        let sentences = tokio::task::spawn_blocking(move || {
+            let output = processor.process(sakurs_core::Input::from_text(text.as_str()))?;
+            let boundaries: Vec<(usize, BoundaryKind)> = output
+                .boundaries
+                .iter()
+                .map(|b| (b.offset, b.kind))
+                .collect();
+            Ok::<_, ApiError>(sentences(&text, &boundaries))
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(to_status)?;
+        Ok(Response::new(SplitResponse {
+            language,
+            sentences,
+        }))
+    }
+
+    type SplitStreamStream = ReceiverStream<Result<SplitStreamResponse, Status>>;
+
+    async fn split_stream(
+        &self,
+        request: Request<Streaming<SplitStreamRequest>>,
+    ) -> Result<Response<Self::SplitStreamStream>, Status> {
+        let mut inbound = request.into_inner();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(status) = service.pump(&mut inbound, &tx).await {
+                let _ = tx.send(Err(status)).await;
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn list_languages(
+        &self,
+        _request: Request<ListLanguagesRequest>,
+    ) -> Result<Response<ListLanguagesResponse>, Status> {
+        Ok(Response::new(ListLanguagesResponse {
+            languages: LanguageConfig::embedded()
+                .into_iter()
+                .map(|config| config.metadata.code.clone())
+                .collect(),
+        }))
+    }
+}
+
+impl SplitterService {
+    /// Feed the pieces of a `SplitStream` call to a sentence stream and
+    /// send back what each completes. Returns early (successfully) when the
+    /// client has gone away.
+    async fn pump(
+        &self,
+        inbound: &mut Streaming<SplitStreamRequest>,
+        tx: &mpsc::Sender<Result<SplitStreamResponse, Status>>,
+    ) -> Result<(), Status> {
+        let Some(first) = inbound.message().await? else {
+            return Ok(());
+        };
+        let (_, processor) = self.processor(first.options.as_ref()).map_err(to_status)?;
+        let mut stream = processor.stream();
+
+        let mut piece = Some(first.text);
+        while let Some(text) = piece {
+            let (returned, spans) = tokio::task::spawn_blocking(move || {
+                let spans = stream.feed_spans(&text);
+                (stream, spans)
+            })
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+            stream = returned;
+            if !send(tx, &spans).await {
+                return Ok(());
+            }
+            piece = inbound.message().await?.map(|request| request.text);
+        }
+
+        let spans = tokio::task::spawn_blocking(move || stream.finish_spans())
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        send(tx, &spans).await;
+        Ok(())
+    }
+}
+
+/// Send the trimmed sentences of `spans`; false once the client is gone
+async fn send(
+    tx: &mpsc::Sender<Result<SplitStreamResponse, Status>>,
+    spans: &[SentenceSpan],
+) -> bool {
+    let sentences = spans.iter().filter_map(trimmed_span).collect();
+    tx.send(Ok(SplitStreamResponse { sentences })).await.is_ok()
+}
+
+/// A stream sentence without its surrounding whitespace, or `None` if it
+/// is all whitespace
+fn trimmed_span(span: &SentenceSpan) -> Option<Sentence> {
+    let text = span.text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let leading = &span.text[..span.text.len() - span.text.trim_start().len()];
+    let start = span.start + leading.len();
+    let char_start = span.char_start + leading.chars().count();
+    Some(Sentence {
+        text: text.to_string(),
+        start: start as u64,
+        end: (start + text.len()) as u64,
+        char_start: char_start as u64,
+        char_end: (char_start + text.chars().count()) as u64,
+        kind: proto::BoundaryKind::Unspecified.into(),
+    })
+}
+
+/// Cut `text` at the boundary offsets into trimmed sentences, each with
+/// the kind of the boundary ending it; trailing text after the last
+/// boundary is the final sentence
+fn sentences(text: &str, boundaries: &[(usize, BoundaryKind)]) -> Vec<Sentence> {
+    let mut sentences = Vec::with_capacity(boundaries.len() + 1);
+    let mut start = 0;
+    let mut chars = 0;
+    for (end, kind) in boundaries
+        .iter()
+        .copied()
+        .chain(std::iter::once((text.len(), BoundaryKind::EndOfText)))
+    {
+        if end < start {
+            continue;
+        }
+        let segment = &text[start..end];
+        let leading = segment.len() - segment.trim_start().len();
+        let trimmed = segment.trim();
+        if !trimmed.is_empty() {
+            let char_start = chars + segment[..leading].chars().count();
+            sentences.push(Sentence {
+                text: trimmed.to_string(),
+                start: (start + leading) as u64,
+                end: (start + leading + trimmed.len()) as u64,
+                char_start: char_start as u64,
+                char_end: (char_start + trimmed.chars().count()) as u64,
+                kind: proto_kind(kind).into(),
+            });
+        }
+        chars += segment.chars().count();
+        start = end;
+    }
+    sentences
+}
+
+fn proto_kind(kind: BoundaryKind) -> proto::BoundaryKind {
+    match kind {
+        BoundaryKind::Strong => proto::BoundaryKind::Strong,
+        BoundaryKind::Weak => proto::BoundaryKind::Weak,
+        BoundaryKind::Forced => proto::BoundaryKind::Forced,
+        BoundaryKind::EndOfText => proto::BoundaryKind::EndOfText,
+    }
+}
+
+/// The gRPC status of a Sakurs error: bad options are the caller's fault
+fn to_status(e: ApiError) -> Status {
+    match e {
+        ApiError::InvalidLanguage(_) | ApiError::Configuration(_) | ApiError::InvalidInput(_) => {
+            Status::invalid_argument(e.to_string())
+        }
+        _ => Status::internal(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_processor_cache_is_bounded() {
+        let service = SplitterService::new("en").unwrap();
+        let options = |chunk_kb| SplitOptions {
+            chunk_kb: Some(chunk_kb),
+            ..SplitOptions::default()
+        };
+        let (_, first) = service.processor(Some(&options(1))).unwrap();
+        for chunk_kb in 2..=MAX_PROCESSORS as u32 + 1 {
+            service.processor(Some(&options(chunk_kb))).unwrap();
+            // Keep the first processor recently used
+            let (_, again) = service.processor(Some(&options(1))).unwrap();
+            assert!(Arc::ptr_eq(&first, &again));
+        }
+        let mut processors = service.inner.processors.lock().unwrap();
+        assert_eq!(processors.entries.len(), MAX_PROCESSORS);
+        // The least recently used, chunk_kb=2, was dropped
+        assert!(processors.get(&("en".to_string(), None, Some(2))).is_none());
+    }
+}
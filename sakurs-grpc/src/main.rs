@@ -0,0 +1,63 @@
+//! `sakurs-grpc`: serve the `sakurs.v1.SentenceSplitter` gRPC service
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use sakurs_grpc::proto::sentence_splitter_server::SentenceSplitterServer;
+use sakurs_grpc::SplitterService;
+
+/// Sentence boundary detection over gRPC
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    /// Address to bind
+    #[arg(long, default_value = "127.0.0.1", env = "SAKURS_GRPC_HOST")]
+    host: String,
+
+    /// Port to listen on
+    #[arg(short, long, default_value = "50051", env = "SAKURS_GRPC_PORT")]
+    port: u16,
+
+    /// Default language when a request sets none
+    #[arg(short, long, default_value = "en")]
+    language: String,
+
+    /// Maximum request message size in MB (a whole text for `Split`, one
+    /// piece for `SplitStream`)
+    #[arg(long, default_value = "10", value_name = "SIZE_MB")]
+    max_message_mb: usize,
+
+    /// Increase verbosity
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let level = match args.verbose {
+        0 => log::LevelFilter::Info,
+        1 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    env_logger::Builder::new().filter_level(level).init();
+
+    let service = SplitterService::new(&args.language)
+        .with_context(|| format!("Failed to load language '{}'", args.language))?;
+    let addr = format!("{}:{}", args.host, args.port)
+        .parse()
+        .with_context(|| format!("Invalid address {}:{}", args.host, args.port))?;
+    let server =
+        SentenceSplitterServer::new(service).max_decoding_message_size(args.max_message_mb << 20);
+
+    log::info!("Default language: {}", args.language);
+    log::info!("Listening on {addr}");
+    tonic::transport::Server::builder()
+        .add_service(server)
+        .serve_with_shutdown(addr, async {
+            let _ = tokio::signal::ctrl_c().await;
+            log::info!("Shutting down");
+        })
+        .await
+        .context("Server failed")?;
+    Ok(())
+}
@@ -0,0 +1,176 @@
+//! End-to-end tests of the gRPC service over a local connection
+
+use sakurs_grpc::proto::sentence_splitter_client::SentenceSplitterClient;
+use sakurs_grpc::proto::sentence_splitter_server::SentenceSplitterServer;
+use sakurs_grpc::proto::{
+    BoundaryKind, ListLanguagesRequest, SplitOptions, SplitRequest, SplitStreamRequest,
+};
+use sakurs_grpc::SplitterService;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Channel;
+use tonic::Code;
+
+/// Start a server on a free port and connect a client to it
+async fn client() -> SentenceSplitterClient<Channel> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = SplitterService::new("en").unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(SentenceSplitterServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    SentenceSplitterClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap()
+}
+
+fn options(language: &str) -> Option<SplitOptions> {
+    Some(SplitOptions {
+        language: language.to_string(),
+        ..SplitOptions::default()
+    })
+}
+
+#[tokio::test]
+async fn test_split() {
+    let mut client = client().await;
+    let response = client
+        .split(SplitRequest {
+            text: "Hello world. Dr. Smith arrived!  Trailing".to_string(),
+            options: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.language, "en");
+    let texts: Vec<&str> = response.sentences.iter().map(|s| s.text.as_str()).collect();
+    assert_eq!(texts, ["Hello world.", "Dr. Smith arrived!", "Trailing"]);
+    let last = &response.sentences[2];
+    assert_eq!((last.start, last.end), (33, 41));
+    assert_eq!(last.kind(), BoundaryKind::EndOfText);
+    assert_eq!(response.sentences[1].kind(), BoundaryKind::Strong);
+}
+
+#[tokio::test]
+async fn test_split_with_language_and_char_offsets() {
+    let mut client = client().await;
+    let response = client
+        .split(SplitRequest {
+            text: "これは文です。もう一つ。".to_string(),
+            options: options("ja"),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.language, "ja");
+    let second = &response.sentences[1];
+    assert_eq!(second.text, "もう一つ。");
+    assert_eq!((second.char_start, second.char_end), (7, 12));
+    assert_eq!((second.start, second.end), (21, 36));
+}
+
+#[tokio::test]
+async fn test_unknown_language_is_invalid_argument() {
+    let mut client = client().await;
+    let status = client
+        .split(SplitRequest {
+            text: "Hello.".to_string(),
+            options: options("xx"),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_huge_options_are_clamped() {
+    let mut client = client().await;
+    let response = client
+        .split(SplitRequest {
+            text: "Hello world. Goodbye.".to_string(),
+            options: Some(SplitOptions {
+                threads: Some(u32::MAX),
+                chunk_kb: Some(u32::MAX),
+                ..SplitOptions::default()
+            }),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.sentences.len(), 2);
+}
+
+#[tokio::test]
+async fn test_split_stream_matches_split() {
+    let mut client = client().await;
+    let text = "First sentence. Second one! Third? ".repeat(50) + "Trailing";
+    let pieces: Vec<SplitStreamRequest> = text
+        .as_bytes()
+        .chunks(7)
+        .map(|piece| SplitStreamRequest {
+            text: String::from_utf8(piece.to_vec()).unwrap(),
+            options: None,
+        })
+        .collect();
+
+    let mut responses = client
+        .split_stream(tokio_stream::iter(pieces))
+        .await
+        .unwrap()
+        .into_inner();
+    let mut streamed = Vec::new();
+    while let Some(response) = responses.message().await.unwrap() {
+        streamed.extend(response.sentences);
+    }
+
+    let whole = client
+        .split(SplitRequest {
+            text: text.clone(),
+            options: None,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .sentences;
+    assert_eq!(streamed.len(), whole.len());
+    for (streamed, whole) in streamed.iter().zip(&whole) {
+        assert_eq!(streamed.text, whole.text);
+        assert_eq!((streamed.start, streamed.end), (whole.start, whole.end));
+        assert_eq!(
+            (streamed.char_start, streamed.char_end),
+            (whole.char_start, whole.char_end)
+        );
+        assert_eq!(streamed.kind(), BoundaryKind::Unspecified);
+    }
+}
+
+#[tokio::test]
+async fn test_split_stream_bad_options() {
+    let mut client = client().await;
+    let first = SplitStreamRequest {
+        text: "Hello.".to_string(),
+        options: options("xx"),
+    };
+    let mut responses = client
+        .split_stream(tokio_stream::iter(vec![first]))
+        .await
+        .unwrap()
+        .into_inner();
+    let status = responses.message().await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_list_languages() {
+    let mut client = client().await;
+    let languages = client
+        .list_languages(ListLanguagesRequest {})
+        .await
+        .unwrap()
+        .into_inner()
+        .languages;
+    assert!(languages.iter().any(|code| code == "ja"));
+}
@@ -0,0 +1,43 @@
+plugins {
+    `java-library`
+}
+
+group = "io.github.sog4be"
+version = "0.2.0"
+
+java {
+    toolchain {
+        languageVersion.set(JavaLanguageVersion.of(11))
+    }
+}
+
+repositories {
+    mavenCentral()
+}
+
+tasks.withType<JavaCompile>().configureEach {
+    options.encoding = "UTF-8"
+}
+
+dependencies {
+    testImplementation("org.junit.jupiter:junit-jupiter:5.10.2")
+    testRuntimeOnly("org.junit.platform:junit-platform-launcher")
+}
+
+// The native library is built by cargo into the workspace target directory
+val cargoProfile = if (project.hasProperty("release")) "release" else "debug"
+val nativeDir = rootDir.resolve("../target/$cargoProfile")
+
+val cargoBuild by tasks.registering(Exec::class) {
+    workingDir = rootDir
+    commandLine(
+        listOf("cargo", "build", "-p", "sakurs-jvm") +
+            if (cargoProfile == "release") listOf("--release") else emptyList()
+    )
+}
+
+tasks.test {
+    dependsOn(cargoBuild)
+    useJUnitPlatform()
+    systemProperty("java.library.path", nativeDir.absolutePath)
+}
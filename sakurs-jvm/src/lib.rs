@@ -0,0 +1,366 @@
+//! Java/Kotlin bindings for Sakurs sentence boundary detection
+//!
+//! Hand-rolled JNI entry points behind the static methods of
+//! `io.github.sog4be.sakurs.Native`; the public Java API in `src/main/java`
+//! wraps them in `Sakurs`, a reusable `Splitter` and a push-based
+//! `SentenceStream`, mirroring the Node.js bindings.
+//!
+//! Native objects are passed to Java as `long` handles: boxed values leaked
+//! with `Box::into_raw` and reclaimed by the matching `free*` call, which
+//! the Java wrappers make exactly once.
+//!
+//! Offsets are UTF-16 code unit indices, so `text.substring(start, end)` in
+//! Java recovers each sentence.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use jni::objects::{JClass, JObject, JString};
+use jni::sys::{jint, jlong, jobjectArray};
+use jni::JNIEnv;
+use sakurs_core::{BoundaryKind, Config, LanguageConfig, SentenceProcessor, SentenceStream};
+
+/// Exception class raised for every failure of a native call
+const EXCEPTION_CLASS: &str = "io/github/sog4be/sakurs/SakursException";
+
+/// Class and constructor of the Java `Sentence`
+const SENTENCE_CLASS: &str = "io/github/sog4be/sakurs/Sentence";
+const SENTENCE_CONSTRUCTOR: &str = "(Ljava/lang/String;IILjava/lang/String;)V";
+
+/// Why a native call failed
+enum Failure {
+    /// A JNI call failed; a Java exception may already be pending
+    Jni(jni::errors::Error),
+    /// Sakurs rejected the call
+    Sakurs(String),
+}
+
+impl From<jni::errors::Error> for Failure {
+    fn from(e: jni::errors::Error) -> Self {
+        Failure::Jni(e)
+    }
+}
+
+impl From<sakurs_core::ApiError> for Failure {
+    fn from(e: sakurs_core::ApiError) -> Self {
+        Failure::Sakurs(e.to_string())
+    }
+}
+
+/// Run the body of a native method, turning failures and panics into a
+/// `SakursException` and returning `T::default()` to the JVM, which ignores
+/// the value while the exception is pending
+fn run<'local, T, F>(env: &mut JNIEnv<'local>, body: F) -> T
+where
+    T: Default,
+    F: FnOnce(&mut JNIEnv<'local>) -> Result<T, Failure>,
+{
+    let message = match catch_unwind(AssertUnwindSafe(|| body(env))) {
+        Ok(Ok(value)) => return value,
+        // The Java exception raised inside the call propagates as is
+        Ok(Err(Failure::Jni(jni::errors::Error::JavaException))) => return T::default(),
+        Ok(Err(Failure::Jni(e))) => format!("JNI call failed: {e}"),
+        Ok(Err(Failure::Sakurs(message))) => message,
+        Err(panic) => match panic.downcast_ref::<&str>() {
+            Some(message) => format!("internal error: {message}"),
+            None => match panic.downcast_ref::<String>() {
+                Some(message) => format!("internal error: {message}"),
+                None => "internal error".to_string(),
+            },
+        },
+    };
+    // Nothing more can be reported if even throwing fails
+    let _ = env.throw_new(EXCEPTION_CLASS, message);
+    T::default()
+}
+
+/// `Native.newSplitter(language, threads, chunkKb)`: a handle to a new
+/// processor; `language` may be null for English, `threads` and `chunkKb`
+/// 0 for their defaults
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_newSplitter<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    language: JString<'local>,
+    threads: jint,
+    chunk_kb: jint,
+) -> jlong {
+    run(&mut env, |env| {
+        let language = if language.is_null() {
+            "en".to_string()
+        } else {
+            String::from(env.get_string(&language)?)
+        };
+        let threads = non_negative(threads, "threads")?;
+        let chunk_kb = non_negative(chunk_kb, "chunkKb")?;
+
+        let mut builder = Config::builder().language(&language)?;
+        if threads > 0 {
+            builder = builder.threads(Some(threads));
+        }
+        if chunk_kb > 0 {
+            builder = builder.chunk_size(chunk_kb * 1024);
+        }
+        let processor = SentenceProcessor::with_config(builder.build()?)?;
+        Ok(Box::into_raw(Box::new(processor)) as jlong)
+    })
+}
+
+/// `Native.freeSplitter(handle)`
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_freeSplitter<'local>(
+    _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+) {
+    if handle != 0 {
+        // SAFETY: made by `newSplitter` and freed once by `Splitter.close()`
+        drop(unsafe { Box::from_raw(handle as *mut SentenceProcessor) });
+    }
+}
+
+/// `Native.split(handle, text)`: the sentences of `text`
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_split<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    text: JString<'local>,
+) -> jobjectArray {
+    run(&mut env, |env| {
+        let processor = splitter(handle)?;
+        let text = java_text(env, &text)?;
+        let sentences = split_text(processor, &text)?;
+        string_array(env, &sentences)
+    })
+}
+
+/// `Native.splitWithOffsets(handle, text)`: the sentences of `text` as
+/// `Sentence` objects with UTF-16 offsets
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_splitWithOffsets<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    text: JString<'local>,
+) -> jobjectArray {
+    run(&mut env, |env| {
+        let processor = splitter(handle)?;
+        let text = java_text(env, &text)?;
+        let sentences = split_text_with_offsets(processor, &text)?;
+        let array =
+            env.new_object_array(sentences.len() as jint, SENTENCE_CLASS, JObject::null())?;
+        for (index, sentence) in sentences.iter().enumerate() {
+            let text = env.new_string(&sentence.text)?;
+            let kind = env.new_string(sentence.kind.as_str())?;
+            let object = env.new_object(
+                SENTENCE_CLASS,
+                SENTENCE_CONSTRUCTOR,
+                &[
+                    (&text).into(),
+                    (sentence.start as jint).into(),
+                    (sentence.end as jint).into(),
+                    (&kind).into(),
+                ],
+            )?;
+            env.set_object_array_element(&array, index as jint, &object)?;
+            // Long texts would otherwise exhaust the local reference table
+            env.delete_local_ref(object)?;
+            env.delete_local_ref(text)?;
+            env.delete_local_ref(kind)?;
+        }
+        Ok(array.into_raw())
+    })
+}
+
+/// `Native.newStream(handle)`: a handle to a new incremental stream using
+/// the splitter's rules
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_newStream<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+) -> jlong {
+    run(&mut env, |_| {
+        let stream = splitter(handle)?.stream();
+        Ok(Box::into_raw(Box::new(stream)) as jlong)
+    })
+}
+
+/// `Native.feed(stream, text)`: append a piece of text; returns the
+/// sentences it completed
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_feed<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    stream: jlong,
+    text: JString<'local>,
+) -> jobjectArray {
+    run(&mut env, |env| {
+        if stream == 0 {
+            return Err(Failure::Sakurs("stream is already finished".to_string()));
+        }
+        // SAFETY: made by `newStream` and only freed by `finish` or
+        // `freeStream`, after which the Java side holds 0
+        let stream = unsafe { &mut *(stream as *mut SentenceStream) };
+        let text = java_text(env, &text)?;
+        let sentences = trimmed(stream.feed_spans(&text).iter().map(|s| s.text.as_str()));
+        string_array(env, &sentences)
+    })
+}
+
+/// `Native.finish(stream)`: end and free the stream; returns the remaining
+/// sentences
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_finish<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    stream: jlong,
+) -> jobjectArray {
+    run(&mut env, |env| {
+        if stream == 0 {
+            return Err(Failure::Sakurs("stream is already finished".to_string()));
+        }
+        // SAFETY: as in `feed`; the Java side drops the handle after this
+        let stream = unsafe { Box::from_raw(stream as *mut SentenceStream) };
+        let sentences = trimmed(stream.finish_spans().iter().map(|s| s.text.as_str()));
+        string_array(env, &sentences)
+    })
+}
+
+/// `Native.freeStream(stream)`: free a stream abandoned before `finish`
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_freeStream<'local>(
+    _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    stream: jlong,
+) {
+    if stream != 0 {
+        // SAFETY: made by `newStream` and not yet finished or freed
+        drop(unsafe { Box::from_raw(stream as *mut SentenceStream) });
+    }
+}
+
+/// `Native.supportedLanguages()`: language codes with built-in rules
+#[no_mangle]
+pub extern "system" fn Java_io_github_sog4be_sakurs_Native_supportedLanguages<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+) -> jobjectArray {
+    run(&mut env, |env| {
+        let languages: Vec<String> = LanguageConfig::embedded()
+            .into_iter()
+            .map(|config| config.metadata.code.clone())
+            .collect();
+        string_array(env, &languages)
+    })
+}
+
+/// The processor behind a splitter handle
+fn splitter<'a>(handle: jlong) -> Result<&'a SentenceProcessor, Failure> {
+    if handle == 0 {
+        return Err(Failure::Sakurs("splitter is closed".to_string()));
+    }
+    // SAFETY: made by `newSplitter`; `Splitter.close()` zeroes the Java
+    // field before freeing it
+    Ok(unsafe { &*(handle as *const SentenceProcessor) })
+}
+
+/// A Java string argument, rejecting null
+fn java_text(env: &mut JNIEnv, text: &JString) -> Result<String, Failure> {
+    if text.is_null() {
+        return Err(Failure::Sakurs("text must not be null".to_string()));
+    }
+    Ok(env.get_string(text)?.into())
+}
+
+/// `value` as a size, rejecting negative numbers
+fn non_negative(value: jint, name: &str) -> Result<usize, Failure> {
+    usize::try_from(value)
+        .map_err(|_| Failure::Sakurs(format!("{name} must not be negative, got {value}")))
+}
+
+/// A Java `String[]` of `strings`
+fn string_array(env: &mut JNIEnv, strings: &[String]) -> Result<jobjectArray, Failure> {
+    let array = env.new_object_array(strings.len() as jint, "java/lang/String", JObject::null())?;
+    for (index, string) in strings.iter().enumerate() {
+        let string = env.new_string(string)?;
+        env.set_object_array_element(&array, index as jint, &string)?;
+        env.delete_local_ref(string)?;
+    }
+    Ok(array.into_raw())
+}
+
+/// A sentence with its UTF-16 position in the input
+struct Sentence {
+    text: String,
+    start: usize,
+    end: usize,
+    kind: BoundaryKind,
+}
+
+/// Byte offsets of the sentence ends with the kind of boundary at each;
+/// trailing text without a terminator is the final sentence
+fn sentence_ends(
+    processor: &SentenceProcessor,
+    text: &str,
+) -> Result<Vec<(usize, BoundaryKind)>, Failure> {
+    let output = processor.process(sakurs_core::Input::from_text(text))?;
+    let mut ends: Vec<(usize, BoundaryKind)> = output
+        .boundaries
+        .iter()
+        .map(|b| (b.offset, b.kind))
+        .collect();
+    if ends.last().map(|&(end, _)| end) != Some(text.len()) {
+        ends.push((text.len(), BoundaryKind::EndOfText));
+    }
+    Ok(ends)
+}
+
+fn split_text(processor: &SentenceProcessor, text: &str) -> Result<Vec<String>, Failure> {
+    let mut start = 0;
+    let segments = sentence_ends(processor, text)?.into_iter().map(|(end, _)| {
+        let segment = &text[start..end];
+        start = end;
+        segment
+    });
+    Ok(trimmed(segments))
+}
+
+fn split_text_with_offsets(
+    processor: &SentenceProcessor,
+    text: &str,
+) -> Result<Vec<Sentence>, Failure> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut units = 0usize;
+    for (end, kind) in sentence_ends(processor, text)? {
+        let segment = &text[start..end];
+        let body = segment.trim();
+        if !body.is_empty() {
+            let leading = segment.len() - segment.trim_start().len();
+            let sentence_start = units + utf16_len(&segment[..leading]);
+            sentences.push(Sentence {
+                text: body.to_string(),
+                start: sentence_start,
+                end: sentence_start + utf16_len(body),
+                kind,
+            });
+        }
+        units += utf16_len(segment);
+        start = end;
+    }
+    Ok(sentences)
+}
+
+/// Trim sentences and drop the empty ones
+fn trimmed<'a>(segments: impl Iterator<Item = &'a str>) -> Vec<String> {
+    segments
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn utf16_len(s: &str) -> usize {
+    s.chars().map(char::len_utf16).sum()
+}
@@ -0,0 +1,40 @@
+package io.github.sog4be.sakurs;
+
+/**
+ * JNI entry points implemented by the {@code sakurs_jvm} native library.
+ *
+ * <p>The library is loaded from the path in the {@code sakurs.library.path}
+ * system property when set, otherwise from {@code java.library.path}.
+ * Handles are pointers owned by {@link Splitter} and {@link SentenceStream}.
+ */
+final class Native {
+    static {
+        String path = System.getProperty("sakurs.library.path");
+        if (path != null) {
+            System.load(path);
+        } else {
+            System.loadLibrary("sakurs_jvm");
+        }
+    }
+
+    private Native() {}
+
+    static native long newSplitter(String language, int threads, int chunkKb);
+
+    static native void freeSplitter(long splitter);
+
+    static native String[] split(long splitter, String text);
+
+    static native Sentence[] splitWithOffsets(long splitter, String text);
+
+    static native long newStream(long splitter);
+
+    static native String[] feed(long stream, String text);
+
+    /** Ends and frees the stream */
+    static native String[] finish(long stream);
+
+    static native void freeStream(long stream);
+
+    static native String[] supportedLanguages();
+}
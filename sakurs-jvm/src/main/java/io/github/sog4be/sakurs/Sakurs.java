@@ -0,0 +1,68 @@
+package io.github.sog4be.sakurs;
+
+import java.io.Reader;
+import java.util.Arrays;
+import java.util.List;
+
+/**
+ * Sentence boundary detection based on the Δ-Stack Monoid algorithm.
+ *
+ * <pre>{@code
+ * Sakurs.split("Hello world. Dr. Smith arrived!");
+ * // [Hello world., Dr. Smith arrived!]
+ *
+ * try (Splitter splitter = new Splitter(SplitOptions.builder().language("ja").build())) {
+ *     splitter.split("これは日本語です。元気ですか？");
+ * }
+ * }</pre>
+ */
+public final class Sakurs {
+    private Sakurs() {}
+
+    /** Split English text into sentences */
+    public static List<String> split(String text) {
+        return DefaultSplitter.INSTANCE.split(text);
+    }
+
+    /** Split text into sentences */
+    public static List<String> split(String text, SplitOptions options) {
+        try (Splitter splitter = new Splitter(options)) {
+            return splitter.split(text);
+        }
+    }
+
+    /** Split English text into sentences with their UTF-16 offsets */
+    public static List<Sentence> splitWithOffsets(String text) {
+        return DefaultSplitter.INSTANCE.splitWithOffsets(text);
+    }
+
+    /** Split text into sentences with their UTF-16 offsets */
+    public static List<Sentence> splitWithOffsets(String text, SplitOptions options) {
+        try (Splitter splitter = new Splitter(options)) {
+            return splitter.splitWithOffsets(text);
+        }
+    }
+
+    /** Split the English text of a reader into sentences as it is read */
+    public static SentenceIterator iterSplit(Reader reader) {
+        return iterSplit(reader, DefaultSplitter.INSTANCE);
+    }
+
+    /**
+     * Split the text of a reader into sentences as it is read; the splitter
+     * must stay open until the iterator is done
+     */
+    public static SentenceIterator iterSplit(Reader reader, Splitter splitter) {
+        return new SentenceIterator(reader, splitter);
+    }
+
+    /** Language codes with built-in rules */
+    public static List<String> supportedLanguages() {
+        return Arrays.asList(Native.supportedLanguages());
+    }
+
+    /** English splitter shared by the shortcuts, created on first use */
+    private static final class DefaultSplitter {
+        static final Splitter INSTANCE = new Splitter();
+    }
+}
@@ -0,0 +1,10 @@
+package io.github.sog4be.sakurs;
+
+/** Thrown when Sakurs rejects a call, such as for an unsupported language. */
+public class SakursException extends RuntimeException {
+    private static final long serialVersionUID = 1L;
+
+    public SakursException(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,73 @@
+package io.github.sog4be.sakurs;
+
+import java.util.Objects;
+
+/**
+ * A sentence with its position in the input.
+ *
+ * <p>Offsets are UTF-16 code unit indices, so
+ * {@code input.substring(start(), end())} recovers the sentence.
+ */
+public final class Sentence {
+    private final String text;
+    private final int start;
+    private final int end;
+    private final String kind;
+
+    Sentence(String text, int start, int end, String kind) {
+        this.text = text;
+        this.start = start;
+        this.end = end;
+        this.kind = kind;
+    }
+
+    /** Sentence text without surrounding whitespace */
+    public String text() {
+        return text;
+    }
+
+    /** Offset where the sentence starts */
+    public int start() {
+        return start;
+    }
+
+    /** Offset just past the sentence end */
+    public int end() {
+        return end;
+    }
+
+    /**
+     * How the sentence ends: {@code "strong"} (!, ?), {@code "weak"} (a
+     * period or other boundary accepted in context), {@code "forced"} (split
+     * for length) or {@code "end_of_text"} (trailing text without a
+     * terminator)
+     */
+    public String kind() {
+        return kind;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        if (this == other) {
+            return true;
+        }
+        if (!(other instanceof Sentence)) {
+            return false;
+        }
+        Sentence sentence = (Sentence) other;
+        return start == sentence.start
+                && end == sentence.end
+                && text.equals(sentence.text)
+                && kind.equals(sentence.kind);
+    }
+
+    @Override
+    public int hashCode() {
+        return Objects.hash(text, start, end, kind);
+    }
+
+    @Override
+    public String toString() {
+        return "Sentence(text=" + text + ", start=" + start + ", end=" + end + ", kind=" + kind + ")";
+    }
+}
@@ -0,0 +1,92 @@
+package io.github.sog4be.sakurs;
+
+import java.io.IOException;
+import java.io.Reader;
+import java.io.UncheckedIOException;
+import java.util.ArrayDeque;
+import java.util.Iterator;
+import java.util.NoSuchElementException;
+
+/**
+ * Sentences of a {@link Reader}, read piece by piece as the iterator
+ * advances; returned by {@link Sakurs#iterSplit(Reader, Splitter)}.
+ *
+ * <p>Closing the iterator closes the reader. Read errors are thrown as
+ * {@link UncheckedIOException}.
+ */
+public final class SentenceIterator implements Iterator<String>, AutoCloseable {
+    /** Characters read from the reader at a time */
+    private static final int PIECE_CHARS = 64 * 1024;
+
+    private final Reader reader;
+    private final SentenceStream stream;
+    private final ArrayDeque<String> ready = new ArrayDeque<>();
+    private final char[] buffer = new char[PIECE_CHARS];
+    /** A high surrogate held back until the low one is read */
+    private char pending;
+    private boolean finished;
+
+    SentenceIterator(Reader reader, Splitter splitter) {
+        this.reader = reader;
+        this.stream = splitter.stream();
+    }
+
+    @Override
+    public boolean hasNext() {
+        while (ready.isEmpty() && !finished) {
+            readPiece();
+        }
+        return !ready.isEmpty();
+    }
+
+    @Override
+    public String next() {
+        if (!hasNext()) {
+            throw new NoSuchElementException();
+        }
+        return ready.poll();
+    }
+
+    /** Stop reading and close the reader */
+    @Override
+    public void close() {
+        finished = true;
+        ready.clear();
+        stream.close();
+        try {
+            reader.close();
+        } catch (IOException e) {
+            throw new UncheckedIOException(e);
+        }
+    }
+
+    private void readPiece() {
+        int offset = 0;
+        if (pending != 0) {
+            buffer[0] = pending;
+            pending = 0;
+            offset = 1;
+        }
+        int read;
+        try {
+            read = reader.read(buffer, offset, buffer.length - offset);
+        } catch (IOException e) {
+            close();
+            throw new UncheckedIOException(e);
+        }
+        if (read < 0) {
+            if (offset > 0) {
+                ready.addAll(stream.feed(new String(buffer, 0, offset)));
+            }
+            ready.addAll(stream.finish());
+            finished = true;
+            return;
+        }
+        int length = offset + read;
+        // A surrogate pair split across reads is fed whole with the next piece
+        if (length > 0 && Character.isHighSurrogate(buffer[length - 1])) {
+            pending = buffer[--length];
+        }
+        ready.addAll(stream.feed(new String(buffer, 0, length)));
+    }
+}
@@ -0,0 +1,57 @@
+package io.github.sog4be.sakurs;
+
+import java.lang.ref.Cleaner;
+import java.util.Arrays;
+import java.util.List;
+
+/**
+ * Push-based sentence stream created by {@link Splitter#stream()}.
+ *
+ * <p>Results are identical to splitting the whole text at once. A stream is
+ * not thread-safe, and its native memory is released by {@link #finish()},
+ * {@link #close()} or garbage collection, whichever comes first.
+ */
+public final class SentenceStream implements AutoCloseable {
+    private final Handle handle;
+    private final Cleaner.Cleanable cleanable;
+
+    SentenceStream(long pointer) {
+        this.handle = new Handle(pointer);
+        this.cleanable = Splitter.CLEANER.register(this, handle);
+    }
+
+    /** Append a piece of text; returns the sentences it completed */
+    public List<String> feed(String text) {
+        return Arrays.asList(Native.feed(handle.pointer, text));
+    }
+
+    /** End the stream; returns the remaining sentences */
+    public List<String> finish() {
+        long pointer = handle.pointer;
+        // The native side frees the stream even when finishing fails
+        handle.pointer = 0;
+        return Arrays.asList(Native.finish(pointer));
+    }
+
+    /** Abandon the stream without finishing it */
+    @Override
+    public void close() {
+        cleanable.clean();
+    }
+
+    /** The native pointer, 0 once finished or freed */
+    private static final class Handle implements Runnable {
+        private volatile long pointer;
+
+        Handle(long pointer) {
+            this.pointer = pointer;
+        }
+
+        @Override
+        public void run() {
+            long current = pointer;
+            pointer = 0;
+            Native.freeStream(current);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+package io.github.sog4be.sakurs;
+
+/**
+ * Options for {@link Splitter} and the {@link Sakurs} shortcuts.
+ *
+ * <pre>{@code
+ * SplitOptions options = SplitOptions.builder().language("ja").threads(4).build();
+ * }</pre>
+ */
+public final class SplitOptions {
+    private static final SplitOptions DEFAULTS = builder().build();
+
+    private final String language;
+    private final int threads;
+    private final int chunkKb;
+
+    private SplitOptions(Builder builder) {
+        this.language = builder.language;
+        this.threads = builder.threads;
+        this.chunkKb = builder.chunkKb;
+    }
+
+    /** English with automatic threading and the default chunk size */
+    public static SplitOptions defaults() {
+        return DEFAULTS;
+    }
+
+    public static Builder builder() {
+        return new Builder();
+    }
+
+    /** Language code ("en", "ja", "pt", "it", "ar", "th", "hi") */
+    public String language() {
+        return language;
+    }
+
+    /** Number of threads, 0 to choose automatically */
+    public int threads() {
+        return threads;
+    }
+
+    /** Chunk size in KB for parallel processing, 0 for the default (256) */
+    public int chunkKb() {
+        return chunkKb;
+    }
+
+    public static final class Builder {
+        private String language = "en";
+        private int threads;
+        private int chunkKb;
+
+        private Builder() {}
+
+        public Builder language(String language) {
+            if (language == null) {
+                throw new NullPointerException("language");
+            }
+            this.language = language;
+            return this;
+        }
+
+        public Builder threads(int threads) {
+            if (threads < 0) {
+                throw new IllegalArgumentException("threads must not be negative, got " + threads);
+            }
+            this.threads = threads;
+            return this;
+        }
+
+        public Builder chunkKb(int chunkKb) {
+            if (chunkKb < 0) {
+                throw new IllegalArgumentException("chunkKb must not be negative, got " + chunkKb);
+            }
+            this.chunkKb = chunkKb;
+            return this;
+        }
+
+        public SplitOptions build() {
+            return new SplitOptions(this);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+package io.github.sog4be.sakurs;
+
+import java.lang.ref.Cleaner;
+import java.util.Arrays;
+import java.util.List;
+
+/**
+ * Reusable sentence splitter: compile the rules once, split many texts.
+ *
+ * <p>A splitter may be shared between threads, but must not be closed while
+ * in use. Its native memory is released by {@link #close()}, or when it is
+ * garbage collected otherwise.
+ */
+public final class Splitter implements AutoCloseable {
+    static final Cleaner CLEANER = Cleaner.create();
+
+    private final String language;
+    private final Handle handle;
+    private final Cleaner.Cleanable cleanable;
+
+    public Splitter() {
+        this(SplitOptions.defaults());
+    }
+
+    public Splitter(SplitOptions options) {
+        this.language = options.language();
+        this.handle = new Handle(Native.newSplitter(options.language(), options.threads(), options.chunkKb()));
+        this.cleanable = CLEANER.register(this, handle);
+    }
+
+    /** Language code this splitter was created with */
+    public String language() {
+        return language;
+    }
+
+    /** Split text into sentences */
+    public List<String> split(String text) {
+        return Arrays.asList(Native.split(handle.get(), text));
+    }
+
+    /** Split text into sentences with their UTF-16 offsets */
+    public List<Sentence> splitWithOffsets(String text) {
+        return Arrays.asList(Native.splitWithOffsets(handle.get(), text));
+    }
+
+    /** Start an incremental stream: feed text pieces, receive sentences as they complete */
+    public SentenceStream stream() {
+        return new SentenceStream(Native.newStream(handle.get()));
+    }
+
+    /** Release the native splitter; later calls throw {@link SakursException} */
+    @Override
+    public void close() {
+        cleanable.clean();
+    }
+
+    /** The native pointer, freed at most once */
+    private static final class Handle implements Runnable {
+        private volatile long pointer;
+
+        Handle(long pointer) {
+            this.pointer = pointer;
+        }
+
+        long get() {
+            long current = pointer;
+            if (current == 0) {
+                throw new SakursException("splitter is closed");
+            }
+            return current;
+        }
+
+        @Override
+        public void run() {
+            long current = pointer;
+            pointer = 0;
+            Native.freeSplitter(current);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+package io.github.sog4be.sakurs;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+import static org.junit.jupiter.api.Assertions.assertThrows;
+import static org.junit.jupiter.api.Assertions.assertTrue;
+
+import java.io.StringReader;
+import java.util.ArrayList;
+import java.util.List;
+import org.junit.jupiter.api.Test;
+
+class SakursTest {
+    @Test
+    void splitsEnglish() {
+        assertEquals(
+                List.of("Hello world.", "Dr. Smith arrived!"),
+                Sakurs.split("Hello world. Dr. Smith arrived!"));
+    }
+
+    @Test
+    void splitsJapanese() {
+        SplitOptions options = SplitOptions.builder().language("ja").build();
+        assertEquals(
+                List.of("これは日本語です。", "元気ですか？"),
+                Sakurs.split("これは日本語です。元気ですか？", options));
+    }
+
+    @Test
+    void offsetsAreUtf16Indices() {
+        String text = "😀 Emoji first. Then text.";
+        for (Sentence sentence : Sakurs.splitWithOffsets(text)) {
+            assertEquals(sentence.text(), text.substring(sentence.start(), sentence.end()));
+        }
+        Sentence last = Sakurs.splitWithOffsets("One. Two").get(1);
+        assertEquals("end_of_text", last.kind());
+    }
+
+    @Test
+    void streamMatchesSplit() {
+        String text = "First sentence. Second one! Third? Trailing";
+        try (Splitter splitter = new Splitter()) {
+            List<String> sentences = new ArrayList<>();
+            SentenceStream stream = splitter.stream();
+            for (int i = 0; i < text.length(); i += 5) {
+                sentences.addAll(stream.feed(text.substring(i, Math.min(i + 5, text.length()))));
+            }
+            sentences.addAll(stream.finish());
+            assertEquals(splitter.split(text), sentences);
+        }
+    }
+
+    @Test
+    void iterSplitReadsReader() {
+        StringBuilder text = new StringBuilder();
+        for (int i = 0; i < 20000; i++) {
+            text.append("Sentence ").append(i).append(". 😀 ");
+        }
+        List<String> sentences = new ArrayList<>();
+        try (SentenceIterator iterator = Sakurs.iterSplit(new StringReader(text.toString()))) {
+            iterator.forEachRemaining(sentences::add);
+        }
+        assertEquals(Sakurs.split(text.toString()), sentences);
+    }
+
+    @Test
+    void rejectsUnknownLanguage() {
+        SplitOptions options = SplitOptions.builder().language("xx").build();
+        assertThrows(SakursException.class, () -> new Splitter(options));
+    }
+
+    @Test
+    void closedSplitterThrows() {
+        Splitter splitter = new Splitter();
+        splitter.close();
+        splitter.close();
+        assertThrows(SakursException.class, () -> splitter.split("Hello."));
+    }
+
+    @Test
+    void finishedStreamThrows() {
+        try (Splitter splitter = new Splitter()) {
+            SentenceStream stream = splitter.stream();
+            stream.finish();
+            assertThrows(SakursException.class, () -> stream.feed("More."));
+            stream.close();
+        }
+    }
+
+    @Test
+    void listsLanguages() {
+        assertTrue(Sakurs.supportedLanguages().contains("ja"));
+    }
+}
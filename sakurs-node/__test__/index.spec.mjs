@@ -0,0 +1,66 @@
+import assert from 'node:assert/strict'
+import { Readable } from 'node:stream'
+import { test } from 'node:test'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const { split, splitWithOffsets, supportedLanguages, Splitter, iterSplit } = require('../index.js')
+
+const TEXT = 'Hello world. Dr. Smith arrived! How are you?'
+const EXPECTED = ['Hello world.', 'Dr. Smith arrived!', 'How are you?']
+
+test('split returns sentences', () => {
+  assert.deepEqual(split(TEXT), EXPECTED)
+  assert.deepEqual(split('これは日本語です。元気ですか？', { language: 'ja' }), ['これは日本語です。', '元気ですか？'])
+})
+
+test('split keeps trailing text without a terminator', () => {
+  assert.deepEqual(split('One. Two'), ['One.', 'Two'])
+})
+
+test('unknown language is rejected', () => {
+  assert.throws(() => split(TEXT, { language: 'xx' }))
+})
+
+test('offsets are UTF-16 indices', () => {
+  const text = '🎉 Party time. 日本語も。 Done.'
+  for (const s of splitWithOffsets(text)) {
+    assert.equal(text.slice(s.start, s.end), s.text)
+  }
+})
+
+test('sentences report the kind of boundary that ends them', () => {
+  const kinds = splitWithOffsets('Stop! It is 3.5 m long. No terminator').map((s) => s.kind)
+  assert.deepEqual(kinds, ['strong', 'weak', 'end_of_text'])
+})
+
+test('Splitter is reusable and splits asynchronously', async () => {
+  const splitter = new Splitter({ language: 'en', threads: 1 })
+  assert.equal(splitter.language, 'en')
+  assert.deepEqual(splitter.split(TEXT), EXPECTED)
+  assert.deepEqual(await splitter.splitAsync(TEXT), EXPECTED)
+  assert.ok(supportedLanguages().includes('ja'))
+})
+
+test('stream cannot be fed after finish', () => {
+  const stream = new Splitter().stream()
+  stream.feed('One. ')
+  stream.finish()
+  assert.throws(() => stream.feed('Two.'))
+})
+
+test('iterSplit matches split for any piece size', async () => {
+  const text = TEXT.repeat(20)
+  for (const size of [1, 3, 7, 64]) {
+    const bytes = Buffer.from(text)
+    const pieces = []
+    for (let i = 0; i < bytes.length; i += size) {
+      pieces.push(bytes.subarray(i, i + size))
+    }
+    const sentences = []
+    for await (const sentence of iterSplit(Readable.from(pieces))) {
+      sentences.push(sentence)
+    }
+    assert.deepEqual(sentences, split(text))
+  }
+})
@@ -0,0 +1,61 @@
+/** Options shared by `split()`, `splitWithOffsets()` and `new Splitter()` */
+export interface SplitOptions {
+  /** Language code ("en", "ja", "pt", "it", "ar", "th", "hi"); default "en" */
+  language?: string
+  /** Number of threads (default: chosen automatically) */
+  threads?: number
+  /** Chunk size in KB for parallel processing (default: 256) */
+  chunkKb?: number
+}
+
+/** A sentence with its position in the input */
+export interface Sentence {
+  /** Sentence text without surrounding whitespace */
+  text: string
+  /** UTF-16 offset where the sentence starts */
+  start: number
+  /** UTF-16 offset just past the sentence end */
+  end: number
+  /**
+   * How the sentence ends: "strong" (!, ?), "weak" (a period or other
+   * boundary accepted in context), "forced" (split for length) or
+   * "end_of_text" (trailing text without a terminator)
+   */
+  kind: 'strong' | 'weak' | 'forced' | 'end_of_text'
+}
+
+/** Split text into sentences */
+export function split(text: string, options?: SplitOptions): string[]
+/** Split text into sentences with their UTF-16 offsets */
+export function splitWithOffsets(text: string, options?: SplitOptions): Sentence[]
+/** Language codes with built-in rules */
+export function supportedLanguages(): string[]
+
+/** Reusable sentence splitter; compile the rules once, split many texts */
+export class Splitter {
+  constructor(options?: SplitOptions)
+  /** Language code this splitter was created with */
+  get language(): string
+  /** Split text into sentences */
+  split(text: string): string[]
+  /** Split text into sentences with their UTF-16 offsets */
+  splitWithOffsets(text: string): Sentence[]
+  /** Split text on the libuv thread pool, resolving to the sentences */
+  splitAsync(text: string): Promise<string[]>
+  /** Start an incremental stream: feed text pieces, receive sentences as they complete */
+  stream(): SentenceStream
+}
+
+/** Push-based sentence stream created by `Splitter.stream()` */
+export class SentenceStream {
+  /** Append a piece of text; returns the sentences it completed */
+  feed(text: string): string[]
+  /** End the stream; returns the remaining sentences */
+  finish(): string[]
+}
+
+/** Split a Readable or (async) iterable of text into sentences as it arrives */
+export function iterSplit(
+  source: AsyncIterable<string | Uint8Array> | Iterable<string | Uint8Array>,
+  options?: SplitOptions | Splitter,
+): AsyncGenerator<string, void, undefined>
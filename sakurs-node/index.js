@@ -0,0 +1,54 @@
+'use strict'
+
+const { existsSync } = require('node:fs')
+const { join } = require('node:path')
+
+// `napi build --platform` names the addon after the target triple; a plain
+// `sakurs.node` (e.g. copied from a cargo build) is accepted as well.
+function loadBinding() {
+  const abi = process.platform === 'linux' ? (process.report?.getReport().header.glibcVersionRuntime ? '-gnu' : '-musl') : ''
+  const candidates = [`sakurs.${process.platform}-${process.arch}${abi}.node`, 'sakurs.node']
+  for (const file of candidates) {
+    const path = join(__dirname, file)
+    if (existsSync(path)) {
+      return require(path)
+    }
+  }
+  throw new Error(`sakurs: no native binding found (looked for ${candidates.join(', ')})`)
+}
+
+const binding = loadBinding()
+
+/**
+ * Split a readable stream of text into sentences as it arrives.
+ *
+ * Accepts a Node.js Readable or any (async) iterable of strings or
+ * Buffers/Uint8Arrays (decoded as UTF-8). Sentences are yielded as soon
+ * as they are final; memory stays bounded by the piece size plus the
+ * current sentence.
+ */
+async function* iterSplit(source, options) {
+  const splitter = options instanceof binding.Splitter ? options : new binding.Splitter(options)
+  const stream = splitter.stream()
+  const decoder = new TextDecoder('utf-8', { fatal: true })
+  for await (const piece of source) {
+    const text = typeof piece === 'string' ? piece : decoder.decode(piece, { stream: true })
+    if (text.length > 0) {
+      yield* stream.feed(text)
+    }
+  }
+  const rest = decoder.decode()
+  if (rest.length > 0) {
+    yield* stream.feed(rest)
+  }
+  yield* stream.finish()
+}
+
+module.exports = {
+  split: binding.split,
+  splitWithOffsets: binding.splitWithOffsets,
+  supportedLanguages: binding.supportedLanguages,
+  Splitter: binding.Splitter,
+  SentenceStream: binding.SentenceStream,
+  iterSplit,
+}
@@ -0,0 +1,259 @@
+//! Node.js bindings for Sakurs sentence boundary detection
+//!
+//! Mirrors the Python bindings: `split()` for one-shot use, a reusable
+//! `Splitter` with a promise-based `splitAsync()`, and push-based streaming
+//! through `SentenceStream`, which the JavaScript `iterSplit()` wrapper in
+//! `index.js` drives from a Readable.
+//!
+//! Offsets are UTF-16 code unit indices, so `text.slice(start, end)` in
+//! JavaScript recovers each sentence.
+
+use std::sync::Arc;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use sakurs_core::{BoundaryKind, Config, LanguageConfig, SentenceProcessor};
+
+/// Options shared by `split()`, `splitWithOffsets()` and `new Splitter()`
+#[napi(object)]
+#[derive(Default)]
+pub struct SplitOptions {
+    /// Language code ("en", "ja", "pt", "it", "ar", "th", "hi"); default "en"
+    pub language: Option<String>,
+    /// Number of threads (default: chosen automatically)
+    pub threads: Option<u32>,
+    /// Chunk size in KB for parallel processing (default: 256)
+    pub chunk_kb: Option<u32>,
+}
+
+/// A sentence with its position in the input
+#[napi(object)]
+pub struct Sentence {
+    /// Sentence text without surrounding whitespace
+    pub text: String,
+    /// UTF-16 offset where the sentence starts
+    pub start: u32,
+    /// UTF-16 offset just past the sentence end
+    pub end: u32,
+    /// How the sentence ends: "strong" (!, ?), "weak" (a period or other
+    /// boundary accepted in context), "forced" (split for length) or
+    /// "end_of_text" (trailing text without a terminator)
+    #[napi(ts_type = "'strong' | 'weak' | 'forced' | 'end_of_text'")]
+    pub kind: String,
+}
+
+/// Split text into sentences
+#[napi]
+pub fn split(text: String, options: Option<SplitOptions>) -> Result<Vec<String>> {
+    let processor = create_processor(options.unwrap_or_default())?;
+    split_text(&processor, &text)
+}
+
+/// Split text into sentences with their UTF-16 offsets
+#[napi]
+pub fn split_with_offsets(text: String, options: Option<SplitOptions>) -> Result<Vec<Sentence>> {
+    let processor = create_processor(options.unwrap_or_default())?;
+    split_text_with_offsets(&processor, &text)
+}
+
+/// Language codes with built-in rules
+#[napi]
+pub fn supported_languages() -> Vec<String> {
+    LanguageConfig::embedded()
+        .into_iter()
+        .map(|config| config.metadata.code.clone())
+        .collect()
+}
+
+/// Reusable sentence splitter; compile the rules once, split many texts
+#[napi]
+pub struct Splitter {
+    processor: Arc<SentenceProcessor>,
+    language: String,
+}
+
+#[napi]
+impl Splitter {
+    #[napi(constructor)]
+    pub fn new(options: Option<SplitOptions>) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        let language = options.language.clone().unwrap_or_else(|| "en".to_string());
+        Ok(Self {
+            processor: Arc::new(create_processor(options)?),
+            language,
+        })
+    }
+
+    /// Language code this splitter was created with
+    #[napi(getter)]
+    pub fn language(&self) -> String {
+        self.language.clone()
+    }
+
+    /// Split text into sentences
+    #[napi]
+    pub fn split(&self, text: String) -> Result<Vec<String>> {
+        split_text(&self.processor, &text)
+    }
+
+    /// Split text into sentences with their UTF-16 offsets
+    #[napi]
+    pub fn split_with_offsets(&self, text: String) -> Result<Vec<Sentence>> {
+        split_text_with_offsets(&self.processor, &text)
+    }
+
+    /// Split text on the libuv thread pool, resolving to the sentences
+    #[napi(ts_return_type = "Promise<string[]>")]
+    pub fn split_async(&self, text: String) -> AsyncTask<SplitTask> {
+        AsyncTask::new(SplitTask {
+            processor: Arc::clone(&self.processor),
+            text,
+        })
+    }
+
+    /// Start an incremental stream: feed text pieces, receive sentences as
+    /// they complete
+    #[napi]
+    pub fn stream(&self) -> SentenceStream {
+        SentenceStream {
+            inner: Some(self.processor.stream()),
+        }
+    }
+}
+
+/// Background task behind `Splitter.splitAsync()`
+pub struct SplitTask {
+    processor: Arc<SentenceProcessor>,
+    text: String,
+}
+
+impl Task for SplitTask {
+    type Output = Vec<String>;
+    type JsValue = Vec<String>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        split_text(&self.processor, &self.text)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Push-based sentence stream created by `Splitter.stream()`
+///
+/// Memory stays proportional to the piece size plus the current sentence,
+/// however long the input.
+#[napi]
+pub struct SentenceStream {
+    inner: Option<sakurs_core::SentenceStream>,
+}
+
+#[napi]
+impl SentenceStream {
+    /// Append a piece of text; returns the sentences it completed
+    #[napi]
+    pub fn feed(&mut self, text: String) -> Result<Vec<String>> {
+        let inner = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("stream is already finished"))?;
+        Ok(trimmed(
+            inner.feed_spans(&text).iter().map(|s| s.text.as_str()),
+        ))
+    }
+
+    /// End the stream; returns the remaining sentences
+    #[napi]
+    pub fn finish(&mut self) -> Result<Vec<String>> {
+        let inner = self
+            .inner
+            .take()
+            .ok_or_else(|| Error::from_reason("stream is already finished"))?;
+        Ok(trimmed(
+            inner.finish_spans().iter().map(|s| s.text.as_str()),
+        ))
+    }
+}
+
+/// Build a processor from JavaScript options
+fn create_processor(options: SplitOptions) -> Result<SentenceProcessor> {
+    let mut builder = Config::builder()
+        .language(options.language.as_deref().unwrap_or("en"))
+        .map_err(to_js_error)?;
+    if let Some(threads) = options.threads {
+        builder = builder.threads(Some(threads as usize));
+    }
+    if let Some(chunk_kb) = options.chunk_kb {
+        builder = builder.chunk_size(chunk_kb as usize * 1024);
+    }
+    let config = builder.build().map_err(to_js_error)?;
+    SentenceProcessor::with_config(config).map_err(to_js_error)
+}
+
+/// Byte offsets of the sentence ends with the kind of boundary at each;
+/// trailing text without a terminator is the final sentence
+fn sentence_ends(processor: &SentenceProcessor, text: &str) -> Result<Vec<(usize, BoundaryKind)>> {
+    let output = processor
+        .process(sakurs_core::Input::from_text(text))
+        .map_err(to_js_error)?;
+    let mut ends: Vec<(usize, BoundaryKind)> = output
+        .boundaries
+        .iter()
+        .map(|b| (b.offset, b.kind))
+        .collect();
+    if ends.last().map(|&(end, _)| end) != Some(text.len()) {
+        ends.push((text.len(), BoundaryKind::EndOfText));
+    }
+    Ok(ends)
+}
+
+fn split_text(processor: &SentenceProcessor, text: &str) -> Result<Vec<String>> {
+    let mut start = 0;
+    let segments = sentence_ends(processor, text)?.into_iter().map(|(end, _)| {
+        let segment = &text[start..end];
+        start = end;
+        segment
+    });
+    Ok(trimmed(segments))
+}
+
+fn split_text_with_offsets(processor: &SentenceProcessor, text: &str) -> Result<Vec<Sentence>> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut units = 0usize;
+    for (end, kind) in sentence_ends(processor, text)? {
+        let segment = &text[start..end];
+        let body = segment.trim();
+        if !body.is_empty() {
+            let leading = segment.len() - segment.trim_start().len();
+            let sentence_start = units + utf16_len(&segment[..leading]);
+            sentences.push(Sentence {
+                text: body.to_string(),
+                start: sentence_start as u32,
+                end: (sentence_start + utf16_len(body)) as u32,
+                kind: kind.as_str().to_string(),
+            });
+        }
+        units += utf16_len(segment);
+        start = end;
+    }
+    Ok(sentences)
+}
+
+/// Trim sentences and drop the empty ones
+fn trimmed<'a>(segments: impl Iterator<Item = &'a str>) -> Vec<String> {
+    segments
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn utf16_len(s: &str) -> usize {
+    s.chars().map(char::len_utf16).sum()
+}
+
+fn to_js_error(e: sakurs_core::ApiError) -> Error {
+    Error::from_reason(e.to_string())
+}
@@ -0,0 +1,225 @@
+#!/usr/bin/env python3
+"""Accuracy and throughput of segmenters on gold datasets.
+
+Run from ``sakurs-py``::
+
+    python -m benchmarks.accuracy --output results.json
+    python -m benchmarks.accuracy --check results.json --tolerance 0.01
+
+With ``--check``, every metric in the snapshot is compared against the
+current run and the command exits with status 1 if any precision, recall or
+F1 dropped by more than ``--tolerance``. Datasets or segmenters that are not
+available in the current environment are reported and skipped.
+"""
+
+import argparse
+import json
+import sys
+import time
+from dataclasses import asdict, dataclass
+from pathlib import Path
+from typing import Any, Final
+
+from benchmarks.baselines import BaselineUnavailableError, load_segmenter
+from benchmarks.datasets import (
+    DATASETS,
+    Dataset,
+    DatasetUnavailableError,
+    boundaries_from_sentences,
+    load_dataset,
+)
+
+# Version of the result file layout
+SCHEMA_VERSION: Final[int] = 1
+
+# Metrics compared by --check
+GATED_METRICS: Final[tuple[str, ...]] = ("precision", "recall", "f1")
+
+DEFAULT_DATASETS: Final[list[str]] = ["ud-en-ewt", "ud-en-gum", "ud-ja-gsd", "brown"]
+DEFAULT_SEGMENTERS: Final[list[str]] = ["sakurs"]
+DEFAULT_TOLERANCE: Final[float] = 0.005
+
+
+@dataclass
+class Score:
+    """Result of one segmenter on one dataset.
+
+    Attributes:
+        precision: Fraction of predicted boundaries that are gold boundaries.
+        recall: Fraction of gold boundaries that were predicted.
+        f1: Harmonic mean of precision and recall.
+        predicted: Number of predicted boundaries.
+        gold: Number of gold boundaries.
+        seconds: Wall-clock segmentation time.
+        chars_per_second: Throughput.
+    """
+
+    precision: float
+    recall: float
+    f1: float
+    predicted: int
+    gold: int
+    seconds: float
+    chars_per_second: float
+
+
+def score_boundaries(
+    predicted: list[int], gold: list[int], text_length: int
+) -> tuple[float, float, float]:
+    """Precision, recall and F1 of predicted boundary offsets.
+
+    A boundary at the end of the text is implied by every segmenter and is
+    left out of both sides.
+    """
+    predicted_set = {offset for offset in predicted if offset < text_length}
+    gold_set = {offset for offset in gold if offset < text_length}
+    correct = len(predicted_set & gold_set)
+    precision = correct / len(predicted_set) if predicted_set else 1.0
+    recall = correct / len(gold_set) if gold_set else 1.0
+    total = precision + recall
+    f1 = 2 * precision * recall / total if total else 0.0
+    return precision, recall, f1
+
+
+def evaluate(dataset: Dataset, segmenter_name: str) -> Score:
+    """Run one segmenter over a dataset and score it against the gold data.
+
+    Raises:
+        BaselineUnavailableError: If the segmenter is not installed.
+        ValueError: If the segmenter does not support the dataset's language.
+    """
+    segment = load_segmenter(segmenter_name, dataset.language)
+    start = time.perf_counter()
+    sentences = segment(dataset.text)
+    seconds = time.perf_counter() - start
+
+    predicted = boundaries_from_sentences(dataset.text, sentences)
+    if dataset.has_gold:
+        precision, recall, f1 = score_boundaries(
+            predicted, dataset.boundaries, len(dataset.text)
+        )
+    else:
+        precision = recall = f1 = float("nan")
+    return Score(
+        precision=precision,
+        recall=recall,
+        f1=f1,
+        predicted=len(predicted),
+        gold=len(dataset.boundaries),
+        seconds=seconds,
+        chars_per_second=len(dataset.text) / seconds if seconds else 0.0,
+    )
+
+
+def run(dataset_names: list[str], segmenter_names: list[str]) -> dict[str, Any]:
+    """Evaluate every available segmenter on every available dataset.
+
+    Returns:
+        The result document: ``{"schema_version", "results"}`` where
+        ``results[dataset][segmenter]`` holds the ``Score`` fields.
+    """
+    results: dict[str, dict[str, Any]] = {}
+    for dataset_name in dataset_names:
+        try:
+            dataset = load_dataset(dataset_name)
+        except DatasetUnavailableError as e:
+            print(f"skipping {dataset_name}: {e}", file=sys.stderr)
+            continue
+        for segmenter_name in segmenter_names:
+            try:
+                score = evaluate(dataset, segmenter_name)
+            except (BaselineUnavailableError, ValueError) as e:
+                print(
+                    f"skipping {segmenter_name} on {dataset_name}: {e}", file=sys.stderr
+                )
+                continue
+            results.setdefault(dataset_name, {})[segmenter_name] = asdict(score)
+    return {"schema_version": SCHEMA_VERSION, "results": results}
+
+
+def find_regressions(
+    baseline: dict[str, Any], current: dict[str, Any], tolerance: float
+) -> list[str]:
+    """Describe every gated metric that dropped by more than ``tolerance``.
+
+    Entries of the baseline that are missing from the current results are not
+    regressions; they were skipped because a dataset or library is missing.
+    """
+    regressions = []
+    for dataset_name, segmenters in baseline["results"].items():
+        for segmenter_name, expected in segmenters.items():
+            actual = current["results"].get(dataset_name, {}).get(segmenter_name)
+            if actual is None:
+                continue
+            for metric in GATED_METRICS:
+                before, after = expected.get(metric), actual.get(metric)
+                # NaN (no gold data) compares false and is never gated
+                if before is None or after is None or not after < before - tolerance:
+                    continue
+                regressions.append(
+                    f"{dataset_name}/{segmenter_name}: {metric} "
+                    f"{before:.4f} -> {after:.4f} ({after - before:+.4f})"
+                )
+    return regressions
+
+
+def format_results(current: dict[str, Any]) -> str:
+    """Render results as a plain-text table."""
+    lines = [
+        f"{'dataset':<14} {'segmenter':<18} {'precision':>9} {'recall':>9} "
+        f"{'f1':>9} {'chars/s':>12}"
+    ]
+    for dataset_name, segmenters in current["results"].items():
+        for segmenter_name, score in segmenters.items():
+            lines.append(
+                f"{dataset_name:<14} {segmenter_name:<18} {score['precision']:>9.4f} "
+                f"{score['recall']:>9.4f} {score['f1']:>9.4f} "
+                f"{score['chars_per_second']:>12,.0f}"
+            )
+    return "\n".join(lines)
+
+
+def main(argv: list[str] | None = None) -> int:
+    """Main entry point for the script."""
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument(
+        "--datasets",
+        default=",".join(DEFAULT_DATASETS),
+        help=f"comma-separated dataset names (available: {', '.join(DATASETS)})",
+    )
+    parser.add_argument(
+        "--segmenters",
+        default=",".join(DEFAULT_SEGMENTERS),
+        help="comma-separated segmenter names from benchmarks/baselines.py",
+    )
+    parser.add_argument("--output", type=Path, help="write results as JSON")
+    parser.add_argument(
+        "--check", type=Path, help="fail if metrics regress against this snapshot"
+    )
+    parser.add_argument(
+        "--tolerance",
+        type=float,
+        default=DEFAULT_TOLERANCE,
+        help=f"allowed drop per metric with --check (default {DEFAULT_TOLERANCE})",
+    )
+    args = parser.parse_args(argv)
+
+    current = run(args.datasets.split(","), args.segmenters.split(","))
+    print(format_results(current))
+    if args.output:
+        args.output.write_text(json.dumps(current, indent=2) + "\n")
+
+    if args.check:
+        baseline = json.loads(args.check.read_text())
+        regressions = find_regressions(baseline, current, args.tolerance)
+        if regressions:
+            print("\nAccuracy regressions:", file=sys.stderr)
+            for regression in regressions:
+                print(f"  {regression}", file=sys.stderr)
+            return 1
+        print(f"\nNo regressions against {args.check} (tolerance {args.tolerance})")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,182 @@
+"""Registry of sentence segmenters compared against sakurs.
+
+Every baseline is a factory returning a ``Segmenter`` (text in, sentence
+strings out) for one language. Third-party libraries and models are optional:
+``load_segmenter`` raises ``BaselineUnavailableError`` when one is missing, so
+benchmark runs skip it instead of failing.
+"""
+
+import functools
+from collections.abc import Callable
+from dataclasses import dataclass
+from typing import Any, Final
+
+import sakurs
+
+Segmenter = Callable[[str], list[str]]
+
+# spaCy pipelines used by the parser-based baseline, per language
+SPACY_MODELS: Final[dict[str, str]] = {
+    "en": "en_core_web_sm",
+    "ja": "ja_core_news_sm",
+}
+
+
+class BaselineUnavailableError(ImportError):
+    """Raised when a baseline's library or model is not installed."""
+
+
+@dataclass(frozen=True)
+class Baseline:
+    """A segmenter that can be benchmarked.
+
+    Attributes:
+        name: Identifier used in test names and result files.
+        display_name: Name shown in reports.
+        languages: Language codes the baseline supports.
+        factory: Builds the segmenter for a language code.
+    """
+
+    name: str
+    display_name: str
+    languages: tuple[str, ...]
+    factory: Callable[[str], Segmenter]
+
+
+def _sakurs(language: str) -> Segmenter:
+    return sakurs.load(language).split
+
+
+def _pysbd(language: str) -> Segmenter:
+    try:
+        import pysbd
+    except ImportError as e:
+        raise BaselineUnavailableError("pysbd is not installed") from e
+    segmenter = pysbd.Segmenter(language=language, clean=False)
+    return lambda text: [str(sentence).strip() for sentence in segmenter.segment(text)]
+
+
+def _syntok(language: str) -> Segmenter:
+    try:
+        from syntok import segmenter
+    except ImportError as e:
+        raise BaselineUnavailableError("syntok is not installed") from e
+
+    def segment(text: str) -> list[str]:
+        return [
+            "".join(token.spacing + token.value for token in sentence).strip()
+            for paragraph in segmenter.process(text)
+            for sentence in paragraph
+        ]
+
+    return segment
+
+
+def _spacy_segmenter(nlp: Any) -> Segmenter:
+    def segment(text: str) -> list[str]:
+        # The default limit (1M characters) guards parser memory; the
+        # sentencizer is linear, and large-text benchmarks exceed it
+        nlp.max_length = max(nlp.max_length, len(text) + 1)
+        return [sentence.text.strip() for sentence in nlp(text).sents]
+
+    return segment
+
+
+def _spacy_sentencizer(language: str) -> Segmenter:
+    try:
+        import spacy
+    except ImportError as e:
+        raise BaselineUnavailableError("spacy is not installed") from e
+    nlp = spacy.blank(language)
+    nlp.add_pipe("sentencizer")
+    return _spacy_segmenter(nlp)
+
+
+def _spacy_parser(language: str) -> Segmenter:
+    try:
+        import spacy
+    except ImportError as e:
+        raise BaselineUnavailableError("spacy is not installed") from e
+    model = SPACY_MODELS[language]
+    try:
+        # Sentence boundaries come from the dependency parse
+        nlp = spacy.load(model, exclude=["ner", "lemmatizer", "attribute_ruler"])
+    except OSError as e:
+        raise BaselineUnavailableError(
+            f"spaCy model {model} is not installed "
+            f"(python -m spacy download {model})"
+        ) from e
+    return _spacy_segmenter(nlp)
+
+
+def _ja_segmenter(language: str) -> Segmenter:
+    try:
+        from ja_sentence_segmenter.common.pipeline import make_pipeline
+        from ja_sentence_segmenter.concatenate.simple_concatenator import (
+            concatenate_matching,
+        )
+        from ja_sentence_segmenter.normalize.neologd_normalizer import normalize
+        from ja_sentence_segmenter.split.simple_splitter import (
+            split_newline,
+            split_punctuation,
+        )
+    except ImportError as e:
+        raise BaselineUnavailableError("ja_sentence_segmenter is not installed") from e
+    split_punc = functools.partial(split_punctuation, punctuations=r"。!?")
+    concat_tail_no = functools.partial(
+        concatenate_matching,
+        former_matching_rule=r"^(?P<r>.+)(の)$",
+        remove_former_matched=False,
+    )
+    pipeline = make_pipeline(normalize, split_newline, concat_tail_no, split_punc)
+    return lambda text: list(pipeline(text))
+
+
+BASELINES: Final[dict[str, Baseline]] = {
+    baseline.name: baseline
+    for baseline in [
+        Baseline("sakurs", "sakurs", tuple(sakurs.supported_languages()), _sakurs),
+        Baseline("pysbd", "PySBD", ("en", "ja"), _pysbd),
+        Baseline("syntok", "syntok", ("en",), _syntok),
+        Baseline(
+            "spacy_sentencizer", "spaCy sentencizer", ("en", "ja"), _spacy_sentencizer
+        ),
+        Baseline("spacy_parser", "spaCy parser", ("en", "ja"), _spacy_parser),
+        Baseline("ja_segmenter", "ja_sentence_segmenter", ("ja",), _ja_segmenter),
+    ]
+}
+
+
+def load_segmenter(name: str, language: str) -> Segmenter:
+    """Build the named baseline's segmenter for a language.
+
+    Args:
+        name: Baseline name, a key of ``BASELINES``.
+        language: Language code.
+
+    Returns:
+        The segmenter.
+
+    Raises:
+        KeyError: If the baseline is unknown.
+        ValueError: If the baseline does not support the language.
+        BaselineUnavailableError: If its library or model is not installed.
+    """
+    baseline = BASELINES[name]
+    if language not in baseline.languages:
+        raise ValueError(f"{baseline.display_name} does not support {language!r}")
+    return baseline.factory(language)
+
+
+def available_baselines(language: str) -> list[str]:
+    """Names of the baselines that support a language and are installed."""
+    names = []
+    for name, baseline in BASELINES.items():
+        if language not in baseline.languages:
+            continue
+        try:
+            baseline.factory(language)
+        except BaselineUnavailableError:
+            continue
+        names.append(name)
+    return names
@@ -0,0 +1,503 @@
+"""Named datasets with gold sentence boundaries for accuracy benchmarks.
+
+Universal Dependencies treebanks and the Brown Corpus are downloaded on
+first use and cached in ``$SAKURS_BENCHMARK_DATA`` (default
+``~/.cache/sakurs-benchmarks``). Each corpus becomes one ``Dataset``: its
+sentences joined into running text, with the character offset where every
+gold sentence ends. Aozora Bunko novels get approximate gold boundaries
+from their line structure. Wikipedia dumps have no gold sentences and load
+as throughput-only datasets. The ``synthetic-*`` datasets are generated by
+``benchmarks.generator`` instead.
+"""
+
+import functools
+import json
+import os
+import re
+import urllib.request
+import zipfile
+from collections.abc import Callable, Iterable
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Final
+
+# Pinned UD release, so gold data does not change under a stored baseline
+UD_RELEASE: Final[str] = "r2.14"
+UD_URL: Final[str] = (
+    "https://raw.githubusercontent.com/UniversalDependencies/{repo}/{release}/{file}"
+)
+
+BROWN_URL: Final[str] = (
+    "https://raw.githubusercontent.com/nltk/nltk_data/gh-pages"
+    "/packages/corpora/brown.zip"
+)
+
+AOZORA_URL: Final[str] = (
+    "https://raw.githubusercontent.com/aozorabunko/aozorabunko/master"
+    "/cards/{card}/files/{file}"
+)
+
+# Aozora Bunko works of the aozora-ja dataset, as (card, zip file, title)
+AOZORA_WORKS: Final[list[tuple[str, str, str]]] = [
+    ("000879", "127_ruby_150.zip", "羅生門"),
+    ("000035", "1567_ruby_4948.zip", "走れメロス"),
+    ("000148", "773_ruby_5968.zip", "こころ"),
+    ("000148", "789_ruby_5639.zip", "吾輩は猫である"),
+]
+
+# Languages written without spaces between sentences
+UNSPACED_LANGUAGES: Final[frozenset[str]] = frozenset({"ja", "zh", "th"})
+
+
+class DatasetUnavailableError(RuntimeError):
+    """Raised when a dataset is neither cached nor downloadable."""
+
+
+@dataclass
+class Dataset:
+    """Running text with gold sentence boundaries.
+
+    Attributes:
+        name: Registered dataset name.
+        language: Language code of the text.
+        text: The running text.
+        boundaries: Character offsets where gold sentences end, ascending.
+            Empty for throughput-only corpora.
+    """
+
+    name: str
+    language: str
+    text: str
+    boundaries: list[int] = field(default_factory=list)
+
+    @property
+    def has_gold(self) -> bool:
+        """Whether the dataset has gold boundaries (else throughput only)."""
+        return bool(self.boundaries)
+
+    @property
+    def sentences(self) -> list[str]:
+        """The gold sentences, stripped."""
+        starts = [0, *self.boundaries]
+        return [
+            self.text[start:end].strip()
+            for start, end in zip(starts, self.boundaries, strict=False)
+            if self.text[start:end].strip()
+        ]
+
+
+@dataclass(frozen=True)
+class DatasetSpec:
+    """A registered dataset: how to load it and what it contains.
+
+    Attributes:
+        name: Dataset name.
+        language: Language code.
+        description: One-line description shown in listings.
+        loader: Builds the dataset, downloading it if needed.
+    """
+
+    name: str
+    language: str
+    description: str
+    loader: Callable[[], Dataset]
+
+
+DATASETS: dict[str, DatasetSpec] = {}
+
+
+def register_dataset(
+    name: str, language: str, description: str, loader: Callable[[], Dataset]
+) -> None:
+    """Register a dataset under a name, replacing any previous one."""
+    DATASETS[name] = DatasetSpec(name, language, description, loader)
+
+
+def load_dataset(name: str) -> Dataset:
+    """Load a registered dataset by name.
+
+    Raises:
+        KeyError: If no dataset has that name.
+        DatasetUnavailableError: If it must be downloaded and cannot be.
+    """
+    return DATASETS[name].loader()
+
+
+def data_dir() -> Path:
+    """Directory where downloaded datasets are cached."""
+    configured = os.environ.get("SAKURS_BENCHMARK_DATA")
+    if configured:
+        return Path(configured)
+    return Path.home() / ".cache" / "sakurs-benchmarks"
+
+
+def fetch(url: str, filename: str) -> Path:
+    """Return the cached copy of ``url``, downloading it on first use."""
+    path = data_dir() / filename
+    if path.exists():
+        return path
+    path.parent.mkdir(parents=True, exist_ok=True)
+    partial = path.with_suffix(path.suffix + ".part")
+    try:
+        with urllib.request.urlopen(url, timeout=60) as response:  # noqa: S310
+            partial.write_bytes(response.read())
+    except OSError as e:
+        raise DatasetUnavailableError(
+            f"cannot download {url} ({e}); place the file at {path} to use it offline"
+        ) from e
+    partial.replace(path)
+    return path
+
+
+def boundaries_from_sentences(text: str, sentences: Iterable[str]) -> list[int]:
+    """Map a segmenter's sentence strings back to end offsets in ``text``.
+
+    Segmenters strip or normalize whitespace, so sentences are aligned by
+    counting their non-whitespace characters rather than by exact search.
+    """
+    boundaries: list[int] = []
+    position = 0
+    for sentence in sentences:
+        remaining = sum(1 for c in sentence if not c.isspace())
+        if remaining == 0:
+            continue
+        while position < len(text) and remaining:
+            if not text[position].isspace():
+                remaining -= 1
+            position += 1
+        boundaries.append(position)
+    return boundaries
+
+
+def conllu_sentences(lines: Iterable[str]) -> Iterable[tuple[str, bool]]:
+    """Yield ``(text, starts_paragraph)`` for each sentence of a CoNLL-U file.
+
+    Sentence text comes from the ``# text =`` comment; ``# newdoc`` and
+    ``# newpar`` comments mark the start of a paragraph.
+    """
+    text: str | None = None
+    new_paragraph = False
+    for raw in lines:
+        line = raw.rstrip("\n")
+        if line.startswith("# text ="):
+            text = line[len("# text =") :].strip()
+        elif line.startswith(("# newdoc", "# newpar")):
+            new_paragraph = True
+        elif not line.strip():
+            if text:
+                yield text, new_paragraph
+                new_paragraph = False
+            text = None
+    if text:
+        yield text, new_paragraph
+
+
+def dataset_from_conllu(name: str, language: str, lines: Iterable[str]) -> Dataset:
+    """Build a dataset from CoNLL-U lines, see ``join_sentences``."""
+    paragraphs: list[list[str]] = []
+    for text, new_paragraph in conllu_sentences(lines):
+        if new_paragraph or not paragraphs:
+            paragraphs.append([])
+        paragraphs[-1].append(text)
+    return join_sentences(name, language, paragraphs)
+
+
+def join_sentences(
+    name: str, language: str, paragraphs: Iterable[list[str]]
+) -> Dataset:
+    """Build a dataset from paragraphs of sentences.
+
+    Sentences are joined with a space (nothing for languages written without
+    spaces) and paragraphs with a blank line.
+    """
+    separator = "" if language in UNSPACED_LANGUAGES else " "
+    parts: list[str] = []
+    boundaries: list[int] = []
+    length = 0
+    for paragraph in paragraphs:
+        for index, sentence in enumerate(paragraph):
+            if parts:
+                joiner = separator if index else "\n\n"
+                parts.append(joiner)
+                length += len(joiner)
+            parts.append(sentence)
+            length += len(sentence)
+            boundaries.append(length)
+    return Dataset(name, language, "".join(parts), boundaries)
+
+
+# Brown tokens attached to the preceding or following token when detokenizing
+_ATTACH_LEFT: Final[frozenset[str]] = frozenset(
+    {".", ",", ";", ":", "?", "!", ")", "''", "'"}
+)
+_ATTACH_RIGHT: Final[frozenset[str]] = frozenset({"(", "``"})
+
+
+def detokenize(tokens: Iterable[str]) -> str:
+    """Rebuild running text from Penn/Brown-style tokens."""
+    text = ""
+    attach = True
+    for token in tokens:
+        word = {"``": '"', "''": '"'}.get(token, token)
+        if not attach and token not in _ATTACH_LEFT:
+            text += " "
+        text += word
+        attach = token in _ATTACH_RIGHT
+    return text
+
+
+def brown_paragraphs(lines: Iterable[str]) -> Iterable[list[str]]:
+    """Yield the paragraphs of an NLTK Brown Corpus file as sentence lists.
+
+    Each non-blank line is one tagged sentence (``word/tag word/tag ...``);
+    blank lines separate paragraphs.
+    """
+    paragraph: list[str] = []
+    for line in lines:
+        tokens = [token.rsplit("/", 1)[0] for token in line.split()]
+        if tokens:
+            paragraph.append(detokenize(tokens))
+        elif paragraph:
+            yield paragraph
+            paragraph = []
+    if paragraph:
+        yield paragraph
+
+
+def _load_brown() -> Dataset:
+    path = fetch(BROWN_URL, "brown/brown.zip")
+    with zipfile.ZipFile(path) as archive:
+        files = sorted(
+            name
+            for name in archive.namelist()
+            if re.fullmatch(r"brown/c[a-r]\d\d", name)
+        )
+        paragraphs = [
+            paragraph
+            for name in files
+            for paragraph in brown_paragraphs(
+                archive.read(name).decode("utf-8").splitlines()
+            )
+        ]
+    return join_sentences("brown", "en", paragraphs)
+
+
+def wikiextractor_documents(lines: Iterable[str]) -> Iterable[str]:
+    """Yield the document texts of WikiExtractor output.
+
+    Documents are wrapped in ``<doc ...>`` and ``</doc>`` lines; the first
+    line inside is the article title and is dropped.
+    """
+    document: list[str] | None = None
+    for raw in lines:
+        line = raw.rstrip("\n")
+        if line.startswith("<doc "):
+            document = []
+        elif line == "</doc>":
+            if document is not None:
+                body = "\n".join(document[1:]).strip()
+                if body:
+                    yield body
+            document = None
+        elif document is not None:
+            document.append(line)
+
+
+def cirrus_documents(lines: Iterable[str]) -> Iterable[str]:
+    """Yield the article texts of a CirrusSearch dump.
+
+    The dump is JSON lines in pairs: an ``{"index": ...}`` action line, then
+    the page with its plain text in ``"text"``.
+    """
+    for line in lines:
+        if not line.strip():
+            continue
+        page = json.loads(line)
+        text = page.get("text") if "index" not in page else None
+        if isinstance(text, str) and text.strip():
+            yield text.strip()
+
+
+def load_wikiextractor(name: str, language: str, path: Path) -> Dataset:
+    """Load WikiExtractor output as a throughput-only dataset.
+
+    Args:
+        name: Dataset name.
+        language: Language of the dump.
+        path: An output file, or a directory searched recursively for
+            ``wiki_*`` files.
+
+    Raises:
+        DatasetUnavailableError: If ``path`` does not exist.
+    """
+    if not path.exists():
+        raise DatasetUnavailableError(
+            f"no WikiExtractor output at {path}; run "
+            f"`wikiextractor -o {path} <dump>.xml.bz2` to create it"
+        )
+    files = sorted(path.rglob("wiki_*")) if path.is_dir() else [path]
+    documents: list[str] = []
+    for file in files:
+        with file.open(encoding="utf-8") as f:
+            documents.extend(wikiextractor_documents(f))
+    return Dataset(name, language, "\n\n".join(documents))
+
+
+def _load_wikipedia(language: str) -> Dataset:
+    path = data_dir() / "wikipedia" / language
+    return load_wikiextractor(f"wikipedia-{language}", language, path)
+
+
+# Aozora Bunko notation: readings, the ｜ starting a base, and ［＃...］
+# editorial notes (with the ※ standing in for a character they describe)
+_AOZORA_MARKUP: Final[re.Pattern[str]] = re.compile(r"《[^》\n]*》|｜|※?［＃[^］\n]*］")
+_AOZORA_TERMINATORS: Final[str] = "。！？!?"
+_AOZORA_OPENERS: Final[str] = "「『（(〈《【〔［"
+_AOZORA_CLOSERS: Final[str] = "」』）)〉》】〕］"
+
+
+def strip_aozora_markup(line: str) -> str:
+    """Remove ruby, ｜ and ［＃...］ notes from one line of Aozora Bunko text."""
+    return _AOZORA_MARKUP.sub("", line)
+
+
+def aozora_body(lines: Iterable[str]) -> Iterable[str]:
+    """Yield the body lines of an Aozora Bunko text file.
+
+    The title block and the notation guide between the two ``-----`` rules
+    are skipped, and so is the colophon from the ``底本：`` line on.
+    """
+    lines = [line.rstrip("\r\n") for line in lines]
+    rules = [i for i, line in enumerate(lines) if re.fullmatch(r"-{10,}", line)]
+    start = rules[1] + 1 if len(rules) >= 2 else 0
+    for line in lines[start:]:
+        if line.startswith("底本："):
+            return
+        yield line
+
+
+def aozora_line_sentences(line: str) -> list[str]:
+    """Split one body line into gold sentences.
+
+    Every line is a paragraph or a line of dialogue, so its end is a gold
+    boundary. Inside it a sentence ends after a run of terminators outside
+    brackets.
+    """
+    sentences: list[str] = []
+    depth = 0
+    start = 0
+    i = 0
+    while i < len(line):
+        ch = line[i]
+        i += 1
+        if ch in _AOZORA_OPENERS:
+            depth += 1
+        elif ch in _AOZORA_CLOSERS:
+            depth = max(depth - 1, 0)
+        elif ch in _AOZORA_TERMINATORS and depth == 0:
+            while i < len(line) and line[i] in _AOZORA_TERMINATORS:
+                i += 1
+            sentences.append(line[start:i].strip())
+            start = i
+    if line[start:].strip():
+        sentences.append(line[start:].strip())
+    return [sentence for sentence in sentences if sentence]
+
+
+def aozora_paragraphs(lines: Iterable[str]) -> Iterable[list[str]]:
+    """Yield the paragraphs of an Aozora Bunko text file as sentence lists.
+
+    Markup is stripped and each body line becomes a paragraph of its gold
+    sentences, see ``aozora_line_sentences``; headings (lines with a
+    ``見出し`` note) and blank lines are dropped.
+    """
+    for line in aozora_body(lines):
+        if "見出し］" in line:
+            continue
+        sentences = aozora_line_sentences(strip_aozora_markup(line).strip("　 "))
+        if sentences:
+            yield sentences
+
+
+def _load_aozora() -> Dataset:
+    paragraphs: list[list[str]] = []
+    for card, file, _title in AOZORA_WORKS:
+        url = AOZORA_URL.format(card=card, file=file)
+        path = fetch(url, f"aozora/{file}")
+        with zipfile.ZipFile(path) as archive:
+            name = next(n for n in archive.namelist() if n.endswith(".txt"))
+            # Aozora Bunko files are Shift_JIS with Windows extensions
+            text = archive.read(name).decode("cp932")
+        paragraphs.extend(aozora_paragraphs(text.splitlines()))
+    return join_sentences("aozora-ja", "ja", paragraphs)
+
+
+def _load_synthetic(name: str) -> Dataset:
+    from benchmarks.generator import PRESETS, generate
+
+    return generate(PRESETS[name], name)
+
+
+def _ud_loader(name: str, language: str, repo: str, file: str) -> Callable[[], Dataset]:
+    def load() -> Dataset:
+        url = UD_URL.format(repo=repo, release=UD_RELEASE, file=file)
+        path = fetch(url, f"ud/{UD_RELEASE}/{file}")
+        with path.open(encoding="utf-8") as f:
+            return dataset_from_conllu(name, language, f)
+
+    return load
+
+
+# Test splits of the UD treebanks, as (name, language, repository, file)
+UD_TREEBANKS: Final[list[tuple[str, str, str, str]]] = [
+    ("ud-en-ewt", "en", "UD_English-EWT", "en_ewt-ud-test.conllu"),
+    ("ud-en-gum", "en", "UD_English-GUM", "en_gum-ud-test.conllu"),
+    ("ud-ja-gsd", "ja", "UD_Japanese-GSD", "ja_gsd-ud-test.conllu"),
+    ("ud-pt-bosque", "pt", "UD_Portuguese-Bosque", "pt_bosque-ud-test.conllu"),
+    ("ud-it-isdt", "it", "UD_Italian-ISDT", "it_isdt-ud-test.conllu"),
+    ("ud-ar-padt", "ar", "UD_Arabic-PADT", "ar_padt-ud-test.conllu"),
+    ("ud-hi-hdtb", "hi", "UD_Hindi-HDTB", "hi_hdtb-ud-test.conllu"),
+    ("ud-th-pud", "th", "UD_Thai-PUD", "th_pud-ud-test.conllu"),
+]
+
+for _name, _language, _repo, _file in UD_TREEBANKS:
+    register_dataset(
+        _name,
+        _language,
+        f"Universal Dependencies {_repo[3:]} ({UD_RELEASE}, test split)",
+        _ud_loader(_name, _language, _repo, _file),
+    )
+
+register_dataset("brown", "en", "Brown Corpus (NLTK distribution)", _load_brown)
+register_dataset(
+    "aozora-ja",
+    "ja",
+    "Aozora Bunko novels ("
+    + "、".join(title for _card, _file, title in AOZORA_WORKS)
+    + "), gold boundaries from line structure",
+    _load_aozora,
+)
+
+for _language in ("en", "ja"):
+    register_dataset(
+        f"wikipedia-{_language}",
+        _language,
+        "Wikipedia articles extracted with WikiExtractor (throughput only)",
+        functools.partial(_load_wikipedia, _language),
+    )
+
+# Presets of benchmarks/generator.py, generated on load rather than downloaded
+for _name, _language, _description in [
+    ("synthetic-en", "en", "Synthetic English with gold boundaries"),
+    ("synthetic-ja", "ja", "Synthetic Japanese with gold boundaries"),
+    ("synthetic-mixed", "en", "Synthetic English/Japanese mix with gold boundaries"),
+    (
+        "synthetic-stress",
+        "en",
+        "Synthetic English with long sentences, dense abbreviations and "
+        "quotations nested three deep",
+    ),
+]:
+    register_dataset(
+        _name, _language, _description, functools.partial(_load_synthetic, _name)
+    )
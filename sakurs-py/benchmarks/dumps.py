@@ -0,0 +1,242 @@
+#!/usr/bin/env python3
+"""Sustained throughput and memory of sakurs on compressed Wikipedia dumps.
+
+Run from ``sakurs-py`` (Linux or macOS)::
+
+    python -m benchmarks.dumps enwiki-cirrussearch-content.json.gz \\
+        --language en --limit-mb 4096 --output dump.json
+
+The dump is streamed, never held in memory: CirrusSearch dumps (gzipped
+JSON lines from https://dumps.wikimedia.org/other/cirrussearch/) and
+WikiExtractor output, plain or compressed with gzip, bzip2 or xz. Article
+texts are written to a temporary file up to ``--limit-mb``, which each
+execution mode then processes in a fresh Python process, as in
+``benchmarks.memory``. Besides peak memory and overall throughput, the
+throughput of every ``--window-mb`` of input is recorded, so a run that
+slows down as it goes shows up in its minimum window.
+
+The result file has the layout of ``benchmarks.memory`` results, keyed by
+the dump's name, and renders with ``benchmarks.report``.
+"""
+
+import argparse
+import bz2
+import gzip
+import itertools
+import json
+import lzma
+import sys
+import tempfile
+import time
+from collections.abc import Iterable, Iterator
+from pathlib import Path
+from typing import IO, Any, Final
+
+from benchmarks.datasets import cirrus_documents, wikiextractor_documents
+from benchmarks.memory import BASELINE_MODE, MODES, SCHEMA_VERSION, measure
+
+DEFAULT_LIMIT_MB: Final[int] = 1024
+DEFAULT_WINDOW_MB: Final[int] = 64
+
+_OPENERS: Final[dict[str, Any]] = {
+    ".gz": gzip.open,
+    ".bz2": bz2.open,
+    ".xz": lzma.open,
+}
+
+
+def open_dump(path: Path) -> IO[str]:
+    """Open a dump as text, decompressing it by its suffix."""
+    opener = _OPENERS.get(path.suffix, open)
+    return opener(path, "rt", encoding="utf-8")  # type: ignore[no-any-return]
+
+
+def dump_name(path: Path) -> str:
+    """Name of a dump in results: its file name without extensions."""
+    return path.name.split(".")[0]
+
+
+def dump_documents(lines: Iterable[str]) -> Iterator[str]:
+    """Yield the article texts of a CirrusSearch or WikiExtractor dump.
+
+    The format is told from the first non-blank line: a JSON object starts
+    CirrusSearch dumps, a ``<doc`` line WikiExtractor output.
+    """
+    lines = iter(lines)
+    for first in lines:
+        if first.strip():
+            break
+    else:
+        return
+    all_lines = itertools.chain([first], lines)
+    if first.lstrip().startswith("{"):
+        yield from cirrus_documents(all_lines)
+    else:
+        yield from wikiextractor_documents(all_lines)
+
+
+def write_documents(
+    documents: Iterable[str], output: IO[str], limit_bytes: int
+) -> tuple[int, int]:
+    """Write documents separated by blank lines until ``limit_bytes``.
+
+    Returns:
+        The number of documents and UTF-8 bytes written. The document that
+        crosses the limit is written whole.
+    """
+    count = 0
+    written = 0
+    for document in documents:
+        if written >= limit_bytes:
+            break
+        if count:
+            output.write("\n\n")
+            written += 2
+        output.write(document)
+        written += len(document.encode("utf-8"))
+        count += 1
+    return count, written
+
+
+def window_rates(marks: list[tuple[int, float]]) -> list[float]:
+    """Throughput of each window from ``(chars, seconds)`` marks."""
+    return [
+        (chars - before_chars) / (seconds - before_seconds)
+        for (before_chars, before_seconds), (chars, seconds) in zip(
+            marks, marks[1:], strict=False
+        )
+        if seconds > before_seconds
+    ]
+
+
+def run_worker(mode: str, language: str, path: str, window_mb: str) -> None:
+    """Process ``path`` in one mode and print the result as JSON.
+
+    Streaming runs mark every ``window_mb`` of sentences; whole-file runs
+    have one window.
+    """
+    import sakurs
+
+    window_chars = int(float(window_mb) * 1024 * 1024)
+    start = time.perf_counter()
+    marks = [(0, 0.0)]
+    sentences = 0
+    chars = 0
+    if mode == "streaming":
+        for sentence in sakurs.iter_split(Path(path), language=language):
+            sentences += 1
+            chars += len(sentence)
+            if chars - marks[-1][0] >= window_chars:
+                marks.append((chars, time.perf_counter() - start))
+    elif mode != BASELINE_MODE:
+        splitter = sakurs.load(language, execution_mode=mode)  # type: ignore[arg-type]
+        result = splitter.split(Path(path))
+        sentences = len(result)
+        chars = sum(len(sentence) for sentence in result)
+    seconds = time.perf_counter() - start
+    if chars > marks[-1][0]:
+        marks.append((chars, seconds))
+    print(
+        json.dumps(
+            {
+                "sentences": sentences,
+                "chars": chars,
+                "seconds": seconds,
+                "window_chars_per_second": window_rates(marks),
+            }
+        )
+    )
+
+
+def run(
+    path: Path, language: str, limit_mb: float, window_mb: float
+) -> dict[str, Any]:
+    """Stream the dump into a file and measure every mode on it.
+
+    Returns:
+        The result document: ``{"schema_version", "results"}`` where
+        ``results[name][mode]`` holds the fields of ``benchmarks.memory``
+        results plus ``documents``, ``window_chars_per_second`` and its
+        minimum, ``min_window_chars_per_second``.
+    """
+    modes: dict[str, Any] = {}
+    with tempfile.TemporaryDirectory() as directory:
+        text_path = Path(directory) / f"{dump_name(path)}.txt"
+        with open_dump(path) as dump, text_path.open("w", encoding="utf-8") as out:
+            documents, _ = write_documents(
+                dump_documents(dump), out, int(limit_mb * 1024 * 1024)
+            )
+        input_mb = text_path.stat().st_size / (1024 * 1024)
+        for mode in (BASELINE_MODE, *MODES):
+            worker = ("benchmarks.dumps", str(window_mb))
+            result = measure(mode, language, text_path, worker=worker)
+            seconds = result["seconds"]
+            if mode != BASELINE_MODE:
+                chars = result["chars"]
+                result["chars_per_second"] = chars / seconds if seconds else 0.0
+                windows = result["window_chars_per_second"]
+                result["min_window_chars_per_second"] = min(windows, default=0.0)
+            result["input_mb"] = input_mb
+            result["documents"] = documents
+            modes[mode] = result
+    return {"schema_version": SCHEMA_VERSION, "results": {dump_name(path): modes}}
+
+
+def format_results(current: dict[str, Any]) -> str:
+    """Render results as a plain-text table of memory and throughput."""
+    lines = [
+        f"{'dump':<14} {'mode':<11} {'input MiB':>9} {'peak MiB':>9} "
+        f"{'chars/s':>12} {'min window':>12}"
+    ]
+    for name, modes in current["results"].items():
+        for mode in MODES:
+            result = modes[mode]
+            lines.append(
+                f"{name:<14} {mode:<11} {result['input_mb']:>9.1f} "
+                f"{result['peak_rss_mb']:>9.1f} "
+                f"{result['chars_per_second']:>12,.0f} "
+                f"{result['min_window_chars_per_second']:>12,.0f}"
+            )
+    return "\n".join(lines)
+
+
+def main(argv: list[str] | None = None) -> int:
+    """Main entry point for the script."""
+    argv = sys.argv[1:] if argv is None else argv
+    if argv[:1] == ["--worker"]:
+        run_worker(*argv[1:5])
+        return 0
+
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument(
+        "dump", type=Path, help="CirrusSearch or WikiExtractor dump, maybe compressed"
+    )
+    parser.add_argument("--language", default="en", help="language of the dump")
+    parser.add_argument(
+        "--limit-mb",
+        type=float,
+        default=DEFAULT_LIMIT_MB,
+        help="stop reading the dump after this much text "
+        f"(default: {DEFAULT_LIMIT_MB})",
+    )
+    parser.add_argument(
+        "--window-mb",
+        type=float,
+        default=DEFAULT_WINDOW_MB,
+        help="input per throughput window of streaming runs "
+        f"(default: {DEFAULT_WINDOW_MB})",
+    )
+    parser.add_argument("--output", type=Path, help="write results as JSON")
+    args = parser.parse_args(argv)
+    if not args.dump.exists():
+        parser.error(f"no dump at {args.dump}")
+
+    current = run(args.dump, args.language, args.limit_mb, args.window_mb)
+    print(format_results(current))
+    if args.output:
+        args.output.write_text(json.dumps(current, indent=2) + "\n")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -16,10 +16,24 @@ ENGLISH_LANGUAGE_KEY: Final[str] = "english"
 JAPANESE_LANGUAGE_KEY: Final[str] = "japanese"
 SAKURS_LIBRARY_KEY: Final[str] = "sakurs"
 PYSBD_LIBRARY_KEY: Final[str] = "pysbd"
+SYNTOK_LIBRARY_KEY: Final[str] = "syntok"
+SPACY_SENTENCIZER_LIBRARY_KEY: Final[str] = "spacy_sentencizer"
+SPACY_PARSER_LIBRARY_KEY: Final[str] = "spacy_parser"
 JA_SEGMENTER_LIBRARY_KEY: Final[str] = "ja_segmenter"
 TEST_400_CHARS_KEY: Final[str] = "400_chars"
 TEST_LARGE_KEY: Final[str] = "large"
 
+# Baselines compared against sakurs, as (library key, display name)
+ENGLISH_BASELINES: Final[list[tuple[str, str]]] = [
+    (PYSBD_LIBRARY_KEY, "PySBD"),
+    (SYNTOK_LIBRARY_KEY, "syntok"),
+    (SPACY_SENTENCIZER_LIBRARY_KEY, "spaCy sentencizer"),
+    (SPACY_PARSER_LIBRARY_KEY, "spaCy parser"),
+]
+JAPANESE_BASELINES: Final[list[tuple[str, str]]] = [
+    (JA_SEGMENTER_LIBRARY_KEY, "ja_sentence_segmenter"),
+]
+
 # Multipliers for large text tests
 ENGLISH_LARGE_MULTIPLIER: Final[int] = 550
 JAPANESE_LARGE_MULTIPLIER: Final[int] = 200
@@ -58,6 +72,12 @@ def extract_benchmark_data(
             library = SAKURS_LIBRARY_KEY
         elif PYSBD_LIBRARY_KEY in name:
             library = PYSBD_LIBRARY_KEY
+        elif SYNTOK_LIBRARY_KEY in name:
+            library = SYNTOK_LIBRARY_KEY
+        elif SPACY_SENTENCIZER_LIBRARY_KEY in name:
+            library = SPACY_SENTENCIZER_LIBRARY_KEY
+        elif SPACY_PARSER_LIBRARY_KEY in name:
+            library = SPACY_PARSER_LIBRARY_KEY
         elif JA_SEGMENTER_LIBRARY_KEY in name:
             library = JA_SEGMENTER_LIBRARY_KEY
         else:
@@ -95,20 +115,18 @@ def extract_benchmark_data(
 
 
 def generate_performance_table(
-    data: dict[str, dict[str, Any]], language: str, test_type: str
+    data: dict[str, dict[str, Any]],
+    language: str,
+    test_type: str,
+    other_library: str,
+    other_name: str,
 ) -> str:
-    """Generate performance comparison table."""
+    """Generate performance comparison table against one baseline library."""
     sakurs_key = f"{language}_{test_type}_{SAKURS_LIBRARY_KEY}"
-
-    if language == ENGLISH_LANGUAGE_KEY:
-        other_key = f"{language}_{test_type}_{PYSBD_LIBRARY_KEY}"
-        other_name = "PySBD"
-    else:
-        other_key = f"{language}_{test_type}_{JA_SEGMENTER_LIBRARY_KEY}"
-        other_name = "ja_sentence_segmenter"
+    other_key = f"{language}_{test_type}_{other_library}"
 
     if sakurs_key not in data or other_key not in data:
-        return "No data available for this comparison.\n"
+        return f"No data available for the {other_name} comparison.\n"
 
     sakurs_data = data[sakurs_key]
     other_data = data[other_key]
@@ -166,6 +184,23 @@ def format_sentences_comparison(segmentation_data: dict[str, dict[str, Any]]) ->
     return output
 
 
+def generate_performance_tables(
+    data: dict[str, dict[str, Any]],
+    language: str,
+    test_type: str,
+    baselines: list[tuple[str, str]],
+) -> str:
+    """Generate one comparison table per baseline that has results."""
+    tables = [
+        generate_performance_table(data, language, test_type, library, name)
+        for library, name in baselines
+        if f"{language}_{test_type}_{library}" in data
+    ]
+    if not tables:
+        return "No data available for this comparison.\n"
+    return "\n".join(tables)
+
+
 def generate_markdown_summary(json_file: str) -> str:
     """Generate markdown summary from benchmark results."""
     with open(json_file) as f:
@@ -189,18 +224,20 @@ def generate_markdown_summary(json_file: str) -> str:
 
     # English section
     output_lines.append("## English Sentence Segmentation\n")
-    output_lines.append("Comparing sakurs against PySBD for English text processing.\n")
+    output_lines.append(
+        "Comparing sakurs against PySBD, syntok and spaCy for English text processing.\n"
+    )
 
     output_lines.append("### 400 Character Text Performance\n")
     output_lines.append(
-        generate_performance_table(
-            benchmark_data, ENGLISH_LANGUAGE_KEY, TEST_400_CHARS_KEY
+        generate_performance_tables(
+            benchmark_data, ENGLISH_LANGUAGE_KEY, TEST_400_CHARS_KEY, ENGLISH_BASELINES
         )
     )
 
     # Extract segmentation results for English 400-char tests
     english_400_data: dict[str, dict[str, Any]] = {}
-    for lib in [SAKURS_LIBRARY_KEY, PYSBD_LIBRARY_KEY]:
+    for lib in [SAKURS_LIBRARY_KEY] + [key for key, _ in ENGLISH_BASELINES]:
         key = f"{ENGLISH_LANGUAGE_KEY}_{TEST_400_CHARS_KEY}_{lib}"
         if key in benchmark_data and "extra_info" in benchmark_data[key]:
             english_400_data[lib] = benchmark_data[key]["extra_info"]
@@ -213,7 +250,9 @@ def generate_markdown_summary(json_file: str) -> str:
         f"Performance on large text (400-char sample repeated {ENGLISH_LARGE_MULTIPLIER} times):\n"
     )
     output_lines.append(
-        generate_performance_table(benchmark_data, ENGLISH_LANGUAGE_KEY, TEST_LARGE_KEY)
+        generate_performance_tables(
+            benchmark_data, ENGLISH_LANGUAGE_KEY, TEST_LARGE_KEY, ENGLISH_BASELINES
+        )
     )
 
     # Japanese section
@@ -224,14 +263,14 @@ def generate_markdown_summary(json_file: str) -> str:
 
     output_lines.append("### 400 Character Text Performance\n")
     output_lines.append(
-        generate_performance_table(
-            benchmark_data, JAPANESE_LANGUAGE_KEY, TEST_400_CHARS_KEY
+        generate_performance_tables(
+            benchmark_data, JAPANESE_LANGUAGE_KEY, TEST_400_CHARS_KEY, JAPANESE_BASELINES
         )
     )
 
     # Extract segmentation results for Japanese 400-char tests
     japanese_400_data: dict[str, dict[str, Any]] = {}
-    for lib in [SAKURS_LIBRARY_KEY, JA_SEGMENTER_LIBRARY_KEY]:
+    for lib in [SAKURS_LIBRARY_KEY] + [key for key, _ in JAPANESE_BASELINES]:
         key = f"{JAPANESE_LANGUAGE_KEY}_{TEST_400_CHARS_KEY}_{lib}"
         if key in benchmark_data and "extra_info" in benchmark_data[key]:
             japanese_400_data[lib] = benchmark_data[key]["extra_info"]
@@ -244,8 +283,8 @@ def generate_markdown_summary(json_file: str) -> str:
         f"Performance on large text (Japanese sample repeated {JAPANESE_LARGE_MULTIPLIER} times):\n"
     )
     output_lines.append(
-        generate_performance_table(
-            benchmark_data, JAPANESE_LANGUAGE_KEY, TEST_LARGE_KEY
+        generate_performance_tables(
+            benchmark_data, JAPANESE_LANGUAGE_KEY, TEST_LARGE_KEY, JAPANESE_BASELINES
         )
     )
 
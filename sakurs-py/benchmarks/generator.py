@@ -0,0 +1,324 @@
+#!/usr/bin/env python3
+"""Synthetic corpora with known gold sentence boundaries.
+
+Real datasets fix the mix of constructs a segmenter sees. The generator
+builds text from a ``GeneratorConfig`` instead, so each difficulty can be
+dialed independently for stress tests and scaling curves:
+
+- sentence lengths follow a log-normal distribution of words;
+- abbreviations ("Dr. Smith", "e.g. tea") put periods inside sentences;
+  languages without abbreviations get decimal numbers ("3.5") instead;
+- quotations nest up to a given depth, with full sentences inside them
+  that must not end the enclosing one;
+- sentences are drawn from a weighted mix of languages.
+
+Generation is deterministic for a given seed. Run from ``sakurs-py``::
+
+    python -m benchmarks.generator --sentences 100000 --languages en=0.7,ja=0.3 \\
+        --quote-depth 3 --output corpus.txt --gold corpus.json
+"""
+
+import argparse
+import json
+import math
+import random
+import sys
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Final
+
+from benchmarks.datasets import UNSPACED_LANGUAGES, Dataset
+
+
+@dataclass(frozen=True)
+class GeneratorConfig:
+    """Shape of a synthetic corpus.
+
+    Attributes:
+        sentences: Number of top-level sentences.
+        languages: Language code to relative weight; every sentence is
+            drawn in one of them.
+        mean_words: Mean sentence length in words.
+        length_spread: Standard deviation of the log length; 0 makes every
+            sentence ``mean_words`` long.
+        min_words: Shortest sentence, in words (at least 2).
+        max_words: Longest sentence, in words.
+        abbreviation_density: Expected abbreviations per sentence.
+        quote_probability: Chance that a sentence contains a quotation.
+        max_quote_depth: Deepest nesting of quotations; each quotation
+            nests 1 to ``max_quote_depth`` levels.
+        paragraph_sentences: Sentences per paragraph; paragraphs are
+            separated by a blank line.
+        seed: Random seed.
+    """
+
+    sentences: int = 1000
+    languages: dict[str, float] = field(default_factory=lambda: {"en": 1.0})
+    mean_words: float = 14.0
+    length_spread: float = 0.4
+    min_words: int = 3
+    max_words: int = 80
+    abbreviation_density: float = 0.3
+    quote_probability: float = 0.15
+    max_quote_depth: int = 1
+    paragraph_sentences: int = 8
+    seed: int = 0
+
+
+@dataclass(frozen=True)
+class Vocabulary:
+    """The material sentences of one language are built from.
+
+    Attributes:
+        words: Ordinary words.
+        terminators: Sentence-final punctuation, the first most common.
+        abbreviations: Phrases with a period that does not end the sentence.
+        quotes: Enclosure pairs by nesting level, outermost first.
+        spaced: Whether words are separated by spaces.
+    """
+
+    words: tuple[str, ...]
+    terminators: tuple[str, ...]
+    abbreviations: tuple[str, ...]
+    quotes: tuple[tuple[str, str], ...]
+    spaced: bool
+
+
+VOCABULARIES: Final[dict[str, Vocabulary]] = {
+    "en": Vocabulary(
+        words=tuple(
+            "the report city council market river said would new plan after "
+            "before with their people morning quickly small house water company "
+            "year several under about team found opened road school every light "
+            "without old".split()
+        ),
+        terminators=(".", ".", ".", "?", "!"),
+        abbreviations=(
+            "Dr. Smith",
+            "Mr. Brown",
+            "Mrs. Green",
+            "Prof. Lee",
+            "e.g. tea",
+            "i.e. water",
+            "vs. them",
+            "at 3 p.m. today",
+            "Acme Inc. staff",
+            "the U.S. market",
+        ),
+        quotes=(('"', '"'), ("(", ")"), ("[", "]")),
+        spaced=True,
+    ),
+    "ja": Vocabulary(
+        words=tuple(
+            "今日は 会議が あり 駅の 近くで 新しい 計画を 発表した 市民は 静かに "
+            "待っていた 午後に 雨が 降り 川の 水が 増えた 学校では 先生が 本を "
+            "読んだ 会社の 報告によると 道路が 開通し".split()
+        ),
+        terminators=("。", "。", "。", "？", "！"),
+        abbreviations=("3.5キロの", "約1.2倍の", "0.8秒で", "第2.1版の"),
+        quotes=(("「", "」"), ("『", "』"), ("（", "）")),
+        spaced=False,
+    ),
+}
+
+
+class Generator:
+    """Draws sentences for a ``GeneratorConfig``.
+
+    Raises:
+        ValueError: If the configuration names a language without a
+            vocabulary or has no positive language weight.
+    """
+
+    def __init__(self, config: GeneratorConfig) -> None:
+        unknown = sorted(set(config.languages) - VOCABULARIES.keys())
+        if unknown:
+            raise ValueError(
+                f"no vocabulary for {', '.join(unknown)} "
+                f"(available: {', '.join(VOCABULARIES)})"
+            )
+        if not any(weight > 0 for weight in config.languages.values()):
+            raise ValueError("at least one language needs a positive weight")
+        self.config = config
+        self.random = random.Random(config.seed)
+        self.languages = list(config.languages)
+        self.weights = [config.languages[language] for language in self.languages]
+
+    def length(self) -> int:
+        """Number of words of the next sentence."""
+        config = self.config
+        mu = math.log(config.mean_words) - config.length_spread**2 / 2
+        words = round(self.random.lognormvariate(mu, config.length_spread))
+        return max(config.min_words, 2, min(config.max_words, words))
+
+    def abbreviation_count(self) -> int:
+        """Number of abbreviations in the next sentence."""
+        density = self.config.abbreviation_density
+        return int(density) + (self.random.random() < density % 1)
+
+    def quote_depth(self) -> int:
+        """Nesting depth of the quotation in the next top-level sentence, 0
+        for none."""
+        if self.random.random() < self.config.quote_probability:
+            return self.random.randint(1, max(1, self.config.max_quote_depth))
+        return 0
+
+    def sentence(self, language: str, depth: int, level: int = 0) -> str:
+        """One sentence containing a quotation nested ``depth`` levels deep;
+        ``level`` is the number of quotations already around it."""
+        vocabulary = VOCABULARIES[language]
+        pieces = [self.random.choice(vocabulary.words) for _ in range(self.length())]
+        for _ in range(self.abbreviation_count()):
+            position = self.random.randrange(1, len(pieces))
+            pieces.insert(position, self.random.choice(vocabulary.abbreviations))
+        if depth:
+            position = self.random.randrange(1, len(pieces))
+            pieces.insert(position, self.quotation(language, depth, level))
+        separator = " " if vocabulary.spaced else ""
+        text = separator.join(pieces)
+        return text[0].upper() + text[1:] + self.random.choice(vocabulary.terminators)
+
+    def quotation(self, language: str, depth: int, level: int) -> str:
+        """A quoted span of one or two sentences, the first of which nests
+        the remaining ``depth - 1`` levels."""
+        vocabulary = VOCABULARIES[language]
+        open_, close = vocabulary.quotes[min(level, len(vocabulary.quotes) - 1)]
+        inner = [self.sentence(language, depth - 1, level + 1)]
+        if self.random.random() < 0.5:
+            inner.append(self.sentence(language, 0, level + 1))
+        separator = " " if vocabulary.spaced else ""
+        return open_ + separator.join(inner) + close
+
+    def paragraphs(self) -> list[list[tuple[str, str]]]:
+        """The corpus as paragraphs of ``(language, sentence)`` pairs."""
+        per_paragraph = max(1, self.config.paragraph_sentences)
+        paragraphs: list[list[tuple[str, str]]] = []
+        for index in range(self.config.sentences):
+            if index % per_paragraph == 0:
+                paragraphs.append([])
+            language = self.random.choices(self.languages, self.weights)[0]
+            sentence = self.sentence(language, self.quote_depth())
+            paragraphs[-1].append((language, sentence))
+        return paragraphs
+
+
+def generate(config: GeneratorConfig, name: str = "synthetic") -> Dataset:
+    """Build a synthetic dataset with the gold boundary of every sentence.
+
+    Quotations are part of the sentence that contains them, so the
+    sentences inside them are not gold boundaries. Sentences are joined with
+    a space unless both neighbours are written without spaces, and
+    paragraphs with a blank line. The dataset's language is the one with
+    the largest weight.
+    """
+    parts: list[str] = []
+    boundaries: list[int] = []
+    length = 0
+    previous: str | None = None
+    for paragraph in Generator(config).paragraphs():
+        for index, (language, sentence) in enumerate(paragraph):
+            if parts:
+                if index == 0:
+                    joiner = "\n\n"
+                elif previous in UNSPACED_LANGUAGES and language in UNSPACED_LANGUAGES:
+                    joiner = ""
+                else:
+                    joiner = " "
+                parts.append(joiner)
+                length += len(joiner)
+            parts.append(sentence)
+            length += len(sentence)
+            boundaries.append(length)
+            previous = language
+    language = max(config.languages, key=lambda code: config.languages[code])
+    return Dataset(name, language, "".join(parts), boundaries)
+
+
+# Configurations registered as datasets in ``benchmarks.datasets``
+PRESETS: Final[dict[str, GeneratorConfig]] = {
+    "synthetic-en": GeneratorConfig(sentences=5000),
+    "synthetic-ja": GeneratorConfig(sentences=5000, languages={"ja": 1.0}),
+    "synthetic-mixed": GeneratorConfig(
+        sentences=5000, languages={"en": 0.6, "ja": 0.4}
+    ),
+    "synthetic-stress": GeneratorConfig(
+        sentences=5000,
+        mean_words=30.0,
+        length_spread=0.9,
+        max_words=400,
+        abbreviation_density=1.5,
+        quote_probability=0.5,
+        max_quote_depth=3,
+    ),
+}
+
+
+def parse_languages(value: str) -> dict[str, float]:
+    """Parse ``en=0.7,ja=0.3`` (a bare code has weight 1)."""
+    languages: dict[str, float] = {}
+    for item in value.split(","):
+        code, _, weight = item.partition("=")
+        languages[code.strip()] = float(weight) if weight else 1.0
+    return languages
+
+
+def main(argv: list[str] | None = None) -> int:
+    """Main entry point for the script."""
+    defaults = GeneratorConfig()
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--sentences", type=int, default=defaults.sentences)
+    parser.add_argument(
+        "--languages",
+        type=parse_languages,
+        default=defaults.languages,
+        help="weighted language mix, e.g. en=0.7,ja=0.3 (default en)",
+    )
+    parser.add_argument("--mean-words", type=float, default=defaults.mean_words)
+    parser.add_argument("--length-spread", type=float, default=defaults.length_spread)
+    parser.add_argument(
+        "--abbreviations",
+        type=float,
+        default=defaults.abbreviation_density,
+        help="expected abbreviations per sentence",
+    )
+    parser.add_argument(
+        "--quotes",
+        type=float,
+        default=defaults.quote_probability,
+        help="chance that a sentence contains a quotation",
+    )
+    parser.add_argument("--quote-depth", type=int, default=defaults.max_quote_depth)
+    parser.add_argument("--seed", type=int, default=defaults.seed)
+    parser.add_argument(
+        "--output", type=Path, help="write the text here instead of stdout"
+    )
+    parser.add_argument("--gold", type=Path, help="write the gold boundaries as JSON")
+    args = parser.parse_args(argv)
+
+    config = GeneratorConfig(
+        sentences=args.sentences,
+        languages=args.languages,
+        mean_words=args.mean_words,
+        length_spread=args.length_spread,
+        abbreviation_density=args.abbreviations,
+        quote_probability=args.quotes,
+        max_quote_depth=args.quote_depth,
+        seed=args.seed,
+    )
+    try:
+        dataset = generate(config)
+    except ValueError as e:
+        parser.error(str(e))
+
+    if args.output:
+        args.output.write_text(dataset.text, encoding="utf-8")
+    else:
+        sys.stdout.write(dataset.text + "\n")
+    if args.gold:
+        gold = {"language": dataset.language, "boundaries": dataset.boundaries}
+        args.gold.write_text(json.dumps(gold) + "\n")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
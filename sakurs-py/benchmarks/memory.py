@@ -0,0 +1,177 @@
+#!/usr/bin/env python3
+"""Peak memory and throughput of sakurs per execution mode.
+
+Run from ``sakurs-py`` (Linux or macOS)::
+
+    python -m benchmarks.memory --datasets brown --scale 20 --output memory.json
+
+Each dataset is written to a temporary file and processed in a fresh Python
+process per mode, so the peak resident set size (``ru_maxrss`` of that child)
+belongs to one run only:
+
+- ``sequential`` and ``parallel`` split the whole file at once with the
+  matching ``execution_mode``;
+- ``streaming`` consumes ``sakurs.iter_split`` over the file, which reads it
+  in chunks.
+
+A ``baseline`` process that only imports sakurs is measured too, so reports
+can show memory attributable to processing.
+"""
+
+import argparse
+import json
+import os
+import subprocess
+import sys
+import tempfile
+import time
+from pathlib import Path
+from typing import Any, Final
+
+from benchmarks.datasets import DATASETS, DatasetUnavailableError, load_dataset
+
+# Version of the result file layout
+SCHEMA_VERSION: Final[int] = 1
+
+MODES: Final[tuple[str, ...]] = ("sequential", "parallel", "streaming")
+BASELINE_MODE: Final[str] = "baseline"
+
+DEFAULT_DATASETS: Final[list[str]] = ["brown", "ud-ja-gsd"]
+
+
+def run_worker(mode: str, language: str, path: str) -> None:
+    """Process ``path`` in one mode and print the result as JSON."""
+    import sakurs
+
+    start = time.perf_counter()
+    if mode == BASELINE_MODE:
+        sentences = 0
+    elif mode == "streaming":
+        sentences = sum(1 for _ in sakurs.iter_split(Path(path), language=language))
+    else:
+        splitter = sakurs.load(language, execution_mode=mode)  # type: ignore[arg-type]
+        sentences = len(splitter.split(Path(path)))
+    seconds = time.perf_counter() - start
+    print(json.dumps({"sentences": sentences, "seconds": seconds}))
+
+
+def max_rss_mb(max_rss: int) -> float:
+    """Convert ``ru_maxrss`` to MiB (kilobytes on Linux, bytes on macOS)."""
+    divisor = 1024 * 1024 if sys.platform == "darwin" else 1024
+    return max_rss / divisor
+
+
+def measure(
+    mode: str,
+    language: str,
+    path: Path,
+    worker: tuple[str, ...] = ("benchmarks.memory",),
+) -> dict[str, Any]:
+    """Run one mode in a child process and return its result and peak RSS.
+
+    Args:
+        mode: Execution mode, or ``baseline``.
+        language: Language of the text.
+        path: File to process.
+        worker: Module whose ``--worker`` entry point runs the mode, and
+            extra arguments for it.
+
+    Raises:
+        RuntimeError: If the child process fails.
+    """
+    module, *extra = worker
+    command = [sys.executable, "-m", module, "--worker", mode]
+    command += [language, str(path), *extra]
+    process = subprocess.Popen(command, stdout=subprocess.PIPE, text=True)
+    assert process.stdout is not None
+    with process.stdout:
+        output = process.stdout.read()
+    # Reap the child with wait4 for its own rusage; tell Popen it has exited
+    _, status, usage = os.wait4(process.pid, 0)
+    process.returncode = os.waitstatus_to_exitcode(status)
+    if process.returncode != 0:
+        raise RuntimeError(f"{mode} run exited with status {process.returncode}")
+    result: dict[str, Any] = json.loads(output)
+    result["peak_rss_mb"] = max_rss_mb(usage.ru_maxrss)
+    return result
+
+
+def run(dataset_names: list[str], scale: int) -> dict[str, Any]:
+    """Measure every mode on every available dataset.
+
+    Returns:
+        The result document: ``{"schema_version", "results"}`` where
+        ``results[dataset][mode]`` holds ``peak_rss_mb``, ``seconds``,
+        ``chars_per_second``, ``sentences`` and ``input_mb``.
+    """
+    results: dict[str, dict[str, Any]] = {}
+    for dataset_name in dataset_names:
+        try:
+            dataset = load_dataset(dataset_name)
+        except DatasetUnavailableError as e:
+            print(f"skipping {dataset_name}: {e}", file=sys.stderr)
+            continue
+        text = "\n\n".join([dataset.text] * scale)
+        with tempfile.TemporaryDirectory() as directory:
+            path = Path(directory) / f"{dataset_name}.txt"
+            path.write_text(text, encoding="utf-8")
+            input_mb = path.stat().st_size / (1024 * 1024)
+            for mode in (BASELINE_MODE, *MODES):
+                result = measure(mode, dataset.language, path)
+                seconds = result["seconds"]
+                if mode != BASELINE_MODE:
+                    result["chars_per_second"] = len(text) / seconds if seconds else 0.0
+                result["input_mb"] = input_mb
+                results.setdefault(dataset_name, {})[mode] = result
+    return {"schema_version": SCHEMA_VERSION, "results": results}
+
+
+def format_results(current: dict[str, Any]) -> str:
+    """Render results as a plain-text memory-vs-throughput table."""
+    lines = [
+        f"{'dataset':<14} {'mode':<11} {'input MiB':>9} {'peak MiB':>9} "
+        f"{'over base':>9} {'chars/s':>12}"
+    ]
+    for dataset_name, modes in current["results"].items():
+        base = modes[BASELINE_MODE]["peak_rss_mb"]
+        for mode in MODES:
+            result = modes[mode]
+            lines.append(
+                f"{dataset_name:<14} {mode:<11} {result['input_mb']:>9.1f} "
+                f"{result['peak_rss_mb']:>9.1f} {result['peak_rss_mb'] - base:>9.1f} "
+                f"{result['chars_per_second']:>12,.0f}"
+            )
+    return "\n".join(lines)
+
+
+def main(argv: list[str] | None = None) -> int:
+    """Main entry point for the script."""
+    argv = sys.argv[1:] if argv is None else argv
+    if argv[:1] == ["--worker"]:
+        run_worker(*argv[1:4])
+        return 0
+
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument(
+        "--datasets",
+        default=",".join(DEFAULT_DATASETS),
+        help=f"comma-separated dataset names (available: {', '.join(DATASETS)})",
+    )
+    parser.add_argument(
+        "--scale",
+        type=int,
+        default=1,
+        help="repeat each dataset this many times to enlarge the input",
+    )
+    parser.add_argument("--output", type=Path, help="write results as JSON")
+    args = parser.parse_args(argv)
+
+    current = run(args.datasets.split(","), args.scale)
+    print(format_results(current))
+    if args.output:
+        args.output.write_text(json.dumps(current, indent=2) + "\n")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
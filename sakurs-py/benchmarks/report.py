@@ -0,0 +1,233 @@
+#!/usr/bin/env python3
+"""Markdown or HTML report from accuracy, memory and scaling result files.
+
+Run from ``sakurs-py``::
+
+    python -m benchmarks.report accuracy.json memory.json \\
+        --previous main-accuracy.json main-memory.json --format markdown
+
+Result files written by ``benchmarks.accuracy``, ``benchmarks.memory`` and
+``benchmarks.scaling`` can be mixed in any order; several files of one kind
+are merged. With
+``--previous``, every value is followed by its change since that run.
+"""
+
+import argparse
+import html
+import json
+import math
+import sys
+from collections.abc import Callable
+from pathlib import Path
+from typing import Any, Final
+
+Results = dict[str, dict[str, dict[str, Any]]]
+Table = tuple[list[str], list[list[str]]]
+
+ACCURACY_KIND: Final[str] = "accuracy"
+MEMORY_KIND: Final[str] = "memory"
+SCALING_KIND: Final[str] = "scaling"
+
+
+def result_kind(results: Results) -> str:
+    """Tell accuracy, memory and scaling results apart by their fields."""
+    for entries in results.values():
+        for entry in entries.values():
+            if "peak_rss_mb" in entry:
+                return MEMORY_KIND
+            if "efficiency" in entry:
+                return SCALING_KIND
+            return ACCURACY_KIND
+    return ACCURACY_KIND
+
+
+def load_results(paths: list[Path]) -> dict[str, Results]:
+    """Merge result files into ``{kind: results}``; later files win."""
+    merged: dict[str, Results] = {ACCURACY_KIND: {}, MEMORY_KIND: {}, SCALING_KIND: {}}
+    for path in paths:
+        results: Results = json.loads(path.read_text())["results"]
+        kind = merged[result_kind(results)]
+        for dataset, entries in results.items():
+            kind.setdefault(dataset, {}).update(entries)
+    return merged
+
+
+def _previous_value(
+    previous: Results, dataset: str, name: str, field: str
+) -> float | None:
+    value = previous.get(dataset, {}).get(name, {}).get(field)
+    return value if isinstance(value, int | float) else None
+
+
+def format_score(value: float, before: float | None) -> str:
+    """Format a 0..1 metric, with its change in points."""
+    text = f"{value:.4f}"
+    if before is not None and not math.isnan(value) and not math.isnan(before):
+        text += f" ({value - before:+.4f})"
+    return text
+
+
+def format_throughput(value: float, before: float | None) -> str:
+    """Format characters per second, with its relative change."""
+    text = f"{value:,.0f}"
+    if before:
+        text += f" ({(value - before) / before:+.1%})"
+    return text
+
+
+def format_memory(value: float, before: float | None) -> str:
+    """Format MiB, with its change in MiB."""
+    text = f"{value:.1f}"
+    if before is not None:
+        text += f" ({value - before:+.1f})"
+    return text
+
+
+def format_ratio(value: float, before: float | None) -> str:
+    """Format a speedup or efficiency, with its change."""
+    text = f"{value:.2f}"
+    if before is not None:
+        text += f" ({value - before:+.2f})"
+    return text
+
+
+def build_table(
+    results: Results,
+    previous: Results,
+    key_header: str,
+    columns: list[tuple[str, str, Callable[[float, float | None], str]]],
+) -> Table:
+    """Build one row per dataset and segmenter (or mode).
+
+    Args:
+        results: Current results.
+        previous: Results of the run to compare against (may be empty).
+        key_header: Header of the segmenter or mode column.
+        columns: ``(header, field, formatter)`` for each value column.
+    """
+    headers = ["Dataset", key_header] + [header for header, _, _ in columns]
+    rows = []
+    for dataset, entries in results.items():
+        for name, entry in entries.items():
+            row = [dataset, name]
+            for _, field, formatter in columns:
+                value = entry.get(field)
+                if value is None:
+                    row.append("-")
+                    continue
+                before = _previous_value(previous, dataset, name, field)
+                row.append(formatter(value, before))
+            rows.append(row)
+    return headers, rows
+
+
+def accuracy_table(results: Results, previous: Results) -> Table:
+    """Table of precision, recall, F1 and throughput per segmenter."""
+    return build_table(
+        results,
+        previous,
+        "Segmenter",
+        [
+            ("Precision", "precision", format_score),
+            ("Recall", "recall", format_score),
+            ("F1", "f1", format_score),
+            ("Chars/s", "chars_per_second", format_throughput),
+        ],
+    )
+
+
+def memory_table(results: Results, previous: Results) -> Table:
+    """Table of peak memory and throughput per execution mode."""
+    return build_table(
+        results,
+        previous,
+        "Mode",
+        [
+            ("Peak RSS (MiB)", "peak_rss_mb", format_memory),
+            ("Chars/s", "chars_per_second", format_throughput),
+        ],
+    )
+
+
+def scaling_table(results: Results, previous: Results) -> Table:
+    """Table of throughput, speedup and efficiency per thread count."""
+    return build_table(
+        results,
+        previous,
+        "Threads",
+        [
+            ("Chars/s", "chars_per_second", format_throughput),
+            ("Speedup", "speedup", format_ratio),
+            ("Efficiency", "efficiency", format_ratio),
+        ],
+    )
+
+
+def render_markdown(sections: list[tuple[str, Table]]) -> str:
+    """Render titled tables as Markdown."""
+    lines = ["# Benchmark Report"]
+    for title, (headers, rows) in sections:
+        lines += ["", f"## {title}", ""]
+        lines.append("| " + " | ".join(headers) + " |")
+        lines.append("|" + "|".join("---" for _ in headers) + "|")
+        lines += ["| " + " | ".join(row) + " |" for row in rows]
+    return "\n".join(lines) + "\n"
+
+
+def render_html(sections: list[tuple[str, Table]]) -> str:
+    """Render titled tables as an HTML fragment for PR comments or pages."""
+    lines = ["<h1>Benchmark Report</h1>"]
+    for title, (headers, rows) in sections:
+        lines.append(f"<h2>{html.escape(title)}</h2>")
+        lines.append("<table>")
+        cells = "".join(f"<th>{html.escape(header)}</th>" for header in headers)
+        lines.append(f"<tr>{cells}</tr>")
+        for row in rows:
+            cells = "".join(f"<td>{html.escape(cell)}</td>" for cell in row)
+            lines.append(f"<tr>{cells}</tr>")
+        lines.append("</table>")
+    return "\n".join(lines) + "\n"
+
+
+def generate_report(
+    paths: list[Path], previous_paths: list[Path], output_format: str
+) -> str:
+    """Render the report for result files, compared against previous ones."""
+    current = load_results(paths)
+    previous = load_results(previous_paths)
+    sections: list[tuple[str, Table]] = []
+    if current[ACCURACY_KIND]:
+        table = accuracy_table(current[ACCURACY_KIND], previous[ACCURACY_KIND])
+        sections.append(("Accuracy", table))
+    if current[MEMORY_KIND]:
+        table = memory_table(current[MEMORY_KIND], previous[MEMORY_KIND])
+        sections.append(("Memory", table))
+    if current[SCALING_KIND]:
+        table = scaling_table(current[SCALING_KIND], previous[SCALING_KIND])
+        sections.append(("Thread Scaling", table))
+    if output_format == "html":
+        return render_html(sections)
+    return render_markdown(sections)
+
+
+def main(argv: list[str] | None = None) -> int:
+    """Main entry point for the script."""
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("results", type=Path, nargs="+", help="result JSON files")
+    parser.add_argument(
+        "--previous",
+        type=Path,
+        nargs="*",
+        default=[],
+        help="result files of the run to compare against",
+    )
+    parser.add_argument(
+        "--format", choices=["markdown", "html"], default="markdown", dest="format"
+    )
+    args = parser.parse_args(argv)
+    print(generate_report(args.results, args.previous, args.format), end="")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,243 @@
+#!/usr/bin/env python3
+"""Throughput of sakurs across thread counts.
+
+Run from ``sakurs-py``::
+
+    python -m benchmarks.scaling --datasets synthetic-en --output scaling.json
+    python -m benchmarks.scaling --check scaling.json --tolerance 0.05
+
+Each dataset is split at 1, 2, 4, ... threads up to ``--max-threads``
+(default: the number of CPUs, which is always measured too); the single
+thread run uses the sequential mode, the others the parallel mode. Every
+count is timed ``--repeat`` times and the fastest run kept. Speedup is
+relative to the single thread run and efficiency is speedup per thread, so
+perfect scaling has an efficiency of 1.
+
+With ``--check``, the command exits with status 1 if the efficiency at any
+thread count dropped by more than ``--tolerance`` against the snapshot.
+Snapshots only compare meaningfully on the same machine.
+"""
+
+import argparse
+import json
+import os
+import sys
+import time
+from pathlib import Path
+from typing import Any, Final
+
+from benchmarks.datasets import DATASETS, DatasetUnavailableError, load_dataset
+from benchmarks.report import render_markdown, scaling_table
+
+# Version of the result file layout
+SCHEMA_VERSION: Final[int] = 1
+
+DEFAULT_DATASETS: Final[list[str]] = ["synthetic-en", "synthetic-ja"]
+DEFAULT_SCALE: Final[int] = 20
+DEFAULT_REPEAT: Final[int] = 3
+DEFAULT_TOLERANCE: Final[float] = 0.05
+
+
+def thread_counts(max_threads: int) -> list[int]:
+    """Powers of two below ``max_threads``, then ``max_threads`` itself."""
+    counts = []
+    threads = 1
+    while threads < max_threads:
+        counts.append(threads)
+        threads *= 2
+    counts.append(max(1, max_threads))
+    return counts
+
+
+def thread_efficiency(speedup: float, threads: int) -> float:
+    """Speedup per thread; 1.0 is perfect linear scaling."""
+    return speedup / threads if threads else 0.0
+
+
+def time_split(
+    text: str, language: str, threads: int, chunk_kb: int | None, repeat: int
+) -> tuple[float, int]:
+    """Fastest of ``repeat`` splits of ``text`` and the sentence count."""
+    import sakurs
+
+    mode = "sequential" if threads == 1 else "parallel"
+    splitter = sakurs.load(
+        language, threads=threads, chunk_kb=chunk_kb, execution_mode=mode
+    )
+    best = float("inf")
+    sentences = 0
+    for _ in range(max(1, repeat)):
+        start = time.perf_counter()
+        sentences = len(splitter.split(text))
+        best = min(best, time.perf_counter() - start)
+    return best, sentences
+
+
+def scaling_curve(timings: dict[int, float], chars: int) -> dict[str, dict[str, Any]]:
+    """Result entries keyed by thread count from the seconds per count.
+
+    Each entry holds ``threads``, ``seconds``, ``chars_per_second``,
+    ``speedup`` and ``efficiency``; speedup is relative to the fewest
+    threads measured.
+    """
+    base = timings[min(timings)]
+    curve = {}
+    for threads, seconds in sorted(timings.items()):
+        speedup = base / seconds if seconds else 0.0
+        curve[str(threads)] = {
+            "threads": threads,
+            "seconds": seconds,
+            "chars_per_second": chars / seconds if seconds else 0.0,
+            "speedup": speedup,
+            "efficiency": thread_efficiency(speedup, threads),
+        }
+    return curve
+
+
+def run(
+    dataset_names: list[str],
+    scale: int,
+    max_threads: int,
+    chunk_kb: int | None,
+    repeat: int,
+) -> dict[str, Any]:
+    """Measure every thread count on every available dataset.
+
+    Returns:
+        The result document: ``{"schema_version", "cpus", "results"}`` where
+        ``results[dataset][threads]`` holds the ``scaling_curve`` fields.
+    """
+    results: dict[str, dict[str, Any]] = {}
+    for dataset_name in dataset_names:
+        try:
+            dataset = load_dataset(dataset_name)
+        except DatasetUnavailableError as e:
+            print(f"skipping {dataset_name}: {e}", file=sys.stderr)
+            continue
+        text = "\n\n".join([dataset.text] * scale)
+        timings: dict[int, float] = {}
+        sentences: set[int] = set()
+        for threads in thread_counts(max_threads):
+            seconds, count = time_split(
+                text, dataset.language, threads, chunk_kb, repeat
+            )
+            timings[threads] = seconds
+            sentences.add(count)
+        if len(sentences) > 1:
+            print(
+                f"warning: {dataset_name} sentence counts differ across thread "
+                f"counts: {sorted(sentences)}",
+                file=sys.stderr,
+            )
+        results[dataset_name] = scaling_curve(timings, len(text))
+    return {
+        "schema_version": SCHEMA_VERSION,
+        "cpus": os.cpu_count(),
+        "results": results,
+    }
+
+
+def find_regressions(
+    baseline: dict[str, Any], current: dict[str, Any], tolerance: float
+) -> list[str]:
+    """Describe every thread count whose efficiency dropped by more than
+    ``tolerance``; counts missing from either run are skipped."""
+    regressions = []
+    for dataset_name, counts in baseline["results"].items():
+        for threads, expected in counts.items():
+            actual = current["results"].get(dataset_name, {}).get(threads)
+            if actual is None:
+                continue
+            before, after = expected["efficiency"], actual["efficiency"]
+            if after < before - tolerance:
+                regressions.append(
+                    f"{dataset_name}/{threads} threads: efficiency "
+                    f"{before:.3f} -> {after:.3f} ({after - before:+.3f})"
+                )
+    return regressions
+
+
+def format_results(current: dict[str, Any]) -> str:
+    """Render results as a plain-text scaling table."""
+    lines = [
+        f"{'dataset':<16} {'threads':>7} {'chars/s':>14} {'speedup':>8} "
+        f"{'efficiency':>10}"
+    ]
+    for dataset_name, counts in current["results"].items():
+        for entry in counts.values():
+            lines.append(
+                f"{dataset_name:<16} {entry['threads']:>7} "
+                f"{entry['chars_per_second']:>14,.0f} {entry['speedup']:>8.2f} "
+                f"{entry['efficiency']:>10.2f}"
+            )
+    return "\n".join(lines)
+
+
+def main(argv: list[str] | None = None) -> int:
+    """Main entry point for the script."""
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument(
+        "--datasets",
+        default=",".join(DEFAULT_DATASETS),
+        help=f"comma-separated dataset names (available: {', '.join(DATASETS)})",
+    )
+    parser.add_argument(
+        "--scale",
+        type=int,
+        default=DEFAULT_SCALE,
+        help=f"repeat each dataset this many times (default {DEFAULT_SCALE})",
+    )
+    parser.add_argument(
+        "--max-threads",
+        type=int,
+        default=os.cpu_count() or 1,
+        help="highest thread count to measure (default: number of CPUs)",
+    )
+    parser.add_argument("--chunk-kb", type=int, help="chunk size for parallel runs")
+    parser.add_argument(
+        "--repeat",
+        type=int,
+        default=DEFAULT_REPEAT,
+        help=f"runs per thread count, fastest kept (default {DEFAULT_REPEAT})",
+    )
+    parser.add_argument("--output", type=Path, help="write results as JSON")
+    parser.add_argument("--markdown", type=Path, help="write a Markdown report")
+    parser.add_argument(
+        "--check", type=Path, help="fail if efficiency regresses against this snapshot"
+    )
+    parser.add_argument(
+        "--tolerance",
+        type=float,
+        default=DEFAULT_TOLERANCE,
+        help=f"allowed efficiency drop with --check (default {DEFAULT_TOLERANCE})",
+    )
+    args = parser.parse_args(argv)
+
+    current = run(
+        args.datasets.split(","),
+        args.scale,
+        args.max_threads,
+        args.chunk_kb,
+        args.repeat,
+    )
+    print(format_results(current))
+    if args.output:
+        args.output.write_text(json.dumps(current, indent=2) + "\n")
+    if args.markdown:
+        table = scaling_table(current["results"], {})
+        args.markdown.write_text(render_markdown([("Thread Scaling", table)]))
+
+    if args.check:
+        baseline = json.loads(args.check.read_text())
+        regressions = find_regressions(baseline, current, args.tolerance)
+        if regressions:
+            print("\nScaling regressions:", file=sys.stderr)
+            for regression in regressions:
+                print(f"  {regression}", file=sys.stderr)
+            return 1
+        print(f"\nNo regressions against {args.check} (tolerance {args.tolerance})")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
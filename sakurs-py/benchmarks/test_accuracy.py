@@ -0,0 +1,28 @@
+"""Tests for the accuracy regression gate."""
+
+import math
+
+from benchmarks.accuracy import find_regressions, score_boundaries
+
+
+def test_score_boundaries_ignores_text_end() -> None:
+    precision, recall, f1 = score_boundaries([5, 9, 20], [5, 12, 20], 20)
+    assert (precision, recall, f1) == (0.5, 0.5, 0.5)
+
+
+def test_find_regressions_respects_tolerance() -> None:
+    baseline = {"results": {"ud-en-ewt": {"sakurs": {"precision": 0.9, "f1": 0.9}}}}
+    current = {"results": {"ud-en-ewt": {"sakurs": {"precision": 0.899, "f1": 0.8}}}}
+    regressions = find_regressions(baseline, current, 0.005)
+    assert regressions == ["ud-en-ewt/sakurs: f1 0.9000 -> 0.8000 (-0.1000)"]
+
+
+def test_missing_results_and_nan_are_not_regressions() -> None:
+    baseline = {
+        "results": {
+            "brown": {"sakurs": {"f1": 0.9}},
+            "wikipedia-en": {"sakurs": {"f1": math.nan}},
+        }
+    }
+    current = {"results": {"wikipedia-en": {"sakurs": {"f1": math.nan}}}}
+    assert find_regressions(baseline, current, 0.0) == []
@@ -1,4 +1,4 @@
-"""English sentence segmentation benchmarks comparing sakurs vs PySBD."""
+"""English sentence segmentation benchmarks comparing sakurs vs PySBD, syntok and spaCy."""
 
 from typing import Final
 
@@ -7,6 +7,7 @@ import pytest
 from pytest_benchmark.fixture import BenchmarkFixture
 
 import sakurs
+from benchmarks.baselines import BaselineUnavailableError, Segmenter, load_segmenter
 
 
 @pytest.fixture
@@ -21,6 +22,29 @@ def pysbd_segmenter() -> pysbd.Segmenter:
     return pysbd.Segmenter(language="en", clean=False)
 
 
+def _load_or_skip(name: str) -> Segmenter:
+    """Load an optional English baseline, skipping the test when unavailable."""
+    try:
+        return load_segmenter(name, "en")
+    except BaselineUnavailableError as e:
+        pytest.skip(str(e))
+
+
+@pytest.fixture
+def syntok_segmenter() -> Segmenter:
+    """Create a syntok splitter returning sentences as strings."""
+    return _load_or_skip("syntok")
+
+
+@pytest.fixture(
+    params=["spacy_sentencizer", "spacy_parser"],
+    ids=["spacy_sentencizer", "spacy_parser"],
+)
+def spacy_segmenter(request: pytest.FixtureRequest) -> Segmenter:
+    """Create spaCy's rule-based sentencizer and its parser-based splitter."""
+    return _load_or_skip(request.param)
+
+
 # Benchmark configuration constants
 LARGE_TEXT_ITERATIONS: Final[int] = 1
 LARGE_TEXT_ROUNDS: Final[int] = 3
@@ -72,6 +96,44 @@ class TestEnglishBenchmarks:
 
         return result
 
+    def test_syntok_english_400(
+        self,
+        benchmark: BenchmarkFixture,
+        english_text_400: str,
+        syntok_segmenter: Segmenter,
+    ) -> list[str]:
+        """Benchmark syntok on 400-character English text."""
+        result = benchmark(syntok_segmenter, english_text_400)
+        assert isinstance(result, list)
+        assert len(result) > 0
+
+        # Store segmentation results in benchmark data
+        benchmark.extra_info["segmentation"] = {
+            "sentences": result,
+            "count": len(result),
+        }
+
+        return result
+
+    def test_spacy_english_400(
+        self,
+        benchmark: BenchmarkFixture,
+        english_text_400: str,
+        spacy_segmenter: Segmenter,
+    ) -> list[str]:
+        """Benchmark spaCy on 400-character English text."""
+        result = benchmark(spacy_segmenter, english_text_400)
+        assert isinstance(result, list)
+        assert len(result) > 0
+
+        # Store segmentation results in benchmark data
+        benchmark.extra_info["segmentation"] = {
+            "sentences": result,
+            "count": len(result),
+        }
+
+        return result
+
     def test_sakurs_english_large(
         self,
         benchmark: BenchmarkFixture,
@@ -109,3 +171,39 @@ class TestEnglishBenchmarks:
             iterations=LARGE_TEXT_ITERATIONS,
             rounds=LARGE_TEXT_ROUNDS,
         )
+
+    def test_syntok_english_large(
+        self,
+        benchmark: BenchmarkFixture,
+        english_text_400: str,
+        large_text_multiplier: int,
+        syntok_segmenter: Segmenter,
+    ) -> None:
+        """Benchmark syntok on large English text."""
+        # Create large text by repeating the sample with spaces
+        large_text = self._create_large_text(english_text_400, large_text_multiplier)
+
+        benchmark.pedantic(
+            syntok_segmenter,
+            args=(large_text,),
+            iterations=LARGE_TEXT_ITERATIONS,
+            rounds=LARGE_TEXT_ROUNDS,
+        )
+
+    def test_spacy_english_large(
+        self,
+        benchmark: BenchmarkFixture,
+        english_text_400: str,
+        large_text_multiplier: int,
+        spacy_segmenter: Segmenter,
+    ) -> None:
+        """Benchmark spaCy on large English text."""
+        # Create large text by repeating the sample with spaces
+        large_text = self._create_large_text(english_text_400, large_text_multiplier)
+
+        benchmark.pedantic(
+            spacy_segmenter,
+            args=(large_text,),
+            iterations=LARGE_TEXT_ITERATIONS,
+            rounds=LARGE_TEXT_ROUNDS,
+        )
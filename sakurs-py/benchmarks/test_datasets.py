@@ -0,0 +1,126 @@
+"""Tests for the gold dataset loaders."""
+
+from benchmarks.datasets import (
+    DATASETS,
+    aozora_line_sentences,
+    aozora_paragraphs,
+    boundaries_from_sentences,
+    brown_paragraphs,
+    dataset_from_conllu,
+    wikiextractor_documents,
+)
+
+CONLLU = """\
+# newdoc id = doc1
+# sent_id = 1
+# text = Hello world.
+1	Hello	hello	INTJ	_	_	0	root	_	_
+2	world	world	NOUN	_	_	1	vocative	_	SpaceAfter=No
+3	.	.	PUNCT	_	_	1	punct	_	_
+
+# sent_id = 2
+# text = How are you?
+1	How	how	ADV	_	_	0	root	_	_
+
+# newpar
+# sent_id = 3
+# text = Fine.
+1	Fine	fine	ADJ	_	_	0	root	_	_
+
+"""
+
+
+def test_conllu_gold_boundaries() -> None:
+    dataset = dataset_from_conllu("sample", "en", CONLLU.splitlines(keepends=True))
+    assert dataset.text == "Hello world. How are you?\n\nFine."
+    assert dataset.boundaries == [12, 25, 32]
+    assert dataset.sentences == ["Hello world.", "How are you?", "Fine."]
+
+
+def test_unspaced_languages_join_without_separator() -> None:
+    lines = ["# text = 今日は晴れ。\n", "\n", "# text = 明日は雨。\n", "\n"]
+    dataset = dataset_from_conllu("sample", "ja", lines)
+    assert dataset.text == "今日は晴れ。明日は雨。"
+    assert dataset.boundaries == [6, 11]
+
+
+def test_boundaries_from_sentences_ignores_whitespace() -> None:
+    text = "Hello world.  How are you?\n\nFine."
+    sentences = ["Hello world.", "How are  you?", "Fine."]
+    assert boundaries_from_sentences(text, sentences) == [12, 26, 33]
+
+
+def test_datasets_are_registered() -> None:
+    assert {
+        "ud-en-ewt",
+        "ud-ja-gsd",
+        "brown",
+        "aozora-ja",
+        "wikipedia-en",
+    } <= DATASETS.keys()
+    assert DATASETS["ud-ja-gsd"].language == "ja"
+
+
+def test_brown_paragraphs_are_detokenized() -> None:
+    lines = [
+        "",
+        "\tThe/at jury/nn said/vbd ``/`` it/pps was/bedz over/rp ''/'' ./.",
+        "\tIt/pps ,/, too/ql ,/, ended/vbd (/( briefly/rb )/) ./.",
+        "",
+        "\tNext/ap ./.",
+    ]
+    assert list(brown_paragraphs(lines)) == [
+        ['The jury said "it was over".', "It, too, ended (briefly)."],
+        ["Next."],
+    ]
+
+
+def test_wikiextractor_documents_drop_titles() -> None:
+    lines = [
+        '<doc id="1" url="u" title="Tokyo">\n',
+        "Tokyo\n",
+        "\n",
+        "Tokyo is a city. It is large.\n",
+        "</doc>\n",
+        '<doc id="2" url="u" title="Empty">\n',
+        "Empty\n",
+        "</doc>\n",
+    ]
+    assert list(wikiextractor_documents(lines)) == ["Tokyo is a city. It is large."]
+
+
+AOZORA = """\
+羅生門
+芥川龍之介
+
+-------------------------------------------------------
+【テキスト中に現れる記号について】
+
+《》：ルビ
+（例）下人《げにん》
+-------------------------------------------------------
+
+［＃５字下げ］一［＃「一」は中見出し］
+　ある日の暮方《くれがた》の事である。一人の｜下人《げにん》が、雨やみを待っていた。
+
+「何をしていた。云え！　云わぬと、これだぞ。」
+　下人の行方《ゆくえ》は、誰も知らない※［＃「凵＋土」、第3水準1-14-54］。
+
+底本：「芥川龍之介全集1」ちくま文庫、筑摩書房
+"""
+
+
+def test_aozora_paragraphs_strip_markup() -> None:
+    assert list(aozora_paragraphs(AOZORA.splitlines())) == [
+        ["ある日の暮方の事である。", "一人の下人が、雨やみを待っていた。"],
+        ["「何をしていた。云え！　云わぬと、これだぞ。」"],
+        ["下人の行方は、誰も知らない。"],
+    ]
+
+
+def test_aozora_sentences_end_outside_brackets() -> None:
+    assert aozora_line_sentences("彼は「はい。」と言った。それだけ！？　終わり") == [
+        "彼は「はい。」と言った。",
+        "それだけ！？",
+        "終わり",
+    ]
@@ -0,0 +1,46 @@
+"""Tests for the Wikipedia dump throughput benchmark."""
+
+import gzip
+import io
+import json
+from pathlib import Path
+
+from benchmarks.dumps import dump_documents, open_dump, window_rates, write_documents
+
+
+def cirrus_lines(*texts: str) -> list[str]:
+    lines = []
+    for index, text in enumerate(texts):
+        lines.append(json.dumps({"index": {"_id": str(index)}}) + "\n")
+        lines.append(json.dumps({"title": f"Page {index}", "text": text}) + "\n")
+    return lines
+
+
+def test_dump_documents_reads_cirrus_and_wikiextractor() -> None:
+    cirrus = ["\n", *cirrus_lines("Tokyo is a city. It is large.", " ")]
+    assert list(dump_documents(cirrus)) == ["Tokyo is a city. It is large."]
+    wikiextractor = ['<doc id="1" url="u" title="T">\n', "T\n", "Body.\n", "</doc>\n"]
+    assert list(dump_documents(wikiextractor)) == ["Body."]
+    assert list(dump_documents([])) == []
+
+
+def test_write_documents_stops_after_the_limit() -> None:
+    output = io.StringIO()
+    count, written = write_documents(iter(["Één.", "Two.", "Three."]), output, 7)
+    assert output.getvalue() == "Één.\n\nTwo."
+    assert (count, written) == (2, len("Één.\n\nTwo.".encode()))
+
+
+def test_open_dump_decompresses_by_suffix(tmp_path: Path) -> None:
+    path = tmp_path / "enwiki-cirrussearch-content.json.gz"
+    with gzip.open(path, "wt", encoding="utf-8") as f:
+        f.writelines(cirrus_lines("One. Two."))
+    with open_dump(path) as dump:
+        assert list(dump_documents(dump)) == ["One. Two."]
+
+
+def test_window_rates() -> None:
+    assert window_rates([(0, 0.0), (100, 1.0), (300, 2.0), (300, 2.0)]) == [
+        100.0,
+        200.0,
+    ]
@@ -0,0 +1,63 @@
+"""Tests for the synthetic corpus generator."""
+
+import pytest
+
+from benchmarks.datasets import load_dataset
+from benchmarks.generator import GeneratorConfig, generate, parse_languages
+
+
+def test_generation_is_deterministic() -> None:
+    config = GeneratorConfig(sentences=50, seed=7)
+    assert generate(config).text == generate(config).text
+    assert generate(config).text != generate(GeneratorConfig(sentences=50)).text
+
+
+def test_gold_boundaries_end_every_sentence() -> None:
+    dataset = generate(GeneratorConfig(sentences=40, paragraph_sentences=10))
+    assert len(dataset.boundaries) == 40
+    assert dataset.boundaries[-1] == len(dataset.text)
+    for boundary in dataset.boundaries:
+        assert dataset.text[boundary - 1] in ".?!"
+    assert dataset.text.count("\n\n") == 3
+
+
+def test_quotations_nest_inside_one_sentence() -> None:
+    config = GeneratorConfig(
+        sentences=20, quote_probability=1.0, max_quote_depth=3, seed=3
+    )
+    dataset = generate(config)
+    for sentence in dataset.sentences:
+        assert sentence.count('"') % 2 == 0
+        assert sentence.count("(") == sentence.count(")")
+    assert "(" in dataset.text
+    # Sentences inside quotations are not gold boundaries
+    assert dataset.text.count(".") > len(dataset.boundaries)
+
+
+def test_abbreviation_density_and_languages() -> None:
+    config = GeneratorConfig(
+        sentences=30, languages={"ja": 1.0}, abbreviation_density=2.0
+    )
+    dataset = generate(config)
+    assert dataset.language == "ja"
+    assert " " not in dataset.text.replace("\n\n", "")
+    for sentence in dataset.sentences:
+        assert sentence.count(".") >= 2
+
+
+def test_language_mix() -> None:
+    config = GeneratorConfig(sentences=200, languages=parse_languages("en=1,ja=1"))
+    text = generate(config).text
+    assert "。" in text or "？" in text
+    assert " the " in text
+
+
+def test_unknown_language_is_rejected() -> None:
+    with pytest.raises(ValueError, match="no vocabulary for xx"):
+        generate(GeneratorConfig(languages={"xx": 1.0}))
+
+
+def test_presets_are_registered_datasets() -> None:
+    dataset = load_dataset("synthetic-stress")
+    assert dataset.has_gold
+    assert dataset.name == "synthetic-stress"
@@ -0,0 +1,44 @@
+"""Tests for the benchmark report generator."""
+
+import json
+from pathlib import Path
+
+from benchmarks.report import generate_report
+
+
+def _write(path: Path, results: dict[str, object]) -> Path:
+    path.write_text(json.dumps({"schema_version": 1, "results": results}))
+    return path
+
+
+def test_markdown_report_with_deltas(tmp_path: Path) -> None:
+    score = {"precision": 0.9, "recall": 0.8, "f1": 0.85, "chars_per_second": 2000}
+    previous = {**score, "f1": 0.8, "chars_per_second": 1000}
+    memory = {"sequential": {"peak_rss_mb": 120.0, "chars_per_second": 500}}
+    current = [
+        _write(tmp_path / "accuracy.json", {"brown": {"sakurs": score}}),
+        _write(tmp_path / "memory.json", {"brown": memory}),
+    ]
+    before = [_write(tmp_path / "previous.json", {"brown": {"sakurs": previous}})]
+
+    report = generate_report(current, before, "markdown")
+    assert "| brown | sakurs | 0.9000 (+0.0000) | 0.8000 (+0.0000) | " in report
+    assert "0.8500 (+0.0500) | 2,000 (+100.0%) |" in report
+    assert "| brown | sequential | 120.0 | 500 |" in report
+
+
+def test_html_report_escapes_names(tmp_path: Path) -> None:
+    memory = {"<b>": {"baseline": {"peak_rss_mb": 40.0}}}
+    report = generate_report([_write(tmp_path / "m.json", memory)], [], "html")
+    assert "<td>&lt;b&gt;</td><td>baseline</td><td>40.0</td><td>-</td>" in report
+
+
+def test_scaling_report(tmp_path: Path) -> None:
+    entry = {"threads": 4, "chars_per_second": 4000, "speedup": 3.2, "efficiency": 0.8}
+    previous = {**entry, "efficiency": 0.9}
+    current = [_write(tmp_path / "scaling.json", {"brown": {"4": entry}})]
+    before = [_write(tmp_path / "previous.json", {"brown": {"4": previous}})]
+
+    report = generate_report(current, before, "markdown")
+    assert "## Thread Scaling" in report
+    assert "| brown | 4 | 4,000 (+0.0%) | 3.20 (+0.00) | 0.80 (-0.10) |" in report
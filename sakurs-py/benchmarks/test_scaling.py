@@ -0,0 +1,26 @@
+"""Tests for the thread scaling report."""
+
+from benchmarks.scaling import find_regressions, scaling_curve, thread_counts
+
+
+def test_thread_counts_double_up_to_the_maximum() -> None:
+    assert thread_counts(1) == [1]
+    assert thread_counts(8) == [1, 2, 4, 8]
+    assert thread_counts(6) == [1, 2, 4, 6]
+
+
+def test_scaling_curve_speedup_and_efficiency() -> None:
+    curve = scaling_curve({1: 8.0, 2: 4.0, 4: 4.0}, 800)
+    assert list(curve) == ["1", "2", "4"]
+    assert curve["1"]["chars_per_second"] == 100.0
+    assert (curve["2"]["speedup"], curve["2"]["efficiency"]) == (2.0, 1.0)
+    assert (curve["4"]["speedup"], curve["4"]["efficiency"]) == (2.0, 0.5)
+
+
+def test_find_regressions_compares_efficiency() -> None:
+    before = {"2": {"efficiency": 0.9}, "4": {"efficiency": 0.8}}
+    after = {"2": {"efficiency": 0.88}, "4": {"efficiency": 0.6}}
+    baseline, current = {"results": {"brown": before}}, {"results": {"brown": after}}
+    assert find_regressions(baseline, current, 0.05) == [
+        "brown/4 threads: efficiency 0.800 -> 0.600 (-0.200)"
+    ]
@@ -1,44 +1,125 @@
-"""Type stubs for sakurs Python bindings."""
+"""Type stubs for sakurs Python bindings.
 
+Generated from the compiled module by `cargo run -p sakurs-py --features
+stub-gen --bin stub_gen`; do not edit by hand.
+"""
+
+from collections.abc import Awaitable, Iterable
+from mmap import mmap
+from os import PathLike
 from pathlib import Path
-from typing import Any, BinaryIO, Literal, Protocol, TextIO, overload
+from typing import Any, BinaryIO, Literal, Protocol, TextIO, TypeAlias, overload
 
 class FileProtocol(Protocol):
     """Protocol for file-like objects with read() method."""
     def read(self, size: int = -1) -> str | bytes: ...
 
+_Input: TypeAlias = (
+    str
+    | bytes
+    | bytearray
+    | memoryview
+    | mmap
+    | PathLike[str]
+    | TextIO
+    | BinaryIO
+    | FileProtocol
+)
+
 __version__: str
 
 # Exception types
 class SakursError(Exception):
     """Base exception for all sakurs errors."""
 
-    ...
-
 class InvalidLanguageError(SakursError):
     """Raised when language code is not recognized."""
 
-    ...
-
 class ProcessingError(SakursError):
     """Raised when text processing fails."""
 
-    ...
-
 class ConfigurationError(SakursError):
     """Raised when configuration is invalid."""
 
-    ...
+# Classes
+class SentenceSplitter:
+    """Main sentence splitter class for sentence boundary detection"""
+
+    def __init__(
+        self,
+        *,
+        language: str | None = None,
+        language_config: LanguageConfig | None = None,
+        threads: int | None = None,
+        chunk_kb: int | None = None,
+        execution_mode: Literal["sequential", "parallel", "adaptive"] = "adaptive",
+        streaming: bool = False,
+        stream_chunk_mb: int = 10,
+        token_estimator: Literal["whitespace", "bpe"] | None = None,
+    ) -> None: ...
+    def __repr__(self) -> str: ...
+    @overload
+    def split(
+        self,
+        input: _Input,
+        *,
+        return_details: Literal[False] = False,
+        encoding: str = "utf-8",
+    ) -> list[str]: ...
+    @overload
+    def split(
+        self,
+        input: _Input,
+        *,
+        return_details: Literal[True],
+        encoding: str = "utf-8",
+    ) -> list[Sentence]: ...
+    @overload
+    def split_series(
+        self,
+        column: Iterable[str | None],
+        *,
+        return_arrow: Literal[False] = False,
+    ) -> list[list[str] | None]: ...
+    @overload
+    def split_series(
+        self,
+        column: Iterable[str | None],
+        *,
+        return_arrow: Literal[True],
+    ) -> Any: ...
+    def iter_split(
+        self,
+        input: _Input,
+        *,
+        encoding: str = "utf-8",
+        preserve_whitespace: bool = False,
+    ) -> SentenceIterator:
+        """Iterate over sentences (memory-efficient)"""
+    def __enter__(self) -> SentenceSplitter: ...
+    def __exit__(
+        self,
+        _exc_type: type[BaseException] | None,
+        _exc_value: BaseException | None,
+        _traceback: object | None,
+    ) -> bool: ...
+    @property
+    def language(self) -> str: ...
+    @property
+    def last_metadata(self) -> ProcessingMetadata | None: ...
+    @property
+    def supports_parallel(self) -> bool: ...
 
-# Output types
 class Sentence:
-    """Sentence with metadata."""
+    """Sentence with metadata"""
 
     text: str
     start: int
     end: int
     confidence: float
     metadata: dict[str, Any]
+    kind: Literal["strong", "weak", "forced", "end_of_text"]
+    token_estimate: int | None
 
     def __init__(
         self,
@@ -47,18 +128,24 @@ class Sentence:
         end: int,
         confidence: float = 1.0,
         metadata: dict[str, Any] | None = None,
+        kind: Literal["strong", "weak", "forced", "end_of_text"] = "weak",
+        token_estimate: int | None = None,
     ) -> None: ...
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
 
 class ProcessingMetadata:
-    """Processing statistics and information."""
+    """Processing statistics and metadata"""
 
     total_sentences: int
     processing_time_ms: float
     threads_used: int
     chunk_kb_used: int
     execution_mode_used: str
+    execution_reason: str
+    bytes_per_thread: int
+    available_threads: int | None
+    warnings: list[TextWarning]
 
     def __init__(
         self,
@@ -67,12 +154,115 @@ class ProcessingMetadata:
         threads_used: int,
         chunk_kb_used: int,
         execution_mode_used: str,
+        execution_reason: str = ...,
+        bytes_per_thread: int = 0,
+        available_threads: int | None = None,
+    ) -> None: ...
+    def __repr__(self) -> str: ...
+
+class TextWarning:
+    """
+    A problem noticed in the text, such as a suspiciously long sentence or
+    invalid UTF-8
+    """
+
+    kind: Literal["unclosed_enclosure", "unmatched_closer", "depth_exceeded", "long_sentence", "forced_split", "invalid_utf8"]
+    offset: int
+    message: str
+
+    def __repr__(self) -> str: ...
+    def __str__(self) -> str: ...
+
+class SentenceIterator:
+    """
+    Python iterator for streaming sentences
+
+    Reads its source in bounded pieces and feeds them to the core stream, so
+    memory stays proportional to the piece size plus the longest sentence
+    rather than the whole input.
+    """
+
+    def __iter__(self) -> SentenceIterator: ...
+    def __next__(self) -> str: ...
+    def close(self) -> None:
+        """
+        Stop iterating and release the input
+
+        A file opened from a path is closed right away, and later calls to
+        next() raise StopIteration. File-like objects passed in are left open
+        for their owner to close. Closing twice does nothing.
+        """
+    def __enter__(self) -> SentenceIterator: ...
+    def __exit__(
+        self,
+        _exc_type: type[BaseException] | None,
+        _exc_value: BaseException | None,
+        _traceback: object | None,
+    ) -> bool: ...
+
+class AsyncSentenceIterator:
+    """
+    Async iterator over sentences, returned by `aiter_split`
+
+    A background thread reads and splits the input, a bounded number of
+    sentences ahead of the consumer.
+    """
+
+    def __aiter__(self) -> AsyncSentenceIterator: ...
+    def __anext__(self) -> Awaitable[str]: ...
+
+class LargeFileIterator:
+    """Iterator for memory-efficient large file processing"""
+
+    def __iter__(self) -> LargeFileIterator: ...
+    def __next__(self) -> str: ...
+    def close(self) -> None:
+        """
+        Stop iterating and close the file
+
+        Later calls to next() raise StopIteration. Closing twice does
+        nothing.
+        """
+    def __enter__(self) -> LargeFileIterator: ...
+    def __exit__(
+        self,
+        _exc_type: type[BaseException] | None,
+        _exc_value: BaseException | None,
+        _traceback: object | None,
+    ) -> bool: ...
+
+class LanguageConfig:
+    """Complete language configuration"""
+
+    metadata: MetadataConfig
+    terminators: TerminatorConfig
+    ellipsis: EllipsisConfig
+    enclosures: EnclosureConfig
+    suppression: SuppressionConfig
+    abbreviations: AbbreviationConfig
+    sentence_starters: SentenceStarterConfig | None
+    hypothesis: HypothesisConfig | None
+
+    def __init__(
+        self,
+        metadata: MetadataConfig,
+        terminators: TerminatorConfig,
+        ellipsis: EllipsisConfig,
+        enclosures: EnclosureConfig,
+        suppression: SuppressionConfig,
+        abbreviations: AbbreviationConfig,
+        sentence_starters: SentenceStarterConfig | None = None,
+        hypothesis: HypothesisConfig | None = None,
     ) -> None: ...
     def __repr__(self) -> str: ...
+    @classmethod
+    def from_toml(cls, path: Path | str) -> LanguageConfig:
+        """Load configuration from TOML file"""
+    def to_toml(self, path: Path | str) -> None:
+        """Save configuration to TOML file"""
 
-# Language Configuration Classes
 class MetadataConfig:
-    """Metadata configuration for a language."""
+    """Metadata configuration for a language"""
 
     code: str
     name: str
@@ -80,8 +270,27 @@ class MetadataConfig:
     def __init__(self, code: str, name: str) -> None: ...
     def __repr__(self) -> str: ...
 
+class TerminatorConfig:
+    """Terminator configuration"""
+
+    chars: list[str]
+    patterns: list[TerminatorPattern]
+    ordinal_indicators: list[str]
+    collapse_runs: bool
+    emoji: bool
+
+    def __init__(
+        self,
+        chars: list[str],
+        patterns: list[TerminatorPattern] | None = ...,
+        ordinal_indicators: list[str] | None = ...,
+        collapse_runs: bool = True,
+        emoji: bool = False,
+    ) -> None: ...
+    def __repr__(self) -> str: ...
+
 class TerminatorPattern:
-    """Terminator pattern configuration."""
+    """Terminator pattern configuration"""
 
     pattern: str
     name: str
@@ -89,19 +298,25 @@ class TerminatorPattern:
     def __init__(self, pattern: str, name: str) -> None: ...
     def __repr__(self) -> str: ...
 
-class TerminatorConfig:
-    """Terminator configuration."""
+class EllipsisConfig:
+    """Ellipsis configuration"""
 
-    chars: list[str]
-    patterns: list[TerminatorPattern]
+    treat_as_boundary: bool
+    patterns: list[str]
+    context_rules: list[ContextRule]
+    exceptions: list[ExceptionPattern]
 
     def __init__(
-        self, chars: list[str], patterns: list[TerminatorPattern] | None = None
+        self,
+        treat_as_boundary: bool = True,
+        patterns: list[str] | None = ...,
+        context_rules: list[ContextRule] | None = ...,
+        exceptions: list[ExceptionPattern] | None = ...,
     ) -> None: ...
     def __repr__(self) -> str: ...
 
 class ContextRule:
-    """Context rule for ellipsis handling."""
+    """Context rule for ellipsis handling"""
 
     condition: str
     boundary: bool
@@ -110,7 +325,7 @@ class ContextRule:
     def __repr__(self) -> str: ...
 
 class ExceptionPattern:
-    """Exception pattern for ellipsis handling."""
+    """Exception pattern for ellipsis handling"""
 
     regex: str
     boundary: bool
@@ -118,43 +333,54 @@ class ExceptionPattern:
     def __init__(self, regex: str, boundary: bool) -> None: ...
     def __repr__(self) -> str: ...
 
-class EllipsisConfig:
-    """Ellipsis configuration."""
+class EnclosureConfig:
+    """Enclosure configuration"""
 
-    treat_as_boundary: bool
-    patterns: list[str]
-    context_rules: list[ContextRule]
-    exceptions: list[ExceptionPattern]
+    pairs: list[EnclosurePair]
 
-    def __init__(
-        self,
-        treat_as_boundary: bool = True,
-        patterns: list[str] | None = None,
-        context_rules: list[ContextRule] | None = None,
-        exceptions: list[ExceptionPattern] | None = None,
-    ) -> None: ...
+    def __init__(self, pairs: list[EnclosurePair]) -> None: ...
     def __repr__(self) -> str: ...
 
 class EnclosurePair:
-    """Enclosure pair configuration."""
+    """Enclosure pair configuration"""
 
     open: str
     close: str
     symmetric: bool
+    boundaries: Literal["suppress", "allow", "strong_only", "dialogue"]
 
-    def __init__(self, open: str, close: str, symmetric: bool = False) -> None: ...
+    def __init__(
+        self,
+        open: str,
+        close: str,
+        symmetric: bool = False,
+        boundaries: Literal["suppress", "allow", "strong_only", "dialogue"] = ...,
+    ) -> None: ...
     def __repr__(self) -> str: ...
 
-class EnclosureConfig:
-    """Enclosure configuration."""
+class SuppressionConfig:
+    """Suppression configuration"""
 
-    pairs: list[EnclosurePair]
+    fast_patterns: list[FastPattern]
+    regex_patterns: list[RegexPattern]
+    trailing_patterns: list[str]
+    web_tokens: bool
+    citations: bool
+    apostrophes: ApostropheConfig | None
 
-    def __init__(self, pairs: list[EnclosurePair]) -> None: ...
+    def __init__(
+        self,
+        fast_patterns: list[FastPattern] | None = ...,
+        regex_patterns: list[RegexPattern] | None = ...,
+        trailing_patterns: list[str] | None = ...,
+        web_tokens: bool = False,
+        citations: bool = False,
+        apostrophes: ApostropheConfig | None = None,
+    ) -> None: ...
     def __repr__(self) -> str: ...
 
 class FastPattern:
-    """Fast pattern for suppression."""
+    """Fast pattern for suppression"""
 
     char: str
     line_start: bool
@@ -171,7 +397,7 @@ class FastPattern:
     def __repr__(self) -> str: ...
 
 class RegexPattern:
-    """Regex pattern for suppression."""
+    """Regex pattern for suppression"""
 
     pattern: str
     description: str | None
@@ -179,136 +405,82 @@ class RegexPattern:
     def __init__(self, pattern: str, description: str | None = None) -> None: ...
     def __repr__(self) -> str: ...
 
-class SuppressionConfig:
-    """Suppression configuration."""
+class ApostropheConfig:
+    """Apostrophes that are not quotation marks"""
 
-    fast_patterns: list[FastPattern]
-    regex_patterns: list[RegexPattern]
+    chars: list[str]
+    contractions: bool
+    possessives: bool
+    years: bool
+    elided_starts: list[str]
+    elided_ends: list[str]
 
     def __init__(
         self,
-        fast_patterns: list[FastPattern] | None = None,
-        regex_patterns: list[RegexPattern] | None = None,
+        chars: list[str] | None = ...,
+        contractions: bool = True,
+        possessives: bool = True,
+        years: bool = True,
+        elided_starts: list[str] | None = ...,
+        elided_ends: list[str] | None = ...,
     ) -> None: ...
     def __repr__(self) -> str: ...
 
 class AbbreviationConfig:
-    """Abbreviation configuration."""
+    """Abbreviation configuration"""
 
-    categories: dict[str, list[str]]
+    categories: dict[str, list[str | dict[str, Any]]]
 
-    def __init__(self, **kwargs: list[str]) -> None: ...
+    def __init__(self, **kwargs: list[str | dict[str, Any]]) -> None: ...
     def __repr__(self) -> str: ...
-    def __getitem__(self, key: str) -> list[str]: ...
-    def __setitem__(self, key: str, value: list[str]) -> None: ...
+    def __getitem__(self, key: str, /) -> list[str | dict[str, Any]]: ...
+    def __setitem__(self, key: str, value: list[str | dict[str, Any]], /) -> None: ...
 
 class SentenceStarterConfig:
-    """Sentence starter configuration."""
+    """Sentence starter configuration"""
 
-    categories: dict[str, list[str]]
+    categories: dict[str, list[str | dict[str, Any]]]
     require_following_space: bool
     min_word_length: int
+    threshold: float
+    uppercase_fallback: float | None
 
     def __init__(
         self,
         require_following_space: bool = True,
         min_word_length: int = 1,
-        **kwargs: list[str],
+        threshold: float = 0.5,
+        uppercase_fallback: float | None = None,
+        **kwargs: list[str | dict[str, Any]],
     ) -> None: ...
     def __repr__(self) -> str: ...
 
-class SentenceIterator:
-    """Iterator for streaming sentence processing."""
+class HypothesisConfig:
+    """Boundary hypothesis strategy configuration"""
 
-    def __iter__(self) -> SentenceIterator: ...
-    def __next__(self) -> str: ...
-
-class LargeFileIterator:
-    """Iterator for memory-efficient large file processing."""
-
-    def __iter__(self) -> LargeFileIterator: ...
-    def __next__(self) -> str: ...
-
-class LanguageConfig:
-    """Complete language configuration."""
-
-    metadata: MetadataConfig
-    terminators: TerminatorConfig
-    ellipsis: EllipsisConfig
-    enclosures: EnclosureConfig
-    suppression: SuppressionConfig
-    abbreviations: AbbreviationConfig
-    sentence_starters: SentenceStarterConfig | None
+    strategy: str
+    min_chars: int
+    sentence_final_particles: list[str]
+    discourse_markers: list[str]
+    continuation_markers: list[str]
 
     def __init__(
         self,
-        metadata: MetadataConfig,
-        terminators: TerminatorConfig,
-        ellipsis: EllipsisConfig,
-        enclosures: EnclosureConfig,
-        suppression: SuppressionConfig,
-        abbreviations: AbbreviationConfig,
-        sentence_starters: SentenceStarterConfig | None = None,
+        strategy: str,
+        min_chars: int = 8,
+        sentence_final_particles: list[str] | None = ...,
+        discourse_markers: list[str] | None = ...,
+        continuation_markers: list[str] | None = ...,
     ) -> None: ...
-    @classmethod
-    def from_toml(cls, path: Path | str) -> LanguageConfig: ...
-    def to_toml(self, path: Path | str) -> None: ...
     def __repr__(self) -> str: ...
 
-class SentenceSplitter:
-    """Main sentence splitter for sentence boundary detection."""
-
-    def __init__(
-        self,
-        *,
-        language: str | None = None,
-        language_config: LanguageConfig | None = None,
-        threads: int | None = None,
-        chunk_kb: int | None = None,
-        execution_mode: Literal["sequential", "parallel", "adaptive"] = "adaptive",
-        streaming: bool = False,
-        stream_chunk_mb: int = 10,
-    ) -> None: ...
-    @overload
-    def split(
-        self,
-        input: str | bytes | Path | TextIO | BinaryIO | FileProtocol,
-        *,
-        return_details: Literal[False] = False,
-        encoding: str = "utf-8",
-    ) -> list[str]: ...
-    @overload
-    def split(
-        self,
-        input: str | bytes | Path | TextIO | BinaryIO | FileProtocol,
-        *,
-        return_details: Literal[True],
-        encoding: str = "utf-8",
-    ) -> list[Sentence]: ...
-    def iter_split(
-        self,
-        input: str | bytes | Path | TextIO | BinaryIO | FileProtocol,
-        *,
-        encoding: str = "utf-8",
-        preserve_whitespace: bool = False,
-    ) -> SentenceIterator: ...
-    @property
-    def language(self) -> str: ...
-    @property
-    def supports_parallel(self) -> bool: ...
-    def __enter__(self) -> SentenceSplitter: ...
-    def __exit__(
-        self,
-        exc_type: type[BaseException] | None,
-        exc_val: BaseException | None,
-        exc_tb: object | None,
-    ) -> bool: ...
-    def __repr__(self) -> str: ...
+# Aliases
+Processor = SentenceSplitter
 
-# Main API functions
+# Functions
 @overload
 def split(
-    input: str | bytes | Path | TextIO | BinaryIO | FileProtocol,
+    input: _Input,
     *,
     language: str | None = None,
     language_config: LanguageConfig | None = None,
@@ -319,10 +491,12 @@ def split(
     return_details: Literal[False] = False,
     preserve_whitespace: bool = False,
     encoding: str = "utf-8",
+    token_estimator: Literal["whitespace", "bpe"] | None = None,
+    return_offsets: None = None,
 ) -> list[str]: ...
 @overload
 def split(
-    input: str | bytes | Path | TextIO | BinaryIO | FileProtocol,
+    input: _Input,
     *,
     language: str | None = None,
     language_config: LanguageConfig | None = None,
@@ -333,7 +507,73 @@ def split(
     return_details: Literal[True],
     preserve_whitespace: bool = False,
     encoding: str = "utf-8",
+    token_estimator: Literal["whitespace", "bpe"] | None = None,
+    return_offsets: None = None,
 ) -> list[Sentence]: ...
+@overload
+def split(
+    input: _Input,
+    *,
+    language: str | None = None,
+    language_config: LanguageConfig | None = None,
+    threads: int | None = None,
+    chunk_kb: int | None = None,
+    parallel: bool = False,
+    execution_mode: Literal["sequential", "parallel", "adaptive"] = "adaptive",
+    return_details: Literal[False] = False,
+    preserve_whitespace: bool = False,
+    encoding: str = "utf-8",
+    token_estimator: Literal["whitespace", "bpe"] | None = None,
+    return_offsets: Literal["numpy"],
+) -> tuple[Any, Any]: ...
+@overload
+def asplit(
+    input: _Input,
+    *,
+    language: str | None = None,
+    language_config: LanguageConfig | None = None,
+    threads: int | None = None,
+    chunk_kb: int | None = None,
+    parallel: bool = False,
+    execution_mode: Literal["sequential", "parallel", "adaptive"] = "adaptive",
+    return_details: Literal[False] = False,
+    preserve_whitespace: bool = False,
+    encoding: str = "utf-8",
+    token_estimator: Literal["whitespace", "bpe"] | None = None,
+    return_offsets: None = None,
+) -> Awaitable[list[str]]: ...
+@overload
+def asplit(
+    input: _Input,
+    *,
+    language: str | None = None,
+    language_config: LanguageConfig | None = None,
+    threads: int | None = None,
+    chunk_kb: int | None = None,
+    parallel: bool = False,
+    execution_mode: Literal["sequential", "parallel", "adaptive"] = "adaptive",
+    return_details: Literal[True],
+    preserve_whitespace: bool = False,
+    encoding: str = "utf-8",
+    token_estimator: Literal["whitespace", "bpe"] | None = None,
+    return_offsets: None = None,
+) -> Awaitable[list[Sentence]]: ...
+@overload
+def asplit(
+    input: _Input,
+    *,
+    language: str | None = None,
+    language_config: LanguageConfig | None = None,
+    threads: int | None = None,
+    chunk_kb: int | None = None,
+    parallel: bool = False,
+    execution_mode: Literal["sequential", "parallel", "adaptive"] = "adaptive",
+    return_details: Literal[False] = False,
+    preserve_whitespace: bool = False,
+    encoding: str = "utf-8",
+    token_estimator: Literal["whitespace", "bpe"] | None = None,
+    return_offsets: Literal["numpy"],
+) -> Awaitable[tuple[Any, Any]]: ...
 def load(
     language: str,
     *,
@@ -341,11 +581,9 @@ def load(
     chunk_kb: int | None = None,
     execution_mode: Literal["sequential", "parallel", "adaptive"] = "adaptive",
 ) -> SentenceSplitter:
-    """Load a sentence splitter for a specific language."""
-    ...
-
+    """Load a sentence splitter for the specified language (spaCy-style API)"""
 def iter_split(
-    input: str | bytes | Path | TextIO | BinaryIO | FileProtocol,
+    input: _Input,
     *,
     language: str | None = None,
     language_config: LanguageConfig | None = None,
@@ -354,16 +592,46 @@ def iter_split(
     encoding: str = "utf-8",
 ) -> SentenceIterator:
     """
-    Process input and return sentences as an iterator.
-
-    This function loads the entire input into memory but returns results
-    incrementally for responsive processing. For true memory-efficient
-    streaming of large files, use split_large_file().
+    Process input and return sentences as an iterator
+
+    Files and file-like objects are read in chunks of `chunk_kb` as the
+    iterator advances, so memory stays proportional to the chunk size rather
+    than the input size. Results are identical to split().
+
+    Args:
+        input: Text string, file path (str or os.PathLike), bytes-like object
+            (bytes, bytearray, memoryview, mmap), or file-like object
+        language: Language code ("en", "ja", "pt", "it", "ar", "th", "hi") for built-in rules (default: "en")
+        language_config: Custom language configuration
+        threads: Number of threads for parallel processing (None for auto)
+        chunk_kb: Chunk size in KB for reading and processing (default: 256)
+        encoding: Text encoding for file and bytes inputs; text-mode file
+            objects are decoded by the file itself (default: "utf-8")
+
+    Returns:
+        Iterator that yields sentences one at a time
     """
-    ...
+def aiter_split(
+    input: _Input,
+    *,
+    language: str | None = None,
+    language_config: LanguageConfig | None = None,
+    threads: int | None = None,
+    chunk_kb: int | None = None,
+    encoding: str = "utf-8",
+) -> AsyncSentenceIterator:
+    """
+    Process input and return sentences as an async iterator
+
+    Takes the same arguments as `iter_split`. The input is read and split on
+    a background thread, a bounded number of sentences ahead of the
+    consumer, so `async for` never blocks the event loop.
 
+    Returns:
+        Async iterator that yields sentences one at a time
+    """
 def split_large_file(
-    file_path: str | Path,
+    file_path: str | PathLike[str],
     *,
     language: str | None = None,
     language_config: LanguageConfig | None = None,
@@ -372,15 +640,72 @@ def split_large_file(
     encoding: str = "utf-8",
 ) -> LargeFileIterator:
     """
-    Process large files with limited memory usage.
+    Process large files with limited memory usage
 
     This function reads and processes the file in chunks, ensuring memory
     usage stays within the specified limit. Sentences that span chunk
     boundaries are handled correctly but may be delayed until the next
     chunk is processed.
-    """
-    ...
 
+    Args:
+        file_path: Path to the file to process (str or os.PathLike)
+        language: Language code ("en", "ja", "pt", "it", "ar", "th", "hi") for built-in rules (default: "en")
+        language_config: Custom language configuration
+        max_memory_mb: Maximum memory to use in MB (default: 100)
+        overlap_size: Bytes to overlap between chunks for boundary handling (default: 1024)
+        encoding: File encoding (default: "utf-8")
+
+    Returns:
+        Iterator yielding sentences as they are found
+
+    Note:
+        Due to the nature of chunk processing, sentences near chunk
+        boundaries may be yielded slightly out of order compared to
+        their position in the file.
+    """
+@overload
+def split_series(
+    column: Iterable[str | None],
+    *,
+    language: str | None = None,
+    language_config: LanguageConfig | None = None,
+    threads: int | None = None,
+    chunk_kb: int | None = None,
+    return_arrow: Literal[False] = False,
+) -> list[list[str] | None]: ...
+@overload
+def split_series(
+    column: Iterable[str | None],
+    *,
+    language: str | None = None,
+    language_config: LanguageConfig | None = None,
+    threads: int | None = None,
+    chunk_kb: int | None = None,
+    return_arrow: Literal[True],
+) -> Any: ...
+def chunk_sentences(
+    sentences: list[str] | list[Sentence],
+    max_tokens: int,
+    *,
+    token_estimator: Literal["whitespace", "bpe"] = "bpe",
+) -> list[list[str]] | list[list[Sentence]]:
+    """
+    Group consecutive sentences into chunks of at most `max_tokens` tokens,
+    for language model contexts
+
+    Sentences are added to the current chunk while they fit and start a new
+    chunk otherwise; a sentence longer than `max_tokens` is a chunk by itself.
+    A Sentence counts its token_estimate when it has one; other sentences
+    are estimated with `token_estimator`.
+
+    Args:
+        sentences: Sentence strings or Sentence objects, in order
+        max_tokens: Most tokens per chunk
+        token_estimator: Estimator for sentences without an estimate
+            ("whitespace" or "bpe", default: "bpe")
+
+    Returns:
+        List of chunks, each a list of the given sentences
+    """
 def supported_languages() -> list[str]:
-    """Get list of supported languages."""
-    ...
+    """Get list of supported languages"""
@@ -0,0 +1,251 @@
+//! asyncio support: awaitable splitting and async sentence iteration
+//!
+//! The work runs on a Rust thread, and its results are handed back with the
+//! event loop's `call_soon_threadsafe`, so coroutines awaiting them never
+//! block the loop.
+
+use crate::exceptions::InternalError;
+use crate::iterator::SentenceIterator;
+use pyo3::exceptions::{PyRuntimeError, PyStopAsyncIteration};
+use pyo3::prelude::*;
+use pyo3::IntoPyObjectExt;
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+/// Sentences an async iterator reads ahead of its consumer, at most
+const READ_AHEAD: usize = 256;
+
+/// An asyncio future and the event loop it belongs to
+struct Waiter {
+    event_loop: Py<PyAny>,
+    future: Py<PyAny>,
+}
+
+impl Waiter {
+    /// A new future of the running event loop; fails outside a coroutine
+    fn new(py: Python) -> PyResult<Self> {
+        let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+        let future = event_loop.call_method0("create_future")?;
+        Ok(Self {
+            event_loop: event_loop.unbind(),
+            future: future.unbind(),
+        })
+    }
+
+    /// Set the outcome of the future from any thread, on its event loop
+    fn resolve(self, py: Python, result: PyResult<Py<PyAny>>) {
+        let (value, failed) = match result {
+            Ok(value) => (value, false),
+            Err(err) => (err.into_value(py).into_any(), true),
+        };
+        let scheduled = wrap_pyfunction!(settle, py).and_then(|settle| {
+            self.event_loop.call_method1(
+                py,
+                "call_soon_threadsafe",
+                (settle, self.future, value, failed),
+            )
+        });
+        // Fails only once the loop is closed, when nobody awaits any more
+        if let Err(err) = scheduled {
+            err.write_unraisable(py, None);
+        }
+    }
+}
+
+/// Set the result or exception of `future`, unless it was cancelled
+#[pyfunction]
+fn settle(future: &Bound<'_, PyAny>, value: Bound<'_, PyAny>, failed: bool) -> PyResult<()> {
+    if future.call_method0("done")?.is_truthy()? {
+        return Ok(());
+    }
+    let method = if failed {
+        "set_exception"
+    } else {
+        "set_result"
+    };
+    future.call_method1(method, (value,))?;
+    Ok(())
+}
+
+/// Run `work` on a new thread and return a future of its result, resolved
+/// on the running event loop
+///
+/// `work` is called with the GIL held; it should release it around the
+/// actual processing.
+pub(crate) fn spawn<F>(py: Python, work: F) -> PyResult<Py<PyAny>>
+where
+    F: FnOnce(Python) -> PyResult<Py<PyAny>> + Send + 'static,
+{
+    let waiter = Waiter::new(py)?;
+    let future = waiter.future.clone_ref(py);
+    std::thread::Builder::new()
+        .name("sakurs-asplit".to_string())
+        .spawn(move || {
+            Python::attach(|py| {
+                let result = work(py);
+                waiter.resolve(py, result);
+            })
+        })
+        .map_err(|e| InternalError::ProcessingError(e.to_string()))?;
+    Ok(future)
+}
+
+/// State shared by an async iterator and the thread reading its sentences
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    /// Signalled when the consumer takes a sentence or goes away
+    room: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    /// Sentences read ahead, not yet taken
+    ready: VecDeque<String>,
+    /// How reading ended once it has: `Ok` at the end of input, or the
+    /// error not yet raised to the consumer
+    finished: Option<PyResult<()>>,
+    /// The future of a `__anext__` waiting for the next sentence
+    waiter: Option<Waiter>,
+    /// Whether the iterator was dropped
+    closed: bool,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Async iterator over sentences, returned by `aiter_split`
+///
+/// A background thread reads and splits the input, a bounded number of
+/// sentences ahead of the consumer.
+#[pyclass]
+pub struct AsyncSentenceIterator {
+    shared: Arc<Shared>,
+}
+
+impl AsyncSentenceIterator {
+    /// Start reading the sentences of `iterator` on a background thread
+    pub(crate) fn new(mut iterator: SentenceIterator) -> PyResult<Self> {
+        let shared = Arc::new(Shared::default());
+        let reader = Arc::clone(&shared);
+        std::thread::Builder::new()
+            .name("sakurs-aiter".to_string())
+            .spawn(move || read_ahead(&reader, &mut iterator))
+            .map_err(|e| InternalError::ProcessingError(e.to_string()))?;
+        Ok(Self { shared })
+    }
+}
+
+/// Body of the reading thread: hand each sentence to a waiting `__anext__`
+/// or queue it, until the input ends or the iterator is dropped
+fn read_ahead(shared: &Shared, iterator: &mut SentenceIterator) {
+    loop {
+        {
+            let mut state = shared.lock();
+            while state.ready.len() >= READ_AHEAD && !state.closed {
+                state = shared
+                    .room
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+            if state.closed {
+                return;
+            }
+        }
+
+        let next = Python::attach(|py| iterator.next_sentence(py));
+
+        // The GIL is never requested with the lock held: `__anext__` takes
+        // the lock with the GIL held
+        let mut state = shared.lock();
+        let waiter = state.waiter.take();
+        let outcome = match next {
+            Ok(Some(sentence)) => match waiter {
+                Some(waiter) => Some((waiter, Ok(sentence))),
+                None => {
+                    state.ready.push_back(sentence);
+                    None
+                }
+            },
+            Ok(None) => {
+                state.finished = Some(Ok(()));
+                waiter.map(|waiter| (waiter, Err(PyStopAsyncIteration::new_err(()))))
+            }
+            Err(err) => match waiter {
+                Some(waiter) => {
+                    state.finished = Some(Ok(()));
+                    Some((waiter, Err(err)))
+                }
+                None => {
+                    state.finished = Some(Err(err));
+                    None
+                }
+            },
+        };
+        let done = state.finished.is_some();
+        drop(state);
+
+        if let Some((waiter, result)) = outcome {
+            Python::attach(|py| {
+                let result = result.and_then(|sentence| sentence.into_py_any(py));
+                waiter.resolve(py, result);
+            });
+        }
+        if done {
+            return;
+        }
+    }
+}
+
+#[pymethods]
+impl AsyncSentenceIterator {
+    /// Python async iterator protocol: return self
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Python async iterator protocol: an awaitable of the next sentence
+    fn __anext__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let mut state = self.shared.lock();
+        if let Some(sentence) = state.ready.pop_front() {
+            self.shared.room.notify_one();
+            drop(state);
+            let waiter = Waiter::new(py)?;
+            waiter.future.call_method1(py, "set_result", (sentence,))?;
+            return Ok(waiter.future);
+        }
+        match state.finished.take() {
+            Some(Ok(())) => {
+                state.finished = Some(Ok(()));
+                Err(PyStopAsyncIteration::new_err(()))
+            }
+            Some(Err(err)) => {
+                state.finished = Some(Ok(()));
+                Err(err)
+            }
+            None => {
+                if state.waiter.is_some() {
+                    return Err(PyRuntimeError::new_err(
+                        "__anext__ called while another call is still pending",
+                    ));
+                }
+                let waiter = Waiter::new(py)?;
+                let future = waiter.future.clone_ref(py);
+                state.waiter = Some(waiter);
+                Ok(future)
+            }
+        }
+    }
+}
+
+impl Drop for AsyncSentenceIterator {
+    fn drop(&mut self) {
+        self.shared.lock().closed = true;
+        self.shared.room.notify_one();
+    }
+}
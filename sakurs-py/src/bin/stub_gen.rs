@@ -0,0 +1,43 @@
+//! Regenerates `sakurs.pyi` from the compiled bindings
+//!
+//! Usage: `cargo run -p sakurs-py --features stub-gen --bin stub_gen [-- --check]`
+//!
+//! With `--check` the stub is only compared, and the process exits non-zero
+//! when the checked-in file is stale.
+
+use pyo3::prelude::*;
+use std::path::Path;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let check = std::env::args().skip(1).any(|arg| arg == "--check");
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("sakurs.pyi");
+
+    Python::initialize();
+    let stub = match Python::attach(sakurs::stub_gen::generate) {
+        Ok(stub) => stub,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if check {
+        let current = std::fs::read_to_string(&path).unwrap_or_default();
+        if current != stub {
+            eprintln!(
+                "{} is out of date; run without --check to regenerate",
+                path.display()
+            );
+            return ExitCode::FAILURE;
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if let Err(e) = std::fs::write(&path, stub) {
+        eprintln!("error: cannot write {}: {e}", path.display());
+        return ExitCode::FAILURE;
+    }
+    println!("wrote {}", path.display());
+    ExitCode::SUCCESS
+}
@@ -1,29 +1,48 @@
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyByteArray, PyBytes, PyMemoryView};
 use sakurs_core::api::Input;
 use std::path::PathBuf;
 
+/// The inputs accepted, for the error raised on anything else
+const EXPECTED: &str =
+    "Expected str, bytes, bytearray, memoryview, mmap, Path, or file-like object with read() method";
+
 /// Enum to represent different input types from Python
 pub enum PyInput {
     Text(String),
     Bytes(Vec<u8>),
     Path(PathBuf),
     FileObject(Py<PyAny>),
+    /// A memory-mapped file (`mmap.mmap`), read in full from its start
+    Mapped(Py<PyAny>),
 }
 
 impl PyInput {
     /// Extract input from a Python object, detecting its type
-    pub fn from_py_object(_py: Python, obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+    pub fn from_py_object(py: Python, obj: &Bound<'_, PyAny>) -> PyResult<Self> {
         // Check if it's bytes first (before string)
         if let Ok(bytes) = obj.cast::<PyBytes>() {
             return Ok(PyInput::Bytes(bytes.as_bytes().to_vec()));
         }
+        if let Ok(bytes) = obj.cast::<PyByteArray>() {
+            return Ok(PyInput::Bytes(bytes.to_vec()));
+        }
+        if obj.is_instance_of::<PyMemoryView>() {
+            let bytes = obj.call_method0("tobytes")?;
+            return Ok(PyInput::Bytes(bytes.cast::<PyBytes>()?.as_bytes().to_vec()));
+        }
 
-        // Check if it's a Path object (pathlib.Path)
-        if let Ok(fspath_method) = obj.getattr("__fspath__") {
-            if let Ok(path_str) = fspath_method.call0().and_then(|r| r.extract::<String>()) {
-                return Ok(PyInput::Path(PathBuf::from(path_str)));
-            }
+        // mmap objects have read() too, but are read whole rather than
+        // from their current position
+        if obj.is_instance(&py.import("mmap")?.getattr("mmap")?)? {
+            return Ok(PyInput::Mapped(obj.clone().unbind()));
+        }
+
+        // os.PathLike objects (pathlib.Path, os.DirEntry, ...), whose path
+        // may be str or bytes; a failing __fspath__ is raised, not skipped
+        if obj.hasattr("__fspath__")? {
+            let path = py.import("os")?.call_method1("fsdecode", (obj,))?;
+            return Ok(PyInput::Path(path.extract::<PathBuf>()?));
         }
 
         // Check if it's a file-like object (has read() method)
@@ -42,9 +61,10 @@ impl PyInput {
             return Ok(PyInput::Text(text));
         }
 
-        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Expected str, bytes, Path, or file-like object with read() method",
-        ))
+        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "{EXPECTED}, got {}",
+            obj.get_type().name()?
+        )))
     }
 
     /// Convert PyInput to sakurs_core::api::Input and return the text content
@@ -54,13 +74,14 @@ impl PyInput {
 
             PyInput::Bytes(bytes) => {
                 // Decode bytes using the specified encoding
-                let text = decode_bytes(&bytes, encoding)?;
+                let text = decode_owned(bytes, encoding)?;
                 Ok((Input::from_text(text.clone()), text))
             }
 
             PyInput::Path(path) => {
-                // Read the file content as bytes first
-                let bytes = std::fs::read(&path).map_err(|e| {
+                // Read the file content as bytes first, letting other
+                // threads run meanwhile
+                let bytes = py.detach(|| std::fs::read(&path)).map_err(|e| {
                     PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
                         "Failed to read file '{}': {}",
                         path.display(),
@@ -68,7 +89,7 @@ impl PyInput {
                     ))
                 })?;
                 // Decode with the specified encoding
-                let text = decode_bytes(&bytes, encoding)?;
+                let text = decode_owned(bytes, encoding)?;
                 Ok((Input::from_file(path), text))
             }
 
@@ -77,6 +98,15 @@ impl PyInput {
                 let content = read_file_object(py, &obj, encoding)?;
                 Ok((Input::from_text(content.clone()), content))
             }
+
+            PyInput::Mapped(obj) => {
+                // The stable ABI has no buffer access, so the mapping is
+                // copied once into a bytes object and decoded from there
+                let bytes = py.import("builtins")?.getattr("bytes")?.call1((obj,))?;
+                let bytes = bytes.cast::<PyBytes>()?.as_bytes();
+                let text = decode_bytes(bytes, encoding)?;
+                Ok((Input::from_text(text.clone()), text))
+            }
         }
     }
 }
@@ -131,6 +161,62 @@ fn decode_bytes(bytes: &[u8], encoding: &str) -> PyResult<String> {
     }
 }
 
+/// [`decode_bytes`] for bytes already owned, which UTF-8 text reuses
+/// without copying
+fn decode_owned(bytes: Vec<u8>, encoding: &str) -> PyResult<String> {
+    if matches!(encoding.to_lowercase().as_str(), "utf-8" | "utf8") {
+        return String::from_utf8(bytes).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Failed to decode bytes as UTF-8: {e}"
+            ))
+        });
+    }
+    decode_bytes(&bytes, encoding)
+}
+
+/// Incremental form of [`decode_bytes`] for input read in pieces.
+///
+/// A UTF-8 sequence split across two pieces is carried over to the next
+/// piece; the single-byte encodings need no carry.
+pub(crate) struct PieceDecoder {
+    encoding: String,
+    carry: Vec<u8>,
+}
+
+impl PieceDecoder {
+    /// Create a decoder, rejecting unsupported encodings up front
+    pub(crate) fn new(encoding: &str) -> PyResult<Self> {
+        decode_bytes(&[], encoding)?;
+        Ok(Self {
+            encoding: encoding.to_lowercase(),
+            carry: Vec::new(),
+        })
+    }
+
+    /// Decode the next piece; `last` marks the end of input, where an
+    /// incomplete carried sequence is an error
+    pub(crate) fn decode(&mut self, bytes: &[u8], last: bool) -> PyResult<String> {
+        if !matches!(self.encoding.as_str(), "utf-8" | "utf8") {
+            return decode_bytes(bytes, &self.encoding);
+        }
+        self.carry.extend_from_slice(bytes);
+        let valid = match std::str::from_utf8(&self.carry) {
+            Ok(_) => self.carry.len(),
+            // Only an incomplete sequence at the very end may be carried
+            Err(e) if e.error_len().is_none() && !last => e.valid_up_to(),
+            Err(e) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Failed to decode bytes as UTF-8: {e}"
+                )))
+            }
+        };
+        let rest = self.carry.split_off(valid);
+        let text = String::from_utf8(std::mem::replace(&mut self.carry, rest))
+            .expect("validated as UTF-8 above");
+        Ok(text)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,4 +241,23 @@ mod tests {
         let result = decode_bytes(bytes, "latin-1").unwrap();
         assert_eq!(result, "Hello é");
     }
+
+    #[test]
+    fn test_piece_decoder_carries_split_sequences() {
+        let bytes = "世界。".as_bytes();
+        let mut decoder = PieceDecoder::new("utf-8").unwrap();
+        let mut text = String::new();
+        for piece in bytes.chunks(2) {
+            text.push_str(&decoder.decode(piece, false).unwrap());
+        }
+        text.push_str(&decoder.decode(&[], true).unwrap());
+        assert_eq!(text, "世界。");
+    }
+
+    #[test]
+    fn test_piece_decoder_rejects_truncated_input() {
+        let mut decoder = PieceDecoder::new("utf-8").unwrap();
+        assert_eq!(decoder.decode(&"世".as_bytes()[..2], false).unwrap(), "");
+        assert!(decoder.decode(&[], true).is_err());
+    }
 }
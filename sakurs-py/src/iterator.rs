@@ -1,211 +1,230 @@
 //! Python iterator implementation for streaming sentence splitting
 
+use crate::exceptions::InternalError;
+use crate::input::PieceDecoder;
 use pyo3::exceptions::PyStopIteration;
 use pyo3::prelude::*;
-use sakurs_core::Input;
+use sakurs_core::SentenceStream;
 use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
+use std::io::Read;
 
 /// Python iterator for streaming sentences
+///
+/// Reads its source in bounded pieces and feeds them to the core stream, so
+/// memory stays proportional to the piece size plus the longest sentence
+/// rather than the whole input.
 #[pyclass]
 pub struct SentenceIterator {
-    /// Shared state for streaming processing
-    state: Arc<Mutex<IteratorState>>,
-}
-
-/// Internal state for the iterator
-pub(crate) struct IteratorState {
-    /// Buffer of pending sentences to yield
+    /// Where the next piece of text comes from; `None` once drained
+    source: Option<TextSource>,
+    /// Core stream; `None` once finished
+    stream: Option<SentenceStream>,
+    /// Text after the last boundary, not yet yielded
+    text: String,
+    /// Byte offset of `text` in the stream
+    text_offset: usize,
+    /// Complete sentences waiting to be yielded
     sentence_buffer: VecDeque<String>,
-    /// Current position in the text (byte offset)
-    #[allow(dead_code)]
-    position: usize,
-    /// Whether we've reached end of input
-    exhausted: bool,
-    /// Text buffer for incomplete sentences
-    text_buffer: String,
+    /// Piece size in bytes for each read
+    read_size: usize,
     /// Whether to preserve whitespace
     preserve_whitespace: bool,
 }
 
-impl IteratorState {
-    fn new(preserve_whitespace: bool) -> Self {
-        Self {
-            sentence_buffer: VecDeque::new(),
-            position: 0,
-            exhausted: false,
-            text_buffer: String::new(),
-            preserve_whitespace,
+/// Input read piece by piece
+pub(crate) enum TextSource {
+    /// Text already in memory, handed out in slices
+    Text { text: String, position: usize },
+    /// A file (or other byte reader) decoded as it is read
+    Reader {
+        reader: Box<dyn Read + Send + Sync>,
+        decoder: PieceDecoder,
+    },
+    /// A Python file-like object, read via `read(size)`
+    FileObject {
+        obj: Py<PyAny>,
+        decoder: PieceDecoder,
+    },
+}
+
+impl TextSource {
+    /// Read the next piece of roughly `size` bytes; `None` at end of input
+    fn next_piece(&mut self, py: Python, size: usize) -> PyResult<Option<String>> {
+        match self {
+            TextSource::Text { text, position } => {
+                if *position >= text.len() {
+                    return Ok(None);
+                }
+                let mut end = (*position + size).min(text.len());
+                while !text.is_char_boundary(end) {
+                    end += 1;
+                }
+                let piece = text[*position..end].to_string();
+                *position = end;
+                Ok(Some(piece))
+            }
+            TextSource::Reader { reader, decoder } => {
+                let mut buf = vec![0u8; size];
+                loop {
+                    let n = py
+                        .detach(|| reader.read(&mut buf))
+                        .map_err(|e| InternalError::IoError(e.to_string()))?;
+                    let piece = decoder.decode(&buf[..n], n == 0)?;
+                    if n == 0 {
+                        return Ok((!piece.is_empty()).then_some(piece));
+                    }
+                    if !piece.is_empty() {
+                        return Ok(Some(piece));
+                    }
+                }
+            }
+            TextSource::FileObject { obj, decoder } => loop {
+                let content = obj.call_method1(py, "read", (size,))?;
+                let content = content.bind(py);
+                if let Ok(text) = content.extract::<String>() {
+                    return Ok((!text.is_empty()).then_some(text));
+                }
+                let bytes = content.extract::<Vec<u8>>().map_err(|_| {
+                    InternalError::InvalidInput(
+                        "file.read() returned neither str nor bytes".to_string(),
+                    )
+                })?;
+                let eof = bytes.is_empty();
+                let piece = decoder.decode(&bytes, eof)?;
+                if eof {
+                    return Ok((!piece.is_empty()).then_some(piece));
+                }
+                if !piece.is_empty() {
+                    return Ok(Some(piece));
+                }
+            },
         }
     }
 }
 
 #[pymethods]
 impl SentenceIterator {
-    /// Create a new sentence iterator
-    #[new]
-    fn new(preserve_whitespace: bool) -> Self {
-        Self {
-            state: Arc::new(Mutex::new(IteratorState::new(preserve_whitespace))),
-        }
-    }
-
     /// Python iterator protocol: return self
     fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
         slf
     }
 
     /// Python iterator protocol: get next sentence
-    fn __next__(&self) -> PyResult<Option<String>> {
-        let mut state = self.state.lock().unwrap();
-
-        // If we have buffered sentences, return one
-        if let Some(sentence) = state.sentence_buffer.pop_front() {
-            return Ok(Some(sentence));
+    fn __next__(&mut self, py: Python) -> PyResult<Option<String>> {
+        match self.next_sentence(py)? {
+            Some(sentence) => Ok(Some(sentence)),
+            None => Err(PyStopIteration::new_err(())),
         }
+    }
 
-        // If exhausted and no more sentences, stop iteration
-        if state.exhausted {
-            return Err(PyStopIteration::new_err(()));
-        }
-
-        // In a real implementation, we would:
-        // 1. Read more data from the input source
-        // 2. Process it to find sentences
-        // 3. Buffer complete sentences
-        // 4. Return the first one
-        // For now, we'll implement the interface
-
-        Err(PyStopIteration::new_err(()))
+    /// Stop iterating and release the input
+    ///
+    /// A file opened from a path is closed right away, and later calls to
+    /// next() raise StopIteration. File-like objects passed in are left open
+    /// for their owner to close. Closing twice does nothing.
+    fn close(&mut self) {
+        self.source = None;
+        self.stream = None;
+        self.text.clear();
+        self.sentence_buffer.clear();
     }
 
-    /// Add sentences to the buffer (internal method for feeding data)
-    pub fn add_sentences(&self, sentences: Vec<String>) -> PyResult<()> {
-        let mut state = self.state.lock().unwrap();
-        state.sentence_buffer.extend(sentences);
-        Ok(())
+    /// Context manager entry
+    fn __enter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
     }
 
-    /// Mark the iterator as exhausted (no more input)
-    pub fn mark_exhausted(&self) -> PyResult<()> {
-        let mut state = self.state.lock().unwrap();
-        state.exhausted = true;
-        Ok(())
+    /// Context manager exit: close the iterator
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.close();
+        // Don't suppress any exceptions
+        Ok(false)
     }
 }
 
 impl SentenceIterator {
-    /// Get the internal state for coordination with streaming processor (internal use only)
-    pub(crate) fn get_state(&self) -> Arc<Mutex<IteratorState>> {
-        Arc::clone(&self.state)
-    }
-
-    /// Create a new iterator for internal use
-    pub(crate) fn new_internal(preserve_whitespace: bool) -> Self {
+    /// Create an iterator over `source`, feeding `stream` in pieces of
+    /// `read_size` bytes
+    pub(crate) fn new(
+        source: TextSource,
+        stream: SentenceStream,
+        read_size: usize,
+        preserve_whitespace: bool,
+    ) -> Self {
         Self {
-            state: Arc::new(Mutex::new(IteratorState::new(preserve_whitespace))),
+            source: Some(source),
+            stream: Some(stream),
+            text: String::new(),
+            text_offset: 0,
+            sentence_buffer: VecDeque::new(),
+            read_size: read_size.max(1),
+            preserve_whitespace,
         }
     }
-}
-
-/// Helper function to process text incrementally and yield sentences
-pub(crate) fn process_text_incrementally(
-    text: &str,
-    state: &Arc<Mutex<IteratorState>>,
-    processor: &sakurs_core::SentenceProcessor,
-) -> PyResult<()> {
-    use sakurs_core::Input;
 
-    let mut state_guard = state.lock().unwrap();
-
-    // Append new text to buffer
-    state_guard.text_buffer.push_str(text);
-
-    // Process the buffered text
-    let input = Input::from_text(&state_guard.text_buffer);
-    let output = processor
-        .process(input)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-
-    // Extract complete sentences (all but potentially the last one)
-    let mut sentences = Vec::new();
-    let mut last_boundary = 0;
-
-    for (i, boundary) in output.boundaries.iter().enumerate() {
-        let is_last = i == output.boundaries.len() - 1;
-
-        // For streaming, we keep the last sentence in the buffer
-        // unless we're sure it's complete (e.g., followed by significant whitespace)
-        if !is_last || text.ends_with('\n') || text.ends_with("\n\n") {
-            let sentence = state_guard.text_buffer[last_boundary..boundary.offset].to_string();
-            let sentence = if state_guard.preserve_whitespace {
-                sentence
-            } else {
-                sentence.trim().to_string()
-            };
-            if !sentence.is_empty() {
-                sentences.push(sentence);
+    /// The next sentence, reading as much input as it takes; `None` at the
+    /// end of input
+    pub(crate) fn next_sentence(&mut self, py: Python) -> PyResult<Option<String>> {
+        loop {
+            if let Some(sentence) = self.sentence_buffer.pop_front() {
+                return Ok(Some(sentence));
+            }
+            if self.stream.is_none() {
+                return Ok(None);
             }
-            last_boundary = boundary.offset;
+            self.pull(py)?;
         }
     }
 
-    // Update the text buffer to contain only unprocessed text
-    if last_boundary > 0 {
-        state_guard.text_buffer = state_guard.text_buffer[last_boundary..].to_string();
-    }
-
-    // Add sentences to the iterator's buffer
-    state_guard.sentence_buffer.extend(sentences);
-
-    Ok(())
-}
-
-/// Flush any remaining text in the buffer as a final sentence
-pub(crate) fn flush_buffer(
-    state: &Arc<Mutex<IteratorState>>,
-    processor: &sakurs_core::SentenceProcessor,
-) -> PyResult<()> {
-    let mut state_guard = state.lock().unwrap();
-
-    if !state_guard.text_buffer.is_empty() {
-        // Process any remaining text
-        let input = Input::from_text(&state_guard.text_buffer);
-        let output = processor
-            .process(input)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-
-        // Extract all sentences from the final buffer
-        let mut last_boundary = 0;
-        for boundary in output.boundaries {
-            let sentence = state_guard.text_buffer[last_boundary..boundary.offset].to_string();
-            let sentence = if state_guard.preserve_whitespace {
-                sentence
-            } else {
-                sentence.trim().to_string()
-            };
-            if !sentence.is_empty() {
-                state_guard.sentence_buffer.push_back(sentence);
+    /// Read one piece and buffer the sentences it completes; at end of input,
+    /// finish the stream and buffer the rest
+    fn pull(&mut self, py: Python) -> PyResult<()> {
+        let piece = match self.source.as_mut() {
+            Some(source) => source.next_piece(py, self.read_size)?,
+            None => None,
+        };
+
+        let offsets: Vec<usize> = match piece {
+            Some(piece) => {
+                let stream = self.stream.as_mut().expect("stream is live while pulling");
+                self.text.push_str(&piece);
+                py.detach(|| stream.feed(&piece))
+                    .into_iter()
+                    .map(|b| b.offset)
+                    .collect()
             }
-            last_boundary = boundary.offset;
-        }
+            None => {
+                self.source = None;
+                let stream = self.stream.take().expect("stream is live while pulling");
+                let mut offsets: Vec<usize> =
+                    stream.finish().into_iter().map(|b| b.offset).collect();
+                // Trailing text without a terminator is the last sentence
+                offsets.push(self.text_offset + self.text.len());
+                offsets
+            }
+        };
 
-        // Add any remaining text as the last sentence
-        if last_boundary < state_guard.text_buffer.len() {
-            let sentence = state_guard.text_buffer[last_boundary..].to_string();
-            let sentence = if state_guard.preserve_whitespace {
+        let mut last = 0;
+        for offset in offsets {
+            let end = offset - self.text_offset;
+            let sentence = &self.text[last..end];
+            let sentence = if self.preserve_whitespace {
                 sentence
             } else {
-                sentence.trim().to_string()
+                sentence.trim()
             };
             if !sentence.is_empty() {
-                state_guard.sentence_buffer.push_back(sentence);
+                self.sentence_buffer.push_back(sentence.to_string());
             }
+            last = end;
         }
-
-        state_guard.text_buffer.clear();
+        self.text.drain(..last);
+        self.text_offset += last;
+        Ok(())
     }
-
-    state_guard.exhausted = true;
-    Ok(())
 }
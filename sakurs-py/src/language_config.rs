@@ -3,12 +3,15 @@
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PyType};
 use sakurs_core::api::language_config::{
-    AbbreviationConfig as CoreAbbreviationConfig, ContextRule as CoreContextRule,
-    EllipsisConfig as CoreEllipsisConfig, EnclosureConfig as CoreEnclosureConfig,
+    AbbreviationConfig as CoreAbbreviationConfig, AbbreviationEntry as CoreAbbreviationEntry,
+    AbbreviationRule as CoreAbbreviationRule, ApostropheConfig as CoreApostropheConfig,
+    ContextRule as CoreContextRule, EllipsisConfig as CoreEllipsisConfig,
+    EnclosureBoundaries as CoreEnclosureBoundaries, EnclosureConfig as CoreEnclosureConfig,
     EnclosurePair as CoreEnclosurePair, ExceptionPattern as CoreExceptionPattern,
-    FastPattern as CoreFastPattern, LanguageConfig as CoreLanguageConfig,
-    MetadataConfig as CoreMetadataConfig, RegexPattern as CoreRegexPattern,
-    SentenceStarterConfig as CoreSentenceStarterConfig, SuppressionConfig as CoreSuppressionConfig,
+    FastPattern as CoreFastPattern, HypothesisConfig as CoreHypothesisConfig,
+    LanguageConfig as CoreLanguageConfig, MetadataConfig as CoreMetadataConfig,
+    RegexPattern as CoreRegexPattern, SentenceStarterConfig as CoreSentenceStarterConfig,
+    StarterEntry as CoreStarterEntry, SuppressionConfig as CoreSuppressionConfig,
     TerminatorConfig as CoreTerminatorConfig, TerminatorPattern as CoreTerminatorPattern,
 };
 use std::collections::HashMap;
@@ -71,14 +74,32 @@ pub struct TerminatorConfig {
     pub chars: Vec<String>, // Python expects strings, not chars
     #[pyo3(get, set)]
     pub patterns: Vec<TerminatorPattern>,
+    #[pyo3(get, set)]
+    pub ordinal_indicators: Vec<String>,
+    #[pyo3(get, set)]
+    pub collapse_runs: bool,
+    #[pyo3(get, set)]
+    pub emoji: bool,
 }
 
 #[pymethods]
 impl TerminatorConfig {
     #[new]
-    #[pyo3(signature = (chars, patterns=vec![]))]
-    fn new(chars: Vec<String>, patterns: Vec<TerminatorPattern>) -> Self {
-        Self { chars, patterns }
+    #[pyo3(signature = (chars, patterns=vec![], ordinal_indicators=vec![], collapse_runs=true, emoji=false))]
+    fn new(
+        chars: Vec<String>,
+        patterns: Vec<TerminatorPattern>,
+        ordinal_indicators: Vec<String>,
+        collapse_runs: bool,
+        emoji: bool,
+    ) -> Self {
+        Self {
+            chars,
+            patterns,
+            ordinal_indicators,
+            collapse_runs,
+            emoji,
+        }
     }
 
     fn __repr__(&self) -> String {
@@ -196,24 +217,27 @@ pub struct EnclosurePair {
     pub close: String,
     #[pyo3(get, set)]
     pub symmetric: bool,
+    #[pyo3(get, set)]
+    pub boundaries: String, // "suppress", "allow", "strong_only" or "dialogue"
 }
 
 #[pymethods]
 impl EnclosurePair {
     #[new]
-    #[pyo3(signature = (open, close, symmetric=false))]
-    fn new(open: String, close: String, symmetric: bool) -> Self {
+    #[pyo3(signature = (open, close, symmetric=false, boundaries="suppress".to_string()))]
+    fn new(open: String, close: String, symmetric: bool, boundaries: String) -> Self {
         Self {
             open,
             close,
             symmetric,
+            boundaries,
         }
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "EnclosurePair(open='{}', close='{}', symmetric={})",
-            self.open, self.close, self.symmetric
+            "EnclosurePair(open='{}', close='{}', symmetric={}, boundaries='{}')",
+            self.open, self.close, self.symmetric, self.boundaries
         )
     }
 }
@@ -310,16 +334,35 @@ pub struct SuppressionConfig {
     pub fast_patterns: Vec<FastPattern>,
     #[pyo3(get, set)]
     pub regex_patterns: Vec<RegexPattern>,
+    #[pyo3(get, set)]
+    pub trailing_patterns: Vec<String>,
+    #[pyo3(get, set)]
+    pub web_tokens: bool,
+    #[pyo3(get, set)]
+    pub citations: bool,
+    #[pyo3(get, set)]
+    pub apostrophes: Option<ApostropheConfig>,
 }
 
 #[pymethods]
 impl SuppressionConfig {
     #[new]
-    #[pyo3(signature = (fast_patterns=vec![], regex_patterns=vec![]))]
-    fn new(fast_patterns: Vec<FastPattern>, regex_patterns: Vec<RegexPattern>) -> Self {
+    #[pyo3(signature = (fast_patterns=vec![], regex_patterns=vec![], trailing_patterns=vec![], web_tokens=false, citations=false, apostrophes=None))]
+    fn new(
+        fast_patterns: Vec<FastPattern>,
+        regex_patterns: Vec<RegexPattern>,
+        trailing_patterns: Vec<String>,
+        web_tokens: bool,
+        citations: bool,
+        apostrophes: Option<ApostropheConfig>,
+    ) -> Self {
         Self {
             fast_patterns,
             regex_patterns,
+            trailing_patterns,
+            web_tokens,
+            citations,
+            apostrophes,
         }
     }
 
@@ -332,6 +375,54 @@ impl SuppressionConfig {
     }
 }
 
+/// Apostrophes that are not quotation marks
+#[pyclass(name = "ApostropheConfig")]
+#[derive(Clone)]
+pub struct ApostropheConfig {
+    #[pyo3(get, set)]
+    pub chars: Vec<String>, // Python expects strings, not chars
+    #[pyo3(get, set)]
+    pub contractions: bool,
+    #[pyo3(get, set)]
+    pub possessives: bool,
+    #[pyo3(get, set)]
+    pub years: bool,
+    #[pyo3(get, set)]
+    pub elided_starts: Vec<String>,
+    #[pyo3(get, set)]
+    pub elided_ends: Vec<String>,
+}
+
+#[pymethods]
+impl ApostropheConfig {
+    #[new]
+    #[pyo3(signature = (chars=vec!["'".to_string(), "\u{2019}".to_string()], contractions=true, possessives=true, years=true, elided_starts=vec![], elided_ends=vec![]))]
+    fn new(
+        chars: Vec<String>,
+        contractions: bool,
+        possessives: bool,
+        years: bool,
+        elided_starts: Vec<String>,
+        elided_ends: Vec<String>,
+    ) -> Self {
+        Self {
+            chars,
+            contractions,
+            possessives,
+            years,
+            elided_starts,
+            elided_ends,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ApostropheConfig(chars={:?}, contractions={}, possessives={}, years={})",
+            self.chars, self.contractions, self.possessives, self.years
+        )
+    }
+}
+
 /// Abbreviation configuration
 #[pyclass(name = "AbbreviationConfig")]
 pub struct AbbreviationConfig {
@@ -380,7 +471,10 @@ impl AbbreviationConfig {
             .map_err(|_| pyo3::exceptions::PyTypeError::new_err("Category must be a list"))
     }
 
-    fn __setitem__(&self, py: Python, key: String, value: Vec<String>) -> PyResult<()> {
+    fn __setitem__(&self, py: Python, key: String, value: Vec<Bound<'_, PyAny>>) -> PyResult<()> {
+        for item in &value {
+            abbreviation_from_py(item)?;
+        }
         let categories = self.categories.bind(py);
         let list = PyList::new(py, value)?;
         categories.set_item(key, list)?;
@@ -388,6 +482,46 @@ impl AbbreviationConfig {
     }
 }
 
+/// An abbreviation entry as Python sees it: a plain string, or a dict with
+/// `abbr` and the optional `case_sensitive` and `followed_by` keys
+fn abbreviation_to_py(py: Python<'_>, entry: CoreAbbreviationEntry) -> PyResult<Bound<'_, PyAny>> {
+    match entry {
+        CoreAbbreviationEntry::Plain(abbr) => Ok(abbr.into_pyobject(py)?.into_any()),
+        CoreAbbreviationEntry::Rule(rule) => {
+            let dict = PyDict::new(py);
+            dict.set_item("abbr", rule.abbr)?;
+            dict.set_item("case_sensitive", rule.case_sensitive)?;
+            if let Some(condition) = rule.followed_by {
+                dict.set_item("followed_by", condition)?;
+            }
+            Ok(dict.into_any())
+        }
+    }
+}
+
+fn abbreviation_from_py(item: &Bound<'_, PyAny>) -> PyResult<CoreAbbreviationEntry> {
+    if let Ok(abbr) = item.extract::<String>() {
+        return Ok(CoreAbbreviationEntry::Plain(abbr));
+    }
+    let rule = item.cast::<PyDict>().map_err(|_| {
+        pyo3::exceptions::PyTypeError::new_err("Abbreviation must be a str or a dict")
+    })?;
+    let abbr = rule.get_item("abbr")?.ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err("Abbreviation dict needs an 'abbr' key")
+    })?;
+    Ok(CoreAbbreviationEntry::Rule(CoreAbbreviationRule {
+        abbr: abbr.extract()?,
+        case_sensitive: match rule.get_item("case_sensitive")? {
+            Some(value) => value.extract()?,
+            None => false,
+        },
+        followed_by: match rule.get_item("followed_by")? {
+            Some(value) => value.extract()?,
+            None => None,
+        },
+    }))
+}
+
 /// Sentence starter configuration
 #[pyclass(name = "SentenceStarterConfig")]
 pub struct SentenceStarterConfig {
@@ -397,6 +531,10 @@ pub struct SentenceStarterConfig {
     pub require_following_space: bool,
     #[pyo3(get, set)]
     pub min_word_length: usize,
+    #[pyo3(get, set)]
+    pub threshold: f64,
+    #[pyo3(get, set)]
+    pub uppercase_fallback: Option<f64>,
 }
 
 // Manual Clone implementation for SentenceStarterConfig
@@ -406,6 +544,8 @@ impl Clone for SentenceStarterConfig {
             categories: self.categories.clone_ref(py),
             require_following_space: self.require_following_space,
             min_word_length: self.min_word_length,
+            threshold: self.threshold,
+            uppercase_fallback: self.uppercase_fallback,
         })
     }
 }
@@ -413,11 +553,13 @@ impl Clone for SentenceStarterConfig {
 #[pymethods]
 impl SentenceStarterConfig {
     #[new]
-    #[pyo3(signature = (require_following_space=true, min_word_length=1, **kwargs))]
+    #[pyo3(signature = (require_following_space=true, min_word_length=1, threshold=0.5, uppercase_fallback=None, **kwargs))]
     fn new(
         py: Python,
         require_following_space: bool,
         min_word_length: usize,
+        threshold: f64,
+        uppercase_fallback: Option<f64>,
         kwargs: Option<&Bound<'_, PyDict>>,
     ) -> PyResult<Self> {
         let dict = PyDict::new(py);
@@ -428,6 +570,8 @@ impl SentenceStarterConfig {
             categories: dict.unbind(),
             require_following_space,
             min_word_length,
+            threshold,
+            uppercase_fallback,
         })
     }
 
@@ -435,12 +579,90 @@ impl SentenceStarterConfig {
         let categories = self.categories.bind(py);
         let num_categories = categories.len();
         Ok(format!(
-            "SentenceStarterConfig(categories=[{} categories], require_following_space={}, min_word_length={})",
-            num_categories, self.require_following_space, self.min_word_length
+            "SentenceStarterConfig(categories=[{} categories], require_following_space={}, min_word_length={}, threshold={})",
+            num_categories, self.require_following_space, self.min_word_length, self.threshold
         ))
     }
 }
 
+/// A sentence starter as Python sees it: a plain string, or a dict with
+/// `word` and `weight` keys
+fn starter_to_py(py: Python<'_>, entry: CoreStarterEntry) -> PyResult<Bound<'_, PyAny>> {
+    match entry {
+        CoreStarterEntry::Plain(word) => Ok(word.into_pyobject(py)?.into_any()),
+        CoreStarterEntry::Weighted { word, weight } => {
+            let dict = PyDict::new(py);
+            dict.set_item("word", word)?;
+            dict.set_item("weight", weight)?;
+            Ok(dict.into_any())
+        }
+    }
+}
+
+fn starter_from_py(item: &Bound<'_, PyAny>) -> PyResult<CoreStarterEntry> {
+    if let Ok(word) = item.extract::<String>() {
+        return Ok(CoreStarterEntry::Plain(word));
+    }
+    let starter = item.cast::<PyDict>().map_err(|_| {
+        pyo3::exceptions::PyTypeError::new_err("Sentence starter must be a str or a dict")
+    })?;
+    let key = |name: &str| {
+        starter.get_item(name)?.ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Sentence starter dict needs a '{name}' key"
+            ))
+        })
+    };
+    Ok(CoreStarterEntry::Weighted {
+        word: key("word")?.extract()?,
+        weight: key("weight")?.extract()?,
+    })
+}
+
+/// Boundary hypothesis strategy configuration
+#[pyclass(name = "HypothesisConfig")]
+#[derive(Clone)]
+pub struct HypothesisConfig {
+    #[pyo3(get, set)]
+    pub strategy: String,
+    #[pyo3(get, set)]
+    pub min_chars: usize,
+    #[pyo3(get, set)]
+    pub sentence_final_particles: Vec<String>,
+    #[pyo3(get, set)]
+    pub discourse_markers: Vec<String>,
+    #[pyo3(get, set)]
+    pub continuation_markers: Vec<String>,
+}
+
+#[pymethods]
+impl HypothesisConfig {
+    #[new]
+    #[pyo3(signature = (strategy, min_chars=8, sentence_final_particles=vec![], discourse_markers=vec![], continuation_markers=vec![]))]
+    fn new(
+        strategy: String,
+        min_chars: usize,
+        sentence_final_particles: Vec<String>,
+        discourse_markers: Vec<String>,
+        continuation_markers: Vec<String>,
+    ) -> Self {
+        Self {
+            strategy,
+            min_chars,
+            sentence_final_particles,
+            discourse_markers,
+            continuation_markers,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "HypothesisConfig(strategy='{}', min_chars={})",
+            self.strategy, self.min_chars
+        )
+    }
+}
+
 /// Complete language configuration
 #[pyclass(name = "LanguageConfig")]
 #[derive(Clone)]
@@ -459,11 +681,15 @@ pub struct LanguageConfig {
     pub abbreviations: AbbreviationConfig,
     #[pyo3(get, set)]
     pub sentence_starters: Option<SentenceStarterConfig>,
+    #[pyo3(get, set)]
+    pub hypothesis: Option<HypothesisConfig>,
 }
 
 #[pymethods]
 impl LanguageConfig {
     #[new]
+    #[pyo3(signature = (metadata, terminators, ellipsis, enclosures, suppression, abbreviations, sentence_starters=None, hypothesis=None))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         metadata: MetadataConfig,
         terminators: TerminatorConfig,
@@ -472,6 +698,7 @@ impl LanguageConfig {
         suppression: SuppressionConfig,
         abbreviations: AbbreviationConfig,
         sentence_starters: Option<SentenceStarterConfig>,
+        hypothesis: Option<HypothesisConfig>,
     ) -> Self {
         Self {
             metadata,
@@ -481,6 +708,7 @@ impl LanguageConfig {
             suppression,
             abbreviations,
             sentence_starters,
+            hypothesis,
         }
     }
 
@@ -491,12 +719,9 @@ impl LanguageConfig {
         let content = std::fs::read_to_string(&path)
             .map_err(|e| InternalError::FileNotFound(format!("Failed to read TOML file: {e}")))?;
 
-        // Parse TOML into Core LanguageConfig
-        let core_config: CoreLanguageConfig = toml::from_str(&content)
-            .map_err(|e| InternalError::ConfigurationError(format!("Failed to parse TOML: {e}")))?;
-
-        // Validate the configuration
-        core_config.validate().map_err(|e| {
+        // Parse and validate, migrating keys renamed since the file's
+        // schema version
+        let (core_config, _) = CoreLanguageConfig::from_toml(&content).map_err(|e| {
             InternalError::ConfigurationError(format!("Invalid configuration: {e}"))
         })?;
 
@@ -514,10 +739,8 @@ impl LanguageConfig {
             InternalError::ConfigurationError(format!("Invalid configuration: {e}"))
         })?;
 
-        // Serialize to TOML
-        let toml_str = toml::to_string_pretty(&core_config).map_err(|e| {
-            InternalError::ConfigurationError(format!("Failed to serialize to TOML: {e}"))
-        })?;
+        // Serialize to TOML, stamped with the schema version
+        let toml_str = core_config.to_toml();
 
         // Write to file
         std::fs::write(&path, toml_str).map_err(|e| {
@@ -561,7 +784,19 @@ impl LanguageConfig {
                 name: p.name,
             })
             .collect();
-        let terminators = TerminatorConfig { chars, patterns };
+        let ordinal_indicators = core
+            .terminators
+            .ordinal_indicators
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect();
+        let terminators = TerminatorConfig {
+            chars,
+            patterns,
+            ordinal_indicators,
+            collapse_runs: core.terminators.collapse_runs,
+            emoji: core.terminators.emoji,
+        };
 
         // Convert ellipsis
         let context_rules = core
@@ -598,6 +833,7 @@ impl LanguageConfig {
                 open: p.open.to_string(),
                 close: p.close.to_string(),
                 symmetric: p.symmetric,
+                boundaries: p.boundaries.as_str().to_string(),
             })
             .collect();
         let enclosures = EnclosureConfig { pairs };
@@ -626,30 +862,61 @@ impl LanguageConfig {
         let suppression = SuppressionConfig {
             fast_patterns,
             regex_patterns,
+            trailing_patterns: core.suppression.trailing_patterns,
+            web_tokens: core.suppression.web_tokens,
+            citations: core.suppression.citations,
+            apostrophes: core.suppression.apostrophes.map(|a| ApostropheConfig {
+                chars: a.chars.iter().map(char::to_string).collect(),
+                contractions: a.contractions,
+                possessives: a.possessives,
+                years: a.years,
+                elided_starts: a.elided_starts,
+                elided_ends: a.elided_ends,
+            }),
         };
 
         // Convert abbreviations
         let abbrev_dict = PyDict::new(py);
-        for (category, words) in core.abbreviations.categories {
-            let word_list = PyList::new(py, words)?;
-            abbrev_dict.set_item(category, word_list)?;
+        for (category, entries) in core.abbreviations.categories {
+            let entries = entries
+                .into_iter()
+                .map(|entry| abbreviation_to_py(py, entry))
+                .collect::<PyResult<Vec<_>>>()?;
+            abbrev_dict.set_item(category, PyList::new(py, entries)?)?;
         }
         let abbreviations = AbbreviationConfig {
             categories: abbrev_dict.unbind(),
         };
 
         // Convert sentence starters
-        let sentence_starters = core.sentence_starters.map(|ss| {
-            let categories_dict = PyDict::new(py);
-            for (category, words) in ss.categories {
-                let word_list = PyList::new(py, words).unwrap();
-                categories_dict.set_item(category, word_list).unwrap();
-            }
-            SentenceStarterConfig {
-                categories: categories_dict.unbind(),
-                require_following_space: ss.require_following_space,
-                min_word_length: ss.min_word_length,
-            }
+        let sentence_starters = core
+            .sentence_starters
+            .map(|ss| -> PyResult<_> {
+                let categories_dict = PyDict::new(py);
+                for (category, entries) in ss.categories {
+                    let entries = entries
+                        .into_iter()
+                        .map(|entry| starter_to_py(py, entry))
+                        .collect::<PyResult<Vec<_>>>()?;
+                    categories_dict.set_item(category, PyList::new(py, entries)?)?;
+                }
+                Ok(SentenceStarterConfig {
+                    categories: categories_dict.unbind(),
+                    require_following_space: ss.require_following_space,
+                    min_word_length: ss.min_word_length,
+                    threshold: ss.threshold,
+                    uppercase_fallback: ss.uppercase_fallback,
+                })
+            })
+            .transpose()?;
+
+        // Convert hypothesis strategy
+        let hypothesis = core.hypothesis.map(|h| HypothesisConfig {
+            strategy: h.strategy,
+            min_chars: h.min_chars,
+            sentence_final_particles: h.sentence_final_particles,
+            discourse_markers: h.discourse_markers,
+            continuation_markers: h.continuation_markers,
         });
 
         Ok(Self {
@@ -660,6 +927,7 @@ impl LanguageConfig {
             suppression,
             abbreviations,
             sentence_starters,
+            hypothesis,
         })
     }
 
@@ -687,7 +955,19 @@ impl LanguageConfig {
                 name: p.name.clone(),
             })
             .collect();
-        let terminators = CoreTerminatorConfig { chars, patterns };
+        let ordinal_indicators = self
+            .terminators
+            .ordinal_indicators
+            .iter()
+            .filter_map(|s| s.chars().next())
+            .collect();
+        let terminators = CoreTerminatorConfig {
+            chars,
+            patterns,
+            ordinal_indicators,
+            collapse_runs: self.terminators.collapse_runs,
+            emoji: self.terminators.emoji,
+        };
 
         // Convert ellipsis
         let context_rules = self
@@ -723,13 +1003,18 @@ impl LanguageConfig {
             .filter_map(|p| {
                 let open_char = p.open.chars().next()?;
                 let close_char = p.close.chars().next()?;
-                Some(CoreEnclosurePair {
+                let boundaries = p
+                    .boundaries
+                    .parse::<CoreEnclosureBoundaries>()
+                    .map_err(|e| InternalError::ConfigurationError(e.to_string()));
+                Some(boundaries.map(|boundaries| CoreEnclosurePair {
                     open: open_char,
                     close: close_char,
                     symmetric: p.symmetric,
-                })
+                    boundaries,
+                }))
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
         let enclosures = CoreEnclosureConfig { pairs };
 
         // Convert suppression
@@ -758,6 +1043,21 @@ impl LanguageConfig {
         let suppression = CoreSuppressionConfig {
             fast_patterns,
             regex_patterns,
+            trailing_patterns: self.suppression.trailing_patterns.clone(),
+            web_tokens: self.suppression.web_tokens,
+            citations: self.suppression.citations,
+            apostrophes: self
+                .suppression
+                .apostrophes
+                .as_ref()
+                .map(|a| CoreApostropheConfig {
+                    chars: a.chars.iter().filter_map(|s| s.chars().next()).collect(),
+                    contractions: a.contractions,
+                    possessives: a.possessives,
+                    years: a.years,
+                    elided_starts: a.elided_starts.clone(),
+                    elided_ends: a.elided_ends.clone(),
+                }),
         };
 
         // Convert abbreviations
@@ -767,29 +1067,50 @@ impl LanguageConfig {
         for key in keys {
             let key_str: String = key.extract()?;
             if let Some(value) = abbrev_categories.get_item(&key)? {
-                let words: Vec<String> = value.extract()?;
-                categories.insert(key_str, words);
+                let entries = value
+                    .try_iter()?
+                    .map(|item| abbreviation_from_py(&item?))
+                    .collect::<PyResult<Vec<_>>>()?;
+                categories.insert(key_str, entries);
             }
         }
         let abbreviations = CoreAbbreviationConfig { categories };
 
         // Convert sentence starters
-        let sentence_starters = self.sentence_starters.as_ref().map(|ss| {
-            let starter_categories = ss.categories.bind(py);
-            let mut categories = HashMap::new();
-            let keys = starter_categories.keys().iter();
-            for key in keys {
-                let key_str: String = key.extract().unwrap();
-                if let Some(value) = starter_categories.get_item(&key).unwrap() {
-                    let words: Vec<String> = value.extract().unwrap();
-                    categories.insert(key_str, words);
+        let sentence_starters = self
+            .sentence_starters
+            .as_ref()
+            .map(|ss| -> PyResult<_> {
+                let starter_categories = ss.categories.bind(py);
+                let mut categories = HashMap::new();
+                let keys = starter_categories.keys().iter();
+                for key in keys {
+                    let key_str: String = key.extract()?;
+                    if let Some(value) = starter_categories.get_item(&key)? {
+                        let entries = value
+                            .try_iter()?
+                            .map(|item| starter_from_py(&item?))
+                            .collect::<PyResult<Vec<_>>>()?;
+                        categories.insert(key_str, entries);
+                    }
                 }
-            }
-            CoreSentenceStarterConfig {
-                categories,
-                require_following_space: ss.require_following_space,
-                min_word_length: ss.min_word_length,
-            }
+                Ok(CoreSentenceStarterConfig {
+                    categories,
+                    require_following_space: ss.require_following_space,
+                    min_word_length: ss.min_word_length,
+                    threshold: ss.threshold,
+                    uppercase_fallback: ss.uppercase_fallback,
+                })
+            })
+            .transpose()?;
+
+        // Convert hypothesis strategy
+        let hypothesis = self.hypothesis.as_ref().map(|h| CoreHypothesisConfig {
+            strategy: h.strategy.clone(),
+            min_chars: h.min_chars,
+            sentence_final_particles: h.sentence_final_particles.clone(),
+            discourse_markers: h.discourse_markers.clone(),
+            continuation_markers: h.continuation_markers.clone(),
         });
 
         Ok(CoreLanguageConfig {
@@ -800,6 +1121,7 @@ impl LanguageConfig {
             suppression,
             abbreviations,
             sentence_starters,
+            hypothesis,
         })
     }
 }
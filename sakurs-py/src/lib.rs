@@ -8,28 +8,36 @@
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 
+mod aio;
 mod exceptions;
 mod input;
 mod iterator;
 mod language_config;
 mod output;
 mod processor;
+mod series;
 mod stream;
+pub mod stub_gen;
 mod types;
 
 use exceptions::{register_exceptions, InternalError};
 use input::PyInput;
 use language_config::LanguageConfig;
-use output::{boundaries_to_sentences_with_char_offsets, ProcessingMetadata, Sentence};
+use output::{
+    boundaries_to_numpy_offsets, boundaries_to_sentences_with_char_offsets, with_token_estimator,
+    ProcessingMetadata, Sentence, TextWarning, TokenEstimatorName,
+};
 use processor::PyProcessor;
 use sakurs_core::{Config, SentenceProcessor};
+use std::path::PathBuf;
 use std::time::Instant;
 
 /// Split text into sentences
 ///
 /// Args:
-///     input: Text string, file path, bytes, or file-like object to split
-///     language: Language code ("en", "ja") for built-in rules (default: "en")
+///     input: Text string, file path (str or os.PathLike), bytes-like object
+///         (bytes, bytearray, memoryview, mmap), or file-like object to split
+///     language: Language code ("en", "ja", "pt", "it", "ar", "th", "hi") for built-in rules (default: "en")
 ///     language_config: Custom language configuration
 ///     threads: Number of threads for parallel processing (None for auto)
 ///     chunk_kb: Chunk size in KB for parallel processing (default: 256)
@@ -37,12 +45,18 @@ use std::time::Instant;
 ///     execution_mode: Processing strategy ("sequential", "parallel", "adaptive")
 ///     return_details: Return Sentence objects with metadata instead of strings
 ///     preserve_whitespace: Keep leading/trailing whitespace in sentences (default: False)
-///     encoding: Text encoding for file/binary inputs (default: "utf-8")
+///     encoding: Text encoding for file and bytes inputs; text-mode file
+///         objects are decoded by the file itself (default: "utf-8")
+///     token_estimator: Estimate each sentence's token count ("whitespace" or
+///         "bpe") into Sentence.token_estimate (default: None)
+///     return_offsets: "numpy" to return the character offsets of the
+///         sentences as two int64 NumPy arrays instead (default: None)
 ///
 /// Returns:
-///     List of sentence strings or Sentence objects if return_details=True
+///     List of sentence strings or Sentence objects if return_details=True,
+///     or a tuple (starts, ends) of NumPy arrays if return_offsets="numpy"
 #[pyfunction]
-#[pyo3(signature = (input, *, language=None, language_config=None, threads=None, chunk_kb=None, parallel=false, execution_mode="adaptive", return_details=false, preserve_whitespace=false, encoding="utf-8"))]
+#[pyo3(signature = (input, *, language=None, language_config=None, threads=None, chunk_kb=None, parallel=false, execution_mode="adaptive", return_details=false, preserve_whitespace=false, encoding="utf-8", token_estimator=None, return_offsets=None))]
 #[allow(clippy::too_many_arguments)]
 #[allow(unused_variables)]
 fn split(
@@ -56,9 +70,12 @@ fn split(
     return_details: bool,
     preserve_whitespace: bool,
     encoding: &str,
+    token_estimator: Option<&str>,
+    return_offsets: Option<&str>,
     py: Python,
 ) -> PyResult<Py<PyAny>> {
     let start_time = Instant::now();
+    let numpy_offsets = parse_return_offsets(return_offsets, return_details)?;
 
     // Extract input from Python object
     let py_input = PyInput::from_py_object(py, input)?;
@@ -66,6 +83,43 @@ fn split(
     // Convert to core Input type and get the text content
     let (core_input, text) = py_input.into_core_input_and_text(py, encoding)?;
 
+    let processor = build_processor(
+        py,
+        language,
+        language_config,
+        threads,
+        chunk_kb,
+        execution_mode,
+        token_estimator,
+    )?;
+
+    // Release GIL during processing for better performance
+    let output = py
+        .detach(|| processor.process(core_input))
+        .map_err(|e| InternalError::ProcessingError(e.to_string()))?;
+
+    sentences_to_py(
+        py,
+        &text,
+        &output,
+        start_time,
+        return_details,
+        numpy_offsets,
+        preserve_whitespace,
+    )
+}
+
+/// Build the processor of `split` and `asplit`
+#[allow(clippy::too_many_arguments)]
+fn build_processor(
+    py: Python,
+    language: Option<&str>,
+    language_config: Option<LanguageConfig>,
+    threads: Option<usize>,
+    chunk_kb: Option<usize>,
+    execution_mode: &str,
+    token_estimator: Option<&str>,
+) -> PyResult<SentenceProcessor> {
     // Build configuration and optionally a custom language config
     let (mut config_builder, custom_language) = if let Some(lang_config) = language_config {
         // Use custom language configuration
@@ -80,10 +134,9 @@ fn split(
         )
     } else {
         // Use built-in language
-        let lang_code = match language.unwrap_or("en").to_lowercase().as_str() {
-            "en" | "english" => "en",
-            "ja" | "japanese" => "ja",
-            _ => {
+        let lang_code = match crate::builtin_language(language.unwrap_or("en")) {
+            Some(code) => code,
+            None => {
                 return Err(InternalError::UnsupportedLanguage(
                     language.unwrap_or("unknown").to_string(),
                 )
@@ -107,7 +160,6 @@ fn split(
         "parallel" => {
             // Use provided threads or let it default to all available
             config_builder = config_builder.threads(threads);
-            let _ = parallel; // historical flag; thread count drives parallelism
         }
         "adaptive" => {
             // Let the system decide based on text size
@@ -126,6 +178,7 @@ fn split(
     if let Some(kb) = chunk_kb {
         config_builder = config_builder.chunk_size(kb * 1024);
     }
+    config_builder = with_token_estimator(config_builder, token_estimator)?;
 
     let config = config_builder
         .build()
@@ -139,52 +192,54 @@ fn split(
         SentenceProcessor::with_config(config)
             .map_err(|e| InternalError::ProcessingError(e.to_string()))?
     };
+    Ok(processor)
+}
 
-    // execution_mode is now properly handled in the configuration above
-
-    // Release GIL during processing for better performance
-    let output = py
-        .detach(|| processor.process(core_input))
-        .map_err(|e| InternalError::ProcessingError(e.to_string()))?;
+/// Whether `return_offsets` asks for NumPy offset arrays, which excludes
+/// `return_details`
+fn parse_return_offsets(return_offsets: Option<&str>, return_details: bool) -> PyResult<bool> {
+    match return_offsets {
+        None => Ok(false),
+        Some("numpy") if return_details => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "return_offsets and return_details cannot be combined",
+        )),
+        Some("numpy") => Ok(true),
+        Some(other) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unknown return_offsets '{other}': expected numpy"
+        ))),
+    }
+}
 
+/// The sentences of `split` and `asplit` as a Python list, of strings or of
+/// `Sentence` objects with `return_details`, or their offsets as NumPy
+/// arrays with `numpy_offsets`
+fn sentences_to_py(
+    py: Python,
+    text: &str,
+    output: &sakurs_core::Output,
+    start_time: Instant,
+    return_details: bool,
+    numpy_offsets: bool,
+    preserve_whitespace: bool,
+) -> PyResult<Py<PyAny>> {
     let processing_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
 
+    if numpy_offsets {
+        return boundaries_to_numpy_offsets(text, &output.boundaries, preserve_whitespace, py);
+    }
+
     if return_details {
         // Return list of Sentence objects with character offsets
-        let boundaries_with_offsets: Vec<(usize, usize)> = output
-            .boundaries
-            .iter()
-            .map(|b| (b.char_offset, b.offset))
-            .collect();
         let sentences = boundaries_to_sentences_with_char_offsets(
-            &text,
-            &boundaries_with_offsets,
+            text,
+            &output.boundaries,
+            &output.token_estimates,
             preserve_whitespace,
             py,
         )?;
 
-        // Determine actual execution mode used (from strategy)
-        let execution_mode_str = match output.metadata.strategy_used.as_str() {
-            "Sequential" => "sequential",
-            "Parallel" => "parallel",
-            _ => "adaptive",
-        };
-
-        // Determine threads used (heuristic based on chunks)
-        let threads_used = if output.metadata.chunks_processed > 1 {
-            output.metadata.chunks_processed.min(8) // Reasonable estimate
-        } else {
-            1
-        };
-
-        // Create metadata
-        let _metadata = ProcessingMetadata::new(
-            sentences.len(),
-            processing_time_ms,
-            threads_used,
-            256, // Default chunk size in KB - we don't have access to actual value
-            execution_mode_str.to_string(),
-        );
+        let _metadata =
+            ProcessingMetadata::from_output(output, text, sentences.len(), processing_time_ms);
 
         // Return list of sentences directly when return_details=True
         Ok(PyList::new(py, sentences)?.unbind().into())
@@ -235,6 +290,65 @@ fn split(
     }
 }
 
+/// Split text into sentences without blocking the asyncio event loop
+///
+/// Takes the same arguments as `split`; the input is read and split on a
+/// background thread. Must be called from a running event loop.
+///
+/// Returns:
+///     Awaitable of the list of sentence strings, of Sentence objects if
+///     return_details=True, or of the (starts, ends) NumPy arrays if
+///     return_offsets="numpy"
+#[pyfunction]
+#[pyo3(signature = (input, *, language=None, language_config=None, threads=None, chunk_kb=None, parallel=false, execution_mode="adaptive", return_details=false, preserve_whitespace=false, encoding="utf-8", token_estimator=None, return_offsets=None))]
+#[allow(clippy::too_many_arguments)]
+#[allow(unused_variables)]
+fn asplit(
+    input: &Bound<'_, PyAny>,
+    language: Option<&str>,
+    language_config: Option<LanguageConfig>,
+    threads: Option<usize>,
+    chunk_kb: Option<usize>,
+    parallel: bool,
+    execution_mode: &str,
+    return_details: bool,
+    preserve_whitespace: bool,
+    encoding: &str,
+    token_estimator: Option<&str>,
+    return_offsets: Option<&str>,
+    py: Python,
+) -> PyResult<Py<PyAny>> {
+    let start_time = Instant::now();
+    let numpy_offsets = parse_return_offsets(return_offsets, return_details)?;
+    let py_input = PyInput::from_py_object(py, input)?;
+    let processor = build_processor(
+        py,
+        language,
+        language_config,
+        threads,
+        chunk_kb,
+        execution_mode,
+        token_estimator,
+    )?;
+    let encoding = encoding.to_string();
+
+    aio::spawn(py, move |py| {
+        let (core_input, text) = py_input.into_core_input_and_text(py, &encoding)?;
+        let output = py
+            .detach(|| processor.process(core_input))
+            .map_err(|e| InternalError::ProcessingError(e.to_string()))?;
+        sentences_to_py(
+            py,
+            &text,
+            &output,
+            start_time,
+            return_details,
+            numpy_offsets,
+            preserve_whitespace,
+        )
+    })
+}
+
 /// Load a sentence splitter for the specified language (spaCy-style API)
 #[pyfunction]
 #[pyo3(signature = (language, *, threads=None, chunk_kb=None, execution_mode="adaptive"))]
@@ -254,23 +368,26 @@ fn load(
         execution_mode,
         false, // streaming
         10,    // stream_chunk_mb (not used when streaming=false)
+        None,  // token_estimator
         py,
     )
 }
 
 /// Process input and return sentences as an iterator
 ///
-/// This function loads the entire input into memory but returns results
-/// incrementally for responsive processing. For true memory-efficient
-/// streaming of large files, use split_large_file().
+/// Files and file-like objects are read in chunks of `chunk_kb` as the
+/// iterator advances, so memory stays proportional to the chunk size rather
+/// than the input size. Results are identical to split().
 ///
 /// Args:
-///     input: Text string, file path, bytes, or file-like object
-///     language: Language code ("en", "ja") for built-in rules (default: "en")
+///     input: Text string, file path (str or os.PathLike), bytes-like object
+///         (bytes, bytearray, memoryview, mmap), or file-like object
+///     language: Language code ("en", "ja", "pt", "it", "ar", "th", "hi") for built-in rules (default: "en")
 ///     language_config: Custom language configuration
 ///     threads: Number of threads for parallel processing (None for auto)
-///     chunk_kb: Chunk size in KB for parallel processing (default: 256)
-///     encoding: Text encoding for file/binary inputs (default: "utf-8")
+///     chunk_kb: Chunk size in KB for reading and processing (default: 256)
+///     encoding: Text encoding for file and bytes inputs; text-mode file
+///         objects are decoded by the file itself (default: "utf-8")
 ///
 /// Returns:
 ///     Iterator that yields sentences one at a time
@@ -297,6 +414,38 @@ fn iter_split(
     )
 }
 
+/// Process input and return sentences as an async iterator
+///
+/// Takes the same arguments as `iter_split`. The input is read and split on
+/// a background thread, a bounded number of sentences ahead of the
+/// consumer, so `async for` never blocks the event loop.
+///
+/// Returns:
+///     Async iterator that yields sentences one at a time
+#[pyfunction]
+#[pyo3(signature = (input, *, language=None, language_config=None, threads=None, chunk_kb=None, encoding="utf-8"))]
+#[allow(clippy::too_many_arguments)]
+fn aiter_split(
+    input: &Bound<'_, PyAny>,
+    language: Option<&str>,
+    language_config: Option<LanguageConfig>,
+    threads: Option<usize>,
+    chunk_kb: Option<usize>,
+    encoding: &str,
+    py: Python,
+) -> PyResult<aio::AsyncSentenceIterator> {
+    let iterator = iter_split(
+        input,
+        language,
+        language_config,
+        threads,
+        chunk_kb,
+        encoding,
+        py,
+    )?;
+    aio::AsyncSentenceIterator::new(iterator)
+}
+
 /// Process large files with limited memory usage
 ///
 /// This function reads and processes the file in chunks, ensuring memory
@@ -305,8 +454,8 @@ fn iter_split(
 /// chunk is processed.
 ///
 /// Args:
-///     file_path: Path to the file to process
-///     language: Language code ("en", "ja") for built-in rules (default: "en")
+///     file_path: Path to the file to process (str or os.PathLike)
+///     language: Language code ("en", "ja", "pt", "it", "ar", "th", "hi") for built-in rules (default: "en")
 ///     language_config: Custom language configuration
 ///     max_memory_mb: Maximum memory to use in MB (default: 100)
 ///     overlap_size: Bytes to overlap between chunks for boundary handling (default: 1024)
@@ -323,7 +472,7 @@ fn iter_split(
 #[pyo3(signature = (file_path, *, language=None, language_config=None, max_memory_mb=100, overlap_size=1024, encoding="utf-8"))]
 #[allow(clippy::too_many_arguments)]
 fn split_large_file(
-    file_path: &str,
+    file_path: PathBuf,
     language: Option<&str>,
     language_config: Option<LanguageConfig>,
     max_memory_mb: usize,
@@ -342,10 +491,113 @@ fn split_large_file(
     )
 }
 
+/// Split a column of documents into sentences (pandas/pyarrow convenience API)
+///
+/// The whole column is processed in Rust with the GIL released, documents
+/// in parallel, avoiding a Python-level loop over DataFrame rows.
+///
+/// Args:
+///     column: pandas Series, pyarrow Array/ChunkedArray, or any iterable of strings
+///     language: Language code ("en", "ja", "pt", "it", "ar", "th", "hi") for built-in rules (default: "en")
+///     language_config: Custom language configuration
+///     threads: Number of threads for processing each document (None for auto)
+///     chunk_kb: Chunk size in KB for processing each document (default: 256)
+///     return_arrow: Return a pyarrow ListArray of strings instead of lists
+///
+/// Returns:
+///     One list of sentences per document, in column order; missing values
+///     (None, NaN, pandas.NA, Arrow nulls) stay None
+#[pyfunction]
+#[pyo3(signature = (column, *, language=None, language_config=None, threads=None, chunk_kb=None, return_arrow=false))]
+fn split_series(
+    column: &Bound<'_, PyAny>,
+    language: Option<&str>,
+    language_config: Option<LanguageConfig>,
+    threads: Option<usize>,
+    chunk_kb: Option<usize>,
+    return_arrow: bool,
+    py: Python,
+) -> PyResult<Py<PyAny>> {
+    let processor = PyProcessor::new(
+        language,
+        language_config,
+        threads,
+        chunk_kb,
+        "adaptive",
+        false, // streaming
+        10,    // stream_chunk_mb (not used when streaming=false)
+        None,  // token_estimator
+        py,
+    )?;
+    processor.split_series(column, return_arrow, py)
+}
+
+/// Group consecutive sentences into chunks of at most `max_tokens` tokens,
+/// for language model contexts
+///
+/// Sentences are added to the current chunk while they fit and start a new
+/// chunk otherwise; a sentence longer than `max_tokens` is a chunk by itself.
+/// A Sentence counts its token_estimate when it has one; other sentences
+/// are estimated with `token_estimator`.
+///
+/// Args:
+///     sentences: Sentence strings or Sentence objects, in order
+///     max_tokens: Most tokens per chunk
+///     token_estimator: Estimator for sentences without an estimate
+///         ("whitespace" or "bpe", default: "bpe")
+///
+/// Returns:
+///     List of chunks, each a list of the given sentences
+#[pyfunction]
+#[pyo3(signature = (sentences, max_tokens, *, token_estimator="bpe"))]
+fn chunk_sentences(
+    sentences: Vec<Bound<'_, PyAny>>,
+    max_tokens: usize,
+    token_estimator: &str,
+    py: Python,
+) -> PyResult<Py<PyAny>> {
+    let estimator = TokenEstimatorName::parse(token_estimator)?;
+    let counts = sentences
+        .iter()
+        .map(|sentence| {
+            if let Ok(sentence) = sentence.cast::<Sentence>() {
+                let sentence = sentence.borrow();
+                return Ok(sentence
+                    .token_estimate
+                    .unwrap_or_else(|| estimator.estimate(&sentence.text)));
+            }
+            let text: &str = sentence.extract().map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                    "chunk_sentences expects str or Sentence items",
+                )
+            })?;
+            Ok(estimator.estimate(text))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let chunks = sakurs_core::chunk_sentences(&counts, max_tokens)
+        .into_iter()
+        .map(|range| PyList::new(py, &sentences[range]))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, chunks)?.unbind().into())
+}
+
 /// Get list of supported languages
 #[pyfunction]
 fn supported_languages() -> Vec<&'static str> {
-    vec!["en", "ja"]
+    sakurs_core::LanguageConfig::embedded()
+        .into_iter()
+        .map(|config| config.metadata.code.as_str())
+        .collect()
+}
+
+/// The code of the built-in language `name` names: its code or its English
+/// name, in any case
+pub(crate) fn builtin_language(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    sakurs_core::LanguageConfig::embedded()
+        .into_iter()
+        .find(|config| config.metadata.code == name || config.metadata.name.to_lowercase() == name)
+        .map(|config| config.metadata.code.as_str())
 }
 
 /// Main Python module for sakurs
@@ -357,7 +609,9 @@ fn sakurs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyProcessor>()?;
     m.add_class::<Sentence>()?;
     m.add_class::<ProcessingMetadata>()?;
+    m.add_class::<TextWarning>()?;
     m.add_class::<iterator::SentenceIterator>()?;
+    m.add_class::<aio::AsyncSentenceIterator>()?;
     m.add_class::<stream::LargeFileIterator>()?;
 
     // Language configuration classes
@@ -373,14 +627,20 @@ fn sakurs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<language_config::SuppressionConfig>()?;
     m.add_class::<language_config::FastPattern>()?;
     m.add_class::<language_config::RegexPattern>()?;
+    m.add_class::<language_config::ApostropheConfig>()?;
     m.add_class::<language_config::AbbreviationConfig>()?;
     m.add_class::<language_config::SentenceStarterConfig>()?;
+    m.add_class::<language_config::HypothesisConfig>()?;
 
     // Main API functions
     m.add_function(pyo3::wrap_pyfunction!(split, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(asplit, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(load, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(iter_split, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(aiter_split, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(split_large_file, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(split_series, m)?)?;
+    m.add_function(pyo3::wrap_pyfunction!(chunk_sentences, m)?)?;
     m.add_function(pyo3::wrap_pyfunction!(supported_languages, m)?)?;
 
     // Register custom exceptions
@@ -435,6 +695,8 @@ mod tests {
                 false, // return_details
                 false, // preserve_whitespace
                 "utf-8",
+                None, // token_estimator
+                None, // return_offsets
                 py,
             );
             assert!(result.is_ok());
@@ -465,6 +727,8 @@ mod tests {
                 true,  // return_details
                 false, // preserve_whitespace
                 "utf-8",
+                None, // token_estimator
+                None, // return_offsets
                 py,
             );
             assert!(result.is_ok());
@@ -482,6 +746,11 @@ mod tests {
         let languages = supported_languages();
         assert!(languages.contains(&"en"));
         assert!(languages.contains(&"ja"));
-        assert_eq!(languages.len(), 2);
+        assert!(languages.contains(&"pt"));
+        assert!(languages.contains(&"it"));
+        assert!(languages.contains(&"ar"));
+        assert!(languages.contains(&"th"));
+        assert!(languages.contains(&"hi"));
+        assert_eq!(languages.len(), 7);
     }
 }
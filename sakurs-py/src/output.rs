@@ -1,7 +1,16 @@
 //! Output types for Python bindings
 
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyByteArray, PyDict, PyTuple};
+use sakurs_core::{Boundary, BoundaryKind, BpeEstimator, TokenEstimator, WhitespaceEstimator};
+
+/// Boundary kinds by the names `Sentence.kind` uses
+const BOUNDARY_KINDS: [BoundaryKind; 4] = [
+    BoundaryKind::Strong,
+    BoundaryKind::Weak,
+    BoundaryKind::Forced,
+    BoundaryKind::EndOfText,
+];
 
 /// Sentence with metadata
 #[pyclass]
@@ -25,21 +34,40 @@ pub struct Sentence {
     /// Additional metadata as a dictionary
     #[pyo3(get)]
     pub metadata: Py<PyAny>,
+
+    /// How the sentence ends: "strong" (!, ?), "weak" (a period or other
+    /// boundary accepted in context), "forced" (split for length) or
+    /// "end_of_text" (trailing text without a terminator)
+    #[pyo3(get)]
+    pub kind: String,
+
+    /// Approximate token count of the sentence, when a token estimator was
+    /// given
+    #[pyo3(get)]
+    pub token_estimate: Option<usize>,
 }
 
 #[pymethods]
 impl Sentence {
     /// Create a new Sentence instance
     #[new]
-    #[pyo3(signature = (text, start, end, confidence=1.0, metadata=None))]
+    #[pyo3(signature = (text, start, end, confidence=1.0, metadata=None, kind="weak", token_estimate=None))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         text: String,
         start: usize,
         end: usize,
         confidence: Option<f32>,
         metadata: Option<Bound<'_, PyDict>>,
+        kind: &str,
+        token_estimate: Option<usize>,
         py: Python,
     ) -> PyResult<Self> {
+        if !BOUNDARY_KINDS.iter().any(|k| k.as_str() == kind) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown sentence kind '{kind}': expected strong, weak, forced or end_of_text"
+            )));
+        }
         let metadata = if let Some(dict) = metadata {
             dict.unbind()
         } else {
@@ -52,13 +80,15 @@ impl Sentence {
             end,
             confidence: confidence.unwrap_or(1.0),
             metadata: metadata.into(),
+            kind: kind.to_string(),
+            token_estimate,
         })
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "Sentence(text='{}', start={}, end={}, confidence={})",
-            self.text, self.start, self.end, self.confidence
+            "Sentence(text='{}', start={}, end={}, confidence={}, kind='{}')",
+            self.text, self.start, self.end, self.confidence, self.kind
         )
     }
 
@@ -67,6 +97,39 @@ impl Sentence {
     }
 }
 
+/// A problem noticed in the text, such as a suspiciously long sentence or
+/// invalid UTF-8
+#[pyclass]
+#[derive(Clone)]
+pub struct TextWarning {
+    /// What kind of problem: "unclosed_enclosure", "unmatched_closer",
+    /// "depth_exceeded", "long_sentence", "forced_split" or "invalid_utf8"
+    #[pyo3(get)]
+    pub kind: String,
+
+    /// Character offset of the problem in the text
+    #[pyo3(get)]
+    pub offset: usize,
+
+    /// The problem in words
+    #[pyo3(get)]
+    pub message: String,
+}
+
+#[pymethods]
+impl TextWarning {
+    fn __repr__(&self) -> String {
+        format!(
+            "TextWarning(kind='{}', offset={}, message='{}')",
+            self.kind, self.offset, self.message
+        )
+    }
+
+    fn __str__(&self) -> String {
+        format!("{} at character {}", self.message, self.offset)
+    }
+}
+
 /// Processing statistics and metadata
 #[pyclass]
 #[derive(Clone)]
@@ -90,18 +153,79 @@ pub struct ProcessingMetadata {
     /// Execution mode used ("sequential", "parallel", or "adaptive")
     #[pyo3(get)]
     pub execution_mode_used: String,
+
+    /// Why the thread count was chosen: the text size against the adaptive
+    /// threshold, and the threads available
+    #[pyo3(get)]
+    pub execution_reason: String,
+
+    /// Text bytes per thread of the adaptive policy
+    #[pyo3(get)]
+    pub bytes_per_thread: usize,
+
+    /// Threads available on the machine, if the decision consulted them
+    #[pyo3(get)]
+    pub available_threads: Option<usize>,
+
+    /// Problems noticed in the text, in text order
+    #[pyo3(get)]
+    pub warnings: Vec<TextWarning>,
+}
+
+impl ProcessingMetadata {
+    /// The metadata of a processing run over `text` that found
+    /// `total_sentences`
+    pub fn from_output(
+        output: &sakurs_core::Output,
+        text: &str,
+        total_sentences: usize,
+        processing_time_ms: f64,
+    ) -> Self {
+        let execution = &output.metadata.execution;
+        let mode = match execution.mode {
+            sakurs_core::ExecutionMode::Sequential => "sequential",
+            sakurs_core::ExecutionMode::Parallel { .. } => "parallel",
+            sakurs_core::ExecutionMode::Adaptive => "adaptive",
+        };
+        Self {
+            total_sentences,
+            processing_time_ms,
+            threads_used: execution.thread_count,
+            chunk_kb_used: execution.chunk_size / 1024,
+            execution_mode_used: mode.to_string(),
+            execution_reason: execution.to_string(),
+            bytes_per_thread: execution.bytes_per_thread,
+            available_threads: execution.available_threads,
+            warnings: output
+                .warnings
+                .iter()
+                .map(|warning| TextWarning {
+                    kind: warning.kind().to_string(),
+                    offset: text
+                        .get(..warning.offset())
+                        .map_or(warning.offset(), |before| before.chars().count()),
+                    message: warning.to_string(),
+                })
+                .collect(),
+        }
+    }
 }
 
 #[pymethods]
 impl ProcessingMetadata {
     /// Create a new ProcessingMetadata instance
     #[new]
+    #[pyo3(signature = (total_sentences, processing_time_ms, threads_used, chunk_kb_used, execution_mode_used, execution_reason=String::new(), bytes_per_thread=0, available_threads=None))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         total_sentences: usize,
         processing_time_ms: f64,
         threads_used: usize,
         chunk_kb_used: usize,
         execution_mode_used: String,
+        execution_reason: String,
+        bytes_per_thread: usize,
+        available_threads: Option<usize>,
     ) -> Self {
         Self {
             total_sentences,
@@ -109,53 +233,123 @@ impl ProcessingMetadata {
             threads_used,
             chunk_kb_used,
             execution_mode_used,
+            execution_reason,
+            bytes_per_thread,
+            available_threads,
+            warnings: Vec::new(),
         }
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "ProcessingMetadata(total_sentences={}, processing_time_ms={:.2}, threads_used={}, chunk_kb_used={}, execution_mode_used='{}')",
+            "ProcessingMetadata(total_sentences={}, processing_time_ms={:.2}, threads_used={}, chunk_kb_used={}, execution_mode_used='{}', execution_reason='{}')",
             self.total_sentences,
             self.processing_time_ms,
             self.threads_used,
             self.chunk_kb_used,
-            self.execution_mode_used
+            self.execution_mode_used,
+            self.execution_reason
         )
     }
 }
 
-/// Helper function to convert boundaries and text into Sentence objects using character offsets
-pub fn boundaries_to_sentences_with_char_offsets(
-    text: &str,
-    boundaries: &[(usize, usize)], // (char_offset, byte_offset)
-    preserve_whitespace: bool,
-    py: Python,
-) -> PyResult<Vec<Sentence>> {
-    let mut sentences = Vec::new();
+/// The token estimators selectable by name
+#[derive(Debug, Clone, Copy)]
+pub enum TokenEstimatorName {
+    /// One token per whitespace-separated word
+    Whitespace,
+    /// An approximation of BPE tokenizers
+    Bpe,
+}
+
+impl TokenEstimatorName {
+    /// The estimator called `name` ("whitespace" or "bpe")
+    pub fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "whitespace" => Ok(Self::Whitespace),
+            "bpe" => Ok(Self::Bpe),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown token estimator '{name}': expected whitespace or bpe"
+            ))),
+        }
+    }
+
+    /// Set the estimator on a configuration builder
+    pub fn apply(self, builder: sakurs_core::ConfigBuilder) -> sakurs_core::ConfigBuilder {
+        match self {
+            Self::Whitespace => builder.token_estimator(WhitespaceEstimator),
+            Self::Bpe => builder.token_estimator(BpeEstimator),
+        }
+    }
+
+    /// The approximate token count of `text`
+    pub fn estimate(self, text: &str) -> usize {
+        match self {
+            Self::Whitespace => WhitespaceEstimator.estimate(text),
+            Self::Bpe => BpeEstimator.estimate(text),
+        }
+    }
+}
+
+/// Set the token estimator called `name`, if any, on a configuration builder
+pub fn with_token_estimator(
+    builder: sakurs_core::ConfigBuilder,
+    name: Option<&str>,
+) -> PyResult<sakurs_core::ConfigBuilder> {
+    match name {
+        Some(name) => Ok(TokenEstimatorName::parse(name)?.apply(builder)),
+        None => Ok(builder),
+    }
+}
+
+/// A sentence of a text, as returned to Python
+struct Span<'a> {
+    /// Number of the sentence, as in `sakurs_core::Output::token_estimates`
+    index: usize,
+    kind: BoundaryKind,
+    /// The sentence, trimmed unless whitespace is preserved
+    text: &'a str,
+    /// Character offsets of the sentence before trimming
+    start: usize,
+    end: usize,
+}
+
+/// The sentences of `text` ending at `boundaries`, skipping blank ones
+/// unless `preserve_whitespace` is set
+fn spans<'a>(text: &'a str, boundaries: &[Boundary], preserve_whitespace: bool) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
     let mut start_char = 0;
     let mut start_byte = 0;
+    let mut push = |index, kind, start_byte, end_byte, start, end| {
+        let sentence = &text[start_byte..end_byte];
+        let sentence = if preserve_whitespace {
+            sentence
+        } else {
+            sentence.trim()
+        };
+        // Skip empty sentences
+        if preserve_whitespace || !sentence.is_empty() {
+            spans.push(Span {
+                index,
+                kind,
+                text: sentence,
+                start,
+                end,
+            });
+        }
+    };
 
-    for &(end_char, end_byte) in boundaries {
+    for (index, boundary) in boundaries.iter().enumerate() {
+        let (end_char, end_byte) = (boundary.char_offset, boundary.offset);
         if end_char > start_char && end_byte <= text.len() {
-            let sentence_text = text[start_byte..end_byte].to_string();
-
-            // Calculate offsets based on whether we trim whitespace
-            let (final_text, final_start, final_end) = if preserve_whitespace {
-                (sentence_text, start_char, end_char)
-            } else {
-                // Trim the text but keep original offsets
-                let trimmed = sentence_text.trim();
-                if trimmed.is_empty() {
-                    // Skip empty sentences
-                    start_char = end_char;
-                    start_byte = end_byte;
-                    continue;
-                }
-                (trimmed.to_string(), start_char, end_char)
-            };
-
-            let sentence = Sentence::new(final_text, final_start, final_end, Some(1.0), None, py)?;
-            sentences.push(sentence);
+            push(
+                index,
+                boundary.kind,
+                start_byte,
+                end_byte,
+                start_char,
+                end_char,
+            );
             start_char = end_char;
             start_byte = end_byte;
         }
@@ -163,25 +357,72 @@ pub fn boundaries_to_sentences_with_char_offsets(
 
     // Handle any remaining text after the last boundary
     if start_byte < text.len() {
-        let sentence_text = text[start_byte..].to_string();
-        let char_count = text.chars().count();
-
-        let (final_text, final_start, final_end) = if preserve_whitespace {
-            (sentence_text, start_char, char_count)
-        } else {
-            // Trim the text but keep original offsets
-            let trimmed = sentence_text.trim();
-            if !trimmed.is_empty() {
-                (trimmed.to_string(), start_char, char_count)
-            } else {
-                // Skip empty sentences
-                return Ok(sentences);
-            }
-        };
-
-        let sentence = Sentence::new(final_text, final_start, final_end, Some(1.0), None, py)?;
-        sentences.push(sentence);
+        let char_count = start_char + text[start_byte..].chars().count();
+        push(
+            boundaries.len(),
+            BoundaryKind::EndOfText,
+            start_byte,
+            text.len(),
+            start_char,
+            char_count,
+        );
     }
+    spans
+}
 
-    Ok(sentences)
+/// Helper function to convert boundaries and text into Sentence objects using
+/// character offsets; each sentence takes the kind of the boundary ending it
+/// and its entry of `token_estimates` (numbered as in
+/// `sakurs_core::Output::token_estimates`), if any
+pub fn boundaries_to_sentences_with_char_offsets(
+    text: &str,
+    boundaries: &[Boundary],
+    token_estimates: &[usize],
+    preserve_whitespace: bool,
+    py: Python,
+) -> PyResult<Vec<Sentence>> {
+    spans(text, boundaries, preserve_whitespace)
+        .into_iter()
+        .map(|span| {
+            Sentence::new(
+                span.text.to_string(),
+                span.start,
+                span.end,
+                Some(1.0),
+                None,
+                span.kind.as_str(),
+                token_estimates.get(span.index).copied(),
+                py,
+            )
+        })
+        .collect()
+}
+
+/// The character offsets of the sentences of `text` as two int64 NumPy
+/// arrays `(starts, ends)`, the `start` and `end` of the `Sentence` objects
+/// [`boundaries_to_sentences_with_char_offsets`] would build
+pub fn boundaries_to_numpy_offsets(
+    text: &str,
+    boundaries: &[Boundary],
+    preserve_whitespace: bool,
+    py: Python,
+) -> PyResult<Py<PyAny>> {
+    let numpy = py.import("numpy").map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyImportError, _>(format!(
+            "return_offsets=\"numpy\" requires NumPy: {e}"
+        ))
+    })?;
+    let spans = spans(text, boundaries, preserve_whitespace);
+    // Native-endian int64 bytes, viewed by NumPy rather than converted one
+    // Python int at a time; a bytearray keeps the arrays writable
+    let array = |offsets: Vec<i64>| -> PyResult<Bound<'_, PyAny>> {
+        let bytes: Vec<u8> = offsets.iter().flat_map(|o| o.to_ne_bytes()).collect();
+        numpy.call_method1(
+            "frombuffer",
+            (PyByteArray::new(py, &bytes), numpy.getattr("int64")?),
+        )
+    };
+    let starts = array(spans.iter().map(|span| span.start as i64).collect())?;
+    let ends = array(spans.iter().map(|span| span.end as i64).collect())?;
+    Ok(PyTuple::new(py, [starts, ends])?.into_any().unbind())
 }
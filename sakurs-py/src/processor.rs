@@ -5,9 +5,12 @@
 use crate::exceptions::InternalError;
 use crate::input::PyInput;
 use crate::language_config::LanguageConfig;
+use crate::output::ProcessingMetadata;
 use crate::types::PyProcessingResult;
 use pyo3::prelude::*;
 use sakurs_core::{Config, SentenceProcessor};
+use std::sync::Mutex;
+use std::time::Instant;
 
 /// Main sentence splitter class for sentence boundary detection
 #[pyclass(name = "SentenceSplitter")]
@@ -18,13 +21,15 @@ pub struct PyProcessor {
     num_threads: Option<usize>,
     #[allow(dead_code)]
     custom_config: bool, // Track if using custom language config
+    /// Metadata of the last `split`
+    last_metadata: Mutex<Option<ProcessingMetadata>>,
 }
 
 #[pymethods]
 impl PyProcessor {
     /// Create a new processor for the specified language
     #[new]
-    #[pyo3(signature = (*, language=None, language_config=None, threads=None, chunk_kb=None, execution_mode="adaptive", streaming=false, stream_chunk_mb=10))]
+    #[pyo3(signature = (*, language=None, language_config=None, threads=None, chunk_kb=None, execution_mode="adaptive", streaming=false, stream_chunk_mb=10, token_estimator=None))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         language: Option<&str>,
@@ -34,6 +39,7 @@ impl PyProcessor {
         execution_mode: &str,
         streaming: bool,
         stream_chunk_mb: usize,
+        token_estimator: Option<&str>,
         py: Python,
     ) -> PyResult<Self> {
         // Convert KB/MB to bytes
@@ -63,10 +69,9 @@ impl PyProcessor {
             } else {
                 // Use built-in language
                 let lang = language.unwrap_or("en");
-                let lang_code = match lang.to_lowercase().as_str() {
-                    "en" | "english" => "en",
-                    "ja" | "japanese" => "ja",
-                    _ => return Err(InternalError::UnsupportedLanguage(lang.to_string()).into()),
+                let lang_code = match crate::builtin_language(lang) {
+                    Some(code) => code,
+                    None => return Err(InternalError::UnsupportedLanguage(lang.to_string()).into()),
                 };
                 (
                     Config::builder()
@@ -100,6 +105,7 @@ impl PyProcessor {
         }
 
         config_builder = config_builder.chunk_size(chunk_size_bytes);
+        config_builder = crate::output::with_token_estimator(config_builder, token_estimator)?;
 
         let rust_config = config_builder
             .build()
@@ -120,6 +126,7 @@ impl PyProcessor {
             chunk_size: chunk_size_bytes,
             num_threads: threads,
             custom_config: is_custom,
+            last_metadata: Mutex::new(None),
         })
     }
 
@@ -142,20 +149,24 @@ impl PyProcessor {
         let (core_input, text) = py_input.into_core_input_and_text(py, encoding)?;
 
         // Release GIL during processing for better performance
+        let start_time = Instant::now();
         let output = py
             .detach(|| self.processor.process(core_input))
             .map_err(|e| InternalError::ProcessingError(e.to_string()))?;
+        *self.last_metadata.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some(ProcessingMetadata::from_output(
+                &output,
+                &text,
+                output.boundaries.len(),
+                start_time.elapsed().as_secs_f64() * 1000.0,
+            ));
 
         if return_details {
             // Return list of Sentence objects
-            let boundaries_with_offsets: Vec<(usize, usize)> = output
-                .boundaries
-                .iter()
-                .map(|b| (b.char_offset, b.offset))
-                .collect();
             let sentences = boundaries_to_sentences_with_char_offsets(
                 &text,
-                &boundaries_with_offsets,
+                &output.boundaries,
+                &output.token_estimates,
                 false, // preserve_whitespace default to false
                 py,
             )?;
@@ -168,12 +179,34 @@ impl PyProcessor {
         }
     }
 
+    /// Split every document of a column (pandas Series, pyarrow Array, or
+    /// any iterable of strings) into a list of sentences, in parallel
+    #[pyo3(signature = (column, *, return_arrow=false))]
+    pub fn split_series(
+        &self,
+        column: &Bound<'_, PyAny>,
+        return_arrow: bool,
+        py: Python,
+    ) -> PyResult<Py<PyAny>> {
+        crate::series::split_series_with(py, &self.processor, column, return_arrow)
+    }
+
     /// Get supported language
     #[getter]
     fn language(&self) -> &str {
         &self.language
     }
 
+    /// Metadata of the last `split`: threads and chunk size used, and why
+    /// (None before the first)
+    #[getter]
+    fn last_metadata(&self) -> Option<ProcessingMetadata> {
+        self.last_metadata
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
     /// Check if the processor supports parallel processing
     #[getter]
     fn supports_parallel(&self) -> bool {
@@ -181,32 +214,21 @@ impl PyProcessor {
     }
 
     /// Iterate over sentences (memory-efficient)
-    #[pyo3(signature = (input, *, encoding="utf-8", _preserve_whitespace=false))]
+    #[pyo3(signature = (input, *, encoding="utf-8", preserve_whitespace=false))]
     pub fn iter_split(
         &self,
         input: &Bound<'_, PyAny>,
         encoding: &str,
-        _preserve_whitespace: bool,
+        preserve_whitespace: bool,
         py: Python,
     ) -> PyResult<crate::iterator::SentenceIterator> {
-        use crate::stream::create_iter_split_iterator;
-
-        // Extract language from processor
-        let language = if self.custom_config {
-            None // Custom config already embedded in processor
-        } else {
-            Some(self.language.as_str())
-        };
-
-        // Use the processor's configuration for streaming
-        create_iter_split_iterator(
+        crate::stream::iter_split_with(
             py,
+            &self.processor,
             input,
-            language,
-            None, // language_config already in processor
-            self.num_threads,
-            Some(self.chunk_size),
             encoding,
+            self.chunk_size,
+            preserve_whitespace,
         )
     }
 
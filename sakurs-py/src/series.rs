@@ -0,0 +1,140 @@
+//! Column-at-a-time splitting for DataFrame workflows
+
+use crate::exceptions::InternalError;
+use crate::types::PyProcessingResult;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyFloat, PyList, PyString};
+use sakurs_core::SentenceProcessor;
+
+/// Split every document of `column` with `processor`
+///
+/// The column is copied out of Python once, processed in Rust with the GIL
+/// released (documents run in parallel), and returned as one list of
+/// sentences per document: a Python list of lists, or a pyarrow
+/// `ListArray` of strings when `return_arrow` is set. Missing values (None,
+/// NaN, pandas.NA, Arrow nulls) stay missing in the result.
+pub fn split_series_with(
+    py: Python,
+    processor: &SentenceProcessor,
+    column: &Bound<'_, PyAny>,
+    return_arrow: bool,
+) -> PyResult<Py<PyAny>> {
+    let documents = column_values(column)?;
+    let texts: Vec<&str> = documents.iter().flatten().map(String::as_str).collect();
+
+    let outputs = py
+        .detach(|| processor.process_batch(&texts))
+        .map_err(|e| InternalError::ProcessingError(e.to_string()))?;
+
+    let mut outputs = outputs.into_iter();
+    let mut rows: Vec<Option<Vec<String>>> = Vec::with_capacity(documents.len());
+    for document in documents {
+        rows.push(document.map(|text| {
+            let output = outputs.next().expect("one output per document");
+            if text.trim().is_empty() {
+                return Vec::new();
+            }
+            let boundaries = output.boundaries.iter().map(|b| b.offset).collect();
+            PyProcessingResult::new(boundaries, output.metadata.stats, text).sentences()
+        }));
+    }
+
+    let rows = PyList::new(py, rows)?;
+    if return_arrow {
+        let pyarrow = py.import("pyarrow")?;
+        let list_type = pyarrow.call_method1("list_", (pyarrow.call_method0("string")?,))?;
+        let kwargs = [("type", list_type)].into_py_dict(py)?;
+        Ok(pyarrow
+            .call_method("array", (rows,), Some(&kwargs))?
+            .unbind())
+    } else {
+        Ok(rows.into_any().unbind())
+    }
+}
+
+/// Read a pandas Series, pyarrow Array/ChunkedArray, NumPy array or any
+/// iterable of strings into owned documents, `None` marking missing values
+fn column_values(column: &Bound<'_, PyAny>) -> PyResult<Vec<Option<String>>> {
+    // Bulk conversions avoid a Python-level call per element
+    let values = if column.hasattr("to_pylist")? {
+        column.call_method0("to_pylist")?
+    } else if column.hasattr("tolist")? {
+        column.call_method0("tolist")?
+    } else {
+        column.clone()
+    };
+
+    let mut documents = Vec::new();
+    for (index, value) in values.try_iter()?.enumerate() {
+        let value = value?;
+        if let Ok(text) = value.cast::<PyString>() {
+            documents.push(Some(text.to_str()?.to_owned()));
+        } else if is_missing(&value)? {
+            documents.push(None);
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "split_series expects a column of strings, found {} at position {index}",
+                value.get_type().name()?
+            )));
+        }
+    }
+    Ok(documents)
+}
+
+/// Whether `value` is a missing-value marker: None, a float NaN or pandas.NA
+fn is_missing(value: &Bound<'_, PyAny>) -> PyResult<bool> {
+    if value.is_none() {
+        return Ok(true);
+    }
+    if let Ok(float) = value.cast::<PyFloat>() {
+        return Ok(float.value().is_nan());
+    }
+    Ok(value.get_type().name()? == "NAType")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_series_lists_and_missing_values() {
+        Python::initialize();
+        Python::attach(|py| {
+            let processor = SentenceProcessor::new();
+            let column = PyList::new(
+                py,
+                [
+                    Some("Hello world. How are you?"),
+                    None,
+                    Some(""),
+                    Some("One sentence"),
+                ],
+            )
+            .unwrap();
+
+            let result = split_series_with(py, &processor, column.as_any(), false).unwrap();
+            let rows: Vec<Option<Vec<String>>> = result.extract(py).unwrap();
+            assert_eq!(
+                rows,
+                vec![
+                    Some(vec!["Hello world.".to_string(), "How are you?".to_string()]),
+                    None,
+                    Some(vec![]),
+                    Some(vec!["One sentence".to_string()]),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_split_series_rejects_non_strings() {
+        Python::initialize();
+        Python::attach(|py| {
+            let processor = SentenceProcessor::new();
+            let column = PyList::new(py, [1, 2]).unwrap();
+            let err = split_series_with(py, &processor, column.as_any(), false).unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+        });
+    }
+}
@@ -1,16 +1,19 @@
 //! Streaming functionality for processing large texts
 
 use crate::exceptions::InternalError;
+use crate::input::PieceDecoder;
 use crate::input::PyInput;
-use crate::iterator::SentenceIterator;
+use crate::iterator::{SentenceIterator, TextSource};
 use crate::language_config::LanguageConfig;
 use pyo3::prelude::*;
-use pyo3::types::PyIterator;
 use sakurs_core::{Config, SentenceProcessor};
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read};
 use std::path::PathBuf;
 
+/// Default read size for iter_split, matching the default chunk size (256KB)
+const DEFAULT_READ_SIZE: usize = 256 * 1024;
+
 /// Default chunk size for streaming (10MB)
 #[allow(dead_code)]
 const DEFAULT_CHUNK_SIZE_MB: usize = 10;
@@ -19,7 +22,12 @@ const DEFAULT_CHUNK_SIZE_MB: usize = 10;
 #[allow(dead_code)]
 const DEFAULT_OVERLAP_SIZE: usize = 1024;
 
-/// Create an iterator that loads all input but yields incrementally  
+/// Create an iterator that reads its input in bounded chunks
+///
+/// Text and bytes already in memory are fed to the core stream in
+/// `chunk_size` slices; paths and file-like objects are read `chunk_size`
+/// bytes (or characters, for text-mode files) at a time, so memory stays
+/// proportional to the chunk size rather than the input size.
 pub fn create_iter_split_iterator(
     py: Python,
     input: &Bound<'_, PyAny>,
@@ -42,10 +50,9 @@ pub fn create_iter_split_iterator(
         )
     } else {
         // Use built-in language
-        let lang_code = match language.unwrap_or("en").to_lowercase().as_str() {
-            "en" | "english" => "en",
-            "ja" | "japanese" => "ja",
-            _ => {
+        let lang_code = match crate::builtin_language(language.unwrap_or("en")) {
+            Some(code) => code,
+            None => {
                 return Err(InternalError::UnsupportedLanguage(
                     language.unwrap_or("unknown").to_string(),
                 )
@@ -81,138 +88,75 @@ pub fn create_iter_split_iterator(
             .map_err(|e| InternalError::ProcessingError(e.to_string()))?
     };
 
-    // Create iterator
-    let iterator = SentenceIterator::new_internal(false); // No whitespace preservation for now
-
-    // Process all input at once and populate iterator
-    let py_input = PyInput::from_py_object(py, input)?;
-
-    // Get the full text from input
-    let text = match py_input {
-        PyInput::Text(text) => text,
-        PyInput::Path(path) => {
-            std::fs::read_to_string(&path).map_err(|e| InternalError::IoError(e.to_string()))?
-        }
-        PyInput::Bytes(bytes) => {
-            String::from_utf8(bytes).map_err(|e| InternalError::EncodingError(e.to_string()))?
-        }
-        PyInput::FileObject(obj) => {
-            // Read entire content from file-like object
-            let obj_bound = obj.bind(py);
-            read_all_from_file_object(py, obj_bound, encoding)?
-        }
-    };
-
-    // Process the entire text at once
-    let input = sakurs_core::Input::from_text(&text);
-    let output = processor
-        .process(input)
-        .map_err(|e| InternalError::ProcessingError(e.to_string()))?;
-
-    // Convert boundaries to sentences and add to iterator
-    let mut sentences = Vec::new();
-    let mut last_pos = 0;
-
-    for boundary in output.boundaries {
-        let sentence = text[last_pos..boundary.offset].trim().to_string();
-        if !sentence.is_empty() {
-            sentences.push(sentence);
-        }
-        last_pos = boundary.offset;
-    }
-
-    // Add any remaining text
-    if last_pos < text.len() {
-        let sentence = text[last_pos..].trim().to_string();
-        if !sentence.is_empty() {
-            sentences.push(sentence);
-        }
-    }
-
-    // Add all sentences to the iterator
-    iterator.add_sentences(sentences)?;
-    iterator.mark_exhausted()?;
-
-    Ok(iterator)
+    let read_size = chunk_size.unwrap_or(DEFAULT_READ_SIZE);
+    iter_split_with(py, &processor, input, encoding, read_size, false)
 }
 
-/// Read all content from a file-like object
-fn read_all_from_file_object(
-    _py: Python,
-    obj: &Bound<'_, PyAny>,
-    encoding: &str,
-) -> PyResult<String> {
-    use pyo3::types::PyBytes;
-
-    // Try to seek to beginning if possible
-    let _ = obj.call_method1("seek", (0,));
-
-    // Read all content at once
-    let content = obj.call_method0("read")?;
-
-    // Check if it's bytes or string
-    if let Ok(text) = content.extract::<String>() {
-        Ok(text)
-    } else if let Ok(bytes_obj) = content.cast::<PyBytes>() {
-        // It's bytes, decode it
-        let bytes = bytes_obj.extract::<Vec<u8>>()?;
-        use encoding_rs::Encoding;
-
-        let encoding_obj = Encoding::for_label(encoding.as_bytes())
-            .ok_or_else(|| InternalError::EncodingError(format!("Unknown encoding: {encoding}")))?;
-
-        let (decoded, _, _) = encoding_obj.decode(&bytes);
-        Ok(decoded.to_string())
-    } else {
-        Err(
-            InternalError::InvalidInput("file.read() returned neither str nor bytes".to_string())
-                .into(),
-        )
-    }
-}
-/// Create an iterator adapter for existing PyIterator
-#[allow(dead_code)]
-pub fn adapt_python_iterator(
-    _py: Python,
-    iter: &Bound<'_, PyIterator>,
+/// Create a sentence iterator over `input` driven by `processor`'s stream,
+/// reading `read_size` bytes at a time
+pub fn iter_split_with(
+    py: Python,
     processor: &SentenceProcessor,
+    input: &Bound<'_, PyAny>,
+    encoding: &str,
+    read_size: usize,
     preserve_whitespace: bool,
 ) -> PyResult<SentenceIterator> {
-    use crate::iterator::{flush_buffer, process_text_incrementally};
-
-    let iterator = SentenceIterator::new_internal(preserve_whitespace);
-    let state = iterator.get_state();
+    let source = open_source(py, input, encoding)?;
+    Ok(SentenceIterator::new(
+        source,
+        processor.stream(),
+        read_size,
+        preserve_whitespace,
+    ))
+}
 
-    // Process each item from the Python iterator
-    for item in iter {
-        let item = item?;
-        if let Ok(text) = item.extract::<String>() {
-            process_text_incrementally(&text, &state, processor)?;
+/// Wrap a Python input as a piece-wise text source without reading it
+fn open_source(py: Python, input: &Bound<'_, PyAny>, encoding: &str) -> PyResult<TextSource> {
+    Ok(match PyInput::from_py_object(py, input)? {
+        PyInput::Text(text) => TextSource::Text { text, position: 0 },
+        PyInput::Bytes(bytes) => {
+            let text = PieceDecoder::new(encoding)?.decode(&bytes, true)?;
+            TextSource::Text { text, position: 0 }
         }
-    }
-
-    // Flush remaining text
-    flush_buffer(&state, processor)?;
-
-    Ok(iterator)
+        PyInput::Path(path) => {
+            let file = File::open(&path).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    InternalError::FileNotFound(path.display().to_string())
+                } else {
+                    InternalError::IoError(e.to_string())
+                }
+            })?;
+            TextSource::Reader {
+                reader: Box::new(file),
+                decoder: PieceDecoder::new(encoding)?,
+            }
+        }
+        // An mmap is read piece by piece like a file, not copied whole
+        PyInput::FileObject(obj) | PyInput::Mapped(obj) => {
+            // Read from the start when the object supports it
+            let _ = obj.call_method1(py, "seek", (0,));
+            TextSource::FileObject {
+                obj,
+                decoder: PieceDecoder::new(encoding)?,
+            }
+        }
+    })
 }
 
 /// Create a memory-efficient iterator for large files
 pub fn create_large_file_iterator(
     py: Python,
-    file_path: &str,
+    file_path: PathBuf,
     language: Option<&str>,
     language_config: Option<LanguageConfig>,
     max_memory_mb: usize,
     overlap_size: usize,
     encoding: &str,
 ) -> PyResult<LargeFileIterator> {
-    use std::path::Path;
-
     // Validate file path
-    let path = Path::new(file_path);
-    if !path.exists() {
-        return Err(InternalError::FileNotFound(file_path.to_string()).into());
+    if !file_path.exists() {
+        return Err(InternalError::FileNotFound(file_path.display().to_string()).into());
     }
 
     // Build processor configuration for memory-efficient processing
@@ -228,10 +172,9 @@ pub fn create_large_file_iterator(
         )
     } else {
         // Use built-in language
-        let lang_code = match language.unwrap_or("en").to_lowercase().as_str() {
-            "en" | "english" => "en",
-            "ja" | "japanese" => "ja",
-            _ => {
+        let lang_code = match crate::builtin_language(language.unwrap_or("en")) {
+            Some(code) => code,
+            None => {
                 return Err(InternalError::UnsupportedLanguage(
                     language.unwrap_or("unknown").to_string(),
                 )
@@ -264,7 +207,7 @@ pub fn create_large_file_iterator(
     };
 
     Ok(LargeFileIterator::new(
-        PathBuf::from(file_path),
+        file_path,
         processor,
         chunk_size,
         overlap_size,
@@ -314,6 +257,34 @@ impl LargeFileIterator {
         slf
     }
 
+    /// Stop iterating and close the file
+    ///
+    /// Later calls to next() raise StopIteration. Closing twice does
+    /// nothing.
+    fn close(&mut self) {
+        self.reader = None;
+        self.carry_over.clear();
+        self.sentence_buffer.clear();
+        self.exhausted = true;
+    }
+
+    /// Context manager entry
+    fn __enter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    /// Context manager exit: close the iterator
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.close();
+        // Don't suppress any exceptions
+        Ok(false)
+    }
+
     fn __next__(&mut self) -> PyResult<Option<String>> {
         use pyo3::exceptions::PyStopIteration;
 
@@ -380,6 +351,8 @@ impl LargeFileIterator {
         // Check if we're at EOF
         if buffer.len() <= self.carry_over.len() {
             self.exhausted = true;
+            // Close the file as soon as it is read through
+            self.reader = None;
             if !self.carry_over.is_empty() {
                 // Process final carry-over as a sentence
                 let sentence = self.carry_over.trim().to_string();
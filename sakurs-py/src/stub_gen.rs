@@ -0,0 +1,833 @@
+//! Type stub generation for the `sakurs` module
+//!
+//! Renders `sakurs.pyi` from the module itself: the classes, functions,
+//! exceptions and aliases it registers (in registration order), their
+//! signatures as Python sees them (`inspect.signature`) and their docstrings.
+//! Runtime signatures carry no types, so annotations come from the tables
+//! below; a parameter, attribute or return value without an annotation is an
+//! error, which keeps the stub from silently drifting when the bindings
+//! change. Run `cargo run -p sakurs-py --features stub-gen --bin stub_gen`
+//! to regenerate the stub, or add `-- --check` to verify it.
+
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+use std::fmt::Write;
+
+/// The input union accepted by `split`, `iter_split` and their async
+/// variants, spelled out in [`HEADER`].
+const INPUT: &str = "_Input";
+
+/// The execution modes accepted by `execution_mode`.
+const EXECUTION_MODE: &str = r#"Literal["sequential", "parallel", "adaptive"]"#;
+
+/// The boundary kinds of `Sentence.kind`.
+const SENTENCE_KIND: &str = r#"Literal["strong", "weak", "forced", "end_of_text"]"#;
+
+/// The values of `TextWarning.kind`.
+const WARNING_KIND: &str = r#"Literal["unclosed_enclosure", "unmatched_closer", "depth_exceeded", "long_sentence", "forced_split", "invalid_utf8"]"#;
+
+/// The estimators accepted by `token_estimator`.
+const TOKEN_ESTIMATOR: &str = r#"Literal["whitespace", "bpe"]"#;
+
+/// The values of `EnclosurePair.boundaries`.
+const ENCLOSURE_BOUNDARIES: &str = r#"Literal["suppress", "allow", "strong_only", "dialogue"]"#;
+
+/// Annotations for parameters, by name, wherever they appear.
+const PARAMETERS: &[(&str, &str)] = &[
+    ("input", INPUT),
+    ("language", "str | None"),
+    ("language_config", "LanguageConfig | None"),
+    ("threads", "int | None"),
+    ("chunk_kb", "int | None"),
+    ("parallel", "bool"),
+    ("execution_mode", EXECUTION_MODE),
+    ("preserve_whitespace", "bool"),
+    ("encoding", "str"),
+    ("streaming", "bool"),
+    ("stream_chunk_mb", "int"),
+    ("max_memory_mb", "int"),
+    ("overlap_size", "int"),
+    ("file_path", "str | PathLike[str]"),
+    ("path", "Path | str"),
+    ("key", "str"),
+    ("column", "Iterable[str | None]"),
+    ("return_arrow", "bool"),
+    ("token_estimator", r#"Literal["whitespace", "bpe"] | None"#),
+    ("max_tokens", "int"),
+    ("_exc_type", "type[BaseException] | None"),
+    ("_exc_value", "BaseException | None"),
+    ("_traceback", "object | None"),
+];
+
+/// Annotations for parameters of one function or method, overriding
+/// [`PARAMETERS`]; keyed by `qualname.parameter`.
+const QUALIFIED_PARAMETERS: &[(&str, &str)] = &[
+    ("load.language", "str"),
+    ("Sentence.__init__.text", "str"),
+    ("Sentence.__init__.start", "int"),
+    ("Sentence.__init__.end", "int"),
+    ("Sentence.__init__.confidence", "float"),
+    ("Sentence.__init__.metadata", "dict[str, Any] | None"),
+    ("Sentence.__init__.kind", SENTENCE_KIND),
+    ("Sentence.__init__.token_estimate", "int | None"),
+    ("chunk_sentences.sentences", "list[str] | list[Sentence]"),
+    ("chunk_sentences.token_estimator", TOKEN_ESTIMATOR),
+    ("ProcessingMetadata.__init__.total_sentences", "int"),
+    ("ProcessingMetadata.__init__.processing_time_ms", "float"),
+    ("ProcessingMetadata.__init__.threads_used", "int"),
+    ("ProcessingMetadata.__init__.chunk_kb_used", "int"),
+    ("ProcessingMetadata.__init__.execution_mode_used", "str"),
+    ("ProcessingMetadata.__init__.execution_reason", "str"),
+    ("ProcessingMetadata.__init__.bytes_per_thread", "int"),
+    (
+        "ProcessingMetadata.__init__.available_threads",
+        "int | None",
+    ),
+    ("LanguageConfig.__init__.metadata", "MetadataConfig"),
+    ("LanguageConfig.__init__.terminators", "TerminatorConfig"),
+    ("LanguageConfig.__init__.ellipsis", "EllipsisConfig"),
+    ("LanguageConfig.__init__.enclosures", "EnclosureConfig"),
+    ("LanguageConfig.__init__.suppression", "SuppressionConfig"),
+    (
+        "LanguageConfig.__init__.abbreviations",
+        "AbbreviationConfig",
+    ),
+    (
+        "LanguageConfig.__init__.sentence_starters",
+        "SentenceStarterConfig | None",
+    ),
+    (
+        "LanguageConfig.__init__.hypothesis",
+        "HypothesisConfig | None",
+    ),
+    ("MetadataConfig.__init__.code", "str"),
+    ("MetadataConfig.__init__.name", "str"),
+    ("TerminatorConfig.__init__.chars", "list[str]"),
+    (
+        "TerminatorConfig.__init__.patterns",
+        "list[TerminatorPattern] | None",
+    ),
+    (
+        "TerminatorConfig.__init__.ordinal_indicators",
+        "list[str] | None",
+    ),
+    ("TerminatorConfig.__init__.collapse_runs", "bool"),
+    ("TerminatorConfig.__init__.emoji", "bool"),
+    ("TerminatorPattern.__init__.pattern", "str"),
+    ("TerminatorPattern.__init__.name", "str"),
+    ("EllipsisConfig.__init__.treat_as_boundary", "bool"),
+    ("EllipsisConfig.__init__.patterns", "list[str] | None"),
+    (
+        "EllipsisConfig.__init__.context_rules",
+        "list[ContextRule] | None",
+    ),
+    (
+        "EllipsisConfig.__init__.exceptions",
+        "list[ExceptionPattern] | None",
+    ),
+    ("ContextRule.__init__.condition", "str"),
+    ("ContextRule.__init__.boundary", "bool"),
+    ("ExceptionPattern.__init__.regex", "str"),
+    ("ExceptionPattern.__init__.boundary", "bool"),
+    ("EnclosureConfig.__init__.pairs", "list[EnclosurePair]"),
+    ("EnclosurePair.__init__.open", "str"),
+    ("EnclosurePair.__init__.close", "str"),
+    ("EnclosurePair.__init__.symmetric", "bool"),
+    ("EnclosurePair.__init__.boundaries", ENCLOSURE_BOUNDARIES),
+    (
+        "SuppressionConfig.__init__.fast_patterns",
+        "list[FastPattern] | None",
+    ),
+    (
+        "SuppressionConfig.__init__.regex_patterns",
+        "list[RegexPattern] | None",
+    ),
+    (
+        "SuppressionConfig.__init__.trailing_patterns",
+        "list[str] | None",
+    ),
+    ("SuppressionConfig.__init__.web_tokens", "bool"),
+    ("SuppressionConfig.__init__.citations", "bool"),
+    (
+        "SuppressionConfig.__init__.apostrophes",
+        "ApostropheConfig | None",
+    ),
+    ("FastPattern.__init__.char", "str"),
+    ("FastPattern.__init__.line_start", "bool"),
+    ("FastPattern.__init__.before", "str | None"),
+    ("FastPattern.__init__.after", "str | None"),
+    ("RegexPattern.__init__.pattern", "str"),
+    ("RegexPattern.__init__.description", "str | None"),
+    ("ApostropheConfig.__init__.chars", "list[str] | None"),
+    ("ApostropheConfig.__init__.contractions", "bool"),
+    ("ApostropheConfig.__init__.possessives", "bool"),
+    ("ApostropheConfig.__init__.years", "bool"),
+    (
+        "ApostropheConfig.__init__.elided_starts",
+        "list[str] | None",
+    ),
+    ("ApostropheConfig.__init__.elided_ends", "list[str] | None"),
+    (
+        "AbbreviationConfig.__init__.kwargs",
+        "list[str | dict[str, Any]]",
+    ),
+    (
+        "AbbreviationConfig.__setitem__.value",
+        "list[str | dict[str, Any]]",
+    ),
+    (
+        "SentenceStarterConfig.__init__.require_following_space",
+        "bool",
+    ),
+    ("SentenceStarterConfig.__init__.min_word_length", "int"),
+    ("SentenceStarterConfig.__init__.threshold", "float"),
+    (
+        "SentenceStarterConfig.__init__.uppercase_fallback",
+        "float | None",
+    ),
+    (
+        "SentenceStarterConfig.__init__.kwargs",
+        "list[str | dict[str, Any]]",
+    ),
+    ("HypothesisConfig.__init__.strategy", "str"),
+    ("HypothesisConfig.__init__.min_chars", "int"),
+    (
+        "HypothesisConfig.__init__.sentence_final_particles",
+        "list[str] | None",
+    ),
+    (
+        "HypothesisConfig.__init__.discourse_markers",
+        "list[str] | None",
+    ),
+    (
+        "HypothesisConfig.__init__.continuation_markers",
+        "list[str] | None",
+    ),
+];
+
+/// Return annotations, keyed by qualname. Dunder methods with a fixed
+/// return type (`__repr__`, `__iter__`, …) and functions in [`OVERLOADS`]
+/// need no entry.
+const RETURNS: &[(&str, &str)] = &[
+    ("load", "SentenceSplitter"),
+    ("iter_split", "SentenceIterator"),
+    ("aiter_split", "AsyncSentenceIterator"),
+    ("split_large_file", "LargeFileIterator"),
+    ("supported_languages", "list[str]"),
+    ("chunk_sentences", "list[list[str]] | list[list[Sentence]]"),
+    ("SentenceSplitter.iter_split", "SentenceIterator"),
+    ("SentenceIterator.__next__", "str"),
+    ("SentenceIterator.close", "None"),
+    ("LargeFileIterator.__next__", "str"),
+    ("LargeFileIterator.close", "None"),
+    ("AsyncSentenceIterator.__anext__", "Awaitable[str]"),
+    ("LanguageConfig.from_toml", "LanguageConfig"),
+    ("LanguageConfig.to_toml", "None"),
+    (
+        "AbbreviationConfig.__getitem__",
+        "list[str | dict[str, Any]]",
+    ),
+];
+
+/// Attribute annotations, keyed by `Class.attribute`. A `@property` prefix
+/// renders a read-only property instead of a plain attribute.
+const ATTRIBUTES: &[(&str, &str)] = &[
+    ("Sentence.text", "str"),
+    ("Sentence.start", "int"),
+    ("Sentence.end", "int"),
+    ("Sentence.confidence", "float"),
+    ("Sentence.metadata", "dict[str, Any]"),
+    ("Sentence.kind", SENTENCE_KIND),
+    ("Sentence.token_estimate", "int | None"),
+    ("ProcessingMetadata.total_sentences", "int"),
+    ("ProcessingMetadata.processing_time_ms", "float"),
+    ("ProcessingMetadata.threads_used", "int"),
+    ("ProcessingMetadata.chunk_kb_used", "int"),
+    ("ProcessingMetadata.execution_mode_used", "str"),
+    ("ProcessingMetadata.execution_reason", "str"),
+    ("ProcessingMetadata.bytes_per_thread", "int"),
+    ("ProcessingMetadata.available_threads", "int | None"),
+    ("ProcessingMetadata.warnings", "list[TextWarning]"),
+    ("TextWarning.kind", WARNING_KIND),
+    ("TextWarning.offset", "int"),
+    ("TextWarning.message", "str"),
+    ("SentenceSplitter.language", "@property str"),
+    (
+        "SentenceSplitter.last_metadata",
+        "@property ProcessingMetadata | None",
+    ),
+    ("SentenceSplitter.supports_parallel", "@property bool"),
+    ("LanguageConfig.metadata", "MetadataConfig"),
+    ("LanguageConfig.terminators", "TerminatorConfig"),
+    ("LanguageConfig.ellipsis", "EllipsisConfig"),
+    ("LanguageConfig.enclosures", "EnclosureConfig"),
+    ("LanguageConfig.suppression", "SuppressionConfig"),
+    ("LanguageConfig.abbreviations", "AbbreviationConfig"),
+    (
+        "LanguageConfig.sentence_starters",
+        "SentenceStarterConfig | None",
+    ),
+    ("LanguageConfig.hypothesis", "HypothesisConfig | None"),
+    ("MetadataConfig.code", "str"),
+    ("MetadataConfig.name", "str"),
+    ("TerminatorConfig.chars", "list[str]"),
+    ("TerminatorConfig.patterns", "list[TerminatorPattern]"),
+    ("TerminatorConfig.ordinal_indicators", "list[str]"),
+    ("TerminatorConfig.collapse_runs", "bool"),
+    ("TerminatorConfig.emoji", "bool"),
+    ("TerminatorPattern.pattern", "str"),
+    ("TerminatorPattern.name", "str"),
+    ("EllipsisConfig.treat_as_boundary", "bool"),
+    ("EllipsisConfig.patterns", "list[str]"),
+    ("EllipsisConfig.context_rules", "list[ContextRule]"),
+    ("EllipsisConfig.exceptions", "list[ExceptionPattern]"),
+    ("ContextRule.condition", "str"),
+    ("ContextRule.boundary", "bool"),
+    ("ExceptionPattern.regex", "str"),
+    ("ExceptionPattern.boundary", "bool"),
+    ("EnclosureConfig.pairs", "list[EnclosurePair]"),
+    ("EnclosurePair.open", "str"),
+    ("EnclosurePair.close", "str"),
+    ("EnclosurePair.symmetric", "bool"),
+    ("EnclosurePair.boundaries", ENCLOSURE_BOUNDARIES),
+    ("SuppressionConfig.fast_patterns", "list[FastPattern]"),
+    ("SuppressionConfig.regex_patterns", "list[RegexPattern]"),
+    ("SuppressionConfig.trailing_patterns", "list[str]"),
+    ("SuppressionConfig.web_tokens", "bool"),
+    ("SuppressionConfig.citations", "bool"),
+    ("SuppressionConfig.apostrophes", "ApostropheConfig | None"),
+    ("FastPattern.char", "str"),
+    ("FastPattern.line_start", "bool"),
+    ("FastPattern.before", "str | None"),
+    ("FastPattern.after", "str | None"),
+    ("RegexPattern.pattern", "str"),
+    ("RegexPattern.description", "str | None"),
+    ("ApostropheConfig.chars", "list[str]"),
+    ("ApostropheConfig.contractions", "bool"),
+    ("ApostropheConfig.possessives", "bool"),
+    ("ApostropheConfig.years", "bool"),
+    ("ApostropheConfig.elided_starts", "list[str]"),
+    ("ApostropheConfig.elided_ends", "list[str]"),
+    (
+        "AbbreviationConfig.categories",
+        "dict[str, list[str | dict[str, Any]]]",
+    ),
+    (
+        "SentenceStarterConfig.categories",
+        "dict[str, list[str | dict[str, Any]]]",
+    ),
+    ("SentenceStarterConfig.require_following_space", "bool"),
+    ("SentenceStarterConfig.min_word_length", "int"),
+    ("SentenceStarterConfig.threshold", "float"),
+    ("SentenceStarterConfig.uppercase_fallback", "float | None"),
+    ("HypothesisConfig.strategy", "str"),
+    ("HypothesisConfig.min_chars", "int"),
+    ("HypothesisConfig.sentence_final_particles", "list[str]"),
+    ("HypothesisConfig.discourse_markers", "list[str]"),
+    ("HypothesisConfig.continuation_markers", "list[str]"),
+];
+
+/// Functions rendered as `@overload` variants on literal parameters:
+/// `(qualname, parameters, [(annotation and default of each parameter,
+/// return type)])`.
+type Overload = (
+    &'static str,
+    &'static [&'static str],
+    &'static [(&'static [&'static str], &'static str)],
+);
+const OVERLOADS: &[Overload] = &[
+    (
+        "split",
+        &["return_details", "return_offsets"],
+        &[
+            (&["Literal[False] = False", "None = None"], "list[str]"),
+            (&["Literal[True]", "None = None"], "list[Sentence]"),
+            // numpy.ndarray, without making numpy a stub dependency
+            (
+                &["Literal[False] = False", r#"Literal["numpy"]"#],
+                "tuple[Any, Any]",
+            ),
+        ],
+    ),
+    (
+        "asplit",
+        &["return_details", "return_offsets"],
+        &[
+            (
+                &["Literal[False] = False", "None = None"],
+                "Awaitable[list[str]]",
+            ),
+            (
+                &["Literal[True]", "None = None"],
+                "Awaitable[list[Sentence]]",
+            ),
+            (
+                &["Literal[False] = False", r#"Literal["numpy"]"#],
+                "Awaitable[tuple[Any, Any]]",
+            ),
+        ],
+    ),
+    (
+        "SentenceSplitter.split",
+        &["return_details"],
+        &[
+            (&["Literal[False] = False"], "list[str]"),
+            (&["Literal[True]"], "list[Sentence]"),
+        ],
+    ),
+    (
+        "split_series",
+        &["return_arrow"],
+        &[
+            (&["Literal[False] = False"], "list[list[str] | None]"),
+            // pyarrow.ListArray, without making pyarrow a stub dependency
+            (&["Literal[True]"], "Any"),
+        ],
+    ),
+    (
+        "SentenceSplitter.split_series",
+        &["return_arrow"],
+        &[
+            (&["Literal[False] = False"], "list[list[str] | None]"),
+            (&["Literal[True]"], "Any"),
+        ],
+    ),
+];
+
+/// Dunder methods included in the stub.
+const DUNDERS: &[&str] = &[
+    "__repr__",
+    "__str__",
+    "__iter__",
+    "__next__",
+    "__aiter__",
+    "__anext__",
+    "__enter__",
+    "__exit__",
+    "__getitem__",
+    "__setitem__",
+];
+
+const HEADER: &str = r#""""Type stubs for sakurs Python bindings.
+
+Generated from the compiled module by `cargo run -p sakurs-py --features
+stub-gen --bin stub_gen`; do not edit by hand.
+"""
+
+from collections.abc import Awaitable, Iterable
+from mmap import mmap
+from os import PathLike
+from pathlib import Path
+from typing import Any, BinaryIO, Literal, Protocol, TextIO, TypeAlias, overload
+
+class FileProtocol(Protocol):
+    """Protocol for file-like objects with read() method."""
+    def read(self, size: int = -1) -> str | bytes: ...
+
+_Input: TypeAlias = (
+    str
+    | bytes
+    | bytearray
+    | memoryview
+    | mmap
+    | PathLike[str]
+    | TextIO
+    | BinaryIO
+    | FileProtocol
+)
+
+__version__: str
+"#;
+
+/// Line length the rendered signatures are wrapped at (ruff's default).
+const LINE_LENGTH: usize = 88;
+
+/// Renders the stub for a freshly initialized `sakurs` module.
+pub fn generate(py: Python<'_>) -> PyResult<String> {
+    let module = PyModule::new(py, "sakurs")?;
+    crate::sakurs(&module)?;
+    let mut gen = Generator {
+        inspect: py.import("inspect")?,
+        missing: Vec::new(),
+    };
+    let out = gen.module(&module)?;
+    if gen.missing.is_empty() {
+        Ok(out)
+    } else {
+        Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "no stub annotation for: {}",
+            gen.missing.join(", ")
+        )))
+    }
+}
+
+struct Generator<'py> {
+    inspect: Bound<'py, PyModule>,
+    /// Table keys that were needed but not found
+    missing: Vec<String>,
+}
+
+/// One parameter of a runtime signature.
+struct Param {
+    name: String,
+    kind: ParamKind,
+    default: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ParamKind {
+    PositionalOnly,
+    Positional,
+    KeywordOnly,
+    VarKeyword,
+}
+
+fn lookup(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+impl<'py> Generator<'py> {
+    fn module(&mut self, module: &Bound<'py, PyModule>) -> PyResult<String> {
+        let mut exceptions = String::new();
+        let mut classes = String::new();
+        let mut functions = String::new();
+        let mut aliases = String::new();
+
+        let base_exception = module.py().get_type::<pyo3::exceptions::PyBaseException>();
+        for (name, value) in module.dict().iter() {
+            let name: String = name.extract()?;
+            if name.starts_with('_') {
+                continue;
+            }
+            if let Ok(class) = value.cast::<PyType>() {
+                if class.name()?.to_string() != name {
+                    writeln!(aliases, "{name} = {}", class.name()?).unwrap();
+                } else if class.is_subclass(&base_exception)? {
+                    exceptions.push_str(&self.exception(class)?);
+                } else {
+                    classes.push_str(&self.class(class)?);
+                }
+            } else if value.is_callable() {
+                functions.push_str(&self.function(&name, &name, &value, None)?);
+            }
+        }
+
+        let mut out = String::from(HEADER);
+        for (comment, section) in [
+            ("# Exception types", exceptions),
+            ("# Classes", classes),
+            ("# Aliases", aliases),
+            ("# Functions", functions),
+        ] {
+            if !section.is_empty() {
+                write!(out, "\n{comment}\n{}\n", section.trim_end()).unwrap();
+            }
+        }
+        Ok(out)
+    }
+
+    fn exception(&mut self, class: &Bound<'py, PyType>) -> PyResult<String> {
+        let base = class
+            .getattr("__base__")?
+            .cast::<PyType>()?
+            .name()?
+            .to_string();
+        let mut out = format!("class {}({base}):\n", class.name()?);
+        match docstring(&class.getattr("__doc__")?, "    ")? {
+            doc if doc.is_empty() => out.push_str("    ...\n"),
+            doc => out.push_str(&doc),
+        }
+        out.push('\n');
+        Ok(out)
+    }
+
+    fn class(&mut self, class: &Bound<'py, PyType>) -> PyResult<String> {
+        let class_name = class.name()?.to_string();
+        let mut out = format!("class {class_name}:\n");
+        let doc = docstring(&class.getattr("__doc__")?, "    ")?;
+        out.push_str(&doc);
+
+        let members = class.getattr("__dict__")?;
+        let members = members.call_method0("items")?;
+        // Descriptors come back in hash order; render them in table order
+        let mut attributes: Vec<(usize, String)> = Vec::new();
+        let mut properties: Vec<(usize, String)> = Vec::new();
+        let mut methods = String::new();
+
+        if !class.getattr("__text_signature__")?.is_none() {
+            methods.push_str(&self.function(
+                &format!("{class_name}.__init__"),
+                "__init__",
+                class.as_any(),
+                Some("self"),
+            )?);
+        }
+
+        for item in members.try_iter()? {
+            let (name, value): (String, Bound<'py, PyAny>) = item?.extract()?;
+            let kind = value.get_type().name()?.to_string();
+            let qualname = format!("{class_name}.{name}");
+            if name.starts_with('_') && !DUNDERS.contains(&name.as_str()) {
+                continue;
+            }
+            match kind.as_str() {
+                "getset_descriptor" => {
+                    let Some(index) = ATTRIBUTES.iter().position(|(k, _)| *k == qualname) else {
+                        self.missing.push(qualname);
+                        continue;
+                    };
+                    let annotation = ATTRIBUTES[index].1;
+                    match annotation.strip_prefix("@property ") {
+                        Some(ty) => properties.push((
+                            index,
+                            format!("    @property\n    def {name}(self) -> {ty}: ...\n"),
+                        )),
+                        None => attributes.push((index, format!("    {name}: {annotation}\n"))),
+                    }
+                }
+                "classmethod_descriptor" => {
+                    let bound = class.getattr(name.as_str())?;
+                    methods.push_str("    @classmethod\n");
+                    methods.push_str(&self.function(&qualname, &name, &bound, Some("cls"))?);
+                }
+                "method_descriptor" | "wrapper_descriptor" => {
+                    methods.push_str(&self.function(&qualname, &name, &value, Some("self"))?);
+                }
+                _ => {}
+            }
+        }
+
+        attributes.sort();
+        properties.sort();
+        let attributes: String = attributes.into_iter().map(|(_, line)| line).collect();
+        methods.extend(properties.into_iter().map(|(_, def)| def));
+        if !attributes.is_empty() {
+            if !doc.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&attributes);
+        }
+        if !methods.is_empty() {
+            if !doc.is_empty() || !attributes.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&methods);
+        }
+        if doc.is_empty() && attributes.is_empty() && methods.is_empty() {
+            out.push_str("    ...\n");
+        }
+        out.push('\n');
+        Ok(out)
+    }
+
+    /// Renders a function or method (indented when `receiver` is set).
+    fn function(
+        &mut self,
+        qualname: &str,
+        name: &str,
+        callable: &Bound<'py, PyAny>,
+        receiver: Option<&str>,
+    ) -> PyResult<String> {
+        let indent = if receiver.is_some() { "    " } else { "" };
+        let mut params = self.signature(callable)?;
+        // Unbound methods list the receiver themselves
+        if receiver == Some("self") && name != "__init__" && !params.is_empty() {
+            params.remove(0);
+        }
+
+        let class_name = qualname.split('.').next().unwrap_or(qualname);
+        let ret = match name {
+            "__init__" | "__setitem__" => "None".to_string(),
+            "__repr__" | "__str__" => "str".to_string(),
+            "__iter__" | "__aiter__" | "__enter__" => class_name.to_string(),
+            "__exit__" => "bool".to_string(),
+            _ if OVERLOADS.iter().any(|(q, _, _)| *q == qualname) => String::new(),
+            _ => match lookup(RETURNS, qualname) {
+                Some(ret) => ret.to_string(),
+                None => {
+                    self.missing.push(format!("{qualname} (return)"));
+                    "Any".to_string()
+                }
+            },
+        };
+
+        let mut rendered: Vec<String> = receiver.iter().map(|r| r.to_string()).collect();
+        let mut saw_keyword_only = false;
+        // Where each overloaded parameter goes in `rendered`
+        let mut slots = Vec::new();
+        let overload = OVERLOADS.iter().find(|(q, _, _)| *q == qualname);
+        for (i, param) in params.iter().enumerate() {
+            if param.kind == ParamKind::KeywordOnly && !saw_keyword_only {
+                rendered.push("*".into());
+                saw_keyword_only = true;
+            }
+            if let Some((_, overloaded, _)) = overload {
+                if let Some(k) = overloaded.iter().position(|name| param.name == *name) {
+                    slots.push((k, rendered.len()));
+                    rendered.push(String::new());
+                    continue;
+                }
+            }
+            let key = format!("{qualname}.{}", param.name);
+            let annotation = lookup(QUALIFIED_PARAMETERS, &key)
+                .or_else(|| lookup(PARAMETERS, &param.name))
+                .unwrap_or_else(|| {
+                    self.missing.push(key);
+                    "Any"
+                });
+            let prefix = if param.kind == ParamKind::VarKeyword {
+                "**"
+            } else {
+                ""
+            };
+            let mut text = format!("{prefix}{}: {annotation}", param.name);
+            if let Some(ref default) = param.default {
+                write!(text, " = {default}").unwrap();
+            }
+            rendered.push(text);
+            let next_is_keyword = params.get(i + 1).map(|p| p.kind);
+            if param.kind == ParamKind::PositionalOnly
+                && next_is_keyword != Some(ParamKind::PositionalOnly)
+            {
+                rendered.push("/".into());
+            }
+        }
+
+        // Dunders inherit object's docstrings, and `__init__` repeats the class
+        let doc = if name.starts_with("__") {
+            String::new()
+        } else {
+            docstring(&callable.getattr("__doc__")?, &format!("{indent}    "))?
+        };
+        let mut out = String::new();
+        match overload {
+            Some((_, overloaded, variants)) if !slots.is_empty() => {
+                for (annotations, ret) in variants.iter() {
+                    let mut params = rendered.clone();
+                    for &(k, slot) in &slots {
+                        params[slot] = format!("{}: {}", overloaded[k], annotations[k]);
+                    }
+                    writeln!(out, "{indent}@overload").unwrap();
+                    out.push_str(&def(indent, name, &params, ret, ""));
+                }
+            }
+            _ => out.push_str(&def(indent, name, &rendered, &ret, &doc)),
+        }
+        Ok(out)
+    }
+
+    /// The runtime signature of a builtin callable, via `inspect.signature`.
+    fn signature(&self, callable: &Bound<'py, PyAny>) -> PyResult<Vec<Param>> {
+        let signature = self.inspect.call_method1("signature", (callable,))?;
+        let empty = self.inspect.getattr("Parameter")?.getattr("empty")?;
+        let parameters = signature.getattr("parameters")?.call_method0("values")?;
+        let mut params = Vec::new();
+        for param in parameters.try_iter()? {
+            let param = param?;
+            let kind: String = param.getattr("kind")?.getattr("name")?.extract()?;
+            let kind = match kind.as_str() {
+                "POSITIONAL_ONLY" => ParamKind::PositionalOnly,
+                "KEYWORD_ONLY" => ParamKind::KeywordOnly,
+                "VAR_KEYWORD" => ParamKind::VarKeyword,
+                _ => ParamKind::Positional,
+            };
+            let default = param.getattr("default")?;
+            let default = if default.is(&empty) {
+                None
+            } else if default.is(callable.py().Ellipsis()) {
+                Some("...".to_string())
+            } else if let Ok(text) = default.extract::<String>() {
+                Some(format!("{text:?}"))
+            } else {
+                Some(default.repr()?.to_string())
+            };
+            params.push(Param {
+                name: param.getattr("name")?.extract()?,
+                kind,
+                default,
+            });
+        }
+        Ok(params)
+    }
+}
+
+/// Renders a `def`, on one line when it fits and one parameter per line
+/// otherwise, with the docstring (if any) as its body.
+fn def(indent: &str, name: &str, params: &[String], ret: &str, doc: &str) -> String {
+    let body = if doc.is_empty() {
+        " ...".to_string()
+    } else {
+        format!("\n{}", doc.trim_end())
+    };
+    let line = format!("{indent}def {name}({}) -> {ret}:", params.join(", "));
+    let mut out = if line.len() + 4 <= LINE_LENGTH {
+        line
+    } else {
+        let mut out = format!("{indent}def {name}(\n");
+        for param in params {
+            writeln!(out, "{indent}    {param},").unwrap();
+        }
+        write!(out, "{indent}) -> {ret}:").unwrap();
+        out
+    };
+    out.push_str(&body);
+    out.push('\n');
+    out
+}
+
+/// Renders a docstring at `indent`, or nothing for a missing one.
+fn docstring(doc: &Bound<'_, PyAny>, indent: &str) -> PyResult<String> {
+    if doc.is_none() {
+        return Ok(String::new());
+    }
+    let doc: String = doc.extract()?;
+    let doc = doc.trim();
+    if doc.is_empty() {
+        return Ok(String::new());
+    }
+    if !doc.contains('\n') {
+        return Ok(format!("{indent}\"\"\"{doc}\"\"\"\n"));
+    }
+    let mut out = format!("{indent}\"\"\"\n");
+    for line in doc.lines() {
+        if line.trim().is_empty() {
+            out.push('\n');
+        } else {
+            writeln!(out, "{indent}{line}").unwrap();
+        }
+    }
+    writeln!(out, "{indent}\"\"\"").unwrap();
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stub_is_up_to_date() {
+        Python::initialize();
+        Python::attach(|py| {
+            let generated = generate(py).unwrap();
+            let shipped = include_str!("../sakurs.pyi");
+            assert!(
+                generated == shipped,
+                "sakurs.pyi is stale; regenerate it with \
+                 `cargo run -p sakurs-py --features stub-gen --bin stub_gen`"
+            );
+        });
+    }
+
+    #[test]
+    fn test_wraps_long_signatures() {
+        let params = vec!["self".to_string(), "x: int".to_string()];
+        assert_eq!(
+            def("    ", "f", &params, "None", ""),
+            "    def f(self, x: int) -> None: ...\n"
+        );
+        let long: Vec<String> = (0..10).map(|i| format!("parameter_{i}: int")).collect();
+        let out = def("", "f", &long, "None", "");
+        assert!(out.starts_with("def f(\n    parameter_0: int,\n"));
+        assert!(out.ends_with(") -> None: ...\n"));
+    }
+}
@@ -0,0 +1,137 @@
+"""Tests for the asyncio API: asplit and aiter_split."""
+
+import asyncio
+import io
+
+import pytest
+
+import sakurs
+
+
+class TestAsplit:
+    """Tests for awaitable splitting."""
+
+    def test_matches_split(self):
+        """asplit returns what split returns."""
+        text = "Hello world. This is a test. Another sentence?"
+
+        async def main():
+            return await sakurs.asplit(text)
+
+        assert asyncio.run(main()) == sakurs.split(text)
+
+    def test_return_details(self):
+        """asplit passes its options through."""
+        text = "これは文です。もう一つの文。"
+
+        async def main():
+            return await sakurs.asplit(text, language="ja", return_details=True)
+
+        sentences = asyncio.run(main())
+        assert [s.text for s in sentences] == ["これは文です。", "もう一つの文。"]
+        assert isinstance(sentences[0], sakurs.Sentence)
+
+    def test_file_path(self, tmp_path):
+        """asplit reads files on its worker thread."""
+        path = tmp_path / "input.txt"
+        path.write_text("One. Two. Three.", encoding="utf-8")
+
+        async def main():
+            return await sakurs.asplit(path)
+
+        assert asyncio.run(main()) == ["One.", "Two.", "Three."]
+
+    def test_concurrent_calls(self):
+        """Several calls run side by side on one loop."""
+
+        async def main():
+            return await asyncio.gather(
+                *(sakurs.asplit(f"Call {i}. Done.") for i in range(8))
+            )
+
+        results = asyncio.run(main())
+        assert results == [[f"Call {i}.", "Done."] for i in range(8)]
+
+    def test_error_is_raised_when_awaited(self, tmp_path):
+        """Errors of the worker thread surface from the await."""
+        missing = tmp_path / "missing.txt"
+
+        async def main():
+            return await sakurs.asplit(missing)
+
+        with pytest.raises(OSError):
+            asyncio.run(main())
+
+    def test_needs_running_loop(self):
+        """Calling asplit outside a coroutine fails right away."""
+        with pytest.raises(RuntimeError):
+            sakurs.asplit("Hello. World.")
+
+    def test_invalid_language_fails_early(self):
+        """Configuration errors are raised by the call itself."""
+
+        async def main():
+            with pytest.raises(sakurs.InvalidLanguageError):
+                sakurs.asplit("Hello.", language="xx")
+
+        asyncio.run(main())
+
+
+class TestAiterSplit:
+    """Tests for async sentence iteration."""
+
+    def test_matches_iter_split(self):
+        """aiter_split yields what iter_split yields."""
+        text = "Hello world. This is a test. Another sentence?"
+
+        async def main():
+            return [s async for s in sakurs.aiter_split(text)]
+
+        assert asyncio.run(main()) == list(sakurs.iter_split(text))
+
+    def test_file_like_object(self):
+        """File-like objects are read as the iterator advances."""
+
+        async def main():
+            stream = io.BytesIO(b"One. Two. Three.")
+            return [s async for s in sakurs.aiter_split(stream, chunk_kb=1)]
+
+        assert asyncio.run(main()) == ["One.", "Two.", "Three."]
+
+    def test_more_sentences_than_read_ahead(self):
+        """Reading pauses and resumes as the consumer catches up."""
+        text = " ".join(f"Sentence {i}." for i in range(1000))
+
+        async def main():
+            sentences = []
+            async for sentence in sakurs.aiter_split(text, chunk_kb=1):
+                sentences.append(sentence)
+                if len(sentences) % 100 == 0:
+                    await asyncio.sleep(0.001)
+            return sentences
+
+        sentences = asyncio.run(main())
+        assert len(sentences) == 1000
+        assert sentences[-1] == "Sentence 999."
+
+    def test_exhausted(self):
+        """An exhausted iterator keeps raising StopAsyncIteration."""
+
+        async def main():
+            iterator = sakurs.aiter_split("Only one.")
+            assert await iterator.__anext__() == "Only one."
+            for _ in range(2):
+                with pytest.raises(StopAsyncIteration):
+                    await iterator.__anext__()
+
+        asyncio.run(main())
+
+    def test_break_early(self):
+        """Leaving the loop early stops the reading thread."""
+        text = " ".join(f"Sentence {i}." for i in range(1000))
+
+        async def main():
+            async for sentence in sakurs.aiter_split(text):
+                return sentence
+
+        assert asyncio.run(main()) == "Sentence 0."
@@ -270,6 +270,101 @@ class TestSentenceClass:
         assert "start=0" in repr_str
         assert "end=12" in repr_str
         assert "confidence=1" in repr_str
+        assert "kind='weak'" in repr_str
+
+    def test_sentence_kind(self):
+        """Test each sentence reports the kind of boundary that ends it."""
+        result = sakurs.split("Stop! It is 3.5 m long. No terminator", return_details=True)
+        assert [s.kind for s in result] == ["strong", "weak", "end_of_text"]
+
+        assert sakurs.Sentence("Hi.", 0, 3).kind == "weak"
+        with pytest.raises(ValueError):
+            sakurs.Sentence("Hi.", 0, 3, kind="unknown")  # type: ignore[arg-type]
+
+    def test_sentence_token_estimate(self):
+        """Test sentences carry a token estimate when an estimator is given."""
+        text = "The cat sat. It purred loudly, then slept."
+        result = sakurs.split(text, return_details=True)
+        assert [s.token_estimate for s in result] == [None, None]
+
+        result = sakurs.split(text, return_details=True, token_estimator="whitespace")
+        assert [s.token_estimate for s in result] == [3, 5]
+
+        result = sakurs.split(text, return_details=True, token_estimator="bpe")
+        assert all(s.token_estimate > 0 for s in result)
+
+        with pytest.raises(ValueError):
+            sakurs.split(text, token_estimator="gpt")  # type: ignore[arg-type]
+
+
+class TestChunkSentences:
+    """Test chunk_sentences() packing of sentences into token budgets."""
+
+    def test_chunks_keep_order_and_budget(self):
+        """Test sentences are grouped greedily without exceeding the budget."""
+        sentences = ["One two three.", "Four five.", "Six seven eight nine.", "Ten."]
+        chunks = sakurs.chunk_sentences(sentences, 5, token_estimator="whitespace")
+        assert chunks == [
+            ["One two three.", "Four five."],
+            ["Six seven eight nine.", "Ten."],
+        ]
+
+        # A sentence over the budget is a chunk by itself
+        chunks = sakurs.chunk_sentences(sentences, 2, token_estimator="whitespace")
+        assert [len(chunk) for chunk in chunks] == [1, 1, 1, 1]
+
+        assert sakurs.chunk_sentences([], 10) == []
+
+    def test_chunks_use_sentence_estimates(self):
+        """Test Sentence objects are packed by their own token estimates."""
+        text = "The cat sat. It purred loudly, then slept. Done."
+        sentences = sakurs.split(text, return_details=True, token_estimator="whitespace")
+        chunks = sakurs.chunk_sentences(sentences, 8)
+        assert [[s.text for s in chunk] for chunk in chunks] == [
+            ["The cat sat.", "It purred loudly, then slept."],
+            ["Done."],
+        ]
+        assert chunks[0][0] is sentences[0]
+
+
+class TestReturnOffsets:
+    """Test return_offsets="numpy" offset arrays."""
+
+    def test_offsets_match_sentence_objects(self):
+        """Test the arrays hold the start and end of each Sentence."""
+        np = pytest.importorskip("numpy")
+        text = "Hello world.  日本語です。\n\nTrailing text"
+        starts, ends = sakurs.split(text, return_offsets="numpy")
+        sentences = sakurs.split(text, return_details=True)
+
+        assert starts.dtype == np.int64 and ends.dtype == np.int64
+        assert starts.tolist() == [s.start for s in sentences]
+        assert ends.tolist() == [s.end for s in sentences]
+        assert [text[a:b].strip() for a, b in zip(starts, ends)] == [
+            s.text for s in sentences
+        ]
+
+    def test_offsets_are_writable(self):
+        """Test the arrays can be modified in place."""
+        pytest.importorskip("numpy")
+        starts, ends = sakurs.split("One. Two.", return_offsets="numpy")
+        starts += 1
+        assert starts.tolist() == [1, 5]
+
+    def test_empty_text(self):
+        """Test empty input returns empty arrays."""
+        pytest.importorskip("numpy")
+        starts, ends = sakurs.split("", return_offsets="numpy")
+        assert len(starts) == 0 and len(ends) == 0
+
+    def test_invalid_options(self):
+        """Test unknown formats and return_details are rejected."""
+        with pytest.raises(ValueError, match="expected numpy"):
+            sakurs.split("Hi.", return_offsets="list")  # type: ignore[call-overload]
+        with pytest.raises(ValueError, match="cannot be combined"):
+            sakurs.split(  # type: ignore[call-overload]
+                "Hi.", return_details=True, return_offsets="numpy"
+            )
 
 
 class TestProcessingMetadata:
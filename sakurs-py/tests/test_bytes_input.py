@@ -1,5 +1,7 @@
 """Tests for bytes input handling."""
 
+import mmap
+
 import pytest
 
 import sakurs
@@ -140,3 +142,34 @@ class TestBytesInput:
         # This is the actual behavior - BOM becomes part of the text
         assert sentences[0] == "\ufeffText with BOM."  # BOM is U+FEFF
         assert sentences[1] == "Should be handled."
+
+    def test_bytearray_input(self):
+        """Test with bytearray input."""
+        sentences = sakurs.split(bytearray("Hello. 世界です。".encode()))
+        assert sentences == ["Hello.", "世界です。"]
+
+    def test_memoryview_input(self):
+        """Test with memoryview input, including a slice of a larger buffer."""
+        data = b"Skip this. Keep this. And this."
+        sentences = sakurs.split(memoryview(data)[11:])
+        assert sentences == ["Keep this.", "And this."]
+
+    def test_mmap_input(self, tmp_path):
+        """Test with mmap input, read whole whatever its position."""
+        file_path = tmp_path / "mapped.txt"
+        file_path.write_bytes("First. Second. Third.".encode("latin-1"))
+
+        with open(file_path, "rb") as f, mmap.mmap(
+            f.fileno(), 0, access=mmap.ACCESS_READ
+        ) as mapped:
+            mapped.seek(7)
+            assert sakurs.split(mapped, encoding="latin-1") == [
+                "First.",
+                "Second.",
+                "Third.",
+            ]
+            assert list(sakurs.iter_split(mapped, chunk_kb=1)) == [
+                "First.",
+                "Second.",
+                "Third.",
+            ]
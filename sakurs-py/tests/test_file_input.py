@@ -1,8 +1,11 @@
 """Tests for file path input handling."""
 
+import os
 import tempfile
 from pathlib import Path
 
+import pytest
+
 import sakurs
 
 
@@ -167,3 +170,44 @@ class TestFileInput:
             assert sentences[1] == "With file input!"
         finally:
             Path(temp_path).unlink()
+
+    def test_bytes_pathlike_input(self, tmp_path):
+        """Test with an os.PathLike object whose path is bytes."""
+        file_path = tmp_path / "bytes_path.txt"
+        file_path.write_text("From a bytes path. It works.")
+
+        class BytesPath:
+            def __fspath__(self):
+                return os.fsencode(file_path)
+
+        assert sakurs.split(BytesPath()) == ["From a bytes path.", "It works."]
+
+    def test_dir_entry_input(self, tmp_path):
+        """Test with an os.DirEntry from os.scandir()."""
+        (tmp_path / "entry.txt").write_text("Scanned. Entry.")
+
+        with os.scandir(tmp_path) as entries:
+            entry = next(entries)
+            assert sakurs.split(entry) == ["Scanned.", "Entry."]
+
+    def test_failing_fspath_is_raised(self):
+        """Test that errors from __fspath__ are raised rather than ignored."""
+
+        class BrokenPath:
+            def __fspath__(self):
+                raise ValueError("no path here")
+
+        with pytest.raises(ValueError, match="no path here"):
+            sakurs.split(BrokenPath())  # type: ignore[call-overload]
+
+    def test_split_large_file_pathlib_input(self, tmp_path):
+        """Test split_large_file with a pathlib.Path object."""
+        file_path = tmp_path / "large.txt"
+        file_path.write_text("First. Second.")
+
+        assert list(sakurs.split_large_file(file_path)) == ["First.", "Second."]
+
+    def test_unsupported_input_type(self):
+        """Test that the error names the type that was passed."""
+        with pytest.raises(TypeError, match="got int"):
+            sakurs.split(42)  # type: ignore[call-overload]
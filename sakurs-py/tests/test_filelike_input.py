@@ -136,7 +136,9 @@ class TestFileLikeInput:
 
         with pytest.raises(TypeError) as exc_info:
             sakurs.split(invalid_obj)  # type: ignore[call-overload]
-        assert "Expected str, bytes, Path, or file-like object" in str(exc_info.value)
+        assert "Expected str, bytes" in str(exc_info.value)
+        assert "file-like object with read() method" in str(exc_info.value)
+        assert "got NotAFileObject" in str(exc_info.value)
 
     def test_read_returns_bytes_in_text_mode(self):
         """Test handling when read() returns bytes (binary mode file)."""
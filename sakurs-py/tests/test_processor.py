@@ -31,6 +31,37 @@ class TestProcessorBasics:
         assert sentences[0] == "Hello world."
         assert sentences[1] == "How are you?"
 
+    def test_processor_last_metadata(self):
+        """Test the execution decision of the last split."""
+        processor = sakurs.SentenceSplitter(language="en")
+        assert processor.last_metadata is None
+        processor.split("Hello world. How are you?")
+        metadata = processor.last_metadata
+        assert metadata.total_sentences == 2
+        assert metadata.threads_used == 1
+        assert metadata.execution_mode_used == "adaptive"
+        assert "below the threshold" in metadata.execution_reason
+        assert metadata.bytes_per_thread == 256 * 1024
+        assert metadata.available_threads is None
+
+        processor = sakurs.SentenceSplitter(threads=2, execution_mode="parallel")
+        processor.split("Hello world. How are you?")
+        assert processor.last_metadata.threads_used == 2
+        assert "fixed by the configuration" in processor.last_metadata.execution_reason
+
+    def test_processor_last_metadata_warnings(self):
+        """Test the warnings about the text of the last split."""
+        processor = sakurs.SentenceSplitter(language="en")
+        processor.split("Hello world. How are you?")
+        assert processor.last_metadata.warnings == []
+
+        processor.split("Short one. " + "word " * 2100 + "end.")
+        warnings = processor.last_metadata.warnings
+        assert len(warnings) == 1
+        assert warnings[0].kind == "long_sentence"
+        assert warnings[0].offset == len("Short one. ")
+        assert "suspiciously long sentence" in warnings[0].message
+
     def test_processor_split_file(self):
         """Test splitting file content with processor."""
         with tempfile.NamedTemporaryFile(mode="w", suffix=".txt", delete=False) as f:
@@ -0,0 +1,59 @@
+"""Tests for the split_series() column API."""
+
+import pytest
+
+import sakurs
+
+
+class TestSplitSeries:
+    """Test split_series() with plain iterables, pandas and pyarrow columns."""
+
+    def test_list_of_documents(self):
+        """Each document becomes one list of sentences, in order."""
+        docs = ["Hello world. How are you?", "One sentence", "Dr. Smith left. He ran."]
+        result = sakurs.split_series(docs)
+
+        assert result == [
+            ["Hello world.", "How are you?"],
+            ["One sentence"],
+            ["Dr. Smith left.", "He ran."],
+        ]
+
+    def test_matches_split(self):
+        """Results agree with splitting each document separately."""
+        docs = ["これはペンです。それは本です。", "短い文。"]
+        result = sakurs.split_series(docs, language="ja")
+
+        assert result == [sakurs.split(doc, language="ja") for doc in docs]
+
+    def test_missing_and_empty_values(self):
+        """None stays None and empty documents have no sentences."""
+        result = sakurs.split_series(["Yes. No.", None, "", float("nan")])
+
+        assert result == [["Yes.", "No."], None, [], None]
+
+    def test_processor_method(self):
+        """SentenceSplitter.split_series reuses the loaded processor."""
+        splitter = sakurs.load("en")
+        assert splitter.split_series(iter(["First. Second."])) == [["First.", "Second."]]
+
+    def test_rejects_non_strings(self):
+        """Non-string values raise TypeError."""
+        with pytest.raises(TypeError, match="position 1"):
+            sakurs.split_series(["ok", 42])
+
+    def test_pandas_series(self):
+        """pandas Series (including pd.NA) are accepted."""
+        pd = pytest.importorskip("pandas")
+        series = pd.Series(["One. Two.", pd.NA, "Three."], dtype="string")
+
+        assert sakurs.split_series(series) == [["One.", "Two."], None, ["Three."]]
+
+    def test_pyarrow_round_trip(self):
+        """pyarrow arrays in, ListArray out."""
+        pa = pytest.importorskip("pyarrow")
+        column = pa.chunked_array([["One. Two.", None], ["Three."]])
+        result = sakurs.split_series(column, return_arrow=True)
+
+        assert result.type == pa.list_(pa.string())
+        assert result.to_pylist() == [["One.", "Two."], None, ["Three."]]
@@ -159,6 +159,29 @@ class TestIterSplit:
 
         assert count == 10000
 
+    def test_iter_split_reads_file_objects_in_bounded_chunks(self):
+        """Test that file-like objects are read lazily in chunk-sized pieces."""
+
+        class RecordingReader:
+            def __init__(self, data):
+                self.data = data
+                self.sizes = []
+
+            def read(self, size=-1):
+                self.sizes.append(size)
+                if size < 0:
+                    size = len(self.data)
+                out, self.data = self.data[:size], self.data[size:]
+                return out
+
+        reader = RecordingReader("「こんにちは。」と言った。元気です！".encode() * 1000)
+        iterator = sakurs.iter_split(reader, language="ja", chunk_kb=1)
+
+        assert next(iterator) == "「こんにちは。」と言った。"
+        assert len(reader.sizes) == 1
+        assert len(list(iterator)) == 1999
+        assert all(0 < size <= 1024 for size in reader.sizes)
+
 
 class TestProcessorIterSplit:
     """Test Processor.iter_split method."""
@@ -226,21 +249,8 @@ class TestProcessorIterSplit:
         processor = sakurs.load("en")
         text = "Hello... World!!! How are you???"
         sentences = list(processor.iter_split(text))
-        # Current specification: The core algorithm treats each terminator (! and ?)
-        # as an independent sentence boundary, resulting in single-character segments
-        # for consecutive terminators. Ellipsis (...) is handled differently and
-        # stays as a single unit.
-        # This behavior might not be ideal for typical use cases but is consistent
-        # with the current implementation of the Delta-Stack Monoid algorithm.
-        # Future enhancement could merge consecutive identical terminators.
-        assert len(sentences) == 7
-        assert sentences[0] == "Hello..."  # Ellipsis is kept together
-        assert sentences[1] == "World!"  # First exclamation
-        assert sentences[2] == "!"  # Second exclamation as separate segment
-        assert sentences[3] == "!"  # Third exclamation as separate segment
-        assert sentences[4] == "How are you?"  # First question mark
-        assert sentences[5] == "?"  # Second question mark as separate segment
-        assert sentences[6] == "?"  # Third question mark as separate segment
+        # A run of terminators ends one sentence, at its last character
+        assert sentences == ["Hello...", "World!!!", "How are you???"]
 
     def test_iter_split_mixed_content(self):
         """Test iteration with mixed content including quotes and parentheses."""
@@ -400,3 +410,77 @@ class TestSplitLargeFile:
         """Test error handling for nonexistent file."""
         with pytest.raises(FileNotFoundError, match=r"nonexistent_file\.txt"):
             list(sakurs.split_large_file("nonexistent_file.txt"))
+
+
+class TestIteratorCleanup:
+    """Test close() and the context-manager protocol of the iterators."""
+
+    def test_iter_split_close(self, tmp_path):
+        """Closing stops iteration, and closing again does nothing."""
+        file_path = tmp_path / "input.txt"
+        file_path.write_text("One. Two. Three.")
+
+        iterator = sakurs.iter_split(file_path)
+        assert next(iterator) == "One."
+        iterator.close()
+        iterator.close()
+        with pytest.raises(StopIteration):
+            next(iterator)
+
+    def test_iter_split_context_manager(self, tmp_path):
+        """Leaving the block closes the iterator."""
+        file_path = tmp_path / "input.txt"
+        file_path.write_text("One. Two. Three.")
+
+        with sakurs.iter_split(file_path) as iterator:
+            assert next(iterator) == "One."
+        assert list(iterator) == []
+
+    def test_iter_split_leaves_file_objects_open(self):
+        """File-like objects belong to the caller and stay open."""
+        stream = io.StringIO("One. Two.")
+        with sakurs.iter_split(stream) as iterator:
+            assert next(iterator) == "One."
+        assert not stream.closed
+
+    def test_split_large_file_close(self, tmp_path):
+        """Closing stops iteration, and closing again does nothing."""
+        file_path = tmp_path / "input.txt"
+        file_path.write_text("One. Two. Three.")
+
+        iterator = sakurs.split_large_file(str(file_path))
+        assert next(iterator) == "One."
+        iterator.close()
+        iterator.close()
+        with pytest.raises(StopIteration):
+            next(iterator)
+
+    def test_split_large_file_context_manager(self, tmp_path):
+        """Leaving the block closes the file, even on an exception."""
+        file_path = tmp_path / "input.txt"
+        file_path.write_text("One. Two. Three.")
+
+        with pytest.raises(ValueError, match="stop"):
+            with sakurs.split_large_file(str(file_path)) as iterator:
+                assert next(iterator) == "One."
+                raise ValueError("stop")
+        assert list(iterator) == []
+        # The file is no longer held open, so it can be removed anywhere
+        file_path.unlink()
+
+    def test_generator_close(self, tmp_path):
+        """Closing a generator that iterates inside a with block closes the
+        iterator and lets GeneratorExit through."""
+        file_path = tmp_path / "input.txt"
+        file_path.write_text("One. Two. Three.")
+        iterators = []
+
+        def sentences():
+            with sakurs.split_large_file(str(file_path)) as iterator:
+                iterators.append(iterator)
+                yield from iterator
+
+        generator = sentences()
+        assert next(generator) == "One."
+        generator.close()
+        assert list(iterators[0]) == []
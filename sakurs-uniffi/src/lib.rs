@@ -0,0 +1,312 @@
+//! UniFFI bindings for Sakurs sentence boundary detection
+//!
+//! Implements the interface defined in `src/sakurs.udl`, from which
+//! `uniffi-bindgen` generates Swift, Kotlin and Python sources, so every
+//! generated binding exposes the same options, errors and types.
+//!
+//! Sentences carry both character (Unicode scalar value) offsets, as in the
+//! Python bindings, and UTF-16 offsets for Kotlin and Swift string indexing.
+
+// The generated scaffolding trips this lint
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use std::sync::{Arc, Mutex};
+
+use sakurs_core::{Config, LanguageConfig, SentenceProcessor};
+
+uniffi::include_scaffolding!("sakurs");
+
+/// Options shared by `split`, `split_with_offsets` and `Splitter`
+#[derive(Debug, Clone)]
+pub struct SplitOptions {
+    pub language: String,
+    pub threads: Option<u32>,
+    pub chunk_kb: Option<u32>,
+}
+
+impl Default for SplitOptions {
+    fn default() -> Self {
+        Self {
+            language: "en".to_string(),
+            threads: None,
+            chunk_kb: None,
+        }
+    }
+}
+
+/// How a sentence ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundaryKind {
+    Strong,
+    Weak,
+    Forced,
+    EndOfText,
+}
+
+impl From<sakurs_core::BoundaryKind> for BoundaryKind {
+    fn from(kind: sakurs_core::BoundaryKind) -> Self {
+        match kind {
+            sakurs_core::BoundaryKind::Strong => BoundaryKind::Strong,
+            sakurs_core::BoundaryKind::Weak => BoundaryKind::Weak,
+            sakurs_core::BoundaryKind::Forced => BoundaryKind::Forced,
+            sakurs_core::BoundaryKind::EndOfText => BoundaryKind::EndOfText,
+        }
+    }
+}
+
+/// A sentence with its position in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sentence {
+    pub text: String,
+    pub start: u64,
+    pub end: u64,
+    pub utf16_start: u64,
+    pub utf16_end: u64,
+    pub kind: BoundaryKind,
+}
+
+/// Errors raised by the bindings; the foreign side receives the message
+#[derive(Debug, thiserror::Error)]
+pub enum SakursError {
+    #[error("{0}")]
+    InvalidLanguage(String),
+    #[error("{0}")]
+    InvalidConfig(String),
+    #[error("{0}")]
+    Processing(String),
+    #[error("stream is already finished")]
+    StreamFinished,
+}
+
+impl From<sakurs_core::ApiError> for SakursError {
+    fn from(e: sakurs_core::ApiError) -> Self {
+        match e {
+            sakurs_core::ApiError::InvalidLanguage(_) => {
+                SakursError::InvalidLanguage(e.to_string())
+            }
+            sakurs_core::ApiError::Configuration(_) => SakursError::InvalidConfig(e.to_string()),
+            _ => SakursError::Processing(e.to_string()),
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, SakursError>;
+
+/// Split text into sentences
+pub fn split(text: String, options: SplitOptions) -> Result<Vec<String>> {
+    Splitter::new(options)?.split(text)
+}
+
+/// Split text into sentences with their offsets
+pub fn split_with_offsets(text: String, options: SplitOptions) -> Result<Vec<Sentence>> {
+    Splitter::new(options)?.split_with_offsets(text)
+}
+
+/// Language codes with built-in rules
+pub fn supported_languages() -> Vec<String> {
+    LanguageConfig::embedded()
+        .into_iter()
+        .map(|config| config.metadata.code.clone())
+        .collect()
+}
+
+/// Reusable sentence splitter; compile the rules once, split many texts
+pub struct Splitter {
+    processor: SentenceProcessor,
+    language: String,
+}
+
+impl Splitter {
+    pub fn new(options: SplitOptions) -> Result<Self> {
+        let mut builder = Config::builder().language(&options.language)?;
+        if let Some(threads) = options.threads {
+            builder = builder.threads(Some(threads as usize));
+        }
+        if let Some(chunk_kb) = options.chunk_kb {
+            builder = builder.chunk_size(chunk_kb as usize * 1024);
+        }
+        Ok(Self {
+            processor: SentenceProcessor::with_config(builder.build()?)?,
+            language: options.language,
+        })
+    }
+
+    /// Language code this splitter was created with
+    pub fn language(&self) -> String {
+        self.language.clone()
+    }
+
+    /// Split text into sentences
+    pub fn split(&self, text: String) -> Result<Vec<String>> {
+        Ok(self
+            .split_with_offsets(text)?
+            .into_iter()
+            .map(|sentence| sentence.text)
+            .collect())
+    }
+
+    /// Split text into sentences with their offsets
+    pub fn split_with_offsets(&self, text: String) -> Result<Vec<Sentence>> {
+        let output = self
+            .processor
+            .process(sakurs_core::Input::from_text(&text))?;
+        let mut ends: Vec<(usize, sakurs_core::BoundaryKind)> = output
+            .boundaries
+            .iter()
+            .map(|b| (b.offset, b.kind))
+            .collect();
+        // Trailing text without a terminator is the final sentence
+        if ends.last().map(|&(end, _)| end) != Some(text.len()) {
+            ends.push((text.len(), sakurs_core::BoundaryKind::EndOfText));
+        }
+
+        let mut sentences = Vec::new();
+        let mut start = 0;
+        let mut position = Position::default();
+        for (end, kind) in ends {
+            let segment = &text[start..end];
+            let body = segment.trim();
+            if !body.is_empty() {
+                let leading = segment.len() - segment.trim_start().len();
+                let sentence_start = position.advance(&segment[..leading]);
+                let sentence_end = sentence_start.advance(body);
+                sentences.push(Sentence {
+                    text: body.to_string(),
+                    start: sentence_start.chars,
+                    end: sentence_end.chars,
+                    utf16_start: sentence_start.utf16,
+                    utf16_end: sentence_end.utf16,
+                    kind: kind.into(),
+                });
+            }
+            position = position.advance(segment);
+            start = end;
+        }
+        Ok(sentences)
+    }
+
+    /// Start an incremental stream: feed text pieces, receive sentences as
+    /// they complete
+    pub fn stream(&self) -> Arc<SentenceStream> {
+        Arc::new(SentenceStream {
+            inner: Mutex::new(Some(self.processor.stream())),
+        })
+    }
+}
+
+/// Push-based sentence stream created by `Splitter.stream()`
+///
+/// Memory stays proportional to the piece size plus the current sentence,
+/// however long the input.
+pub struct SentenceStream {
+    inner: Mutex<Option<sakurs_core::SentenceStream>>,
+}
+
+impl SentenceStream {
+    /// Append a piece of text; returns the sentences it completed
+    pub fn feed(&self, text: String) -> Result<Vec<String>> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let inner = inner.as_mut().ok_or(SakursError::StreamFinished)?;
+        Ok(trimmed(
+            inner.feed_spans(&text).iter().map(|s| s.text.as_str()),
+        ))
+    }
+
+    /// End the stream; returns the remaining sentences
+    pub fn finish(&self) -> Result<Vec<String>> {
+        let inner = self
+            .inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .ok_or(SakursError::StreamFinished)?;
+        Ok(trimmed(
+            inner.finish_spans().iter().map(|s| s.text.as_str()),
+        ))
+    }
+}
+
+/// An offset in both characters and UTF-16 code units
+#[derive(Debug, Clone, Copy, Default)]
+struct Position {
+    chars: u64,
+    utf16: u64,
+}
+
+impl Position {
+    /// The position just past `text`, which starts here
+    fn advance(self, text: &str) -> Self {
+        text.chars().fold(self, |position, c| Position {
+            chars: position.chars + 1,
+            utf16: position.utf16 + c.len_utf16() as u64,
+        })
+    }
+}
+
+/// Trim sentences and drop the empty ones
+fn trimmed<'a>(segments: impl Iterator<Item = &'a str>) -> Vec<String> {
+    segments
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_with_default_options() {
+        let sentences = split(
+            "Hello world. Dr. Smith arrived!".to_string(),
+            SplitOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(sentences, ["Hello world.", "Dr. Smith arrived!"]);
+    }
+
+    #[test]
+    fn offsets_count_characters_and_utf16_units() {
+        let text = "😀 Emoji first. Then text";
+        let sentences = split_with_offsets(text.to_string(), SplitOptions::default()).unwrap();
+        let last = &sentences[1];
+        assert_eq!(last.text, "Then text");
+        assert_eq!((last.start, last.end), (15, 24));
+        assert_eq!((last.utf16_start, last.utf16_end), (16, 25));
+        assert_eq!(last.kind, BoundaryKind::EndOfText);
+    }
+
+    #[test]
+    fn stream_matches_split() {
+        let splitter = Splitter::new(SplitOptions::default()).unwrap();
+        let text = "First sentence. Second one! Third? Trailing".to_string();
+        let stream = splitter.stream();
+        let mut sentences = Vec::new();
+        for piece in text.as_bytes().chunks(5) {
+            sentences.extend(
+                stream
+                    .feed(String::from_utf8(piece.to_vec()).unwrap())
+                    .unwrap(),
+            );
+        }
+        sentences.extend(stream.finish().unwrap());
+        assert_eq!(sentences, splitter.split(text.clone()).unwrap());
+        assert!(matches!(
+            stream.feed("More.".to_string()),
+            Err(SakursError::StreamFinished)
+        ));
+    }
+
+    #[test]
+    fn unknown_language_is_an_error() {
+        let options = SplitOptions {
+            language: "xx".to_string(),
+            ..SplitOptions::default()
+        };
+        assert!(matches!(
+            Splitter::new(options),
+            Err(SakursError::InvalidLanguage(_))
+        ));
+    }
+}